@@ -13,6 +13,7 @@ fn main() {
         common::open_impl_file(&station_fn_dir, "station_fn_metadata_ext.rs");
     let mut station_fn_res_impl = common::open_impl_file(&station_fn_dir, "station_fn_res_impl.rs");
     let mut station_fn_resource = common::open_impl_file(&station_fn_dir, "station_fn_resource.rs");
+    let mut station_fn_send = common::open_impl_file(&station_fn_dir, "station_fn_send.rs");
 
     let mut write_fn = |arg_exprs: ArgExprs<'_>| {
         station_fn_metadata_ext::write_station_fn_metadata_ext(
@@ -22,6 +23,7 @@ fn main() {
 
         station_fn_res_impl::write_station_fn_res_impl(&mut station_fn_res_impl, arg_exprs);
         station_fn_resource::write_station_fn_resource(&mut station_fn_resource, arg_exprs);
+        station_fn_send::write_station_fn_send(&mut station_fn_send, arg_exprs);
     };
 
     generate_impls_for_n_args::<_, 1>(&mut write_fn);
@@ -30,6 +32,12 @@ fn main() {
     generate_impls_for_n_args::<_, 4>(&mut write_fn);
     generate_impls_for_n_args::<_, 5>(&mut write_fn);
     generate_impls_for_n_args::<_, 6>(&mut write_fn);
+    generate_impls_for_n_args::<_, 7>(&mut write_fn);
+    generate_impls_for_n_args::<_, 8>(&mut write_fn);
+    generate_impls_for_n_args::<_, 9>(&mut write_fn);
+    generate_impls_for_n_args::<_, 10>(&mut write_fn);
+    generate_impls_for_n_args::<_, 11>(&mut write_fn);
+    generate_impls_for_n_args::<_, 12>(&mut write_fn);
 
     station_fn_metadata_ext
         .flush()
@@ -40,6 +48,9 @@ fn main() {
     station_fn_resource
         .flush()
         .expect("Failed to flush writer for station_fn_resource.rs");
+    station_fn_send
+        .flush()
+        .expect("Failed to flush writer for station_fn_send.rs");
 
     println!("cargo:rerun-if-changed=build.rs");
 }
@@ -62,6 +73,10 @@ mod common {
         pub resource_arg_borrows: &'s str,
         pub resource_arg_try_borrows: &'s str,
         pub resource_arg_vars: &'s str,
+        pub type_ids_borrows: &'s str,
+        pub type_ids_borrow_muts: &'s str,
+        pub type_names_borrows: &'s str,
+        pub type_names_borrow_muts: &'s str,
     }
 
     #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -145,8 +160,8 @@ mod common {
                 arg_refs_lifetime_csv
             };
 
-            // let a0 = train_resources.borrow::<A0>();
-            // let mut a1 = train_resources.borrow_mut::<A1>();
+            // let a0 = train_resources.station_borrow::<A0>(station.rt_id);
+            // let mut a1 = train_resources.station_borrow_mut::<A1>(station.rt_id);
             // ..
             let resource_arg_borrows = resource_arg_borrows(arg_refs);
             let resource_arg_try_borrows = resource_arg_try_borrows(arg_refs);
@@ -154,6 +169,16 @@ mod common {
             // &*a0, &mut *a1
             let resource_arg_vars = resource_arg_vars::<N>(arg_refs);
 
+            // let mut type_ids = TypeIds::new();
+            // type_ids.push(TypeId::of::<A0>());
+            // type_ids
+            let type_ids_borrows = type_ids(arg_refs, Ref::Immutable);
+            let type_ids_borrow_muts = type_ids(arg_refs, Ref::Mutable);
+
+            // vec![std::any::type_name::<A0>()]
+            let type_names_borrows = type_names(arg_refs, Ref::Immutable);
+            let type_names_borrow_muts = type_names(arg_refs, Ref::Mutable);
+
             let args_csv = args_csv.as_str();
             let arg_refs_csv = arg_refs_csv.as_str();
             let arg_refs_lifetime_csv = arg_refs_lifetime_csv.as_str();
@@ -161,6 +186,10 @@ mod common {
             let resource_arg_borrows = resource_arg_borrows.as_str();
             let resource_arg_try_borrows = resource_arg_try_borrows.as_str();
             let resource_arg_vars = resource_arg_vars.as_str();
+            let type_ids_borrows = type_ids_borrows.as_str();
+            let type_ids_borrow_muts = type_ids_borrow_muts.as_str();
+            let type_names_borrows = type_names_borrows.as_str();
+            let type_names_borrow_muts = type_names_borrow_muts.as_str();
 
             let arg_exprs = ArgExprs {
                 args_csv,
@@ -170,6 +199,10 @@ mod common {
                 resource_arg_borrows,
                 resource_arg_try_borrows,
                 resource_arg_vars,
+                type_ids_borrows,
+                type_ids_borrow_muts,
+                type_names_borrows,
+                type_names_borrow_muts,
             };
 
             fn_write(arg_exprs);
@@ -195,18 +228,61 @@ mod common {
         resource_arg_vars
     }
 
+    fn type_ids<const N: usize>(arg_refs: [Ref; N], ref_kind: Ref) -> String {
+        let mut indices = arg_refs
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|(_index, arg_ref)| *arg_ref == ref_kind)
+            .map(|(index, _arg_ref)| index)
+            .peekable();
+
+        if indices.peek().is_none() {
+            return String::from("TypeIds::new()");
+        }
+
+        let mut type_ids = String::with_capacity(N * 40);
+        type_ids.push_str("let mut type_ids = TypeIds::new();\n");
+        indices
+            .try_for_each(|index| writeln!(type_ids, "type_ids.push(TypeId::of::<A{index}>());"))
+            .expect("Failed to append to `type_ids` string.");
+        type_ids.push_str("type_ids");
+        type_ids
+    }
+
+    fn type_names<const N: usize>(arg_refs: [Ref; N], ref_kind: Ref) -> String {
+        let mut indices = arg_refs
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|(_index, arg_ref)| *arg_ref == ref_kind)
+            .map(|(index, _arg_ref)| index);
+
+        let mut type_names = String::with_capacity(N * 36);
+        type_names.push_str("vec![");
+        if let Some(index) = indices.next() {
+            write!(type_names, "std::any::type_name::<A{index}>()")
+                .expect("Failed to append to `type_names` string.");
+        }
+        indices
+            .try_for_each(|index| write!(type_names, ", std::any::type_name::<A{index}>()"))
+            .expect("Failed to append to `type_names` string.");
+        type_names.push(']');
+        type_names
+    }
+
     fn resource_arg_borrows<const N: usize>(arg_refs: [Ref; N]) -> String {
-        let mut resource_arg_borrows = String::with_capacity(N * 44);
+        let mut resource_arg_borrows = String::with_capacity(N * 72);
         let mut arg_refs_iter = arg_refs.iter().copied().enumerate();
         arg_refs_iter
             .try_for_each(|(index, arg_ref)| match arg_ref {
                 Ref::Immutable => writeln!(
                     resource_arg_borrows,
-                    "let a{index} = train_resources.borrow::<A{index}>();",
+                    "let a{index} = train_resources.station_borrow::<A{index}>(station.rt_id);",
                 ),
                 Ref::Mutable => writeln!(
                     resource_arg_borrows,
-                    "let mut a{index} = train_resources.borrow_mut::<A{index}>();",
+                    "let mut a{index} = train_resources.station_borrow_mut::<A{index}>(station.rt_id);",
                 ),
             })
             .expect("Failed to append to `resource_arg_borrows` string.");
@@ -214,17 +290,17 @@ mod common {
     }
 
     fn resource_arg_try_borrows<const N: usize>(arg_refs: [Ref; N]) -> String {
-        let mut resource_arg_try_borrows = String::with_capacity(N * 44);
+        let mut resource_arg_try_borrows = String::with_capacity(N * 72);
         let mut arg_refs_iter = arg_refs.iter().copied().enumerate();
         arg_refs_iter
             .try_for_each(|(index, arg_ref)| match arg_ref {
                 Ref::Immutable => writeln!(
                     resource_arg_try_borrows,
-                    "let a{index} = train_resources.try_borrow::<A{index}>()?;",
+                    "let a{index} = train_resources.station_try_borrow::<A{index}>(station.rt_id)?;",
                 ),
                 Ref::Mutable => writeln!(
                     resource_arg_try_borrows,
-                    "let mut a{index} = train_resources.try_borrow_mut::<A{index}>()?;",
+                    "let mut a{index} = train_resources.station_try_borrow_mut::<A{index}>(station.rt_id)?;",
                 ),
             })
             .expect("Failed to append to `resource_arg_try_borrows` string.");
@@ -372,6 +448,10 @@ mod station_fn_res_impl {
             arg_refs_csv,
             arg_refs_lifetime_csv,
             arg_bounds_list,
+            type_ids_borrows,
+            type_ids_borrow_muts,
+            type_names_borrows,
+            type_names_borrow_muts,
             ..
         } = arg_exprs;
 
@@ -398,6 +478,22 @@ where
     -> Result<LocalBoxFuture<'f2, Result<R, RErr>>, BorrowFail> {{
         Self::try_call(self, station, train_resources)
     }}
+
+    fn borrows(&self) -> TypeIds {{
+        {type_ids_borrows}
+    }}
+
+    fn borrow_muts(&self) -> TypeIds {{
+        {type_ids_borrow_muts}
+    }}
+
+    fn borrow_type_names(&self) -> Vec<&'static str> {{
+        {type_names_borrows}
+    }}
+
+    fn borrow_mut_type_names(&self) -> Vec<&'static str> {{
+        {type_names_borrow_muts}
+    }}
 }}
 "#,
         )
@@ -425,6 +521,7 @@ mod station_fn_resource {
             resource_arg_borrows,
             resource_arg_try_borrows,
             resource_arg_vars,
+            ..
         } = arg_exprs;
 
         write!(
@@ -463,3 +560,81 @@ where
         .expect("Failed to write to station_fn_resource.rs");
     }
 }
+
+mod station_fn_send {
+    use std::{
+        fs::File,
+        io::{BufWriter, Write},
+    };
+
+    use super::common::ArgExprs;
+
+    pub fn write_station_fn_send(station_fn_send: &mut BufWriter<File>, arg_exprs: ArgExprs<'_>) {
+        let ArgExprs {
+            args_csv,
+            arg_refs_csv,
+            arg_refs_lifetime_csv,
+            arg_bounds_list,
+            resource_arg_borrows,
+            resource_arg_try_borrows,
+            resource_arg_vars,
+            type_ids_borrows,
+            type_ids_borrow_muts,
+            type_names_borrows,
+            type_names_borrow_muts,
+        } = arg_exprs;
+
+        write!(
+            station_fn_send,
+            r#"
+impl<Fun, R, RErr, E, {args_csv}> StationFnRes<R, RErr, E> for StationFnSend<Fun, R, RErr, E, ({arg_refs_csv})>
+where
+    Fun: for<'f> Fn(&'f mut StationMutRef<'_, E>, {arg_refs_lifetime_csv}) -> BoxFuture<'static, Result<R, RErr>> + 'static,
+    R: Send + 'static,
+    RErr: From<StationFnTaskError> + Send + 'static,
+    E: 'static,
+    {arg_bounds_list}
+{{
+    fn call<'f1: 'f2, 'f2>(
+            &'f2 self,
+            station: &'f1 mut StationMutRef<'_, E>,
+            train_resources: &'f2 TrainResources<E>)
+    -> LocalBoxFuture<'f2, Result<R, RErr>> {{
+        {resource_arg_borrows}
+        let fut = (self.func)(station, {resource_arg_vars});
+
+        Self::execute(station, train_resources, fut)
+    }}
+
+    fn try_call<'f1: 'f2, 'f2>(
+            &'f2 self,
+            station: &'f1 mut StationMutRef<'_, E>,
+            train_resources: &'f2 TrainResources<E>)
+    -> Result<LocalBoxFuture<'f2, Result<R, RErr>>, BorrowFail> {{
+        {resource_arg_try_borrows}
+        let fut = (self.func)(station, {resource_arg_vars});
+
+        Ok(Self::execute(station, train_resources, fut))
+    }}
+
+    fn borrows(&self) -> TypeIds {{
+        {type_ids_borrows}
+    }}
+
+    fn borrow_muts(&self) -> TypeIds {{
+        {type_ids_borrow_muts}
+    }}
+
+    fn borrow_type_names(&self) -> Vec<&'static str> {{
+        {type_names_borrows}
+    }}
+
+    fn borrow_mut_type_names(&self) -> Vec<&'static str> {{
+        {type_names_borrow_muts}
+    }}
+}}
+"#,
+        )
+        .expect("Failed to write to station_fn_send.rs");
+    }
+}
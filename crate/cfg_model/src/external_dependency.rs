@@ -0,0 +1,41 @@
+use choochoo_resource::Profile;
+
+use crate::StationId;
+
+/// Station in another profile that must be successfully created before a
+/// station's work is done.
+///
+/// This sequences layered environments, such as an application stack that is
+/// deployed on top of a network stack that is managed in a separate profile.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ExternalDependency {
+    /// Profile of the destination that contains the station.
+    profile: Profile,
+    /// Unique identifier of the station within its destination.
+    station_id: StationId,
+}
+
+impl ExternalDependency {
+    /// Returns a new `ExternalDependency`.
+    ///
+    /// # Parameters
+    ///
+    /// * `profile`: Profile of the destination that contains the station.
+    /// * `station_id`: Unique identifier of the station within its destination.
+    pub fn new(profile: Profile, station_id: StationId) -> Self {
+        Self {
+            profile,
+            station_id,
+        }
+    }
+
+    /// Returns the profile of the destination that contains the station.
+    pub fn profile(&self) -> &Profile {
+        &self.profile
+    }
+
+    /// Returns the unique identifier of the station within its destination.
+    pub fn station_id(&self) -> &StationId {
+        &self.station_id
+    }
+}
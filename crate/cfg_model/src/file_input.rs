@@ -0,0 +1,46 @@
+use crate::StationId;
+
+/// File published by another station that a station's functions read.
+///
+/// The producing station publishes the file under a name with
+/// [`StationMutRef::publish_file`], and the consuming station looks up its
+/// path with [`StationMutRef::file_input`].
+///
+/// [`StationMutRef::publish_file`]: crate::rt::StationMutRef::publish_file
+/// [`StationMutRef::file_input`]: crate::rt::StationMutRef::file_input
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FileInput {
+    /// Unique identifier of the station that publishes the file.
+    station_id: StationId,
+    /// Name the file is published under.
+    name: String,
+}
+
+impl FileInput {
+    /// Returns a new `FileInput`.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_id`: Unique identifier of the station that publishes the
+    ///   file.
+    /// * `name`: Name the file is published under.
+    pub fn new<S>(station_id: StationId, name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            station_id,
+            name: name.into(),
+        }
+    }
+
+    /// Returns the unique identifier of the station that publishes the file.
+    pub fn station_id(&self) -> &StationId {
+        &self.station_id
+    }
+
+    /// Returns the name the file is published under.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
@@ -12,25 +12,49 @@ pub use rt_map;
 pub use srcerr;
 
 pub use crate::{
+    external_dependency::ExternalDependency,
+    file_input::FileInput,
+    resource_init_fn::{ResourceInitFn, ResourceInitFnReturn},
     setup_fn::{SetupFn, SetupFnReturn},
-    station_fn::{StationFn, StationFnRes, StationFnResource},
+    station_exists_fn::{StationExistsFn, StationExistsFnReturn},
+    station_fn::{StationFn, StationFnRes, StationFnResource, StationFnSend},
     station_fn_metadata_ext::StationFnMetadataExt,
+    station_hook_fn::{StationHookFn, StationHookFnReturn},
     station_id::StationId,
     station_id_invalid_fmt::StationIdInvalidFmt,
+    station_migrate_fn::{StationMigrateFn, StationMigrateFnReturn},
     station_op::{CleanFns, CreateFns, OpFns, StationOp},
     station_spec::StationSpec,
     station_spec_builder::StationSpecBuilder,
+    station_spec_template::StationSpecTemplate,
     station_specs::StationSpecs,
 };
+#[cfg(feature = "mock")]
+pub use crate::{
+    mock_invocation::MockInvocation, mock_invocations::MockInvocations, mock_op_fn::MockOpFn,
+};
 
 pub mod rt;
 
+mod external_dependency;
+mod file_input;
+#[cfg(feature = "mock")]
+mod mock_invocation;
+#[cfg(feature = "mock")]
+mod mock_invocations;
+#[cfg(feature = "mock")]
+mod mock_op_fn;
+mod resource_init_fn;
 mod setup_fn;
+mod station_exists_fn;
 mod station_fn;
 mod station_fn_metadata_ext;
+mod station_hook_fn;
 mod station_id;
 mod station_id_invalid_fmt;
+mod station_migrate_fn;
 mod station_op;
 mod station_spec;
 mod station_spec_builder;
+mod station_spec_template;
 mod station_specs;
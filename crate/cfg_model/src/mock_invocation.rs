@@ -0,0 +1,36 @@
+use crate::{
+    rt::{OpStatus, ProgressLimit, StationRtId},
+    MockOpFn, StationId,
+};
+
+/// Record of a station function invocation on a mock station.
+///
+/// See [`MockInvocations`].
+///
+/// [`MockInvocations`]: crate::MockInvocations
+#[derive(Clone, Debug, PartialEq)]
+pub struct MockInvocation {
+    /// Unique identifier of the station.
+    pub station_id: StationId,
+    /// Runtime identifier of the station.
+    pub station_rt_id: StationRtId,
+    /// Station function that was invoked.
+    pub op_fn: MockOpFn,
+    /// Operation status of the station when the function was invoked.
+    pub op_status: OpStatus,
+    /// Progress limit of the station when the function was invoked.
+    pub progress_limit: ProgressLimit,
+    /// Type names of the resources the function borrows.
+    ///
+    /// Setup functions access [`TrainResources`] directly instead of through
+    /// parameters, so this is empty for [`MockOpFn::CreateSetup`] and
+    /// [`MockOpFn::CleanSetup`].
+    ///
+    /// [`TrainResources`]: crate::rt::TrainResources
+    pub borrow_type_names: Vec<&'static str>,
+    /// Type names of the resources the function mutably borrows.
+    ///
+    /// This is empty for setup functions, as for
+    /// [`MockInvocation::borrow_type_names`].
+    pub borrow_mut_type_names: Vec<&'static str>,
+}
@@ -0,0 +1,58 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{MockInvocation, MockOpFn};
+
+/// Records the station functions invoked on mock stations.
+///
+/// Pass a clone of this to [`StationSpecBuilder::with_mock_invocations`] to
+/// record each invocation of the station's functions, in order. This allows
+/// tests to assert that a function was or was not run, such as the `work_fn`
+/// not being run when the `check_fn` returns
+/// [`CheckStatus::WorkNotRequired`].
+///
+/// [`CheckStatus::WorkNotRequired`]: crate::rt::CheckStatus::WorkNotRequired
+/// [`StationSpecBuilder::with_mock_invocations`]: crate::StationSpecBuilder::with_mock_invocations
+#[derive(Clone, Debug, Default)]
+pub struct MockInvocations(Arc<Mutex<Vec<MockInvocation>>>);
+
+impl MockInvocations {
+    /// Returns a new empty [`MockInvocations`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the recorded invocations, in the order they were invoked.
+    pub fn invocations(&self) -> Vec<MockInvocation> {
+        self.0
+            .lock()
+            .map(|invocations| invocations.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the number of times a station function was invoked.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_id`: Unique identifier of the station.
+    /// * `op_fn`: Station function to count invocations of.
+    pub fn count(&self, station_id: &str, op_fn: MockOpFn) -> usize {
+        self.0
+            .lock()
+            .map(|invocations| {
+                invocations
+                    .iter()
+                    .filter(|invocation| {
+                        &**invocation.station_id == station_id && invocation.op_fn == op_fn
+                    })
+                    .count()
+            })
+            .unwrap_or(0)
+    }
+
+    /// Records an invocation.
+    pub(crate) fn record(&self, invocation: MockInvocation) {
+        if let Ok(mut invocations) = self.0.lock() {
+            invocations.push(invocation);
+        }
+    }
+}
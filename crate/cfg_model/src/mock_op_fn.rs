@@ -0,0 +1,20 @@
+/// Station function that was invoked on a mock station.
+///
+/// See [`MockInvocations`].
+///
+/// [`MockInvocations`]: crate::MockInvocations
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MockOpFn {
+    /// The create `setup_fn`.
+    CreateSetup,
+    /// The create `check_fn`.
+    CreateCheck,
+    /// The create `work_fn`.
+    CreateWork,
+    /// The clean `setup_fn`.
+    CleanSetup,
+    /// The clean `check_fn`.
+    CleanCheck,
+    /// The clean `work_fn`.
+    CleanWork,
+}
@@ -0,0 +1,104 @@
+use std::{
+    fmt::{self, Debug},
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+};
+
+use crate::rt::TrainResources;
+
+/// Return type of the `ResourceInitFn`.
+pub type ResourceInitFnReturn<'f, E> = Pin<Box<dyn Future<Output = Result<(), E>> + 'f>>;
+
+// **Note:** `Debug`, `Clone`, `PartialEq` are manually implemented to avoid the
+// trait bound on `E`.
+/// Logic to insert resources into the train resources before stations are set
+/// up, such as API clients, configuration, and secrets.
+///
+/// Resource initializers run after the built in resources, such as the
+/// workspace and profile directories, are inserted, so they may borrow them.
+#[allow(clippy::type_complexity)] // trait aliases don't exist yet, so we have to suppress clippy.
+pub struct ResourceInitFn<E> {
+    /// Name of the initializer, used when reporting its failure.
+    name: String,
+    /// Logic to run.
+    f: Arc<dyn for<'f> Fn(&'f mut TrainResources<E>) -> ResourceInitFnReturn<'f, E>>,
+}
+
+impl<E> ResourceInitFn<E> {
+    /// Returns a new `ResourceInitFn`.
+    ///
+    /// # Parameters
+    ///
+    /// * `name`: Name of the initializer, used when reporting its failure.
+    /// * `f`: Logic to run.
+    pub fn new<F>(name: impl Into<String>, f: F) -> Self
+    where
+        F: for<'f> Fn(&'f mut TrainResources<E>) -> ResourceInitFnReturn<'f, E> + 'static,
+    {
+        Self {
+            name: name.into(),
+            f: Arc::new(f),
+        }
+    }
+
+    /// Returns the name of the initializer.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Runs the initializer.
+    pub async fn call(&self, train_resources: &mut TrainResources<E>) -> Result<(), E> {
+        (self.f)(train_resources).await
+    }
+
+    /// Returns a `ResourceInitFn` that always returns `Result::Ok`.
+    #[cfg(feature = "mock")]
+    pub fn ok(name: impl Into<String>) -> Self
+    where
+        E: 'static,
+    {
+        ResourceInitFn::new(name, |_| Box::pin(async { Result::<(), E>::Ok(()) }))
+    }
+
+    /// Returns a `ResourceInitFn` that always returns `Result::Err`.
+    #[cfg(feature = "mock")]
+    pub fn err(name: impl Into<String>, e: E) -> Self
+    where
+        E: Clone + 'static,
+    {
+        ResourceInitFn::new(name, move |_| {
+            let e = e.clone();
+            Box::pin(async move { Result::<(), E>::Err(e) })
+        })
+    }
+}
+
+// We `impl Clone` to avoid the `E: Clone` bound generated by the derive.
+#[cfg(not(tarpaulin_include))]
+impl<E> Clone for ResourceInitFn<E> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            f: Arc::clone(&self.f),
+        }
+    }
+}
+
+impl<E> Debug for ResourceInitFn<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ResourceInitFn")
+            .field("name", &self.name)
+            .field(
+                "f",
+                &"fn(&'_ mut TrainResources<E>) -> ResourceInitFnReturn<'_, E>",
+            )
+            .finish()
+    }
+}
+
+impl<E> PartialEq for ResourceInitFn<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && Arc::ptr_eq(&self.f, &other.f)
+    }
+}
@@ -1,24 +1,101 @@
 //! Runtime data types referenced within configuration.
 
 pub use self::{
-    check_status::CheckStatus, op_status::OpStatus, progress_limit::ProgressLimit,
-    res_id_logical::ResIdLogical, res_ids::ResIds, station::Station, station_dir::StationDir,
-    station_errors::StationErrors, station_mut::StationMut, station_mut_ref::StationMutRef,
-    station_progress::StationProgress, station_rt_id::StationRtId, train_resources::TrainResources,
+    check_status::CheckStatus,
+    check_statuses_speculative::CheckStatusesSpeculative,
+    effect::Effect,
+    effect_record::EffectRecord,
+    execution_model::ExecutionModel,
+    op_status::OpStatus,
+    progress_limit::ProgressLimit,
+    progress_reader::ProgressReader,
+    progress_stream::ProgressStream,
+    progress_theme::ProgressTheme,
+    progress_writer::ProgressWriter,
+    res_id_logical::ResIdLogical,
+    res_id_metadata::ResIdMetadata,
+    res_ids::ResIds,
+    res_ids_previous::ResIdsPrevious,
+    resource_scope::ResourceScope,
+    resource_serialize::ResourceSerialize,
+    resource_teardown::ResourceTeardown,
+    resource_undeclared::ResourceUndeclared,
+    scoped_resources::{ScopedRef, ScopedRefMut},
+    spawner::Spawner,
+    station::Station,
+    station_artifact::StationArtifact,
+    station_dir::StationDir,
+    station_env::StationEnv,
+    station_error_phase::StationErrorPhase,
+    station_error_record::StationErrorRecord,
+    station_errors::StationErrors,
+    station_files::StationFiles,
+    station_fn_task_error::StationFnTaskError,
+    station_inputs_digests::StationInputsDigests,
+    station_mut::StationMut,
+    station_mut_ref::StationMutRef,
+    station_output::StationOutput,
+    station_progress::StationProgress,
+    station_resource_error::StationResourceError,
+    station_resources::StationResources,
+    station_resources_mut::StationResourcesMut,
+    station_rt_id::StationRtId,
+    station_state::StationState,
+    station_version_record::StationVersionRecord,
+    station_warnings::StationWarnings,
+    sub_step::SubStep,
+    sub_step_status::SubStepStatus,
+    train_resources::TrainResources,
     visit_op::VisitOp,
 };
 
+pub(crate) use self::resources_declared::ResourcesDeclared;
+
 mod check_status;
+mod check_statuses_speculative;
+mod effect;
+mod effect_record;
+mod execution_model;
 mod op_status;
 mod progress_limit;
+mod progress_reader;
+mod progress_stream;
+mod progress_theme;
+mod progress_writer;
 mod res_id_logical;
+mod res_id_metadata;
 mod res_ids;
+mod res_ids_previous;
+mod resource_scope;
+mod resource_serialize;
+mod resource_teardown;
+mod resource_teardowns;
+mod resource_undeclared;
+mod resources_declared;
+mod scoped_resources;
+mod spawner;
 mod station;
+mod station_artifact;
 mod station_dir;
+mod station_env;
+mod station_error_phase;
+mod station_error_record;
 mod station_errors;
+mod station_files;
+mod station_fn_task_error;
+mod station_inputs_digests;
 mod station_mut;
 mod station_mut_ref;
+mod station_output;
 mod station_progress;
+mod station_resource_error;
+mod station_resources;
+mod station_resources_mut;
 mod station_rt_id;
+mod station_state;
+mod station_version_record;
+mod station_warnings;
+mod sub_step;
+mod sub_step_status;
 mod train_resources;
 mod visit_op;
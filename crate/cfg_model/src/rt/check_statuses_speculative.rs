@@ -0,0 +1,41 @@
+use std::{
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+};
+
+use crate::rt::{CheckStatus, StationRtId};
+
+/// Map from [`StationRtId`] to the result of each station's speculative check.
+///
+/// Stations whose specs enable [`StationSpecBuilder::with_check_speculative`],
+/// or that are checked ahead by the train's execution strategy, have their
+/// create check function run before their parents are visited. When the
+/// station is visited, its result is taken from this map instead of running
+/// the check again. Results are removed when an ancestor's visit does anything
+/// other than find its work unnecessary, as that may change whether the
+/// station's work is required.
+///
+/// [`StationSpecBuilder::with_check_speculative`]: crate::StationSpecBuilder::with_check_speculative
+#[derive(Clone, Debug, Default)]
+pub struct CheckStatusesSpeculative(pub HashMap<StationRtId, CheckStatus>);
+
+impl CheckStatusesSpeculative {
+    /// Returns an empty `CheckStatusesSpeculative` map.
+    pub fn new() -> Self {
+        Self(HashMap::new())
+    }
+}
+
+impl Deref for CheckStatusesSpeculative {
+    type Target = HashMap<StationRtId, CheckStatus>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for CheckStatusesSpeculative {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
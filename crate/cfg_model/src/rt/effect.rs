@@ -0,0 +1,73 @@
+use std::{future::Future, sync::Arc};
+
+use tokio::sync::RwLock;
+
+use crate::rt::{EffectRecord, StationRtId};
+
+/// Runs side effects of station functions, or records them instead when the
+/// train is a dry run.
+///
+/// Stations may access this by taking `&Effect` as a function parameter, and
+/// wrapping each call that changes the outside world in [`Effect::run`]. The
+/// descriptions of effects that were not run are available in the train
+/// report.
+#[derive(Clone, Debug, Default)]
+pub struct Effect {
+    /// Whether effects are recorded instead of run.
+    dry_run: bool,
+    /// Effects that were recorded instead of run.
+    records: Arc<RwLock<Vec<EffectRecord>>>,
+}
+
+impl Effect {
+    /// Returns a new `Effect`.
+    ///
+    /// # Parameters
+    ///
+    /// * `dry_run`: Whether effects are recorded instead of run.
+    pub fn new(dry_run: bool) -> Self {
+        Self {
+            dry_run,
+            records: Arc::new(RwLock::new(Vec::new())),
+        }
+    }
+
+    /// Returns whether effects are recorded instead of run.
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Runs the effect and returns its output, or records its description and
+    /// returns `None` when this is a dry run.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_rt_id`: Runtime ID of the station running the effect.
+    /// * `description`: What the effect does, e.g. "upload app.zip".
+    /// * `f`: Logic that performs the effect.
+    pub async fn run<D, F, Fut, T>(
+        &self,
+        station_rt_id: StationRtId,
+        description: D,
+        f: F,
+    ) -> Option<T>
+    where
+        D: Into<String>,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        if self.dry_run {
+            let record = EffectRecord::new(station_rt_id, description.into());
+            self.records.write().await.push(record);
+            None
+        } else {
+            Some(f().await)
+        }
+    }
+
+    /// Returns the effects that were recorded instead of run, in the order
+    /// they were recorded.
+    pub async fn records(&self) -> Vec<EffectRecord> {
+        self.records.read().await.clone()
+    }
+}
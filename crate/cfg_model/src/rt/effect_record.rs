@@ -0,0 +1,37 @@
+use crate::rt::StationRtId;
+
+/// A side effect that a station would have run, had the train not been a dry
+/// run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EffectRecord {
+    /// Runtime ID of the station that would have run the effect.
+    station_rt_id: StationRtId,
+    /// What the effect does.
+    description: String,
+}
+
+impl EffectRecord {
+    /// Returns a new `EffectRecord`.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_rt_id`: Runtime ID of the station that would have run the
+    ///   effect.
+    /// * `description`: What the effect does.
+    pub fn new(station_rt_id: StationRtId, description: String) -> Self {
+        Self {
+            station_rt_id,
+            description,
+        }
+    }
+
+    /// Returns the runtime ID of the station that would have run the effect.
+    pub fn station_rt_id(&self) -> StationRtId {
+        self.station_rt_id
+    }
+
+    /// Returns what the effect does.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+}
@@ -0,0 +1,26 @@
+/// How station functions are executed.
+///
+/// This is inserted into [`TrainResources`] by the train, and is read by
+/// station functions constructed using [`StationFn::new_send`].
+///
+/// [`TrainResources`]: crate::rt::TrainResources
+/// [`StationFn::new_send`]: crate::StationFn::new_send
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ExecutionModel {
+    /// Station functions are awaited on the task that drives the train.
+    ///
+    /// This works with both current thread and multi-threaded runtimes.
+    CurrentThread,
+    /// `Send` station functions are spawned as tasks on the tokio runtime.
+    ///
+    /// When the train is driven on a multi-threaded runtime, these may run in
+    /// parallel on worker threads. Station functions that are not `Send` are
+    /// still awaited on the task that drives the train.
+    MultiThread,
+}
+
+impl Default for ExecutionModel {
+    fn default() -> Self {
+        Self::CurrentThread
+    }
+}
@@ -1,5 +1,7 @@
+use serde::{Deserialize, Serialize};
+
 /// Status of an operation's execution.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub enum OpStatus {
     /// Operation setup function has not been run.
     SetupQueued,
@@ -15,6 +17,12 @@ pub enum OpStatus {
     ParentFail,
     /// Operation is ready to be executed, but has not been.
     OpQueued,
+    /// Operation was not executed.
+    ///
+    /// This is because either the train stopped after another station failed,
+    /// or the station was not selected to be visited, such as by a partial
+    /// clean.
+    OpSkipped,
     /// Operation check function failed.
     CheckFail,
     /// Work execution is in progress.
@@ -25,4 +33,20 @@ pub enum OpStatus {
     WorkSuccess,
     /// The work execution failed.
     WorkFail,
+    /// The work was executed, but the verify function reported that the
+    /// created resources are not healthy.
+    VerifyFail,
+    /// A station function panicked while the operation was executed.
+    ///
+    /// The panic is caught, and recorded as a station error, so the train
+    /// continues as if the operation failed.
+    Panicked,
+    /// The work execution was stopped before it completed, because another
+    /// station failed.
+    WorkInterrupted,
+    /// Operation did not complete before the train's deadline.
+    ///
+    /// This includes operations that were not started, and operations that
+    /// were in progress when the deadline was reached.
+    DeadlineExceeded,
 }
@@ -2,6 +2,12 @@
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ProgressLimit {
     /// There is no meaningful way to measure progress.
+    ///
+    /// The station is rendered with a spinner. If the limit becomes known while
+    /// the station's work is in progress, it may be updated with
+    /// [`StationProgress::progress_limit_set`].
+    ///
+    /// [`StationProgress::progress_limit_set`]: crate::rt::StationProgress::progress_limit_set
     Unknown,
     /// Progress is complete when `n` steps have been completed.
     Steps(u64),
@@ -9,6 +15,10 @@ pub enum ProgressLimit {
     ///
     /// Useful for upload / download progress.
     Bytes(u64),
+    /// Progress is complete when `n` items are processed.
+    ///
+    /// Useful when processing a collection, such as files in a directory.
+    Items(u64),
 }
 
 impl Default for ProgressLimit {
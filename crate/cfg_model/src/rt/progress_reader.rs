@@ -0,0 +1,69 @@
+use std::{
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use indicatif::ProgressBar;
+use tokio::io::{AsyncRead, ReadBuf};
+
+use crate::rt::StationProgress;
+
+/// [`AsyncRead`] adapter that advances a station's progress bar by the number
+/// of bytes read.
+///
+/// The station's progress limit is usually [`ProgressLimit::Bytes`].
+///
+/// [`ProgressLimit::Bytes`]: crate::rt::ProgressLimit::Bytes
+#[derive(Debug)]
+pub struct ProgressReader<R> {
+    /// Progress bar to advance.
+    progress_bar: ProgressBar,
+    /// Underlying reader.
+    inner: R,
+}
+
+impl<R> ProgressReader<R> {
+    /// Returns a new [`ProgressReader`].
+    ///
+    /// # Parameters
+    ///
+    /// * `station_progress`: Progress of the station that is reading.
+    /// * `inner`: Underlying reader.
+    pub fn new(station_progress: &StationProgress, inner: R) -> Self {
+        Self {
+            progress_bar: station_progress.progress_bar().clone(),
+            inner,
+        }
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> AsyncRead for ProgressReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let filled_len_before = buf.filled().len();
+        let poll = Pin::new(&mut self.inner).poll_read(cx, buf);
+        if let Poll::Ready(Ok(())) = poll {
+            let byte_count = buf.filled().len() - filled_len_before;
+            self.progress_bar.inc(byte_count as u64);
+        }
+
+        poll
+    }
+}
@@ -0,0 +1,67 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::Stream;
+use indicatif::ProgressBar;
+
+use crate::rt::StationProgress;
+
+/// [`Stream`] adapter that advances a station's progress bar by the number of
+/// bytes in each successful item.
+///
+/// This is useful for byte streams such as HTTP response bodies. Streams that
+/// are not [`Unpin`] may be wrapped with [`Box::pin`].
+///
+/// The station's progress limit is usually [`ProgressLimit::Bytes`].
+///
+/// [`ProgressLimit::Bytes`]: crate::rt::ProgressLimit::Bytes
+#[derive(Debug)]
+pub struct ProgressStream<S> {
+    /// Progress bar to advance.
+    progress_bar: ProgressBar,
+    /// Underlying stream.
+    inner: S,
+}
+
+impl<S> ProgressStream<S> {
+    /// Returns a new [`ProgressStream`].
+    ///
+    /// # Parameters
+    ///
+    /// * `station_progress`: Progress of the station that is streaming.
+    /// * `inner`: Underlying stream.
+    pub fn new(station_progress: &StationProgress, inner: S) -> Self {
+        Self {
+            progress_bar: station_progress.progress_bar().clone(),
+            inner,
+        }
+    }
+
+    /// Returns the underlying stream.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S, T, E> Stream for ProgressStream<S>
+where
+    S: Stream<Item = Result<T, E>> + Unpin,
+    T: AsRef<[u8]>,
+{
+    type Item = Result<T, E>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let poll = Pin::new(&mut self.inner).poll_next(cx);
+        if let Poll::Ready(Some(Ok(bytes))) = &poll {
+            self.progress_bar.inc(bytes.as_ref().len() as u64);
+        }
+
+        poll
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
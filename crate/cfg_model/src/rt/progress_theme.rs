@@ -0,0 +1,152 @@
+use std::borrow::Cow;
+
+use console::Style;
+
+use crate::StationId;
+
+/// Appearance of a station's progress bar.
+///
+/// The template is an [`indicatif`] template, with the following additional
+/// placeholders that are substituted based on the station's [`OpStatus`] and
+/// [`ProgressLimit`]:
+///
+/// * `{op_symbol}`: Symbol for the operation status.
+/// * `{op_bar}`: Progress bar, styled for the operation status.
+/// * `{op_units}`: Progress units, e.g. `{pos}/{len}`.
+/// * `{op_status}`: Short description of the operation status.
+/// * `{op_eta}`: Estimated duration of the station's work, e.g. `, eta ~12s`,
+///   while the station is queued or in progress. This is empty when the
+///   duration is not known.
+///
+/// The message template supports the `{id}` and `{name}` placeholders, which
+/// are substituted with the station's ID and name.
+///
+/// This can be set per station using
+/// [`StationSpecBuilder::with_progress_theme`], or for all stations on the
+/// train.
+///
+/// [`OpStatus`]: crate::rt::OpStatus
+/// [`ProgressLimit`]: crate::rt::ProgressLimit
+/// [`StationSpecBuilder::with_progress_theme`]: crate::StationSpecBuilder::with_progress_theme
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProgressTheme {
+    /// Template for the progress bar.
+    template: Cow<'static, str>,
+    /// Template for the progress bar message.
+    message_template: Cow<'static, str>,
+    /// Characters to use for the progress bar.
+    progress_chars: Cow<'static, str>,
+    /// Width of the progress bar, in characters.
+    bar_width: usize,
+}
+
+impl ProgressTheme {
+    /// Default width of the progress bar, in characters.
+    pub const BAR_WIDTH: usize = 40;
+    /// Template for the progress bar message.
+    pub const MESSAGE_TEMPLATE: &'static str = "{id} {name}";
+    /// Characters to use for the progress bar to have fine grained animation.
+    pub const PROGRESS_CHARS: &'static str = "█▉▊▋▌▍▎▏  ";
+    /// Template that renders a progress bar with units.
+    pub const TEMPLATE_BAR: &'static str =
+        "{op_symbol} {msg:20} [{op_bar}] {op_units} ({op_status}{op_eta})";
+    /// Template that renders a spinner instead of a progress bar.
+    ///
+    /// Useful for stations that complete quickly, such as simple checks.
+    pub const TEMPLATE_SPINNER: &'static str =
+        "{op_symbol} {msg:20} {spinner:.green} ({op_status}{op_eta})";
+
+    /// Returns a new [`ProgressTheme`] with the default appearance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a [`ProgressTheme`] that renders a spinner instead of a
+    /// progress bar.
+    pub fn spinner() -> Self {
+        Self::default().with_template(Self::TEMPLATE_SPINNER)
+    }
+
+    /// Sets the template for the progress bar.
+    #[must_use]
+    pub fn with_template<S>(mut self, template: S) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        self.template = template.into();
+        self
+    }
+
+    /// Sets the template for the progress bar message.
+    #[must_use]
+    pub fn with_message_template<S>(mut self, message_template: S) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        self.message_template = message_template.into();
+        self
+    }
+
+    /// Sets the characters to use for the progress bar.
+    #[must_use]
+    pub fn with_progress_chars<S>(mut self, progress_chars: S) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        self.progress_chars = progress_chars.into();
+        self
+    }
+
+    /// Sets the width of the progress bar, in characters.
+    #[must_use]
+    pub fn with_bar_width(mut self, bar_width: usize) -> Self {
+        self.bar_width = bar_width;
+        self
+    }
+
+    /// Returns the template for the progress bar.
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    /// Returns the template for the progress bar message.
+    pub fn message_template(&self) -> &str {
+        &self.message_template
+    }
+
+    /// Returns the characters to use for the progress bar.
+    pub fn progress_chars(&self) -> &str {
+        &self.progress_chars
+    }
+
+    /// Returns the width of the progress bar, in characters.
+    pub fn bar_width(&self) -> usize {
+        self.bar_width
+    }
+
+    /// Returns the progress bar message for a station.
+    ///
+    /// # Parameters
+    ///
+    /// * `id`: Unique identifier of the station.
+    /// * `name`: Human readable name of the station.
+    pub fn message(&self, id: &StationId, name: &str) -> String {
+        let id_style = Style::new().blue().bold();
+        let name_style = Style::new().bold().bright();
+
+        self.message_template
+            .replace("{id}", &id_style.apply_to(id).to_string())
+            .replace("{name}", &name_style.apply_to(name).to_string())
+    }
+}
+
+impl Default for ProgressTheme {
+    fn default() -> Self {
+        Self {
+            template: Cow::Borrowed(Self::TEMPLATE_BAR),
+            message_template: Cow::Borrowed(Self::MESSAGE_TEMPLATE),
+            progress_chars: Cow::Borrowed(Self::PROGRESS_CHARS),
+            bar_width: Self::BAR_WIDTH,
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use std::{
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use indicatif::ProgressBar;
+use tokio::io::AsyncWrite;
+
+use crate::rt::StationProgress;
+
+/// [`AsyncWrite`] adapter that advances a station's progress bar by the number
+/// of bytes written.
+///
+/// The station's progress limit is usually [`ProgressLimit::Bytes`].
+///
+/// [`ProgressLimit::Bytes`]: crate::rt::ProgressLimit::Bytes
+#[derive(Debug)]
+pub struct ProgressWriter<W> {
+    /// Progress bar to advance.
+    progress_bar: ProgressBar,
+    /// Underlying writer.
+    inner: W,
+}
+
+impl<W> ProgressWriter<W> {
+    /// Returns a new [`ProgressWriter`].
+    ///
+    /// # Parameters
+    ///
+    /// * `station_progress`: Progress of the station that is writing.
+    /// * `inner`: Underlying writer.
+    pub fn new(station_progress: &StationProgress, inner: W) -> Self {
+        Self {
+            progress_bar: station_progress.progress_bar().clone(),
+            inner,
+        }
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W> AsyncWrite for ProgressWriter<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let poll = Pin::new(&mut self.inner).poll_write(cx, buf);
+        if let Poll::Ready(Ok(byte_count)) = poll {
+            self.progress_bar.inc(byte_count as u64);
+        }
+
+        poll
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
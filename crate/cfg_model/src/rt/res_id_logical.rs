@@ -20,10 +20,16 @@ use serde::{Deserialize, Serialize};
 /// | ------------------------ | -------------------------------------- |
 /// | `app_server_instance_id` | `ef34a9a4-0c02-45a6-96ec-a4db06d4980c` |
 /// | `app_server.address`     | `10.0.0.1`                             |
+///
+/// Logical IDs may be grouped under namespaces separated by a `/`, such as
+/// `network/vpc` and `network/subnet/0`. Reports render grouped IDs as a tree.
 #[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
 pub struct ResIdLogical(pub String);
 
 impl ResIdLogical {
+    /// Separator between the segments of a grouped logical ID.
+    pub const SEPARATOR: char = '/';
+
     /// Returns a new [`ResIdLogical`].
     pub fn new<S>(s: S) -> Self
     where
@@ -31,6 +37,46 @@ impl ResIdLogical {
     {
         Self(s.into())
     }
+
+    /// Returns the segments of this ID, separated by
+    /// [`ResIdLogical::SEPARATOR`].
+    ///
+    /// For `network/subnet/0`, this returns `network`, `subnet`, and `0`.
+    pub fn segments(&self) -> impl Iterator<Item = &str> + '_ {
+        self.0.split(Self::SEPARATOR)
+    }
+
+    /// Returns the segments of this ID before the last separator, if any.
+    ///
+    /// For `network/subnet/0`, this returns `network/subnet`. For
+    /// `instance_id`, this returns `None`.
+    pub fn namespace(&self) -> Option<&str> {
+        self.0
+            .rsplit_once(Self::SEPARATOR)
+            .map(|(namespace, _)| namespace)
+    }
+
+    /// Returns the segment of this ID after the last separator.
+    ///
+    /// For `network/subnet/0`, this returns `0`.
+    pub fn name(&self) -> &str {
+        self.0
+            .rsplit_once(Self::SEPARATOR)
+            .map_or(self.0.as_str(), |(_, name)| name)
+    }
+
+    /// Returns whether this ID is equal to, or is grouped within, the given
+    /// prefix.
+    ///
+    /// The prefix is matched by whole segments, so `network/vpc` has the
+    /// prefix `network`, but `networks` does not.
+    pub fn has_prefix(&self, prefix: &str) -> bool {
+        let prefix = prefix.trim_end_matches(Self::SEPARATOR);
+        match self.0.strip_prefix(prefix) {
+            Some(rest) => rest.is_empty() || rest.starts_with(Self::SEPARATOR),
+            None => false,
+        }
+    }
 }
 
 impl Deref for ResIdLogical {
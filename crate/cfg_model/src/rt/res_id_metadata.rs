@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+/// Structured information about a resource, such as its estimated cost.
+///
+/// Stations attach metadata to a resource ID using
+/// [`ResIds::metadata_insert`]. Metadata is persisted alongside the resource
+/// ID, so that information about every resource in a profile can be
+/// aggregated.
+///
+/// [`ResIds::metadata_insert`]: crate::rt::ResIds::metadata_insert
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct ResIdMetadata {
+    /// Estimated monthly cost of the resource.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cost_monthly: Option<f64>,
+    /// Region the resource is located in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    region: Option<String>,
+    /// Owner of the resource.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    owner: Option<String>,
+}
+
+impl ResIdMetadata {
+    /// Returns empty `ResIdMetadata`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the estimated monthly cost of the resource.
+    ///
+    /// The currency is not recorded, so all stations in a destination should
+    /// use the same currency.
+    #[must_use]
+    pub fn with_cost_monthly(mut self, cost_monthly: f64) -> Self {
+        self.cost_monthly = Some(cost_monthly);
+        self
+    }
+
+    /// Sets the region the resource is located in.
+    #[must_use]
+    pub fn with_region<S>(mut self, region: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.region = Some(region.into());
+        self
+    }
+
+    /// Sets the owner of the resource.
+    #[must_use]
+    pub fn with_owner<S>(mut self, owner: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.owner = Some(owner.into());
+        self
+    }
+
+    /// Returns the estimated monthly cost of the resource.
+    pub fn cost_monthly(&self) -> Option<f64> {
+        self.cost_monthly
+    }
+
+    /// Returns the region the resource is located in.
+    pub fn region(&self) -> Option<&str> {
+        self.region.as_deref()
+    }
+
+    /// Returns the owner of the resource.
+    pub fn owner(&self) -> Option<&str> {
+        self.owner.as_deref()
+    }
+}
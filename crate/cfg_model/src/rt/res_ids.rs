@@ -1,8 +1,9 @@
 use std::ops::{Deref, DerefMut};
 
+use indexmap::IndexMap;
 use type_reg::untagged::TypeMap;
 
-use crate::rt::ResIdLogical;
+use crate::rt::{ResIdLogical, ResIdMetadata};
 
 /// List of [`ResourceIdPhysical`]s, `TypeMap<ResIdLogical>` newtype.
 ///
@@ -17,8 +18,16 @@ use crate::rt::ResIdLogical;
 /// which is sensible for runtime values. We would have to create a new trait
 /// and a new map type if we wanted that. However the TypeId key serialization
 /// problem is still there.
+///
+/// Each resource ID may also have [`ResIdMetadata`], such as its estimated
+/// cost.
 #[derive(Clone, Debug, Default)]
-pub struct ResIds(pub TypeMap<ResIdLogical>);
+pub struct ResIds {
+    /// Physical resource IDs, keyed by their logical ID.
+    res_ids: TypeMap<ResIdLogical>,
+    /// Metadata of each resource ID that has it.
+    metadata: IndexMap<ResIdLogical, ResIdMetadata>,
+}
 
 impl ResIds {
     /// Returns an empty map of resource IDs.
@@ -28,7 +37,46 @@ impl ResIds {
 
     /// Returns an empty map of resource IDs with the specified capacity.
     pub fn with_capacity(capacity: usize) -> Self {
-        Self(TypeMap::with_capacity(capacity))
+        Self {
+            res_ids: TypeMap::with_capacity(capacity),
+            metadata: IndexMap::new(),
+        }
+    }
+
+    /// Attaches metadata to a resource ID.
+    ///
+    /// Returns the metadata previously attached to the resource ID, if any.
+    pub fn metadata_insert(
+        &mut self,
+        res_id_logical: ResIdLogical,
+        metadata: ResIdMetadata,
+    ) -> Option<ResIdMetadata> {
+        self.metadata.insert(res_id_logical, metadata)
+    }
+
+    /// Returns the metadata of each resource ID that has it.
+    pub fn metadata(&self) -> &IndexMap<ResIdLogical, ResIdMetadata> {
+        &self.metadata
+    }
+
+    /// Returns the logical IDs that are equal to, or grouped within, the given
+    /// prefix, in insertion order.
+    ///
+    /// See [`ResIdLogical::has_prefix`] for how the prefix is matched.
+    pub fn keys_with_prefix<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> impl Iterator<Item = &'a ResIdLogical> + 'a {
+        self.res_ids
+            .keys()
+            .filter(move |res_id_logical| res_id_logical.has_prefix(prefix))
+    }
+
+    /// Moves all resource IDs and metadata from `other` into `self`, leaving
+    /// `other` empty.
+    pub fn append(&mut self, other: &mut ResIds) {
+        self.res_ids.extend(other.res_ids.drain(..));
+        self.metadata.extend(other.metadata.drain(..));
     }
 }
 
@@ -37,13 +85,13 @@ impl Deref for ResIds {
 
     #[cfg(not(tarpaulin_include))]
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.res_ids
     }
 }
 
 impl DerefMut for ResIds {
     #[cfg(not(tarpaulin_include))]
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        &mut self.res_ids
     }
 }
@@ -0,0 +1,87 @@
+use std::ops::{Deref, DerefMut};
+
+use indexmap::IndexMap;
+use serde::de::DeserializeOwned;
+
+use crate::rt::{ResIdLogical, StationRtId};
+
+/// Resource IDs persisted by each station in previous executions.
+///
+/// This allows check and clean functions to compare against the resources
+/// that were actually created, instead of probing for every resource.
+///
+/// Stations may access these by taking `&ResIdsPrevious` as a function
+/// parameter, and calling [`ResIdsPrevious::get`] with the station's
+/// [`StationRtId`].
+///
+/// Physical resource IDs are stored in their serialized form, and are
+/// deserialized into the requested type when accessed.
+#[derive(Clone, Debug, Default)]
+pub struct ResIdsPrevious(IndexMap<StationRtId, IndexMap<ResIdLogical, serde_json::Value>>);
+
+impl ResIdsPrevious {
+    /// Returns an empty map of previous resource IDs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the physical resource ID persisted by a station, deserialized
+    /// as `T`.
+    ///
+    /// Returns `Ok(None)` if the station did not persist a resource ID with the
+    /// given logical ID.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_rt_id`: Runtime ID of the station that persisted the resource
+    ///   ID.
+    /// * `res_id_logical`: Logical ID of the resource.
+    pub fn get<T>(
+        &self,
+        station_rt_id: StationRtId,
+        res_id_logical: &str,
+    ) -> Result<Option<T>, serde_json::Error>
+    where
+        T: DeserializeOwned,
+    {
+        self.0
+            .get(&station_rt_id)
+            .and_then(|station_res_ids| {
+                station_res_ids
+                    .iter()
+                    .find(|(res_id_logical_existing, _)| {
+                        res_id_logical_existing.as_str() == res_id_logical
+                    })
+                    .map(|(_, res_id_physical)| res_id_physical)
+            })
+            .map(|res_id_physical| T::deserialize(res_id_physical))
+            .transpose()
+    }
+
+    /// Returns the resource IDs persisted by a station, if any.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_rt_id`: Runtime ID of the station that persisted the resource
+    ///   IDs.
+    pub fn station_res_ids(
+        &self,
+        station_rt_id: StationRtId,
+    ) -> Option<&IndexMap<ResIdLogical, serde_json::Value>> {
+        self.0.get(&station_rt_id)
+    }
+}
+
+impl Deref for ResIdsPrevious {
+    type Target = IndexMap<StationRtId, IndexMap<ResIdLogical, serde_json::Value>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for ResIdsPrevious {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
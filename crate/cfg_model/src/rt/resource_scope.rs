@@ -0,0 +1,24 @@
+use crate::rt::StationRtId;
+
+/// How long a resource inserted into [`TrainResources`] lives, and which
+/// stations may borrow it.
+///
+/// When a station function's parameters are borrowed, the station's
+/// [`ResourceScope::Op`] resources are checked first, then its
+/// [`ResourceScope::Station`] resources, then [`ResourceScope::Global`]
+/// resources.
+///
+/// [`TrainResources`]: crate::rt::TrainResources
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ResourceScope {
+    /// Borrowable by all stations, and lives until the train is dropped.
+    ///
+    /// This is the scope of resources inserted through `Resources::insert`.
+    Global,
+    /// Only borrowable by the station, and dropped after the station
+    /// finishes, including its hooks and rollback.
+    Station(StationRtId),
+    /// Only borrowable by the station, and dropped after the station's create
+    /// or clean operation completes, before its hooks and rollback are run.
+    Op(StationRtId),
+}
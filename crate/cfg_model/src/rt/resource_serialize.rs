@@ -0,0 +1,16 @@
+use resman::Resource;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Resource that can be saved to a snapshot at the end of a train's drive, and
+/// loaded into the [`TrainResources`] at the start of the next.
+///
+/// This avoids repeating expensive discovery, such as looking up instance IDs
+/// through an API, on every drive.
+///
+/// [`TrainResources`]: crate::rt::TrainResources
+pub trait ResourceSerialize: Resource + Serialize + DeserializeOwned {
+    /// Key of the resource within the snapshot.
+    ///
+    /// This must be unique among the resources in a snapshot.
+    const SNAPSHOT_KEY: &'static str;
+}
@@ -0,0 +1,16 @@
+use futures::future::LocalBoxFuture;
+use resman::Resource;
+
+/// Resource that is shut down at the end of a train's drive, such as an API
+/// client, temporary directory, or database pool.
+///
+/// Resources are torn down in the reverse order that they were inserted with
+/// [`TrainResources::insert_teardown`], so a resource may still use resources
+/// that were inserted before it. Errors are recorded as warnings in the
+/// train's report, instead of failing the drive.
+///
+/// [`TrainResources::insert_teardown`]: crate::rt::TrainResources::insert_teardown
+pub trait ResourceTeardown<E>: Resource {
+    /// Shuts down this resource.
+    fn teardown(&mut self) -> LocalBoxFuture<'_, Result<(), E>>;
+}
@@ -0,0 +1,84 @@
+use std::{any::TypeId, fmt};
+
+use futures::future::{FutureExt, LocalBoxFuture};
+use resman::Resources;
+
+use crate::rt::ResourceTeardown;
+
+/// Tears down a resource in the given resources, if it is present.
+type ResourceTeardownFn<E> = for<'r> fn(&'r Resources) -> LocalBoxFuture<'r, Option<Result<(), E>>>;
+
+/// Teardown functions of resources inserted with
+/// [`TrainResources::insert_teardown`], in insertion order.
+///
+/// [`TrainResources::insert_teardown`]: crate::rt::TrainResources::insert_teardown
+pub(crate) struct ResourceTeardowns<E> {
+    /// Type ID, type name, and teardown function of each resource.
+    teardown_fns: Vec<(TypeId, &'static str, ResourceTeardownFn<E>)>,
+}
+
+impl<E> ResourceTeardowns<E>
+where
+    E: 'static,
+{
+    /// Registers the teardown function of `R`.
+    ///
+    /// If `R` is already registered, it is moved to the end of the list, as
+    /// the resource has been reinserted.
+    pub(crate) fn push<R>(&mut self)
+    where
+        R: ResourceTeardown<E>,
+    {
+        let type_id = TypeId::of::<R>();
+        self.teardown_fns
+            .retain(|(type_id_existing, _, _)| *type_id_existing != type_id);
+        self.teardown_fns.push((
+            type_id,
+            std::any::type_name::<R>(),
+            Self::resource_teardown::<R>,
+        ));
+    }
+
+    /// Tears down each registered resource in reverse insertion order, and
+    /// returns the errors.
+    ///
+    /// Resources are only torn down once; subsequent calls do nothing.
+    pub(crate) async fn teardown(&mut self, resources: &Resources) -> Vec<E> {
+        let teardown_fns = std::mem::take(&mut self.teardown_fns);
+        let mut errors = Vec::new();
+        for (_, _, teardown_fn) in teardown_fns.into_iter().rev() {
+            if let Some(Err(error)) = teardown_fn(resources).await {
+                errors.push(error);
+            }
+        }
+
+        errors
+    }
+
+    fn resource_teardown<R>(resources: &Resources) -> LocalBoxFuture<'_, Option<Result<(), E>>>
+    where
+        R: ResourceTeardown<E>,
+    {
+        async move {
+            let mut resource = resources.try_borrow_mut::<R>().ok()?;
+            Some(resource.teardown().await)
+        }
+        .boxed_local()
+    }
+}
+
+impl<E> Default for ResourceTeardowns<E> {
+    fn default() -> Self {
+        Self {
+            teardown_fns: Vec::new(),
+        }
+    }
+}
+
+impl<E> fmt::Debug for ResourceTeardowns<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.teardown_fns.iter().map(|(_, type_name, _)| type_name))
+            .finish()
+    }
+}
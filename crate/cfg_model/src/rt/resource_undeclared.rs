@@ -0,0 +1,74 @@
+use std::fmt;
+
+use crate::StationId;
+
+/// Error when a station borrows a resource that it did not declare.
+///
+/// Stations declare the resources they access through the parameters of their
+/// check, work, and verify functions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResourceUndeclared {
+    /// Unique identifier of the station.
+    station_id: StationId,
+    /// Human readable name of the station.
+    station_name: String,
+    /// Type name of the resource.
+    type_name: &'static str,
+    /// Whether the resource was borrowed mutably.
+    mutable: bool,
+}
+
+impl ResourceUndeclared {
+    /// Returns a new `ResourceUndeclared`.
+    pub fn new(
+        station_id: StationId,
+        station_name: String,
+        type_name: &'static str,
+        mutable: bool,
+    ) -> Self {
+        Self {
+            station_id,
+            station_name,
+            type_name,
+            mutable,
+        }
+    }
+
+    /// Returns the unique identifier of the station.
+    pub fn station_id(&self) -> &StationId {
+        &self.station_id
+    }
+
+    /// Returns the human readable name of the station.
+    pub fn station_name(&self) -> &str {
+        &self.station_name
+    }
+
+    /// Returns the type name of the resource.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns whether the resource was borrowed mutably.
+    pub fn mutable(&self) -> bool {
+        self.mutable
+    }
+}
+
+impl fmt::Display for ResourceUndeclared {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Self {
+            station_id,
+            station_name,
+            type_name,
+            mutable,
+        } = self;
+        let borrow = if *mutable { "&mut " } else { "&" };
+        write!(
+            f,
+            "Station `{station_id}: {station_name}` borrowed `{borrow}{type_name}`, but no check, work, or verify function of the station takes it as a parameter."
+        )
+    }
+}
+
+impl std::error::Error for ResourceUndeclared {}
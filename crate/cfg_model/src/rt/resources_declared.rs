@@ -0,0 +1,75 @@
+use std::any::{self, TypeId};
+
+use fn_graph::{FnMeta, TypeIds};
+
+use crate::{rt::ResourceUndeclared, StationId, StationSpec};
+
+/// Resources that a station declared to borrow.
+///
+/// These are the resources that the station's check, work, and verify
+/// functions take as parameters.
+#[derive(Debug)]
+pub(crate) struct ResourcesDeclared {
+    /// Unique identifier of the station.
+    station_id: StationId,
+    /// Human readable name of the station.
+    station_name: String,
+    /// [`TypeId`]s of resources the station declared to borrow.
+    borrows: TypeIds,
+    /// [`TypeId`]s of resources the station declared to mutably borrow.
+    borrow_muts: TypeIds,
+}
+
+impl ResourcesDeclared {
+    /// Returns the resources that the given station declared.
+    ///
+    /// # Parameters
+    ///
+    /// * `spec`: Behaviour specification of the station.
+    pub(crate) fn new<E>(spec: &StationSpec<E>) -> Self {
+        let station_op = spec.station_op();
+        let borrows = station_op.borrows();
+        let borrow_muts = station_op.borrow_muts();
+
+        Self {
+            station_id: spec.id().clone(),
+            station_name: spec.name().to_string(),
+            borrows,
+            borrow_muts,
+        }
+    }
+
+    /// Returns an error if the station did not declare the resource.
+    pub(crate) fn borrow_check<R>(&self) -> Result<(), ResourceUndeclared>
+    where
+        R: 'static,
+    {
+        let type_id = TypeId::of::<R>();
+        if self.borrows.contains(&type_id) || self.borrow_muts.contains(&type_id) {
+            Ok(())
+        } else {
+            Err(self.resource_undeclared::<R>(false))
+        }
+    }
+
+    /// Returns an error if the station did not declare the resource as `&mut`.
+    pub(crate) fn borrow_mut_check<R>(&self) -> Result<(), ResourceUndeclared>
+    where
+        R: 'static,
+    {
+        if self.borrow_muts.contains(&TypeId::of::<R>()) {
+            Ok(())
+        } else {
+            Err(self.resource_undeclared::<R>(true))
+        }
+    }
+
+    fn resource_undeclared<R>(&self, mutable: bool) -> ResourceUndeclared {
+        ResourceUndeclared::new(
+            self.station_id.clone(),
+            self.station_name.clone(),
+            any::type_name::<R>(),
+            mutable,
+        )
+    }
+}
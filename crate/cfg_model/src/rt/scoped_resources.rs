@@ -0,0 +1,235 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+use resman::{BorrowFail, Ref, RefMut, Resource, Resources};
+
+use crate::rt::{ResourceScope, StationRtId};
+
+/// Resources that are only borrowable by one station, grouped by scope.
+///
+/// Scopes are inserted into with exclusive access, such as in setup functions,
+/// but dropped with shared access, while other stations are being visited.
+/// To allow this, each resource is held in a [`ScopedValue`], which is emptied
+/// when its scope is dropped.
+#[derive(Debug, Default)]
+pub(crate) struct ScopedResources {
+    /// Resources in each station or operation scope.
+    scopes: HashMap<ResourceScope, ScopeResources>,
+}
+
+impl ScopedResources {
+    /// Inserts a resource into the given scope, replacing any existing
+    /// resource of the same type in that scope.
+    ///
+    /// This must not be called with [`ResourceScope::Global`].
+    pub(crate) fn insert<R>(&mut self, scope: ResourceScope, resource: R)
+    where
+        R: Resource,
+    {
+        let scope_resources = self.scopes.entry(scope).or_default();
+        scope_resources
+            .resources
+            .insert(ScopedValue(Some(resource)));
+        scope_resources.droppers.push(ScopedValue::<R>::drop_in);
+    }
+
+    /// Drops the resources in the given scope.
+    ///
+    /// Resources that are currently borrowed are not dropped.
+    pub(crate) fn drop_scope(&self, scope: ResourceScope) {
+        if let Some(scope_resources) = self.scopes.get(&scope) {
+            scope_resources
+                .droppers
+                .iter()
+                .for_each(|dropper| dropper(&scope_resources.resources));
+        }
+    }
+
+    /// Returns an immutable reference to the resource in the station's
+    /// narrowest scope that holds it, or `None` if no scope holds it.
+    pub(crate) fn try_borrow<R>(
+        &self,
+        station_rt_id: StationRtId,
+    ) -> Option<Result<Ref<'_, ScopedValue<R>>, BorrowFail>>
+    where
+        R: Resource,
+    {
+        Self::station_scopes(station_rt_id).find_map(|scope| {
+            let scope_resources = self.scopes.get(&scope)?;
+            match scope_resources.resources.try_borrow::<ScopedValue<R>>() {
+                Ok(scoped_value) if scoped_value.0.is_some() => Some(Ok(scoped_value)),
+                Ok(_) | Err(BorrowFail::ValueNotFound) => None,
+                Err(borrow_fail) => Some(Err(borrow_fail)),
+            }
+        })
+    }
+
+    /// Returns a mutable reference to the resource in the station's narrowest
+    /// scope that holds it, or `None` if no scope holds it.
+    pub(crate) fn try_borrow_mut<R>(
+        &self,
+        station_rt_id: StationRtId,
+    ) -> Option<Result<RefMut<'_, ScopedValue<R>>, BorrowFail>>
+    where
+        R: Resource,
+    {
+        Self::station_scopes(station_rt_id).find_map(|scope| {
+            let scope_resources = self.scopes.get(&scope)?;
+            match scope_resources.resources.try_borrow_mut::<ScopedValue<R>>() {
+                Ok(scoped_value) if scoped_value.0.is_some() => Some(Ok(scoped_value)),
+                Ok(_) | Err(BorrowFail::ValueNotFound) => None,
+                Err(borrow_fail) => Some(Err(borrow_fail)),
+            }
+        })
+    }
+
+    /// Returns the station's scopes, from narrowest to widest.
+    fn station_scopes(station_rt_id: StationRtId) -> impl Iterator<Item = ResourceScope> {
+        IntoIterator::into_iter([
+            ResourceScope::Op(station_rt_id),
+            ResourceScope::Station(station_rt_id),
+        ])
+    }
+}
+
+/// Resources in one scope, and the functions to drop each of them.
+#[derive(Default)]
+struct ScopeResources {
+    /// Resources in the scope, each held in a [`ScopedValue`].
+    resources: Resources,
+    /// Functions that drop each resource type in the scope.
+    droppers: Vec<fn(&Resources)>,
+}
+
+impl fmt::Debug for ScopeResources {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScopeResources")
+            .field("resources", &self.resources)
+            .finish()
+    }
+}
+
+/// Scoped resource, which is `None` once its scope is dropped.
+#[derive(Debug)]
+pub(crate) struct ScopedValue<R>(Option<R>);
+
+impl<R> ScopedValue<R>
+where
+    R: Resource,
+{
+    /// Drops the `R` resource held in the given resources, if it is not
+    /// borrowed.
+    fn drop_in(resources: &Resources) {
+        if let Ok(mut scoped_value) = resources.try_borrow_mut::<Self>() {
+            scoped_value.0 = None;
+        }
+    }
+}
+
+/// Immutable reference to a resource borrowed for a station, which may be from
+/// the station's scopes or the global scope.
+pub struct ScopedRef<'a, R>(ScopedRefInner<'a, R>);
+
+enum ScopedRefInner<'a, R> {
+    Global(Ref<'a, R>),
+    Scoped(Ref<'a, ScopedValue<R>>),
+}
+
+impl<'a, R> ScopedRef<'a, R> {
+    pub(crate) fn global(r: Ref<'a, R>) -> Self {
+        Self(ScopedRefInner::Global(r))
+    }
+
+    pub(crate) fn scoped(r: Ref<'a, ScopedValue<R>>) -> Self {
+        Self(ScopedRefInner::Scoped(r))
+    }
+}
+
+impl<'a, R> Deref for ScopedRef<'a, R>
+where
+    R: Resource,
+{
+    type Target = R;
+
+    fn deref(&self) -> &R {
+        match &self.0 {
+            ScopedRefInner::Global(r) => r,
+            ScopedRefInner::Scoped(r) => {
+                r.0.as_ref()
+                    .expect("Scoped resources are not dropped while borrowed.")
+            }
+        }
+    }
+}
+
+impl<'a, R> fmt::Debug for ScopedRef<'a, R>
+where
+    R: Resource,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+/// Mutable reference to a resource borrowed for a station, which may be from
+/// the station's scopes or the global scope.
+pub struct ScopedRefMut<'a, R>(ScopedRefMutInner<'a, R>);
+
+enum ScopedRefMutInner<'a, R> {
+    Global(RefMut<'a, R>),
+    Scoped(RefMut<'a, ScopedValue<R>>),
+}
+
+impl<'a, R> ScopedRefMut<'a, R> {
+    pub(crate) fn global(r: RefMut<'a, R>) -> Self {
+        Self(ScopedRefMutInner::Global(r))
+    }
+
+    pub(crate) fn scoped(r: RefMut<'a, ScopedValue<R>>) -> Self {
+        Self(ScopedRefMutInner::Scoped(r))
+    }
+}
+
+impl<'a, R> Deref for ScopedRefMut<'a, R>
+where
+    R: Resource,
+{
+    type Target = R;
+
+    fn deref(&self) -> &R {
+        match &self.0 {
+            ScopedRefMutInner::Global(r) => r,
+            ScopedRefMutInner::Scoped(r) => {
+                r.0.as_ref()
+                    .expect("Scoped resources are not dropped while borrowed.")
+            }
+        }
+    }
+}
+
+impl<'a, R> fmt::Debug for ScopedRefMut<'a, R>
+where
+    R: Resource,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<'a, R> DerefMut for ScopedRefMut<'a, R>
+where
+    R: Resource,
+{
+    fn deref_mut(&mut self) -> &mut R {
+        match &mut self.0 {
+            ScopedRefMutInner::Global(r) => r,
+            ScopedRefMutInner::Scoped(r) => {
+                r.0.as_mut()
+                    .expect("Scoped resources are not dropped while borrowed.")
+            }
+        }
+    }
+}
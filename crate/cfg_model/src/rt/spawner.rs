@@ -0,0 +1,48 @@
+use std::{fmt, sync::Arc};
+
+use futures::future::BoxFuture;
+
+// **Note:** `Debug` is manually implemented as closures do not implement
+// `Debug`.
+/// Spawns the futures of `Send` station functions as tasks.
+///
+/// When the [`ExecutionModel`] is [`ExecutionModel::MultiThread`], the futures
+/// of station functions constructed using [`StationFn::new_send`] are spawned
+/// using the `Spawner` in [`TrainResources`], if present. Otherwise they are
+/// spawned using `tokio::spawn` when the `native` feature is enabled, and
+/// awaited in place when it is not.
+///
+/// This allows station work to be spawned on executors other than tokio, such
+/// as the browser's event loop.
+///
+/// [`ExecutionModel`]: crate::rt::ExecutionModel
+/// [`ExecutionModel::MultiThread`]: crate::rt::ExecutionModel::MultiThread
+/// [`StationFn::new_send`]: crate::StationFn::new_send
+/// [`TrainResources`]: crate::rt::TrainResources
+#[derive(Clone)]
+pub struct Spawner(Arc<dyn Fn(BoxFuture<'static, ()>) + Send + Sync>);
+
+impl Spawner {
+    /// Returns a new `Spawner`.
+    ///
+    /// # Parameters
+    ///
+    /// * `f`: Spawns the given future as a task.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(BoxFuture<'static, ()>) + Send + Sync + 'static,
+    {
+        Self(Arc::new(f))
+    }
+
+    /// Spawns the given future as a task.
+    pub fn spawn(&self, fut: BoxFuture<'static, ()>) {
+        (self.0)(fut)
+    }
+}
+
+impl fmt::Debug for Spawner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Spawner(fn(BoxFuture<'static, ()>))")
+    }
+}
@@ -1,4 +1,4 @@
-use rt_map::Ref;
+use tokio::sync::RwLockReadGuard;
 
 use crate::{
     rt::{StationProgress, StationRtId},
@@ -15,5 +15,5 @@ pub struct Station<'s, E> {
     /// Runtime identifier for a station.
     pub rt_id: StationRtId,
     /// Station progress to reaching the destination.
-    pub progress: Ref<'s, StationProgress>,
+    pub progress: RwLockReadGuard<'s, StationProgress>,
 }
@@ -0,0 +1,44 @@
+use std::path::{Path, PathBuf};
+
+/// Output of a station, such as a build output, that is collected after the
+/// train's drive.
+///
+/// Stations register artifacts with [`StationMutRef::artifact_register`].
+///
+/// [`StationMutRef::artifact_register`]: crate::rt::StationMutRef::artifact_register
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StationArtifact {
+    /// Path to the artifact file or directory.
+    path: PathBuf,
+    /// Human readable label of the artifact.
+    label: String,
+}
+
+impl StationArtifact {
+    /// Returns a new [`StationArtifact`].
+    ///
+    /// # Parameters
+    ///
+    /// * `path`: Path to the artifact file or directory.
+    /// * `label`: Human readable label of the artifact.
+    pub fn new<P, L>(path: P, label: L) -> Self
+    where
+        P: Into<PathBuf>,
+        L: Into<String>,
+    {
+        Self {
+            path: path.into(),
+            label: label.into(),
+        }
+    }
+
+    /// Returns the path to the artifact file or directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the human readable label of the artifact.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+}
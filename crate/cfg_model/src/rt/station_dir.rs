@@ -8,14 +8,66 @@ use std::{
 ///
 /// Information stored in this directory should be able to be reused in
 /// subsequent executions or simply for reporting -- e.g. last execution status.
+///
+/// Stations may organize their files into the standard subdirectories, which
+/// the train creates through the destination's file system before stations
+/// are visited:
+///
+/// * `inputs/`: Files the station's work is done with.
+/// * `outputs/`: Files produced by the station's work.
+/// * `logs/`: Logs of the station's work.
+/// * `tmp/`: Scratch files, removed after each visit of the station.
 #[derive(Clone, Debug, PartialEq)]
 pub struct StationDir(PathBuf);
 
 impl StationDir {
+    /// Name of the subdirectory for files the station's work is done with.
+    pub const INPUTS_DIR_NAME: &'static str = "inputs";
+    /// Name of the subdirectory for logs of the station's work.
+    pub const LOGS_DIR_NAME: &'static str = "logs";
+    /// Name of the subdirectory for files produced by the station's work.
+    pub const OUTPUTS_DIR_NAME: &'static str = "outputs";
+    /// Name of the subdirectory for scratch files.
+    pub const TMP_DIR_NAME: &'static str = "tmp";
+
     /// Returns a new [`StationDir`].
     pub fn new(path: PathBuf) -> Self {
         Self(path)
     }
+
+    /// Returns the `inputs/` subdirectory.
+    pub fn inputs_dir(&self) -> PathBuf {
+        self.0.join(Self::INPUTS_DIR_NAME)
+    }
+
+    /// Returns the `outputs/` subdirectory.
+    pub fn outputs_dir(&self) -> PathBuf {
+        self.0.join(Self::OUTPUTS_DIR_NAME)
+    }
+
+    /// Returns the `logs/` subdirectory.
+    pub fn logs_dir(&self) -> PathBuf {
+        self.0.join(Self::LOGS_DIR_NAME)
+    }
+
+    /// Returns the `tmp/` subdirectory.
+    ///
+    /// The directory and its contents are removed after each visit of the
+    /// station completes, so it should only hold files that are not needed
+    /// by subsequent visits.
+    pub fn tmp_dir(&self) -> PathBuf {
+        self.0.join(Self::TMP_DIR_NAME)
+    }
+
+    /// Returns the standard subdirectories.
+    pub fn subdirs(&self) -> [PathBuf; 4] {
+        [
+            self.inputs_dir(),
+            self.outputs_dir(),
+            self.logs_dir(),
+            self.tmp_dir(),
+        ]
+    }
 }
 
 impl AsRef<OsStr> for StationDir {
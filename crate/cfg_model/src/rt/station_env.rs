@@ -0,0 +1,181 @@
+use std::{
+    env::{self, JoinPathsError},
+    ffi::OsString,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use indexmap::IndexMap;
+
+/// Environment that external commands run by a station are invoked with.
+///
+/// This holds environment variables, directories to prepend to `PATH`, and
+/// the working directory, so that tool invocations behave the same way across
+/// machines.
+///
+/// A base environment for all stations may be set on the train, and each
+/// station may override it using [`StationSpecBuilder::with_env`]. The
+/// environment for each station is resolved before stations are visited,
+/// with the working directory defaulting to the station's directory.
+///
+/// [`StationSpecBuilder::with_env`]: crate::StationSpecBuilder::with_env
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StationEnv {
+    /// Whether variables inherited from the current process are removed.
+    env_clear: bool,
+    /// Environment variables to set.
+    env_vars: IndexMap<String, String>,
+    /// Directories to prepend to `PATH`, in order of precedence.
+    path_prepend: Vec<PathBuf>,
+    /// Working directory of invoked commands.
+    cwd: Option<PathBuf>,
+}
+
+impl StationEnv {
+    /// Name of the environment variable that holds the executable search
+    /// paths.
+    pub const PATH: &'static str = "PATH";
+
+    /// Returns a new, empty `StationEnv`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether variables inherited from the current process are removed.
+    ///
+    /// When `true`, commands only see the variables set in this environment,
+    /// which avoids differences between machines leaking into the station.
+    #[must_use]
+    pub fn with_env_clear(mut self, env_clear: bool) -> Self {
+        self.env_clear = env_clear;
+        self
+    }
+
+    /// Sets an environment variable.
+    #[must_use]
+    pub fn with_env_var<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.env_vars.insert(key.into(), value.into());
+        self
+    }
+
+    /// Prepends a directory to `PATH`.
+    ///
+    /// Directories added later take precedence over those added earlier.
+    #[must_use]
+    pub fn with_path_prepend<P>(mut self, dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.path_prepend.insert(0, dir.into());
+        self
+    }
+
+    /// Sets the working directory of invoked commands.
+    #[must_use]
+    pub fn with_cwd<P>(mut self, cwd: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.cwd = Some(cwd.into());
+        self
+    }
+
+    /// Returns whether variables inherited from the current process are
+    /// removed.
+    pub fn env_clear(&self) -> bool {
+        self.env_clear
+    }
+
+    /// Returns the environment variables to set.
+    pub fn env_vars(&self) -> &IndexMap<String, String> {
+        &self.env_vars
+    }
+
+    /// Returns the directories to prepend to `PATH`, in order of precedence.
+    pub fn path_prepend(&self) -> &[PathBuf] {
+        &self.path_prepend
+    }
+
+    /// Returns the working directory of invoked commands, if set.
+    pub fn cwd(&self) -> Option<&Path> {
+        self.cwd.as_deref()
+    }
+
+    /// Returns this environment with the given overrides applied.
+    ///
+    /// * Variables are removed if either environment removes them.
+    /// * Variables in `overrides` replace those with the same name.
+    /// * Directories in `overrides` are prepended to `PATH` before these
+    ///   directories.
+    /// * The working directory in `overrides` is used if set.
+    pub fn merge(&self, overrides: &StationEnv) -> StationEnv {
+        let mut env_vars = self.env_vars.clone();
+        env_vars.extend(
+            overrides
+                .env_vars
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
+        let path_prepend = overrides
+            .path_prepend
+            .iter()
+            .chain(self.path_prepend.iter())
+            .cloned()
+            .collect();
+
+        StationEnv {
+            env_clear: self.env_clear || overrides.env_clear,
+            env_vars,
+            path_prepend,
+            cwd: overrides.cwd.clone().or_else(|| self.cwd.clone()),
+        }
+    }
+
+    /// Returns the value of `PATH` for invoked commands, if it is changed.
+    ///
+    /// The prepended directories are followed by the `PATH` variable set in
+    /// this environment, or the current process' `PATH` if variables are
+    /// inherited.
+    pub fn path(&self) -> Result<Option<OsString>, JoinPathsError> {
+        if self.path_prepend.is_empty() {
+            return Ok(None);
+        }
+
+        let path_base = match self.env_vars.get(Self::PATH) {
+            Some(path) => Some(OsString::from(path)),
+            None if !self.env_clear => env::var_os(Self::PATH),
+            None => None,
+        };
+        let path_base_dirs = path_base
+            .as_ref()
+            .map(|path_base| env::split_paths(path_base).collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        env::join_paths(self.path_prepend.iter().chain(path_base_dirs.iter())).map(Some)
+    }
+
+    /// Applies this environment to a command.
+    ///
+    /// For `tokio` commands, apply this to a [`std::process::Command`] and
+    /// convert it using `tokio::process::Command::from`.
+    pub fn apply<'c>(&self, command: &'c mut Command) -> Result<&'c mut Command, JoinPathsError> {
+        let path = self.path()?;
+
+        if self.env_clear {
+            command.env_clear();
+        }
+        command.envs(self.env_vars.iter());
+        if let Some(path) = path {
+            command.env(Self::PATH, path);
+        }
+        if let Some(cwd) = self.cwd.as_ref() {
+            command.current_dir(cwd);
+        }
+
+        Ok(command)
+    }
+}
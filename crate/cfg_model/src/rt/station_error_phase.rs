@@ -0,0 +1,26 @@
+use crate::rt::VisitOp;
+
+/// Phase of a station's visit that an error was encountered in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StationErrorPhase {
+    /// Error when setting up the station's resources, before it is visited.
+    Setup,
+    /// Error when checking whether the station's work is required.
+    Check,
+    /// Error when running the station's work.
+    Work,
+    /// Error when verifying the station's work after it ran.
+    Verify,
+    /// Error when checking or running the station's clean functions.
+    Clean,
+}
+
+impl From<VisitOp> for StationErrorPhase {
+    /// Returns the phase that runs the work of the given operation.
+    fn from(visit_op: VisitOp) -> Self {
+        match visit_op {
+            VisitOp::Create => Self::Work,
+            VisitOp::Clean => Self::Clean,
+        }
+    }
+}
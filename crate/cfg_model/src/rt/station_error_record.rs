@@ -0,0 +1,73 @@
+use std::time::SystemTime;
+
+use crate::rt::{StationErrorPhase, StationRtId};
+
+/// An error encountered when visiting a station, and when it was encountered.
+#[derive(Clone, Debug)]
+pub struct StationErrorRecord<E> {
+    /// Runtime ID of the station the error is for.
+    station_rt_id: StationRtId,
+    /// Phase of the visit that the error was encountered in.
+    phase: StationErrorPhase,
+    /// Attempt of the phase that the error was encountered in, starting at 1.
+    attempt: u32,
+    /// Time the error was recorded.
+    timestamp: SystemTime,
+    /// The error.
+    error: E,
+}
+
+impl<E> StationErrorRecord<E> {
+    /// Returns a new `StationErrorRecord`, timestamped with the current time.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_rt_id`: Runtime ID of the station the error is for.
+    /// * `phase`: Phase of the visit that the error was encountered in.
+    /// * `attempt`: Attempt of the phase, starting at 1.
+    /// * `error`: The error.
+    pub fn new(
+        station_rt_id: StationRtId,
+        phase: StationErrorPhase,
+        attempt: u32,
+        error: E,
+    ) -> Self {
+        Self {
+            station_rt_id,
+            phase,
+            attempt,
+            timestamp: SystemTime::now(),
+            error,
+        }
+    }
+
+    /// Returns the runtime ID of the station the error is for.
+    pub fn station_rt_id(&self) -> StationRtId {
+        self.station_rt_id
+    }
+
+    /// Returns the phase of the visit that the error was encountered in.
+    pub fn phase(&self) -> StationErrorPhase {
+        self.phase
+    }
+
+    /// Returns the attempt of the phase that the error was encountered in.
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    /// Returns the time the error was recorded.
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    /// Returns the error.
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// Returns the error, consuming the record.
+    pub fn into_error(self) -> E {
+        self.error
+    }
+}
@@ -1,40 +1,92 @@
-use std::{
-    ops::{Deref, DerefMut},
-    sync::Arc,
-};
+use std::sync::Arc;
 
-use indexmap::IndexMap;
-use tokio::sync::RwLock;
+use tokio::sync::{OwnedRwLockReadGuard, RwLock, RwLockReadGuard, TryLockError};
 
-use crate::rt::StationRtId;
+use crate::rt::{StationErrorPhase, StationErrorRecord, StationRtId};
 
 /// Errors encountered when visiting stations.
-#[derive(Clone, Debug)]
-pub struct StationErrors<E>(Arc<RwLock<IndexMap<StationRtId, E>>>);
+///
+/// Errors are appended in the order they are recorded, and are never replaced
+/// or removed, so a station that fails in more than one phase, or more than
+/// once in a phase, has a record for each failure.
+#[derive(Debug)]
+pub struct StationErrors<E>(Arc<RwLock<Vec<StationErrorRecord<E>>>>);
 
 impl<E> StationErrors<E> {
     /// Returns new [`StationErrors`].
     pub fn new() -> Self {
         Self::default()
     }
-}
 
-impl<E> Default for StationErrors<E> {
-    fn default() -> Self {
-        Self(Arc::new(RwLock::new(IndexMap::new())))
+    /// Records an error for the given station.
+    ///
+    /// The attempt number is one more than the number of errors already
+    /// recorded for the station in the same phase.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_rt_id`: Runtime ID of the station the error is for.
+    /// * `phase`: Phase of the visit that the error was encountered in.
+    /// * `error`: The error to record.
+    pub async fn insert(&self, station_rt_id: StationRtId, phase: StationErrorPhase, error: E) {
+        let mut records = self.0.write().await;
+        let attempt = records
+            .iter()
+            .filter(|record| record.station_rt_id() == station_rt_id && record.phase() == phase)
+            .count() as u32
+            + 1;
+        records.push(StationErrorRecord::new(
+            station_rt_id,
+            phase,
+            attempt,
+            error,
+        ));
+    }
+
+    /// Returns the recorded errors, waiting for any in-progress insertion.
+    pub async fn read(&self) -> RwLockReadGuard<'_, Vec<StationErrorRecord<E>>> {
+        self.0.read().await
+    }
+
+    /// Returns the recorded errors, failing if an insertion is in progress.
+    pub fn try_read(
+        &self,
+    ) -> Result<RwLockReadGuard<'_, Vec<StationErrorRecord<E>>>, TryLockError> {
+        self.0.try_read()
     }
-}
 
-impl<E> Deref for StationErrors<E> {
-    type Target = Arc<RwLock<IndexMap<StationRtId, E>>>;
+    /// Returns the recorded errors through a guard that does not borrow this
+    /// handle.
+    pub async fn read_owned(&self) -> OwnedRwLockReadGuard<Vec<StationErrorRecord<E>>> {
+        Arc::clone(&self.0).read_owned().await
+    }
+
+    /// Returns whether no errors have been recorded.
+    pub async fn is_empty(&self) -> bool {
+        self.0.read().await.is_empty()
+    }
+
+    /// Returns the recorded errors, consuming this handle.
+    ///
+    /// # Errors
+    ///
+    /// Returns this handle if other handles to the errors exist.
+    pub fn into_records(self) -> Result<Vec<StationErrorRecord<E>>, Self> {
+        Arc::try_unwrap(self.0)
+            .map(RwLock::into_inner)
+            .map_err(Self)
+    }
+}
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
+// We `impl Clone` to avoid the `E: Clone` bound generated by the derive.
+impl<E> Clone for StationErrors<E> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
     }
 }
 
-impl<E> DerefMut for StationErrors<E> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+impl<E> Default for StationErrors<E> {
+    fn default() -> Self {
+        Self(Arc::new(RwLock::new(Vec::new())))
     }
 }
@@ -0,0 +1,88 @@
+use std::{
+    ops::{Deref, DerefMut},
+    path::PathBuf,
+    sync::Arc,
+};
+
+use indexmap::IndexMap;
+use tokio::sync::RwLock;
+
+use crate::StationId;
+
+/// Files published by stations for other stations to read, by station and
+/// name.
+///
+/// Files published with [`StationMut::publish_file`] or
+/// [`StationMutRef::publish_file`] are recorded here after the station is set
+/// up, and after it is visited. Stations that declare a file input have its
+/// path resolved from here before their create check.
+///
+/// [`StationMut::publish_file`]: crate::rt::StationMut::publish_file
+/// [`StationMutRef::publish_file`]: crate::rt::StationMutRef::publish_file
+#[derive(Clone, Debug, Default)]
+pub struct StationFiles(Arc<RwLock<IndexMap<StationId, IndexMap<String, PathBuf>>>>);
+
+impl StationFiles {
+    /// Returns new [`StationFiles`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the files published by a station.
+    ///
+    /// Files previously published under the same name are replaced.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_id`: Unique identifier of the station that published the
+    ///   files.
+    /// * `files_published`: Paths of the published files, by name.
+    pub async fn publish(
+        &self,
+        station_id: &StationId,
+        files_published: &IndexMap<String, PathBuf>,
+    ) {
+        if files_published.is_empty() {
+            return;
+        }
+
+        let mut station_files = self.0.write().await;
+        station_files
+            .entry(station_id.clone())
+            .or_insert_with(IndexMap::new)
+            .extend(
+                files_published
+                    .iter()
+                    .map(|(name, path)| (name.clone(), path.clone())),
+            );
+    }
+
+    /// Returns the path of a file published by a station.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_id`: Unique identifier of the station that published the
+    ///   file.
+    /// * `name`: Name the file is published under.
+    pub async fn get(&self, station_id: &StationId, name: &str) -> Option<PathBuf> {
+        let station_files = self.0.read().await;
+        station_files
+            .get(station_id)
+            .and_then(|files_published| files_published.get(name))
+            .cloned()
+    }
+}
+
+impl Deref for StationFiles {
+    type Target = Arc<RwLock<IndexMap<StationId, IndexMap<String, PathBuf>>>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for StationFiles {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
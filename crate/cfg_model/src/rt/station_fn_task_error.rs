@@ -0,0 +1,77 @@
+use std::fmt;
+
+use crate::{rt::ResIds, StationId};
+
+/// Error when the task that a station function's future is spawned on does
+/// not complete.
+///
+/// This happens when the [`Spawner`] drops the task, or the runtime is shut
+/// down before the task completes.
+///
+/// [`Spawner`]: crate::rt::Spawner
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StationFnTaskError {
+    /// Unique identifier of the station.
+    station_id: StationId,
+    /// Human readable name of the station.
+    station_name: String,
+    /// Description of why the task did not complete.
+    detail: String,
+}
+
+impl StationFnTaskError {
+    /// Returns a new `StationFnTaskError`.
+    pub fn new(station_id: StationId, station_name: String, detail: String) -> Self {
+        Self {
+            station_id,
+            station_name,
+            detail,
+        }
+    }
+
+    /// Returns the unique identifier of the station.
+    pub fn station_id(&self) -> &StationId {
+        &self.station_id
+    }
+
+    /// Returns the human readable name of the station.
+    pub fn station_name(&self) -> &str {
+        &self.station_name
+    }
+
+    /// Returns the description of why the task did not complete.
+    pub fn detail(&self) -> &str {
+        &self.detail
+    }
+}
+
+impl fmt::Display for StationFnTaskError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Self {
+            station_id,
+            station_name,
+            detail,
+        } = self;
+        write!(
+            f,
+            "The task running station `{station_id}: {station_name}`'s function did not complete: {detail}."
+        )
+    }
+}
+
+impl std::error::Error for StationFnTaskError {}
+
+impl From<StationFnTaskError> for () {
+    fn from(_: StationFnTaskError) {}
+}
+
+// Work functions return the resource IDs produced so far with their error. As
+// the task did not complete, no resource IDs are known.
+impl<E> From<StationFnTaskError> for (ResIds, E)
+where
+    E: From<StationFnTaskError>,
+{
+    fn from(error: StationFnTaskError) -> Self {
+        (ResIds::new(), E::from(error))
+    }
+}
@@ -0,0 +1,115 @@
+use std::{
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+    path::PathBuf,
+    sync::{Mutex, PoisonError},
+};
+
+use crate::rt::{StationDir, StationRtId};
+
+/// Map from [`StationRtId`] to the digest of each station's input files.
+///
+/// Digests are computed when stations are set up, for stations that declare
+/// inputs through [`StationSpecBuilder::with_input`]. The digest is recorded in
+/// the station's directory after its work succeeds, and compared with the
+/// current digest when the station is checked.
+///
+/// Reading and writing the recorded digest is done by the train through the
+/// destination's file system, so this only holds the digests that were read
+/// or written.
+///
+/// [`StationSpecBuilder::with_input`]: crate::StationSpecBuilder::with_input
+#[derive(Debug, Default)]
+pub struct StationInputsDigests {
+    /// Digest of each station's current inputs.
+    digests: HashMap<StationRtId, String>,
+    /// Digest of the inputs used by each station's last successful work.
+    digests_recorded: Mutex<HashMap<StationRtId, String>>,
+}
+
+impl StationInputsDigests {
+    /// Name of the file in the station's directory that records the digest of
+    /// the inputs used by the last successful work.
+    pub const DIGEST_FILE_NAME: &'static str = ".choochoo_inputs_digest";
+
+    /// Returns an empty `StationInputsDigests` map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty `StationInputsDigests` map with the specified
+    /// capacity.
+    ///
+    /// The map will be able to hold at least `capacity` elements without
+    /// reallocating. If `capacity` is 0, the map will not allocate.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            digests: HashMap::with_capacity(capacity),
+            digests_recorded: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the path of the file that records the digest of the inputs
+    /// used by the station's last successful work.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_dir`: Directory of the station.
+    pub fn digest_path(station_dir: &StationDir) -> PathBuf {
+        station_dir.join(Self::DIGEST_FILE_NAME)
+    }
+
+    /// Returns whether the station's inputs differ from the inputs used by its
+    /// last successful work.
+    ///
+    /// Returns `None` if the station has no inputs digest. If no digest has
+    /// been recorded, or it could not be read, the inputs are treated as
+    /// changed.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_rt_id`: Runtime ID of the station.
+    pub fn changed(&self, station_rt_id: StationRtId) -> Option<bool> {
+        self.digests.get(&station_rt_id).map(|digest| {
+            let digests_recorded = self
+                .digests_recorded
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            digests_recorded
+                .get(&station_rt_id)
+                .map(|digest_recorded| digest_recorded.trim() != digest)
+                .unwrap_or(true)
+        })
+    }
+
+    /// Sets the digest of the inputs used by the station's last successful
+    /// work.
+    ///
+    /// This is called after the digest is read from or written to the
+    /// station's directory.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_rt_id`: Runtime ID of the station.
+    /// * `digest_recorded`: Digest recorded in the station's directory.
+    pub fn recorded_insert(&self, station_rt_id: StationRtId, digest_recorded: String) {
+        self.digests_recorded
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(station_rt_id, digest_recorded);
+    }
+}
+
+impl Deref for StationInputsDigests {
+    type Target = HashMap<StationRtId, String>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.digests
+    }
+}
+
+impl DerefMut for StationInputsDigests {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.digests
+    }
+}
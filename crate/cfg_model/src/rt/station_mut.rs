@@ -1,7 +1,12 @@
-use rt_map::RefMut;
+use std::path::PathBuf;
+
+use tokio::sync::RwLockWriteGuard;
 
 use crate::{
-    rt::{ProgressLimit, StationDir, StationProgress, StationRtId, TrainResources},
+    rt::{
+        ProgressLimit, StationDir, StationProgress, StationResourcesMut, StationRtId, StationState,
+        TrainResources,
+    },
     StationSpec,
 };
 
@@ -18,27 +23,64 @@ pub struct StationMut<'s, E> {
     /// Directory to hold data specific to each station.
     pub dir: &'s StationDir,
     /// Station progress to reaching the destination.
-    pub progress: RefMut<'s, StationProgress>,
+    pub progress: RwLockWriteGuard<'s, StationProgress>,
+    /// State that the station's operation carries across its functions.
+    pub state: RwLockWriteGuard<'s, StationState>,
 }
 
 impl<'s, E> StationMut<'s, E> {
+    /// Publishes a file for other stations to read.
+    ///
+    /// Publishing a file in the setup function makes it available to other
+    /// stations even when this station's work is unnecessary. See
+    /// [`StationMutRef::publish_file`] for how the path is resolved.
+    ///
+    /// # Parameters
+    ///
+    /// * `name`: Name to publish the file under.
+    /// * `path`: Path to the file, relative to this station's directory.
+    ///
+    /// [`StationMutRef::publish_file`]: crate::rt::StationMutRef::publish_file
+    pub fn publish_file<S, P>(&mut self, name: S, path: P)
+    where
+        S: Into<String>,
+        P: Into<PathBuf>,
+    {
+        let path = self.dir.join(path.into());
+        self.progress.file_publish(name.into(), path);
+    }
+
     /// Verifies input, calculates progress limit, and inserts resources.
+    ///
+    /// The setup function may only borrow resources that the station declares.
     pub async fn create_setup(
         &mut self,
         train_resources: &mut TrainResources<E>,
-    ) -> Result<ProgressLimit, E> {
-        let setup_fn = self.spec.station_op.create_fns().setup_fn.clone();
-        setup_fn.0(self, train_resources).await
+    ) -> Result<ProgressLimit, E>
+    where
+        E: 'static,
+    {
+        let spec = self.spec;
+        let setup_fn = spec.station_op.create_fns().setup_fn.clone();
+        let mut station_resources = StationResourcesMut::new(spec, self.rt_id, train_resources);
+        setup_fn.0(self, &mut station_resources).await
     }
 
     /// Verifies input and inserts resources.
+    ///
+    /// The setup function may only borrow resources that the station declares.
     pub async fn clean_setup(
         &mut self,
         train_resources: &mut TrainResources<E>,
-    ) -> Option<Result<ProgressLimit, E>> {
-        if let Some(clean_fns) = self.spec.station_op.clean_fns().as_ref() {
+    ) -> Option<Result<ProgressLimit, E>>
+    where
+        E: 'static,
+    {
+        let spec = self.spec;
+        if let Some(clean_fns) = spec.station_op.clean_fns().as_ref() {
             let setup_fn = clean_fns.setup_fn.clone();
-            Some(setup_fn.0(self, train_resources).await)
+            let mut station_resources = StationResourcesMut::new(spec, self.rt_id, train_resources);
+            Some(setup_fn.0(self, &mut station_resources).await)
         } else {
             None
         }
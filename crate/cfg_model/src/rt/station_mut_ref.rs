@@ -1,9 +1,15 @@
+use std::path::{Path, PathBuf};
+
 use fn_graph::FnRef;
-use rt_map::{BorrowFail, RefMut};
+use rt_map::BorrowFail;
+use tokio::sync::RwLockWriteGuard;
 
 use crate::{
-    rt::{CheckStatus, ResIds, StationDir, StationProgress, StationRtId, TrainResources},
-    StationSpec,
+    rt::{
+        CheckStatus, ResIds, StationArtifact, StationDir, StationInputsDigests, StationProgress,
+        StationRtId, StationState, TrainResources,
+    },
+    StationId, StationSpec,
 };
 
 /// Station runtime information.
@@ -22,27 +28,96 @@ pub struct StationMutRef<'s, E> {
     /// Directory to hold data specific to each station.
     pub dir: &'s StationDir,
     /// Station progress to reaching the destination.
-    pub progress: RefMut<'s, StationProgress>,
+    pub progress: RwLockWriteGuard<'s, StationProgress>,
+    /// State that the station's operation carries across its functions.
+    pub state: RwLockWriteGuard<'s, StationState>,
 }
 
 impl<'s, E> StationMutRef<'s, E>
 where
     E: 'static,
 {
+    /// Registers an output of this station, such as a build output, to be
+    /// collected into the profile history directory after the train's drive.
+    ///
+    /// # Parameters
+    ///
+    /// * `path`: Path to the artifact file or directory.
+    /// * `label`: Human readable label of the artifact.
+    pub fn artifact_register<P, L>(&mut self, path: P, label: L)
+    where
+        P: Into<PathBuf>,
+        L: Into<String>,
+    {
+        self.progress
+            .artifact_register(StationArtifact::new(path, label));
+    }
+
+    /// Publishes a file for other stations to read.
+    ///
+    /// Stations that declare the file as an input with
+    /// [`StationSpecBuilder::with_file_input`] read its path with
+    /// [`file_input`]. Relative paths are resolved against this station's
+    /// directory, and absolute paths are used as is.
+    ///
+    /// # Parameters
+    ///
+    /// * `name`: Name to publish the file under.
+    /// * `path`: Path to the file, relative to this station's directory.
+    ///
+    /// [`StationSpecBuilder::with_file_input`]: crate::StationSpecBuilder::with_file_input
+    /// [`file_input`]: Self::file_input
+    pub fn publish_file<S, P>(&mut self, name: S, path: P)
+    where
+        S: Into<String>,
+        P: Into<PathBuf>,
+    {
+        let path = self.dir.join(path.into());
+        self.progress.file_publish(name.into(), path);
+    }
+
+    /// Returns the path of a file published by another station, if this
+    /// station declares it as an input.
+    ///
+    /// Declared inputs are resolved before the station's create check, so
+    /// this returns `Some` for each declared input within the check, work,
+    /// and verify functions.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_id`: Unique identifier of the station that published the
+    ///   file.
+    /// * `name`: Name the file is published under.
+    pub fn file_input(&self, station_id: &StationId, name: &str) -> Option<&Path> {
+        self.progress.file_input(station_id, name)
+    }
+
     /// Checks if the create function needs to be run.
+    ///
+    /// If the station declares inputs, and they have changed since its work
+    /// last succeeded, the work is required without running the check
+    /// function. If they have not changed and the station has no check
+    /// function, the work is not required.
     pub async fn create_check<'f>(
         &'f mut self,
         train_resources: &'f TrainResources<E>,
     ) -> Option<Result<Result<CheckStatus, E>, BorrowFail>> {
+        let inputs_changed = train_resources
+            .try_borrow::<StationInputsDigests>()
+            .ok()
+            .and_then(|station_inputs_digests| station_inputs_digests.changed(self.rt_id));
         let check_fn = self.spec.station_op.create_fns().check_fn.clone();
-        if let Some(check_fn) = check_fn {
-            let call = check_fn.f.try_call(self, train_resources);
-            match call {
-                Ok(fut) => Some(Ok(fut.await)),
-                Err(e) => Some(Err(e)),
+        match (inputs_changed, check_fn) {
+            (Some(true), _) => Some(Ok(Ok(CheckStatus::WorkRequired))),
+            (_, Some(check_fn)) => {
+                let call = check_fn.f.try_call(self, train_resources);
+                match call {
+                    Ok(fut) => Some(Ok(fut.await)),
+                    Err(e) => Some(Err(e)),
+                }
             }
-        } else {
-            None
+            (Some(false), None) => Some(Ok(Ok(CheckStatus::WorkNotRequired))),
+            (None, None) => None,
         }
     }
 
@@ -59,6 +134,45 @@ where
         }
     }
 
+    /// Runs the create verify function, if the station has one.
+    pub async fn create_verify<'f>(
+        &'f mut self,
+        train_resources: &'f TrainResources<E>,
+    ) -> Option<Result<Result<(), E>, BorrowFail>> {
+        let verify_fn = self.spec.station_op.create_fns().verify_fn.clone();
+        if let Some(verify_fn) = verify_fn {
+            let call = verify_fn.f.try_call(self, train_resources);
+            let result = match call {
+                Ok(fut) => Ok(fut.await),
+                Err(e) => Err(e),
+            };
+            Some(result)
+        } else {
+            None
+        }
+    }
+
+    /// Runs the create output function, if the station has one.
+    ///
+    /// This provides the output of the station's work function when the work
+    /// is not required.
+    pub async fn create_output<'f>(
+        &'f mut self,
+        train_resources: &'f TrainResources<E>,
+    ) -> Option<Result<Result<(), E>, BorrowFail>> {
+        let output_fn = self.spec.station_op.create_fns().output_fn.clone();
+        if let Some(output_fn) = output_fn {
+            let call = output_fn.f.try_call(self, train_resources);
+            let result = match call {
+                Ok(fut) => Ok(fut.await),
+                Err(e) => Err(e),
+            };
+            Some(result)
+        } else {
+            None
+        }
+    }
+
     /// Checks if the create function needs to be run.
     ///
     /// Layers:
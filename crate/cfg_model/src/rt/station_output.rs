@@ -0,0 +1,60 @@
+use std::{
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+
+use tokio::sync::RwLock;
+
+// **Note:** `Clone` is manually implemented to avoid the trait bound on `O`.
+/// Output value produced by a station's create work function.
+///
+/// Stations whose [`CreateFns`] are constructed with
+/// [`CreateFns::new_with_output`] store their work function's output in this
+/// resource, which downstream stations may access by taking
+/// `&StationOutput<O>` as a function parameter.
+///
+/// The value is `None` until the producing station's work function succeeds.
+/// When the producing station's check function returns
+/// [`CheckStatus::WorkNotRequired`], the work function is not run, and the
+/// value is provided by the station's output function instead.
+///
+/// Each output type should be produced by at most one station.
+///
+/// [`CheckStatus::WorkNotRequired`]: crate::rt::CheckStatus::WorkNotRequired
+/// [`CreateFns`]: crate::CreateFns
+/// [`CreateFns::new_with_output`]: crate::OpFns::new_with_output
+#[derive(Debug)]
+pub struct StationOutput<O>(Arc<RwLock<Option<O>>>);
+
+impl<O> StationOutput<O> {
+    /// Returns a new empty [`StationOutput`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<O> Clone for StationOutput<O> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<O> Default for StationOutput<O> {
+    fn default() -> Self {
+        Self(Arc::new(RwLock::new(None)))
+    }
+}
+
+impl<O> Deref for StationOutput<O> {
+    type Target = Arc<RwLock<Option<O>>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<O> DerefMut for StationOutput<O> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
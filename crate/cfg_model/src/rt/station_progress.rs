@@ -1,11 +1,16 @@
-use std::fmt;
+use std::{
+    borrow::Cow,
+    fmt,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
 
-use console::Style;
+use indexmap::IndexMap;
 use indicatif::{ProgressBar, ProgressStyle};
 
 use crate::{
-    rt::{OpStatus, ProgressLimit},
-    StationSpec,
+    rt::{OpStatus, ProgressLimit, ProgressTheme, StationArtifact, SubStep, SubStepStatus},
+    FileInput, StationId, StationSpec,
 };
 
 /// Station progress to reaching the destination.
@@ -20,14 +25,44 @@ pub struct StationProgress {
     progress_bar: ProgressBar,
     /// Unit of measurement and limit to indicate progress.
     progress_limit: ProgressLimit,
+    /// Appearance of the progress bar.
+    progress_theme: ProgressTheme,
+    /// Named phases of the station's work.
+    sub_steps: Vec<SubStep>,
+    /// Progress bar message without the transient message.
+    message_base: String,
+    /// Transient message that describes the station's current activity.
+    message: Option<String>,
+    /// When the transient message was last rendered.
+    message_rendered_at: Option<Instant>,
+    /// Estimated time the station's work takes.
+    estimated_duration: Option<Duration>,
+    /// Outputs of the station to collect after the train's drive.
+    artifacts: Vec<StationArtifact>,
+    /// Files published by the station for other stations to read, by name.
+    files_published: IndexMap<String, PathBuf>,
+    /// Paths of the files published by other stations that the station reads.
+    file_inputs: IndexMap<FileInput, PathBuf>,
 }
 
 impl StationProgress {
+    /// Minimum interval in milliseconds between renders of the transient
+    /// message.
+    pub const MESSAGE_THROTTLE_MS: u64 = 100;
     /// Characters to use for the progress bar to have fine grained animation.
-    pub const PROGRESS_CHARS: &'static str = "█▉▊▋▌▍▎▏  ";
+    pub const PROGRESS_CHARS: &'static str = ProgressTheme::PROGRESS_CHARS;
+    /// Interval in milliseconds between spinner ticks when the progress limit
+    /// is unknown.
+    pub const SPINNER_TICK_MS: u64 = 100;
+    /// Number of progress bar units each sub-step contributes to the station's
+    /// progress bar.
+    pub const SUB_STEP_RESOLUTION: u64 = 1000;
 
     /// Returns a new [`StationProgress`].
     ///
+    /// The progress bar uses the station's [`ProgressTheme`] if it has one,
+    /// otherwise the default theme.
+    ///
     /// # Parameters
     ///
     /// * `station_spec`: Behaviour specification of the station.
@@ -39,22 +74,29 @@ impl StationProgress {
         let op_status = OpStatus::SetupQueued;
         let progress_bar = ProgressBar::hidden();
 
-        let message = {
-            let id_style = Style::new().blue().bold();
-            let name_style = Style::new().bold().bright();
+        let progress_theme = station_spec.progress_theme().cloned().unwrap_or_default();
+        let message_base = progress_theme.message(station_spec.id(), station_spec.name());
+        progress_bar.set_message(message_base.clone());
 
-            format!(
-                "{id} {name}",
-                id = id_style.apply_to(station_spec.id()),
-                name = name_style.apply_to(station_spec.name())
-            )
-        };
-        progress_bar.set_message(message);
+        let sub_steps = station_spec
+            .sub_steps()
+            .iter()
+            .map(SubStep::new)
+            .collect::<Vec<_>>();
 
         let station_progress = Self {
             op_status,
             progress_bar,
             progress_limit,
+            progress_theme,
+            sub_steps,
+            message_base,
+            message: None,
+            message_rendered_at: None,
+            estimated_duration: station_spec.estimated_duration(),
+            artifacts: Vec::new(),
+            files_published: IndexMap::new(),
+            file_inputs: IndexMap::new(),
         };
 
         station_progress.progress_style_update();
@@ -80,29 +122,231 @@ impl StationProgress {
         }
     }
 
+    /// Returns the unit of measurement and limit to indicate progress.
+    pub fn progress_limit(&self) -> ProgressLimit {
+        self.progress_limit
+    }
+
     /// Updates the progress limit.
+    ///
+    /// This may be called while the station's work is in progress, such as when
+    /// the size of a download is only known after the response is received.
+    /// The progress bar's position is retained.
     pub fn progress_limit_set(&mut self, progress_limit: ProgressLimit) {
         self.progress_limit = progress_limit;
         self.progress_style_update();
     }
 
+    /// Returns the outputs of the station to collect after the train's drive.
+    pub fn artifacts(&self) -> &[StationArtifact] {
+        &self.artifacts
+    }
+
+    /// Registers an output of the station to collect after the train's drive.
+    pub fn artifact_register(&mut self, artifact: StationArtifact) {
+        self.artifacts.push(artifact);
+    }
+
+    /// Clears the outputs registered by the station.
+    pub fn artifacts_clear(&mut self) {
+        self.artifacts.clear();
+    }
+
+    /// Returns the files published by the station for other stations to read,
+    /// by name.
+    pub fn files_published(&self) -> &IndexMap<String, PathBuf> {
+        &self.files_published
+    }
+
+    /// Publishes a file for other stations to read, replacing any file
+    /// previously published under the same name.
+    pub fn file_publish(&mut self, name: String, path: PathBuf) {
+        self.files_published.insert(name, path);
+    }
+
+    /// Returns the path of a file published by another station that the
+    /// station reads, if it has been resolved.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_id`: Unique identifier of the station that published the
+    ///   file.
+    /// * `name`: Name the file is published under.
+    pub fn file_input(&self, station_id: &StationId, name: &str) -> Option<&Path> {
+        self.file_inputs
+            .iter()
+            .find(|(file_input, _path)| {
+                file_input.station_id() == station_id && file_input.name() == name
+            })
+            .map(|(_file_input, path)| path.as_path())
+    }
+
+    /// Sets the path of a file published by another station that the station
+    /// reads.
+    pub fn file_input_set(&mut self, file_input: FileInput, path: PathBuf) {
+        self.file_inputs.insert(file_input, path);
+    }
+
+    /// Returns the named phases of the station's work.
+    pub fn sub_steps(&self) -> &[SubStep] {
+        &self.sub_steps
+    }
+
+    /// Marks a sub-step as in progress.
+    ///
+    /// If the sub-step was not declared on the station spec, it is appended to
+    /// the station's sub-steps.
+    ///
+    /// # Parameters
+    ///
+    /// * `name`: Name of the sub-step.
+    /// * `progress_limit`: Unit of measurement and limit to indicate the
+    ///   sub-step's progress.
+    pub fn sub_step_begin(&mut self, name: &str, progress_limit: ProgressLimit) {
+        self.sub_step_mut(name).begin(progress_limit);
+        self.progress_style_update();
+    }
+
+    /// Advances a sub-step's progress.
+    ///
+    /// # Parameters
+    ///
+    /// * `name`: Name of the sub-step.
+    /// * `delta`: Amount of progress made.
+    pub fn sub_step_inc(&mut self, name: &str, delta: u64) {
+        self.sub_step_mut(name).inc(delta);
+        self.progress_bar.set_position(self.sub_steps_position());
+    }
+
+    /// Marks a sub-step as successfully completed.
+    ///
+    /// # Parameters
+    ///
+    /// * `name`: Name of the sub-step.
+    pub fn sub_step_complete(&mut self, name: &str) {
+        self.sub_step_mut(name).complete();
+        self.progress_style_update();
+    }
+
+    /// Sets a transient message that describes the station's current
+    /// activity, e.g. `"uploading chunk 3/10"`.
+    ///
+    /// The message is rendered next to the progress bar while the station's
+    /// work is in progress. To avoid overwhelming the renderer when this is
+    /// called frequently, the message is rendered at most once every
+    /// [`MESSAGE_THROTTLE_MS`] milliseconds. Messages set in between are
+    /// recorded, and the latest one is rendered with the next update.
+    ///
+    /// [`MESSAGE_THROTTLE_MS`]: Self::MESSAGE_THROTTLE_MS
+    pub fn message<S>(&mut self, message: S)
+    where
+        S: Into<String>,
+    {
+        self.message = Some(message.into());
+
+        let now = Instant::now();
+        let render_due = self
+            .message_rendered_at
+            .map(|message_rendered_at| {
+                now.saturating_duration_since(message_rendered_at)
+                    >= Duration::from_millis(Self::MESSAGE_THROTTLE_MS)
+            })
+            .unwrap_or(true);
+        if render_due {
+            self.message_rendered_at = Some(now);
+            self.progress_bar.set_message(self.message_display());
+        }
+    }
+
+    /// Returns the latest transient message, if any.
+    ///
+    /// This is the latest message set, even if it has not been rendered.
+    pub fn message_current(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    /// Clears the transient message.
+    pub fn message_clear(&mut self) {
+        self.message = None;
+        self.message_rendered_at = None;
+        self.progress_bar.set_message(self.message_base.clone());
+    }
+
+    /// Returns the estimated time the station's work takes, if known.
+    pub fn estimated_duration(&self) -> Option<Duration> {
+        self.estimated_duration
+    }
+
+    /// Updates the estimated time the station's work takes.
+    ///
+    /// This defaults to the station spec's estimated duration, and may be
+    /// updated with durations recorded by previous visits.
+    pub fn estimated_duration_set(&mut self, estimated_duration: Option<Duration>) {
+        self.estimated_duration = estimated_duration;
+        self.progress_style_update();
+    }
+
+    /// Returns the appearance of the progress bar.
+    pub fn progress_theme(&self) -> &ProgressTheme {
+        &self.progress_theme
+    }
+
+    /// Updates the appearance of the progress bar.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_spec`: Behaviour specification of the station.
+    /// * `progress_theme`: Appearance of the progress bar.
+    pub fn progress_theme_set<E>(
+        &mut self,
+        station_spec: &StationSpec<E>,
+        progress_theme: ProgressTheme,
+    ) where
+        E: 'static,
+    {
+        self.message_base = progress_theme.message(station_spec.id(), station_spec.name());
+        self.progress_bar.set_message(self.message_display());
+        self.progress_theme = progress_theme;
+        self.progress_style_update();
+    }
+
     /// Updates the style of the progress bar.
     pub fn progress_style_update(&self) {
-        let progress_length = match self.progress_limit {
-            ProgressLimit::Unknown => 0, // indicatif uses `0` for spinner type progress bars.
-            ProgressLimit::Steps(n) | ProgressLimit::Bytes(n) => n,
+        let progress_length = if self.sub_steps.is_empty() {
+            match self.progress_limit {
+                ProgressLimit::Unknown => 0, // indicatif uses `0` for spinner type progress bars.
+                ProgressLimit::Steps(n) | ProgressLimit::Bytes(n) | ProgressLimit::Items(n) => n,
+            }
+        } else {
+            self.sub_steps.len() as u64 * Self::SUB_STEP_RESOLUTION
         };
 
-        let progress_style_template =
-            Self::progress_style_template(self.op_status, self.progress_limit);
+        let progress_style_template = Self::progress_style_template(
+            &self.progress_theme,
+            self.op_status,
+            progress_length,
+            &self.units(),
+            self.estimated_duration,
+        );
         self.progress_bar.set_length(progress_length);
+        self.progress_bar.set_message(self.message_display());
+        if !self.sub_steps.is_empty() {
+            self.progress_bar.set_position(self.sub_steps_position());
+        }
 
         self.progress_bar.set_style(
             ProgressStyle::default_bar()
                 .template(progress_style_template.as_str())
-                .progress_chars(StationProgress::PROGRESS_CHARS),
+                .progress_chars(self.progress_theme.progress_chars()),
         );
 
+        // Animate the spinner while work of unknown size is in progress.
+        if self.op_status == OpStatus::WorkInProgress && progress_length == 0 {
+            self.progress_bar.enable_steady_tick(Self::SPINNER_TICK_MS);
+        } else {
+            self.progress_bar.disable_steady_tick();
+        }
+
         // Finish the progress bar if our progress is complete.
         match self.op_status {
             OpStatus::SetupQueued
@@ -112,8 +356,13 @@ impl StationProgress {
             | OpStatus::WorkInProgress => {}
             OpStatus::SetupFail
             | OpStatus::ParentFail
+            | OpStatus::OpSkipped
             | OpStatus::CheckFail
-            | OpStatus::WorkFail => {
+            | OpStatus::WorkFail
+            | OpStatus::VerifyFail
+            | OpStatus::Panicked
+            | OpStatus::WorkInterrupted
+            | OpStatus::DeadlineExceeded => {
                 self.progress_bar.abandon();
             }
             OpStatus::WorkSuccess | OpStatus::WorkUnnecessary => {
@@ -125,7 +374,76 @@ impl StationProgress {
         self.progress_bar.tick();
     }
 
-    fn progress_style_template(op_status: OpStatus, progress_limit: ProgressLimit) -> String {
+    /// Returns the progress bar message, including the transient message while
+    /// the station's work is in progress.
+    fn message_display(&self) -> String {
+        match (self.op_status, self.message.as_deref()) {
+            (OpStatus::WorkInProgress, Some(message)) => {
+                format!("{} {}", self.message_base, message)
+            }
+            _ => self.message_base.clone(),
+        }
+    }
+
+    /// Returns the sub-step with the given name, appending it if it does not
+    /// exist.
+    fn sub_step_mut(&mut self, name: &str) -> &mut SubStep {
+        let index = self
+            .sub_steps
+            .iter()
+            .position(|sub_step| sub_step.name() == name)
+            .unwrap_or_else(|| {
+                self.sub_steps.push(SubStep::new(name));
+                self.sub_steps.len() - 1
+            });
+
+        &mut self.sub_steps[index]
+    }
+
+    /// Returns the progress bar position aggregated from all sub-steps.
+    fn sub_steps_position(&self) -> u64 {
+        self.sub_steps
+            .iter()
+            .map(|sub_step| (sub_step.fraction() * Self::SUB_STEP_RESOLUTION as f64) as u64)
+            .sum()
+    }
+
+    /// Returns the progress units to display.
+    ///
+    /// When the station has sub-steps, this is the name and index of the
+    /// current sub-step.
+    fn units(&self) -> Cow<'static, str> {
+        if self.sub_steps.is_empty() {
+            let units = match self.progress_limit {
+                ProgressLimit::Unknown => "",
+                ProgressLimit::Steps(_) => "{pos}/{len}",
+                ProgressLimit::Bytes(_) => "{bytes}/{total_bytes}",
+                ProgressLimit::Items(_) => "{pos}/{len} items",
+            };
+            Cow::Borrowed(units)
+        } else {
+            let sub_step_count = self.sub_steps.len();
+            let sub_step_index = self
+                .sub_steps
+                .iter()
+                .position(|sub_step| sub_step.status() != SubStepStatus::Success)
+                .unwrap_or(sub_step_count - 1);
+            let sub_step_name = self.sub_steps[sub_step_index].name();
+            let sub_step_number = sub_step_index + 1;
+
+            Cow::Owned(format!(
+                "{sub_step_name} ({sub_step_number}/{sub_step_count})"
+            ))
+        }
+    }
+
+    fn progress_style_template(
+        progress_theme: &ProgressTheme,
+        op_status: OpStatus,
+        progress_length: u64,
+        units: &str,
+        estimated_duration: Option<Duration>,
+    ) -> String {
         let (symbol, status) = match op_status {
             OpStatus::SetupQueued => ("⏳", "setup queued"),
             OpStatus::SetupSuccess => ("⏳", "setup success"),
@@ -133,46 +451,70 @@ impl StationProgress {
             OpStatus::ParentPending => ("⏰", "parent pending"),
             OpStatus::ParentFail => ("☠️ ", "parent fail"), // Extra space is deliberate
             OpStatus::OpQueued => ("⏳", "visit queued"),
+            OpStatus::OpSkipped => ("⏭️ ", "visit skipped"), // Extra space is deliberate
             OpStatus::CheckFail => ("❌", "check fail"),
             OpStatus::WorkInProgress => ("{spinner:.green}{spinner:.green}", "in progress"),
             OpStatus::WorkUnnecessary => ("✅", "visit unnecessary"),
             OpStatus::WorkSuccess => ("✅", "visit success"),
             OpStatus::WorkFail => ("❌", "visit fail"),
+            OpStatus::VerifyFail => ("❌", "verify fail"),
+            OpStatus::Panicked => ("💥", "panicked"),
+            OpStatus::WorkInterrupted => ("⛔", "visit interrupted"),
+            OpStatus::DeadlineExceeded => ("⌛", "deadline exceeded"),
         };
 
+        let bar_width = progress_theme.bar_width();
+        let bar_placeholder = "▒".repeat(bar_width);
         let progress_bar = match op_status {
-            OpStatus::SetupQueued => console::style("▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒")
-                .black()
-                .dim(),
-            OpStatus::SetupSuccess => console::style("▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒")
-                .blue()
-                .dim(),
-            OpStatus::SetupFail => console::style("▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒")
-                .magenta()
-                .dim(),
-            OpStatus::ParentPending => console::style("▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒")
-                .blue()
-                .dim(),
-            OpStatus::ParentFail => console::style("▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒")
-                .black()
-                .dim(),
-            OpStatus::OpQueued => console::style("▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒")
-                .blue()
-                .dim(),
-            OpStatus::CheckFail => console::style("▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒").red(),
-            OpStatus::WorkInProgress => console::style("{bar:40.green.on_17}"),
-            OpStatus::WorkUnnecessary => console::style("{bar:40.green.dim}"),
-            OpStatus::WorkSuccess => console::style("{bar:40.green}"),
-            OpStatus::WorkFail => console::style("{bar:40.red.dim}"),
+            OpStatus::SetupQueued => console::style(bar_placeholder).black().dim(),
+            OpStatus::SetupSuccess => console::style(bar_placeholder).blue().dim(),
+            OpStatus::SetupFail => console::style(bar_placeholder).magenta().dim(),
+            OpStatus::ParentPending => console::style(bar_placeholder).blue().dim(),
+            OpStatus::ParentFail => console::style(bar_placeholder).black().dim(),
+            OpStatus::OpQueued => console::style(bar_placeholder).blue().dim(),
+            OpStatus::OpSkipped => console::style(bar_placeholder).black().dim(),
+            OpStatus::CheckFail => console::style(bar_placeholder).red(),
+            // indicatif renders a full bar when the length is `0`, so we render a placeholder
+            // and rely on the spinner to show activity.
+            OpStatus::WorkInProgress if progress_length == 0 => {
+                console::style(bar_placeholder).green().dim()
+            }
+            OpStatus::WorkInProgress => console::style(format!("{{bar:{bar_width}.green.on_17}}")),
+            OpStatus::WorkUnnecessary => console::style(format!("{{bar:{bar_width}.green.dim}}")),
+            OpStatus::WorkSuccess => console::style(format!("{{bar:{bar_width}.green}}")),
+            OpStatus::WorkFail => console::style(format!("{{bar:{bar_width}.red.dim}}")),
+            OpStatus::VerifyFail => console::style(format!("{{bar:{bar_width}.red}}")),
+            OpStatus::Panicked => console::style(format!("{{bar:{bar_width}.red}}")),
+            OpStatus::WorkInterrupted => console::style(format!("{{bar:{bar_width}.red.dim}}")),
+            OpStatus::DeadlineExceeded => console::style(format!("{{bar:{bar_width}.yellow.dim}}")),
         };
 
-        let units = match progress_limit {
-            ProgressLimit::Unknown => "",
-            ProgressLimit::Steps(_) => "{pos}/{len}",
-            ProgressLimit::Bytes(_) => "{bytes}/{total_bytes}",
-        };
+        progress_theme
+            .template()
+            .replace("{op_symbol}", symbol)
+            .replace("{op_bar}", &progress_bar.to_string())
+            .replace("{op_units}", units)
+            .replace("{op_status}", status)
+            .replace(
+                "{op_eta}",
+                &Self::eta_display(op_status, estimated_duration),
+            )
+    }
 
-        format!("{symbol} {{msg:20}} [{progress_bar}] {units} ({status})")
+    /// Returns the estimated duration to display while the station is queued
+    /// or in progress.
+    fn eta_display(op_status: OpStatus, estimated_duration: Option<Duration>) -> String {
+        match (op_status, estimated_duration) {
+            (
+                OpStatus::SetupQueued
+                | OpStatus::SetupSuccess
+                | OpStatus::ParentPending
+                | OpStatus::OpQueued
+                | OpStatus::WorkInProgress,
+                Some(estimated_duration),
+            ) => format!(", eta ~{}s", estimated_duration.as_secs().max(1)),
+            _ => String::new(),
+        }
     }
 }
 
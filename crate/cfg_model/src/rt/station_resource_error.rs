@@ -0,0 +1,52 @@
+use std::fmt;
+
+use resman::BorrowFail;
+
+use crate::rt::ResourceUndeclared;
+
+/// Error when a station's setup, hook, or migration function borrows a
+/// resource.
+#[derive(Clone, Debug, PartialEq)]
+pub enum StationResourceError {
+    /// The station did not declare the resource.
+    ResourceUndeclared(ResourceUndeclared),
+    /// The resource does not exist, or is already borrowed in a conflicting
+    /// way.
+    BorrowFail {
+        /// Type name of the resource.
+        type_name: &'static str,
+        /// Why the resource could not be borrowed.
+        borrow_fail: BorrowFail,
+    },
+}
+
+impl fmt::Display for StationResourceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ResourceUndeclared(resource_undeclared) => write!(f, "{resource_undeclared}"),
+            Self::BorrowFail {
+                type_name,
+                borrow_fail,
+            } => write!(f, "Failed to borrow `{type_name}`: {borrow_fail:?}"),
+        }
+    }
+}
+
+impl std::error::Error for StationResourceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::ResourceUndeclared(resource_undeclared) => Some(resource_undeclared),
+            Self::BorrowFail { .. } => None,
+        }
+    }
+}
+
+impl From<ResourceUndeclared> for StationResourceError {
+    fn from(resource_undeclared: ResourceUndeclared) -> Self {
+        Self::ResourceUndeclared(resource_undeclared)
+    }
+}
+
+impl From<StationResourceError> for () {
+    fn from(_: StationResourceError) {}
+}
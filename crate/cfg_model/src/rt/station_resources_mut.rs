@@ -0,0 +1,100 @@
+use std::any;
+
+use resman::Resource;
+
+use crate::{
+    rt::{
+        ResourcesDeclared, ScopedRef, ScopedRefMut, StationResourceError, StationRtId,
+        TrainResources,
+    },
+    StationSpec,
+};
+
+/// Resources that a station's setup functions may insert and borrow.
+///
+/// Setup functions may insert any resource, for the station's other functions
+/// to borrow. Like [`StationResources`], only resources declared as parameters
+/// of the station's check, work, or verify functions may be borrowed, and only
+/// those declared as `&mut` may be borrowed mutably.
+///
+/// [`StationResources`]: crate::rt::StationResources
+#[derive(Debug)]
+pub struct StationResourcesMut<'r, E> {
+    /// Runtime identifier of the station, used to borrow its scoped resources.
+    station_rt_id: StationRtId,
+    /// Resources of the train.
+    train_resources: &'r mut TrainResources<E>,
+    /// Resources the station declared to borrow.
+    resources_declared: ResourcesDeclared,
+}
+
+impl<'r, E> StationResourcesMut<'r, E>
+where
+    E: 'static,
+{
+    /// Returns the resources that the given station may insert and borrow.
+    ///
+    /// # Parameters
+    ///
+    /// * `spec`: Behaviour specification of the station.
+    /// * `station_rt_id`: Runtime identifier of the station.
+    /// * `train_resources`: Resources of the train.
+    pub fn new(
+        spec: &StationSpec<E>,
+        station_rt_id: StationRtId,
+        train_resources: &'r mut TrainResources<E>,
+    ) -> Self {
+        Self {
+            station_rt_id,
+            train_resources,
+            resources_declared: ResourcesDeclared::new(spec),
+        }
+    }
+
+    /// Inserts a resource into the train resources, replacing any existing
+    /// resource of the same type.
+    pub fn insert<R>(&mut self, resource: R)
+    where
+        R: Resource,
+    {
+        self.train_resources.insert(resource);
+    }
+
+    /// Returns an immutable reference to a resource the station declared.
+    ///
+    /// The resource is borrowed from the station's narrowest [`ResourceScope`]
+    /// that holds it. An error is returned if the resource does not exist, or
+    /// is already mutably borrowed.
+    ///
+    /// [`ResourceScope`]: crate::rt::ResourceScope
+    pub fn borrow<R>(&self) -> Result<ScopedRef<'_, R>, StationResourceError>
+    where
+        R: Resource,
+    {
+        self.resources_declared.borrow_check::<R>()?;
+        self.train_resources
+            .station_try_borrow::<R>(self.station_rt_id)
+            .map_err(|borrow_fail| StationResourceError::BorrowFail {
+                type_name: any::type_name::<R>(),
+                borrow_fail,
+            })
+    }
+
+    /// Returns a mutable reference to a resource the station declared as
+    /// `&mut`.
+    ///
+    /// An error is returned if the resource does not exist, or is already
+    /// borrowed.
+    pub fn borrow_mut<R>(&self) -> Result<ScopedRefMut<'_, R>, StationResourceError>
+    where
+        R: Resource,
+    {
+        self.resources_declared.borrow_mut_check::<R>()?;
+        self.train_resources
+            .station_try_borrow_mut::<R>(self.station_rt_id)
+            .map_err(|borrow_fail| StationResourceError::BorrowFail {
+                type_name: any::type_name::<R>(),
+                borrow_fail,
+            })
+    }
+}
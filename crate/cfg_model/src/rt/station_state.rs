@@ -0,0 +1,69 @@
+use std::any::Any;
+
+/// State that a station's operation carries across its functions within one
+/// run.
+///
+/// This allows a station to create an object once, such as a client connection
+/// pool in its setup function, and use it in its check, work, and clean
+/// functions. The state is cleared at the beginning of each run.
+#[derive(Debug, Default)]
+pub struct StationState(Option<Box<dyn Any + Send + Sync>>);
+
+impl StationState {
+    /// Returns an empty [`StationState`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the state, replacing any existing state.
+    pub fn insert<S>(&mut self, state: S)
+    where
+        S: Send + Sync + 'static,
+    {
+        self.0 = Some(Box::new(state));
+    }
+
+    /// Returns a reference to the state, if it is set and is of type `S`.
+    pub fn get<S>(&self) -> Option<&S>
+    where
+        S: Send + Sync + 'static,
+    {
+        self.0.as_ref().and_then(|state| state.downcast_ref::<S>())
+    }
+
+    /// Returns a mutable reference to the state, if it is set and is of type
+    /// `S`.
+    pub fn get_mut<S>(&mut self) -> Option<&mut S>
+    where
+        S: Send + Sync + 'static,
+    {
+        self.0.as_mut().and_then(|state| state.downcast_mut::<S>())
+    }
+
+    /// Removes and returns the state, if it is set and is of type `S`.
+    ///
+    /// If the state is of a different type, it is left in place.
+    pub fn remove<S>(&mut self) -> Option<S>
+    where
+        S: Send + Sync + 'static,
+    {
+        match self.0.take().map(|state| state.downcast::<S>()) {
+            Some(Ok(state)) => Some(*state),
+            Some(Err(state)) => {
+                self.0 = Some(state);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Returns whether the state is set.
+    pub fn is_set(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Clears the state.
+    pub fn clear(&mut self) {
+        self.0 = None;
+    }
+}
@@ -0,0 +1,42 @@
+use std::{path::PathBuf, str};
+
+use crate::rt::StationDir;
+
+/// Record of the spec version used by a station's last successful visit.
+///
+/// The version is recorded in the station's directory for stations whose spec
+/// has a version, and compared with the spec's version when the station is
+/// checked.
+///
+/// Reading and writing the record is done by the train through the
+/// destination's file system.
+#[derive(Clone, Copy, Debug)]
+pub struct StationVersionRecord;
+
+impl StationVersionRecord {
+    /// Name of the file in the station's directory that records the spec
+    /// version.
+    pub const VERSION_FILE_NAME: &'static str = ".choochoo_spec_version";
+
+    /// Returns the path of the file that records the spec version.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_dir`: Directory of the station.
+    pub fn version_path(station_dir: &StationDir) -> PathBuf {
+        station_dir.join(Self::VERSION_FILE_NAME)
+    }
+
+    /// Returns the spec version in the contents of a version record file.
+    ///
+    /// Returns `None` if the contents are not a version.
+    ///
+    /// # Parameters
+    ///
+    /// * `contents`: Contents of the version record file.
+    pub fn version_parse(contents: &[u8]) -> Option<u32> {
+        str::from_utf8(contents)
+            .ok()
+            .and_then(|version| version.trim().parse::<u32>().ok())
+    }
+}
@@ -0,0 +1,62 @@
+use std::{
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+
+use indexmap::IndexMap;
+use tokio::sync::RwLock;
+
+use crate::rt::StationRtId;
+
+/// Warnings recorded when visiting stations.
+///
+/// Unlike [`StationErrors`], warnings do not fail the station. Whether they
+/// fail the train is determined by the train's severity threshold.
+///
+/// Stations may record warnings by taking `&StationWarnings<E>` as a function
+/// parameter, and calling [`StationWarnings::insert`].
+///
+/// [`StationErrors`]: crate::rt::StationErrors
+#[derive(Clone, Debug)]
+pub struct StationWarnings<E>(Arc<RwLock<IndexMap<StationRtId, Vec<E>>>>);
+
+impl<E> StationWarnings<E> {
+    /// Returns new [`StationWarnings`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a warning for the given station.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_rt_id`: Runtime ID of the station the warning is for.
+    /// * `warning`: The warning to record.
+    pub async fn insert(&self, station_rt_id: StationRtId, warning: E) {
+        let mut station_warnings = self.0.write().await;
+        station_warnings
+            .entry(station_rt_id)
+            .or_insert_with(Vec::new)
+            .push(warning);
+    }
+}
+
+impl<E> Default for StationWarnings<E> {
+    fn default() -> Self {
+        Self(Arc::new(RwLock::new(IndexMap::new())))
+    }
+}
+
+impl<E> Deref for StationWarnings<E> {
+    type Target = Arc<RwLock<IndexMap<StationRtId, Vec<E>>>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<E> DerefMut for StationWarnings<E> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
@@ -0,0 +1,96 @@
+use crate::rt::{ProgressLimit, SubStepStatus};
+
+/// Named phase of a station's work, with its own progress.
+///
+/// Stations that wrap multi-phase work, such as "download", "extract", and
+/// "verify", may declare sub-steps with [`StationSpecBuilder::with_sub_step`].
+/// Sub-step progress is aggregated into the station's progress bar.
+///
+/// [`StationSpecBuilder::with_sub_step`]: crate::StationSpecBuilder::with_sub_step
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubStep {
+    /// Name of the sub-step.
+    name: String,
+    /// Status of the sub-step.
+    status: SubStepStatus,
+    /// Unit of measurement and limit to indicate progress.
+    progress_limit: ProgressLimit,
+    /// Amount of progress made.
+    position: u64,
+}
+
+impl SubStep {
+    /// Returns a new queued [`SubStep`].
+    ///
+    /// # Parameters
+    ///
+    /// * `name`: Name of the sub-step.
+    pub fn new<S>(name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            status: SubStepStatus::Queued,
+            progress_limit: ProgressLimit::Unknown,
+            position: 0,
+        }
+    }
+
+    /// Returns the name of the sub-step.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the status of the sub-step.
+    pub fn status(&self) -> SubStepStatus {
+        self.status
+    }
+
+    /// Returns the unit of measurement and limit to indicate progress.
+    pub fn progress_limit(&self) -> ProgressLimit {
+        self.progress_limit
+    }
+
+    /// Returns the amount of progress made.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns the fraction of this sub-step that is complete, between `0.0`
+    /// and `1.0`.
+    ///
+    /// Sub-steps with an unknown progress limit are `0.0` until they succeed.
+    pub fn fraction(&self) -> f64 {
+        match (self.status, self.progress_limit) {
+            (SubStepStatus::Success, _) => 1.0,
+            (_, ProgressLimit::Unknown) => 0.0,
+            (_, ProgressLimit::Steps(0) | ProgressLimit::Bytes(0) | ProgressLimit::Items(0)) => 0.0,
+            (_, ProgressLimit::Steps(n) | ProgressLimit::Bytes(n) | ProgressLimit::Items(n)) => {
+                (self.position as f64 / n as f64).min(1.0)
+            }
+        }
+    }
+
+    /// Marks the sub-step as in progress.
+    pub(crate) fn begin(&mut self, progress_limit: ProgressLimit) {
+        self.status = SubStepStatus::InProgress;
+        self.progress_limit = progress_limit;
+        self.position = 0;
+    }
+
+    /// Advances the sub-step's progress.
+    pub(crate) fn inc(&mut self, delta: u64) {
+        self.position = self.position.saturating_add(delta);
+    }
+
+    /// Marks the sub-step as successfully completed.
+    pub(crate) fn complete(&mut self) {
+        self.status = SubStepStatus::Success;
+        if let ProgressLimit::Steps(n) | ProgressLimit::Bytes(n) | ProgressLimit::Items(n) =
+            self.progress_limit
+        {
+            self.position = n;
+        }
+    }
+}
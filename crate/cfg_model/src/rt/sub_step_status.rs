@@ -0,0 +1,10 @@
+/// Status of a station's sub-step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SubStepStatus {
+    /// Sub-step has not begun.
+    Queued,
+    /// Sub-step is in progress.
+    InProgress,
+    /// Sub-step completed successfully.
+    Success,
+}
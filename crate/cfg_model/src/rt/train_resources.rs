@@ -1,17 +1,38 @@
 use std::{
     fmt,
-    marker::PhantomData,
     ops::{Deref, DerefMut},
 };
 
-use choochoo_resource::FilesRw;
-use resman::{Ref, Resources};
+#[cfg(feature = "mock")]
+use std::path::PathBuf;
 
-use crate::rt::StationErrors;
+#[cfg(feature = "mock")]
+use choochoo_resource::{
+    CacheDir, HistoryDir, Profile, ProfileDir, ProfileHistoryDir, WorkspaceDir,
+};
+use choochoo_resource::{FilesRw, SourceRegistry};
+use resman::{BorrowFail, Ref, Resource, Resources};
+
+#[cfg(feature = "mock")]
+use crate::rt::ResIdsPrevious;
+use crate::rt::{
+    resource_teardowns::ResourceTeardowns,
+    scoped_resources::{ScopedRef, ScopedRefMut, ScopedResources},
+    Effect, ResourceScope, ResourceTeardown, StationErrors, StationFiles, StationRtId,
+    StationWarnings,
+};
 
 /// Record of what happened during a train's drive.
+///
+/// Resources inserted through [`Resources::insert`] are in the
+/// [`ResourceScope::Global`] scope. Resources that are only used by one
+/// station may be inserted with [`TrainResources::insert_scoped`], so that
+/// they are dropped once the station no longer needs them.
+///
+/// Resources that need to be shut down at the end of the drive may be
+/// inserted with [`TrainResources::insert_teardown`].
 #[derive(Debug)]
-pub struct TrainResources<E>(Resources, PhantomData<E>);
+pub struct TrainResources<E>(Resources, ScopedResources, ResourceTeardowns<E>);
 
 impl<E> TrainResources<E>
 where
@@ -24,14 +45,194 @@ where
 
     /// Returns a reference to [`StationErrors`].
     ///
-    /// Note that [`StationErrors`] is behind a [`RwLock`], and you can choose
+    /// Errors may be recorded with [`StationErrors::insert`], and read with
+    /// [`StationErrors::read`].
+    pub fn station_errors(&self) -> Ref<StationErrors<E>> {
+        self.0.borrow::<StationErrors<E>>()
+    }
+
+    /// Returns a reference to [`StationWarnings`].
+    ///
+    /// Note that [`StationWarnings`] is behind a [`RwLock`], and you can choose
     /// to [`read`] or [`write`] as necessary.
     ///
     /// [`RwLock`]: tokio::sync::RwLock
     /// [`read`]: tokio::sync::RwLock::read
     /// [`write`]: tokio::sync::RwLock::write
-    pub fn station_errors(&self) -> Ref<StationErrors<E>> {
-        self.0.borrow::<StationErrors<E>>()
+    pub fn station_warnings(&self) -> Ref<StationWarnings<E>> {
+        self.0.borrow::<StationWarnings<E>>()
+    }
+
+    /// Returns `TrainResources` with mock execution resources, for testing
+    /// station functions without a `Destination`.
+    ///
+    /// In addition to the default resources, this includes the following,
+    /// matching the layout used by a `Destination`:
+    ///
+    /// * [`WorkspaceDir`]: `${workspace}`
+    /// * [`HistoryDir`]: `${workspace}/target/.history`
+    /// * [`ProfileHistoryDir`]: `${workspace}/target/.history/default`
+    /// * [`Profile`]: `default`
+    /// * [`ProfileDir`]: `${workspace}/target/default`
+    /// * [`CacheDir`]: `${workspace}/target/.cache`
+    /// * [`ResIdsPrevious`]: empty
+    ///
+    /// Directories are not created. Additional resources may be inserted using
+    /// [`TrainResources::with_resource`].
+    ///
+    /// # Parameters
+    ///
+    /// * `workspace_dir`: Directory to use as the workspace.
+    #[cfg(feature = "mock")]
+    pub fn with_mocks<P>(workspace_dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        let workspace_dir = workspace_dir.into();
+        let history_dir = workspace_dir.join("target").join(".history");
+        let profile = Profile::default();
+        let profile_history_dir = history_dir.join(&*profile);
+        let profile_dir = workspace_dir.join("target").join(&*profile);
+        let cache_dir = workspace_dir.join("target").join(".cache");
+
+        Self::new()
+            .with_resource(WorkspaceDir::new(workspace_dir))
+            .with_resource(HistoryDir::new(history_dir))
+            .with_resource(ProfileHistoryDir::new(profile_history_dir))
+            .with_resource(ProfileDir::new(profile_dir))
+            .with_resource(CacheDir::new(cache_dir))
+            .with_resource(profile)
+            .with_resource(ResIdsPrevious::new())
+    }
+
+    /// Inserts a resource, replacing any existing resource of the same type.
+    #[must_use]
+    pub fn with_resource<R>(mut self, resource: R) -> Self
+    where
+        R: Resource,
+    {
+        self.0.insert(resource);
+        self
+    }
+
+    /// Inserts a resource into the given scope, replacing any existing
+    /// resource of the same type in that scope.
+    ///
+    /// This is usually called in a station's setup function, with the
+    /// station's [`ResourceScope::Station`] or [`ResourceScope::Op`] scope.
+    pub fn insert_scoped<R>(&mut self, scope: ResourceScope, resource: R)
+    where
+        R: Resource,
+    {
+        match scope {
+            ResourceScope::Global => self.0.insert(resource),
+            ResourceScope::Station(_) | ResourceScope::Op(_) => self.1.insert(scope, resource),
+        }
+    }
+
+    /// Drops the resources in the given station or operation scope.
+    ///
+    /// Resources in the [`ResourceScope::Global`] scope, and resources that
+    /// are currently borrowed, are not dropped.
+    pub fn scope_drop(&self, scope: ResourceScope) {
+        match scope {
+            ResourceScope::Global => {}
+            ResourceScope::Station(_) | ResourceScope::Op(_) => self.1.drop_scope(scope),
+        }
+    }
+
+    /// Returns an immutable reference to a resource for a station, from the
+    /// station's narrowest scope that holds it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource does not exist in any of the station's scopes,
+    /// or is already mutably borrowed.
+    pub fn station_borrow<R>(&self, station_rt_id: StationRtId) -> ScopedRef<'_, R>
+    where
+        R: Resource,
+    {
+        match self.1.try_borrow::<R>(station_rt_id) {
+            Some(Ok(scoped_value)) => ScopedRef::scoped(scoped_value),
+            Some(Err(borrow_fail)) => panic!(
+                "Failed to borrow `{}`: {borrow_fail:?}",
+                std::any::type_name::<R>()
+            ),
+            None => ScopedRef::global(self.0.borrow::<R>()),
+        }
+    }
+
+    /// Returns an immutable reference to a resource for a station, from the
+    /// station's narrowest scope that holds it.
+    pub fn station_try_borrow<R>(
+        &self,
+        station_rt_id: StationRtId,
+    ) -> Result<ScopedRef<'_, R>, BorrowFail>
+    where
+        R: Resource,
+    {
+        match self.1.try_borrow::<R>(station_rt_id) {
+            Some(scoped_value) => scoped_value.map(ScopedRef::scoped),
+            None => self.0.try_borrow::<R>().map(ScopedRef::global),
+        }
+    }
+
+    /// Returns a mutable reference to a resource for a station, from the
+    /// station's narrowest scope that holds it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource does not exist in any of the station's scopes,
+    /// or is already borrowed.
+    pub fn station_borrow_mut<R>(&self, station_rt_id: StationRtId) -> ScopedRefMut<'_, R>
+    where
+        R: Resource,
+    {
+        match self.1.try_borrow_mut::<R>(station_rt_id) {
+            Some(Ok(scoped_value)) => ScopedRefMut::scoped(scoped_value),
+            Some(Err(borrow_fail)) => panic!(
+                "Failed to mutably borrow `{}`: {borrow_fail:?}",
+                std::any::type_name::<R>()
+            ),
+            None => ScopedRefMut::global(self.0.borrow_mut::<R>()),
+        }
+    }
+
+    /// Returns a mutable reference to a resource for a station, from the
+    /// station's narrowest scope that holds it.
+    pub fn station_try_borrow_mut<R>(
+        &self,
+        station_rt_id: StationRtId,
+    ) -> Result<ScopedRefMut<'_, R>, BorrowFail>
+    where
+        R: Resource,
+    {
+        match self.1.try_borrow_mut::<R>(station_rt_id) {
+            Some(scoped_value) => scoped_value.map(ScopedRefMut::scoped),
+            None => self.0.try_borrow_mut::<R>().map(ScopedRefMut::global),
+        }
+    }
+
+    /// Inserts a resource that is torn down at the end of the train's drive,
+    /// replacing any existing resource of the same type.
+    ///
+    /// Resources are torn down in the reverse order that they are inserted.
+    pub fn insert_teardown<R>(&mut self, resource: R)
+    where
+        R: ResourceTeardown<E>,
+    {
+        self.0.insert(resource);
+        self.2.push::<R>();
+    }
+
+    /// Tears down the resources inserted with
+    /// [`TrainResources::insert_teardown`], and returns the errors.
+    ///
+    /// Resources are torn down in reverse insertion order, and only once, so
+    /// subsequent calls return no errors. Resources that are borrowed when
+    /// this is called are not torn down.
+    pub async fn teardown(&mut self) -> Vec<E> {
+        self.2.teardown(&self.0).await
     }
 }
 
@@ -41,10 +242,19 @@ where
 {
     fn default() -> Self {
         let mut resources = Resources::default();
-        resources.insert(FilesRw::new());
+        let files_rw = FilesRw::new();
+        resources.insert(SourceRegistry::new(&files_rw));
+        resources.insert(files_rw);
         resources.insert(StationErrors::<E>::new());
+        resources.insert(StationWarnings::<E>::new());
+        resources.insert(StationFiles::new());
+        resources.insert(Effect::new(false));
 
-        Self(resources, PhantomData)
+        Self(
+            resources,
+            ScopedResources::default(),
+            ResourceTeardowns::default(),
+        )
     }
 }
 
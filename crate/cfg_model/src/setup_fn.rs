@@ -7,7 +7,7 @@ use std::{
 
 #[cfg(feature = "mock")]
 use crate::rt::OpStatus;
-use crate::rt::{ProgressLimit, StationMut, TrainResources};
+use crate::rt::{ProgressLimit, StationMut, StationResourcesMut};
 
 /// Return type of the `SetupFn`.
 pub type SetupFnReturn<'f, E> = Pin<Box<dyn Future<Output = Result<ProgressLimit, E>> + 'f>>;
@@ -15,10 +15,17 @@ pub type SetupFnReturn<'f, E> = Pin<Box<dyn Future<Output = Result<ProgressLimit
 // **Note:** `Debug`, `Clone`, `PartialEq` are manually implemented to avoid the
 // trait bound on `E`.
 /// Verifies input, calculates progress limit, and inserts resources.
+///
+/// Resources are inserted and borrowed through [`StationResourcesMut`], which
+/// only lends resources that the station's check, work, or verify functions
+/// declare as parameters.
 #[allow(clippy::type_complexity)] // trait aliases don't exist yet, so we have to suppress clippy.
 pub struct SetupFn<E>(
     pub  Arc<
-        dyn for<'f> Fn(&'f mut StationMut<E>, &'f mut TrainResources<E>) -> SetupFnReturn<'f, E>,
+        dyn for<'f> Fn(
+            &'f mut StationMut<E>,
+            &'f mut StationResourcesMut<'_, E>,
+        ) -> SetupFnReturn<'f, E>,
     >,
 );
 
@@ -30,7 +37,10 @@ impl<E> SetupFn<E> {
     /// * `f`: Logic to run.
     pub fn new<F>(f: F) -> Self
     where
-        F: for<'f> Fn(&'f mut StationMut<E>, &'f mut TrainResources<E>) -> SetupFnReturn<'f, E>
+        F: for<'f> Fn(
+                &'f mut StationMut<E>,
+                &'f mut StationResourcesMut<'_, E>,
+            ) -> SetupFnReturn<'f, E>
             + 'static,
     {
         Self(Arc::new(f))
@@ -70,7 +80,9 @@ impl<E> Clone for SetupFn<E> {
 
 impl<E> Debug for SetupFn<E> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.write_str("SetupFn(fn(&'_ mut Station<E>) -> SetupFnReturn<'_, E>)")
+        f.write_str(
+            "SetupFn(fn(&'_ mut StationMut<E>, &'_ mut StationResourcesMut<'_, E>) -> SetupFnReturn<'_, E>)",
+        )
     }
 }
 
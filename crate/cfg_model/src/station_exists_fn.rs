@@ -0,0 +1,109 @@
+use std::{
+    fmt::{self, Debug},
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+};
+
+use indexmap::IndexMap;
+
+use crate::rt::{ResIdLogical, StationMutRef, StationResources};
+
+/// Return type of the `StationExistsFn`.
+pub type StationExistsFnReturn<'f, E> =
+    Pin<Box<dyn Future<Output = Result<IndexMap<ResIdLogical, serde_json::Value>, E>> + 'f>>;
+
+// **Note:** `Debug`, `Clone`, `PartialEq` are manually implemented to avoid the
+// trait bound on `E`.
+/// Logic to look up which of a station's resources currently exist.
+///
+/// The function receives the station, and the serialized resource IDs that the
+/// station persisted in previous executions. It returns the serialized
+/// resource IDs of the station's resources that exist, which may include
+/// resources that were not persisted, such as ones found by listing resources
+/// with the station's naming convention.
+///
+/// Unlike check functions, which aggregate a station's resources into whether
+/// work is required, this is compared per resource ID when a train audits the
+/// destination.
+///
+/// Like hooks, the function may only borrow resources that the station's
+/// functions declare as parameters, through [`StationResources`].
+#[allow(clippy::type_complexity)] // trait aliases don't exist yet, so we have to suppress clippy.
+pub struct StationExistsFn<E>(
+    pub  Arc<
+        dyn for<'f> Fn(
+            &'f StationMutRef<'_, E>,
+            &'f IndexMap<ResIdLogical, serde_json::Value>,
+            &'f StationResources<'_, E>,
+        ) -> StationExistsFnReturn<'f, E>,
+    >,
+);
+
+impl<E> StationExistsFn<E> {
+    /// Returns a new `StationExistsFn`.
+    ///
+    /// # Parameters
+    ///
+    /// * `f`: Logic to run.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: for<'f> Fn(
+                &'f StationMutRef<'_, E>,
+                &'f IndexMap<ResIdLogical, serde_json::Value>,
+                &'f StationResources<'_, E>,
+            ) -> StationExistsFnReturn<'f, E>
+            + 'static,
+    {
+        Self(Arc::new(f))
+    }
+
+    /// Returns a `StationExistsFn` that reports every persisted resource ID as
+    /// existing.
+    #[cfg(feature = "mock")]
+    pub fn ok() -> Self
+    where
+        E: 'static,
+    {
+        StationExistsFn::new(|_, res_ids_persisted, _| {
+            let res_ids_existing = res_ids_persisted.clone();
+            Box::pin(async move { Result::<_, E>::Ok(res_ids_existing) })
+        })
+    }
+
+    /// Returns a `StationExistsFn` that always returns `Result::Err`.
+    #[cfg(feature = "mock")]
+    pub fn err(e: E) -> Self
+    where
+        E: Clone + 'static,
+    {
+        StationExistsFn::new(move |_, _, _| {
+            let e = e.clone();
+            Box::pin(async move { Result::<_, E>::Err(e) })
+        })
+    }
+}
+
+// We `impl Clone` to avoid the `E: Clone` bound generated by the derive.
+#[cfg(not(tarpaulin_include))]
+impl<E> Clone for StationExistsFn<E> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<E> Debug for StationExistsFn<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(
+            "StationExistsFn(fn(&'_ StationMutRef<'_, E>, \
+             &'_ IndexMap<ResIdLogical, serde_json::Value>, \
+             &'_ StationResources<'_, E>) -> StationExistsFnReturn<'_, E>)",
+        )
+    }
+}
+
+impl<E> PartialEq for StationExistsFn<E> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(&self.0, &other.0)
+    }
+}
@@ -1,25 +1,36 @@
 use std::{
     fmt::{self, Debug},
+    panic::Location,
     sync::Arc,
 };
 
-use fn_graph::{FnMeta, FnMetadata, TypeIds};
+use fn_graph::{FnMeta, TypeIds};
 use futures::future::{FutureExt, LocalBoxFuture};
 
-#[cfg(feature = "mock")]
 use crate::rt::StationMutRef;
-use crate::StationFnMetadataExt;
 
 pub use self::{
     into_station_fn_res::IntoStationFnRes, into_station_fn_resource::IntoStationFnResource,
     station_fn_res::StationFnRes, station_fn_resource::StationFnResource,
+    station_fn_send::StationFnSend,
+};
+
+#[cfg(feature = "mock")]
+pub(crate) use self::station_fn_recorded::StationFnRecorded;
+pub(crate) use self::{
+    station_fn_output::StationFnOutput, station_fn_output_restore::StationFnOutputRestore,
 };
 
 mod into_station_fn_res;
 mod into_station_fn_resource;
+mod station_fn_output;
+mod station_fn_output_restore;
+#[cfg(feature = "mock")]
+mod station_fn_recorded;
 mod station_fn_res;
 mod station_fn_res_impl;
 mod station_fn_resource;
+mod station_fn_send;
 
 // **Note:** `Debug`, `Clone`, `PartialEq` are manually implemented to avoid the
 // trait bound on `E`.
@@ -39,6 +50,8 @@ pub struct StationFn<R, RErr, E> {
     ///
     /// [`TypeId`]: core::any::TypeId
     borrow_muts: TypeIds,
+    /// Source code location that the function was constructed at.
+    location: &'static Location<'static>,
 }
 
 impl<R, RErr, E> StationFn<R, RErr, E>
@@ -57,23 +70,71 @@ where
     /// # Parameters
     ///
     /// * `f`: Logic to run.
+    #[track_caller]
     pub fn new<Fun, ArgRefs>(f: Fun) -> Self
     where
-        Fun: IntoStationFnRes<Fun, R, RErr, E, ArgRefs>
-            + StationFnMetadataExt<Fun, R, RErr, ArgRefs>
-            + 'static,
-        for<'f> FnMetadata<Fun, LocalBoxFuture<'f, Result<R, RErr>>, ArgRefs>: FnMeta,
+        Fun: IntoStationFnRes<Fun, R, RErr, E, ArgRefs> + 'static,
         ArgRefs: 'static,
     {
-        let metadata = f.metadata();
-        let f = f.into_station_fn_res();
+        Self::from_station_fn_res(f.into_station_fn_res(), Location::caller())
+    }
+
+    /// Returns a new `StationFn` from an existing resource function.
+    ///
+    /// # Parameters
+    ///
+    /// * `f`: Logic to run.
+    /// * `location`: Source code location that the function was constructed at.
+    pub(crate) fn from_station_fn_res(
+        f: Box<dyn StationFnRes<R, RErr, E>>,
+        location: &'static Location<'static>,
+    ) -> Self {
+        let borrows = f.borrows();
+        let borrow_muts = f.borrow_muts();
         Self {
             f: Arc::new(f),
-            borrows: metadata.borrows(),
-            borrow_muts: metadata.borrow_muts(),
+            borrows,
+            borrow_muts,
+            location,
         }
     }
 
+    /// Returns the source code location that the function was constructed at.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// Returns a new `StationFn` whose future is `Send + 'static`.
+    ///
+    /// The function is called with the station and its resource arguments, and
+    /// may read from them before returning the future. The returned future
+    /// must not borrow from the station or resources, so values it needs
+    /// should be cloned into it.
+    ///
+    /// When the train's [`ExecutionModel`] is [`ExecutionModel::MultiThread`],
+    /// the future is spawned as a tokio task, allowing station work to run in
+    /// parallel on a multi-threaded runtime. If there is a [`Spawner`] in the
+    /// train resources, it is used to spawn the future instead. If the task
+    /// does not complete, the function returns a [`StationFnTaskError`].
+    ///
+    /// [`ExecutionModel`]: crate::rt::ExecutionModel
+    /// [`ExecutionModel::MultiThread`]: crate::rt::ExecutionModel::MultiThread
+    /// [`Spawner`]: crate::rt::Spawner
+    /// [`StationFnTaskError`]: crate::rt::StationFnTaskError
+    ///
+    /// # Parameters
+    ///
+    /// * `f`: Logic to run.
+    #[track_caller]
+    pub fn new_send<Fun, ArgRefs>(f: Fun) -> Self
+    where
+        Fun: 'static,
+        ArgRefs: 'static,
+        StationFnSend<Fun, R, RErr, E, ArgRefs>: StationFnRes<R, RErr, E>,
+    {
+        Self::from_station_fn_res(Box::new(StationFnSend::new(f)), Location::caller())
+    }
+
     /// Returns a new `StationFn`.
     ///
     /// This method allows you to construct a StationFn using a closure, as it
@@ -90,13 +151,12 @@ where
     /// # Parameters
     ///
     /// * `f`: Logic to run.
+    #[track_caller]
     pub fn new0<Fun>(f: Fun) -> Self
     where
         Fun: for<'f> Fn(&'f mut StationMutRef<'_, E>) -> LocalBoxFuture<'f, Result<R, RErr>>
             + IntoStationFnRes<Fun, R, RErr, E, ()>
-            + StationFnMetadataExt<Fun, R, RErr, ()>
             + 'static,
-        for<'f> FnMetadata<Fun, LocalBoxFuture<'f, Result<R, RErr>>, ()>: FnMeta,
     {
         Self::new(f)
     }
@@ -117,13 +177,12 @@ where
     /// # Parameters
     ///
     /// * `f`: Logic to run.
+    #[track_caller]
     pub fn new1<Fun, A0>(f: Fun) -> Self
     where
         Fun: for<'f> Fn(&'f mut StationMutRef<'_, E>, &'f A0) -> LocalBoxFuture<'f, Result<R, RErr>>
             + IntoStationFnRes<Fun, R, RErr, E, (&'static A0,)>
-            + for<'f> StationFnMetadataExt<Fun, R, RErr, (&'f A0,)>
             + 'static,
-        for<'f> FnMetadata<Fun, LocalBoxFuture<'f, Result<R, RErr>>, (&'f A0,)>: FnMeta,
         A0: 'static,
     {
         Self::new(f)
@@ -145,6 +204,7 @@ where
     /// # Parameters
     ///
     /// * `f`: Logic to run.
+    #[track_caller]
     pub fn new2<Fun, A0, A1>(f: Fun) -> Self
     where
         Fun: for<'f> Fn(
@@ -153,9 +213,7 @@ where
                 &'f A1,
             ) -> LocalBoxFuture<'f, Result<R, RErr>>
             + IntoStationFnRes<Fun, R, RErr, E, (&'static A0, &'static A1)>
-            + for<'f> StationFnMetadataExt<Fun, R, RErr, (&'f A0, &'f A1)>
             + 'static,
-        for<'f> FnMetadata<Fun, LocalBoxFuture<'f, Result<R, RErr>>, (&'f A0, &'f A1)>: FnMeta,
         A0: 'static,
         A1: 'static,
     {
@@ -178,6 +236,7 @@ where
     /// # Parameters
     ///
     /// * `f`: Logic to run.
+    #[track_caller]
     pub fn new3<Fun, A0, A1, A2>(f: Fun) -> Self
     where
         Fun: for<'f> Fn(
@@ -186,11 +245,8 @@ where
                 &'f A1,
                 &'f A2,
             ) -> LocalBoxFuture<'f, Result<R, RErr>>
-            + IntoStationFnRes<Fun, R, RErr, E, (&'static A0, &'static A1)>
-            + for<'f> StationFnMetadataExt<Fun, R, RErr, (&'f A0, &'f A1, &'f A2)>
+            + IntoStationFnRes<Fun, R, RErr, E, (&'static A0, &'static A1, &'static A2)>
             + 'static,
-        for<'f> FnMetadata<Fun, LocalBoxFuture<'f, Result<R, RErr>>, (&'f A0, &'f A1, &'f A2)>:
-            FnMeta,
         A0: 'static,
         A1: 'static,
         A2: 'static,
@@ -214,6 +270,7 @@ where
     /// # Parameters
     ///
     /// * `f`: Logic to run.
+    #[track_caller]
     pub fn new4<Fun, A0, A1, A2, A3>(f: Fun) -> Self
     where
         Fun: for<'f> Fn(
@@ -223,11 +280,8 @@ where
                 &'f A2,
                 &'f A3,
             ) -> LocalBoxFuture<'f, Result<R, RErr>>
-            + IntoStationFnRes<Fun, R, RErr, E, (&'static A0, &'static A1)>
-            + for<'f> StationFnMetadataExt<Fun, R, RErr, (&'f A0, &'f A1, &'f A2, &'f A3)>
+            + IntoStationFnRes<Fun, R, RErr, E, (&'static A0, &'static A1, &'static A2, &'static A3)>
             + 'static,
-        for<'f> FnMetadata<Fun, LocalBoxFuture<'f, Result<R, RErr>>, (&'f A0, &'f A1, &'f A2, &'f A3)>:
-            FnMeta,
         A0: 'static,
         A1: 'static,
         A2: 'static,
@@ -252,6 +306,7 @@ where
     /// # Parameters
     ///
     /// * `f`: Logic to run.
+    #[track_caller]
     pub fn new5<Fun, A0, A1, A2, A3, A4>(f: Fun) -> Self
     where
         Fun: for<'f> Fn(
@@ -262,14 +317,19 @@ where
                 &'f A3,
                 &'f A4,
             ) -> LocalBoxFuture<'f, Result<R, RErr>>
-            + IntoStationFnRes<Fun, R, RErr, E, (&'static A0, &'static A1)>
-            + for<'f> StationFnMetadataExt<Fun, R, RErr, (&'f A0, &'f A1, &'f A2, &'f A3, &'f A4)>
-            + 'static,
-        for<'f> FnMetadata<
-            Fun,
-            LocalBoxFuture<'f, Result<R, RErr>>,
-            (&'f A0, &'f A1, &'f A2, &'f A3, &'f A4),
-        >: FnMeta,
+            + IntoStationFnRes<
+                Fun,
+                R,
+                RErr,
+                E,
+                (
+                    &'static A0,
+                    &'static A1,
+                    &'static A2,
+                    &'static A3,
+                    &'static A4,
+                ),
+            > + 'static,
         A0: 'static,
         A1: 'static,
         A2: 'static,
@@ -295,6 +355,7 @@ where
     /// # Parameters
     ///
     /// * `f`: Logic to run.
+    #[track_caller]
     pub fn new6<Fun, A0, A1, A2, A3, A4, A5>(f: Fun) -> Self
     where
         Fun: for<'f> Fn(
@@ -306,18 +367,20 @@ where
                 &'f A4,
                 &'f A5,
             ) -> LocalBoxFuture<'f, Result<R, RErr>>
-            + IntoStationFnRes<Fun, R, RErr, E, (&'static A0, &'static A1)>
-            + for<'f> StationFnMetadataExt<
+            + IntoStationFnRes<
                 Fun,
                 R,
+                RErr,
                 E,
-                (&'f A0, &'f A1, &'f A2, &'f A3, &'f A4, &'f A5),
+                (
+                    &'static A0,
+                    &'static A1,
+                    &'static A2,
+                    &'static A3,
+                    &'static A4,
+                    &'static A5,
+                ),
             > + 'static,
-        for<'f> FnMetadata<
-            Fun,
-            LocalBoxFuture<'f, Result<R, RErr>>,
-            (&'f A0, &'f A1, &'f A2, &'f A3, &'f A4, &'f A5),
-        >: FnMeta,
         A0: 'static,
         A1: 'static,
         A2: 'static,
@@ -330,6 +393,7 @@ where
 
     /// Returns a `StationFn` that always returns `Result::Ok`.
     #[cfg(feature = "mock")]
+    #[track_caller]
     pub fn ok(r: R) -> Self
     where
         R: Clone + 'static,
@@ -342,6 +406,7 @@ where
 
     /// Returns a `StationFn` that always returns `Result::Err`.
     #[cfg(feature = "mock")]
+    #[track_caller]
     pub fn err(e: RErr) -> Self
     where
         RErr: Clone + 'static,
@@ -361,6 +426,7 @@ impl<R, RErr, E> Clone for StationFn<R, RErr, E> {
             f: Arc::clone(&self.f),
             borrows: self.borrows.clone(),
             borrow_muts: self.borrow_muts.clone(),
+            location: self.location,
         }
     }
 }
@@ -1,6 +1,15 @@
 use crate::{station_fn::IntoStationFnResource, StationFnRes, StationFnResource};
 
 /// Extension to return `Box<dyn StationFnRes>` for a function.
+///
+/// This is implemented for functions that take a `&mut StationMutRef<'_, E>`,
+/// followed by up to 12 resource references.
+#[diagnostic::on_unimplemented(
+    message = "`{Fun}` is not a valid station function",
+    label = "station functions take `&mut StationMutRef<'_, E>`, followed by up to 12 resource references",
+    note = "station functions support at most 12 resource references",
+    note = "if more resources are needed, group them into a struct and insert that into `TrainResources`"
+)]
 pub trait IntoStationFnRes<Fun, R, RErr, E, Args> {
     /// R, Eurns the function wrapped as a `Box<dyn StationFnRes>`.
     fn into_station_fn_res(self) -> Box<dyn StationFnRes<R, RErr, E>>;
@@ -119,3 +128,148 @@ where
         Box::new(self.into_station_fn_resource())
     }
 }
+
+impl<Fun, R, RErr, E, A0, A1, A2, A3, A4, A5, A6>
+    IntoStationFnRes<Fun, R, RErr, E, (A0, A1, A2, A3, A4, A5, A6)> for Fun
+where
+    Fun: 'static,
+    R: 'static,
+    RErr: 'static,
+    E: 'static,
+    A0: 'static,
+    A1: 'static,
+    A2: 'static,
+    A3: 'static,
+    A4: 'static,
+    A5: 'static,
+    A6: 'static,
+    StationFnResource<Fun, R, RErr, E, (A0, A1, A2, A3, A4, A5, A6)>: StationFnRes<R, RErr, E>,
+{
+    fn into_station_fn_res(self) -> Box<dyn StationFnRes<R, RErr, E>> {
+        Box::new(self.into_station_fn_resource())
+    }
+}
+
+impl<Fun, R, RErr, E, A0, A1, A2, A3, A4, A5, A6, A7>
+    IntoStationFnRes<Fun, R, RErr, E, (A0, A1, A2, A3, A4, A5, A6, A7)> for Fun
+where
+    Fun: 'static,
+    R: 'static,
+    RErr: 'static,
+    E: 'static,
+    A0: 'static,
+    A1: 'static,
+    A2: 'static,
+    A3: 'static,
+    A4: 'static,
+    A5: 'static,
+    A6: 'static,
+    A7: 'static,
+    StationFnResource<Fun, R, RErr, E, (A0, A1, A2, A3, A4, A5, A6, A7)>: StationFnRes<R, RErr, E>,
+{
+    fn into_station_fn_res(self) -> Box<dyn StationFnRes<R, RErr, E>> {
+        Box::new(self.into_station_fn_resource())
+    }
+}
+
+impl<Fun, R, RErr, E, A0, A1, A2, A3, A4, A5, A6, A7, A8>
+    IntoStationFnRes<Fun, R, RErr, E, (A0, A1, A2, A3, A4, A5, A6, A7, A8)> for Fun
+where
+    Fun: 'static,
+    R: 'static,
+    RErr: 'static,
+    E: 'static,
+    A0: 'static,
+    A1: 'static,
+    A2: 'static,
+    A3: 'static,
+    A4: 'static,
+    A5: 'static,
+    A6: 'static,
+    A7: 'static,
+    A8: 'static,
+    StationFnResource<Fun, R, RErr, E, (A0, A1, A2, A3, A4, A5, A6, A7, A8)>:
+        StationFnRes<R, RErr, E>,
+{
+    fn into_station_fn_res(self) -> Box<dyn StationFnRes<R, RErr, E>> {
+        Box::new(self.into_station_fn_resource())
+    }
+}
+
+impl<Fun, R, RErr, E, A0, A1, A2, A3, A4, A5, A6, A7, A8, A9>
+    IntoStationFnRes<Fun, R, RErr, E, (A0, A1, A2, A3, A4, A5, A6, A7, A8, A9)> for Fun
+where
+    Fun: 'static,
+    R: 'static,
+    RErr: 'static,
+    E: 'static,
+    A0: 'static,
+    A1: 'static,
+    A2: 'static,
+    A3: 'static,
+    A4: 'static,
+    A5: 'static,
+    A6: 'static,
+    A7: 'static,
+    A8: 'static,
+    A9: 'static,
+    StationFnResource<Fun, R, RErr, E, (A0, A1, A2, A3, A4, A5, A6, A7, A8, A9)>:
+        StationFnRes<R, RErr, E>,
+{
+    fn into_station_fn_res(self) -> Box<dyn StationFnRes<R, RErr, E>> {
+        Box::new(self.into_station_fn_resource())
+    }
+}
+
+impl<Fun, R, RErr, E, A0, A1, A2, A3, A4, A5, A6, A7, A8, A9, A10>
+    IntoStationFnRes<Fun, R, RErr, E, (A0, A1, A2, A3, A4, A5, A6, A7, A8, A9, A10)> for Fun
+where
+    Fun: 'static,
+    R: 'static,
+    RErr: 'static,
+    E: 'static,
+    A0: 'static,
+    A1: 'static,
+    A2: 'static,
+    A3: 'static,
+    A4: 'static,
+    A5: 'static,
+    A6: 'static,
+    A7: 'static,
+    A8: 'static,
+    A9: 'static,
+    A10: 'static,
+    StationFnResource<Fun, R, RErr, E, (A0, A1, A2, A3, A4, A5, A6, A7, A8, A9, A10)>:
+        StationFnRes<R, RErr, E>,
+{
+    fn into_station_fn_res(self) -> Box<dyn StationFnRes<R, RErr, E>> {
+        Box::new(self.into_station_fn_resource())
+    }
+}
+
+impl<Fun, R, RErr, E, A0, A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11>
+    IntoStationFnRes<Fun, R, RErr, E, (A0, A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11)> for Fun
+where
+    Fun: 'static,
+    R: 'static,
+    RErr: 'static,
+    E: 'static,
+    A0: 'static,
+    A1: 'static,
+    A2: 'static,
+    A3: 'static,
+    A4: 'static,
+    A5: 'static,
+    A6: 'static,
+    A7: 'static,
+    A8: 'static,
+    A9: 'static,
+    A10: 'static,
+    A11: 'static,
+    StationFnResource<Fun, R, RErr, E, (A0, A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11)>:
+        StationFnRes<R, RErr, E>,
+{
+    fn into_station_fn_res(self) -> Box<dyn StationFnRes<R, RErr, E>> {
+        Box::new(self.into_station_fn_resource())
+    }
+}
@@ -80,3 +80,83 @@ impl<Fun, R, RErr, E, A0, A1, A2, A3, A4, A5>
         }
     }
 }
+
+impl<Fun, R, RErr, E, A0, A1, A2, A3, A4, A5, A6>
+    IntoStationFnResource<Fun, R, RErr, E, (A0, A1, A2, A3, A4, A5, A6)> for Fun
+{
+    fn into_station_fn_resource(
+        self,
+    ) -> StationFnResource<Fun, R, RErr, E, (A0, A1, A2, A3, A4, A5, A6)> {
+        StationFnResource {
+            func: self,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<Fun, R, RErr, E, A0, A1, A2, A3, A4, A5, A6, A7>
+    IntoStationFnResource<Fun, R, RErr, E, (A0, A1, A2, A3, A4, A5, A6, A7)> for Fun
+{
+    fn into_station_fn_resource(
+        self,
+    ) -> StationFnResource<Fun, R, RErr, E, (A0, A1, A2, A3, A4, A5, A6, A7)> {
+        StationFnResource {
+            func: self,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<Fun, R, RErr, E, A0, A1, A2, A3, A4, A5, A6, A7, A8>
+    IntoStationFnResource<Fun, R, RErr, E, (A0, A1, A2, A3, A4, A5, A6, A7, A8)> for Fun
+{
+    fn into_station_fn_resource(
+        self,
+    ) -> StationFnResource<Fun, R, RErr, E, (A0, A1, A2, A3, A4, A5, A6, A7, A8)> {
+        StationFnResource {
+            func: self,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<Fun, R, RErr, E, A0, A1, A2, A3, A4, A5, A6, A7, A8, A9>
+    IntoStationFnResource<Fun, R, RErr, E, (A0, A1, A2, A3, A4, A5, A6, A7, A8, A9)> for Fun
+{
+    fn into_station_fn_resource(
+        self,
+    ) -> StationFnResource<Fun, R, RErr, E, (A0, A1, A2, A3, A4, A5, A6, A7, A8, A9)> {
+        StationFnResource {
+            func: self,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<Fun, R, RErr, E, A0, A1, A2, A3, A4, A5, A6, A7, A8, A9, A10>
+    IntoStationFnResource<Fun, R, RErr, E, (A0, A1, A2, A3, A4, A5, A6, A7, A8, A9, A10)> for Fun
+{
+    fn into_station_fn_resource(
+        self,
+    ) -> StationFnResource<Fun, R, RErr, E, (A0, A1, A2, A3, A4, A5, A6, A7, A8, A9, A10)> {
+        StationFnResource {
+            func: self,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<Fun, R, RErr, E, A0, A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11>
+    IntoStationFnResource<Fun, R, RErr, E, (A0, A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11)>
+    for Fun
+{
+    fn into_station_fn_resource(
+        self,
+    ) -> StationFnResource<Fun, R, RErr, E, (A0, A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11)>
+    {
+        StationFnResource {
+            func: self,
+            marker: PhantomData,
+        }
+    }
+}
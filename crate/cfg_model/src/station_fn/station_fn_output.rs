@@ -0,0 +1,99 @@
+use std::{any::TypeId, fmt};
+
+use fn_graph::{FnMeta, TypeIds};
+use futures::future::LocalBoxFuture;
+use resman::BorrowFail;
+
+use crate::{
+    rt::{ResIds, StationMutRef, StationOutput, StationResourceError, TrainResources},
+    StationFn, StationFnRes,
+};
+
+/// Create work function that stores its output in a [`StationOutput`].
+///
+/// This adapts a work function that returns `(ResIds, O)` into one that
+/// returns [`ResIds`], so that it may be held by [`CreateFns`].
+///
+/// If the [`StationOutput`] resource does not exist, the work function is not
+/// run, and a [`StationResourceError`] is returned.
+///
+/// [`CreateFns`]: crate::CreateFns
+pub(crate) struct StationFnOutput<O, E> {
+    /// Work function that returns both the resource IDs and output.
+    work_fn: StationFn<(ResIds, O), (ResIds, E), E>,
+}
+
+impl<O, E> StationFnOutput<O, E> {
+    /// Returns a new `StationFnOutput`.
+    pub(crate) fn new(work_fn: StationFn<(ResIds, O), (ResIds, E), E>) -> Self {
+        Self { work_fn }
+    }
+}
+
+impl<O, E> StationFnRes<ResIds, (ResIds, E), E> for StationFnOutput<O, E>
+where
+    O: fmt::Debug + Send + Sync + 'static,
+    E: From<StationResourceError> + 'static,
+{
+    fn call<'f1: 'f2, 'f2>(
+        &'f2 self,
+        station: &'f1 mut StationMutRef<'_, E>,
+        train_resources: &'f2 TrainResources<E>,
+    ) -> LocalBoxFuture<'f2, Result<ResIds, (ResIds, E)>> {
+        let station_output = match train_resources.try_borrow::<StationOutput<O>>() {
+            Ok(station_output) => StationOutput::clone(&station_output),
+            Err(borrow_fail) => {
+                let error = E::from(StationResourceError::BorrowFail {
+                    type_name: std::any::type_name::<StationOutput<O>>(),
+                    borrow_fail,
+                });
+                return Box::pin(async move { Err((ResIds::new(), error)) });
+            }
+        };
+        let work_fut = self.work_fn.f.call(station, train_resources);
+
+        Box::pin(async move {
+            let (res_ids, output) = work_fut.await?;
+            *station_output.write().await = Some(output);
+
+            Ok(res_ids)
+        })
+    }
+
+    fn try_call<'f1: 'f2, 'f2>(
+        &'f2 self,
+        station: &'f1 mut StationMutRef<'_, E>,
+        train_resources: &'f2 TrainResources<E>,
+    ) -> Result<LocalBoxFuture<'f2, Result<ResIds, (ResIds, E)>>, BorrowFail> {
+        let station_output =
+            StationOutput::clone(&*train_resources.try_borrow::<StationOutput<O>>()?);
+        let work_fut = self.work_fn.f.try_call(station, train_resources)?;
+
+        Ok(Box::pin(async move {
+            let (res_ids, output) = work_fut.await?;
+            *station_output.write().await = Some(output);
+
+            Ok(res_ids)
+        }))
+    }
+
+    fn borrows(&self) -> TypeIds {
+        let mut type_ids = self.work_fn.borrows();
+        type_ids.push(TypeId::of::<StationOutput<O>>());
+        type_ids
+    }
+
+    fn borrow_muts(&self) -> TypeIds {
+        self.work_fn.borrow_muts()
+    }
+
+    fn borrow_type_names(&self) -> Vec<&'static str> {
+        let mut type_names = self.work_fn.f.borrow_type_names();
+        type_names.push(std::any::type_name::<StationOutput<O>>());
+        type_names
+    }
+
+    fn borrow_mut_type_names(&self) -> Vec<&'static str> {
+        self.work_fn.f.borrow_mut_type_names()
+    }
+}
@@ -0,0 +1,100 @@
+use std::{any::TypeId, fmt};
+
+use fn_graph::{FnMeta, TypeIds};
+use futures::future::LocalBoxFuture;
+use resman::BorrowFail;
+
+use crate::{
+    rt::{StationMutRef, StationOutput, StationResourceError, TrainResources},
+    StationFn, StationFnRes,
+};
+
+/// Create output function that stores its output in a [`StationOutput`].
+///
+/// This adapts a function that returns `O` into one that returns `()`, so that
+/// it may be held by [`CreateFns`] to provide the output when the work is not
+/// required.
+///
+/// If the [`StationOutput`] resource does not exist, the output function is
+/// not run, and a [`StationResourceError`] is returned.
+///
+/// [`CreateFns`]: crate::CreateFns
+pub(crate) struct StationFnOutputRestore<O, E> {
+    /// Function that returns the output.
+    output_fn: StationFn<O, E, E>,
+}
+
+impl<O, E> StationFnOutputRestore<O, E> {
+    /// Returns a new `StationFnOutputRestore`.
+    pub(crate) fn new(output_fn: StationFn<O, E, E>) -> Self {
+        Self { output_fn }
+    }
+}
+
+impl<O, E> StationFnRes<(), E, E> for StationFnOutputRestore<O, E>
+where
+    O: fmt::Debug + Send + Sync + 'static,
+    E: From<StationResourceError> + 'static,
+{
+    fn call<'f1: 'f2, 'f2>(
+        &'f2 self,
+        station: &'f1 mut StationMutRef<'_, E>,
+        train_resources: &'f2 TrainResources<E>,
+    ) -> LocalBoxFuture<'f2, Result<(), E>> {
+        let station_output = match train_resources.try_borrow::<StationOutput<O>>() {
+            Ok(station_output) => StationOutput::clone(&station_output),
+            Err(borrow_fail) => {
+                let error = E::from(StationResourceError::BorrowFail {
+                    type_name: std::any::type_name::<StationOutput<O>>(),
+                    borrow_fail,
+                });
+                return Box::pin(async move { Err(error) });
+            }
+        };
+        let output_fut = self.output_fn.f.call(station, train_resources);
+
+        Box::pin(async move {
+            let output = output_fut.await?;
+            *station_output.write().await = Some(output);
+
+            Ok(())
+        })
+    }
+
+    fn try_call<'f1: 'f2, 'f2>(
+        &'f2 self,
+        station: &'f1 mut StationMutRef<'_, E>,
+        train_resources: &'f2 TrainResources<E>,
+    ) -> Result<LocalBoxFuture<'f2, Result<(), E>>, BorrowFail> {
+        let station_output =
+            StationOutput::clone(&*train_resources.try_borrow::<StationOutput<O>>()?);
+        let output_fut = self.output_fn.f.try_call(station, train_resources)?;
+
+        Ok(Box::pin(async move {
+            let output = output_fut.await?;
+            *station_output.write().await = Some(output);
+
+            Ok(())
+        }))
+    }
+
+    fn borrows(&self) -> TypeIds {
+        let mut type_ids = self.output_fn.borrows();
+        type_ids.push(TypeId::of::<StationOutput<O>>());
+        type_ids
+    }
+
+    fn borrow_muts(&self) -> TypeIds {
+        self.output_fn.borrow_muts()
+    }
+
+    fn borrow_type_names(&self) -> Vec<&'static str> {
+        let mut type_names = self.output_fn.f.borrow_type_names();
+        type_names.push(std::any::type_name::<StationOutput<O>>());
+        type_names
+    }
+
+    fn borrow_mut_type_names(&self) -> Vec<&'static str> {
+        self.output_fn.f.borrow_mut_type_names()
+    }
+}
@@ -0,0 +1,87 @@
+use fn_graph::{FnMeta, TypeIds};
+use futures::future::LocalBoxFuture;
+use resman::BorrowFail;
+
+use crate::{
+    rt::{StationMutRef, TrainResources},
+    MockInvocation, MockInvocations, MockOpFn, StationFn, StationFnRes,
+};
+
+/// Station function that records each invocation before running.
+///
+/// See [`MockInvocations`].
+pub(crate) struct StationFnRecorded<R, RErr, E> {
+    /// Function to run.
+    station_fn: StationFn<R, RErr, E>,
+    /// Where to record invocations.
+    mock_invocations: MockInvocations,
+    /// Which station function this is.
+    op_fn: MockOpFn,
+}
+
+impl<R, RErr, E> StationFnRecorded<R, RErr, E> {
+    /// Returns a new `StationFnRecorded`.
+    pub(crate) fn new(
+        station_fn: StationFn<R, RErr, E>,
+        mock_invocations: MockInvocations,
+        op_fn: MockOpFn,
+    ) -> Self {
+        Self {
+            station_fn,
+            mock_invocations,
+            op_fn,
+        }
+    }
+
+    /// Returns the invocation record for the given station.
+    fn invocation(&self, station: &StationMutRef<'_, E>) -> MockInvocation {
+        MockInvocation {
+            station_id: station.spec.id().clone(),
+            station_rt_id: station.rt_id,
+            op_fn: self.op_fn,
+            op_status: station.progress.op_status,
+            progress_limit: station.progress.progress_limit(),
+            borrow_type_names: self.station_fn.f.borrow_type_names(),
+            borrow_mut_type_names: self.station_fn.f.borrow_mut_type_names(),
+        }
+    }
+}
+
+impl<R, RErr, E> StationFnRes<R, RErr, E> for StationFnRecorded<R, RErr, E> {
+    fn call<'f1: 'f2, 'f2>(
+        &'f2 self,
+        station: &'f1 mut StationMutRef<'_, E>,
+        train_resources: &'f2 TrainResources<E>,
+    ) -> LocalBoxFuture<'f2, Result<R, RErr>> {
+        self.mock_invocations.record(self.invocation(station));
+        self.station_fn.f.call(station, train_resources)
+    }
+
+    fn try_call<'f1: 'f2, 'f2>(
+        &'f2 self,
+        station: &'f1 mut StationMutRef<'_, E>,
+        train_resources: &'f2 TrainResources<E>,
+    ) -> Result<LocalBoxFuture<'f2, Result<R, RErr>>, BorrowFail> {
+        let invocation = self.invocation(station);
+        let station_fn_fut = self.station_fn.f.try_call(station, train_resources)?;
+        self.mock_invocations.record(invocation);
+
+        Ok(station_fn_fut)
+    }
+
+    fn borrows(&self) -> TypeIds {
+        self.station_fn.borrows()
+    }
+
+    fn borrow_muts(&self) -> TypeIds {
+        self.station_fn.borrow_muts()
+    }
+
+    fn borrow_type_names(&self) -> Vec<&'static str> {
+        self.station_fn.f.borrow_type_names()
+    }
+
+    fn borrow_mut_type_names(&self) -> Vec<&'static str> {
+        self.station_fn.f.borrow_mut_type_names()
+    }
+}
@@ -1,5 +1,6 @@
 use std::ops::Deref;
 
+use fn_graph::TypeIds;
 use futures::future::LocalBoxFuture;
 use resman::BorrowFail;
 
@@ -23,6 +24,29 @@ pub trait StationFnRes<R, RErr, E> {
         station: &'f1 mut StationMutRef<'_, E>,
         _train_resources: &'f2 TrainResources<E>,
     ) -> Result<LocalBoxFuture<'f2, Result<R, RErr>>, BorrowFail>;
+
+    /// Returns the [`TypeId`]s of borrowed arguments.
+    ///
+    /// [`TypeId`]: core::any::TypeId
+    fn borrows(&self) -> TypeIds;
+
+    /// Returns the [`TypeId`]s of mutably borrowed arguments.
+    ///
+    /// [`TypeId`]: core::any::TypeId
+    fn borrow_muts(&self) -> TypeIds;
+
+    /// Returns the type names of borrowed arguments.
+    ///
+    /// These are in the same order as [`StationFnRes::borrows`], and are
+    /// intended for diagnostics, such as [`MockInvocation`]s.
+    ///
+    /// [`MockInvocation`]: crate::MockInvocation
+    fn borrow_type_names(&self) -> Vec<&'static str>;
+
+    /// Returns the type names of mutably borrowed arguments.
+    ///
+    /// These are in the same order as [`StationFnRes::borrow_muts`].
+    fn borrow_mut_type_names(&self) -> Vec<&'static str>;
 }
 
 impl<Fun, R, RErr, E> StationFnRes<R, RErr, E> for Box<Fun>
@@ -44,4 +68,20 @@ where
     ) -> Result<LocalBoxFuture<'f2, Result<R, RErr>>, BorrowFail> {
         self.deref().try_call(station, train_resources)
     }
+
+    fn borrows(&self) -> TypeIds {
+        self.deref().borrows()
+    }
+
+    fn borrow_muts(&self) -> TypeIds {
+        self.deref().borrow_muts()
+    }
+
+    fn borrow_type_names(&self) -> Vec<&'static str> {
+        self.deref().borrow_type_names()
+    }
+
+    fn borrow_mut_type_names(&self) -> Vec<&'static str> {
+        self.deref().borrow_mut_type_names()
+    }
 }
@@ -1,3 +1,6 @@
+use std::any::TypeId;
+
+use fn_graph::TypeIds;
 use resman::BorrowFail;
 
 use futures::future::LocalBoxFuture;
@@ -1,5 +1,6 @@
 use std::marker::PhantomData;
 
+use fn_graph::TypeIds;
 use futures::future::LocalBoxFuture;
 use resman::BorrowFail;
 
@@ -45,6 +46,22 @@ where
     ) -> Result<LocalBoxFuture<'f2, Result<R, RErr>>, BorrowFail> {
         Ok((self.func)(station))
     }
+
+    fn borrows(&self) -> TypeIds {
+        TypeIds::new()
+    }
+
+    fn borrow_muts(&self) -> TypeIds {
+        TypeIds::new()
+    }
+
+    fn borrow_type_names(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    fn borrow_mut_type_names(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
 }
 
 // Unfortunately we have to `include!` instead of use a `#[path]` attribute.
@@ -0,0 +1,173 @@
+#[cfg(feature = "native")]
+use std::panic;
+use std::{any::TypeId, marker::PhantomData};
+
+use fn_graph::TypeIds;
+use futures::{
+    channel::oneshot::{self, Canceled},
+    future::{BoxFuture, LocalBoxFuture},
+};
+use resman::BorrowFail;
+
+use crate::{
+    rt::{ExecutionModel, Spawner, StationFnTaskError, StationMutRef, TrainResources},
+    StationFnRes,
+};
+
+/// Station function whose returned future is `Send + 'static`.
+///
+/// The function itself runs on the task that drives the train, so it may read
+/// from the station and its resource arguments before returning the future.
+/// When the [`ExecutionModel`] resource is [`ExecutionModel::MultiThread`],
+/// the returned future is spawned using the [`Spawner`] resource, or as a
+/// tokio task if there is none. Otherwise it is awaited in place.
+///
+/// If the spawned task does not complete, such as when the spawner drops it,
+/// the function returns a [`StationFnTaskError`].
+pub struct StationFnSend<Fun, R, RErr, E, Args> {
+    /// Function that returns the `Send` future.
+    func: Fun,
+    /// Marker.
+    marker: PhantomData<(Fun, R, RErr, E, Args)>,
+}
+
+impl<Fun, R, RErr, E, Args> StationFnSend<Fun, R, RErr, E, Args> {
+    /// Returns a new `StationFnSend`.
+    pub fn new(func: Fun) -> Self {
+        Self {
+            func,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<Fun, R, RErr, E, Args> StationFnSend<Fun, R, RErr, E, Args>
+where
+    R: Send + 'static,
+    RErr: From<StationFnTaskError> + Send + 'static,
+    E: 'static,
+{
+    /// Returns a future that awaits the station function's future according
+    /// to the execution model.
+    ///
+    /// # Parameters
+    ///
+    /// * `station`: Station that the function was called with.
+    /// * `train_resources`: Resources that hold the execution model and
+    ///   spawner.
+    /// * `fut`: Future returned by the station function.
+    fn execute<'f>(
+        station: &StationMutRef<'_, E>,
+        train_resources: &TrainResources<E>,
+        fut: BoxFuture<'static, Result<R, RErr>>,
+    ) -> LocalBoxFuture<'f, Result<R, RErr>> {
+        let execution_model = train_resources
+            .try_borrow::<ExecutionModel>()
+            .map(|execution_model| *execution_model)
+            .unwrap_or_default();
+        let spawner = train_resources
+            .try_borrow::<Spawner>()
+            .ok()
+            .map(|spawner| Spawner::clone(&spawner));
+        let station_id = station.spec.id().clone();
+        let station_name = station.spec.name().to_string();
+
+        Box::pin(async move {
+            let task_result = match (execution_model, spawner) {
+                (ExecutionModel::CurrentThread, _) => Ok(fut.await),
+                (ExecutionModel::MultiThread, Some(spawner)) => {
+                    let (result_tx, result_rx) = oneshot::channel();
+                    spawner.spawn(Box::pin(async move {
+                        // The receiver is only dropped if the train is dropped.
+                        let _ = result_tx.send(fut.await);
+                    }));
+
+                    result_rx.await.map_err(|Canceled| {
+                        String::from("the spawner dropped the task before it completed")
+                    })
+                }
+                (ExecutionModel::MultiThread, None) => Self::spawn_default(fut).await,
+            };
+
+            task_result.unwrap_or_else(|detail| {
+                Err(RErr::from(StationFnTaskError::new(
+                    station_id,
+                    station_name,
+                    detail,
+                )))
+            })
+        })
+    }
+
+    /// Spawns the future as a tokio task, and waits for its result.
+    ///
+    /// Returns the reason the task failed if it did not complete. Panics within
+    /// the task are resumed, so that they are caught like panics in other
+    /// station functions.
+    #[cfg(feature = "native")]
+    async fn spawn_default(
+        fut: BoxFuture<'static, Result<R, RErr>>,
+    ) -> Result<Result<R, RErr>, String> {
+        match tokio::spawn(fut).await {
+            Ok(result) => Ok(result),
+            Err(join_error) if join_error.is_panic() => {
+                panic::resume_unwind(join_error.into_panic())
+            }
+            Err(join_error) => Err(join_error.to_string()),
+        }
+    }
+
+    /// Awaits the future in place, as there is no default spawner.
+    #[cfg(not(feature = "native"))]
+    async fn spawn_default(
+        fut: BoxFuture<'static, Result<R, RErr>>,
+    ) -> Result<Result<R, RErr>, String> {
+        Ok(fut.await)
+    }
+}
+
+impl<Fun, R, RErr, E> StationFnRes<R, RErr, E> for StationFnSend<Fun, R, RErr, E, ()>
+where
+    Fun: for<'f> Fn(&'f mut StationMutRef<'_, E>) -> BoxFuture<'static, Result<R, RErr>>,
+    R: Send + 'static,
+    RErr: From<StationFnTaskError> + Send + 'static,
+    E: 'static,
+{
+    fn call<'f1: 'f2, 'f2>(
+        &'f2 self,
+        station: &'f1 mut StationMutRef<'_, E>,
+        train_resources: &'f2 TrainResources<E>,
+    ) -> LocalBoxFuture<'f2, Result<R, RErr>> {
+        let fut = (self.func)(station);
+
+        Self::execute(station, train_resources, fut)
+    }
+
+    fn try_call<'f1: 'f2, 'f2>(
+        &'f2 self,
+        station: &'f1 mut StationMutRef<'_, E>,
+        train_resources: &'f2 TrainResources<E>,
+    ) -> Result<LocalBoxFuture<'f2, Result<R, RErr>>, BorrowFail> {
+        Ok(self.call(station, train_resources))
+    }
+
+    fn borrows(&self) -> TypeIds {
+        TypeIds::new()
+    }
+
+    fn borrow_muts(&self) -> TypeIds {
+        TypeIds::new()
+    }
+
+    fn borrow_type_names(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    fn borrow_mut_type_names(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+}
+
+// Unfortunately we have to `include!` instead of use a `#[path]` attribute.
+// Pending: <https://github.com/rust-lang/rust/issues/48250>
+include!(concat!(env!("OUT_DIR"), "/station_fn/station_fn_send.rs"));
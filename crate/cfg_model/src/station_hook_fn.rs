@@ -0,0 +1,96 @@
+use std::{
+    fmt::{self, Debug},
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+};
+
+use crate::rt::{ResIds, StationMutRef, StationResources};
+
+/// Return type of the `StationHookFn`.
+pub type StationHookFnReturn<'f, E> = Pin<Box<dyn Future<Output = Result<(), E>> + 'f>>;
+
+// **Note:** `Debug`, `Clone`, `PartialEq` are manually implemented to avoid the
+// trait bound on `E`.
+/// Logic to run after a station's operation completes, such as sending a
+/// notification or writing a marker file.
+///
+/// The hook receives the station, whose progress holds the operation's status,
+/// and the resource IDs produced by the operation. Errors returned by the hook
+/// are recorded as warnings, and do not fail the station.
+///
+/// The hook may only borrow resources that the station's functions declare as
+/// parameters, through [`StationResources`].
+#[allow(clippy::type_complexity)] // trait aliases don't exist yet, so we have to suppress clippy.
+pub struct StationHookFn<E>(
+    pub  Arc<
+        dyn for<'f> Fn(
+            &'f StationMutRef<'_, E>,
+            &'f ResIds,
+            &'f StationResources<'_, E>,
+        ) -> StationHookFnReturn<'f, E>,
+    >,
+);
+
+impl<E> StationHookFn<E> {
+    /// Returns a new `StationHookFn`.
+    ///
+    /// # Parameters
+    ///
+    /// * `f`: Logic to run.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: for<'f> Fn(
+                &'f StationMutRef<'_, E>,
+                &'f ResIds,
+                &'f StationResources<'_, E>,
+            ) -> StationHookFnReturn<'f, E>
+            + 'static,
+    {
+        Self(Arc::new(f))
+    }
+
+    /// Returns a `StationHookFn` that always returns `Result::Ok`.
+    #[cfg(feature = "mock")]
+    pub fn ok() -> Self
+    where
+        E: 'static,
+    {
+        StationHookFn::new(|_, _, _| Box::pin(async { Result::<(), E>::Ok(()) }))
+    }
+
+    /// Returns a `StationHookFn` that always returns `Result::Err`.
+    #[cfg(feature = "mock")]
+    pub fn err(e: E) -> Self
+    where
+        E: Clone + 'static,
+    {
+        StationHookFn::new(move |_, _, _| {
+            let e = e.clone();
+            Box::pin(async move { Result::<(), E>::Err(e) })
+        })
+    }
+}
+
+// We `impl Clone` to avoid the `E: Clone` bound generated by the derive.
+#[cfg(not(tarpaulin_include))]
+impl<E> Clone for StationHookFn<E> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<E> Debug for StationHookFn<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(
+            "StationHookFn(fn(&'_ StationMutRef<'_, E>, &'_ ResIds, \
+             &'_ StationResources<'_, E>) -> StationHookFnReturn<'_, E>)",
+        )
+    }
+}
+
+impl<E> PartialEq for StationHookFn<E> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(&self.0, &other.0)
+    }
+}
@@ -6,16 +6,23 @@ use std::{
     str::FromStr,
 };
 
+use serde::{Deserialize, Serialize};
+
 use crate::StationIdInvalidFmt;
 
 /// Unique identifier for a Station, `Cow<'static, str>` newtype.
 ///
-/// Can only contain ASCII letters, numbers, and underscores.
-#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// Can only contain ASCII letters, numbers, and underscores. IDs may be
+/// hierarchical, such as `app/upload`, where each segment separated by a `/`
+/// must be non-empty.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "String")]
 pub struct StationId(Cow<'static, str>);
 
 impl StationId {
+    /// Separator between the segments of a hierarchical station ID.
+    pub const SEPARATOR: char = '/';
+
     /// Returns a `StationId` if the given `&str` is valid.
     pub fn new(s: &'static str) -> Result<Self, StationIdInvalidFmt> {
         Self::try_from(s)
@@ -23,7 +30,47 @@ impl StationId {
 
     /// Returns whether the provided `&str` is a valid station identifier.
     pub fn is_valid_id(s: &str) -> bool {
-        s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        let segment_is_valid = |segment: &str| {
+            segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        };
+        if s.contains(Self::SEPARATOR) {
+            s.split(Self::SEPARATOR)
+                .all(|segment| !segment.is_empty() && segment_is_valid(segment))
+        } else {
+            segment_is_valid(s)
+        }
+    }
+
+    /// Returns the segments of this ID, separated by [`StationId::SEPARATOR`].
+    ///
+    /// For `app/upload`, this returns `app` and `upload`.
+    pub fn segments(&self) -> impl Iterator<Item = &str> + '_ {
+        self.0.split(Self::SEPARATOR)
+    }
+
+    /// Returns the segments of this ID before the last separator, if any.
+    ///
+    /// For `app/db/create`, this returns `app/db`. For `create`, this returns
+    /// `None`.
+    pub fn namespace(&self) -> Option<&str> {
+        self.0
+            .rsplit_once(Self::SEPARATOR)
+            .map(|(namespace, _)| namespace)
+    }
+
+    /// Returns whether this ID is equal to, or is nested within, the given
+    /// prefix.
+    ///
+    /// The prefix is matched by whole segments, so `app/upload` has the prefix
+    /// `app`, but `application` does not.
+    pub fn has_prefix(&self, prefix: &str) -> bool {
+        let prefix = prefix.trim_end_matches(Self::SEPARATOR);
+        match self.0.strip_prefix(prefix) {
+            Some(rest) => rest.is_empty() || rest.starts_with(Self::SEPARATOR),
+            None => false,
+        }
     }
 }
 
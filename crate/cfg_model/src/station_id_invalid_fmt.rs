@@ -25,7 +25,7 @@ impl<'s> fmt::Display for StationIdInvalidFmt<'s> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "`{}` is not a valid station ID. Station IDs can only contain letters, numbers, and underscores.",
+            "`{}` is not a valid station ID. Station IDs can only contain letters, numbers, and underscores, with `/` separating non-empty segments.",
             self.value
         )
     }
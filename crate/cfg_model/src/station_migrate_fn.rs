@@ -0,0 +1,96 @@
+use std::{
+    fmt::{self, Debug},
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+};
+
+use crate::rt::{StationMutRef, StationResources};
+
+/// Return type of the `StationMigrateFn`.
+pub type StationMigrateFnReturn<'f, E> = Pin<Box<dyn Future<Output = Result<(), E>> + 'f>>;
+
+// **Note:** `Debug`, `Clone`, `PartialEq` are manually implemented to avoid the
+// trait bound on `E`.
+/// Logic to migrate state persisted by an older version of a station's spec.
+///
+/// The function receives the station and the version recorded by the
+/// station's last successful work. It runs before the station is checked, when
+/// the recorded version differs from the spec's version. If it returns an
+/// error, the station's check fails.
+///
+/// Like hooks, the function may only borrow resources that the station's
+/// functions declare as parameters, through [`StationResources`].
+#[allow(clippy::type_complexity)] // trait aliases don't exist yet, so we have to suppress clippy.
+pub struct StationMigrateFn<E>(
+    pub  Arc<
+        dyn for<'f> Fn(
+            &'f mut StationMutRef<'_, E>,
+            u32,
+            &'f StationResources<'_, E>,
+        ) -> StationMigrateFnReturn<'f, E>,
+    >,
+);
+
+impl<E> StationMigrateFn<E> {
+    /// Returns a new `StationMigrateFn`.
+    ///
+    /// # Parameters
+    ///
+    /// * `f`: Logic to run.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: for<'f> Fn(
+                &'f mut StationMutRef<'_, E>,
+                u32,
+                &'f StationResources<'_, E>,
+            ) -> StationMigrateFnReturn<'f, E>
+            + 'static,
+    {
+        Self(Arc::new(f))
+    }
+
+    /// Returns a `StationMigrateFn` that always returns `Result::Ok`.
+    #[cfg(feature = "mock")]
+    pub fn ok() -> Self
+    where
+        E: 'static,
+    {
+        StationMigrateFn::new(|_, _, _| Box::pin(async { Result::<(), E>::Ok(()) }))
+    }
+
+    /// Returns a `StationMigrateFn` that always returns `Result::Err`.
+    #[cfg(feature = "mock")]
+    pub fn err(e: E) -> Self
+    where
+        E: Clone + 'static,
+    {
+        StationMigrateFn::new(move |_, _, _| {
+            let e = e.clone();
+            Box::pin(async move { Result::<(), E>::Err(e) })
+        })
+    }
+}
+
+// We `impl Clone` to avoid the `E: Clone` bound generated by the derive.
+#[cfg(not(tarpaulin_include))]
+impl<E> Clone for StationMigrateFn<E> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<E> Debug for StationMigrateFn<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(
+            "StationMigrateFn(fn(&'_ mut StationMutRef<'_, E>, u32, \
+             &'_ StationResources<'_, E>) -> StationMigrateFnReturn<'_, E>)",
+        )
+    }
+}
+
+impl<E> PartialEq for StationMigrateFn<E> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(&self.0, &other.0)
+    }
+}
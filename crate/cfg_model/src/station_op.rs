@@ -1,3 +1,5 @@
+use fn_graph::{FnMeta, TypeIds};
+
 pub use self::{clean_fns::CleanFns, create_fns::CreateFns, op_fns::OpFns};
 
 mod clean_fns;
@@ -16,9 +18,14 @@ pub struct StationOp<E> {
 
 impl<E> StationOp<E> {
     /// Returns a new `StationOp`.
-    pub fn new(create_fns: CreateFns<E>, clean_fns: Option<CleanFns<E>>) -> Self {
+    ///
+    /// # Parameters
+    ///
+    /// * `create_fns`: Steps to run when this station is visited.
+    /// * `clean_fns`: Steps to run to clean up the station.
+    pub fn new<O>(create_fns: CreateFns<E, O>, clean_fns: Option<CleanFns<E>>) -> Self {
         Self {
-            create_fns,
+            create_fns: create_fns.output_type_erase(),
             clean_fns,
         }
     }
@@ -32,6 +39,16 @@ impl<E> StationOp<E> {
     pub fn clean_fns(&self) -> Option<&CleanFns<E>> {
         self.clean_fns.as_ref()
     }
+
+    /// Returns the check, work, and verify functions of both the create and
+    /// clean operations.
+    pub(crate) fn fn_metas(&self) -> impl Iterator<Item = &dyn FnMeta> + '_ {
+        self.create_fns.fn_metas().chain(
+            self.clean_fns
+                .iter()
+                .flat_map(|clean_fns| clean_fns.fn_metas()),
+        )
+    }
 }
 
 impl<E> Clone for StationOp<E> {
@@ -48,3 +65,23 @@ impl<E> PartialEq for StationOp<E> {
         self.create_fns.eq(&other.create_fns) && self.clean_fns.eq(&other.clean_fns)
     }
 }
+
+/// The borrows of a `StationOp` are those of the check, work, and verify
+/// functions of both its create and clean operations.
+impl<E> FnMeta for StationOp<E> {
+    fn borrows(&self) -> TypeIds {
+        self.fn_metas()
+            .fold(TypeIds::new(), |mut type_ids, fn_meta| {
+                type_ids.extend(fn_meta.borrows());
+                type_ids
+            })
+    }
+
+    fn borrow_muts(&self) -> TypeIds {
+        self.fn_metas()
+            .fold(TypeIds::new(), |mut type_ids, fn_meta| {
+                type_ids.extend(fn_meta.borrow_muts());
+                type_ids
+            })
+    }
+}
@@ -1,4 +1,93 @@
-use crate::{rt::ResIds, OpFns};
+use std::{fmt, marker::PhantomData};
+
+use crate::{
+    rt::{ResIds, StationOutput, StationResourceError},
+    station_fn::{StationFnOutput, StationFnOutputRestore},
+    OpFns, SetupFn, StationFn,
+};
 
 /// Functions for creating an operation's resources.
-pub type CreateFns<E> = OpFns<ResIds, (ResIds, E), E>;
+///
+/// `O` is the type of output that the work function produces for downstream
+/// stations. See [`CreateFns::new_with_output`].
+///
+/// [`CreateFns::new_with_output`]: crate::OpFns::new_with_output
+pub type CreateFns<E, O = ()> = OpFns<ResIds, (ResIds, E), E, O>;
+
+impl<E> OpFns<ResIds, (ResIds, E), E>
+where
+    E: 'static,
+{
+    /// Returns new `CreateFns` whose work function also produces an output.
+    ///
+    /// The work function returns `(ResIds, O)`. The output is stored in the
+    /// [`StationOutput<O>`] resource, which downstream stations may access by
+    /// taking `&StationOutput<O>` as a function parameter.
+    ///
+    /// When the check function reports that the work is not required, the work
+    /// function is not run, so `output_fn` is run instead to provide the
+    /// output, such as by reading what the work function recorded in the
+    /// station's directory.
+    ///
+    /// The [`StationOutput<O>`] resource is inserted before `setup_fn` is run.
+    ///
+    /// # Parameters
+    ///
+    /// * `setup_fn`: Verifies input, calculates progress limit, and inserts
+    ///   resources.
+    /// * `work_fn`: Steps to execute when visiting the station.
+    /// * `output_fn`: Provides the output when the work is not required.
+    pub fn new_with_output<O>(
+        setup_fn: SetupFn<E>,
+        work_fn: StationFn<(ResIds, O), (ResIds, E), E>,
+        output_fn: StationFn<O, E, E>,
+    ) -> CreateFns<E, O>
+    where
+        O: fmt::Debug + Send + Sync + 'static,
+        E: From<StationResourceError>,
+    {
+        let setup_fn = SetupFn::new(move |station, train_resources| {
+            train_resources.insert(StationOutput::<O>::new());
+            (setup_fn.0)(station, train_resources)
+        });
+        let work_fn = {
+            let location = work_fn.location();
+            StationFn::from_station_fn_res(Box::new(StationFnOutput::new(work_fn)), location)
+        };
+        let output_fn = {
+            let location = output_fn.location();
+            StationFn::from_station_fn_res(
+                Box::new(StationFnOutputRestore::new(output_fn)),
+                location,
+            )
+        };
+
+        OpFns {
+            setup_fn,
+            check_fn: None,
+            work_fn,
+            verify_fn: None,
+            output_fn: Some(output_fn),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<E, O> OpFns<ResIds, (ResIds, E), E, O>
+where
+    E: 'static,
+{
+    /// Sets the `verify_fn` for these `CreateFns`.
+    ///
+    /// The verify function is run after the work function succeeds, and
+    /// before the station is marked [`OpStatus::WorkSuccess`]. If it fails,
+    /// the station is marked [`OpStatus::VerifyFail`].
+    ///
+    /// [`OpStatus::VerifyFail`]: crate::rt::OpStatus::VerifyFail
+    /// [`OpStatus::WorkSuccess`]: crate::rt::OpStatus::WorkSuccess
+    #[must_use]
+    pub fn with_verify_fn(mut self, verify_fn: StationFn<(), E, E>) -> Self {
+        self.verify_fn = Some(verify_fn);
+        self
+    }
+}
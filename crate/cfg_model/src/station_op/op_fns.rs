@@ -1,12 +1,22 @@
+use std::marker::PhantomData;
+
 use fn_graph::{FnMeta, TypeIds};
 
 use crate::{rt::CheckStatus, SetupFn, StationFn};
+#[cfg(feature = "mock")]
+use crate::{station_fn::StationFnRecorded, MockInvocation, MockInvocations, MockOpFn};
 
 // **Note:** `Clone` and `PartialEq` are manually implemented to avoid the trait
-// bound on `WorkRet`, `WorkErr` and `E`.
+// bound on `WorkRet`, `WorkErr`, `E` and `O`.
 /// Grouping of a station's behaviours.
+///
+/// `O` is the type of output that the work function produces for downstream
+/// stations, which is `()` if it does not produce one. See
+/// [`CreateFns::new_with_output`].
+///
+/// [`CreateFns::new_with_output`]: crate::OpFns::new_with_output
 #[derive(Debug)]
-pub struct OpFns<WorkRet, WorkErr, E> {
+pub struct OpFns<WorkRet, WorkErr, E, O = ()> {
     /// Verifies input, calculates progress limit, and inserts resources.
     pub setup_fn: SetupFn<E>,
     /// Checks whether the operation needs to be executed.
@@ -17,6 +27,25 @@ pub struct OpFns<WorkRet, WorkErr, E> {
     pub check_fn: Option<StationFn<CheckStatus, E, E>>,
     /// Steps to execute when visiting a station.
     pub work_fn: StationFn<WorkRet, WorkErr, E>,
+    /// Verifies the resources are usable after `work_fn` is executed, such as
+    /// by polling a health endpoint.
+    ///
+    /// This is only run for create operations. If it fails, the station is
+    /// marked [`OpStatus::VerifyFail`] instead of [`OpStatus::WorkSuccess`].
+    ///
+    /// [`OpStatus::VerifyFail`]: crate::rt::OpStatus::VerifyFail
+    /// [`OpStatus::WorkSuccess`]: crate::rt::OpStatus::WorkSuccess
+    pub verify_fn: Option<StationFn<(), E, E>>,
+    /// Provides the output of `work_fn` when the work is not required, such
+    /// as from what the work recorded in the station's directory.
+    ///
+    /// This is only run for create operations, and is set by
+    /// [`CreateFns::new_with_output`].
+    ///
+    /// [`CreateFns::new_with_output`]: crate::OpFns::new_with_output
+    pub output_fn: Option<StationFn<(), E, E>>,
+    /// Marker for the type of output.
+    pub(crate) marker: PhantomData<O>,
 }
 
 impl<WorkRet, WorkErr, E> OpFns<WorkRet, WorkErr, E> {
@@ -26,36 +55,166 @@ impl<WorkRet, WorkErr, E> OpFns<WorkRet, WorkErr, E> {
             setup_fn,
             check_fn: None,
             work_fn,
+            verify_fn: None,
+            output_fn: None,
+            marker: PhantomData,
         }
     }
+}
 
+impl<WorkRet, WorkErr, E, O> OpFns<WorkRet, WorkErr, E, O> {
     /// Sets the `check_fn` for this `OpFns`.
     #[must_use]
     pub fn with_check_fn(mut self, check_fn: StationFn<CheckStatus, E, E>) -> Self {
         self.check_fn = Some(check_fn);
         self
     }
+
+    /// Returns these `OpFns` without the type of output.
+    ///
+    /// The output function already stores the output as a resource, so the
+    /// type is only needed while building the `OpFns`.
+    pub(crate) fn output_type_erase(self) -> OpFns<WorkRet, WorkErr, E> {
+        let OpFns {
+            setup_fn,
+            check_fn,
+            work_fn,
+            verify_fn,
+            output_fn,
+            marker: _,
+        } = self;
+
+        OpFns {
+            setup_fn,
+            check_fn,
+            work_fn,
+            verify_fn,
+            output_fn,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the check, work, verify, and output functions, whose parameters
+    /// declare the resources they borrow.
+    pub(crate) fn fn_metas(&self) -> impl Iterator<Item = &dyn FnMeta> + '_ {
+        self.check_fn
+            .iter()
+            .map(|check_fn| check_fn as &dyn FnMeta)
+            .chain(std::iter::once(&self.work_fn as &dyn FnMeta))
+            .chain(
+                self.verify_fn
+                    .iter()
+                    .map(|verify_fn| verify_fn as &dyn FnMeta),
+            )
+            .chain(
+                self.output_fn
+                    .iter()
+                    .map(|output_fn| output_fn as &dyn FnMeta),
+            )
+    }
+}
+
+#[cfg(feature = "mock")]
+impl<WorkRet, WorkErr, E> OpFns<WorkRet, WorkErr, E>
+where
+    WorkRet: 'static,
+    WorkErr: 'static,
+    E: 'static,
+{
+    /// Returns these `OpFns` with each function recording its invocations.
+    ///
+    /// # Parameters
+    ///
+    /// * `mock_invocations`: Where to record invocations.
+    /// * `op_fns`: Which station functions the setup, check, and work functions
+    ///   are.
+    pub(crate) fn recorded(
+        self,
+        mock_invocations: &MockInvocations,
+        [op_fn_setup, op_fn_check, op_fn_work]: [MockOpFn; 3],
+    ) -> Self {
+        let OpFns {
+            setup_fn,
+            check_fn,
+            work_fn,
+            verify_fn,
+            output_fn,
+            marker,
+        } = self;
+
+        let setup_fn = {
+            let mock_invocations = mock_invocations.clone();
+            SetupFn::new(move |station, train_resources| {
+                mock_invocations.record(MockInvocation {
+                    station_id: station.spec.id().clone(),
+                    station_rt_id: station.rt_id,
+                    op_fn: op_fn_setup,
+                    op_status: station.progress.op_status,
+                    progress_limit: station.progress.progress_limit(),
+                    borrow_type_names: Vec::new(),
+                    borrow_mut_type_names: Vec::new(),
+                });
+                (setup_fn.0)(station, train_resources)
+            })
+        };
+        let check_fn = check_fn.map(|check_fn| {
+            let location = check_fn.location();
+            StationFn::from_station_fn_res(
+                Box::new(StationFnRecorded::new(
+                    check_fn,
+                    mock_invocations.clone(),
+                    op_fn_check,
+                )),
+                location,
+            )
+        });
+        let work_fn = {
+            let location = work_fn.location();
+            StationFn::from_station_fn_res(
+                Box::new(StationFnRecorded::new(
+                    work_fn,
+                    mock_invocations.clone(),
+                    op_fn_work,
+                )),
+                location,
+            )
+        };
+
+        Self {
+            setup_fn,
+            check_fn,
+            work_fn,
+            verify_fn,
+            output_fn,
+            marker,
+        }
+    }
 }
 
-impl<WorkRet, WorkErr, E> Clone for OpFns<WorkRet, WorkErr, E> {
+impl<WorkRet, WorkErr, E, O> Clone for OpFns<WorkRet, WorkErr, E, O> {
     fn clone(&self) -> Self {
         Self {
             setup_fn: self.setup_fn.clone(),
             check_fn: self.check_fn.clone(),
             work_fn: self.work_fn.clone(),
+            verify_fn: self.verify_fn.clone(),
+            output_fn: self.output_fn.clone(),
+            marker: PhantomData,
         }
     }
 }
 
-impl<WorkRet, WorkErr, E> PartialEq for OpFns<WorkRet, WorkErr, E> {
+impl<WorkRet, WorkErr, E, O> PartialEq for OpFns<WorkRet, WorkErr, E, O> {
     fn eq(&self, other: &Self) -> bool {
         self.setup_fn.eq(&other.setup_fn)
             && self.check_fn.eq(&other.check_fn)
             && self.work_fn.eq(&other.work_fn)
+            && self.verify_fn.eq(&other.verify_fn)
+            && self.output_fn.eq(&other.output_fn)
     }
 }
 
-impl<WorkRet, WorkErr, E> FnMeta for OpFns<WorkRet, WorkErr, E> {
+impl<WorkRet, WorkErr, E, O> FnMeta for OpFns<WorkRet, WorkErr, E, O> {
     fn borrows(&self) -> TypeIds {
         self.work_fn.borrows()
     }
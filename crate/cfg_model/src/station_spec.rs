@@ -1,8 +1,12 @@
-use std::{convert::TryFrom, fmt};
+use std::{convert::TryFrom, fmt, panic::Location, time::Duration};
 
 use fn_graph::{FnMeta, TypeIds};
 
-use crate::{StationId, StationIdInvalidFmt, StationOp, StationSpecBuilder};
+use crate::{
+    rt::{ProgressTheme, StationEnv},
+    ExternalDependency, FileInput, StationExistsFn, StationHookFn, StationId, StationIdInvalidFmt,
+    StationMigrateFn, StationOp, StationSpecBuilder,
+};
 
 // **Note:** `Clone` is manually implemented to avoid the trait bound on `E`.
 /// Behaviour specification of the station.
@@ -14,8 +18,44 @@ pub struct StationSpec<E> {
     pub(crate) name: String,
     /// Short description of the station's purpose.
     pub(crate) description: String,
+    /// Labels to group and select stations.
+    pub(crate) tags: Vec<String>,
+    /// Appearance of the station's progress bar.
+    pub(crate) progress_theme: Option<ProgressTheme>,
+    /// Names of the phases of the station's work.
+    pub(crate) sub_steps: Vec<String>,
+    /// Overrides to the environment of external commands run by the station.
+    pub(crate) env: StationEnv,
+    /// Paths or glob patterns of files the station's work depends on.
+    pub(crate) inputs: Vec<String>,
+    /// Stations in other profiles that must be created before this station's
+    /// work is done.
+    pub(crate) external_dependencies: Vec<ExternalDependency>,
+    /// Files published by other stations that this station's functions read.
+    pub(crate) file_inputs: Vec<FileInput>,
+    /// Version of the spec, compared with the version recorded by the last
+    /// successful visit.
+    pub(crate) version: Option<u32>,
+    /// Logic to migrate state persisted by an older version of the spec.
+    pub(crate) migrate_fn: Option<StationMigrateFn<E>>,
+    /// Priority of the station when multiple stations are ready to be
+    /// visited.
+    pub(crate) priority: i32,
+    /// Estimated time the station's work takes.
+    pub(crate) estimated_duration: Option<Duration>,
+    /// Whether the station's create check function may run before its
+    /// parents are visited.
+    pub(crate) check_speculative: bool,
     /// Grouping of operations to create and clean up resources.
     pub(crate) station_op: StationOp<E>,
+    /// Logic to run after the station's operation succeeds.
+    pub(crate) on_success_fn: Option<StationHookFn<E>>,
+    /// Logic to run after the station's operation fails.
+    pub(crate) on_failure_fn: Option<StationHookFn<E>>,
+    /// Logic to look up which of the station's resources exist.
+    pub(crate) exists_fn: Option<StationExistsFn<E>>,
+    /// Source code location that the station was defined at.
+    pub(crate) location: &'static Location<'static>,
 }
 
 impl<E> StationSpec<E>
@@ -35,12 +75,29 @@ where
     /// * `station_op`: Grouping of operations to create and clean up resources.
     ///
     /// [`builder`]: Self::builder
+    #[track_caller]
     pub fn new(id: StationId, name: String, description: String, station_op: StationOp<E>) -> Self {
         Self {
             id,
             name,
             description,
+            tags: Vec::new(),
+            progress_theme: None,
+            sub_steps: Vec::new(),
+            env: StationEnv::new(),
+            inputs: Vec::new(),
+            external_dependencies: Vec::new(),
+            file_inputs: Vec::new(),
+            version: None,
+            migrate_fn: None,
+            priority: 0,
+            estimated_duration: None,
+            check_speculative: false,
             station_op,
+            on_success_fn: None,
+            on_failure_fn: None,
+            exists_fn: None,
+            location: Location::caller(),
         }
     }
 
@@ -50,6 +107,7 @@ where
     ///
     /// * `id`: Unique identifier of the station.
     /// * `station_op`: Grouping of operations to create and clean up resources.
+    #[track_caller]
     pub fn builder<Id>(
         id: Id,
         station_op: StationOp<E>,
@@ -68,6 +126,7 @@ where
     ///
     /// * `id`: Unique identifier of the station.
     #[cfg(feature = "mock")]
+    #[track_caller]
     pub fn mock<Id>(id: Id) -> Result<StationSpecBuilder<E>, StationIdInvalidFmt<'static>>
     where
         StationId: TryFrom<Id, Error = StationIdInvalidFmt<'static>>,
@@ -90,10 +149,129 @@ where
         &self.description
     }
 
+    /// Returns the labels to group and select stations.
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Returns whether this station has the given tag.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|station_tag| station_tag == tag)
+    }
+
+    /// Returns the appearance of the station's progress bar, if set.
+    ///
+    /// When this is `None`, the train's progress theme is used.
+    pub fn progress_theme(&self) -> Option<&ProgressTheme> {
+        self.progress_theme.as_ref()
+    }
+
+    /// Returns the names of the phases of the station's work.
+    pub fn sub_steps(&self) -> &[String] {
+        &self.sub_steps
+    }
+
+    /// Returns the overrides to the environment of external commands run by
+    /// the station.
+    ///
+    /// The station's resolved environment is in the `StationEnvs` resource.
+    pub fn env(&self) -> &StationEnv {
+        &self.env
+    }
+
+    /// Returns the paths or glob patterns of files the station's work depends
+    /// on.
+    ///
+    /// Patterns are relative to the workspace directory. When any matching
+    /// file's contents change, the station's work is required.
+    pub fn inputs(&self) -> &[String] {
+        &self.inputs
+    }
+
+    /// Returns the stations in other profiles that must be created before this
+    /// station's work is done.
+    pub fn external_dependencies(&self) -> &[ExternalDependency] {
+        &self.external_dependencies
+    }
+
+    /// Returns the files published by other stations that this station's
+    /// functions read.
+    pub fn file_inputs(&self) -> &[FileInput] {
+        &self.file_inputs
+    }
+
+    /// Returns the version of the spec, if it is versioned.
+    ///
+    /// When the version differs from the version recorded by the station's
+    /// last successful visit, the station's migration function is run, or its
+    /// work is required if it has none.
+    pub fn version(&self) -> Option<u32> {
+        self.version
+    }
+
+    /// Returns the logic to migrate state persisted by an older version of
+    /// the spec, if any.
+    pub fn migrate_fn(&self) -> Option<&StationMigrateFn<E>> {
+        self.migrate_fn.as_ref()
+    }
+
+    /// Returns the priority of the station when multiple stations are ready
+    /// to be visited.
+    ///
+    /// Stations with a higher priority are visited first. Defaults to `0`.
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    /// Returns the estimated time the station's work takes, if declared.
+    pub fn estimated_duration(&self) -> Option<Duration> {
+        self.estimated_duration
+    }
+
+    /// Returns whether the station's create check function may run before its
+    /// parents are visited.
+    ///
+    /// Defaults to `false`.
+    pub fn check_speculative(&self) -> bool {
+        self.check_speculative
+    }
+
     /// Returns this station's behaviours.
     pub fn station_op(&self) -> &StationOp<E> {
         &self.station_op
     }
+
+    /// Returns this station spec with its behaviours replaced.
+    #[must_use]
+    pub fn with_station_op(mut self, station_op: StationOp<E>) -> Self {
+        self.station_op = station_op;
+        self
+    }
+
+    /// Returns the logic to run after the station's operation succeeds, if
+    /// any.
+    pub fn on_success_fn(&self) -> Option<&StationHookFn<E>> {
+        self.on_success_fn.as_ref()
+    }
+
+    /// Returns the logic to run after the station's operation fails, if any.
+    pub fn on_failure_fn(&self) -> Option<&StationHookFn<E>> {
+        self.on_failure_fn.as_ref()
+    }
+
+    /// Returns the logic to look up which of the station's resources exist,
+    /// if any.
+    pub fn exists_fn(&self) -> Option<&StationExistsFn<E>> {
+        self.exists_fn.as_ref()
+    }
+
+    /// Returns the source code location that the station was defined at.
+    ///
+    /// This is where the `StationSpec` or its builder was constructed, and is
+    /// included in diagnostics about the station's specification.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
 }
 
 impl<E> Clone for StationSpec<E> {
@@ -102,7 +280,23 @@ impl<E> Clone for StationSpec<E> {
             id: self.id.clone(),
             name: self.name.clone(),
             description: self.description.clone(),
+            tags: self.tags.clone(),
+            progress_theme: self.progress_theme.clone(),
+            sub_steps: self.sub_steps.clone(),
+            env: self.env.clone(),
+            inputs: self.inputs.clone(),
+            external_dependencies: self.external_dependencies.clone(),
+            file_inputs: self.file_inputs.clone(),
+            version: self.version,
+            migrate_fn: self.migrate_fn.clone(),
+            priority: self.priority,
+            estimated_duration: self.estimated_duration,
+            check_speculative: self.check_speculative,
             station_op: self.station_op.clone(),
+            on_success_fn: self.on_success_fn.clone(),
+            on_failure_fn: self.on_failure_fn.clone(),
+            exists_fn: self.exists_fn.clone(),
+            location: self.location,
         }
     }
 }
@@ -1,10 +1,14 @@
-use std::convert::TryFrom;
+use std::{convert::TryFrom, panic::Location, time::Duration};
+
+use choochoo_resource::Profile;
 
 use crate::{
-    rt::{CheckStatus, ResIds},
-    CleanFns, CreateFns, SetupFn, StationFn, StationId, StationIdInvalidFmt, StationOp,
-    StationSpec,
+    rt::{CheckStatus, ProgressTheme, ResIds, StationEnv},
+    CleanFns, CreateFns, ExternalDependency, FileInput, SetupFn, StationExistsFn, StationFn,
+    StationHookFn, StationId, StationIdInvalidFmt, StationMigrateFn, StationOp, StationSpec,
 };
+#[cfg(feature = "mock")]
+use crate::{MockInvocations, MockOpFn};
 
 /// Builder to make it more ergonomic to construct a [`StationSpec`].
 ///
@@ -19,8 +23,47 @@ pub struct StationSpecBuilder<E> {
     name: Option<String>,
     /// Short description of the station's purpose.
     description: Option<String>,
+    /// Labels to group and select stations.
+    tags: Vec<String>,
+    /// Appearance of the station's progress bar.
+    progress_theme: Option<ProgressTheme>,
+    /// Names of the phases of the station's work.
+    sub_steps: Vec<String>,
+    /// Overrides to the environment of external commands run by the station.
+    env: StationEnv,
+    /// Paths or glob patterns of files the station's work depends on.
+    inputs: Vec<String>,
+    /// Stations in other profiles that must be created before this station's
+    /// work is done.
+    external_dependencies: Vec<ExternalDependency>,
+    /// Files published by other stations that this station's functions read.
+    file_inputs: Vec<FileInput>,
+    /// Version of the spec, compared with the version recorded by the last
+    /// successful visit.
+    version: Option<u32>,
+    /// Logic to migrate state persisted by an older version of the spec.
+    migrate_fn: Option<StationMigrateFn<E>>,
+    /// Priority of the station when multiple stations are ready to be
+    /// visited.
+    priority: i32,
+    /// Estimated time the station's work takes.
+    estimated_duration: Option<Duration>,
+    /// Whether the station's create check function may run before its
+    /// parents are visited.
+    check_speculative: bool,
     /// Grouping of operations to create and clean up resources.
     station_op: StationOp<E>,
+    /// Logic to run after the station's operation succeeds.
+    on_success_fn: Option<StationHookFn<E>>,
+    /// Logic to run after the station's operation fails.
+    on_failure_fn: Option<StationHookFn<E>>,
+    /// Logic to look up which of the station's resources exist.
+    exists_fn: Option<StationExistsFn<E>>,
+    /// Source code location that the station was defined at.
+    location: &'static Location<'static>,
+    /// Records the station functions invoked on this station.
+    #[cfg(feature = "mock")]
+    mock_invocations: Option<MockInvocations>,
 }
 
 impl<E> StationSpecBuilder<E>
@@ -33,17 +76,48 @@ where
     ///
     /// * `id`: Unique identifier of the station.
     /// * `station_op`: Grouping of operations to create and clean up resources.
+    #[track_caller]
     pub fn new<Id>(id: Id, station_op: StationOp<E>) -> Result<Self, StationIdInvalidFmt<'static>>
     where
         StationId: TryFrom<Id, Error = StationIdInvalidFmt<'static>>,
     {
         let id = StationId::try_from(id)?;
-        Ok(StationSpecBuilder {
+        Ok(Self::from_station_id(id, station_op))
+    }
+
+    /// Returns a new [`StationSpecBuilder`] for an already validated
+    /// [`StationId`].
+    ///
+    /// # Parameters
+    ///
+    /// * `id`: Unique identifier of the station.
+    /// * `station_op`: Grouping of operations to create and clean up resources.
+    #[track_caller]
+    pub fn from_station_id(id: StationId, station_op: StationOp<E>) -> Self {
+        StationSpecBuilder {
             id,
             name: None,
             description: None,
+            tags: Vec::new(),
+            progress_theme: None,
+            sub_steps: Vec::new(),
+            env: StationEnv::new(),
+            inputs: Vec::new(),
+            external_dependencies: Vec::new(),
+            file_inputs: Vec::new(),
+            version: None,
+            migrate_fn: None,
+            priority: 0,
+            estimated_duration: None,
+            check_speculative: false,
             station_op,
-        })
+            on_success_fn: None,
+            on_failure_fn: None,
+            exists_fn: None,
+            location: Location::caller(),
+            #[cfg(feature = "mock")]
+            mock_invocations: None,
+        }
     }
 
     /// Returns a new [`StationSpecBuilder`] to build a mock [`StationSpec`].
@@ -54,6 +128,7 @@ where
     ///
     /// * `id`: Unique identifier of the station.
     #[cfg(feature = "mock")]
+    #[track_caller]
     pub fn mock<Id>(id: Id) -> Result<Self, StationIdInvalidFmt<'static>>
     where
         StationId: TryFrom<Id, Error = StationIdInvalidFmt<'static>>,
@@ -92,6 +167,193 @@ where
         self
     }
 
+    /// Adds a tag to the [`StationSpec`].
+    ///
+    /// Tags are labels used to group and select stations.
+    #[must_use]
+    pub fn with_tag<S>(mut self, tag: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Sets the tags of the [`StationSpec`].
+    ///
+    /// Tags are labels used to group and select stations.
+    #[must_use]
+    pub fn with_tags<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tags = tags.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the appearance of the station's progress bar.
+    ///
+    /// This takes precedence over the train's progress theme.
+    #[must_use]
+    pub fn with_progress_theme(mut self, progress_theme: ProgressTheme) -> Self {
+        self.progress_theme = Some(progress_theme);
+        self
+    }
+
+    /// Adds a named sub-step to the [`StationSpec`].
+    ///
+    /// Sub-steps are phases of the station's work, such as "download",
+    /// "extract", and "verify". Their progress is aggregated into the station's
+    /// progress bar, and is updated through [`StationProgress::sub_step_begin`]
+    /// and related methods.
+    ///
+    /// [`StationProgress::sub_step_begin`]: crate::rt::StationProgress::sub_step_begin
+    #[must_use]
+    pub fn with_sub_step<S>(mut self, sub_step: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.sub_steps.push(sub_step.into());
+        self
+    }
+
+    /// Sets the overrides to the environment of external commands run by the
+    /// station.
+    ///
+    /// These are applied over the train's base environment.
+    #[must_use]
+    pub fn with_env(mut self, env: StationEnv) -> Self {
+        self.env = env;
+        self
+    }
+
+    /// Adds a path or glob pattern of files the station's work depends on.
+    ///
+    /// Patterns are relative to the workspace directory, such as
+    /// `app/src/**/*.rs`. Directories are not traversed, so use a `**` pattern
+    /// to match the files within one.
+    ///
+    /// The contents of the matching files are hashed when the station is set
+    /// up, and the station's work is required when the hash differs from the
+    /// hash recorded after the last successful work. If the station has no
+    /// check function, the work is not required when the inputs are unchanged.
+    #[must_use]
+    pub fn with_input<S>(mut self, input: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.inputs.push(input.into());
+        self
+    }
+
+    /// Adds a station in another profile that must be created before this
+    /// station's work is done.
+    ///
+    /// The other profile must be in the same workspace. The dependency is
+    /// satisfied when the latest work recorded in that profile's audit log for
+    /// the station is a successful create. Otherwise this station's check
+    /// fails, and its work is not done.
+    #[must_use]
+    pub fn with_external_dependency(mut self, profile: Profile, station_id: StationId) -> Self {
+        self.external_dependencies
+            .push(ExternalDependency::new(profile, station_id));
+        self
+    }
+
+    /// Adds a file published by another station that this station's functions
+    /// read.
+    ///
+    /// The file's path is resolved before this station's create check, and
+    /// may be read with [`StationMutRef::file_input`]. If the other station
+    /// has not published the file by then, this station's check fails.
+    ///
+    /// The other station should be one that this station depends on, so that
+    /// it is visited first.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_id`: Unique identifier of the station that publishes the
+    ///   file.
+    /// * `name`: Name the file is published under.
+    ///
+    /// [`StationMutRef::file_input`]: crate::rt::StationMutRef::file_input
+    #[must_use]
+    pub fn with_file_input<S>(mut self, station_id: StationId, name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.file_inputs.push(FileInput::new(station_id, name));
+        self
+    }
+
+    /// Sets the version of the [`StationSpec`].
+    ///
+    /// Increment the version when a change to the station makes state
+    /// persisted by older versions stale. The version is recorded in the
+    /// station's directory after it is successfully visited. When the
+    /// recorded version differs, the station's migration function is run
+    /// before it is checked, or its work is required if it has none.
+    #[must_use]
+    pub fn with_version(mut self, version: u32) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Sets the logic to migrate state persisted by an older version of the
+    /// [`StationSpec`].
+    ///
+    /// This only runs when the station has a version set through
+    /// [`with_version`], and it differs from the recorded version.
+    ///
+    /// [`with_version`]: Self::with_version
+    #[must_use]
+    pub fn with_migrate_fn(mut self, migrate_fn: StationMigrateFn<E>) -> Self {
+        self.migrate_fn = Some(migrate_fn);
+        self
+    }
+
+    /// Sets the priority of the [`StationSpec`] when multiple stations are
+    /// ready to be visited.
+    ///
+    /// When more stations are ready than may be visited concurrently,
+    /// stations with a higher priority are visited first, such as stations
+    /// that take the longest. Defaults to `0`.
+    #[must_use]
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Sets the estimated time the [`StationSpec`]'s work takes.
+    ///
+    /// This is shown in the station's progress bar, and is used to estimate
+    /// how long the train takes to reach its destination. When not set, the
+    /// duration of the station's last successful visit is used, if recorded.
+    #[must_use]
+    pub fn with_estimated_duration(mut self, estimated_duration: Duration) -> Self {
+        self.estimated_duration = Some(estimated_duration);
+        self
+    }
+
+    /// Sets whether the [`StationSpec`]'s create check function may run
+    /// before its parents are visited.
+    ///
+    /// Only enable this for stations whose check does not depend on what
+    /// their parents create, such as checking whether a remote artifact
+    /// exists. Speculative checks run concurrently with the visits of other
+    /// stations, so a long chain of stations is not checked one after
+    /// another.
+    ///
+    /// The speculative result is used when the station is visited, unless an
+    /// ancestor's visit did anything other than find its work unnecessary, in
+    /// which case the check is run again. Defaults to `false`.
+    #[must_use]
+    pub fn with_check_speculative(mut self, check_speculative: bool) -> Self {
+        self.check_speculative = check_speculative;
+        self
+    }
+
     /// Sets the [`CreateFns`] of the [`StationSpec`].
     #[must_use]
     pub fn with_station_op(mut self, station_op: StationOp<E>) -> Self {
@@ -101,8 +363,8 @@ where
 
     /// Sets the create functions for the [`StationSpec`].
     #[must_use]
-    pub fn with_create_fns(mut self, create_fns: CreateFns<E>) -> Self {
-        self.station_op.create_fns = create_fns;
+    pub fn with_create_fns<O>(mut self, create_fns: CreateFns<E, O>) -> Self {
+        self.station_op.create_fns = create_fns.output_type_erase();
         self
     }
 
@@ -127,6 +389,13 @@ where
         self
     }
 
+    /// Sets the verify function for the [`StationSpec`].
+    #[must_use]
+    pub fn with_create_verify_fn(mut self, verify_fn: StationFn<(), E, E>) -> Self {
+        self.station_op.create_fns.verify_fn = Some(verify_fn);
+        self
+    }
+
     /// Sets the clean functions for the [`StationSpec`].
     #[must_use]
     pub fn with_clean_fns(mut self, clean_fns: CleanFns<E>) -> Self {
@@ -134,15 +403,119 @@ where
         self
     }
 
+    /// Sets the logic to run after the station's operation succeeds.
+    ///
+    /// This runs when the station's status is [`OpStatus::WorkSuccess`] or
+    /// [`OpStatus::WorkUnnecessary`]. Errors returned by the hook are recorded
+    /// as warnings, and do not fail the station.
+    ///
+    /// [`OpStatus::WorkSuccess`]: crate::rt::OpStatus::WorkSuccess
+    /// [`OpStatus::WorkUnnecessary`]: crate::rt::OpStatus::WorkUnnecessary
+    #[must_use]
+    pub fn with_on_success_fn(mut self, on_success_fn: StationHookFn<E>) -> Self {
+        self.on_success_fn = Some(on_success_fn);
+        self
+    }
+
+    /// Sets the logic to run after the station's operation fails.
+    ///
+    /// This runs when the station's check, work, or verify function fails.
+    /// Errors returned by the hook are recorded as warnings.
+    #[must_use]
+    pub fn with_on_failure_fn(mut self, on_failure_fn: StationHookFn<E>) -> Self {
+        self.on_failure_fn = Some(on_failure_fn);
+        self
+    }
+
+    /// Sets the logic to look up which of the station's resources exist.
+    ///
+    /// This is only run when a train audits the destination, to compare the
+    /// resources that exist with the resource IDs the station persisted.
+    #[must_use]
+    pub fn with_exists_fn(mut self, exists_fn: StationExistsFn<E>) -> Self {
+        self.exists_fn = Some(exists_fn);
+        self
+    }
+
+    /// Sets the source code location that the station was defined at.
+    ///
+    /// This defaults to where the builder was constructed. Code that builds
+    /// stations on behalf of its caller may set this to the caller's location,
+    /// using `#[track_caller]` and [`Location::caller`].
+    #[must_use]
+    pub fn with_location(mut self, location: &'static Location<'static>) -> Self {
+        self.location = location;
+        self
+    }
+
+    /// Records each invocation of the station's functions.
+    ///
+    /// The functions are wrapped when the [`StationSpec`] is built, so this
+    /// applies to functions set before or after this call.
+    #[cfg(feature = "mock")]
+    #[must_use]
+    pub fn with_mock_invocations(mut self, mock_invocations: MockInvocations) -> Self {
+        self.mock_invocations = Some(mock_invocations);
+        self
+    }
+
     /// Builds and returns the [`StationSpec`].
     pub fn build(self) -> StationSpec<E> {
         let StationSpecBuilder {
             id,
             name,
             description,
+            tags,
+            progress_theme,
+            sub_steps,
+            env,
+            inputs,
+            external_dependencies,
+            file_inputs,
+            version,
+            migrate_fn,
+            priority,
+            estimated_duration,
+            check_speculative,
             station_op,
+            on_success_fn,
+            on_failure_fn,
+            exists_fn,
+            location,
+            #[cfg(feature = "mock")]
+            mock_invocations,
         } = self;
 
+        #[cfg(feature = "mock")]
+        let station_op = if let Some(mock_invocations) = mock_invocations {
+            let StationOp {
+                create_fns,
+                clean_fns,
+            } = station_op;
+            let create_fns = create_fns.recorded(
+                &mock_invocations,
+                [
+                    MockOpFn::CreateSetup,
+                    MockOpFn::CreateCheck,
+                    MockOpFn::CreateWork,
+                ],
+            );
+            let clean_fns = clean_fns.map(|clean_fns| {
+                clean_fns.recorded(
+                    &mock_invocations,
+                    [
+                        MockOpFn::CleanSetup,
+                        MockOpFn::CleanCheck,
+                        MockOpFn::CleanWork,
+                    ],
+                )
+            });
+
+            StationOp::new(create_fns, clean_fns)
+        } else {
+            station_op
+        };
+
         let id_ref = &*id;
         let name = name.unwrap_or_else(|| id_ref.clone().into_owned());
         let description = description.unwrap_or_default();
@@ -151,7 +524,23 @@ where
             id,
             name,
             description,
+            tags,
+            progress_theme,
+            sub_steps,
+            env,
+            inputs,
+            external_dependencies,
+            file_inputs,
+            version,
+            migrate_fn,
+            priority,
+            estimated_duration,
+            check_speculative,
             station_op,
+            on_success_fn,
+            on_failure_fn,
+            exists_fn,
+            location,
         }
     }
 }
@@ -0,0 +1,113 @@
+use std::{fmt, panic::Location, sync::Arc};
+
+use crate::{StationIdInvalidFmt, StationOp, StationSpec, StationSpecBuilder};
+
+// **Note:** `Clone` and `Debug` are manually implemented to avoid the trait
+// bound on `P`.
+/// Template to instantiate a [`StationSpec`] once per parameter.
+///
+/// Each instantiated station is given the ID `${id_prefix}_${index}`, where
+/// `index` is the parameter's position in the collection of parameters. The
+/// station's name defaults to its ID, and may be overridden by the `spec_fn`.
+///
+/// This is useful when the same kind of work is done for each item in a
+/// collection that is only known at build time, such as one "upload artifact"
+/// station per file in a manifest.
+pub struct StationSpecTemplate<P, E> {
+    /// Prefix of the generated station IDs.
+    id_prefix: String,
+    /// Grouping of operations that each instantiated station begins with.
+    station_op: StationOp<E>,
+    /// Function to customize each instantiated station for its parameter.
+    #[allow(clippy::type_complexity)]
+    spec_fn: Arc<dyn Fn(StationSpecBuilder<E>, &P) -> StationSpecBuilder<E>>,
+}
+
+impl<P, E> StationSpecTemplate<P, E>
+where
+    E: 'static,
+{
+    /// Returns a new [`StationSpecTemplate`].
+    ///
+    /// # Parameters
+    ///
+    /// * `id_prefix`: Prefix of the generated station IDs.
+    /// * `station_op`: Grouping of operations that each instantiated station
+    ///   begins with.
+    /// * `spec_fn`: Function to customize each instantiated station for its
+    ///   parameter.
+    pub fn new<S, F>(id_prefix: S, station_op: StationOp<E>, spec_fn: F) -> Self
+    where
+        S: Into<String>,
+        F: Fn(StationSpecBuilder<E>, &P) -> StationSpecBuilder<E> + 'static,
+    {
+        Self {
+            id_prefix: id_prefix.into(),
+            station_op,
+            spec_fn: Arc::new(spec_fn),
+        }
+    }
+
+    /// Returns the prefix of the generated station IDs.
+    pub fn id_prefix(&self) -> &str {
+        &self.id_prefix
+    }
+
+    /// Returns the [`StationSpec`]s instantiated for each parameter.
+    ///
+    /// Each station's [`location`] is where this method is called.
+    ///
+    /// [`location`]: StationSpec::location
+    ///
+    /// # Parameters
+    ///
+    /// * `params`: Parameters to instantiate a station for.
+    #[track_caller]
+    pub fn instantiate<'p, I>(
+        &self,
+        params: I,
+    ) -> Result<Vec<StationSpec<E>>, StationIdInvalidFmt<'static>>
+    where
+        I: IntoIterator<Item = &'p P>,
+        P: 'p,
+    {
+        let location = Location::caller();
+        params
+            .into_iter()
+            .enumerate()
+            .map(|(index, param)| {
+                let id = format!("{}_{}", self.id_prefix, index);
+                let builder =
+                    StationSpecBuilder::new(id, self.station_op.clone())?.with_location(location);
+
+                Ok((self.spec_fn)(builder, param).build())
+            })
+            .collect()
+    }
+}
+
+impl<P, E> Clone for StationSpecTemplate<P, E> {
+    fn clone(&self) -> Self {
+        Self {
+            id_prefix: self.id_prefix.clone(),
+            station_op: self.station_op.clone(),
+            spec_fn: Arc::clone(&self.spec_fn),
+        }
+    }
+}
+
+impl<P, E> fmt::Debug for StationSpecTemplate<P, E>
+where
+    E: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StationSpecTemplate")
+            .field("id_prefix", &self.id_prefix)
+            .field("station_op", &self.station_op)
+            .field(
+                "spec_fn",
+                &"fn(StationSpecBuilder<E>, &P) -> StationSpecBuilder<E>",
+            )
+            .finish()
+    }
+}
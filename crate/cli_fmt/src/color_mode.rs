@@ -0,0 +1,41 @@
+/// Whether formatted output includes color.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorMode {
+    /// Color is used unless the `NO_COLOR` environment variable is set to a
+    /// non-empty value, or the `TERM` environment variable is `dumb`.
+    ///
+    /// This is the default.
+    Auto,
+    /// Color is always used.
+    Always,
+    /// Color is never used.
+    Never,
+}
+
+impl ColorMode {
+    /// Returns whether color should be used.
+    ///
+    /// For [`ColorMode::Auto`], this reads the `NO_COLOR` and `TERM`
+    /// environment variables.
+    pub fn enabled(self) -> bool {
+        match self {
+            Self::Auto => {
+                let no_color = std::env::var_os("NO_COLOR")
+                    .map(|no_color| !no_color.is_empty())
+                    .unwrap_or(false);
+                let term_dumb = std::env::var_os("TERM")
+                    .map(|term| term == "dumb")
+                    .unwrap_or(false);
+                !no_color && !term_dumb
+            }
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
+}
+
+impl Default for ColorMode {
+    fn default() -> Self {
+        Self::Auto
+    }
+}
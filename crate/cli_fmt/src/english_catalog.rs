@@ -0,0 +1,9 @@
+use crate::MessageCatalog;
+
+/// Built in [`MessageCatalog`] with English messages.
+///
+/// This is the catalog used by default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EnglishCatalog;
+
+impl MessageCatalog for EnglishCatalog {}
@@ -0,0 +1,129 @@
+use std::sync::Arc;
+
+use choochoo_rt_model::Verbosity;
+
+use crate::{ColorMode, EnglishCatalog, MessageCatalog};
+
+/// Options that control how reports are formatted.
+///
+/// The defaults match a modern terminal: color is used when the environment
+/// allows it, station statuses are shown with emoji, and lines are not
+/// truncated, and messages are in English.
+#[derive(Clone, Debug)]
+pub struct FormatterOpts {
+    /// Whether output includes color.
+    color_mode: ColorMode,
+    /// Whether output is restricted to ASCII characters.
+    ascii_only: bool,
+    /// Maximum number of characters in each station status line.
+    width: Option<usize>,
+    /// Amount of detail in the output.
+    verbosity: Verbosity,
+    /// Text written for status labels and headings.
+    message_catalog: Arc<dyn MessageCatalog>,
+}
+
+impl FormatterOpts {
+    /// Returns new `FormatterOpts` with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether output includes color.
+    ///
+    /// Defaults to [`ColorMode::Auto`].
+    #[must_use]
+    pub fn with_color_mode(mut self, color_mode: ColorMode) -> Self {
+        self.color_mode = color_mode;
+        self
+    }
+
+    /// Sets whether output is restricted to ASCII characters.
+    ///
+    /// When `true`, station statuses are shown with ASCII markers such as
+    /// `[ok]` instead of emoji, and diagnostics are drawn with ASCII
+    /// characters. This is useful for terminals and log processors that do
+    /// not handle Unicode.
+    ///
+    /// Defaults to `false`.
+    #[must_use]
+    pub fn with_ascii_only(mut self, ascii_only: bool) -> Self {
+        self.ascii_only = ascii_only;
+        self
+    }
+
+    /// Sets the maximum number of characters in each station status line.
+    ///
+    /// Longer lines are truncated and end with an ellipsis.
+    ///
+    /// Defaults to no limit.
+    #[must_use]
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Sets the amount of detail in the output.
+    ///
+    /// * [`Verbosity::Quiet`]: Only errors and the summary are written.
+    /// * [`Verbosity::Normal`]: Each station's status is also written, along
+    ///   with warnings.
+    /// * [`Verbosity::Verbose`]: Each station's result, visit duration, and the
+    ///   resource IDs it produced are also written.
+    ///
+    /// Defaults to [`Verbosity::Normal`].
+    #[must_use]
+    pub fn with_verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
+
+    /// Sets the text written for status labels and headings.
+    ///
+    /// Defaults to [`EnglishCatalog`].
+    #[must_use]
+    pub fn with_message_catalog<C>(mut self, message_catalog: C) -> Self
+    where
+        C: MessageCatalog + 'static,
+    {
+        self.message_catalog = Arc::new(message_catalog);
+        self
+    }
+
+    /// Returns whether output includes color.
+    pub fn color_mode(&self) -> ColorMode {
+        self.color_mode
+    }
+
+    /// Returns whether output is restricted to ASCII characters.
+    pub fn ascii_only(&self) -> bool {
+        self.ascii_only
+    }
+
+    /// Returns the maximum number of characters in each station status line.
+    pub fn width(&self) -> Option<usize> {
+        self.width
+    }
+
+    /// Returns the amount of detail in the output.
+    pub fn verbosity(&self) -> Verbosity {
+        self.verbosity
+    }
+
+    /// Returns the text written for status labels and headings.
+    pub fn message_catalog(&self) -> &dyn MessageCatalog {
+        &*self.message_catalog
+    }
+}
+
+impl Default for FormatterOpts {
+    fn default() -> Self {
+        Self {
+            color_mode: ColorMode::default(),
+            ascii_only: false,
+            width: None,
+            verbosity: Verbosity::default(),
+            message_catalog: Arc::new(EnglishCatalog),
+        }
+    }
+}
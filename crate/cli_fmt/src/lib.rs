@@ -1,5 +1,12 @@
 //! Command line interface formatting logic for the choochoo automation library.
 
-pub use crate::plain_text_formatter::PlainTextFormatter;
+pub use crate::{
+    color_mode::ColorMode, english_catalog::EnglishCatalog, formatter_opts::FormatterOpts,
+    message_catalog::MessageCatalog, plain_text_formatter::PlainTextFormatter,
+};
 
+mod color_mode;
+mod english_catalog;
+mod formatter_opts;
+mod message_catalog;
 mod plain_text_formatter;
@@ -0,0 +1,152 @@
+use std::{borrow::Cow, fmt, time::Duration};
+
+use choochoo_cfg_model::rt::{OpStatus, VisitOp};
+
+/// Text written by the formatters, such as status labels and headings.
+///
+/// Each method returns English text by default, so implementors only need to
+/// override the messages they want to translate or re-word.
+///
+/// [`EnglishCatalog`] uses all of the defaults.
+///
+/// [`EnglishCatalog`]: crate::EnglishCatalog
+pub trait MessageCatalog: fmt::Debug + Send + Sync {
+    /// Returns the label for a station's status.
+    ///
+    /// This is shown before each station's name.
+    fn status_label(&self, op_status: OpStatus) -> Cow<'_, str> {
+        let status_label = match op_status {
+            OpStatus::SetupQueued => "⏳",
+            OpStatus::SetupSuccess => "⏳",
+            OpStatus::ParentPending => "⏰",
+            OpStatus::ParentFail => "☠️",
+            OpStatus::OpQueued => "⏳",
+            OpStatus::WorkInProgress => "⏳",
+            OpStatus::OpSkipped => "⏭️",
+            OpStatus::WorkUnnecessary | OpStatus::WorkSuccess => "✅",
+            OpStatus::SetupFail
+            | OpStatus::CheckFail
+            | OpStatus::WorkFail
+            | OpStatus::VerifyFail
+            | OpStatus::Panicked => "❌",
+            OpStatus::WorkInterrupted => "⛔",
+            OpStatus::DeadlineExceeded => "⌛",
+        };
+        Cow::Borrowed(status_label)
+    }
+
+    /// Returns the label for a station's status using only ASCII characters.
+    ///
+    /// This is used instead of [`status_label`] when the formatter is
+    /// restricted to ASCII output.
+    ///
+    /// [`status_label`]: Self::status_label
+    fn status_label_ascii(&self, op_status: OpStatus) -> Cow<'_, str> {
+        let status_label = match op_status {
+            OpStatus::SetupQueued => "[..]",
+            OpStatus::SetupSuccess => "[..]",
+            OpStatus::ParentPending => "[--]",
+            OpStatus::ParentFail => "[xx]",
+            OpStatus::OpQueued => "[..]",
+            OpStatus::WorkInProgress => "[..]",
+            OpStatus::OpSkipped => "[>>]",
+            OpStatus::WorkUnnecessary | OpStatus::WorkSuccess => "[ok]",
+            OpStatus::SetupFail
+            | OpStatus::CheckFail
+            | OpStatus::WorkFail
+            | OpStatus::VerifyFail
+            | OpStatus::Panicked => "[!!]",
+            OpStatus::WorkInterrupted => "[^C]",
+            OpStatus::DeadlineExceeded => "[TO]",
+        };
+        Cow::Borrowed(status_label)
+    }
+
+    /// Returns the heading of the stations that did not complete before the
+    /// train's deadline.
+    fn deadline_exceeded_heading(&self) -> Cow<'_, str> {
+        Cow::Borrowed("Deadline reached before the following stations completed:")
+    }
+
+    /// Returns the line that counts the stations with each outcome.
+    fn summary(
+        &self,
+        succeeded: usize,
+        unnecessary: usize,
+        failed: usize,
+        parent_failed: usize,
+    ) -> Cow<'_, str> {
+        Cow::Owned(format!(
+            "Summary: {succeeded} succeeded, {unnecessary} unnecessary, {failed} failed, \
+            {parent_failed} parent failed",
+            succeeded = succeeded,
+            unnecessary = unnecessary,
+            failed = failed,
+            parent_failed = parent_failed
+        ))
+    }
+
+    /// Returns the heading of the first error of each failing subgraph.
+    fn subgraph_errors_heading(&self) -> Cow<'_, str> {
+        Cow::Borrowed("First error of each failing subgraph:")
+    }
+
+    /// Returns the result of a station's visit, shown below its status when
+    /// the output is verbose.
+    ///
+    /// `duration` is how long the station's check and work took, if it was
+    /// visited.
+    fn station_detail(&self, op_status: OpStatus, duration: Option<Duration>) -> Cow<'_, str> {
+        let station_detail = match op_status {
+            OpStatus::SetupQueued
+            | OpStatus::SetupSuccess
+            | OpStatus::ParentPending
+            | OpStatus::OpQueued
+            | OpStatus::WorkInProgress => "not visited",
+            OpStatus::SetupFail => "setup failed",
+            OpStatus::ParentFail => "parent failed",
+            OpStatus::OpSkipped => "skipped",
+            OpStatus::CheckFail => "check failed",
+            OpStatus::WorkUnnecessary => "check passed, work not required",
+            OpStatus::WorkSuccess => "work done",
+            OpStatus::WorkFail => "work failed",
+            OpStatus::VerifyFail => "work done, verify failed",
+            OpStatus::Panicked => "panicked",
+            OpStatus::WorkInterrupted => "work interrupted",
+            OpStatus::DeadlineExceeded => "deadline exceeded",
+        };
+        match duration {
+            Some(duration) => Cow::Owned(format!("{} in {:.2?}", station_detail, duration)),
+            None => Cow::Borrowed(station_detail),
+        }
+    }
+
+    /// Returns the heading of the resource IDs produced by each station.
+    fn res_ids_heading(&self) -> Cow<'_, str> {
+        Cow::Borrowed("Resource IDs:")
+    }
+
+    /// Returns the heading of the chain of dependent stations that took the
+    /// longest.
+    ///
+    /// `duration` is the sum of the durations of the stations on the chain.
+    fn critical_path_heading(&self, duration: Duration) -> Cow<'_, str> {
+        Cow::Owned(format!("Critical path ({:.2?}):", duration))
+    }
+
+    /// Returns the heading of the stations that are about to be visited.
+    fn train_plan_heading(&self, visit_op: VisitOp) -> Cow<'_, str> {
+        Cow::Owned(format!("Stations to {}:", visit_op))
+    }
+
+    /// Returns the heading of the estimated monthly cost of each station's
+    /// resources.
+    fn res_id_costs_heading(&self) -> Cow<'_, str> {
+        Cow::Borrowed("Estimated monthly cost:")
+    }
+
+    /// Returns the line stating the estimated monthly cost of all resources.
+    fn res_id_costs_total(&self, total: f64) -> Cow<'_, str> {
+        Cow::Owned(format!("Total: {:.2}", total))
+    }
+}
@@ -1,18 +1,31 @@
 use std::{
+    borrow::Cow,
+    collections::{HashSet, VecDeque},
     fmt,
     io::{self, Write},
     marker::PhantomData,
 };
 
 use choochoo_cfg_model::{
-    rt::{OpStatus, TrainResources},
-    srcerr::codespan_reporting::{term, term::termcolor::Buffer},
+    daggy::Walker,
+    indexmap::IndexMap,
+    rt::{OpStatus, ResIdLogical, Station, StationErrorRecord, StationRtId, TrainResources},
+    srcerr::codespan_reporting::{
+        term,
+        term::{termcolor::Buffer, Chars},
+    },
+    StationId,
 };
 use choochoo_resource::{Files, FilesRw};
-use choochoo_rt_model::{error::AsDiagnostic, Destination, TrainReport};
+use choochoo_rt_model::{
+    error::AsDiagnostic, Destination, DestinationLint, Error, ResIdCosts, TrainPlan, TrainReport,
+    Verbosity,
+};
 use futures::{stream, StreamExt, TryStreamExt};
 use tokio::io::{AsyncWrite, AsyncWriteExt, BufWriter};
 
+use crate::FormatterOpts;
+
 /// Format trait for plain text.
 #[derive(Debug)]
 pub struct PlainTextFormatter<W, E>(PhantomData<(W, E)>);
@@ -71,9 +84,14 @@ where
         w: &mut W,
         dest: &Destination<E>,
         train_report: &TrainReport<E>,
+        opts: &FormatterOpts,
     ) -> Result<(), io::Error> {
         let mut write_buf = WriterAndBuffer::new(w);
-        write_buf = Self::write_station_statuses(dest, write_buf).await?;
+        write_buf = Self::write_station_statuses(dest, train_report, opts, write_buf).await?;
+        write_buf = Self::write_res_ids(dest, train_report, opts, write_buf).await?;
+        write_buf = Self::write_critical_path(dest, train_report, opts, write_buf).await?;
+        write_buf =
+            Self::write_stations_deadline_exceeded(dest, train_report, opts, write_buf).await?;
 
         let train_resources = train_report.train_resources();
 
@@ -85,16 +103,19 @@ where
         //
         // * It should store its own `SimpleFile`, and we call `term::emit` with that
         //   (and we retrieve `files` from E itself).
-        let writer = Buffer::ansi(); // TODO: switch between `ansi()` and `no_color()`
-        let config = term::Config::default();
+        let writer = buffer(opts);
+        let config = term_config(opts);
         let config = &config;
         let files = train_resources.borrow::<FilesRw>();
         let files = files.read().await;
-        let files = &*files;
+        let files: &Files = &files;
 
         let station_errors = train_resources.station_errors();
-        let station_rt_id_to_error = station_errors.read().await;
-        let (mut write_buf, _writer) = stream::iter(station_rt_id_to_error.values())
+        let station_error_records = station_errors.read().await;
+        let station_rt_id_to_error = Self::station_errors_latest(&station_error_records);
+        let write_buf =
+            Self::write_summary(dest, &station_rt_id_to_error, files, opts, write_buf).await?;
+        let (write_buf, _writer) = stream::iter(station_rt_id_to_error.values().copied())
             .map(Result::<&E, io::Error>::Ok)
             .try_fold(
                 (write_buf, writer),
@@ -109,6 +130,8 @@ where
                 },
             )
             .await?;
+        let mut write_buf =
+            Self::write_station_warnings(train_resources, files, opts, write_buf).await?;
 
         write_buf.writer.flush().await
     }
@@ -118,6 +141,7 @@ where
     pub async fn fmt_errors(
         w: &mut W,
         train_resources: &TrainResources<E>,
+        opts: &FormatterOpts,
     ) -> Result<(), io::Error> {
         let write_buf = WriterAndBuffer::new(w);
 
@@ -129,16 +153,17 @@ where
         //
         // * It should store its own `SimpleFile`, and we call `term::emit` with that
         //   (and we retrieve `files` from E itself).
-        let writer = Buffer::ansi(); // TODO: switch between `ansi()` and `no_color()`
-        let config = term::Config::default();
+        let writer = buffer(opts);
+        let config = term_config(opts);
         let config = &config;
         let files = &*train_resources.borrow::<FilesRw>();
         let files = files.read().await;
-        let files = &*files;
+        let files: &Files = &files;
 
         let station_errors = train_resources.station_errors();
-        let station_rt_id_to_error = station_errors.read().await;
-        let (mut write_buf, _writer) = stream::iter(station_rt_id_to_error.values())
+        let station_error_records = station_errors.read().await;
+        let station_rt_id_to_error = Self::station_errors_latest(&station_error_records);
+        let (write_buf, _writer) = stream::iter(station_rt_id_to_error.values().copied())
             .map(Result::<&E, io::Error>::Ok)
             .try_fold(
                 (write_buf, writer),
@@ -153,39 +178,578 @@ where
                 },
             )
             .await?;
+        let mut write_buf =
+            Self::write_station_warnings(train_resources, files, opts, write_buf).await?;
+
+        write_buf.writer.flush().await
+    }
+
+    /// Formats an error from driving the train as a human readable text
+    /// report.
+    ///
+    /// This renders the error's code, message, and underlying causes.
+    pub async fn fmt_error(
+        w: &mut W,
+        error: &Error<E>,
+        opts: &FormatterOpts,
+    ) -> Result<(), io::Error> {
+        let mut write_buf = WriterAndBuffer::new(w);
+
+        let mut writer = buffer(opts);
+        let config = term_config(opts);
+        let files = Files::new();
+
+        let diagnostic = error.as_diagnostic(&files);
+        term::emit(&mut writer, &config, &files, &diagnostic)
+            .expect("TODO: Handle codespan_reporting::files::Error");
+        b_write_bytes!(write_buf, writer.as_slice());
+
+        write_buf.writer.flush().await
+    }
+
+    /// Formats the lints found by the [`DestinationValidator`] as a human
+    /// readable text report.
+    ///
+    /// [`DestinationValidator`]: choochoo_rt_model::DestinationValidator
+    pub async fn fmt_lints(
+        w: &mut W,
+        lints: &[DestinationLint],
+        opts: &FormatterOpts,
+    ) -> Result<(), io::Error> {
+        let mut write_buf = WriterAndBuffer::new(w);
+
+        let mut writer = buffer(opts);
+        let config = term_config(opts);
+        let files = Files::new();
+
+        for lint in lints {
+            let diagnostic = lint.as_diagnostic(&files);
+
+            term::emit(&mut writer, &config, &files, &diagnostic)
+                .expect("TODO: Handle codespan_reporting::files::Error");
+            b_write_bytes!(write_buf, writer.as_slice());
+            writer.clear();
+        }
+
+        write_buf.writer.flush().await
+    }
+
+    /// Formats the stations that are about to be visited as a human readable
+    /// text report.
+    ///
+    /// Each wave of stations that may be visited concurrently is written on
+    /// its own line, so that the plan can be shown before the train is run,
+    /// such as the plan returned by `Train::clean_selection_plan`.
+    pub async fn fmt_train_plan(
+        w: &mut W,
+        dest: &Destination<E>,
+        train_plan: &TrainPlan,
+        opts: &FormatterOpts,
+    ) -> Result<(), io::Error> {
+        let mut write_buf = WriterAndBuffer::new(w);
+        let station_specs = dest.station_specs();
+
+        b_writeln!(
+            write_buf,
+            "{heading}",
+            heading = opts
+                .message_catalog()
+                .train_plan_heading(train_plan.visit_op())
+        );
+        for (wave_index, wave) in train_plan.waves().iter().enumerate() {
+            let station_ids = wave
+                .iter()
+                .map(|station_rt_id| station_specs[*station_rt_id].id().to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            b_writeln!(
+                write_buf,
+                "  {wave_number}. {station_ids}",
+                wave_number = wave_index + 1,
+                station_ids = station_ids
+            );
+        }
 
         write_buf.writer.flush().await
     }
 
+    /// Formats the estimated monthly cost of the resources in a profile as a
+    /// human readable text report.
+    ///
+    /// Nothing is written if no resources have a cost.
+    pub async fn fmt_res_id_costs(
+        w: &mut W,
+        res_id_costs: &ResIdCosts,
+        opts: &FormatterOpts,
+    ) -> Result<(), io::Error> {
+        let mut write_buf = WriterAndBuffer::new(w);
+        if res_id_costs.is_empty() {
+            return write_buf.writer.flush().await;
+        }
+
+        let message_catalog = opts.message_catalog();
+        b_writeln!(
+            write_buf,
+            "{heading}",
+            heading = message_catalog.res_id_costs_heading()
+        );
+        for (station_id, cost_monthly) in res_id_costs.iter() {
+            b_writeln!(
+                write_buf,
+                "* {station_id}: {cost_monthly:.2}",
+                station_id = station_id,
+                cost_monthly = cost_monthly
+            );
+        }
+        b_writeln!(
+            write_buf,
+            "{total}",
+            total = message_catalog.res_id_costs_total(res_id_costs.total())
+        );
+
+        write_buf.writer.flush().await
+    }
+
+    /// Writes the stations that did not complete before the train's deadline.
+    async fn write_stations_deadline_exceeded<'w>(
+        dest: &Destination<E>,
+        train_report: &TrainReport<E>,
+        opts: &FormatterOpts,
+        mut write_buf: WriterAndBuffer<'w, W>,
+    ) -> Result<WriterAndBuffer<'w, W>, io::Error> {
+        let stations_deadline_exceeded = train_report.stations_deadline_exceeded();
+        if stations_deadline_exceeded.is_empty() {
+            return Ok(write_buf);
+        }
+
+        b_writeln!(write_buf);
+        b_writeln!(
+            write_buf,
+            "{heading}",
+            heading = opts.message_catalog().deadline_exceeded_heading()
+        );
+        for station_rt_id in stations_deadline_exceeded {
+            let station_spec = &dest.station_specs()[*station_rt_id];
+            b_writeln!(
+                write_buf,
+                "* {id}: {name}",
+                id = station_spec.id(),
+                name = station_spec.name()
+            );
+        }
+
+        Ok(write_buf)
+    }
+
+    /// Returns the most recent error recorded for each station, in the order
+    /// the stations first recorded an error.
+    fn station_errors_latest(
+        station_error_records: &[StationErrorRecord<E>],
+    ) -> IndexMap<StationRtId, &E> {
+        station_error_records
+            .iter()
+            .map(|record| (record.station_rt_id(), record.error()))
+            .collect()
+    }
+
+    /// Writes the number of stations with each outcome, and the first error of
+    /// each failing subgraph.
+    ///
+    /// A failing subgraph is a set of connected stations that failed, or whose
+    /// parent failed. Subgraphs without a recorded error are not listed.
+    async fn write_summary<'w>(
+        dest: &Destination<E>,
+        station_rt_id_to_error: &IndexMap<StationRtId, &E>,
+        files: &Files,
+        opts: &FormatterOpts,
+        mut write_buf: WriterAndBuffer<'w, W>,
+    ) -> Result<WriterAndBuffer<'w, W>, io::Error> {
+        let mut succeeded = 0;
+        let mut unnecessary = 0;
+        let mut failed = 0;
+        let mut parent_failed = 0;
+        let mut stations_failing = HashSet::new();
+        dest.stations()
+            .for_each(|station| match station.progress.op_status {
+                OpStatus::WorkSuccess => succeeded += 1,
+                OpStatus::WorkUnnecessary => unnecessary += 1,
+                OpStatus::SetupFail
+                | OpStatus::CheckFail
+                | OpStatus::WorkFail
+                | OpStatus::VerifyFail
+                | OpStatus::Panicked => {
+                    failed += 1;
+                    stations_failing.insert(station.rt_id);
+                }
+                OpStatus::ParentFail => {
+                    parent_failed += 1;
+                    stations_failing.insert(station.rt_id);
+                }
+                _ => {}
+            });
+
+        b_writeln!(write_buf);
+        b_writeln!(
+            write_buf,
+            "{summary}",
+            summary = opts
+                .message_catalog()
+                .summary(succeeded, unnecessary, failed, parent_failed)
+        );
+
+        let subgraph_first_errors =
+            Self::subgraph_first_errors(dest, station_rt_id_to_error, stations_failing);
+        if subgraph_first_errors.is_empty() {
+            return Ok(write_buf);
+        }
+
+        b_writeln!(
+            write_buf,
+            "{heading}",
+            heading = opts.message_catalog().subgraph_errors_heading()
+        );
+        for (station_rt_id, error) in subgraph_first_errors {
+            let station_spec = &dest.station_specs()[station_rt_id];
+            let diagnostic = error.as_diagnostic(files);
+            if diagnostic.message.is_empty() {
+                b_writeln!(
+                    write_buf,
+                    "* {id}: {name}",
+                    id = station_spec.id(),
+                    name = station_spec.name()
+                );
+            } else {
+                b_writeln!(
+                    write_buf,
+                    "* {id}: {name}: {message}",
+                    id = station_spec.id(),
+                    name = station_spec.name(),
+                    message = diagnostic.message
+                );
+            }
+        }
+
+        Ok(write_buf)
+    }
+
+    /// Returns the first recorded error of each failing subgraph, in station
+    /// order.
+    fn subgraph_first_errors<'e>(
+        dest: &Destination<E>,
+        station_rt_id_to_error: &IndexMap<StationRtId, &'e E>,
+        mut stations_failing: HashSet<StationRtId>,
+    ) -> Vec<(StationRtId, &'e E)> {
+        let station_specs = dest.station_specs();
+        let mut subgraph_first_errors = Vec::new();
+        let mut station_rt_ids = station_specs.graph().node_indices();
+        while !stations_failing.is_empty() {
+            let station_rt_id_first = match station_rt_ids
+                .by_ref()
+                .find(|station_rt_id| stations_failing.contains(station_rt_id))
+            {
+                Some(station_rt_id) => station_rt_id,
+                None => break,
+            };
+
+            // Visit the stations connected to the first failing station that
+            // are also failing, regardless of edge direction.
+            let mut subgraph = Vec::new();
+            let mut to_visit = VecDeque::from([station_rt_id_first]);
+            stations_failing.remove(&station_rt_id_first);
+            while let Some(station_rt_id) = to_visit.pop_front() {
+                subgraph.push(station_rt_id);
+                station_specs
+                    .parents(station_rt_id)
+                    .iter(station_specs)
+                    .chain(station_specs.children(station_rt_id).iter(station_specs))
+                    .for_each(|(_edge, station_rt_id_neighbour)| {
+                        if stations_failing.remove(&station_rt_id_neighbour) {
+                            to_visit.push_back(station_rt_id_neighbour);
+                        }
+                    });
+            }
+
+            subgraph.sort_unstable();
+            let first_error = subgraph.into_iter().find_map(|station_rt_id| {
+                station_rt_id_to_error
+                    .get(&station_rt_id)
+                    .map(|error| (station_rt_id, *error))
+            });
+            if let Some(first_error) = first_error {
+                subgraph_first_errors.push(first_error);
+            }
+        }
+
+        subgraph_first_errors
+    }
+
+    /// Writes the warnings recorded by stations.
+    ///
+    /// These are written regardless of the train's severity threshold, so that
+    /// all diagnostics are visible.
+    async fn write_station_warnings<'w>(
+        train_resources: &TrainResources<E>,
+        files: &Files,
+        opts: &FormatterOpts,
+        write_buf: WriterAndBuffer<'w, W>,
+    ) -> Result<WriterAndBuffer<'w, W>, io::Error> {
+        if opts.verbosity() == Verbosity::Quiet {
+            return Ok(write_buf);
+        }
+
+        let writer = buffer(opts);
+        let config = term_config(opts);
+        let config = &config;
+
+        let station_warnings = train_resources.station_warnings();
+        let station_rt_id_to_warnings = station_warnings.read().await;
+        let (write_buf, _writer) = stream::iter(station_rt_id_to_warnings.values().flatten())
+            .map(Result::<&E, io::Error>::Ok)
+            .try_fold(
+                (write_buf, writer),
+                |(mut write_buf, mut writer), warning| async move {
+                    let diagnostic = warning.as_diagnostic(files);
+
+                    term::emit(&mut writer, config, files, &diagnostic)
+                        .expect("TODO: Handle codespan_reporting::files::Error");
+                    b_write_bytes!(write_buf, writer.as_slice());
+                    writer.clear();
+
+                    Ok((write_buf, writer))
+                },
+            )
+            .await?;
+
+        Ok(write_buf)
+    }
+
+    /// Writes the status of each station.
+    ///
+    /// Stations with hierarchical IDs are grouped under a heading of their
+    /// namespace, in the order each namespace first appears.
+    ///
+    /// Nothing is written when the verbosity is [`Verbosity::Quiet`]. When it
+    /// is [`Verbosity::Verbose`], each station's result and visit duration is
+    /// written below its status.
     // clippy warns on this, but if we elide the lifetime, it doesn't compile.
     #[allow(clippy::needless_lifetimes)]
     async fn write_station_statuses<'w>(
         dest: &Destination<E>,
+        train_report: &TrainReport<E>,
+        opts: &FormatterOpts,
         write_buf: WriterAndBuffer<'w, W>,
     ) -> Result<WriterAndBuffer<'w, W>, io::Error> {
-        stream::iter(dest.stations())
+        if opts.verbosity() == Verbosity::Quiet {
+            return Ok(write_buf);
+        }
+
+        let station_durations = train_report.station_durations();
+        let station_groups = dest.stations().fold(
+            IndexMap::<Option<&str>, Vec<Station<'_, E>>>::new(),
+            |mut station_groups, station| {
+                let station_spec = station.spec;
+                station_groups
+                    .entry(station_spec.id().namespace())
+                    .or_default()
+                    .push(station);
+                station_groups
+            },
+        );
+        stream::iter(station_groups)
             .map(Result::<_, io::Error>::Ok)
-            .try_fold(write_buf, |mut write_buf, station| async move {
-                let icon = match station.progress.op_status {
-                    OpStatus::SetupQueued => "⏳",
-                    OpStatus::SetupSuccess => "⏳",
-                    OpStatus::ParentPending => "⏰",
-                    OpStatus::ParentFail => "☠️",
-                    OpStatus::OpQueued => "⏳",
-                    OpStatus::WorkInProgress => "⏳",
-                    OpStatus::WorkUnnecessary | OpStatus::WorkSuccess => "✅",
-                    OpStatus::SetupFail | OpStatus::CheckFail | OpStatus::WorkFail => "❌",
-                };
+            .try_fold(
+                write_buf,
+                |mut write_buf, (namespace, stations)| async move {
+                    let indent = if let Some(namespace) = namespace {
+                        b_writeln!(
+                            write_buf,
+                            "{namespace}{separator}",
+                            separator = StationId::SEPARATOR
+                        );
+                        "  "
+                    } else {
+                        ""
+                    };
 
+                    for station in stations {
+                        let message_catalog = opts.message_catalog();
+                        let op_status = station.progress.op_status;
+                        let icon = if opts.ascii_only() {
+                            message_catalog.status_label_ascii(op_status)
+                        } else {
+                            message_catalog.status_label(op_status)
+                        };
+                        let line = format!(
+                            "{indent}{status} {name}: {desc}",
+                            status = icon,
+                            name = station.spec.name(),
+                            desc = station.spec.description()
+                        );
+
+                        b_writeln!(write_buf, "{line}", line = line_truncate(&line, opts));
+
+                        if opts.verbosity() == Verbosity::Verbose {
+                            let duration = station_durations.get(&station.rt_id).copied();
+                            b_writeln!(
+                                write_buf,
+                                "{indent}    {detail}",
+                                detail = message_catalog.station_detail(op_status, duration)
+                            );
+                        }
+                    }
+                    Ok(write_buf)
+                },
+            )
+            .await
+    }
+
+    /// Writes the resource IDs produced by each station, grouped by station,
+    /// when the verbosity is [`Verbosity::Verbose`].
+    async fn write_res_ids<'w>(
+        dest: &Destination<E>,
+        train_report: &TrainReport<E>,
+        opts: &FormatterOpts,
+        mut write_buf: WriterAndBuffer<'w, W>,
+    ) -> Result<WriterAndBuffer<'w, W>, io::Error> {
+        let station_res_ids = train_report.station_res_ids();
+        if opts.verbosity() != Verbosity::Verbose || station_res_ids.is_empty() {
+            return Ok(write_buf);
+        }
+
+        b_writeln!(write_buf);
+        b_writeln!(
+            write_buf,
+            "{heading}",
+            heading = opts.message_catalog().res_ids_heading()
+        );
+        for station in dest.stations() {
+            let res_ids = match station_res_ids.get(&station.rt_id) {
+                Some(res_ids) => res_ids,
+                None => continue,
+            };
+
+            b_writeln!(write_buf, "* {name}:", name = station.spec.name());
+
+            // Grouped IDs are written under a line for each namespace segment. Sorting
+            // by namespace keeps each group together, and the insertion order within it.
+            let mut res_ids = res_ids
+                .iter()
+                .map(|(res_id_logical, res_id_physical)| {
+                    let namespace_segments = res_id_logical
+                        .namespace()
+                        .map(|namespace| namespace.split(ResIdLogical::SEPARATOR).collect())
+                        .unwrap_or_default();
+                    (namespace_segments, res_id_logical, res_id_physical)
+                })
+                .collect::<Vec<(Vec<&str>, _, _)>>();
+            res_ids.sort_by(|(segments_a, ..), (segments_b, ..)| segments_a.cmp(segments_b));
+
+            let mut namespace_segments_previous: &[&str] = &[];
+            for (namespace_segments, res_id_logical, res_id_physical) in res_ids.iter() {
+                let segments_common = namespace_segments_previous
+                    .iter()
+                    .zip(namespace_segments.iter())
+                    .take_while(|(segment_previous, segment)| segment_previous == segment)
+                    .count();
+                for (depth, segment) in namespace_segments.iter().enumerate().skip(segments_common)
+                {
+                    b_writeln!(
+                        write_buf,
+                        "{indent}{segment}/",
+                        indent = "    ".repeat(depth + 1),
+                        segment = segment
+                    );
+                }
                 b_writeln!(
                     write_buf,
-                    "{status} {name}: {desc}",
-                    status = icon,
-                    name = station.spec.name(),
-                    desc = station.spec.description()
+                    "{indent}{logical}: {physical:?}",
+                    indent = "    ".repeat(namespace_segments.len() + 1),
+                    logical = res_id_logical.name(),
+                    physical = res_id_physical
                 );
-                Ok(write_buf)
-            })
-            .await
+                namespace_segments_previous = namespace_segments;
+            }
+        }
+
+        Ok(write_buf)
+    }
+
+    /// Writes the chain of dependent stations that took the longest, when the
+    /// verbosity is [`Verbosity::Verbose`].
+    async fn write_critical_path<'w>(
+        dest: &Destination<E>,
+        train_report: &TrainReport<E>,
+        opts: &FormatterOpts,
+        mut write_buf: WriterAndBuffer<'w, W>,
+    ) -> Result<WriterAndBuffer<'w, W>, io::Error> {
+        let critical_path = train_report.critical_path();
+        if opts.verbosity() != Verbosity::Verbose || critical_path.is_empty() {
+            return Ok(write_buf);
+        }
+
+        let station_specs = dest.station_specs();
+        let station_durations = train_report.station_durations();
+        b_writeln!(write_buf);
+        b_writeln!(
+            write_buf,
+            "{heading}",
+            heading = opts
+                .message_catalog()
+                .critical_path_heading(critical_path.duration())
+        );
+        for station_rt_id in critical_path.station_rt_ids().iter().copied() {
+            let duration = station_durations
+                .get(&station_rt_id)
+                .copied()
+                .unwrap_or_default();
+            b_writeln!(
+                write_buf,
+                "* {name}: {duration:.2?}",
+                name = station_specs[station_rt_id].name()
+            );
+        }
+
+        Ok(write_buf)
+    }
+}
+
+/// Returns the buffer to render diagnostics into, with or without color.
+fn buffer(opts: &FormatterOpts) -> Buffer {
+    if opts.color_mode().enabled() {
+        Buffer::ansi()
+    } else {
+        Buffer::no_color()
+    }
+}
+
+/// Returns the configuration to render diagnostics with.
+fn term_config(opts: &FormatterOpts) -> term::Config {
+    if opts.ascii_only() {
+        term::Config {
+            chars: Chars::ascii(),
+            ..term::Config::default()
+        }
+    } else {
+        term::Config::default()
+    }
+}
+
+/// Returns the line truncated to the configured width, ending with an
+/// ellipsis if it was truncated.
+fn line_truncate<'l>(line: &'l str, opts: &FormatterOpts) -> Cow<'l, str> {
+    let width = match opts.width() {
+        Some(width) if line.chars().count() > width => width,
+        _ => return Cow::Borrowed(line),
+    };
+
+    let ellipsis = if opts.ascii_only() { "..." } else { "…" };
+    let ellipsis_len = ellipsis.chars().count();
+    let mut line_truncated = line
+        .chars()
+        .take(width.saturating_sub(ellipsis_len))
+        .collect::<String>();
+    if width >= ellipsis_len {
+        line_truncated.push_str(ellipsis);
     }
+    Cow::Owned(line_truncated)
 }
@@ -0,0 +1,56 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Index, Member};
+
+use crate::CratePath;
+
+/// Generates the `AsDiagnostic` implementation for a struct with a single
+/// field, delegating to that field.
+///
+/// This is intended for newtypes around `srcerr::SourceError`.
+pub(crate) fn derive(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let crate_path = CratePath::from_attrs(&input.attrs)?;
+    let srcerr = crate_path.srcerr();
+    let as_diagnostic = crate_path.as_diagnostic();
+
+    let fields = match &input.data {
+        Data::Struct(data_struct) => &data_struct.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`AsDiagnostic` can only be derived for structs",
+            ));
+        }
+    };
+    let field = match fields.iter().next() {
+        Some(field) if fields.len() == 1 => field,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`AsDiagnostic` can only be derived for structs with a single field",
+            ));
+        }
+    };
+    let member = match &field.ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(Index::from(0)),
+    };
+    let field_ty = &field.ty;
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #as_diagnostic<'static> for #ident #ty_generics #where_clause {
+            type Files = <#field_ty as #as_diagnostic<'static>>::Files;
+
+            fn as_diagnostic(
+                &self,
+                files: &Self::Files,
+            ) -> #srcerr::codespan_reporting::diagnostic::Diagnostic<
+                <Self::Files as #srcerr::codespan_reporting::files::Files<'static>>::FileId,
+            > {
+                <#field_ty as #as_diagnostic<'static>>::as_diagnostic(&self.#member, files)
+            }
+        }
+    })
+}
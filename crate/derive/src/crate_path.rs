@@ -0,0 +1,64 @@
+use syn::{Attribute, Lit, Meta, NestedMeta, Path};
+
+/// Path to the `choochoo` crate that generated code refers to.
+///
+/// This defaults to `::choochoo`, and may be overridden with
+/// `#[choochoo(crate = "path::to::choochoo")]` on the type.
+pub(crate) struct CratePath(pub(crate) Path);
+
+impl CratePath {
+    /// Returns the `choochoo` crate path from the type's attributes.
+    pub(crate) fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut path = syn::parse_quote!(::choochoo);
+
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("choochoo")) {
+            let nested_metas = match attr.parse_meta()? {
+                Meta::List(meta_list) => meta_list.nested,
+                meta => {
+                    return Err(syn::Error::new_spanned(
+                        meta,
+                        "expected `#[choochoo(crate = \"path\")]`",
+                    ));
+                }
+            };
+
+            for nested_meta in nested_metas {
+                match nested_meta {
+                    NestedMeta::Meta(Meta::NameValue(name_value))
+                        if name_value.path.is_ident("crate") =>
+                    {
+                        path = match &name_value.lit {
+                            Lit::Str(lit_str) => lit_str.parse::<Path>()?,
+                            lit => {
+                                return Err(syn::Error::new_spanned(
+                                    lit,
+                                    "expected crate path as a string",
+                                ));
+                            }
+                        };
+                    }
+                    nested_meta => {
+                        return Err(syn::Error::new_spanned(
+                            nested_meta,
+                            "unknown `choochoo` attribute, expected `crate = \"path\"`",
+                        ));
+                    }
+                }
+            }
+        }
+
+        Ok(Self(path))
+    }
+
+    /// Returns the path to the `srcerr` crate, re-exported by `choochoo`.
+    pub(crate) fn srcerr(&self) -> Path {
+        let path = &self.0;
+        syn::parse_quote!(#path::cfg_model::srcerr)
+    }
+
+    /// Returns the path to the `AsDiagnostic` trait.
+    pub(crate) fn as_diagnostic(&self) -> Path {
+        let path = &self.0;
+        syn::parse_quote!(#path::rt_model::error::AsDiagnostic)
+    }
+}
@@ -0,0 +1,224 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, Data, DeriveInput, Fields, Lit, LitStr, Meta, NestedMeta};
+
+use crate::CratePath;
+
+/// Default prefix for error codes.
+const PREFIX_DEFAULT: &str = "E";
+/// Default maximum error code.
+const ERROR_CODE_MAX_DEFAULT: usize = 99;
+
+/// Generates the `srcerr::ErrorCode` implementation for an enum.
+pub(crate) fn derive(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let crate_path = CratePath::from_attrs(&input.attrs)?;
+    let srcerr = crate_path.srcerr();
+
+    let data_enum = match &input.data {
+        Data::Enum(data_enum) => data_enum,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`ErrorCode` can only be derived for enums",
+            ));
+        }
+    };
+
+    let container_attrs = ContainerAttrs::from_attrs(&input.attrs)?;
+    let prefix = container_attrs.prefix;
+    let error_code_max = container_attrs.error_code_max;
+
+    let mut code_next = 1usize;
+    let mut code_arms = Vec::with_capacity(data_enum.variants.len());
+    let mut description_arms = Vec::with_capacity(data_enum.variants.len());
+    for variant in &data_enum.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "`ErrorCode` variants must not have fields",
+            ));
+        }
+
+        let variant_attrs = VariantAttrs::from_attrs(&variant.attrs)?;
+        let code = variant_attrs.code.unwrap_or(code_next);
+        if code > error_code_max {
+            return Err(syn::Error::new_spanned(
+                variant,
+                format!(
+                    "error code `{}` exceeds the maximum `{}`, set `#[error_code(max = ..)]`",
+                    code, error_code_max
+                ),
+            ));
+        }
+        code_next = code + 1;
+
+        let description = match variant_attrs.description {
+            Some(description) => description,
+            None => doc_description(&variant.attrs).ok_or_else(|| {
+                syn::Error::new_spanned(
+                    variant,
+                    "`ErrorCode` variants need a doc comment or \
+                    `#[error_code(description = \"..\")]`",
+                )
+            })?,
+        };
+
+        let variant_ident = &variant.ident;
+        code_arms.push(quote!(Self::#variant_ident => #code));
+        description_arms.push(quote!(Self::#variant_ident => #description));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #srcerr::ErrorCode for #ident #ty_generics #where_clause {
+            const ERROR_CODE_MAX: usize = #error_code_max;
+            const PREFIX: &'static str = #prefix;
+
+            fn code(self) -> usize {
+                match self {
+                    #(#code_arms,)*
+                }
+            }
+
+            fn description(self) -> &'static str {
+                match self {
+                    #(#description_arms,)*
+                }
+            }
+        }
+    })
+}
+
+/// Returns the doc comment of a variant, with its lines joined by spaces.
+fn doc_description(attrs: &[Attribute]) -> Option<String> {
+    let lines = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(name_value)) => match name_value.lit {
+                Lit::Str(lit_str) => Some(lit_str.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        // Only use the first paragraph.
+        .take_while(|line| !line.is_empty())
+        .collect::<Vec<String>>();
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join(" "))
+    }
+}
+
+/// Returns the `#[error_code(..)]` name-value pairs in the attributes.
+fn error_code_metas(attrs: &[Attribute]) -> syn::Result<Vec<syn::MetaNameValue>> {
+    let mut name_values = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("error_code")) {
+        let nested_metas = match attr.parse_meta()? {
+            Meta::List(meta_list) => meta_list.nested,
+            meta => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    "expected `#[error_code(name = value)]`",
+                ));
+            }
+        };
+
+        for nested_meta in nested_metas {
+            match nested_meta {
+                NestedMeta::Meta(Meta::NameValue(name_value)) => name_values.push(name_value),
+                nested_meta => {
+                    return Err(syn::Error::new_spanned(
+                        nested_meta,
+                        "expected `name = value`",
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(name_values)
+}
+
+/// Returns the `usize` value of an integer literal.
+fn lit_usize(lit: &Lit) -> syn::Result<usize> {
+    match lit {
+        Lit::Int(lit_int) => lit_int.base10_parse::<usize>(),
+        lit => Err(syn::Error::new_spanned(lit, "expected an integer")),
+    }
+}
+
+/// Returns the value of a string literal.
+fn lit_str(lit: &Lit) -> syn::Result<&LitStr> {
+    match lit {
+        Lit::Str(lit_str) => Ok(lit_str),
+        lit => Err(syn::Error::new_spanned(lit, "expected a string")),
+    }
+}
+
+/// `#[error_code(..)]` attributes on the enum.
+struct ContainerAttrs {
+    /// Prefix for error codes, e.g. `"E"`.
+    prefix: String,
+    /// Maximum error code, used to zero pad codes.
+    error_code_max: usize,
+}
+
+impl ContainerAttrs {
+    fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut container_attrs = Self {
+            prefix: String::from(PREFIX_DEFAULT),
+            error_code_max: ERROR_CODE_MAX_DEFAULT,
+        };
+
+        for name_value in error_code_metas(attrs)? {
+            if name_value.path.is_ident("prefix") {
+                container_attrs.prefix = lit_str(&name_value.lit)?.value();
+            } else if name_value.path.is_ident("max") {
+                container_attrs.error_code_max = lit_usize(&name_value.lit)?;
+            } else {
+                return Err(syn::Error::new_spanned(
+                    name_value.path,
+                    "unknown `error_code` attribute, expected `prefix` or `max`",
+                ));
+            }
+        }
+
+        Ok(container_attrs)
+    }
+}
+
+/// `#[error_code(..)]` attributes on a variant.
+struct VariantAttrs {
+    /// Code of the variant, defaulting to one more than the previous variant.
+    code: Option<usize>,
+    /// Description of the variant, defaulting to its doc comment.
+    description: Option<String>,
+}
+
+impl VariantAttrs {
+    fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut variant_attrs = Self {
+            code: None,
+            description: None,
+        };
+
+        for name_value in error_code_metas(attrs)? {
+            if name_value.path.is_ident("code") {
+                variant_attrs.code = Some(lit_usize(&name_value.lit)?);
+            } else if name_value.path.is_ident("description") {
+                variant_attrs.description = Some(lit_str(&name_value.lit)?.value());
+            } else {
+                return Err(syn::Error::new_spanned(
+                    name_value.path,
+                    "unknown `error_code` attribute, expected `code` or `description`",
+                ));
+            }
+        }
+
+        Ok(variant_attrs)
+    }
+}
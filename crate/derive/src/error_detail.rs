@@ -0,0 +1,230 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Attribute, Data, DeriveInput, Expr, ExprLit, Fields, Ident, Lit, LitStr, Token, Variant,
+};
+
+use crate::CratePath;
+
+/// Generates the `srcerr::ErrorDetail` implementation for an enum.
+pub(crate) fn derive(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let crate_path = CratePath::from_attrs(&input.attrs)?;
+    let srcerr = crate_path.srcerr();
+
+    let data_enum = match &input.data {
+        Data::Enum(data_enum) => data_enum,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`ErrorDetail` can only be derived for enums",
+            ));
+        }
+    };
+
+    let mut label_arms = Vec::with_capacity(data_enum.variants.len());
+    let mut note_arms = Vec::with_capacity(data_enum.variants.len());
+    for variant in &data_enum.variants {
+        let pattern = variant_pattern(variant);
+        let labels = label_attrs(&variant.attrs)?
+            .into_iter()
+            .map(|label_attr| label_attr.to_tokens(&srcerr))
+            .collect::<Vec<TokenStream>>();
+        let notes = note_attrs(&variant.attrs)?
+            .into_iter()
+            .map(NoteAttr::into_tokens)
+            .collect::<Vec<TokenStream>>();
+
+        label_arms.push(quote!(#pattern => ::std::vec![#(#labels),*]));
+        note_arms.push(quote!(#pattern => ::std::vec![#(#notes),*]));
+    }
+
+    let ident = &input.ident;
+    let mut generics_with_files = input.generics.clone();
+    generics_with_files
+        .params
+        .insert(0, syn::parse_quote!('__files));
+    let (impl_generics, _, _) = generics_with_files.split_for_impl();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #srcerr::ErrorDetail<'__files> for #ident #ty_generics #where_clause {
+            type Files = #srcerr::codespan::Files<::std::borrow::Cow<'__files, str>>;
+
+            #[allow(unused_variables)]
+            fn labels(
+                &self,
+            ) -> ::std::vec::Vec<
+                #srcerr::codespan_reporting::diagnostic::Label<#srcerr::codespan::FileId>,
+            > {
+                match self {
+                    #(#label_arms,)*
+                }
+            }
+
+            #[allow(unused_variables)]
+            fn notes(&self, files: &Self::Files) -> ::std::vec::Vec<::std::string::String> {
+                match self {
+                    #(#note_arms,)*
+                }
+            }
+        }
+    })
+}
+
+/// Returns the pattern that binds each of a variant's fields.
+///
+/// Named fields are bound by name, and unnamed fields are bound as `_0`, `_1`,
+/// and so on.
+fn variant_pattern(variant: &Variant) -> TokenStream {
+    let variant_ident = &variant.ident;
+    match &variant.fields {
+        Fields::Named(fields_named) => {
+            let field_idents = fields_named.named.iter().map(|field| &field.ident);
+            quote!(Self::#variant_ident { #(#field_idents),* })
+        }
+        Fields::Unnamed(fields_unnamed) => {
+            let field_idents =
+                (0..fields_unnamed.unnamed.len()).map(|index| format_ident!("_{}", index));
+            quote!(Self::#variant_ident(#(#field_idents),*))
+        }
+        Fields::Unit => quote!(Self::#variant_ident),
+    }
+}
+
+/// Returns the `#[label(..)]` attributes of a variant.
+fn label_attrs(attrs: &[Attribute]) -> syn::Result<Vec<LabelAttr>> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("label"))
+        .map(|attr| attr.parse_args::<LabelAttr>())
+        .collect()
+}
+
+/// Returns the `#[note(..)]` attributes of a variant.
+fn note_attrs(attrs: &[Attribute]) -> syn::Result<Vec<NoteAttr>> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("note"))
+        .map(|attr| attr.parse_args::<NoteAttr>())
+        .collect()
+}
+
+/// `#[label(primary, file_id = field, span = field, message = "..")]`.
+struct LabelAttr {
+    /// `primary` or `secondary`.
+    style: Ident,
+    /// Field that holds the label's `FileId`.
+    file_id: Ident,
+    /// Field that holds the label's `Span`.
+    span: Ident,
+    /// Message to show with the label.
+    message: Option<LitStr>,
+}
+
+impl LabelAttr {
+    fn to_tokens(&self, srcerr: &syn::Path) -> TokenStream {
+        let Self {
+            style,
+            file_id,
+            span,
+            message,
+        } = self;
+
+        let label = quote! {
+            #srcerr::codespan_reporting::diagnostic::Label::#style(*#file_id, *#span)
+        };
+        match message {
+            Some(message) => quote!(#label.with_message(#message)),
+            None => label,
+        }
+    }
+}
+
+impl Parse for LabelAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let style = input.parse::<Ident>()?;
+        if style != "primary" && style != "secondary" {
+            return Err(syn::Error::new_spanned(
+                style,
+                "expected label style `primary` or `secondary`",
+            ));
+        }
+
+        let mut file_id = None;
+        let mut span = None;
+        let mut message = None;
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+
+            let key = input.parse::<Ident>()?;
+            input.parse::<Token![=]>()?;
+            if key == "file_id" {
+                file_id = Some(input.parse::<Ident>()?);
+            } else if key == "span" {
+                span = Some(input.parse::<Ident>()?);
+            } else if key == "message" {
+                message = Some(input.parse::<LitStr>()?);
+            } else {
+                return Err(syn::Error::new_spanned(
+                    key,
+                    "unknown `label` attribute, expected `file_id`, `span`, or `message`",
+                ));
+            }
+        }
+
+        let file_id = file_id
+            .ok_or_else(|| syn::Error::new(style.span(), "label is missing `file_id = field`"))?;
+        let span =
+            span.ok_or_else(|| syn::Error::new(style.span(), "label is missing `span = field`"))?;
+
+        Ok(Self {
+            style,
+            file_id,
+            span,
+            message,
+        })
+    }
+}
+
+/// `#[note("format string", args..)]`.
+///
+/// The variant's fields are in scope as references, as is `files`, so they
+/// may be used in the arguments, or captured in the format string.
+struct NoteAttr {
+    /// Format string of the note.
+    format: LitStr,
+    /// Arguments to the format string.
+    args: Vec<Expr>,
+}
+
+impl NoteAttr {
+    fn into_tokens(self) -> TokenStream {
+        let Self { format, args } = self;
+        quote!(::std::format!(#format, #(#args),*))
+    }
+}
+
+impl Parse for NoteAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut exprs = Punctuated::<Expr, Token![,]>::parse_terminated(input)?.into_iter();
+        let format = match exprs.next() {
+            Some(Expr::Lit(ExprLit {
+                lit: Lit::Str(lit_str),
+                ..
+            })) => lit_str,
+            Some(expr) => {
+                return Err(syn::Error::new_spanned(expr, "expected note format string"));
+            }
+            None => return Err(input.error("expected note format string")),
+        };
+
+        Ok(Self {
+            format,
+            args: exprs.collect(),
+        })
+    }
+}
@@ -0,0 +1,113 @@
+//! Derive macros for the choochoo automation library's diagnostics.
+//!
+//! These generate the [`srcerr`] trait implementations that station error
+//! types need, so that errors render as rich diagnostics without hand written
+//! `match` blocks for every variant.
+//!
+//! Generated code refers to `srcerr` and `AsDiagnostic` through the
+//! `choochoo` crate. If `choochoo` is renamed or re-exported, set its path
+//! with `#[choochoo(crate = "path::to::choochoo")]` on the type.
+//!
+//! [`srcerr`]: https://docs.rs/srcerr
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+use crate::crate_path::CratePath;
+
+mod as_diagnostic;
+mod crate_path;
+mod error_code;
+mod error_detail;
+
+/// Derives `srcerr::ErrorCode` for a fieldless enum.
+///
+/// Each variant's code defaults to one more than the previous variant's,
+/// starting at `1`, and its description defaults to the first paragraph of its
+/// doc comment.
+///
+/// # Attributes
+///
+/// * `#[error_code(prefix = "E", max = 99)]` on the enum sets the code prefix,
+///   and the maximum code used to zero pad codes. These are the defaults.
+/// * `#[error_code(code = 20, description = "..")]` on a variant overrides its
+///   code or description.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// #[derive(Clone, Copy, Debug, PartialEq, Eq, ErrorCode)]
+/// pub enum ErrorCode {
+///     /// Failed to open `app.zip` to upload.
+///     AppZipOpen,
+///     /// Failed to clean app.zip from artifact server.
+///     #[error_code(code = 20)]
+///     CleanArtifactServerAppZip,
+/// }
+/// ```
+#[proc_macro_derive(ErrorCode, attributes(choochoo, error_code))]
+pub fn error_code_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    error_code::derive(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `srcerr::ErrorDetail` for an enum, from labels and notes declared
+/// on each variant.
+///
+/// Within the attributes, a variant's named fields are in scope by name as
+/// references, and unnamed fields as `_0`, `_1`, and so on. The
+/// `codespan::Files` that the diagnostic is rendered with is in scope as
+/// `files` for notes.
+///
+/// # Attributes
+///
+/// * `#[label(primary, file_id = field, span = field, message = "..")]` adds a
+///   primary or secondary label over the `Span` in `span`, within the source in
+///   `file_id`. `message` is optional.
+/// * `#[note("format string", args..)]` adds a note, formatted with `format!`.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// #[derive(Debug, ErrorDetail)]
+/// pub enum ErrorDetail {
+///     #[label(primary, file_id = address_file_id, span = address_span,
+///         message = "failed to connect to server")]
+///     #[note("Try running `simple-http-server --port {}`.",
+///         files.source_slice(*address_file_id, *port_span).expect("Expected file to exist."))]
+///     ArtifactServerConnect {
+///         address_file_id: FileId,
+///         address_span: Span,
+///         port_span: Span,
+///         error: reqwest::Error,
+///     },
+/// }
+/// ```
+#[proc_macro_derive(ErrorDetail, attributes(choochoo, label, note))]
+pub fn error_detail_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    error_detail::derive(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `AsDiagnostic` for a struct with a single field, such as a newtype
+/// around `srcerr::SourceError`, by delegating to the field.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// #[derive(Debug, AsDiagnostic)]
+/// pub struct DemoError(pub SourceError<'static, ErrorCode, ErrorDetail, Files>);
+/// ```
+#[proc_macro_derive(AsDiagnostic, attributes(choochoo))]
+pub fn as_diagnostic_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    as_diagnostic::derive(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
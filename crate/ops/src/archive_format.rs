@@ -0,0 +1,8 @@
+/// Format of an archive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ArchiveFormat {
+    /// `zip` archive, compressed using deflate.
+    Zip,
+    /// `tar` archive, compressed using gzip.
+    TarGz,
+}
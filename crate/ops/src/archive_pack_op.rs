@@ -0,0 +1,399 @@
+use std::{
+    ffi::OsString,
+    fs::{self, File},
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+use choochoo_cfg_model::{
+    indicatif::ProgressBar,
+    rt::{CheckStatus, ProgressLimit, ResIdLogical, ResIds, StationMutRef},
+    CleanFns, CreateFns, SetupFn, StationFn, StationOp,
+};
+use flate2::{write::GzEncoder, Compression};
+use futures::future::LocalBoxFuture;
+use tokio::task;
+use zip::{write::FileOptions, ZipWriter};
+
+use crate::{archive_support, ArchiveFormat, Checksum, ChecksumAlgorithm, OpsError};
+
+/// Station operation that creates an archive from the files in a directory.
+///
+/// * **Setup:** The progress limit is the number of files to archive.
+/// * **Check:** The archive exists, and the SHA-256 [`Checksum`] of the source
+///   files matches the checksum recorded when the archive was created.
+/// * **Work:** Creates the archive, and records the checksum of the source
+///   files next to the archive, in a file with the
+///   [`ArchivePackOp::HASH_FILE_SUFFIX`] suffix.
+/// * **Clean:** Removes the archive and the recorded hash.
+///
+/// The archive's path is recorded in the station's [`ResIds`] under
+/// [`ArchivePackOp::RES_ID_PATH`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArchivePackOp {
+    /// Format of the archive.
+    archive_format: ArchiveFormat,
+    /// Directory with the files to archive.
+    ///
+    /// Relative paths are relative to the station's directory.
+    src_dir: PathBuf,
+    /// Path of the archive to create.
+    ///
+    /// Relative paths are relative to the station's directory.
+    archive_path: PathBuf,
+}
+
+impl ArchivePackOp {
+    /// Suffix appended to the archive's path for the file that holds the
+    /// SHA-256 hash of the source files.
+    pub const HASH_FILE_SUFFIX: &'static str = ".src.sha256";
+    /// Logical ID of the archive's path in the station's [`ResIds`].
+    pub const RES_ID_PATH: &'static str = "archive_pack_path";
+
+    /// Returns a new `ArchivePackOp`.
+    ///
+    /// # Parameters
+    ///
+    /// * `archive_format`: Format of the archive.
+    /// * `src_dir`: Directory with the files to archive. Relative paths are
+    ///   relative to the station's directory.
+    /// * `archive_path`: Path of the archive to create. Relative paths are
+    ///   relative to the station's directory.
+    pub fn new<S, P>(archive_format: ArchiveFormat, src_dir: S, archive_path: P) -> Self
+    where
+        S: Into<PathBuf>,
+        P: Into<PathBuf>,
+    {
+        Self {
+            archive_format,
+            src_dir: src_dir.into(),
+            archive_path: archive_path.into(),
+        }
+    }
+
+    /// Returns the format of the archive.
+    pub fn archive_format(&self) -> ArchiveFormat {
+        self.archive_format
+    }
+
+    /// Returns the directory with the files to archive.
+    pub fn src_dir(&self) -> &Path {
+        &self.src_dir
+    }
+
+    /// Returns the path of the archive to create.
+    pub fn archive_path(&self) -> &Path {
+        &self.archive_path
+    }
+
+    /// Returns the [`StationOp`] that creates the archive.
+    pub fn build<E>(self) -> StationOp<E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        let create_fns =
+            CreateFns::new(self.setup_fn(), self.work_fn()).with_check_fn(self.check_fn());
+        let clean_fns = CleanFns::new(
+            SetupFn::new(|_station, _train_resources| {
+                Box::pin(async move { Result::<ProgressLimit, E>::Ok(ProgressLimit::Steps(1)) })
+            }),
+            self.clean_work_fn(),
+        )
+        .with_check_fn(self.clean_check_fn());
+
+        StationOp::new(create_fns, Some(clean_fns))
+    }
+
+    fn setup_fn<E>(&self) -> SetupFn<E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        let src_dir = self.src_dir.clone();
+        let archive_path = self.archive_path.clone();
+        SetupFn::new(move |station, _train_resources| {
+            let src_dir = station.dir.join(&src_dir);
+            let archive_path = station.dir.join(&archive_path);
+            Box::pin(async move {
+                let src_files =
+                    task::spawn_blocking(move || Self::src_files(&src_dir, &archive_path))
+                        .await
+                        .map_err(OpsError::TaskJoin)??;
+
+                Result::<ProgressLimit, E>::Ok(ProgressLimit::Items(src_files.len() as u64))
+            })
+        })
+    }
+
+    fn check_fn<E>(&self) -> StationFn<CheckStatus, E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        let src_dir = self.src_dir.clone();
+        let archive_path = self.archive_path.clone();
+        StationFn::new0(
+            move |station: &mut StationMutRef<'_, E>|
+                  -> LocalBoxFuture<'_, Result<CheckStatus, E>> {
+                let src_dir = station.dir.join(&src_dir);
+                let archive_path = station.dir.join(&archive_path);
+                Box::pin(async move {
+                    if !archive_path.exists() {
+                        return Ok(CheckStatus::WorkRequired);
+                    }
+                    let hash_path = Self::hash_path(&archive_path);
+                    let hash_recorded = match tokio::fs::read_to_string(&hash_path).await {
+                        Ok(hash_recorded) => hash_recorded,
+                        Err(error) if error.kind() == ErrorKind::NotFound => {
+                            return Ok(CheckStatus::WorkRequired);
+                        }
+                        Err(error) => {
+                            return Err(E::from(OpsError::FileRead {
+                                path: hash_path,
+                                error,
+                            }));
+                        }
+                    };
+                    let src_files = {
+                        let src_dir = src_dir.clone();
+                        task::spawn_blocking(move || Self::src_files(&src_dir, &archive_path))
+                            .await
+                            .map_err(OpsError::TaskJoin)??
+                    };
+                    let checksum =
+                        Checksum::files(ChecksumAlgorithm::Sha256, &src_dir, &src_files, None)
+                            .await?;
+
+                    let check_status = if hash_recorded.trim() == checksum.to_string() {
+                        CheckStatus::WorkNotRequired
+                    } else {
+                        CheckStatus::WorkRequired
+                    };
+                    Result::<CheckStatus, E>::Ok(check_status)
+                })
+            },
+        )
+    }
+
+    fn work_fn<E>(&self) -> StationFn<ResIds, (ResIds, E), E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        let archive_format = self.archive_format;
+        let src_dir = self.src_dir.clone();
+        let archive_path = self.archive_path.clone();
+        StationFn::new0(
+            move |station: &mut StationMutRef<'_, E>|
+                  -> LocalBoxFuture<'_, Result<ResIds, (ResIds, E)>> {
+                let src_dir = station.dir.join(&src_dir);
+                let archive_path = station.dir.join(&archive_path);
+                let progress_bar = station.progress.progress_bar().clone();
+                Box::pin(async move {
+                    let mut res_ids = ResIds::new();
+                    let pack_result = {
+                        let src_dir = src_dir.clone();
+                        let archive_path = archive_path.clone();
+                        task::spawn_blocking(move || {
+                            Self::pack(archive_format, &src_dir, &archive_path, progress_bar)
+                        })
+                        .await
+                        .map_err(OpsError::TaskJoin)
+                        .and_then(|pack_result| pack_result)
+                    };
+                    let src_files =
+                        pack_result.map_err(|error| (res_ids.clone(), E::from(error)))?;
+                    Self::hash_record(&src_dir, &src_files, &archive_path)
+                        .await
+                        .map_err(|error| (res_ids.clone(), E::from(error)))?;
+
+                    let _ = res_ids.insert(ResIdLogical::new(Self::RES_ID_PATH), archive_path);
+
+                    Result::<ResIds, (ResIds, E)>::Ok(res_ids)
+                })
+            },
+        )
+    }
+
+    fn clean_check_fn<E>(&self) -> StationFn<CheckStatus, E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        let archive_path = self.archive_path.clone();
+        StationFn::new0(
+            move |station: &mut StationMutRef<'_, E>|
+                  -> LocalBoxFuture<'_, Result<CheckStatus, E>> {
+                let archive_path = station.dir.join(&archive_path);
+                Box::pin(async move {
+                    let check_status = if archive_path.exists() {
+                        CheckStatus::WorkRequired
+                    } else {
+                        CheckStatus::WorkNotRequired
+                    };
+                    Result::<CheckStatus, E>::Ok(check_status)
+                })
+            },
+        )
+    }
+
+    fn clean_work_fn<E>(&self) -> StationFn<(), E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        let archive_path = self.archive_path.clone();
+        StationFn::new0(
+            move |station: &mut StationMutRef<'_, E>| -> LocalBoxFuture<'_, Result<(), E>> {
+                let archive_path = station.dir.join(&archive_path);
+                Box::pin(async move {
+                    let hash_path = Self::hash_path(&archive_path);
+                    [archive_path, hash_path]
+                        .iter()
+                        .try_for_each(|path| match fs::remove_file(path) {
+                            Ok(()) => Ok(()),
+                            Err(error) if error.kind() == ErrorKind::NotFound => Ok(()),
+                            Err(error) => Err(OpsError::FileRemove {
+                                path: path.clone(),
+                                error,
+                            }),
+                        })?;
+                    station.progress.progress_bar().inc(1);
+
+                    Result::<(), E>::Ok(())
+                })
+            },
+        )
+    }
+
+    /// Returns the path of the file that holds the hash of the source files.
+    fn hash_path(archive_path: &Path) -> PathBuf {
+        let mut hash_path = OsString::from(archive_path.as_os_str());
+        hash_path.push(Self::HASH_FILE_SUFFIX);
+        PathBuf::from(hash_path)
+    }
+
+    /// Returns the files to archive, relative to the source directory.
+    ///
+    /// The archive and its hash file are excluded, in case they are in the
+    /// source directory.
+    fn src_files(src_dir: &Path, archive_path: &Path) -> Result<Vec<PathBuf>, OpsError> {
+        let hash_path = Self::hash_path(archive_path);
+        let mut src_files = archive_support::dir_files(src_dir)?;
+        src_files.retain(|src_file| {
+            let src_path = src_dir.join(src_file);
+            src_path != archive_path && src_path != hash_path
+        });
+
+        Ok(src_files)
+    }
+
+    /// Records the checksum of the source files next to the archive.
+    async fn hash_record(
+        src_dir: &Path,
+        src_files: &[PathBuf],
+        archive_path: &Path,
+    ) -> Result<(), OpsError> {
+        let checksum = Checksum::files(ChecksumAlgorithm::Sha256, src_dir, src_files, None).await?;
+        checksum.record(&Self::hash_path(archive_path)).await
+    }
+
+    /// Creates the archive, returning the archived files relative to the source
+    /// directory.
+    fn pack(
+        archive_format: ArchiveFormat,
+        src_dir: &Path,
+        archive_path: &Path,
+        progress_bar: ProgressBar,
+    ) -> Result<Vec<PathBuf>, OpsError> {
+        let src_files = Self::src_files(src_dir, archive_path)?;
+        if let Some(archive_dir) = archive_path.parent() {
+            fs::create_dir_all(archive_dir).map_err(|error| OpsError::DirCreate {
+                path: archive_dir.to_path_buf(),
+                error,
+            })?;
+        }
+        let file = File::create(archive_path).map_err(|error| OpsError::FileCreate {
+            path: archive_path.to_path_buf(),
+            error,
+        })?;
+
+        match archive_format {
+            ArchiveFormat::Zip => {
+                Self::pack_zip(file, src_dir, &src_files, archive_path, &progress_bar)?
+            }
+            ArchiveFormat::TarGz => {
+                Self::pack_tar_gz(file, src_dir, &src_files, archive_path, &progress_bar)?
+            }
+        }
+
+        Ok(src_files)
+    }
+
+    /// Writes the source files into a `zip` archive.
+    fn pack_zip(
+        file: File,
+        src_dir: &Path,
+        src_files: &[PathBuf],
+        archive_path: &Path,
+        progress_bar: &ProgressBar,
+    ) -> Result<(), OpsError> {
+        let archive_write_error = |error| OpsError::ArchiveWrite {
+            path: archive_path.to_path_buf(),
+            error,
+        };
+        let mut zip_writer = ZipWriter::new(file);
+        src_files.iter().try_for_each(|src_file| {
+            // Entry names always use `/` as the separator.
+            let entry_name = src_file
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            zip_writer
+                .start_file(entry_name, FileOptions::default())
+                .map_err(io::Error::from)
+                .map_err(archive_write_error)?;
+
+            let src_path = src_dir.join(src_file);
+            let mut src = File::open(&src_path).map_err(|error| OpsError::FileOpen {
+                path: src_path.clone(),
+                error,
+            })?;
+            io::copy(&mut src, &mut zip_writer).map_err(archive_write_error)?;
+            progress_bar.inc(1);
+
+            Ok(())
+        })?;
+        zip_writer
+            .finish()
+            .map_err(io::Error::from)
+            .map_err(archive_write_error)?;
+
+        Ok(())
+    }
+
+    /// Writes the source files into a `tar.gz` archive.
+    fn pack_tar_gz(
+        file: File,
+        src_dir: &Path,
+        src_files: &[PathBuf],
+        archive_path: &Path,
+        progress_bar: &ProgressBar,
+    ) -> Result<(), OpsError> {
+        let archive_write_error = |error| OpsError::ArchiveWrite {
+            path: archive_path.to_path_buf(),
+            error,
+        };
+        let mut tar_builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+        src_files.iter().try_for_each(|src_file| {
+            tar_builder
+                .append_path_with_name(src_dir.join(src_file), src_file)
+                .map_err(archive_write_error)?;
+            progress_bar.inc(1);
+
+            Ok(())
+        })?;
+        tar_builder
+            .into_inner()
+            .and_then(GzEncoder::finish)
+            .map_err(archive_write_error)?;
+
+        Ok(())
+    }
+}
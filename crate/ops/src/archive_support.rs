@@ -0,0 +1,74 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use choochoo_cfg_model::indicatif::ProgressBar;
+
+use crate::OpsError;
+
+/// [`Read`] adapter that advances a progress bar by the number of bytes read.
+#[derive(Debug)]
+pub(crate) struct ProgressBarRead<R> {
+    /// Progress bar to advance.
+    progress_bar: ProgressBar,
+    /// Underlying reader.
+    inner: R,
+}
+
+impl<R> ProgressBarRead<R> {
+    /// Returns a new `ProgressBarRead`.
+    pub(crate) fn new(progress_bar: ProgressBar, inner: R) -> Self {
+        Self {
+            progress_bar,
+            inner,
+        }
+    }
+}
+
+impl<R> Read for ProgressBarRead<R>
+where
+    R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let byte_count = self.inner.read(buf)?;
+        self.progress_bar.inc(byte_count as u64);
+        Ok(byte_count)
+    }
+}
+
+/// Returns the paths of files in a directory and its subdirectories, relative
+/// to the directory, in sorted order.
+pub(crate) fn dir_files(dir: &Path) -> Result<Vec<PathBuf>, OpsError> {
+    let mut files = Vec::new();
+    dir_files_collect(dir, Path::new(""), &mut files)?;
+    files.sort();
+
+    Ok(files)
+}
+
+fn dir_files_collect(
+    dir_base: &Path,
+    dir_relative: &Path,
+    files: &mut Vec<PathBuf>,
+) -> Result<(), OpsError> {
+    let dir = dir_base.join(dir_relative);
+    let dir_read_error = |error| OpsError::DirRead {
+        path: dir.clone(),
+        error,
+    };
+    fs::read_dir(&dir)
+        .map_err(dir_read_error)?
+        .try_for_each(|dir_entry| {
+            let dir_entry = dir_entry.map_err(dir_read_error)?;
+            let file_type = dir_entry.file_type().map_err(dir_read_error)?;
+            let path_relative = dir_relative.join(dir_entry.file_name());
+            if file_type.is_dir() {
+                dir_files_collect(dir_base, &path_relative, files)
+            } else {
+                files.push(path_relative);
+                Ok(())
+            }
+        })
+}
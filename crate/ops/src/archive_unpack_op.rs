@@ -0,0 +1,472 @@
+use std::{
+    cmp::Reverse,
+    fs::{self, File},
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+use choochoo_cfg_model::{
+    indicatif::ProgressBar,
+    rt::{CheckStatus, ProgressLimit, ResIdLogical, ResIds, StationMutRef},
+    CleanFns, CreateFns, SetupFn, StationFn, StationOp,
+};
+use flate2::read::GzDecoder;
+use futures::future::LocalBoxFuture;
+use tokio::task;
+use zip::ZipArchive;
+
+use crate::{
+    archive_support::ProgressBarRead, ArchiveFormat, Checksum, ChecksumAlgorithm, OpsError,
+};
+
+/// Station operation that extracts an archive into a directory.
+///
+/// * **Setup:** The progress limit is the number of entries for `zip` archives,
+///   and the archive's length in bytes for `tar.gz` archives.
+/// * **Check:** The SHA-256 [`Checksum`] of the archive matches the checksum
+///   recorded when it was last extracted.
+/// * **Work:** Extracts the archive, and records its checksum and the extracted
+///   entries in the destination directory.
+/// * **Clean:** Removes the extracted entries, and the recorded hash.
+///
+/// Entries that would be extracted outside the destination directory are
+/// skipped.
+///
+/// The destination directory is recorded in the station's [`ResIds`] under
+/// [`ArchiveUnpackOp::RES_ID_DIR`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArchiveUnpackOp {
+    /// Format of the archive.
+    archive_format: ArchiveFormat,
+    /// Path of the archive to extract.
+    ///
+    /// Relative paths are relative to the station's directory.
+    archive_path: PathBuf,
+    /// Directory to extract the archive into.
+    ///
+    /// Relative paths are relative to the station's directory.
+    dest_dir: PathBuf,
+}
+
+impl ArchiveUnpackOp {
+    /// Name of the file in the destination directory that lists the extracted
+    /// entries.
+    pub const ENTRIES_FILE_NAME: &'static str = ".choochoo_archive.entries";
+    /// Name of the file in the destination directory that holds the SHA-256
+    /// checksum of the extracted archive.
+    pub const HASH_FILE_NAME: &'static str = ".choochoo_archive.sha256";
+    /// Logical ID of the destination directory in the station's [`ResIds`].
+    pub const RES_ID_DIR: &'static str = "archive_unpack_dir";
+
+    /// Returns a new `ArchiveUnpackOp`.
+    ///
+    /// # Parameters
+    ///
+    /// * `archive_format`: Format of the archive.
+    /// * `archive_path`: Path of the archive to extract. Relative paths are
+    ///   relative to the station's directory.
+    /// * `dest_dir`: Directory to extract the archive into. Relative paths are
+    ///   relative to the station's directory.
+    pub fn new<P, D>(archive_format: ArchiveFormat, archive_path: P, dest_dir: D) -> Self
+    where
+        P: Into<PathBuf>,
+        D: Into<PathBuf>,
+    {
+        Self {
+            archive_format,
+            archive_path: archive_path.into(),
+            dest_dir: dest_dir.into(),
+        }
+    }
+
+    /// Returns the format of the archive.
+    pub fn archive_format(&self) -> ArchiveFormat {
+        self.archive_format
+    }
+
+    /// Returns the path of the archive to extract.
+    pub fn archive_path(&self) -> &Path {
+        &self.archive_path
+    }
+
+    /// Returns the directory to extract the archive into.
+    pub fn dest_dir(&self) -> &Path {
+        &self.dest_dir
+    }
+
+    /// Returns the [`StationOp`] that extracts the archive.
+    pub fn build<E>(self) -> StationOp<E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        let create_fns =
+            CreateFns::new(self.setup_fn(), self.work_fn()).with_check_fn(self.check_fn());
+        let clean_fns = CleanFns::new(
+            SetupFn::new(|_station, _train_resources| {
+                Box::pin(async move { Result::<ProgressLimit, E>::Ok(ProgressLimit::Unknown) })
+            }),
+            self.clean_work_fn(),
+        )
+        .with_check_fn(self.clean_check_fn());
+
+        StationOp::new(create_fns, Some(clean_fns))
+    }
+
+    fn setup_fn<E>(&self) -> SetupFn<E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        let archive_format = self.archive_format;
+        let archive_path = self.archive_path.clone();
+        SetupFn::new(move |station, _train_resources| {
+            let archive_path = station.dir.join(&archive_path);
+            Box::pin(async move {
+                let progress_limit = task::spawn_blocking(move || {
+                    Self::progress_limit(archive_format, &archive_path)
+                })
+                .await
+                .map_err(OpsError::TaskJoin)??;
+
+                Result::<ProgressLimit, E>::Ok(progress_limit)
+            })
+        })
+    }
+
+    fn check_fn<E>(&self) -> StationFn<CheckStatus, E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        let archive_path = self.archive_path.clone();
+        let dest_dir = self.dest_dir.clone();
+        StationFn::new0(
+            move |station: &mut StationMutRef<'_, E>|
+                  -> LocalBoxFuture<'_, Result<CheckStatus, E>> {
+                let archive_path = station.dir.join(&archive_path);
+                let hash_path = station.dir.join(&dest_dir).join(Self::HASH_FILE_NAME);
+                Box::pin(async move {
+                    let hash_recorded = match tokio::fs::read_to_string(&hash_path).await {
+                        Ok(hash_recorded) => hash_recorded,
+                        Err(error) if error.kind() == ErrorKind::NotFound => {
+                            return Ok(CheckStatus::WorkRequired);
+                        }
+                        Err(error) => {
+                            return Err(E::from(OpsError::FileRead {
+                                path: hash_path,
+                                error,
+                            }));
+                        }
+                    };
+                    let checksum =
+                        Checksum::file(ChecksumAlgorithm::Sha256, &archive_path, None).await?;
+
+                    let check_status = if hash_recorded.trim() == checksum.to_string() {
+                        CheckStatus::WorkNotRequired
+                    } else {
+                        CheckStatus::WorkRequired
+                    };
+                    Result::<CheckStatus, E>::Ok(check_status)
+                })
+            },
+        )
+    }
+
+    fn work_fn<E>(&self) -> StationFn<ResIds, (ResIds, E), E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        let archive_format = self.archive_format;
+        let archive_path = self.archive_path.clone();
+        let dest_dir = self.dest_dir.clone();
+        StationFn::new0(
+            move |station: &mut StationMutRef<'_, E>|
+                  -> LocalBoxFuture<'_, Result<ResIds, (ResIds, E)>> {
+                let archive_path = station.dir.join(&archive_path);
+                let dest_dir = station.dir.join(&dest_dir);
+                let progress_bar = station.progress.progress_bar().clone();
+                Box::pin(async move {
+                    let mut res_ids = ResIds::new();
+                    let unpack_result = {
+                        let archive_path = archive_path.clone();
+                        let dest_dir = dest_dir.clone();
+                        task::spawn_blocking(move || {
+                            Self::unpack(archive_format, &archive_path, &dest_dir, progress_bar)
+                        })
+                        .await
+                        .map_err(OpsError::TaskJoin)
+                        .and_then(|unpack_result| unpack_result)
+                    };
+                    unpack_result.map_err(|error| (res_ids.clone(), E::from(error)))?;
+                    Self::hash_record(&archive_path, &dest_dir)
+                        .await
+                        .map_err(|error| (res_ids.clone(), E::from(error)))?;
+
+                    let _ = res_ids.insert(ResIdLogical::new(Self::RES_ID_DIR), dest_dir);
+
+                    Result::<ResIds, (ResIds, E)>::Ok(res_ids)
+                })
+            },
+        )
+    }
+
+    fn clean_check_fn<E>(&self) -> StationFn<CheckStatus, E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        let dest_dir = self.dest_dir.clone();
+        StationFn::new0(
+            move |station: &mut StationMutRef<'_, E>|
+                  -> LocalBoxFuture<'_, Result<CheckStatus, E>> {
+                let entries_path = station.dir.join(&dest_dir).join(Self::ENTRIES_FILE_NAME);
+                Box::pin(async move {
+                    let check_status = if entries_path.exists() {
+                        CheckStatus::WorkRequired
+                    } else {
+                        CheckStatus::WorkNotRequired
+                    };
+                    Result::<CheckStatus, E>::Ok(check_status)
+                })
+            },
+        )
+    }
+
+    fn clean_work_fn<E>(&self) -> StationFn<(), E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        let dest_dir = self.dest_dir.clone();
+        StationFn::new0(
+            move |station: &mut StationMutRef<'_, E>| -> LocalBoxFuture<'_, Result<(), E>> {
+                let dest_dir = station.dir.join(&dest_dir);
+                Box::pin(async move {
+                    task::spawn_blocking(move || Self::clean(&dest_dir))
+                        .await
+                        .map_err(OpsError::TaskJoin)??;
+
+                    Result::<(), E>::Ok(())
+                })
+            },
+        )
+    }
+
+    /// Returns the progress limit for extracting the archive.
+    fn progress_limit(
+        archive_format: ArchiveFormat,
+        archive_path: &Path,
+    ) -> Result<ProgressLimit, OpsError> {
+        match archive_format {
+            ArchiveFormat::Zip => {
+                let file = File::open(archive_path).map_err(|error| OpsError::FileOpen {
+                    path: archive_path.to_path_buf(),
+                    error,
+                })?;
+                let zip_archive = ZipArchive::new(file).map_err(|error| OpsError::ArchiveRead {
+                    path: archive_path.to_path_buf(),
+                    error: io::Error::from(error),
+                })?;
+
+                Ok(ProgressLimit::Items(zip_archive.len() as u64))
+            }
+            ArchiveFormat::TarGz => {
+                let metadata =
+                    fs::metadata(archive_path).map_err(|error| OpsError::FileMetadata {
+                        path: archive_path.to_path_buf(),
+                        error,
+                    })?;
+
+                Ok(ProgressLimit::Bytes(metadata.len()))
+            }
+        }
+    }
+
+    /// Records the checksum of the archive in the destination directory.
+    async fn hash_record(archive_path: &Path, dest_dir: &Path) -> Result<(), OpsError> {
+        let checksum = Checksum::file(ChecksumAlgorithm::Sha256, archive_path, None).await?;
+        checksum.record(&dest_dir.join(Self::HASH_FILE_NAME)).await
+    }
+
+    /// Extracts the archive, and records its extracted entries.
+    fn unpack(
+        archive_format: ArchiveFormat,
+        archive_path: &Path,
+        dest_dir: &Path,
+        progress_bar: ProgressBar,
+    ) -> Result<(), OpsError> {
+        fs::create_dir_all(dest_dir).map_err(|error| OpsError::DirCreate {
+            path: dest_dir.to_path_buf(),
+            error,
+        })?;
+        let file = File::open(archive_path).map_err(|error| OpsError::FileOpen {
+            path: archive_path.to_path_buf(),
+            error,
+        })?;
+
+        let entries = match archive_format {
+            ArchiveFormat::Zip => Self::unpack_zip(file, archive_path, dest_dir, &progress_bar)?,
+            ArchiveFormat::TarGz => {
+                Self::unpack_tar_gz(file, archive_path, dest_dir, progress_bar)?
+            }
+        };
+        let entries = entries
+            .iter()
+            .map(|entry| entry.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let entries_path = dest_dir.join(Self::ENTRIES_FILE_NAME);
+        fs::write(&entries_path, entries).map_err(|error| OpsError::FileWrite {
+            path: entries_path,
+            error,
+        })?;
+
+        Ok(())
+    }
+
+    /// Extracts a `zip` archive, returning the paths of the extracted entries.
+    fn unpack_zip(
+        file: File,
+        archive_path: &Path,
+        dest_dir: &Path,
+        progress_bar: &ProgressBar,
+    ) -> Result<Vec<PathBuf>, OpsError> {
+        let archive_read_error = |error| OpsError::ArchiveRead {
+            path: archive_path.to_path_buf(),
+            error,
+        };
+        let mut zip_archive = ZipArchive::new(file)
+            .map_err(io::Error::from)
+            .map_err(archive_read_error)?;
+
+        let mut entries = Vec::with_capacity(zip_archive.len());
+        for index in 0..zip_archive.len() {
+            let mut zip_file = zip_archive
+                .by_index(index)
+                .map_err(io::Error::from)
+                .map_err(archive_read_error)?;
+            progress_bar.inc(1);
+
+            let entry = match zip_file.enclosed_name() {
+                Some(entry) => entry.to_path_buf(),
+                None => continue,
+            };
+            let entry_path = dest_dir.join(&entry);
+            let dir = if zip_file.is_dir() {
+                Some(entry_path.as_path())
+            } else {
+                entry_path.parent()
+            };
+            if let Some(dir) = dir {
+                fs::create_dir_all(dir).map_err(|error| OpsError::DirCreate {
+                    path: dir.to_path_buf(),
+                    error,
+                })?;
+            }
+            if zip_file.is_file() {
+                let mut entry_file =
+                    File::create(&entry_path).map_err(|error| OpsError::FileCreate {
+                        path: entry_path.clone(),
+                        error,
+                    })?;
+                io::copy(&mut zip_file, &mut entry_file).map_err(|error| OpsError::FileWrite {
+                    path: entry_path.clone(),
+                    error,
+                })?;
+            }
+
+            entries.push(entry);
+        }
+
+        Ok(entries)
+    }
+
+    /// Extracts a `tar.gz` archive, returning the paths of the extracted
+    /// entries.
+    fn unpack_tar_gz(
+        file: File,
+        archive_path: &Path,
+        dest_dir: &Path,
+        progress_bar: ProgressBar,
+    ) -> Result<Vec<PathBuf>, OpsError> {
+        let archive_read_error = |error| OpsError::ArchiveRead {
+            path: archive_path.to_path_buf(),
+            error,
+        };
+        let mut tar_archive =
+            tar::Archive::new(GzDecoder::new(ProgressBarRead::new(progress_bar, file)));
+
+        let mut entries = Vec::new();
+        for tar_entry in tar_archive.entries().map_err(archive_read_error)? {
+            let mut tar_entry = tar_entry.map_err(archive_read_error)?;
+            let entry = tar_entry.path().map_err(archive_read_error)?.into_owned();
+
+            // `unpack_in` skips entries that would be extracted outside `dest_dir`.
+            let unpacked = tar_entry
+                .unpack_in(dest_dir)
+                .map_err(|error| OpsError::FileWrite {
+                    path: dest_dir.join(&entry),
+                    error,
+                })?;
+            if unpacked {
+                entries.push(entry);
+            }
+        }
+
+        Ok(entries)
+    }
+
+    /// Removes the extracted entries, and the recorded hash and entries.
+    ///
+    /// Directories are only removed if they are empty after their entries are
+    /// removed.
+    fn clean(dest_dir: &Path) -> Result<(), OpsError> {
+        let entries_path = dest_dir.join(Self::ENTRIES_FILE_NAME);
+        let entries = match fs::read_to_string(&entries_path) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(error) => {
+                return Err(OpsError::FileRead {
+                    path: entries_path,
+                    error,
+                });
+            }
+        };
+
+        // Archives may not have entries for the directories of the files within them,
+        // so the ancestors of each entry are also removed if they are empty.
+        //
+        // Deeper entries are removed first, so that directories are empty when we
+        // reach them.
+        let mut entry_paths = entries
+            .lines()
+            .filter(|entry| !entry.is_empty())
+            .flat_map(|entry| Path::new(entry).ancestors())
+            .filter(|entry| !entry.as_os_str().is_empty())
+            .map(|entry| dest_dir.join(entry))
+            .collect::<Vec<_>>();
+        entry_paths.sort_by_key(|entry_path| Reverse(entry_path.components().count()));
+        entry_paths.dedup();
+        entry_paths.iter().try_for_each(|entry_path| {
+            if entry_path.is_dir() {
+                // Directories that still contain other files are kept.
+                let _ = fs::remove_dir(entry_path);
+                Ok(())
+            } else {
+                Self::file_remove(entry_path)
+            }
+        })?;
+
+        Self::file_remove(&dest_dir.join(Self::HASH_FILE_NAME))?;
+        Self::file_remove(&entries_path)
+    }
+
+    /// Removes a file, ignoring it if it does not exist.
+    fn file_remove(path: &Path) -> Result<(), OpsError> {
+        match fs::remove_file(path) {
+            Ok(()) => Ok(()),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(()),
+            Err(error) => Err(OpsError::FileRemove {
+                path: path.to_path_buf(),
+                error,
+            }),
+        }
+    }
+}
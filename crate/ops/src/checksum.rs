@@ -0,0 +1,362 @@
+use std::{
+    fmt,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use choochoo_cfg_model::rt::{CheckStatus, StationProgress};
+use sha2::{Digest, Sha256};
+use tokio::{
+    fs::{self, File},
+    io::AsyncReadExt,
+};
+
+use crate::{ChecksumAlgorithm, OpsError};
+
+/// Hash of a file's contents, computed using a [`ChecksumAlgorithm`].
+///
+/// The string form is `${algorithm}:${hex}`, e.g. `sha256:9f86d0...`, which
+/// may be recorded in a file and parsed back using [`str::parse`].
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let check_status = Checksum::file_check(
+///     &app_zip_path,
+///     &Checksum::new(ChecksumAlgorithm::Sha256, app_zip_sha256),
+///     Some(&station.progress),
+/// )
+/// .await?;
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Checksum {
+    /// Algorithm used to compute the hash.
+    algorithm: ChecksumAlgorithm,
+    /// Hex encoded hash.
+    hex: String,
+}
+
+impl Checksum {
+    /// Size of each chunk read when hashing a file.
+    pub const CHUNK_SIZE: usize = 64 * 1024;
+
+    /// Returns a new `Checksum`.
+    ///
+    /// The hex encoded hash is compared case-insensitively.
+    ///
+    /// # Parameters
+    ///
+    /// * `algorithm`: Algorithm used to compute the hash.
+    /// * `hex`: Hex encoded hash.
+    pub fn new<S>(algorithm: ChecksumAlgorithm, hex: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            algorithm,
+            hex: hex.into().to_ascii_lowercase(),
+        }
+    }
+
+    /// Returns the algorithm used to compute the hash.
+    pub fn algorithm(&self) -> ChecksumAlgorithm {
+        self.algorithm
+    }
+
+    /// Returns the hex encoded hash.
+    pub fn hex(&self) -> &str {
+        &self.hex
+    }
+
+    /// Returns the checksum of in-memory bytes.
+    ///
+    /// # Parameters
+    ///
+    /// * `algorithm`: Algorithm to compute the hash with.
+    /// * `bytes`: Bytes to hash.
+    pub fn bytes(algorithm: ChecksumAlgorithm, bytes: &[u8]) -> Self {
+        let mut hasher = ChecksumHasher::new(algorithm);
+        hasher.update(bytes);
+
+        Self {
+            algorithm,
+            hex: hasher.finalize(),
+        }
+    }
+
+    /// Returns the checksum of a file's contents.
+    ///
+    /// The file is read in chunks of [`Checksum::CHUNK_SIZE`] bytes. If
+    /// `station_progress` is provided, its progress bar is advanced by the
+    /// number of bytes read, so the station's progress limit is usually
+    /// [`ProgressLimit::Bytes`] of the file's length.
+    ///
+    /// # Parameters
+    ///
+    /// * `algorithm`: Algorithm to compute the hash with.
+    /// * `path`: Path of the file.
+    /// * `station_progress`: Progress of the station that is hashing.
+    ///
+    /// [`ProgressLimit::Bytes`]: choochoo_cfg_model::rt::ProgressLimit::Bytes
+    pub async fn file(
+        algorithm: ChecksumAlgorithm,
+        path: &Path,
+        station_progress: Option<&StationProgress>,
+    ) -> Result<Self, OpsError> {
+        let mut hasher = ChecksumHasher::new(algorithm);
+        let mut buffer = vec![0u8; Self::CHUNK_SIZE];
+        Self::hasher_update_file(&mut hasher, &mut buffer, path, station_progress).await?;
+
+        Ok(Self {
+            algorithm,
+            hex: hasher.finalize(),
+        })
+    }
+
+    /// Returns the checksum of the files within a directory.
+    ///
+    /// Each file's path relative to `dir` is hashed along with its contents,
+    /// in sorted path order, so the checksum changes when files are added,
+    /// removed, renamed, or modified. If `station_progress` is provided, its
+    /// progress bar is advanced by the number of bytes read.
+    ///
+    /// # Parameters
+    ///
+    /// * `algorithm`: Algorithm to compute the hash with.
+    /// * `dir`: Path of the directory.
+    /// * `station_progress`: Progress of the station that is hashing.
+    pub async fn dir(
+        algorithm: ChecksumAlgorithm,
+        dir: &Path,
+        station_progress: Option<&StationProgress>,
+    ) -> Result<Self, OpsError> {
+        let mut file_paths = Vec::new();
+        let mut dir_paths = vec![dir.to_path_buf()];
+        while let Some(dir_path) = dir_paths.pop() {
+            let dir_read_error = |error| OpsError::DirRead {
+                path: dir_path.clone(),
+                error,
+            };
+            let mut read_dir = fs::read_dir(&dir_path).await.map_err(dir_read_error)?;
+            while let Some(entry) = read_dir.next_entry().await.map_err(dir_read_error)? {
+                let entry_path = entry.path();
+                let file_type =
+                    entry
+                        .file_type()
+                        .await
+                        .map_err(|error| OpsError::FileMetadata {
+                            path: entry_path.clone(),
+                            error,
+                        })?;
+                if file_type.is_dir() {
+                    dir_paths.push(entry_path);
+                } else {
+                    file_paths.push(entry_path);
+                }
+            }
+        }
+        let mut files = file_paths
+            .iter()
+            .map(|file_path| {
+                file_path
+                    .strip_prefix(dir)
+                    .unwrap_or(file_path)
+                    .to_path_buf()
+            })
+            .collect::<Vec<PathBuf>>();
+        files.sort();
+
+        Self::files(algorithm, dir, &files, station_progress).await
+    }
+
+    /// Returns the checksum of the given files within a directory.
+    ///
+    /// Each file's path relative to `dir` is hashed along with its contents,
+    /// in the order given, so the checksum changes when files are added,
+    /// removed, renamed, or modified. This is the same as [`Checksum::dir`]
+    /// when `files` holds every file in the directory in sorted order. If
+    /// `station_progress` is provided, its progress bar is advanced by the
+    /// number of bytes read.
+    ///
+    /// # Parameters
+    ///
+    /// * `algorithm`: Algorithm to compute the hash with.
+    /// * `dir`: Path of the directory.
+    /// * `files`: Paths of the files, relative to `dir`.
+    /// * `station_progress`: Progress of the station that is hashing.
+    pub async fn files(
+        algorithm: ChecksumAlgorithm,
+        dir: &Path,
+        files: &[PathBuf],
+        station_progress: Option<&StationProgress>,
+    ) -> Result<Self, OpsError> {
+        let mut hasher = ChecksumHasher::new(algorithm);
+        let mut buffer = vec![0u8; Self::CHUNK_SIZE];
+        for file in files {
+            hasher.update(file.to_string_lossy().as_bytes());
+            hasher.update(&[0]);
+            Self::hasher_update_file(&mut hasher, &mut buffer, &dir.join(file), station_progress)
+                .await?;
+        }
+
+        Ok(Self {
+            algorithm,
+            hex: hasher.finalize(),
+        })
+    }
+
+    /// Returns whether a file needs to be produced for it to match the
+    /// expected checksum.
+    ///
+    /// * If the file does not exist, this returns `WorkRequired`.
+    /// * If the file's checksum differs from `expected`, this returns
+    ///   `WorkRequired`.
+    /// * Otherwise, this returns `WorkNotRequired`.
+    ///
+    /// The file's checksum is computed using the expected checksum's
+    /// algorithm. `expected` may be recorded from a previous run using
+    /// [`Checksum::record`], or be published by a remote server.
+    ///
+    /// # Parameters
+    ///
+    /// * `path`: Path of the file.
+    /// * `expected`: Checksum the file should have.
+    /// * `station_progress`: Progress of the station that is hashing.
+    pub async fn file_check(
+        path: &Path,
+        expected: &Checksum,
+        station_progress: Option<&StationProgress>,
+    ) -> Result<CheckStatus, OpsError> {
+        if !path.exists() {
+            return Ok(CheckStatus::WorkRequired);
+        }
+
+        let checksum = Self::file(expected.algorithm, path, station_progress).await?;
+        if &checksum == expected {
+            Ok(CheckStatus::WorkNotRequired)
+        } else {
+            Ok(CheckStatus::WorkRequired)
+        }
+    }
+
+    /// Returns the checksum recorded in a file, or `None` if the file does not
+    /// exist.
+    ///
+    /// # Parameters
+    ///
+    /// * `path`: Path of the file the checksum is recorded in.
+    pub async fn recorded(path: &Path) -> Result<Option<Self>, OpsError> {
+        match fs::read_to_string(path).await {
+            Ok(checksum) => checksum.trim().parse().map(Some),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(OpsError::FileRead {
+                path: path.to_path_buf(),
+                error,
+            }),
+        }
+    }
+
+    /// Records this checksum in a file, so it can be read using
+    /// [`Checksum::recorded`].
+    ///
+    /// # Parameters
+    ///
+    /// * `path`: Path of the file to record the checksum in.
+    pub async fn record(&self, path: &Path) -> Result<(), OpsError> {
+        fs::write(path, self.to_string())
+            .await
+            .map_err(|error| OpsError::FileWrite {
+                path: path.to_path_buf(),
+                error,
+            })
+    }
+
+    /// Reads a file in chunks, updating the hasher with its contents.
+    async fn hasher_update_file(
+        hasher: &mut ChecksumHasher,
+        buffer: &mut [u8],
+        path: &Path,
+        station_progress: Option<&StationProgress>,
+    ) -> Result<(), OpsError> {
+        let mut file = File::open(path).await.map_err(|error| OpsError::FileOpen {
+            path: path.to_path_buf(),
+            error,
+        })?;
+
+        loop {
+            let byte_count = file
+                .read(buffer)
+                .await
+                .map_err(|error| OpsError::FileRead {
+                    path: path.to_path_buf(),
+                    error,
+                })?;
+            if byte_count == 0 {
+                break;
+            }
+            hasher.update(&buffer[..byte_count]);
+            if let Some(station_progress) = station_progress {
+                station_progress.progress_bar().inc(byte_count as u64);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Checksum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.algorithm, self.hex)
+    }
+}
+
+impl FromStr for Checksum {
+    type Err = OpsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (algorithm, hex) = s.split_once(':').ok_or_else(|| OpsError::ChecksumParse {
+            value: s.to_string(),
+        })?;
+        if hex.is_empty() || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(OpsError::ChecksumParse {
+                value: s.to_string(),
+            });
+        }
+
+        Ok(Self::new(algorithm.parse()?, hex))
+    }
+}
+
+/// Incremental hasher for each [`ChecksumAlgorithm`].
+enum ChecksumHasher {
+    /// SHA-256 hasher.
+    Sha256(Sha256),
+    /// BLAKE3 hasher.
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl ChecksumHasher {
+    fn new(algorithm: ChecksumAlgorithm) -> Self {
+        match algorithm {
+            ChecksumAlgorithm::Sha256 => Self::Sha256(Sha256::new()),
+            ChecksumAlgorithm::Blake3 => Self::Blake3(Box::new(blake3::Hasher::new())),
+        }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        match self {
+            Self::Sha256(hasher) => hasher.update(bytes),
+            Self::Blake3(hasher) => {
+                hasher.update(bytes);
+            }
+        }
+    }
+
+    fn finalize(self) -> String {
+        match self {
+            Self::Sha256(hasher) => format!("{:x}", hasher.finalize()),
+            Self::Blake3(hasher) => hasher.finalize().to_hex().to_string(),
+        }
+    }
+}
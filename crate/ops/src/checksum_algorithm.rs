@@ -0,0 +1,45 @@
+use std::{fmt, str::FromStr};
+
+use crate::OpsError;
+
+/// Hash algorithm used to compute a [`Checksum`].
+///
+/// [`Checksum`]: crate::Checksum
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ChecksumAlgorithm {
+    /// SHA-256, widely used to publish checksums of downloads.
+    Sha256,
+    /// BLAKE3, which is faster to compute for large files.
+    Blake3,
+}
+
+impl ChecksumAlgorithm {
+    /// Returns the name of the algorithm, used as the prefix of a checksum's
+    /// string form.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Sha256 => "sha256",
+            Self::Blake3 => "blake3",
+        }
+    }
+}
+
+impl fmt::Display for ChecksumAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ChecksumAlgorithm {
+    type Err = OpsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sha256" => Ok(Self::Sha256),
+            "blake3" => Ok(Self::Blake3),
+            _ => Err(OpsError::ChecksumParse {
+                value: s.to_string(),
+            }),
+        }
+    }
+}
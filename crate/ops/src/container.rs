@@ -0,0 +1,13 @@
+//! Station operations that build, push, and run container images.
+//!
+//! These operations run the `docker` CLI, which must be installed and able to
+//! reach the Docker daemon.
+
+pub use self::{
+    container_run_op::ContainerRunOp, image_build_op::ImageBuildOp, image_push_op::ImagePushOp,
+};
+
+mod container_run_op;
+mod docker_command;
+mod image_build_op;
+mod image_push_op;
@@ -0,0 +1,228 @@
+use choochoo_cfg_model::{
+    rt::{CheckStatus, ProgressLimit, ResIdLogical, ResIds, StationMutRef},
+    CleanFns, CreateFns, SetupFn, StationFn, StationOp,
+};
+use futures::future::LocalBoxFuture;
+
+use crate::{container::docker_command, OpsError};
+
+/// Station operation that runs a container in the background using
+/// `docker container run --detach`.
+///
+/// * **Check:** A container with the given name is running the given image.
+/// * **Work:** Removes any existing container with the given name, then runs a
+///   new container.
+/// * **Clean:** Stops and removes the container.
+///
+/// The container's ID is recorded in the station's [`ResIds`] under
+/// [`ContainerRunOp::RES_ID_CONTAINER_ID`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContainerRunOp {
+    /// Image to run, e.g. `app:1.0.0`.
+    image: String,
+    /// Name of the container.
+    name: String,
+    /// Additional arguments to `docker container run`, e.g. `--publish`.
+    run_args: Vec<String>,
+}
+
+impl ContainerRunOp {
+    /// Logical ID of the container's ID in the station's [`ResIds`].
+    pub const RES_ID_CONTAINER_ID: &'static str = "container_run_container_id";
+
+    /// Returns a new `ContainerRunOp`.
+    ///
+    /// # Parameters
+    ///
+    /// * `image`: Image to run, e.g. `app:1.0.0`.
+    /// * `name`: Name of the container.
+    pub fn new<I, N>(image: I, name: N) -> Self
+    where
+        I: Into<String>,
+        N: Into<String>,
+    {
+        Self {
+            image: image.into(),
+            name: name.into(),
+            run_args: Vec::new(),
+        }
+    }
+
+    /// Adds an argument to `docker container run`, e.g. `--publish`.
+    ///
+    /// Arguments are passed before the image name.
+    #[must_use]
+    pub fn with_run_arg<S>(mut self, run_arg: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.run_args.push(run_arg.into());
+        self
+    }
+
+    /// Returns the image to run.
+    pub fn image(&self) -> &str {
+        &self.image
+    }
+
+    /// Returns the name of the container.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the additional arguments to `docker container run`.
+    pub fn run_args(&self) -> &[String] {
+        &self.run_args
+    }
+
+    /// Returns the arguments passed to `docker` to run the container.
+    ///
+    /// The additional run arguments are passed before the image name, so they
+    /// are interpreted as options to `docker container run` rather than the
+    /// container's command.
+    pub fn docker_args(&self) -> Vec<&str> {
+        let mut args = vec!["container", "run", "--detach", "--name", self.name.as_str()];
+        args.extend(self.run_args.iter().map(String::as_str));
+        args.push(&self.image);
+        args
+    }
+
+    /// Returns the [`StationOp`] that runs the container.
+    pub fn build<E>(self) -> StationOp<E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        let create_fns = CreateFns::new(
+            SetupFn::new(|_station, _train_resources| {
+                Box::pin(async move { Result::<ProgressLimit, E>::Ok(ProgressLimit::Steps(2)) })
+            }),
+            Self::work_fn(self.clone()),
+        )
+        .with_check_fn(Self::check_fn(self.image.clone(), self.name.clone()));
+        let clean_fns = CleanFns::new(
+            SetupFn::new(|_station, _train_resources| {
+                Box::pin(async move { Result::<ProgressLimit, E>::Ok(ProgressLimit::Steps(1)) })
+            }),
+            Self::clean_work_fn(self.name.clone()),
+        )
+        .with_check_fn(Self::clean_check_fn(self.name));
+
+        StationOp::new(create_fns, Some(clean_fns))
+    }
+
+    fn check_fn<E>(image: String, name: String) -> StationFn<CheckStatus, E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new0(
+            move |_station: &mut StationMutRef<'_, E>|
+                  -> LocalBoxFuture<'_, Result<CheckStatus, E>> {
+                let image = image.clone();
+                let name = name.clone();
+                Box::pin(async move {
+                    let state = docker_command::stdout_if_success(&[
+                        "container",
+                        "inspect",
+                        "--format",
+                        "{{.State.Running}} {{.Config.Image}}",
+                        &name,
+                    ])
+                    .await?;
+                    let check_status = match state {
+                        Some(state) if state == format!("true {image}") => {
+                            CheckStatus::WorkNotRequired
+                        }
+                        Some(_) | None => CheckStatus::WorkRequired,
+                    };
+
+                    Result::<CheckStatus, E>::Ok(check_status)
+                })
+            },
+        )
+    }
+
+    fn work_fn<E>(container_run_op: ContainerRunOp) -> StationFn<ResIds, (ResIds, E), E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new0(
+            move |station: &mut StationMutRef<'_, E>|
+                  -> LocalBoxFuture<'_, Result<ResIds, (ResIds, E)>> {
+                let container_run_op = container_run_op.clone();
+                Box::pin(async move {
+                    let mut res_ids = ResIds::new();
+                    let name = container_run_op.name.as_str();
+
+                    // A stopped container, or one running a different image, still holds the
+                    // name, so it is replaced.
+                    if container_exists(name)
+                        .await
+                        .map_err(|error| (res_ids.clone(), E::from(error)))?
+                    {
+                        docker_command::stdout(&["container", "rm", "--force", name])
+                            .await
+                            .map_err(|error| (res_ids.clone(), E::from(error)))?;
+                    }
+                    station.progress.progress_bar().inc(1);
+
+                    let container_id = docker_command::stdout(&container_run_op.docker_args())
+                        .await
+                        .map_err(|error| (res_ids.clone(), E::from(error)))?;
+                    station.progress.progress_bar().inc(1);
+
+                    let _ = res_ids.insert(
+                        ResIdLogical::new(Self::RES_ID_CONTAINER_ID),
+                        container_id,
+                    );
+
+                    Result::<ResIds, (ResIds, E)>::Ok(res_ids)
+                })
+            },
+        )
+    }
+
+    fn clean_check_fn<E>(name: String) -> StationFn<CheckStatus, E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new0(
+            move |_station: &mut StationMutRef<'_, E>|
+                  -> LocalBoxFuture<'_, Result<CheckStatus, E>> {
+                let name = name.clone();
+                Box::pin(async move {
+                    let check_status = if container_exists(&name).await? {
+                        CheckStatus::WorkRequired
+                    } else {
+                        CheckStatus::WorkNotRequired
+                    };
+                    Result::<CheckStatus, E>::Ok(check_status)
+                })
+            },
+        )
+    }
+
+    fn clean_work_fn<E>(name: String) -> StationFn<(), E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new0(
+            move |station: &mut StationMutRef<'_, E>| -> LocalBoxFuture<'_, Result<(), E>> {
+                let name = name.clone();
+                Box::pin(async move {
+                    docker_command::stdout(&["container", "rm", "--force", &name]).await?;
+                    station.progress.progress_bar().inc(1);
+                    Result::<(), E>::Ok(())
+                })
+            },
+        )
+    }
+}
+
+/// Returns whether a container with the given name exists, whether or not it
+/// is running.
+async fn container_exists(name: &str) -> Result<bool, OpsError> {
+    let container_id =
+        docker_command::stdout_if_success(&["container", "inspect", "--format", "{{.Id}}", name])
+            .await?;
+    Ok(container_id.is_some())
+}
@@ -0,0 +1,147 @@
+use std::{
+    collections::VecDeque,
+    process::{Output, Stdio},
+};
+
+use choochoo_cfg_model::rt::StationProgress;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, BufReader},
+    process::Command,
+};
+
+use crate::OpsError;
+
+/// Name of the `docker` executable.
+const DOCKER: &str = "docker";
+/// Number of lines of command output to keep for error messages.
+const OUTPUT_TAIL_LINES: usize = 20;
+
+/// Runs a `docker` command, and returns its trimmed standard output.
+///
+/// Returns an error if the command exits unsuccessfully.
+pub(crate) async fn stdout(args: &[&str]) -> Result<String, OpsError> {
+    let output = output(args).await?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(OpsError::ContainerCommandFailed {
+            command: command_display(args),
+            status: output.status,
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        })
+    }
+}
+
+/// Runs a `docker` command, and returns its trimmed standard output if it
+/// exits successfully.
+///
+/// This is used for `inspect` commands, which exit unsuccessfully when the
+/// object does not exist.
+pub(crate) async fn stdout_if_success(args: &[&str]) -> Result<Option<String>, OpsError> {
+    let output = output(args).await?;
+    if output.status.success() {
+        Ok(Some(
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        ))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Runs a `docker` command, advancing the station's progress bar for each line
+/// of output.
+///
+/// Stations that use this should have a [`ProgressLimit::Unknown`] limit, so
+/// the progress bar is displayed as a spinner.
+///
+/// [`ProgressLimit::Unknown`]: choochoo_cfg_model::rt::ProgressLimit::Unknown
+pub(crate) async fn run_with_progress(
+    station_progress: &StationProgress,
+    args: &[&str],
+) -> Result<(), OpsError> {
+    let mut child = Command::new(DOCKER)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| OpsError::ContainerCommandSpawn {
+            command: command_display(args),
+            error,
+        })?;
+
+    // `docker build` writes its progress to stderr, so both streams advance the
+    // progress bar.
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    let (stdout_result, stderr_result, status_result) = futures::join!(
+        lines_progress(station_progress, stdout),
+        lines_progress(station_progress, stderr),
+        child.wait(),
+    );
+    let spawn_error = |error| OpsError::ContainerCommandSpawn {
+        command: command_display(args),
+        error,
+    };
+    stdout_result.map_err(spawn_error)?;
+    let stderr = stderr_result.map_err(spawn_error)?;
+    let status = status_result.map_err(spawn_error)?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(OpsError::ContainerCommandFailed {
+            command: command_display(args),
+            status,
+            stderr,
+        })
+    }
+}
+
+/// Runs a `docker` command and returns its output.
+async fn output(args: &[&str]) -> Result<Output, OpsError> {
+    Command::new(DOCKER)
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .map_err(|error| OpsError::ContainerCommandSpawn {
+            command: command_display(args),
+            error,
+        })
+}
+
+/// Reads lines from the stream, advancing the progress bar for each line, and
+/// returns the last lines read.
+async fn lines_progress<R>(
+    station_progress: &StationProgress,
+    stream: Option<R>,
+) -> Result<String, std::io::Error>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut lines_tail = VecDeque::with_capacity(OUTPUT_TAIL_LINES);
+    if let Some(stream) = stream {
+        let mut lines = BufReader::new(stream).lines();
+        while let Some(line) = lines.next_line().await? {
+            station_progress.progress_bar().inc(1);
+
+            if lines_tail.len() == OUTPUT_TAIL_LINES {
+                lines_tail.pop_front();
+            }
+            lines_tail.push_back(line);
+        }
+    }
+
+    Ok(lines_tail.into_iter().collect::<Vec<_>>().join("\n"))
+}
+
+/// Returns the command as it would be typed into a shell.
+fn command_display(args: &[&str]) -> String {
+    let mut command = String::from(DOCKER);
+    args.iter().for_each(|arg| {
+        command.push(' ');
+        command.push_str(arg);
+    });
+    command
+}
@@ -0,0 +1,320 @@
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use choochoo_cfg_model::{
+    rt::{CheckStatus, ProgressLimit, ResIdLogical, ResIds, StationMutRef},
+    CleanFns, CreateFns, SetupFn, StationFn, StationOp,
+};
+use futures::future::LocalBoxFuture;
+use tokio::fs;
+
+use crate::{container::docker_command, Checksum, ChecksumAlgorithm, OpsError};
+
+/// Station operation that builds a container image using `docker build`.
+///
+/// * **Check:** The image exists with the ID recorded by the last build, and
+///   the build context and Dockerfile are unchanged since then.
+/// * **Work:** Builds and tags the image, advancing the station's progress bar
+///   for each line of build output.
+/// * **Clean:** Removes the image tag.
+///
+/// The built image's ID is recorded in the station's [`ResIds`] under
+/// [`ImageBuildOp::RES_ID_IMAGE_ID`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImageBuildOp {
+    /// Directory to use as the build context.
+    context_dir: PathBuf,
+    /// Path of the Dockerfile, if it is not `Dockerfile` in the context
+    /// directory.
+    dockerfile_path: Option<PathBuf>,
+    /// Tag to apply to the built image, e.g. `app:1.0.0`.
+    tag: String,
+}
+
+impl ImageBuildOp {
+    /// Name of the file in the station's directory that records the last build.
+    pub const RECORD_FILE_NAME: &'static str = ".choochoo_image_build";
+    /// Logical ID of the built image's ID in the station's [`ResIds`].
+    pub const RES_ID_IMAGE_ID: &'static str = "image_build_image_id";
+
+    /// Returns a new `ImageBuildOp`.
+    ///
+    /// # Parameters
+    ///
+    /// * `context_dir`: Directory to use as the build context.
+    /// * `tag`: Tag to apply to the built image, e.g. `app:1.0.0`.
+    pub fn new<P, T>(context_dir: P, tag: T) -> Self
+    where
+        P: Into<PathBuf>,
+        T: Into<String>,
+    {
+        Self {
+            context_dir: context_dir.into(),
+            dockerfile_path: None,
+            tag: tag.into(),
+        }
+    }
+
+    /// Sets the path of the Dockerfile.
+    ///
+    /// Defaults to `Dockerfile` in the context directory.
+    #[must_use]
+    pub fn with_dockerfile_path<P>(mut self, dockerfile_path: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.dockerfile_path = Some(dockerfile_path.into());
+        self
+    }
+
+    /// Returns the directory to use as the build context.
+    pub fn context_dir(&self) -> &Path {
+        &self.context_dir
+    }
+
+    /// Returns the path of the Dockerfile, if it is not `Dockerfile` in the
+    /// context directory.
+    pub fn dockerfile_path(&self) -> Option<&Path> {
+        self.dockerfile_path.as_deref()
+    }
+
+    /// Returns the tag to apply to the built image.
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Returns the arguments passed to `docker` to build the image.
+    pub fn docker_args(&self) -> Vec<String> {
+        let mut args = vec![
+            String::from("build"),
+            String::from("--tag"),
+            self.tag.clone(),
+        ];
+        if let Some(dockerfile_path) = self.dockerfile_path.as_deref() {
+            args.push(String::from("--file"));
+            args.push(dockerfile_path.to_string_lossy().into_owned());
+        }
+        args.push(self.context_dir.to_string_lossy().into_owned());
+        args
+    }
+
+    /// Returns the contents of the record file for a build.
+    ///
+    /// # Parameters
+    ///
+    /// * `inputs_checksum`: Checksum of the build context and Dockerfile.
+    /// * `image_id`: ID of the built image.
+    pub fn record_format(inputs_checksum: &str, image_id: &str) -> String {
+        format!("{inputs_checksum}\n{image_id}\n")
+    }
+
+    /// Returns the inputs checksum and image ID from the contents of the
+    /// record file, or `None` if the record is incomplete.
+    ///
+    /// # Parameters
+    ///
+    /// * `record`: Contents of the record file.
+    pub fn record_parse(record: &str) -> Option<(&str, &str)> {
+        let mut record_lines = record.lines();
+        match (record_lines.next(), record_lines.next()) {
+            (Some(inputs_checksum), Some(image_id))
+                if !inputs_checksum.is_empty() && !image_id.is_empty() =>
+            {
+                Some((inputs_checksum, image_id))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the [`StationOp`] that builds the image.
+    pub fn build<E>(self) -> StationOp<E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        let create_fns = CreateFns::new(
+            SetupFn::new(|_station, _train_resources| {
+                Box::pin(async move { Result::<ProgressLimit, E>::Ok(ProgressLimit::Unknown) })
+            }),
+            Self::work_fn(self.clone()),
+        )
+        .with_check_fn(Self::check_fn(self.clone()));
+        let clean_fns = CleanFns::new(
+            SetupFn::new(|_station, _train_resources| {
+                Box::pin(async move { Result::<ProgressLimit, E>::Ok(ProgressLimit::Steps(1)) })
+            }),
+            Self::clean_work_fn(self.tag.clone()),
+        )
+        .with_check_fn(Self::clean_check_fn(self.tag));
+
+        StationOp::new(create_fns, Some(clean_fns))
+    }
+
+    fn check_fn<E>(image_build_op: ImageBuildOp) -> StationFn<CheckStatus, E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new0(
+            move |station: &mut StationMutRef<'_, E>|
+                  -> LocalBoxFuture<'_, Result<CheckStatus, E>> {
+                let image_build_op = image_build_op.clone();
+                let record_path = station.dir.join(Self::RECORD_FILE_NAME);
+                Box::pin(async move {
+                    let record = match fs::read_to_string(&record_path).await {
+                        Ok(record) => record,
+                        Err(error) if error.kind() == ErrorKind::NotFound => {
+                            return Ok(CheckStatus::WorkRequired);
+                        }
+                        Err(error) => {
+                            return Err(E::from(OpsError::FileRead {
+                                path: record_path,
+                                error,
+                            }));
+                        }
+                    };
+                    let (inputs_checksum_recorded, image_id_recorded) =
+                        match Self::record_parse(&record) {
+                            Some(record_parsed) => record_parsed,
+                            None => return Ok(CheckStatus::WorkRequired),
+                        };
+
+                    let inputs_checksum = image_build_op.inputs_checksum().await?;
+                    if inputs_checksum != inputs_checksum_recorded {
+                        return Ok(CheckStatus::WorkRequired);
+                    }
+
+                    let image_id = image_id(&image_build_op.tag).await?;
+                    let check_status = match image_id {
+                        Some(image_id) if image_id == image_id_recorded => {
+                            CheckStatus::WorkNotRequired
+                        }
+                        Some(_) | None => CheckStatus::WorkRequired,
+                    };
+
+                    Result::<CheckStatus, E>::Ok(check_status)
+                })
+            },
+        )
+    }
+
+    fn work_fn<E>(image_build_op: ImageBuildOp) -> StationFn<ResIds, (ResIds, E), E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new0(
+            move |station: &mut StationMutRef<'_, E>|
+                  -> LocalBoxFuture<'_, Result<ResIds, (ResIds, E)>> {
+                let image_build_op = image_build_op.clone();
+                let record_path = station.dir.join(Self::RECORD_FILE_NAME);
+                Box::pin(async move {
+                    let mut res_ids = ResIds::new();
+
+                    // The checksum is computed before building, so changes made during the
+                    // build cause the next check to rebuild the image.
+                    let inputs_checksum = image_build_op
+                        .inputs_checksum()
+                        .await
+                        .map_err(|error| (res_ids.clone(), E::from(error)))?;
+
+                    let args = image_build_op.docker_args();
+                    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+                    docker_command::run_with_progress(&station.progress, &args)
+                        .await
+                        .map_err(|error| (res_ids.clone(), E::from(error)))?;
+
+                    let image_id = docker_command::stdout(&[
+                        "image",
+                        "inspect",
+                        "--format",
+                        "{{.Id}}",
+                        &image_build_op.tag,
+                    ])
+                    .await
+                    .map_err(|error| (res_ids.clone(), E::from(error)))?;
+                    let _ = res_ids.insert(
+                        ResIdLogical::new(Self::RES_ID_IMAGE_ID),
+                        image_id.clone(),
+                    );
+
+                    let record = Self::record_format(&inputs_checksum, &image_id);
+                    fs::write(&record_path, record).await.map_err(|error| {
+                        let error = OpsError::FileWrite {
+                            path: record_path.clone(),
+                            error,
+                        };
+                        (res_ids.clone(), E::from(error))
+                    })?;
+
+                    Result::<ResIds, (ResIds, E)>::Ok(res_ids)
+                })
+            },
+        )
+    }
+
+    fn clean_check_fn<E>(tag: String) -> StationFn<CheckStatus, E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new0(
+            move |_station: &mut StationMutRef<'_, E>|
+                  -> LocalBoxFuture<'_, Result<CheckStatus, E>> {
+                let tag = tag.clone();
+                Box::pin(async move {
+                    let check_status = if image_id(&tag).await?.is_some() {
+                        CheckStatus::WorkRequired
+                    } else {
+                        CheckStatus::WorkNotRequired
+                    };
+                    Result::<CheckStatus, E>::Ok(check_status)
+                })
+            },
+        )
+    }
+
+    fn clean_work_fn<E>(tag: String) -> StationFn<(), E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new0(
+            move |station: &mut StationMutRef<'_, E>| -> LocalBoxFuture<'_, Result<(), E>> {
+                let tag = tag.clone();
+                let record_path = station.dir.join(Self::RECORD_FILE_NAME);
+                Box::pin(async move {
+                    docker_command::stdout(&["image", "rm", &tag]).await?;
+                    match fs::remove_file(&record_path).await {
+                        Ok(()) => {}
+                        Err(error) if error.kind() == ErrorKind::NotFound => {}
+                        Err(error) => {
+                            return Err(E::from(OpsError::FileRemove {
+                                path: record_path,
+                                error,
+                            }));
+                        }
+                    }
+                    station.progress.progress_bar().inc(1);
+                    Result::<(), E>::Ok(())
+                })
+            },
+        )
+    }
+
+    /// Returns the checksum of the build context and Dockerfile.
+    async fn inputs_checksum(&self) -> Result<String, OpsError> {
+        let context_checksum =
+            Checksum::dir(ChecksumAlgorithm::Blake3, &self.context_dir, None).await?;
+        match self.dockerfile_path.as_deref() {
+            Some(dockerfile_path) => {
+                let dockerfile_checksum =
+                    Checksum::file(ChecksumAlgorithm::Blake3, dockerfile_path, None).await?;
+                Ok(format!("{context_checksum} {dockerfile_checksum}"))
+            }
+            None => Ok(context_checksum.to_string()),
+        }
+    }
+}
+
+/// Returns the ID of the image with the given tag, if it exists.
+async fn image_id(tag: &str) -> Result<Option<String>, OpsError> {
+    docker_command::stdout_if_success(&["image", "inspect", "--format", "{{.Id}}", tag]).await
+}
@@ -0,0 +1,177 @@
+use choochoo_cfg_model::{
+    rt::{CheckStatus, ProgressLimit, ResIdLogical, ResIds, StationMutRef},
+    CreateFns, SetupFn, StationFn, StationOp,
+};
+use futures::future::LocalBoxFuture;
+
+use crate::{container::docker_command, OpsError};
+
+/// Station operation that pushes a container image to a registry using
+/// `docker push`.
+///
+/// * **Check:** The local image has a digest for the image's repository, and
+///   the registry's manifest for the image's tag references that digest.
+/// * **Work:** Pushes the image, advancing the station's progress bar for each
+///   line of push output.
+///
+/// The `docker` CLI cannot delete images from a registry, so this operation
+/// has no clean functions.
+///
+/// The pushed image's digest is recorded in the station's [`ResIds`] under
+/// [`ImagePushOp::RES_ID_DIGEST`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImagePushOp {
+    /// Image to push, e.g. `registry.example.com/app:1.0.0`.
+    image: String,
+}
+
+impl ImagePushOp {
+    /// Logical ID of the pushed image's digest in the station's [`ResIds`].
+    pub const RES_ID_DIGEST: &'static str = "image_push_digest";
+
+    /// Returns a new `ImagePushOp`.
+    ///
+    /// # Parameters
+    ///
+    /// * `image`: Image to push, e.g. `registry.example.com/app:1.0.0`.
+    pub fn new<S>(image: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            image: image.into(),
+        }
+    }
+
+    /// Returns the image to push.
+    pub fn image(&self) -> &str {
+        &self.image
+    }
+
+    /// Returns the digest of the image in its repository, from the output of
+    /// `docker image inspect`, which lists one `repository@digest` per line.
+    ///
+    /// Digests in other repositories are ignored.
+    ///
+    /// # Parameters
+    ///
+    /// * `image`: Image reference, e.g. `registry.example.com/app:1.0.0`.
+    /// * `repo_digests`: Repository digests of the local image.
+    pub fn repo_digest_find<'d>(image: &str, repo_digests: &'d str) -> Option<&'d str> {
+        let repository = image_repository(image);
+        repo_digests.lines().find_map(|repo_digest| {
+            repo_digest
+                .strip_prefix(repository)
+                .and_then(|digest| digest.strip_prefix('@'))
+        })
+    }
+
+    /// Returns the [`StationOp`] that pushes the image.
+    pub fn build<E>(self) -> StationOp<E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        let create_fns = CreateFns::new(
+            SetupFn::new(|_station, _train_resources| {
+                Box::pin(async move { Result::<ProgressLimit, E>::Ok(ProgressLimit::Unknown) })
+            }),
+            Self::work_fn(self.image.clone()),
+        )
+        .with_check_fn(Self::check_fn(self.image));
+
+        StationOp::new(create_fns, None)
+    }
+
+    fn check_fn<E>(image: String) -> StationFn<CheckStatus, E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new0(
+            move |_station: &mut StationMutRef<'_, E>|
+                  -> LocalBoxFuture<'_, Result<CheckStatus, E>> {
+                let image = image.clone();
+                Box::pin(async move {
+                    let digest = match repo_digest(&image).await? {
+                        Some(digest) => digest,
+                        None => return Ok(CheckStatus::WorkRequired),
+                    };
+
+                    let manifest = docker_command::stdout_if_success(&[
+                        "manifest",
+                        "inspect",
+                        "--verbose",
+                        &image,
+                    ])
+                    .await?;
+                    let check_status = match manifest {
+                        Some(manifest) if manifest.contains(&digest) => {
+                            CheckStatus::WorkNotRequired
+                        }
+                        Some(_) | None => CheckStatus::WorkRequired,
+                    };
+
+                    Result::<CheckStatus, E>::Ok(check_status)
+                })
+            },
+        )
+    }
+
+    fn work_fn<E>(image: String) -> StationFn<ResIds, (ResIds, E), E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new0(
+            move |station: &mut StationMutRef<'_, E>|
+                  -> LocalBoxFuture<'_, Result<ResIds, (ResIds, E)>> {
+                let image = image.clone();
+                Box::pin(async move {
+                    let mut res_ids = ResIds::new();
+                    docker_command::run_with_progress(&station.progress, &["push", &image])
+                        .await
+                        .map_err(|error| (res_ids.clone(), E::from(error)))?;
+
+                    let digest = repo_digest(&image)
+                        .await
+                        .map_err(|error| (res_ids.clone(), E::from(error)))?;
+                    if let Some(digest) = digest {
+                        let _ = res_ids.insert(ResIdLogical::new(Self::RES_ID_DIGEST), digest);
+                    }
+
+                    Result::<ResIds, (ResIds, E)>::Ok(res_ids)
+                })
+            },
+        )
+    }
+}
+
+/// Returns the digest of the local image in the image's repository, if it has
+/// been pushed to or pulled from the repository.
+async fn repo_digest(image: &str) -> Result<Option<String>, OpsError> {
+    let repo_digests = docker_command::stdout_if_success(&[
+        "image",
+        "inspect",
+        "--format",
+        "{{range .RepoDigests}}{{println .}}{{end}}",
+        image,
+    ])
+    .await?;
+
+    let digest = repo_digests.and_then(|repo_digests| {
+        ImagePushOp::repo_digest_find(image, &repo_digests).map(str::to_string)
+    });
+
+    Ok(digest)
+}
+
+/// Returns the image reference without its tag or digest.
+///
+/// `registry.example.com:5000/app:1.0.0` returns
+/// `registry.example.com:5000/app`.
+fn image_repository(image: &str) -> &str {
+    let image = image.split('@').next().unwrap_or(image);
+    let name_start = image.rfind('/').map(|index| index + 1).unwrap_or(0);
+    match image[name_start..].rfind(':') {
+        Some(tag_index) => &image[..name_start + tag_index],
+        None => image,
+    }
+}
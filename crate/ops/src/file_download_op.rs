@@ -0,0 +1,383 @@
+use std::{
+    fmt,
+    io::ErrorKind,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+use choochoo_cfg_model::{
+    rt::{CheckStatus, ProgressLimit, ProgressWriter, ResIdLogical, ResIds, StationMutRef},
+    CleanFns, CreateFns, SetupFn, StationFn, StationOp,
+};
+use futures::{future::LocalBoxFuture, StreamExt};
+use reqwest::header::CONTENT_LENGTH;
+use tokio::{
+    fs::{self, File},
+    io::{AsyncWriteExt, BufWriter},
+};
+
+use crate::{Checksum, ChecksumAlgorithm, OpsError};
+
+// **Note:** `Clone` and `Debug` are manually implemented to avoid the trait
+// bound on `U`.
+/// Station operation that downloads a file over HTTP.
+///
+/// The URL to download from is read from the `U` resource, which allows it to
+/// be inserted by an earlier station's setup function. The file is downloaded
+/// using the train's shared `reqwest::Client`, so the `http` feature of
+/// `choochoo_rt_logic` must be enabled.
+///
+/// * **Check:** The file's contents match the expected checksum, if one is set.
+///   Otherwise, the file's contents match the checksum recorded by the last
+///   download, and its length matches the `Content-Length` reported by the
+///   server. If the server does not report a length, the file matching the
+///   recorded checksum is sufficient.
+/// * **Work:** Streams the response body to the file, advancing the station's
+///   progress bar by the bytes written. The file's checksum is compared with
+///   the expected checksum, if one is set, and recorded in
+///   [`FileDownloadOp::RECORD_FILE_NAME`] in the station's directory.
+/// * **Clean:** Deletes the file and the recorded checksum.
+///
+/// The file's path is recorded in the station's [`ResIds`] under
+/// [`FileDownloadOp::RES_ID_PATH`].
+pub struct FileDownloadOp<U> {
+    /// Path to download the file to.
+    ///
+    /// Relative paths are relative to the station's directory.
+    dest_path: PathBuf,
+    /// Checksum the downloaded file is expected to have.
+    checksum: Option<Checksum>,
+    /// Marker.
+    marker: PhantomData<U>,
+}
+
+impl<U> FileDownloadOp<U>
+where
+    U: AsRef<str> + fmt::Debug + Send + Sync + 'static,
+{
+    /// Name of the file in the station's directory that records the checksum
+    /// of the last download.
+    pub const RECORD_FILE_NAME: &'static str = ".choochoo_file_download";
+    /// Logical ID of the downloaded file's path in the station's [`ResIds`].
+    pub const RES_ID_PATH: &'static str = "file_download_path";
+
+    /// Returns a new `FileDownloadOp`.
+    ///
+    /// # Parameters
+    ///
+    /// * `dest_path`: Path to download the file to. Relative paths are relative
+    ///   to the station's directory.
+    pub fn new<P>(dest_path: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self {
+            dest_path: dest_path.into(),
+            checksum: None,
+            marker: PhantomData,
+        }
+    }
+
+    /// Sets the checksum the downloaded file is expected to have, such as one
+    /// published alongside the file.
+    ///
+    /// When set, the file is only downloaded if the local file does not match
+    /// the checksum, and the download fails if the downloaded file does not
+    /// match it.
+    #[must_use]
+    pub fn with_checksum(mut self, checksum: Checksum) -> Self {
+        self.checksum = Some(checksum);
+        self
+    }
+
+    /// Returns the path to download the file to.
+    pub fn dest_path(&self) -> &Path {
+        &self.dest_path
+    }
+
+    /// Returns the checksum the downloaded file is expected to have.
+    pub fn checksum(&self) -> Option<&Checksum> {
+        self.checksum.as_ref()
+    }
+
+    /// Returns the [`StationOp`] that downloads the file.
+    pub fn build<E>(self) -> StationOp<E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        let create_fns = CreateFns::new(
+            SetupFn::new(|_station, _train_resources| {
+                Box::pin(async move { Result::<ProgressLimit, E>::Ok(ProgressLimit::Unknown) })
+            }),
+            Self::work_fn(self.dest_path.clone(), self.checksum.clone()),
+        )
+        .with_check_fn(Self::check_fn(self.dest_path.clone(), self.checksum));
+        let clean_fns = CleanFns::new(
+            SetupFn::new(|_station, _train_resources| {
+                Box::pin(async move { Result::<ProgressLimit, E>::Ok(ProgressLimit::Steps(1)) })
+            }),
+            Self::clean_work_fn(self.dest_path.clone()),
+        )
+        .with_check_fn(Self::clean_check_fn(self.dest_path));
+
+        StationOp::new(create_fns, Some(clean_fns))
+    }
+
+    fn check_fn<E>(dest_path: PathBuf, checksum: Option<Checksum>) -> StationFn<CheckStatus, E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new2(
+            move |station: &mut StationMutRef<'_, E>,
+                  client: &reqwest::Client,
+                  url: &U|
+                  -> LocalBoxFuture<'_, Result<CheckStatus, E>> {
+                let dest_path = station.dir.join(&dest_path);
+                let record_path = station.dir.join(Self::RECORD_FILE_NAME);
+                let checksum = checksum.clone();
+                Box::pin(async move {
+                    // The published checksum identifies the remote file, so the server does
+                    // not need to be queried.
+                    if let Some(checksum) = checksum.as_ref() {
+                        let check_status = Checksum::file_check(&dest_path, checksum, None).await?;
+                        return Ok(check_status);
+                    }
+
+                    // A file that was modified since it was downloaded, including one whose
+                    // length is unchanged, is downloaded again.
+                    let checksum_recorded = match Checksum::recorded(&record_path).await? {
+                        Some(checksum_recorded) => checksum_recorded,
+                        None => return Ok(CheckStatus::WorkRequired),
+                    };
+                    if Checksum::file_check(&dest_path, &checksum_recorded, None).await?
+                        == CheckStatus::WorkRequired
+                    {
+                        return Ok(CheckStatus::WorkRequired);
+                    }
+
+                    let local_length = match fs::metadata(&dest_path).await {
+                        Ok(metadata) => metadata.len(),
+                        Err(error) if error.kind() == ErrorKind::NotFound => {
+                            return Ok(CheckStatus::WorkRequired);
+                        }
+                        Err(error) => {
+                            return Err(E::from(OpsError::FileMetadata {
+                                path: dest_path,
+                                error,
+                            }));
+                        }
+                    };
+
+                    let url = url.as_ref();
+                    let response =
+                        client
+                            .head(url)
+                            .send()
+                            .await
+                            .map_err(|error| OpsError::HttpRequest {
+                                url: url.to_string(),
+                                error,
+                            })?;
+                    if !response.status().is_success() {
+                        return Err(E::from(OpsError::HttpResponseStatus {
+                            url: url.to_string(),
+                            status: response.status(),
+                        }));
+                    }
+
+                    // `Response::content_length` is the length of the `HEAD` response's body,
+                    // so we read the header instead.
+                    let remote_length = response
+                        .headers()
+                        .get(CONTENT_LENGTH)
+                        .and_then(|content_length| content_length.to_str().ok())
+                        .and_then(|content_length| content_length.parse::<u64>().ok());
+                    let check_status = match remote_length {
+                        Some(remote_length) if remote_length != local_length => {
+                            CheckStatus::WorkRequired
+                        }
+                        Some(_) | None => CheckStatus::WorkNotRequired,
+                    };
+
+                    Result::<CheckStatus, E>::Ok(check_status)
+                })
+            },
+        )
+    }
+
+    fn work_fn<E>(
+        dest_path: PathBuf,
+        checksum: Option<Checksum>,
+    ) -> StationFn<ResIds, (ResIds, E), E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new2(
+            move |station: &mut StationMutRef<'_, E>,
+                  client: &reqwest::Client,
+                  url: &U|
+                  -> LocalBoxFuture<'_, Result<ResIds, (ResIds, E)>> {
+                let dest_path = station.dir.join(&dest_path);
+                let record_path = station.dir.join(Self::RECORD_FILE_NAME);
+                let checksum = checksum.clone();
+                Box::pin(async move {
+                    let res_ids = ResIds::new();
+                    let url = url.as_ref();
+                    let response = client
+                        .get(url)
+                        .send()
+                        .await
+                        .map_err(|error| OpsError::HttpRequest {
+                            url: url.to_string(),
+                            error,
+                        })
+                        .map_err(|error| (res_ids.clone(), E::from(error)))?;
+                    if !response.status().is_success() {
+                        let error = OpsError::HttpResponseStatus {
+                            url: url.to_string(),
+                            status: response.status(),
+                        };
+                        return Err((res_ids, E::from(error)));
+                    }
+                    if let Some(content_length) = response.content_length() {
+                        station
+                            .progress
+                            .progress_limit_set(ProgressLimit::Bytes(content_length));
+                    }
+
+                    let file = File::create(&dest_path).await.map_err(|error| {
+                        let error = OpsError::FileCreate {
+                            path: dest_path.clone(),
+                            error,
+                        };
+                        (res_ids.clone(), E::from(error))
+                    })?;
+                    let mut writer = ProgressWriter::new(&station.progress, BufWriter::new(file));
+                    let mut byte_stream = response.bytes_stream();
+                    while let Some(bytes) = byte_stream.next().await {
+                        let bytes = bytes.map_err(|error| {
+                            let error = OpsError::HttpRequest {
+                                url: url.to_string(),
+                                error,
+                            };
+                            (res_ids.clone(), E::from(error))
+                        })?;
+                        writer.write_all(&bytes).await.map_err(|error| {
+                            let error = OpsError::FileWrite {
+                                path: dest_path.clone(),
+                                error,
+                            };
+                            (res_ids.clone(), E::from(error))
+                        })?;
+                    }
+                    writer.flush().await.map_err(|error| {
+                        let error = OpsError::FileWrite {
+                            path: dest_path.clone(),
+                            error,
+                        };
+                        (res_ids.clone(), E::from(error))
+                    })?;
+
+                    let algorithm = checksum
+                        .as_ref()
+                        .map(Checksum::algorithm)
+                        .unwrap_or(ChecksumAlgorithm::Sha256);
+                    let checksum_downloaded = Checksum::file(algorithm, &dest_path, None)
+                        .await
+                        .map_err(|error| (res_ids.clone(), E::from(error)))?;
+                    if let Some(checksum) = checksum {
+                        if checksum != checksum_downloaded {
+                            let error = OpsError::ChecksumMismatch {
+                                path: dest_path,
+                                expected: checksum,
+                                actual: checksum_downloaded,
+                            };
+                            return Err((res_ids, E::from(error)));
+                        }
+                    }
+                    checksum_downloaded
+                        .record(&record_path)
+                        .await
+                        .map_err(|error| (res_ids.clone(), E::from(error)))?;
+
+                    let mut res_ids = res_ids;
+                    let _ = res_ids.insert(ResIdLogical::new(Self::RES_ID_PATH), dest_path);
+
+                    Result::<ResIds, (ResIds, E)>::Ok(res_ids)
+                })
+            },
+        )
+    }
+
+    fn clean_check_fn<E>(dest_path: PathBuf) -> StationFn<CheckStatus, E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new0(
+            move |station: &mut StationMutRef<'_, E>|
+                  -> LocalBoxFuture<'_, Result<CheckStatus, E>> {
+                let dest_path = station.dir.join(&dest_path);
+                Box::pin(async move {
+                    let check_status = if dest_path.exists() {
+                        CheckStatus::WorkRequired
+                    } else {
+                        CheckStatus::WorkNotRequired
+                    };
+                    Result::<CheckStatus, E>::Ok(check_status)
+                })
+            },
+        )
+    }
+
+    fn clean_work_fn<E>(dest_path: PathBuf) -> StationFn<(), E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new0(
+            move |station: &mut StationMutRef<'_, E>| -> LocalBoxFuture<'_, Result<(), E>> {
+                let dest_path = station.dir.join(&dest_path);
+                let record_path = station.dir.join(Self::RECORD_FILE_NAME);
+                Box::pin(async move {
+                    fs::remove_file(&dest_path)
+                        .await
+                        .map_err(|error| OpsError::FileRemove {
+                            path: dest_path,
+                            error,
+                        })?;
+                    match fs::remove_file(&record_path).await {
+                        Ok(()) => {}
+                        Err(error) if error.kind() == ErrorKind::NotFound => {}
+                        Err(error) => {
+                            return Err(E::from(OpsError::FileRemove {
+                                path: record_path,
+                                error,
+                            }));
+                        }
+                    }
+                    station.progress.progress_bar().inc(1);
+                    Result::<(), E>::Ok(())
+                })
+            },
+        )
+    }
+}
+
+impl<U> Clone for FileDownloadOp<U> {
+    fn clone(&self) -> Self {
+        Self {
+            dest_path: self.dest_path.clone(),
+            checksum: self.checksum.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<U> fmt::Debug for FileDownloadOp<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileDownloadOp")
+            .field("dest_path", &self.dest_path)
+            .field("checksum", &self.checksum)
+            .field("marker", &self.marker)
+            .finish()
+    }
+}
@@ -0,0 +1,348 @@
+use std::{
+    fmt,
+    io::ErrorKind,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+use choochoo_cfg_model::{
+    rt::{CheckStatus, ProgressLimit, ProgressStream, ResIdLogical, ResIds, StationMutRef},
+    CleanFns, CreateFns, SetupFn, StationFn, StationOp,
+};
+use futures::future::LocalBoxFuture;
+use reqwest::{header::CONTENT_LENGTH, Body, Response, StatusCode};
+use tokio::fs::{self, File};
+use tokio_util::io::ReaderStream;
+
+use crate::{Checksum, ChecksumAlgorithm, OpsError};
+
+// **Note:** `Clone` and `Debug` are manually implemented to avoid the trait
+// bound on `U`.
+/// Station operation that uploads a file over HTTP.
+///
+/// The URL to upload to is read from the `U` resource, which allows it to be
+/// inserted by an earlier station's setup function. The file is uploaded using
+/// the train's shared `reqwest::Client`, so the `http` feature of
+/// `choochoo_rt_logic` must be enabled.
+///
+/// * **Setup:** The progress limit is the file's length in bytes.
+/// * **Check:** The local file's contents match the checksum recorded by the
+///   last upload, and the server has the file at the URL with a
+///   `Content-Length` matching the local file's length. If the server does not
+///   report a length, the file existing on the server is sufficient.
+/// * **Work:** Sends the file in the body of a `PUT` request, advancing the
+///   station's progress bar by the bytes sent. The file's checksum is recorded
+///   in [`FileUploadOp::RECORD_FILE_NAME`] in the station's directory.
+/// * **Clean:** Sends a `DELETE` request to the URL, and deletes the recorded
+///   checksum.
+///
+/// The URL is recorded in the station's [`ResIds`] under
+/// [`FileUploadOp::RES_ID_URL`].
+pub struct FileUploadOp<U> {
+    /// Path of the file to upload.
+    ///
+    /// Relative paths are relative to the station's directory.
+    src_path: PathBuf,
+    /// Marker.
+    marker: PhantomData<U>,
+}
+
+impl<U> FileUploadOp<U>
+where
+    U: AsRef<str> + fmt::Debug + Send + Sync + 'static,
+{
+    /// Name of the file in the station's directory that records the checksum
+    /// of the last upload.
+    pub const RECORD_FILE_NAME: &'static str = ".choochoo_file_upload";
+    /// Logical ID of the uploaded file's URL in the station's [`ResIds`].
+    pub const RES_ID_URL: &'static str = "file_upload_url";
+
+    /// Returns a new `FileUploadOp`.
+    ///
+    /// # Parameters
+    ///
+    /// * `src_path`: Path of the file to upload. Relative paths are relative to
+    ///   the station's directory.
+    pub fn new<P>(src_path: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self {
+            src_path: src_path.into(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the path of the file to upload.
+    pub fn src_path(&self) -> &Path {
+        &self.src_path
+    }
+
+    /// Returns the [`StationOp`] that uploads the file.
+    pub fn build<E>(self) -> StationOp<E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        let create_fns = CreateFns::new(
+            Self::setup_fn(self.src_path.clone()),
+            Self::work_fn(self.src_path.clone()),
+        )
+        .with_check_fn(Self::check_fn(self.src_path));
+        let clean_fns = CleanFns::new(
+            SetupFn::new(|_station, _train_resources| {
+                Box::pin(async move { Result::<ProgressLimit, E>::Ok(ProgressLimit::Steps(1)) })
+            }),
+            Self::clean_work_fn(),
+        )
+        .with_check_fn(Self::clean_check_fn());
+
+        StationOp::new(create_fns, Some(clean_fns))
+    }
+
+    fn setup_fn<E>(src_path: PathBuf) -> SetupFn<E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        SetupFn::new(move |station, _train_resources| {
+            let src_path = station.dir.join(&src_path);
+            Box::pin(async move {
+                let metadata =
+                    fs::metadata(&src_path)
+                        .await
+                        .map_err(|error| OpsError::FileMetadata {
+                            path: src_path,
+                            error,
+                        })?;
+
+                Result::<ProgressLimit, E>::Ok(ProgressLimit::Bytes(metadata.len()))
+            })
+        })
+    }
+
+    fn check_fn<E>(src_path: PathBuf) -> StationFn<CheckStatus, E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new2(
+            move |station: &mut StationMutRef<'_, E>,
+                  client: &reqwest::Client,
+                  url: &U|
+                  -> LocalBoxFuture<'_, Result<CheckStatus, E>> {
+                let src_path = station.dir.join(&src_path);
+                let record_path = station.dir.join(Self::RECORD_FILE_NAME);
+                Box::pin(async move {
+                    // A file that was modified since it was uploaded, including one whose
+                    // length is unchanged, is uploaded again.
+                    let checksum_recorded = match Checksum::recorded(&record_path).await? {
+                        Some(checksum_recorded) => checksum_recorded,
+                        None => return Ok(CheckStatus::WorkRequired),
+                    };
+                    if Checksum::file_check(&src_path, &checksum_recorded, None).await?
+                        == CheckStatus::WorkRequired
+                    {
+                        return Ok(CheckStatus::WorkRequired);
+                    }
+
+                    let local_length = fs::metadata(&src_path)
+                        .await
+                        .map_err(|error| OpsError::FileMetadata {
+                            path: src_path,
+                            error,
+                        })?
+                        .len();
+
+                    let response = Self::head(client, url.as_ref()).await?;
+                    let check_status = match response {
+                        Some(response) => match Self::content_length(&response) {
+                            Some(remote_length) if remote_length != local_length => {
+                                CheckStatus::WorkRequired
+                            }
+                            Some(_) | None => CheckStatus::WorkNotRequired,
+                        },
+                        None => CheckStatus::WorkRequired,
+                    };
+
+                    Result::<CheckStatus, E>::Ok(check_status)
+                })
+            },
+        )
+    }
+
+    fn work_fn<E>(src_path: PathBuf) -> StationFn<ResIds, (ResIds, E), E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new2(
+            move |station: &mut StationMutRef<'_, E>,
+                  client: &reqwest::Client,
+                  url: &U|
+                  -> LocalBoxFuture<'_, Result<ResIds, (ResIds, E)>> {
+                let src_path = station.dir.join(&src_path);
+                let record_path = station.dir.join(Self::RECORD_FILE_NAME);
+                Box::pin(async move {
+                    let res_ids = ResIds::new();
+                    let url = url.as_ref();
+                    // Hashed before uploading, so the recorded checksum is of the bytes that
+                    // were sent.
+                    let checksum = Checksum::file(ChecksumAlgorithm::Sha256, &src_path, None)
+                        .await
+                        .map_err(|error| (res_ids.clone(), E::from(error)))?;
+                    let file = File::open(&src_path).await.map_err(|error| {
+                        let error = OpsError::FileOpen {
+                            path: src_path.clone(),
+                            error,
+                        };
+                        (res_ids.clone(), E::from(error))
+                    })?;
+                    let byte_stream =
+                        ProgressStream::new(&station.progress, ReaderStream::new(file));
+
+                    let response = client
+                        .put(url)
+                        .body(Body::wrap_stream(byte_stream))
+                        .send()
+                        .await
+                        .map_err(|error| OpsError::HttpRequest {
+                            url: url.to_string(),
+                            error,
+                        })
+                        .map_err(|error| (res_ids.clone(), E::from(error)))?;
+                    if !response.status().is_success() {
+                        let error = OpsError::HttpResponseStatus {
+                            url: url.to_string(),
+                            status: response.status(),
+                        };
+                        return Err((res_ids, E::from(error)));
+                    }
+                    checksum
+                        .record(&record_path)
+                        .await
+                        .map_err(|error| (res_ids.clone(), E::from(error)))?;
+
+                    let mut res_ids = res_ids;
+                    let _ = res_ids.insert(ResIdLogical::new(Self::RES_ID_URL), url.to_string());
+
+                    Result::<ResIds, (ResIds, E)>::Ok(res_ids)
+                })
+            },
+        )
+    }
+
+    fn clean_check_fn<E>() -> StationFn<CheckStatus, E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new2(
+            |_station: &mut StationMutRef<'_, E>,
+             client: &reqwest::Client,
+             url: &U|
+             -> LocalBoxFuture<'_, Result<CheckStatus, E>> {
+                Box::pin(async move {
+                    let check_status = match Self::head(client, url.as_ref()).await? {
+                        Some(_) => CheckStatus::WorkRequired,
+                        None => CheckStatus::WorkNotRequired,
+                    };
+                    Result::<CheckStatus, E>::Ok(check_status)
+                })
+            },
+        )
+    }
+
+    fn clean_work_fn<E>() -> StationFn<(), E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new2(
+            |station: &mut StationMutRef<'_, E>,
+             client: &reqwest::Client,
+             url: &U|
+             -> LocalBoxFuture<'_, Result<(), E>> {
+                let record_path = station.dir.join(Self::RECORD_FILE_NAME);
+                Box::pin(async move {
+                    let url = url.as_ref();
+                    let response =
+                        client
+                            .delete(url)
+                            .send()
+                            .await
+                            .map_err(|error| OpsError::HttpRequest {
+                                url: url.to_string(),
+                                error,
+                            })?;
+                    let status = response.status();
+                    if !status.is_success() && status != StatusCode::NOT_FOUND {
+                        return Err(E::from(OpsError::HttpResponseStatus {
+                            url: url.to_string(),
+                            status,
+                        }));
+                    }
+                    match fs::remove_file(&record_path).await {
+                        Ok(()) => {}
+                        Err(error) if error.kind() == ErrorKind::NotFound => {}
+                        Err(error) => {
+                            return Err(E::from(OpsError::FileRemove {
+                                path: record_path,
+                                error,
+                            }));
+                        }
+                    }
+                    station.progress.progress_bar().inc(1);
+
+                    Result::<(), E>::Ok(())
+                })
+            },
+        )
+    }
+
+    /// Returns the response to a `HEAD` request, or `None` if the server does
+    /// not have the file.
+    async fn head(client: &reqwest::Client, url: &str) -> Result<Option<Response>, OpsError> {
+        let response = client
+            .head(url)
+            .send()
+            .await
+            .map_err(|error| OpsError::HttpRequest {
+                url: url.to_string(),
+                error,
+            })?;
+
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(None),
+            status if status.is_success() => Ok(Some(response)),
+            status => Err(OpsError::HttpResponseStatus {
+                url: url.to_string(),
+                status,
+            }),
+        }
+    }
+
+    /// Returns the `Content-Length` header of a response.
+    ///
+    /// `Response::content_length` is the length of the `HEAD` response's body,
+    /// so we read the header instead.
+    fn content_length(response: &Response) -> Option<u64> {
+        response
+            .headers()
+            .get(CONTENT_LENGTH)
+            .and_then(|content_length| content_length.to_str().ok())
+            .and_then(|content_length| content_length.parse::<u64>().ok())
+    }
+}
+
+impl<U> Clone for FileUploadOp<U> {
+    fn clone(&self) -> Self {
+        Self {
+            src_path: self.src_path.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<U> fmt::Debug for FileUploadOp<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileUploadOp")
+            .field("src_path", &self.src_path)
+            .field("marker", &self.marker)
+            .finish()
+    }
+}
@@ -0,0 +1,66 @@
+//! Reusable station operations for the choochoo automation library.
+//!
+//! Each operation builds a [`StationOp`] with setup, check, work, and clean
+//! functions for a common step, so that stations can be composed from them
+//! instead of implementing the functions for each station.
+//!
+//! [`StationOp`]: choochoo_cfg_model::StationOp
+
+pub use crate::ops_error::OpsError;
+
+#[cfg(feature = "archive")]
+pub use crate::{
+    archive_format::ArchiveFormat, archive_pack_op::ArchivePackOp,
+    archive_unpack_op::ArchiveUnpackOp,
+};
+
+#[cfg(feature = "checksum")]
+pub use crate::{checksum::Checksum, checksum_algorithm::ChecksumAlgorithm};
+
+#[cfg(feature = "http")]
+pub use crate::{file_download_op::FileDownloadOp, file_upload_op::FileUploadOp};
+
+#[cfg(feature = "migration")]
+pub use crate::{
+    migration::{Migration, MigrationFn},
+    migration_op::MigrationOp,
+};
+
+#[cfg(feature = "template")]
+pub use crate::{template_params::TemplateParams, template_render_op::TemplateRenderOp};
+
+#[cfg(feature = "container")]
+pub mod container;
+#[cfg(feature = "ssh")]
+pub mod ssh;
+
+mod ops_error;
+
+#[cfg(feature = "archive")]
+mod archive_format;
+#[cfg(feature = "archive")]
+mod archive_pack_op;
+#[cfg(feature = "archive")]
+mod archive_support;
+#[cfg(feature = "archive")]
+mod archive_unpack_op;
+
+#[cfg(feature = "checksum")]
+mod checksum;
+#[cfg(feature = "checksum")]
+mod checksum_algorithm;
+
+#[cfg(feature = "http")]
+mod file_download_op;
+#[cfg(feature = "http")]
+mod file_upload_op;
+
+#[cfg(feature = "migration")]
+mod migration;
+#[cfg(feature = "migration")]
+mod migration_op;
+
+#[cfg(feature = "template")]
+mod template_params;
+#[cfg(feature = "template")]
+mod template_render_op;
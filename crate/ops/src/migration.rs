@@ -0,0 +1,108 @@
+use std::{fmt, sync::Arc};
+
+use futures::future::LocalBoxFuture;
+
+/// Logic that applies or reverts a migration, using the `R` resource.
+#[allow(clippy::type_complexity)] // trait aliases don't exist yet, so we have to suppress clippy.
+pub type MigrationFn<R, E> = Arc<dyn for<'f> Fn(&'f R) -> LocalBoxFuture<'f, Result<(), E>>>;
+
+// **Note:** `Clone` and `Debug` are manually implemented to avoid the trait
+// bounds on `R` and `E`.
+/// A versioned step of a [`MigrationOp`].
+///
+/// The up function applies the migration, and the optional down function
+/// reverts it. Both are passed the `R` resource, such as a database connection
+/// pool inserted by an earlier station.
+///
+/// [`MigrationOp`]: crate::MigrationOp
+pub struct Migration<R, E> {
+    /// Version of the migration, which determines the order of application.
+    version: u64,
+    /// Human readable name of the migration.
+    name: String,
+    /// Logic to apply the migration.
+    up_fn: MigrationFn<R, E>,
+    /// Logic to revert the migration.
+    down_fn: Option<MigrationFn<R, E>>,
+}
+
+impl<R, E> Migration<R, E> {
+    /// Returns a new `Migration`.
+    ///
+    /// # Parameters
+    ///
+    /// * `version`: Version of the migration, which determines the order of
+    ///   application.
+    /// * `name`: Human readable name of the migration.
+    /// * `up_fn`: Logic to apply the migration.
+    pub fn new<S, F>(version: u64, name: S, up_fn: F) -> Self
+    where
+        S: Into<String>,
+        F: for<'f> Fn(&'f R) -> LocalBoxFuture<'f, Result<(), E>> + 'static,
+    {
+        Self {
+            version,
+            name: name.into(),
+            up_fn: Arc::new(up_fn),
+            down_fn: None,
+        }
+    }
+
+    /// Sets the logic to revert the migration.
+    #[must_use]
+    pub fn with_down_fn<F>(mut self, down_fn: F) -> Self
+    where
+        F: for<'f> Fn(&'f R) -> LocalBoxFuture<'f, Result<(), E>> + 'static,
+    {
+        self.down_fn = Some(Arc::new(down_fn));
+        self
+    }
+
+    /// Returns the version of the migration.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Returns the human readable name of the migration.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the logic to apply the migration.
+    pub fn up_fn(&self) -> &MigrationFn<R, E> {
+        &self.up_fn
+    }
+
+    /// Returns the logic to revert the migration.
+    pub fn down_fn(&self) -> Option<&MigrationFn<R, E>> {
+        self.down_fn.as_ref()
+    }
+}
+
+impl<R, E> Clone for Migration<R, E> {
+    fn clone(&self) -> Self {
+        Self {
+            version: self.version,
+            name: self.name.clone(),
+            up_fn: Arc::clone(&self.up_fn),
+            down_fn: self.down_fn.clone(),
+        }
+    }
+}
+
+impl<R, E> fmt::Debug for Migration<R, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Migration")
+            .field("version", &self.version)
+            .field("name", &self.name)
+            .field("up_fn", &"fn(&'_ R) -> LocalBoxFuture<'_, Result<(), E>>")
+            .field(
+                "down_fn",
+                &self
+                    .down_fn
+                    .as_ref()
+                    .map(|_| "fn(&'_ R) -> LocalBoxFuture<'_, Result<(), E>>"),
+            )
+            .finish()
+    }
+}
@@ -0,0 +1,314 @@
+use std::{collections::BTreeSet, fmt, io::ErrorKind, path::Path};
+
+use choochoo_cfg_model::{
+    rt::{CheckStatus, ProgressLimit, ResIdLogical, ResIds, StationMutRef},
+    CleanFns, CreateFns, SetupFn, StationFn, StationOp,
+};
+use futures::future::LocalBoxFuture;
+use tokio::fs;
+
+use crate::{Migration, OpsError};
+
+// **Note:** `Clone` and `Debug` are manually implemented to avoid the trait
+// bounds on `R` and `E`.
+/// Station operation that applies versioned [`Migration`]s, such as database
+/// schema changes.
+///
+/// The versions of applied migrations are recorded in
+/// [`MigrationOp::APPLIED_FILE_NAME`] in the station's directory, which is
+/// within the profile directory.
+///
+/// * **Check:** Every migration's version is recorded as applied.
+/// * **Work:** Applies each unapplied migration in version order, recording its
+///   version after it succeeds.
+/// * **Clean:** Reverts each applied migration in reverse version order. This
+///   is only available when every migration has a down function.
+///
+/// The highest applied version is recorded in the station's [`ResIds`] under
+/// [`MigrationOp::RES_ID_VERSION`].
+pub struct MigrationOp<R, E> {
+    /// Migrations to apply, sorted by version.
+    migrations: Vec<Migration<R, E>>,
+}
+
+impl<R, E> MigrationOp<R, E>
+where
+    R: fmt::Debug + Send + Sync + 'static,
+    E: From<OpsError> + 'static,
+{
+    /// Name of the file in the station's directory that records the applied
+    /// versions.
+    pub const APPLIED_FILE_NAME: &'static str = "migrations_applied";
+    /// Logical ID of the highest applied version in the station's [`ResIds`].
+    pub const RES_ID_VERSION: &'static str = "migration_version";
+
+    /// Returns a new `MigrationOp` with no migrations.
+    pub fn new() -> Self {
+        Self {
+            migrations: Vec::new(),
+        }
+    }
+
+    /// Adds a migration.
+    ///
+    /// Migrations are applied in version order, regardless of the order they
+    /// are added.
+    #[must_use]
+    pub fn with_migration(mut self, migration: Migration<R, E>) -> Self {
+        let index = self
+            .migrations
+            .partition_point(|existing| existing.version() <= migration.version());
+        self.migrations.insert(index, migration);
+        self
+    }
+
+    /// Returns the migrations to apply, sorted by version.
+    pub fn migrations(&self) -> &[Migration<R, E>] {
+        &self.migrations
+    }
+
+    /// Returns the [`StationOp`] that applies the migrations.
+    pub fn build(self) -> StationOp<E> {
+        let versions = self
+            .migrations
+            .iter()
+            .map(Migration::version)
+            .collect::<BTreeSet<u64>>();
+
+        let create_fns = CreateFns::new(
+            Self::setup_fn(versions.clone(), false),
+            Self::work_fn(self.migrations.clone()),
+        )
+        .with_check_fn(Self::check_fn(versions.clone()));
+        let clean_fns = if self
+            .migrations
+            .iter()
+            .all(|migration| migration.down_fn().is_some())
+        {
+            let clean_fns = CleanFns::new(
+                Self::setup_fn(versions.clone(), true),
+                Self::clean_work_fn(self.migrations),
+            )
+            .with_check_fn(Self::clean_check_fn(versions));
+            Some(clean_fns)
+        } else {
+            None
+        };
+
+        StationOp::new(create_fns, clean_fns)
+    }
+
+    /// Returns a setup function whose progress limit is the number of
+    /// migrations to apply, or to revert when `clean` is `true`.
+    fn setup_fn(versions: BTreeSet<u64>, clean: bool) -> SetupFn<E> {
+        SetupFn::new(move |station, _train_resources| {
+            let versions = versions.clone();
+            let applied_path = station.dir.join(Self::APPLIED_FILE_NAME);
+            Box::pin(async move {
+                let applied = applied_versions_read(&applied_path).await?;
+                let migration_count = if clean {
+                    versions.intersection(&applied).count()
+                } else {
+                    versions.difference(&applied).count()
+                };
+                Result::<ProgressLimit, E>::Ok(ProgressLimit::Steps(migration_count as u64))
+            })
+        })
+    }
+
+    fn check_fn(versions: BTreeSet<u64>) -> StationFn<CheckStatus, E, E> {
+        StationFn::new0(
+            move |station: &mut StationMutRef<'_, E>|
+                  -> LocalBoxFuture<'_, Result<CheckStatus, E>> {
+                let versions = versions.clone();
+                let applied_path = station.dir.join(Self::APPLIED_FILE_NAME);
+                Box::pin(async move {
+                    let applied = applied_versions_read(&applied_path).await?;
+                    let check_status = if versions.is_subset(&applied) {
+                        CheckStatus::WorkNotRequired
+                    } else {
+                        CheckStatus::WorkRequired
+                    };
+                    Result::<CheckStatus, E>::Ok(check_status)
+                })
+            },
+        )
+    }
+
+    fn work_fn(migrations: Vec<Migration<R, E>>) -> StationFn<ResIds, (ResIds, E), E> {
+        StationFn::new1(
+            move |station: &mut StationMutRef<'_, E>,
+                  resource: &R|
+                  -> LocalBoxFuture<'_, Result<ResIds, (ResIds, E)>> {
+                let migrations = migrations.clone();
+                let applied_path = station.dir.join(Self::APPLIED_FILE_NAME);
+                Box::pin(async move {
+                    let mut res_ids = ResIds::new();
+                    let mut applied = applied_versions_read(&applied_path)
+                        .await
+                        .map_err(|error| (res_ids.clone(), E::from(error)))?;
+                    if let Some(version) = applied.iter().next_back() {
+                        let _ = res_ids.insert(ResIdLogical::new(Self::RES_ID_VERSION), *version);
+                    }
+
+                    let migrations_unapplied = migrations
+                        .iter()
+                        .filter(|migration| !applied.contains(&migration.version()))
+                        .collect::<Vec<_>>();
+                    for migration in migrations_unapplied {
+                        (migration.up_fn())(resource)
+                            .await
+                            .map_err(|error| (res_ids.clone(), error))?;
+
+                        applied.insert(migration.version());
+                        applied_versions_write(&applied_path, &applied)
+                            .await
+                            .map_err(|error| (res_ids.clone(), E::from(error)))?;
+                        let _ = res_ids
+                            .insert(ResIdLogical::new(Self::RES_ID_VERSION), migration.version());
+                        station.progress.progress_bar().inc(1);
+                    }
+
+                    Result::<ResIds, (ResIds, E)>::Ok(res_ids)
+                })
+            },
+        )
+    }
+
+    fn clean_check_fn(versions: BTreeSet<u64>) -> StationFn<CheckStatus, E, E> {
+        StationFn::new0(
+            move |station: &mut StationMutRef<'_, E>|
+                  -> LocalBoxFuture<'_, Result<CheckStatus, E>> {
+                let versions = versions.clone();
+                let applied_path = station.dir.join(Self::APPLIED_FILE_NAME);
+                Box::pin(async move {
+                    let applied = applied_versions_read(&applied_path).await?;
+                    let check_status = if versions.is_disjoint(&applied) {
+                        CheckStatus::WorkNotRequired
+                    } else {
+                        CheckStatus::WorkRequired
+                    };
+                    Result::<CheckStatus, E>::Ok(check_status)
+                })
+            },
+        )
+    }
+
+    fn clean_work_fn(migrations: Vec<Migration<R, E>>) -> StationFn<(), E, E> {
+        StationFn::new1(
+            move |station: &mut StationMutRef<'_, E>,
+                  resource: &R|
+                  -> LocalBoxFuture<'_, Result<(), E>> {
+                let migrations = migrations.clone();
+                let applied_path = station.dir.join(Self::APPLIED_FILE_NAME);
+                Box::pin(async move {
+                    let mut applied = applied_versions_read(&applied_path).await?;
+
+                    let migrations_applied = migrations
+                        .iter()
+                        .rev()
+                        .filter(|migration| applied.contains(&migration.version()))
+                        .collect::<Vec<_>>();
+                    for migration in migrations_applied {
+                        // `build` only creates clean functions when every migration has a down
+                        // function.
+                        if let Some(down_fn) = migration.down_fn() {
+                            down_fn(resource).await?;
+                        }
+
+                        applied.remove(&migration.version());
+                        applied_versions_write(&applied_path, &applied).await?;
+                        station.progress.progress_bar().inc(1);
+                    }
+
+                    Result::<(), E>::Ok(())
+                })
+            },
+        )
+    }
+}
+
+impl<R, E> Default for MigrationOp<R, E>
+where
+    R: fmt::Debug + Send + Sync + 'static,
+    E: From<OpsError> + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R, E> Clone for MigrationOp<R, E> {
+    fn clone(&self) -> Self {
+        Self {
+            migrations: self.migrations.clone(),
+        }
+    }
+}
+
+impl<R, E> fmt::Debug for MigrationOp<R, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MigrationOp")
+            .field("migrations", &self.migrations)
+            .finish()
+    }
+}
+
+/// Returns the versions recorded as applied, which is empty if the file does
+/// not exist.
+async fn applied_versions_read(applied_path: &Path) -> Result<BTreeSet<u64>, OpsError> {
+    let applied = match fs::read_to_string(applied_path).await {
+        Ok(applied) => applied,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(BTreeSet::new()),
+        Err(error) => {
+            return Err(OpsError::FileRead {
+                path: applied_path.to_path_buf(),
+                error,
+            });
+        }
+    };
+
+    applied
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            line.parse::<u64>()
+                .map_err(|_| OpsError::MigrationAppliedParse {
+                    path: applied_path.to_path_buf(),
+                    value: line.to_string(),
+                })
+        })
+        .collect()
+}
+
+/// Records the applied versions, one per line.
+///
+/// The file is removed when no versions are applied.
+async fn applied_versions_write(
+    applied_path: &Path,
+    applied: &BTreeSet<u64>,
+) -> Result<(), OpsError> {
+    if applied.is_empty() {
+        return match fs::remove_file(applied_path).await {
+            Ok(()) => Ok(()),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(()),
+            Err(error) => Err(OpsError::FileRemove {
+                path: applied_path.to_path_buf(),
+                error,
+            }),
+        };
+    }
+
+    let contents = applied.iter().fold(String::new(), |mut contents, version| {
+        contents.push_str(&version.to_string());
+        contents.push('\n');
+        contents
+    });
+    fs::write(applied_path, contents)
+        .await
+        .map_err(|error| OpsError::FileWrite {
+            path: applied_path.to_path_buf(),
+            error,
+        })
+}
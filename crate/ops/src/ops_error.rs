@@ -0,0 +1,314 @@
+use std::{fmt, path::PathBuf};
+
+/// Errors when running a built-in station operation.
+///
+/// Station error types used with the built-in operations implement
+/// `From<OpsError>`.
+#[derive(Debug)]
+pub enum OpsError {
+    /// Failed to read an archive.
+    ArchiveRead {
+        /// Path of the archive.
+        path: PathBuf,
+        /// Underlying IO error.
+        error: std::io::Error,
+    },
+    /// Failed to write an archive.
+    ArchiveWrite {
+        /// Path of the archive.
+        path: PathBuf,
+        /// Underlying IO error.
+        error: std::io::Error,
+    },
+    /// File's checksum differs from the checksum it is expected to have.
+    #[cfg(feature = "checksum")]
+    ChecksumMismatch {
+        /// Path of the file.
+        path: PathBuf,
+        /// Checksum the file is expected to have.
+        expected: crate::Checksum,
+        /// Checksum of the file.
+        actual: crate::Checksum,
+    },
+    /// Checksum string is not in the `${algorithm}:${hex}` form.
+    #[cfg(feature = "checksum")]
+    ChecksumParse {
+        /// The string that failed to be parsed.
+        value: String,
+    },
+    /// Container command exited unsuccessfully.
+    #[cfg(feature = "container")]
+    ContainerCommandFailed {
+        /// The command that was run.
+        command: String,
+        /// Exit status of the command.
+        status: std::process::ExitStatus,
+        /// Trailing lines of the command's standard error.
+        stderr: String,
+    },
+    /// Failed to run a container command.
+    #[cfg(feature = "container")]
+    ContainerCommandSpawn {
+        /// The command that was run.
+        command: String,
+        /// Underlying IO error.
+        error: std::io::Error,
+    },
+    /// Failed to create a directory.
+    DirCreate {
+        /// Path of the directory.
+        path: PathBuf,
+        /// Underlying IO error.
+        error: std::io::Error,
+    },
+    /// Failed to read a directory's entries.
+    DirRead {
+        /// Path of the directory.
+        path: PathBuf,
+        /// Underlying IO error.
+        error: std::io::Error,
+    },
+    /// Failed to create a file.
+    FileCreate {
+        /// Path of the file.
+        path: PathBuf,
+        /// Underlying IO error.
+        error: std::io::Error,
+    },
+    /// Failed to read a file's metadata.
+    FileMetadata {
+        /// Path of the file.
+        path: PathBuf,
+        /// Underlying IO error.
+        error: std::io::Error,
+    },
+    /// Failed to open a file.
+    FileOpen {
+        /// Path of the file.
+        path: PathBuf,
+        /// Underlying IO error.
+        error: std::io::Error,
+    },
+    /// Failed to read a file.
+    FileRead {
+        /// Path of the file.
+        path: PathBuf,
+        /// Underlying IO error.
+        error: std::io::Error,
+    },
+    /// Failed to remove a file.
+    FileRemove {
+        /// Path of the file.
+        path: PathBuf,
+        /// Underlying IO error.
+        error: std::io::Error,
+    },
+    /// Failed to write to a file.
+    FileWrite {
+        /// Path of the file.
+        path: PathBuf,
+        /// Underlying IO error.
+        error: std::io::Error,
+    },
+    /// Failed to send an HTTP request, or to receive its response.
+    #[cfg(feature = "http")]
+    HttpRequest {
+        /// URL of the request.
+        url: String,
+        /// Underlying HTTP error.
+        error: reqwest::Error,
+    },
+    /// HTTP response had an unsuccessful status code.
+    #[cfg(feature = "http")]
+    HttpResponseStatus {
+        /// URL of the request.
+        url: String,
+        /// Status code of the response.
+        status: reqwest::StatusCode,
+    },
+    /// Applied migration versions file contains a value that is not a version.
+    #[cfg(feature = "migration")]
+    MigrationAppliedParse {
+        /// Path of the applied versions file.
+        path: PathBuf,
+        /// The value that failed to be parsed.
+        value: String,
+    },
+    /// SSH command exited unsuccessfully.
+    #[cfg(feature = "ssh")]
+    SshCommandFailed {
+        /// The command that was run.
+        command: String,
+        /// Exit status of the command.
+        status: std::process::ExitStatus,
+        /// Trailing lines of the command's standard error.
+        stderr: String,
+    },
+    /// Failed to run an SSH command.
+    #[cfg(feature = "ssh")]
+    SshCommandSpawn {
+        /// The command that was run.
+        command: String,
+        /// Underlying IO error.
+        error: std::io::Error,
+    },
+    /// Failed to join the task that runs blocking operations.
+    TaskJoin(tokio::task::JoinError),
+    /// Template placeholder is missing its closing `}}`.
+    #[cfg(feature = "template")]
+    TemplatePlaceholderUnclosed {
+        /// Path of the template.
+        template_path: PathBuf,
+        /// Byte offset of the placeholder's opening `{{`.
+        offset: usize,
+    },
+    /// Template references a parameter that has no value.
+    #[cfg(feature = "template")]
+    TemplateParamNotFound {
+        /// Path of the template.
+        template_path: PathBuf,
+        /// Name of the parameter.
+        name: String,
+    },
+}
+
+impl fmt::Display for OpsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ArchiveRead { path, .. } => {
+                write!(f, "Failed to read archive `{}`.", path.display())
+            }
+            Self::ArchiveWrite { path, .. } => {
+                write!(f, "Failed to write archive `{}`.", path.display())
+            }
+            #[cfg(feature = "checksum")]
+            Self::ChecksumMismatch {
+                path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "File `{}` has checksum `{actual}`, but `{expected}` was expected.",
+                path.display()
+            ),
+            #[cfg(feature = "checksum")]
+            Self::ChecksumParse { value } => write!(
+                f,
+                "Failed to parse checksum `{value}`. Expected `sha256:<hex>` or `blake3:<hex>`."
+            ),
+            #[cfg(feature = "container")]
+            Self::ContainerCommandFailed {
+                command,
+                status,
+                stderr,
+            } => write!(f, "`{command}` failed with {status}:\n{stderr}"),
+            #[cfg(feature = "container")]
+            Self::ContainerCommandSpawn { command, .. } => write!(f, "Failed to run `{command}`."),
+            Self::DirCreate { path, .. } => {
+                write!(f, "Failed to create directory `{}`.", path.display())
+            }
+            Self::DirRead { path, .. } => {
+                write!(f, "Failed to read directory `{}`.", path.display())
+            }
+            Self::FileCreate { path, .. } => {
+                write!(f, "Failed to create file `{}`.", path.display())
+            }
+            Self::FileMetadata { path, .. } => {
+                write!(f, "Failed to read metadata of file `{}`.", path.display())
+            }
+            Self::FileOpen { path, .. } => write!(f, "Failed to open file `{}`.", path.display()),
+            Self::FileRead { path, .. } => write!(f, "Failed to read file `{}`.", path.display()),
+            Self::FileRemove { path, .. } => {
+                write!(f, "Failed to remove file `{}`.", path.display())
+            }
+            Self::FileWrite { path, .. } => {
+                write!(f, "Failed to write to file `{}`.", path.display())
+            }
+            #[cfg(feature = "http")]
+            Self::HttpRequest { url, .. } => write!(f, "Failed to send request to `{url}`."),
+            #[cfg(feature = "http")]
+            Self::HttpResponseStatus { url, status } => {
+                write!(f, "Request to `{url}` failed with status `{status}`.")
+            }
+            #[cfg(feature = "migration")]
+            Self::MigrationAppliedParse { path, value } => write!(
+                f,
+                "Failed to parse `{value}` as a migration version in `{}`.",
+                path.display()
+            ),
+            #[cfg(feature = "ssh")]
+            Self::SshCommandFailed {
+                command,
+                status,
+                stderr,
+            } => write!(f, "`{command}` failed with {status}:\n{stderr}"),
+            #[cfg(feature = "ssh")]
+            Self::SshCommandSpawn { command, .. } => write!(f, "Failed to run `{command}`."),
+            Self::TaskJoin(_) => {
+                write!(f, "Failed to join the task that runs blocking operations.")
+            }
+            #[cfg(feature = "template")]
+            Self::TemplatePlaceholderUnclosed {
+                template_path,
+                offset,
+            } => write!(
+                f,
+                "Template `{}` has an unclosed placeholder at byte {offset}.",
+                template_path.display()
+            ),
+            #[cfg(feature = "template")]
+            Self::TemplateParamNotFound {
+                template_path,
+                name,
+            } => write!(
+                f,
+                "Template `{}` references parameter `{name}`, which has no value.",
+                template_path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for OpsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::ArchiveRead { error, .. } => Some(error),
+            Self::ArchiveWrite { error, .. } => Some(error),
+            #[cfg(feature = "checksum")]
+            Self::ChecksumMismatch { .. } => None,
+            #[cfg(feature = "checksum")]
+            Self::ChecksumParse { .. } => None,
+            #[cfg(feature = "container")]
+            Self::ContainerCommandFailed { .. } => None,
+            #[cfg(feature = "container")]
+            Self::ContainerCommandSpawn { error, .. } => Some(error),
+            Self::DirCreate { error, .. } => Some(error),
+            Self::DirRead { error, .. } => Some(error),
+            Self::FileCreate { error, .. } => Some(error),
+            Self::FileMetadata { error, .. } => Some(error),
+            Self::FileOpen { error, .. } => Some(error),
+            Self::FileRead { error, .. } => Some(error),
+            Self::FileRemove { error, .. } => Some(error),
+            Self::FileWrite { error, .. } => Some(error),
+            #[cfg(feature = "http")]
+            Self::HttpRequest { error, .. } => Some(error),
+            #[cfg(feature = "http")]
+            Self::HttpResponseStatus { .. } => None,
+            #[cfg(feature = "migration")]
+            Self::MigrationAppliedParse { .. } => None,
+            #[cfg(feature = "ssh")]
+            Self::SshCommandFailed { .. } => None,
+            #[cfg(feature = "ssh")]
+            Self::SshCommandSpawn { error, .. } => Some(error),
+            Self::TaskJoin(error) => Some(error),
+            #[cfg(feature = "template")]
+            Self::TemplatePlaceholderUnclosed { .. } => None,
+            #[cfg(feature = "template")]
+            Self::TemplateParamNotFound { .. } => None,
+        }
+    }
+}
+
+impl From<OpsError> for () {
+    fn from(_: OpsError) {}
+}
@@ -0,0 +1,13 @@
+//! Station operations that run commands and upload files over SSH.
+//!
+//! These operations run the `ssh` and `scp` CLIs, which must be installed. The
+//! remote host is read from a typed resource that borrows as an [`SshTarget`].
+
+pub use self::{
+    ssh_command_op::SshCommandOp, ssh_file_upload_op::SshFileUploadOp, ssh_target::SshTarget,
+};
+
+mod ssh_command;
+mod ssh_command_op;
+mod ssh_file_upload_op;
+mod ssh_target;
@@ -0,0 +1,209 @@
+use std::{
+    collections::VecDeque,
+    path::Path,
+    process::{ExitStatus, Output, Stdio},
+};
+
+use choochoo_cfg_model::rt::StationProgress;
+use tokio::{
+    fs::File,
+    io::{AsyncBufReadExt, AsyncRead, AsyncWriteExt, BufReader, BufWriter},
+    process::Command,
+};
+
+use crate::{ssh::SshTarget, OpsError};
+
+/// Exit code that `ssh` returns when it fails, rather than the remote command.
+const SSH_ERROR_EXIT_CODE: i32 = 255;
+/// Number of lines of standard error to keep for error messages.
+const STDERR_TAIL_LINES: usize = 20;
+
+/// Returns an `ssh` command that runs `remote_command` on the target.
+pub(crate) fn ssh(ssh_target: &SshTarget, remote_command: &str) -> Command {
+    let mut command = Command::new("ssh");
+    command.args(ssh_target.ssh_args(remote_command));
+    command
+}
+
+/// Returns an `scp` command that copies a local file to the target.
+pub(crate) fn scp(ssh_target: &SshTarget, src_path: &Path, dest_path: &str) -> Command {
+    let mut command = Command::new("scp");
+    command.args(ssh_target.scp_args(src_path, dest_path));
+    command
+}
+
+/// Runs the command, and returns its exit status.
+///
+/// Returns an error if `ssh` itself fails, such as when the host cannot be
+/// reached, so that it is not mistaken for the remote command's status.
+pub(crate) async fn status(command: Command) -> Result<ExitStatus, OpsError> {
+    output(command).await.map(|output| output.status)
+}
+
+/// Runs the command, and returns its trimmed standard output.
+///
+/// Returns an error if the command exits unsuccessfully.
+pub(crate) async fn stdout(command: Command) -> Result<String, OpsError> {
+    let command_display = command_display(&command);
+    let output = output(command).await?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(OpsError::SshCommandFailed {
+            command: command_display,
+            status: output.status,
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        })
+    }
+}
+
+/// Runs the command, and returns its trimmed standard output if it exits
+/// successfully.
+///
+/// Returns an error if `ssh` itself fails.
+pub(crate) async fn stdout_if_success(command: Command) -> Result<Option<String>, OpsError> {
+    let output = output(command).await?;
+    if output.status.success() {
+        Ok(Some(
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        ))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Runs the command, writing its output to log files and advancing the
+/// station's progress bar for each line of output.
+///
+/// Returns an error if the command exits unsuccessfully.
+pub(crate) async fn run_logged(
+    station_progress: &StationProgress,
+    mut command: Command,
+    stdout_path: &Path,
+    stderr_path: &Path,
+) -> Result<(), OpsError> {
+    let command_display = command_display(&command);
+    let spawn_error = |error| OpsError::SshCommandSpawn {
+        command: command_display.clone(),
+        error,
+    };
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(spawn_error)?;
+
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    let (stdout_result, stderr_result, status_result) = futures::join!(
+        lines_log(station_progress, &command_display, stdout, stdout_path),
+        lines_log(station_progress, &command_display, stderr, stderr_path),
+        child.wait(),
+    );
+    stdout_result?;
+    let stderr = stderr_result?;
+    let status = status_result.map_err(spawn_error)?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(OpsError::SshCommandFailed {
+            command: command_display,
+            status,
+            stderr,
+        })
+    }
+}
+
+/// Runs the command and returns its output.
+///
+/// Returns an error if `ssh` itself fails.
+async fn output(mut command: Command) -> Result<Output, OpsError> {
+    let output = command
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .map_err(|error| OpsError::SshCommandSpawn {
+            command: command_display(&command),
+            error,
+        })?;
+    if output.status.code() == Some(SSH_ERROR_EXIT_CODE) {
+        Err(OpsError::SshCommandFailed {
+            command: command_display(&command),
+            status: output.status,
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        })
+    } else {
+        Ok(output)
+    }
+}
+
+/// Writes lines from the stream to the log file, advancing the progress bar
+/// for each line, and returns the last lines read.
+async fn lines_log<R>(
+    station_progress: &StationProgress,
+    command_display: &str,
+    stream: Option<R>,
+    log_path: &Path,
+) -> Result<String, OpsError>
+where
+    R: AsyncRead + Unpin,
+{
+    let log_write_error = |error| OpsError::FileWrite {
+        path: log_path.to_path_buf(),
+        error,
+    };
+    let log_file = File::create(log_path)
+        .await
+        .map_err(|error| OpsError::FileCreate {
+            path: log_path.to_path_buf(),
+            error,
+        })?;
+    let mut log_writer = BufWriter::new(log_file);
+
+    let mut lines_tail = VecDeque::with_capacity(STDERR_TAIL_LINES);
+    if let Some(stream) = stream {
+        let mut lines = BufReader::new(stream).lines();
+        while let Some(line) =
+            lines
+                .next_line()
+                .await
+                .map_err(|error| OpsError::SshCommandSpawn {
+                    command: command_display.to_string(),
+                    error,
+                })?
+        {
+            station_progress.progress_bar().inc(1);
+            log_writer
+                .write_all(line.as_bytes())
+                .await
+                .map_err(log_write_error)?;
+            log_writer.write_all(b"\n").await.map_err(log_write_error)?;
+
+            if lines_tail.len() == STDERR_TAIL_LINES {
+                lines_tail.pop_front();
+            }
+            lines_tail.push_back(line);
+        }
+    }
+    log_writer.flush().await.map_err(log_write_error)?;
+
+    Ok(lines_tail.into_iter().collect::<Vec<_>>().join("\n"))
+}
+
+/// Returns the argument quoted for a POSIX shell on the remote host.
+pub(crate) fn shell_quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', "'\\''"))
+}
+
+/// Returns the command as it would be typed into a shell.
+fn command_display(command: &Command) -> String {
+    let command = command.as_std();
+    let mut command_display = command.get_program().to_string_lossy().into_owned();
+    command.get_args().for_each(|arg| {
+        command_display.push(' ');
+        command_display.push_str(&arg.to_string_lossy());
+    });
+    command_display
+}
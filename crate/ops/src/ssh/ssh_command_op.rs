@@ -0,0 +1,240 @@
+use std::{borrow::Borrow, fmt, marker::PhantomData};
+
+use choochoo_cfg_model::{
+    rt::{CheckStatus, ProgressLimit, ResIds, StationMutRef},
+    CleanFns, CreateFns, SetupFn, StationFn, StationOp,
+};
+use futures::future::LocalBoxFuture;
+
+use crate::{
+    ssh::{ssh_command, SshTarget},
+    OpsError,
+};
+
+// **Note:** `Clone` and `Debug` are manually implemented to avoid the trait
+// bound on `T`.
+/// Station operation that runs a command on a remote host over SSH.
+///
+/// The host and credentials are read from the `T` resource, which allows them
+/// to be inserted by an earlier station's setup function.
+///
+/// * **Check:** Runs the check command, if any. The work is not required if it
+///   exits successfully.
+/// * **Work:** Runs the command, advancing the station's progress bar for each
+///   line of output.
+/// * **Clean:** Runs the clean command, if any. If there is a check command,
+///   the clean command is only run when the check command exits successfully.
+///
+/// The command's output is written to [`SshCommandOp::STDOUT_LOG_FILE_NAME`]
+/// and [`SshCommandOp::STDERR_LOG_FILE_NAME`] in the station's directory, and
+/// the clean command's output to the corresponding `CLEAN_*` files.
+pub struct SshCommandOp<T = SshTarget> {
+    /// Command to run on the remote host.
+    command: String,
+    /// Command that exits successfully if the work is already done.
+    check_command: Option<String>,
+    /// Command that undoes the work.
+    clean_command: Option<String>,
+    /// Marker.
+    marker: PhantomData<T>,
+}
+
+impl<T> SshCommandOp<T>
+where
+    T: Borrow<SshTarget> + fmt::Debug + Send + Sync + 'static,
+{
+    /// Name of the file in the station's directory that the clean command's
+    /// standard error is written to.
+    pub const CLEAN_STDERR_LOG_FILE_NAME: &'static str = "ssh_command_clean.stderr.log";
+    /// Name of the file in the station's directory that the clean command's
+    /// standard output is written to.
+    pub const CLEAN_STDOUT_LOG_FILE_NAME: &'static str = "ssh_command_clean.stdout.log";
+    /// Name of the file in the station's directory that the command's standard
+    /// error is written to.
+    pub const STDERR_LOG_FILE_NAME: &'static str = "ssh_command.stderr.log";
+    /// Name of the file in the station's directory that the command's standard
+    /// output is written to.
+    pub const STDOUT_LOG_FILE_NAME: &'static str = "ssh_command.stdout.log";
+
+    /// Returns a new `SshCommandOp`.
+    ///
+    /// # Parameters
+    ///
+    /// * `command`: Command to run on the remote host, interpreted by the
+    ///   remote user's shell.
+    pub fn new<S>(command: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            command: command.into(),
+            check_command: None,
+            clean_command: None,
+            marker: PhantomData,
+        }
+    }
+
+    /// Sets the command that exits successfully if the work is already done.
+    ///
+    /// Without a check command, the command is run every time the station is
+    /// visited.
+    #[must_use]
+    pub fn with_check_command<S>(mut self, check_command: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.check_command = Some(check_command.into());
+        self
+    }
+
+    /// Sets the command that undoes the work.
+    ///
+    /// Without a clean command, the station has no clean functions.
+    #[must_use]
+    pub fn with_clean_command<S>(mut self, clean_command: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.clean_command = Some(clean_command.into());
+        self
+    }
+
+    /// Returns the command to run on the remote host.
+    pub fn command(&self) -> &str {
+        &self.command
+    }
+
+    /// Returns the command that exits successfully if the work is already
+    /// done.
+    pub fn check_command(&self) -> Option<&str> {
+        self.check_command.as_deref()
+    }
+
+    /// Returns the command that undoes the work.
+    pub fn clean_command(&self) -> Option<&str> {
+        self.clean_command.as_deref()
+    }
+
+    /// Returns the [`StationOp`] that runs the command.
+    pub fn build<E>(self) -> StationOp<E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        let mut create_fns = CreateFns::new(
+            SetupFn::new(|_station, _train_resources| {
+                Box::pin(async move { Result::<ProgressLimit, E>::Ok(ProgressLimit::Unknown) })
+            }),
+            Self::work_fn(self.command),
+        );
+        if let Some(check_command) = self.check_command.clone() {
+            create_fns = create_fns.with_check_fn(Self::check_fn(check_command, false));
+        }
+
+        let clean_fns = self.clean_command.map(|clean_command| {
+            let clean_fns = CleanFns::new(
+                SetupFn::new(|_station, _train_resources| {
+                    Box::pin(async move { Result::<ProgressLimit, E>::Ok(ProgressLimit::Unknown) })
+                }),
+                Self::clean_work_fn(clean_command),
+            );
+            match self.check_command {
+                Some(check_command) => clean_fns.with_check_fn(Self::check_fn(check_command, true)),
+                None => clean_fns,
+            }
+        });
+
+        StationOp::new(create_fns, clean_fns)
+    }
+
+    /// Returns a check function that runs the check command.
+    ///
+    /// When `clean` is `true`, the check command exiting successfully means the
+    /// clean work is required.
+    fn check_fn<E>(check_command: String, clean: bool) -> StationFn<CheckStatus, E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new1(
+            move |_station: &mut StationMutRef<'_, E>,
+                  ssh_target: &T|
+                  -> LocalBoxFuture<'_, Result<CheckStatus, E>> {
+                let command = ssh_command::ssh(ssh_target.borrow(), &check_command);
+                Box::pin(async move {
+                    let work_done = ssh_command::status(command).await?.success();
+                    let check_status = if work_done == clean {
+                        CheckStatus::WorkRequired
+                    } else {
+                        CheckStatus::WorkNotRequired
+                    };
+
+                    Result::<CheckStatus, E>::Ok(check_status)
+                })
+            },
+        )
+    }
+
+    fn work_fn<E>(command: String) -> StationFn<ResIds, (ResIds, E), E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new1(
+            move |station: &mut StationMutRef<'_, E>,
+                  ssh_target: &T|
+                  -> LocalBoxFuture<'_, Result<ResIds, (ResIds, E)>> {
+                let command = ssh_command::ssh(ssh_target.borrow(), &command);
+                let stdout_path = station.dir.join(Self::STDOUT_LOG_FILE_NAME);
+                let stderr_path = station.dir.join(Self::STDERR_LOG_FILE_NAME);
+                Box::pin(async move {
+                    let res_ids = ResIds::new();
+                    ssh_command::run_logged(&station.progress, command, &stdout_path, &stderr_path)
+                        .await
+                        .map_err(|error| (res_ids.clone(), E::from(error)))?;
+
+                    Result::<ResIds, (ResIds, E)>::Ok(res_ids)
+                })
+            },
+        )
+    }
+
+    fn clean_work_fn<E>(clean_command: String) -> StationFn<(), E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new1(
+            move |station: &mut StationMutRef<'_, E>,
+                  ssh_target: &T|
+                  -> LocalBoxFuture<'_, Result<(), E>> {
+                let command = ssh_command::ssh(ssh_target.borrow(), &clean_command);
+                let stdout_path = station.dir.join(Self::CLEAN_STDOUT_LOG_FILE_NAME);
+                let stderr_path = station.dir.join(Self::CLEAN_STDERR_LOG_FILE_NAME);
+                Box::pin(async move {
+                    ssh_command::run_logged(&station.progress, command, &stdout_path, &stderr_path)
+                        .await?;
+                    Result::<(), E>::Ok(())
+                })
+            },
+        )
+    }
+}
+
+impl<T> Clone for SshCommandOp<T> {
+    fn clone(&self) -> Self {
+        Self {
+            command: self.command.clone(),
+            check_command: self.check_command.clone(),
+            clean_command: self.clean_command.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for SshCommandOp<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SshCommandOp")
+            .field("command", &self.command)
+            .field("check_command", &self.check_command)
+            .field("clean_command", &self.clean_command)
+            .field("marker", &self.marker)
+            .finish()
+    }
+}
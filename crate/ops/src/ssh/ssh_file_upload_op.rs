@@ -0,0 +1,228 @@
+use std::{
+    borrow::Borrow,
+    fmt,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+use choochoo_cfg_model::{
+    rt::{CheckStatus, ProgressLimit, ResIdLogical, ResIds, StationMutRef},
+    CleanFns, CreateFns, SetupFn, StationFn, StationOp,
+};
+use futures::future::LocalBoxFuture;
+
+use crate::{
+    ssh::{ssh_command, SshTarget},
+    Checksum, ChecksumAlgorithm, OpsError,
+};
+
+// **Note:** `Clone` and `Debug` are manually implemented to avoid the trait
+// bound on `T`.
+/// Station operation that uploads a file to a remote host using `scp`.
+///
+/// The host and credentials are read from the `T` resource, which allows them
+/// to be inserted by an earlier station's setup function. The remote host
+/// must have `sha256sum` available.
+///
+/// * **Check:** The remote file exists, and its SHA-256 hash matches the local
+///   file's.
+/// * **Work:** Copies the file to the remote host.
+/// * **Clean:** Deletes the remote file.
+///
+/// The remote file's path is recorded in the station's [`ResIds`] under
+/// [`SshFileUploadOp::RES_ID_DEST_PATH`], and `scp`'s output is written to
+/// [`SshFileUploadOp::STDOUT_LOG_FILE_NAME`] and
+/// [`SshFileUploadOp::STDERR_LOG_FILE_NAME`] in the station's directory.
+pub struct SshFileUploadOp<T = SshTarget> {
+    /// Path of the local file to upload.
+    src_path: PathBuf,
+    /// Path on the remote host to upload the file to.
+    dest_path: String,
+    /// Marker.
+    marker: PhantomData<T>,
+}
+
+impl<T> SshFileUploadOp<T>
+where
+    T: Borrow<SshTarget> + fmt::Debug + Send + Sync + 'static,
+{
+    /// Logical ID of the remote file's path in the station's [`ResIds`].
+    pub const RES_ID_DEST_PATH: &'static str = "ssh_file_upload_dest_path";
+    /// Name of the file in the station's directory that `scp`'s standard error
+    /// is written to.
+    pub const STDERR_LOG_FILE_NAME: &'static str = "ssh_file_upload.stderr.log";
+    /// Name of the file in the station's directory that `scp`'s standard output
+    /// is written to.
+    pub const STDOUT_LOG_FILE_NAME: &'static str = "ssh_file_upload.stdout.log";
+
+    /// Returns a new `SshFileUploadOp`.
+    ///
+    /// # Parameters
+    ///
+    /// * `src_path`: Path of the local file to upload.
+    /// * `dest_path`: Path on the remote host to upload the file to. Relative
+    ///   paths are relative to the remote user's home directory.
+    pub fn new<P, S>(src_path: P, dest_path: S) -> Self
+    where
+        P: Into<PathBuf>,
+        S: Into<String>,
+    {
+        Self {
+            src_path: src_path.into(),
+            dest_path: dest_path.into(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the path of the local file to upload.
+    pub fn src_path(&self) -> &Path {
+        &self.src_path
+    }
+
+    /// Returns the path on the remote host to upload the file to.
+    pub fn dest_path(&self) -> &str {
+        &self.dest_path
+    }
+
+    /// Returns the [`StationOp`] that uploads the file.
+    pub fn build<E>(self) -> StationOp<E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        let create_fns = CreateFns::new(
+            SetupFn::new(|_station, _train_resources| {
+                Box::pin(async move { Result::<ProgressLimit, E>::Ok(ProgressLimit::Unknown) })
+            }),
+            Self::work_fn(self.src_path.clone(), self.dest_path.clone()),
+        )
+        .with_check_fn(Self::check_fn(self.src_path, self.dest_path.clone()));
+        let clean_fns = CleanFns::new(
+            SetupFn::new(|_station, _train_resources| {
+                Box::pin(async move { Result::<ProgressLimit, E>::Ok(ProgressLimit::Steps(1)) })
+            }),
+            Self::clean_work_fn(self.dest_path.clone()),
+        )
+        .with_check_fn(Self::clean_check_fn(self.dest_path));
+
+        StationOp::new(create_fns, Some(clean_fns))
+    }
+
+    fn check_fn<E>(src_path: PathBuf, dest_path: String) -> StationFn<CheckStatus, E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new1(
+            move |_station: &mut StationMutRef<'_, E>,
+                  ssh_target: &T|
+                  -> LocalBoxFuture<'_, Result<CheckStatus, E>> {
+                let src_path = src_path.clone();
+                let remote_command =
+                    format!("sha256sum -- {}", ssh_command::shell_quote(&dest_path));
+                let command = ssh_command::ssh(ssh_target.borrow(), &remote_command);
+                Box::pin(async move {
+                    // `sha256sum` exits unsuccessfully if the file does not exist.
+                    let remote_hex = match ssh_command::stdout_if_success(command).await? {
+                        Some(sha256sum_output) => sha256sum_output
+                            .split_whitespace()
+                            .next()
+                            .unwrap_or_default()
+                            .to_string(),
+                        None => return Ok(CheckStatus::WorkRequired),
+                    };
+
+                    let expected = Checksum::new(ChecksumAlgorithm::Sha256, remote_hex);
+                    let check_status = Checksum::file_check(&src_path, &expected, None).await?;
+
+                    Result::<CheckStatus, E>::Ok(check_status)
+                })
+            },
+        )
+    }
+
+    fn work_fn<E>(src_path: PathBuf, dest_path: String) -> StationFn<ResIds, (ResIds, E), E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new1(
+            move |station: &mut StationMutRef<'_, E>,
+                  ssh_target: &T|
+                  -> LocalBoxFuture<'_, Result<ResIds, (ResIds, E)>> {
+                let dest_path = dest_path.clone();
+                let command = ssh_command::scp(ssh_target.borrow(), &src_path, &dest_path);
+                let stdout_path = station.dir.join(Self::STDOUT_LOG_FILE_NAME);
+                let stderr_path = station.dir.join(Self::STDERR_LOG_FILE_NAME);
+                Box::pin(async move {
+                    let mut res_ids = ResIds::new();
+                    ssh_command::run_logged(&station.progress, command, &stdout_path, &stderr_path)
+                        .await
+                        .map_err(|error| (res_ids.clone(), E::from(error)))?;
+
+                    let _ = res_ids.insert(ResIdLogical::new(Self::RES_ID_DEST_PATH), dest_path);
+
+                    Result::<ResIds, (ResIds, E)>::Ok(res_ids)
+                })
+            },
+        )
+    }
+
+    fn clean_check_fn<E>(dest_path: String) -> StationFn<CheckStatus, E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new1(
+            move |_station: &mut StationMutRef<'_, E>,
+                  ssh_target: &T|
+                  -> LocalBoxFuture<'_, Result<CheckStatus, E>> {
+                let remote_command = format!("test -e {}", ssh_command::shell_quote(&dest_path));
+                let command = ssh_command::ssh(ssh_target.borrow(), &remote_command);
+                Box::pin(async move {
+                    let check_status = if ssh_command::status(command).await?.success() {
+                        CheckStatus::WorkRequired
+                    } else {
+                        CheckStatus::WorkNotRequired
+                    };
+                    Result::<CheckStatus, E>::Ok(check_status)
+                })
+            },
+        )
+    }
+
+    fn clean_work_fn<E>(dest_path: String) -> StationFn<(), E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new1(
+            move |station: &mut StationMutRef<'_, E>,
+                  ssh_target: &T|
+                  -> LocalBoxFuture<'_, Result<(), E>> {
+                let remote_command = format!("rm -f -- {}", ssh_command::shell_quote(&dest_path));
+                let command = ssh_command::ssh(ssh_target.borrow(), &remote_command);
+                Box::pin(async move {
+                    ssh_command::stdout(command).await?;
+                    station.progress.progress_bar().inc(1);
+                    Result::<(), E>::Ok(())
+                })
+            },
+        )
+    }
+}
+
+impl<T> Clone for SshFileUploadOp<T> {
+    fn clone(&self) -> Self {
+        Self {
+            src_path: self.src_path.clone(),
+            dest_path: self.dest_path.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for SshFileUploadOp<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SshFileUploadOp")
+            .field("src_path", &self.src_path)
+            .field("dest_path", &self.dest_path)
+            .field("marker", &self.marker)
+            .finish()
+    }
+}
@@ -0,0 +1,156 @@
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+/// Host and credentials to connect to over SSH.
+///
+/// SSH operations read this from a typed resource, so the host may be inserted
+/// by an earlier station, such as one that provisions a server.
+///
+/// Password authentication is not supported, as commands are run
+/// non-interactively. Connections use the identity file if provided, otherwise
+/// the user's SSH agent and configuration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SshTarget {
+    /// Host name or address of the server.
+    host: String,
+    /// Port of the SSH server, if not the default.
+    port: Option<u16>,
+    /// User to log in as, if not the SSH configuration's default.
+    user: Option<String>,
+    /// Path of the private key to authenticate with.
+    identity_file: Option<PathBuf>,
+}
+
+impl SshTarget {
+    /// Returns a new `SshTarget`.
+    ///
+    /// # Parameters
+    ///
+    /// * `host`: Host name or address of the server.
+    pub fn new<S>(host: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            host: host.into(),
+            port: None,
+            user: None,
+            identity_file: None,
+        }
+    }
+
+    /// Sets the port of the SSH server.
+    #[must_use]
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    /// Sets the user to log in as.
+    #[must_use]
+    pub fn with_user<S>(mut self, user: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.user = Some(user.into());
+        self
+    }
+
+    /// Sets the path of the private key to authenticate with.
+    #[must_use]
+    pub fn with_identity_file<P>(mut self, identity_file: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.identity_file = Some(identity_file.into());
+        self
+    }
+
+    /// Returns the host name or address of the server.
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Returns the port of the SSH server, if not the default.
+    pub fn port(&self) -> Option<u16> {
+        self.port
+    }
+
+    /// Returns the user to log in as, if not the SSH configuration's default.
+    pub fn user(&self) -> Option<&str> {
+        self.user.as_deref()
+    }
+
+    /// Returns the path of the private key to authenticate with.
+    pub fn identity_file(&self) -> Option<&Path> {
+        self.identity_file.as_deref()
+    }
+
+    /// Returns the `user@host` destination passed to `ssh` and `scp`.
+    pub fn destination(&self) -> String {
+        match self.user.as_deref() {
+            Some(user) => format!("{user}@{}", self.host),
+            None => self.host.clone(),
+        }
+    }
+
+    /// Returns the arguments passed to `ssh` to run a command on the target.
+    ///
+    /// The destination is placed after `--`, so a host or user that begins
+    /// with `-` is not interpreted as an option.
+    ///
+    /// # Parameters
+    ///
+    /// * `remote_command`: Command to run on the remote host.
+    pub fn ssh_args(&self, remote_command: &str) -> Vec<OsString> {
+        let mut args = Vec::new();
+        if let Some(port) = self.port {
+            args.push(OsString::from("-p"));
+            args.push(OsString::from(port.to_string()));
+        }
+        self.connection_args_push(&mut args);
+        args.push(OsString::from("--"));
+        args.push(OsString::from(self.destination()));
+        args.push(OsString::from(remote_command));
+        args
+    }
+
+    /// Returns the arguments passed to `scp` to copy a local file to the
+    /// target.
+    ///
+    /// The source and destination are placed after `--`, so a path, host, or
+    /// user that begins with `-` is not interpreted as an option.
+    ///
+    /// # Parameters
+    ///
+    /// * `src_path`: Path of the local file to copy.
+    /// * `dest_path`: Path on the remote host to copy the file to.
+    pub fn scp_args(&self, src_path: &Path, dest_path: &str) -> Vec<OsString> {
+        let mut args = Vec::new();
+        if let Some(port) = self.port {
+            args.push(OsString::from("-P"));
+            args.push(OsString::from(port.to_string()));
+        }
+        self.connection_args_push(&mut args);
+        args.push(OsString::from("--"));
+        args.push(src_path.as_os_str().to_os_string());
+        args.push(OsString::from(format!(
+            "{}:{dest_path}",
+            self.destination()
+        )));
+        args
+    }
+
+    /// Adds the options shared by `ssh` and `scp`.
+    fn connection_args_push(&self, args: &mut Vec<OsString>) {
+        if let Some(identity_file) = self.identity_file.as_deref() {
+            args.push(OsString::from("-i"));
+            args.push(identity_file.as_os_str().to_os_string());
+        }
+        // Fail instead of prompting for a password or host key confirmation.
+        args.push(OsString::from("-o"));
+        args.push(OsString::from("BatchMode=yes"));
+    }
+}
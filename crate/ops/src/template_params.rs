@@ -0,0 +1,86 @@
+use std::{
+    collections::BTreeMap,
+    ops::{Deref, DerefMut},
+    path::Path,
+};
+
+use crate::OpsError;
+
+/// Values substituted into a template, `BTreeMap<String, String>` newtype.
+///
+/// Templates reference values using `{{ name }}` placeholders. Whitespace
+/// around the name is ignored, and there is no escaping or control flow.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TemplateParams(pub BTreeMap<String, String>);
+
+impl TemplateParams {
+    /// Returns empty `TemplateParams`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the value to substitute for a placeholder name.
+    #[must_use]
+    pub fn with_param<K, V>(mut self, name: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.0.insert(name.into(), value.into());
+        self
+    }
+
+    /// Returns the template with each `{{ name }}` placeholder replaced with
+    /// its value.
+    ///
+    /// # Parameters
+    ///
+    /// * `template_path`: Path of the template, used in error messages.
+    /// * `template`: Contents of the template.
+    pub fn render(&self, template_path: &Path, template: &str) -> Result<String, OpsError> {
+        let mut rendered = String::with_capacity(template.len());
+        let mut remaining = template;
+        while let Some(open_index) = remaining.find("{{") {
+            rendered.push_str(&remaining[..open_index]);
+
+            let placeholder = &remaining[open_index + 2..];
+            let close_index =
+                placeholder
+                    .find("}}")
+                    .ok_or_else(|| OpsError::TemplatePlaceholderUnclosed {
+                        template_path: template_path.to_path_buf(),
+                        offset: template.len() - remaining.len() + open_index,
+                    })?;
+            let name = placeholder[..close_index].trim();
+            let value = self
+                .0
+                .get(name)
+                .ok_or_else(|| OpsError::TemplateParamNotFound {
+                    template_path: template_path.to_path_buf(),
+                    name: name.to_string(),
+                })?;
+            rendered.push_str(value);
+
+            remaining = &placeholder[close_index + 2..];
+        }
+        rendered.push_str(remaining);
+
+        Ok(rendered)
+    }
+}
+
+impl Deref for TemplateParams {
+    type Target = BTreeMap<String, String>;
+
+    #[cfg(not(tarpaulin_include))]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for TemplateParams {
+    #[cfg(not(tarpaulin_include))]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
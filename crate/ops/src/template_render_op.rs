@@ -0,0 +1,231 @@
+use std::{
+    borrow::Borrow,
+    fmt,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+use choochoo_cfg_model::{
+    rt::{CheckStatus, ProgressLimit, ResIdLogical, ResIds, StationMutRef},
+    CleanFns, CreateFns, SetupFn, StationFn, StationOp,
+};
+use futures::future::LocalBoxFuture;
+use tokio::fs;
+
+use crate::{Checksum, ChecksumAlgorithm, OpsError, TemplateParams};
+
+// **Note:** `Clone` and `Debug` are manually implemented to avoid the trait
+// bound on `P`.
+/// Station operation that renders a template file into the station's
+/// directory.
+///
+/// The values to substitute are read from the `P` resource, which allows them
+/// to be inserted by an earlier station's setup function. See
+/// [`TemplateParams`] for the placeholder syntax.
+///
+/// * **Check:** The rendered file exists, and its hash matches the hash of the
+///   template rendered with the current values.
+/// * **Work:** Renders the template and writes it to the file.
+/// * **Clean:** Deletes the rendered file.
+///
+/// The rendered file's path is recorded in the station's [`ResIds`] under
+/// [`TemplateRenderOp::RES_ID_PATH`].
+pub struct TemplateRenderOp<P = TemplateParams> {
+    /// Path of the template to render.
+    template_path: PathBuf,
+    /// Path to write the rendered file to.
+    ///
+    /// Relative paths are relative to the station's directory.
+    dest_path: PathBuf,
+    /// Marker.
+    marker: PhantomData<P>,
+}
+
+impl<P> TemplateRenderOp<P>
+where
+    P: Borrow<TemplateParams> + fmt::Debug + Send + Sync + 'static,
+{
+    /// Logical ID of the rendered file's path in the station's [`ResIds`].
+    pub const RES_ID_PATH: &'static str = "template_render_path";
+
+    /// Returns a new `TemplateRenderOp`.
+    ///
+    /// # Parameters
+    ///
+    /// * `template_path`: Path of the template to render.
+    /// * `dest_path`: Path to write the rendered file to. Relative paths are
+    ///   relative to the station's directory.
+    pub fn new<TP, DP>(template_path: TP, dest_path: DP) -> Self
+    where
+        TP: Into<PathBuf>,
+        DP: Into<PathBuf>,
+    {
+        Self {
+            template_path: template_path.into(),
+            dest_path: dest_path.into(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the path of the template to render.
+    pub fn template_path(&self) -> &Path {
+        &self.template_path
+    }
+
+    /// Returns the path to write the rendered file to.
+    pub fn dest_path(&self) -> &Path {
+        &self.dest_path
+    }
+
+    /// Returns the [`StationOp`] that renders the template.
+    pub fn build<E>(self) -> StationOp<E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        let create_fns = CreateFns::new(
+            SetupFn::new(|_station, _train_resources| {
+                Box::pin(async move { Result::<ProgressLimit, E>::Ok(ProgressLimit::Steps(1)) })
+            }),
+            Self::work_fn(self.template_path.clone(), self.dest_path.clone()),
+        )
+        .with_check_fn(Self::check_fn(
+            self.template_path.clone(),
+            self.dest_path.clone(),
+        ));
+        let clean_fns = CleanFns::new(
+            SetupFn::new(|_station, _train_resources| {
+                Box::pin(async move { Result::<ProgressLimit, E>::Ok(ProgressLimit::Steps(1)) })
+            }),
+            Self::clean_work_fn(self.dest_path.clone()),
+        )
+        .with_check_fn(Self::clean_check_fn(self.dest_path));
+
+        StationOp::new(create_fns, Some(clean_fns))
+    }
+
+    fn check_fn<E>(template_path: PathBuf, dest_path: PathBuf) -> StationFn<CheckStatus, E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new1(
+            move |station: &mut StationMutRef<'_, E>,
+                  params: &P|
+                  -> LocalBoxFuture<'_, Result<CheckStatus, E>> {
+                let template_path = template_path.clone();
+                let dest_path = station.dir.join(&dest_path);
+                Box::pin(async move {
+                    let rendered = render(&template_path, params.borrow()).await?;
+                    let expected = Checksum::bytes(ChecksumAlgorithm::Sha256, rendered.as_bytes());
+                    let check_status = Checksum::file_check(&dest_path, &expected, None).await?;
+
+                    Result::<CheckStatus, E>::Ok(check_status)
+                })
+            },
+        )
+    }
+
+    fn work_fn<E>(template_path: PathBuf, dest_path: PathBuf) -> StationFn<ResIds, (ResIds, E), E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new1(
+            move |station: &mut StationMutRef<'_, E>,
+                  params: &P|
+                  -> LocalBoxFuture<'_, Result<ResIds, (ResIds, E)>> {
+                let template_path = template_path.clone();
+                let dest_path = station.dir.join(&dest_path);
+                Box::pin(async move {
+                    let mut res_ids = ResIds::new();
+                    let rendered = render(&template_path, params.borrow())
+                        .await
+                        .map_err(|error| (res_ids.clone(), E::from(error)))?;
+                    fs::write(&dest_path, rendered).await.map_err(|error| {
+                        let error = OpsError::FileWrite {
+                            path: dest_path.clone(),
+                            error,
+                        };
+                        (res_ids.clone(), E::from(error))
+                    })?;
+                    station.progress.progress_bar().inc(1);
+
+                    let _ = res_ids.insert(ResIdLogical::new(Self::RES_ID_PATH), dest_path);
+
+                    Result::<ResIds, (ResIds, E)>::Ok(res_ids)
+                })
+            },
+        )
+    }
+
+    fn clean_check_fn<E>(dest_path: PathBuf) -> StationFn<CheckStatus, E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new0(
+            move |station: &mut StationMutRef<'_, E>|
+                  -> LocalBoxFuture<'_, Result<CheckStatus, E>> {
+                let dest_path = station.dir.join(&dest_path);
+                Box::pin(async move {
+                    let check_status = if dest_path.exists() {
+                        CheckStatus::WorkRequired
+                    } else {
+                        CheckStatus::WorkNotRequired
+                    };
+                    Result::<CheckStatus, E>::Ok(check_status)
+                })
+            },
+        )
+    }
+
+    fn clean_work_fn<E>(dest_path: PathBuf) -> StationFn<(), E, E>
+    where
+        E: From<OpsError> + 'static,
+    {
+        StationFn::new0(
+            move |station: &mut StationMutRef<'_, E>| -> LocalBoxFuture<'_, Result<(), E>> {
+                let dest_path = station.dir.join(&dest_path);
+                Box::pin(async move {
+                    fs::remove_file(&dest_path)
+                        .await
+                        .map_err(|error| OpsError::FileRemove {
+                            path: dest_path,
+                            error,
+                        })?;
+                    station.progress.progress_bar().inc(1);
+                    Result::<(), E>::Ok(())
+                })
+            },
+        )
+    }
+}
+
+/// Reads the template and renders it with the given values.
+async fn render(template_path: &Path, params: &TemplateParams) -> Result<String, OpsError> {
+    let template = fs::read_to_string(template_path)
+        .await
+        .map_err(|error| OpsError::FileRead {
+            path: template_path.to_path_buf(),
+            error,
+        })?;
+
+    params.render(template_path, &template)
+}
+
+impl<P> Clone for TemplateRenderOp<P> {
+    fn clone(&self) -> Self {
+        Self {
+            template_path: self.template_path.clone(),
+            dest_path: self.dest_path.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<P> fmt::Debug for TemplateRenderOp<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TemplateRenderOp")
+            .field("template_path", &self.template_path)
+            .field("dest_path", &self.dest_path)
+            .field("marker", &self.marker)
+            .finish()
+    }
+}
@@ -0,0 +1,62 @@
+use std::{
+    ffi::OsStr,
+    ops::Deref,
+    path::{Path, PathBuf},
+};
+
+/// Directory to cache data that is shared across profiles.
+///
+/// Typically `${workspace}/target/.cache`.
+///
+/// Unlike the [`ProfileDir`], which is specific to each environment, the cache
+/// directory is shared by all profiles. This allows expensive artifacts such as
+/// downloads to be reused across profiles.
+///
+/// Stations should store data in a namespaced subdirectory, returned by
+/// [`CacheDir::namespace_dir`], so that they do not conflict with each other.
+///
+/// [`ProfileDir`]: crate::ProfileDir
+#[derive(Clone, Debug, PartialEq)]
+pub struct CacheDir(PathBuf);
+
+impl CacheDir {
+    /// Returns a new [`CacheDir`].
+    pub fn new(path: PathBuf) -> Self {
+        Self(path)
+    }
+
+    /// Returns the path of the cache subdirectory for the given namespace.
+    ///
+    /// The directory is not created.
+    ///
+    /// # Parameters
+    ///
+    /// * `namespace`: Namespace of the cached data, such as the station ID or
+    ///   the kind of artifact.
+    pub fn namespace_dir<P>(&self, namespace: P) -> PathBuf
+    where
+        P: AsRef<Path>,
+    {
+        self.0.join(namespace)
+    }
+}
+
+impl AsRef<OsStr> for CacheDir {
+    fn as_ref(&self) -> &OsStr {
+        self.0.as_ref()
+    }
+}
+
+impl AsRef<Path> for CacheDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Deref for CacheDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
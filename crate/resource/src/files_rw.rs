@@ -5,11 +5,11 @@ use std::{
 
 use tokio::sync::RwLock;
 
-use crate::Files;
+use crate::FilesStore;
 
-/// Atomic RW access to `Files`.
+/// Atomic RW access to [`FilesStore`].
 #[derive(Debug, Default)]
-pub struct FilesRw(Arc<RwLock<Files>>);
+pub struct FilesRw(Arc<RwLock<FilesStore>>);
 
 impl FilesRw {
     /// Returns new [`FilesRw`].
@@ -19,7 +19,7 @@ impl FilesRw {
 }
 
 impl Deref for FilesRw {
-    type Target = Arc<RwLock<Files>>;
+    type Target = Arc<RwLock<FilesStore>>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -0,0 +1,196 @@
+use std::{
+    borrow::Cow,
+    collections::{hash_map::DefaultHasher, HashMap, VecDeque},
+    ffi::{OsStr, OsString},
+    hash::{Hash, Hasher},
+    ops::{Deref, DerefMut},
+};
+
+use srcerr::codespan::FileId;
+
+use crate::Files;
+
+/// [`Files`] that deduplicates sources, and evicts old sources.
+///
+/// Adding a source with the same name and content as a source that is
+/// already stored returns the existing [`FileId`], so checks that add the
+/// same logical sources each time do not grow the store.
+///
+/// When a maximum number of sources is set, the oldest sources are evicted
+/// once it is exceeded. `codespan` cannot remove files, so an evicted
+/// source's content is cleared, and its `FileId` is reused for the next
+/// source added with the same name. Diagnostics should be rendered before
+/// the sources they refer to are evicted.
+#[derive(Debug, Default)]
+pub struct FilesStore {
+    /// Underlying `codespan` files.
+    files: Files,
+    /// Stored sources by name, in the order they were added.
+    file_ids_by_name: HashMap<OsString, Vec<FileId>>,
+    /// Stored sources by the hash of their name and content.
+    file_ids_by_hash: HashMap<u64, Vec<FileId>>,
+    /// Stored sources, least recently added first.
+    file_ids_stored: VecDeque<FileId>,
+    /// Evicted sources by name, whose `FileId`s may be reused.
+    file_ids_evicted: HashMap<OsString, Vec<FileId>>,
+    /// Maximum number of sources to store before evicting the oldest.
+    sources_max: Option<usize>,
+}
+
+impl FilesStore {
+    /// Returns a new, empty `FilesStore` that never evicts sources.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of sources to store before evicting the
+    /// oldest.
+    #[must_use]
+    pub fn with_sources_max(mut self, sources_max: usize) -> Self {
+        self.sources_max_set(Some(sources_max));
+        self
+    }
+
+    /// Sets the maximum number of sources to store before evicting the
+    /// oldest, or `None` to never evict sources.
+    ///
+    /// If more sources are stored than the new maximum, the oldest are evicted
+    /// immediately.
+    pub fn sources_max_set(&mut self, sources_max: Option<usize>) {
+        self.sources_max = sources_max;
+        self.evict_excess();
+    }
+
+    /// Adds a source, returning the `FileId` of an identical stored source if
+    /// there is one.
+    ///
+    /// A source is identical if it has the same name and content. Adding an
+    /// identical source marks it as recently added, so it is evicted last.
+    pub fn add(&mut self, name: impl Into<OsString>, source: Cow<'static, str>) -> FileId {
+        let name = name.into();
+        let hash = Self::source_hash(&name, &source);
+
+        let file_id_existing = self.file_ids_by_hash.get(&hash).and_then(|file_ids| {
+            file_ids.iter().copied().find(|file_id| {
+                self.files.name(*file_id) == name.as_os_str()
+                    && self.files.source(*file_id) == &source
+            })
+        });
+        if let Some(file_id) = file_id_existing {
+            self.file_ids_stored
+                .retain(|file_id_stored| *file_id_stored != file_id);
+            self.file_ids_stored.push_back(file_id);
+            return file_id;
+        }
+
+        let file_id_evicted = self
+            .file_ids_evicted
+            .get_mut(&name)
+            .and_then(|file_ids| file_ids.pop());
+        let file_id = match file_id_evicted {
+            Some(file_id) => {
+                self.files.update(file_id, source);
+                file_id
+            }
+            None => self.files.add(name.clone(), source),
+        };
+
+        self.file_ids_by_name.entry(name).or_default().push(file_id);
+        self.file_ids_by_hash.entry(hash).or_default().push(file_id);
+        self.file_ids_stored.push_back(file_id);
+        self.evict_excess();
+
+        file_id
+    }
+
+    /// Returns the `FileId` of the most recently added source with the given
+    /// name, if it is stored.
+    pub fn file_id(&self, name: impl AsRef<OsStr>) -> Option<FileId> {
+        self.file_ids_by_name
+            .get(name.as_ref())
+            .and_then(|file_ids| file_ids.last().copied())
+    }
+
+    /// Evicts a source, clearing its content.
+    ///
+    /// Returns whether the source was stored.
+    pub fn evict(&mut self, file_id: FileId) -> bool {
+        let position = self
+            .file_ids_stored
+            .iter()
+            .position(|file_id_stored| *file_id_stored == file_id);
+        if let Some(position) = position {
+            self.file_ids_stored.remove(position);
+            self.evict_inner(file_id);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the number of stored sources, excluding evicted ones.
+    pub fn len(&self) -> usize {
+        self.file_ids_stored.len()
+    }
+
+    /// Returns whether there are no stored sources.
+    pub fn is_empty(&self) -> bool {
+        self.file_ids_stored.is_empty()
+    }
+
+    /// Evicts the oldest sources until there are no more than the maximum.
+    fn evict_excess(&mut self) {
+        if let Some(sources_max) = self.sources_max {
+            while self.file_ids_stored.len() > sources_max {
+                if let Some(file_id) = self.file_ids_stored.pop_front() {
+                    self.evict_inner(file_id);
+                }
+            }
+        }
+    }
+
+    /// Removes a source from the indices, and clears its content.
+    ///
+    /// The source must already be removed from `file_ids_stored`.
+    fn evict_inner(&mut self, file_id: FileId) {
+        let name = self.files.name(file_id).to_os_string();
+        let hash = Self::source_hash(&name, self.files.source(file_id));
+
+        if let Some(file_ids) = self.file_ids_by_name.get_mut(&name) {
+            file_ids.retain(|file_id_named| *file_id_named != file_id);
+            if file_ids.is_empty() {
+                self.file_ids_by_name.remove(&name);
+            }
+        }
+        if let Some(file_ids) = self.file_ids_by_hash.get_mut(&hash) {
+            file_ids.retain(|file_id_hashed| *file_id_hashed != file_id);
+            if file_ids.is_empty() {
+                self.file_ids_by_hash.remove(&hash);
+            }
+        }
+
+        self.files.update(file_id, Cow::Borrowed(""));
+        self.file_ids_evicted.entry(name).or_default().push(file_id);
+    }
+
+    fn source_hash(name: &OsStr, source: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        name.hash(&mut hasher);
+        source.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl Deref for FilesStore {
+    type Target = Files;
+
+    fn deref(&self) -> &Self::Target {
+        &self.files
+    }
+}
+
+impl DerefMut for FilesStore {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.files
+    }
+}
@@ -1,16 +1,27 @@
 //! Runtime data types for the choochoo automation library.
 
 pub use crate::{
-    files::Files, files_rw::FilesRw, history_dir::HistoryDir, profile::Profile,
-    profile_dir::ProfileDir, profile_error::ProfileError, profile_history_dir::ProfileHistoryDir,
-    workspace_dir::WorkspaceDir,
+    cache_dir::CacheDir, files::Files, files_rw::FilesRw, files_store::FilesStore,
+    history_dir::HistoryDir, profile::Profile, profile_dir::ProfileDir,
+    profile_error::ProfileError, profile_history_dir::ProfileHistoryDir,
+    source_provenance::SourceProvenance, source_ref::SourceRef, source_registry::SourceRegistry,
+    span_finder::SpanFinder, workspace_dir::WorkspaceDir, workspace_path::WorkspacePath,
+    workspace_path_error::WorkspacePathError,
 };
 
+mod cache_dir;
 mod files;
 mod files_rw;
+mod files_store;
 mod history_dir;
 mod profile;
 mod profile_dir;
 mod profile_error;
 mod profile_history_dir;
+mod source_provenance;
+mod source_ref;
+mod source_registry;
+mod span_finder;
 mod workspace_dir;
+mod workspace_path;
+mod workspace_path_error;
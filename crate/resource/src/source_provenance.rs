@@ -0,0 +1,38 @@
+use std::{fmt, path::PathBuf};
+
+/// Where a logical source's value came from.
+///
+/// This is shown alongside diagnostics, so that users know where to change the
+/// value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SourceProvenance {
+    /// Value of a key in a configuration file.
+    ConfigValue {
+        /// Path to the configuration file.
+        path: PathBuf,
+        /// Key of the value within the file.
+        key: String,
+    },
+    /// Value of an environment variable.
+    EnvVar {
+        /// Name of the environment variable.
+        name: String,
+    },
+    /// Value passed on the command line.
+    CommandLine,
+    /// Value computed by a station, such as an address or path it derives.
+    Computed,
+}
+
+impl fmt::Display for SourceProvenance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ConfigValue { path, key } => {
+                write!(f, "configuration value `{}` in `{}`", key, path.display())
+            }
+            Self::EnvVar { name } => write!(f, "environment variable `{}`", name),
+            Self::CommandLine => write!(f, "command line"),
+            Self::Computed => write!(f, "computed value"),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::{borrow::Cow, ops::Range};
+
+use srcerr::{
+    codespan::{FileId, Span},
+    codespan_reporting::diagnostic::Label,
+};
+
+use crate::{SourceProvenance, SpanFinder};
+
+/// Logical source registered in the [`SourceRegistry`].
+///
+/// This carries the source's [`FileId`] and content, so that spans of parts of
+/// the value, such as the host of an address, can be found without manual
+/// offset arithmetic.
+///
+/// [`SourceRegistry`]: crate::SourceRegistry
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourceRef {
+    /// Name of the source.
+    name: String,
+    /// ID of the source in the [`FilesStore`](crate::FilesStore).
+    file_id: FileId,
+    /// Content of the source.
+    source: Cow<'static, str>,
+    /// Where the source's value came from.
+    provenance: SourceProvenance,
+}
+
+impl SourceRef {
+    /// Returns a new `SourceRef`.
+    ///
+    /// # Parameters
+    ///
+    /// * `name`: Name of the source.
+    /// * `file_id`: ID of the source in the `FilesStore`.
+    /// * `source`: Content of the source.
+    /// * `provenance`: Where the source's value came from.
+    pub fn new(
+        name: String,
+        file_id: FileId,
+        source: Cow<'static, str>,
+        provenance: SourceProvenance,
+    ) -> Self {
+        Self {
+            name,
+            file_id,
+            source,
+            provenance,
+        }
+    }
+
+    /// Returns the name of the source.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the ID of the source in the `FilesStore`.
+    pub fn file_id(&self) -> FileId {
+        self.file_id
+    }
+
+    /// Returns the content of the source.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns where the source's value came from.
+    pub fn provenance(&self) -> &SourceProvenance {
+        &self.provenance
+    }
+
+    /// Returns the span of the whole source.
+    pub fn span(&self) -> Span {
+        Span::from_str(&self.source)
+    }
+
+    /// Returns a [`SpanFinder`] to compute spans of parts of the source, such
+    /// as the host of a URL.
+    pub fn span_finder(&self) -> SpanFinder<'_> {
+        SpanFinder::new(&self.source)
+    }
+
+    /// Returns the span of the first occurrence of `part` within the source.
+    ///
+    /// Returns `None` if the source does not contain `part`.
+    pub fn span_of(&self, part: &str) -> Option<Span> {
+        self.span_finder().substring(part)
+    }
+
+    /// Returns the span of the first occurrence of `part` at or after
+    /// `after`.
+    ///
+    /// This is useful when `part` may occur earlier in the source, such as a
+    /// port that is also a substring of the host.
+    ///
+    /// Returns `None` if the source does not contain `part` after `after`.
+    pub fn span_of_after(&self, part: &str, after: Span) -> Option<Span> {
+        let offset = after.end().to_usize();
+        self.source
+            .get(offset..)
+            .and_then(|remainder| remainder.find(part))
+            .map(|start| Self::span_from_range(offset + start..offset + start + part.len()))
+    }
+
+    /// Returns a primary label over `span` with the given message.
+    pub fn label_primary(&self, span: Span, message: impl Into<String>) -> Label<FileId> {
+        Label::primary(self.file_id, span).with_message(message)
+    }
+
+    /// Returns a secondary label over `span` with the given message.
+    pub fn label_secondary(&self, span: Span, message: impl Into<String>) -> Label<FileId> {
+        Label::secondary(self.file_id, span).with_message(message)
+    }
+
+    /// Returns a note describing where the source's value came from.
+    pub fn provenance_note(&self) -> String {
+        format!("`{}` is from the {}.", self.name, self.provenance)
+    }
+
+    fn span_from_range(range: Range<usize>) -> Span {
+        Span::new(range.start as u32, range.end as u32)
+    }
+}
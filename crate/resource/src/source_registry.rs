@@ -0,0 +1,41 @@
+use std::{borrow::Cow, sync::Arc};
+
+use tokio::sync::RwLock;
+
+use crate::{FilesRw, FilesStore, SourceProvenance, SourceRef};
+
+/// Registry of logical sources, such as configuration values, addresses, and
+/// paths, that diagnostics refer to.
+///
+/// Sources are stored in the same [`FilesStore`] as [`FilesRw`], so
+/// diagnostics that use the returned [`SourceRef`]s are rendered with the
+/// train's other diagnostics.
+#[derive(Clone, Debug)]
+pub struct SourceRegistry(Arc<RwLock<FilesStore>>);
+
+impl SourceRegistry {
+    /// Returns a new `SourceRegistry` that stores sources in `files_rw`.
+    pub fn new(files_rw: &FilesRw) -> Self {
+        Self(Arc::clone(files_rw))
+    }
+
+    /// Registers a source, returning a reference to use in diagnostics.
+    ///
+    /// # Parameters
+    ///
+    /// * `name`: Name of the source, shown in diagnostics.
+    /// * `source`: Content of the source.
+    /// * `provenance`: Where the source's value came from.
+    pub async fn register(
+        &self,
+        name: impl Into<String>,
+        source: impl Into<Cow<'static, str>>,
+        provenance: SourceProvenance,
+    ) -> SourceRef {
+        let name = name.into();
+        let source = source.into();
+        let file_id = self.0.write().await.add(name.as_str(), source.clone());
+
+        SourceRef::new(name, file_id, source, provenance)
+    }
+}
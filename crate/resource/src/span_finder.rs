@@ -0,0 +1,367 @@
+use srcerr::codespan::Span;
+
+/// Computes [`Span`]s of parts of a source, such as the host of a URL, or a
+/// value within a configuration file.
+///
+/// These are lightweight scanners, not full parsers -- they find spans within
+/// well formed sources, and return `None` when the part cannot be found.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SpanFinder<'s> {
+    /// Source to find spans in.
+    source: &'s str,
+}
+
+impl<'s> SpanFinder<'s> {
+    /// Returns a new `SpanFinder` over the given source.
+    pub fn new(source: &'s str) -> Self {
+        Self { source }
+    }
+
+    /// Returns the span of the whole source.
+    pub fn all(&self) -> Span {
+        Span::from_str(self.source)
+    }
+
+    /// Returns the span of the first occurrence of `part`.
+    pub fn substring(&self, part: &str) -> Option<Span> {
+        self.source
+            .find(part)
+            .map(|start| Self::span(start, start + part.len()))
+    }
+
+    /// Returns the span of the authority of a URL, i.e. the `host:port` of
+    /// `http://user@host:port/path`, excluding user information.
+    ///
+    /// Sources without a scheme are treated as starting with the authority.
+    pub fn url_authority(&self) -> Span {
+        let (start, end) = self.url_authority_range();
+        Self::span(start, end)
+    }
+
+    /// Returns the span of the host of a URL.
+    ///
+    /// IPv6 hosts include their surrounding brackets.
+    pub fn url_host(&self) -> Option<Span> {
+        let (start, end) = self.url_authority_range();
+        let host_end = start + Self::url_host_len(&self.source[start..end]);
+
+        if host_end == start {
+            None
+        } else {
+            Some(Self::span(start, host_end))
+        }
+    }
+
+    /// Returns the span of the port of a URL, excluding the `:` separator.
+    pub fn url_port(&self) -> Option<Span> {
+        let (start, end) = self.url_authority_range();
+        let host_end = start + Self::url_host_len(&self.source[start..end]);
+        let port_start = host_end + 1;
+
+        if self.source[host_end..end].starts_with(':') && port_start < end {
+            Some(Self::span(port_start, end))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the span of the path of a URL, excluding the query and
+    /// fragment.
+    pub fn url_path(&self) -> Option<Span> {
+        let (_, start) = self.url_authority_range();
+        let path = &self.source[start..];
+        let path_len = path
+            .find(|c| matches!(c, '?' | '#'))
+            .unwrap_or_else(|| path.len());
+
+        if path_len == 0 {
+            None
+        } else {
+            Some(Self::span(start, start + path_len))
+        }
+    }
+
+    /// Returns the span of the value of `key` in `key=value` lines.
+    ///
+    /// Whitespace around the key and value is ignored, as are lines that begin
+    /// with `#`.
+    pub fn key_value(&self, key: &str) -> Option<Span> {
+        self.lines()
+            .filter(|(_, line)| !line.trim_start().starts_with('#'))
+            .find_map(|(line_start, line)| {
+                let (line_key, value_start, value_end) = Self::key_value_split(line)?;
+                if line_key == key {
+                    Some(Self::span(line_start + value_start, line_start + value_end))
+                } else {
+                    None
+                }
+            })
+    }
+
+    /// Returns the span of the value at a dotted path within a TOML source,
+    /// such as `server.address`.
+    ///
+    /// Keys under `[table]` headers are prefixed with the table name. Values
+    /// that span multiple lines are not supported.
+    pub fn toml_path(&self, path: &str) -> Option<Span> {
+        let mut table = String::new();
+        for (line_start, line) in self.lines() {
+            let trimmed = line.trim();
+            if trimmed.starts_with('[') {
+                table = Self::toml_key_normalize(trimmed.trim_matches(|c| c == '[' || c == ']'));
+                continue;
+            }
+            if trimmed.starts_with('#') {
+                continue;
+            }
+
+            if let Some((key, value_start, value_end)) = Self::key_value_split(line) {
+                let key = Self::toml_key_normalize(key);
+                let key_matches = if table.is_empty() {
+                    key == path
+                } else {
+                    path.strip_prefix(table.as_str())
+                        .and_then(|path| path.strip_prefix('.'))
+                        .map(|path_key| path_key == key)
+                        .unwrap_or(false)
+                };
+
+                if key_matches {
+                    let value = &line[value_start..value_end];
+                    let value_end = value_start + Self::toml_value_len(value);
+                    return Some(Self::span(line_start + value_start, line_start + value_end));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Returns the span of the value at a [JSON pointer] within a JSON
+    /// source, such as `/servers/0/address`.
+    ///
+    /// Object keys are compared without decoding escape sequences.
+    ///
+    /// [JSON pointer]: https://datatracker.ietf.org/doc/html/rfc6901
+    pub fn json_pointer(&self, pointer: &str) -> Option<Span> {
+        let tokens = if pointer.is_empty() {
+            Vec::new()
+        } else {
+            pointer
+                .strip_prefix('/')?
+                .split('/')
+                .map(|token| token.replace("~1", "/").replace("~0", "~"))
+                .collect::<Vec<String>>()
+        };
+
+        self.json_value_find(0, &tokens)
+            .map(|(start, end)| Self::span(start, end))
+    }
+
+    /// Returns the lines of the source, with the byte offset each line starts
+    /// at.
+    fn lines(&self) -> impl Iterator<Item = (usize, &'s str)> {
+        let source = self.source;
+        let mut line_start = 0;
+        source.split('\n').map(move |line| {
+            let offset = line_start;
+            line_start += line.len() + 1;
+            (offset, line.strip_suffix('\r').unwrap_or(line))
+        })
+    }
+
+    /// Splits a `key = value` line into its trimmed key, and the start and end
+    /// offsets of its trimmed value.
+    fn key_value_split(line: &str) -> Option<(&str, usize, usize)> {
+        let eq_index = line.find('=')?;
+        let key = line[..eq_index].trim();
+        let value = &line[eq_index + 1..];
+        let value_start = eq_index + 1 + (value.len() - value.trim_start().len());
+        let value_end = eq_index + 1 + value.trim_end().len();
+
+        if key.is_empty() {
+            None
+        } else {
+            Some((key, value_start, value_end.max(value_start)))
+        }
+    }
+
+    /// Joins the parts of a dotted TOML key, removing whitespace and quotes.
+    fn toml_key_normalize(key: &str) -> String {
+        key.split('.')
+            .map(|part| part.trim().trim_matches(|c| c == '"' || c == '\''))
+            .collect::<Vec<&str>>()
+            .join(".")
+    }
+
+    /// Returns the length of a TOML value, excluding any trailing comment.
+    fn toml_value_len(value: &str) -> usize {
+        let mut quote = None;
+        let mut escaped = false;
+        for (index, c) in value.char_indices() {
+            match (quote, c) {
+                (Some('"'), '\\') if !escaped => {
+                    escaped = true;
+                    continue;
+                }
+                (Some(q), c) if c == q && !escaped => quote = None,
+                (None, '"') | (None, '\'') => quote = Some(c),
+                (None, '#') => return value[..index].trim_end().len(),
+                _ => {}
+            }
+            escaped = false;
+        }
+
+        value.len()
+    }
+
+    /// Returns the start and end offsets of the authority of a URL.
+    fn url_authority_range(&self) -> (usize, usize) {
+        let scheme_end = self.source.find("://").map(|index| index + 3).unwrap_or(0);
+        let rest = &self.source[scheme_end..];
+        let authority_len = rest
+            .find(|c| matches!(c, '/' | '?' | '#'))
+            .unwrap_or_else(|| rest.len());
+        let user_info_len = rest[..authority_len]
+            .rfind('@')
+            .map(|index| index + 1)
+            .unwrap_or(0);
+
+        (scheme_end + user_info_len, scheme_end + authority_len)
+    }
+
+    /// Returns the length of the host within a URL authority.
+    fn url_host_len(authority: &str) -> usize {
+        if authority.starts_with('[') {
+            authority
+                .find(']')
+                .map(|index| index + 1)
+                .unwrap_or_else(|| authority.len())
+        } else {
+            authority.find(':').unwrap_or_else(|| authority.len())
+        }
+    }
+
+    /// Returns the start and end offsets of the JSON value at `tokens`, with
+    /// the value beginning at or after `pos`.
+    fn json_value_find(&self, pos: usize, tokens: &[String]) -> Option<(usize, usize)> {
+        let bytes = self.source.as_bytes();
+        let mut pos = self.json_whitespace_skip(pos);
+
+        let (token, tokens_remaining) = match tokens.split_first() {
+            Some(token_and_remaining) => token_and_remaining,
+            None => return self.json_value_end(pos).map(|end| (pos, end)),
+        };
+
+        match *bytes.get(pos)? {
+            b'{' => loop {
+                pos = self.json_whitespace_skip(pos + 1);
+                if *bytes.get(pos)? != b'"' {
+                    return None;
+                }
+                let key_end = self.json_string_end(pos)?;
+                let key = &self.source[pos + 1..key_end - 1];
+
+                pos = self.json_whitespace_skip(key_end);
+                if *bytes.get(pos)? != b':' {
+                    return None;
+                }
+                pos = self.json_whitespace_skip(pos + 1);
+
+                if key == token {
+                    return self.json_value_find(pos, tokens_remaining);
+                }
+
+                pos = self.json_whitespace_skip(self.json_value_end(pos)?);
+                if *bytes.get(pos)? != b',' {
+                    return None;
+                }
+            },
+            b'[' => {
+                let index = token.parse::<usize>().ok()?;
+                pos += 1;
+                for _ in 0..index {
+                    pos = self.json_whitespace_skip(pos);
+                    pos = self.json_whitespace_skip(self.json_value_end(pos)?);
+                    if *bytes.get(pos)? != b',' {
+                        return None;
+                    }
+                    pos += 1;
+                }
+
+                pos = self.json_whitespace_skip(pos);
+                if *bytes.get(pos)? == b']' {
+                    None
+                } else {
+                    self.json_value_find(pos, tokens_remaining)
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the offset after the JSON value that begins at `pos`.
+    fn json_value_end(&self, pos: usize) -> Option<usize> {
+        let bytes = self.source.as_bytes();
+        match *bytes.get(pos)? {
+            b'"' => self.json_string_end(pos),
+            b'{' | b'[' => {
+                let mut depth = 0usize;
+                let mut index = pos;
+                while index < bytes.len() {
+                    match bytes[index] {
+                        b'"' => {
+                            index = self.json_string_end(index)?;
+                            continue;
+                        }
+                        b'{' | b'[' => depth += 1,
+                        b'}' | b']' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                return Some(index + 1);
+                            }
+                        }
+                        _ => {}
+                    }
+                    index += 1;
+                }
+                None
+            }
+            b',' | b'}' | b']' => None,
+            _ => {
+                let len = bytes[pos..]
+                    .iter()
+                    .position(|b| matches!(b, b',' | b'}' | b']') || b.is_ascii_whitespace())
+                    .unwrap_or(bytes.len() - pos);
+                Some(pos + len)
+            }
+        }
+    }
+
+    /// Returns the offset after the JSON string that begins at `pos`.
+    fn json_string_end(&self, pos: usize) -> Option<usize> {
+        let bytes = self.source.as_bytes();
+        let mut index = pos + 1;
+        while index < bytes.len() {
+            match bytes[index] {
+                b'\\' => index += 2,
+                b'"' => return Some(index + 1),
+                _ => index += 1,
+            }
+        }
+        None
+    }
+
+    /// Returns the offset of the first non-whitespace byte at or after `pos`.
+    fn json_whitespace_skip(&self, pos: usize) -> usize {
+        let bytes = self.source.as_bytes();
+        pos + bytes
+            .get(pos..)
+            .map(|rest| rest.iter().take_while(|b| b.is_ascii_whitespace()).count())
+            .unwrap_or(0)
+    }
+
+    fn span(start: usize, end: usize) -> Span {
+        Span::new(start as u32, end as u32)
+    }
+}
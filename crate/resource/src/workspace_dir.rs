@@ -4,6 +4,8 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use crate::{WorkspacePath, WorkspacePathError};
+
 /// Base directory of the workspace.
 ///
 /// Given a workspace lives in `workspace_dir`, it is natural for users to
@@ -17,6 +19,17 @@ impl WorkspaceDir {
     pub fn new(path: PathBuf) -> Self {
         Self(path)
     }
+
+    /// Returns the given path resolved against this directory, rejecting paths
+    /// that would leave it.
+    ///
+    /// See [`WorkspacePath::resolve`] for details.
+    pub fn path_resolve<P>(&self, path: P) -> Result<WorkspacePath, WorkspacePathError>
+    where
+        P: AsRef<Path>,
+    {
+        WorkspacePath::resolve(self, path)
+    }
 }
 
 impl AsRef<OsStr> for WorkspaceDir {
@@ -0,0 +1,85 @@
+use std::{
+    ffi::OsStr,
+    ops::Deref,
+    path::{Component, Path, PathBuf},
+};
+
+use crate::{WorkspaceDir, WorkspacePathError};
+
+/// Path that is within the workspace directory.
+///
+/// This is resolved from a user supplied path, such as one from configuration,
+/// so that stations only read and write files within the workspace. Paths are
+/// resolved lexically -- symlinks within the workspace are not followed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WorkspacePath(PathBuf);
+
+impl WorkspacePath {
+    /// Returns the given path resolved against the workspace directory.
+    ///
+    /// Relative paths are resolved relative to the workspace directory, and
+    /// absolute paths must be within it. `.` and `..` components are
+    /// resolved, and an error is returned if the path would leave the
+    /// workspace directory.
+    ///
+    /// # Parameters
+    ///
+    /// * `workspace_dir`: Base directory of the workspace.
+    /// * `path`: User supplied path to resolve.
+    pub fn resolve<P>(workspace_dir: &WorkspaceDir, path: P) -> Result<Self, WorkspacePathError>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let path_relative = if path.is_absolute() {
+            path.strip_prefix(&**workspace_dir)
+                .map_err(|_| WorkspacePathError(path.to_path_buf()))?
+        } else {
+            path
+        };
+
+        let mut path_resolved = workspace_dir.to_path_buf();
+        let mut depth = 0usize;
+        for component in path_relative.components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if depth == 0 {
+                        return Err(WorkspacePathError(path.to_path_buf()));
+                    }
+                    depth -= 1;
+                    path_resolved.pop();
+                }
+                Component::Normal(segment) => {
+                    depth += 1;
+                    path_resolved.push(segment);
+                }
+                Component::Prefix(_) | Component::RootDir => {
+                    return Err(WorkspacePathError(path.to_path_buf()));
+                }
+            }
+        }
+
+        Ok(Self(path_resolved))
+    }
+}
+
+impl AsRef<OsStr> for WorkspacePath {
+    fn as_ref(&self) -> &OsStr {
+        self.0.as_ref()
+    }
+}
+
+impl AsRef<Path> for WorkspacePath {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Deref for WorkspacePath {
+    type Target = Path;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
@@ -0,0 +1,21 @@
+use std::{fmt, path::PathBuf};
+
+/// Error representing a path that resolves outside the workspace directory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WorkspacePathError(pub PathBuf);
+
+impl fmt::Display for WorkspacePathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Path `{}` resolves outside the workspace directory.",
+            self.0.display()
+        )
+    }
+}
+
+impl std::error::Error for WorkspacePathError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        None
+    }
+}
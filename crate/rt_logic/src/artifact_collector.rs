@@ -0,0 +1,130 @@
+use std::{
+    fs, io,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+use choochoo_cfg_model::{
+    indexmap::IndexMap,
+    rt::{StationArtifact, StationRtId},
+    StationSpecs,
+};
+use choochoo_resource::ProfileHistoryDir;
+use choochoo_rt_model::Error;
+use serde_json::{json, Value};
+
+/// Copies artifacts registered by stations into the profile history
+/// directory, alongside an index file:
+///
+/// ```text
+/// ${workspace}/target/.history/${profile}/${execution}/artifacts/index.json
+/// ${workspace}/target/.history/${profile}/${execution}/artifacts/${station_id}/${file_name}
+/// ```
+///
+/// The index lists each artifact's station ID, label, original path, and path
+/// relative to the artifacts directory. Artifacts of the same station with the
+/// same file name overwrite each other.
+#[derive(Debug)]
+pub struct ArtifactCollector<E>(PhantomData<E>);
+
+impl<E> ArtifactCollector<E>
+where
+    E: 'static,
+{
+    /// Name of the artifacts directory within the execution directory.
+    pub const DIR_NAME: &'static str = "artifacts";
+    /// Name of the index file within the artifacts directory.
+    pub const INDEX_FILE_NAME: &'static str = "index.json";
+
+    /// Returns the path to the artifacts directory of an execution.
+    ///
+    /// # Parameters
+    ///
+    /// * `profile_history_dir`: Directory containing the execution directory.
+    /// * `execution`: Name of the execution directory.
+    pub fn dir(profile_history_dir: &ProfileHistoryDir, execution: &str) -> PathBuf {
+        profile_history_dir.join(execution).join(Self::DIR_NAME)
+    }
+
+    /// Copies each station's artifacts into the execution's artifacts
+    /// directory, and writes the index.
+    ///
+    /// Returns the artifacts directory, or `None` if no station registered an
+    /// artifact.
+    ///
+    /// # Parameters
+    ///
+    /// * `profile_history_dir`: Directory to create the execution directory in.
+    /// * `execution`: Name of the execution directory.
+    /// * `station_specs`: Stations in the destination.
+    /// * `station_artifacts`: Artifacts registered by each station.
+    pub fn collect(
+        profile_history_dir: &ProfileHistoryDir,
+        execution: &str,
+        station_specs: &StationSpecs<E>,
+        station_artifacts: &IndexMap<StationRtId, Vec<StationArtifact>>,
+    ) -> Result<Option<PathBuf>, Error<E>> {
+        if station_artifacts.values().all(Vec::is_empty) {
+            return Ok(None);
+        }
+
+        let artifacts_dir = Self::dir(profile_history_dir, execution);
+        let index_entries = station_artifacts
+            .iter()
+            .flat_map(|(station_rt_id, artifacts)| {
+                let station_id = station_specs[*station_rt_id].id();
+                artifacts.iter().map(move |artifact| (station_id, artifact))
+            })
+            .map(|(station_id, artifact)| {
+                let file_name = artifact
+                    .path()
+                    .file_name()
+                    .map(Path::new)
+                    .unwrap_or_else(|| Path::new(Self::DIR_NAME));
+                let path_relative = Path::new(&***station_id).join(file_name);
+
+                Self::artifact_copy(artifact.path(), &artifacts_dir.join(&path_relative)).map_err(
+                    |error| Error::ArtifactCopy {
+                        station_id: station_id.clone(),
+                        path: artifact.path().to_path_buf(),
+                        error,
+                    },
+                )?;
+
+                Ok(json!({
+                    "station_id": &***station_id,
+                    "label": artifact.label(),
+                    "source": artifact.path().display().to_string(),
+                    "path": path_relative.display().to_string(),
+                }))
+            })
+            .collect::<Result<Vec<Value>, Error<E>>>()?;
+
+        let index_path = artifacts_dir.join(Self::INDEX_FILE_NAME);
+        fs::create_dir_all(&artifacts_dir)
+            .and_then(|()| fs::write(&index_path, Value::Array(index_entries).to_string()))
+            .map_err(|error| Error::ArtifactIndexWrite {
+                path: index_path,
+                error,
+            })?;
+
+        Ok(Some(artifacts_dir))
+    }
+
+    /// Copies an artifact file, or a directory and its contents.
+    fn artifact_copy(src: &Path, dest: &Path) -> io::Result<()> {
+        if let Some(dest_parent) = dest.parent() {
+            fs::create_dir_all(dest_parent)?;
+        }
+
+        if src.is_dir() {
+            fs::create_dir_all(dest)?;
+            fs::read_dir(src)?.try_for_each(|dir_entry| {
+                let dir_entry = dir_entry?;
+                Self::artifact_copy(&dir_entry.path(), &dest.join(dir_entry.file_name()))
+            })
+        } else {
+            fs::copy(src, dest).map(|_| ())
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, BufRead, BufReader, ErrorKind, Write},
+    marker::PhantomData,
+    path::PathBuf,
+};
+
+use choochoo_resource::ProfileHistoryDir;
+use choochoo_rt_model::{AuditEntry, AuditQuery, Error};
+
+/// Append-only log of the work and clean functions invoked on stations.
+///
+/// Each [`AuditEntry`] is written as one line of JSON to:
+///
+/// ```text
+/// ${workspace}/target/.history/${profile}/audit.jsonl
+/// ```
+///
+/// Entries are appended as each station's visit ends, so the log records
+/// visits that completed before a crash. Visits that are interrupted before
+/// the station's function returns are not recorded.
+#[derive(Debug)]
+pub struct AuditLog<E>(PhantomData<E>);
+
+impl<E> AuditLog<E>
+where
+    E: 'static,
+{
+    /// Name of the audit log file in the profile history directory.
+    pub const FILE_NAME: &'static str = "audit.jsonl";
+
+    /// Returns the path to the audit log in the profile history directory.
+    pub fn path(profile_history_dir: &ProfileHistoryDir) -> PathBuf {
+        profile_history_dir.join(Self::FILE_NAME)
+    }
+
+    /// Appends an entry to the audit log, creating the log if it does not
+    /// exist.
+    pub fn append(
+        profile_history_dir: &ProfileHistoryDir,
+        audit_entry: &AuditEntry,
+    ) -> io::Result<()> {
+        let mut audit_entry_serialized = serde_json::to_vec(audit_entry)?;
+        audit_entry_serialized.push(b'\n');
+
+        fs::create_dir_all(profile_history_dir)?;
+        let mut audit_log_file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(Self::path(profile_history_dir))?;
+
+        // A single write per entry, so concurrent appends are not interleaved.
+        audit_log_file.write_all(&audit_entry_serialized)?;
+        audit_log_file.sync_data()
+    }
+
+    /// Returns the entries in the audit log that match the query, in the order
+    /// they were appended.
+    ///
+    /// Returns an empty list if the audit log does not exist.
+    pub fn query(
+        profile_history_dir: &ProfileHistoryDir,
+        audit_query: &AuditQuery,
+    ) -> Result<Vec<AuditEntry>, Error<E>> {
+        let path = Self::path(profile_history_dir);
+        let audit_log_file = match fs::File::open(&path) {
+            Ok(audit_log_file) => audit_log_file,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(Error::AuditLogRead { path, error }),
+        };
+
+        BufReader::new(audit_log_file)
+            .lines()
+            .enumerate()
+            .filter(|(_index, line)| line.as_ref().map_or(true, |line| !line.trim().is_empty()))
+            .map(|(index, line)| {
+                let line = line.map_err(|error| Error::AuditLogRead {
+                    path: path.clone(),
+                    error,
+                })?;
+                serde_json::from_str::<AuditEntry>(&line).map_err(|error| {
+                    Error::AuditEntryDeserialize {
+                        path: path.clone(),
+                        line_number: index + 1,
+                        error,
+                    }
+                })
+            })
+            .filter(|audit_entry| {
+                audit_entry
+                    .as_ref()
+                    .map_or(true, |audit_entry| audit_query.matches(audit_entry))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,20 @@
+use std::time::Duration;
+
+/// Fault to inject into a station's visit, returned by a [`ChaosPolicy`].
+///
+/// [`ChaosPolicy`]: crate::ChaosPolicy
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChaosAction<E> {
+    /// Continue visiting the station as normal.
+    Proceed,
+    /// Wait for the given duration before continuing.
+    ///
+    /// The delay counts towards the train's deadline.
+    Delay(Duration),
+    /// Fail the station with the given error instead of running the function.
+    Fail(E),
+    /// Stop visiting the station, marking it [`OpStatus::WorkInterrupted`].
+    ///
+    /// [`OpStatus::WorkInterrupted`]: choochoo_cfg_model::rt::OpStatus::WorkInterrupted
+    Cancel,
+}
@@ -0,0 +1,18 @@
+/// Boundary within a station's visit at which a [`ChaosPolicy`] is consulted.
+///
+/// [`ChaosPolicy`]: crate::ChaosPolicy
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ChaosPoint {
+    /// Before the station's check function is run.
+    ///
+    /// Failures injected here are recorded as [`OpStatus::CheckFail`].
+    ///
+    /// [`OpStatus::CheckFail`]: choochoo_cfg_model::rt::OpStatus::CheckFail
+    Check,
+    /// Before the station's work function is run.
+    ///
+    /// Failures injected here are recorded as [`OpStatus::WorkFail`].
+    ///
+    /// [`OpStatus::WorkFail`]: choochoo_cfg_model::rt::OpStatus::WorkFail
+    Work,
+}
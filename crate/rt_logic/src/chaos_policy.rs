@@ -0,0 +1,100 @@
+use std::{fmt, sync::Arc};
+
+use choochoo_cfg_model::{rt::VisitOp, StationSpec};
+
+use crate::{timer, ChaosAction, ChaosPoint, Timer};
+
+// **Note:** `Clone` and `Debug` are manually implemented to avoid the trait
+// bound on `E`.
+/// Injects failures, delays, or cancellations into station visits.
+///
+/// This is intended for testing that flows recover correctly under partial
+/// failure, such as verifying that dependent stations are marked
+/// [`OpStatus::ParentFail`], and that clean still removes what was created.
+///
+/// The policy is consulted at each [`ChaosPoint`] of each station that is
+/// visited, and returns the [`ChaosAction`] to take.
+///
+/// [`OpStatus::ParentFail`]: choochoo_cfg_model::rt::OpStatus::ParentFail
+#[allow(clippy::type_complexity)]
+pub struct ChaosPolicy<E> {
+    /// Returns the fault to inject for a station at a chaos point.
+    f: Arc<dyn Fn(&StationSpec<E>, VisitOp, ChaosPoint) -> ChaosAction<E> + Send + Sync>,
+    /// Waits for injected delays to elapse.
+    timer: Option<Arc<dyn Timer>>,
+}
+
+impl<E> ChaosPolicy<E> {
+    /// Returns a new `ChaosPolicy`.
+    ///
+    /// # Parameters
+    ///
+    /// * `f`: Returns the fault to inject for a station at a chaos point.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(&StationSpec<E>, VisitOp, ChaosPoint) -> ChaosAction<E> + Send + Sync + 'static,
+    {
+        Self {
+            f: Arc::new(f),
+            timer: timer::timer_default(),
+        }
+    }
+
+    /// Sets the timer that waits for injected delays to elapse.
+    ///
+    /// The train sets this to its own timer.
+    pub(crate) fn timer_set(&mut self, timer: Option<Arc<dyn Timer>>) {
+        self.timer = timer;
+    }
+
+    /// Returns the action to take for a station at the given chaos point.
+    pub fn action(
+        &self,
+        station_spec: &StationSpec<E>,
+        visit_op: VisitOp,
+        chaos_point: ChaosPoint,
+    ) -> ChaosAction<E> {
+        (self.f)(station_spec, visit_op, chaos_point)
+    }
+
+    /// Returns the action to take for a station, after any delay has elapsed.
+    ///
+    /// When there is no policy, or the policy returns [`ChaosAction::Delay`],
+    /// this returns [`ChaosAction::Proceed`]. Delays are skipped if there is no
+    /// timer.
+    pub(crate) async fn inject(
+        chaos_policy: Option<&Self>,
+        station_spec: &StationSpec<E>,
+        visit_op: VisitOp,
+        chaos_point: ChaosPoint,
+    ) -> ChaosAction<E> {
+        let chaos_action = chaos_policy
+            .map(|chaos_policy| chaos_policy.action(station_spec, visit_op, chaos_point))
+            .unwrap_or(ChaosAction::Proceed);
+
+        if let ChaosAction::Delay(duration) = chaos_action {
+            let timer = chaos_policy.and_then(|chaos_policy| chaos_policy.timer.as_ref());
+            if let Some(timer) = timer {
+                timer.sleep(duration).await;
+            }
+            ChaosAction::Proceed
+        } else {
+            chaos_action
+        }
+    }
+}
+
+impl<E> Clone for ChaosPolicy<E> {
+    fn clone(&self) -> Self {
+        Self {
+            f: Arc::clone(&self.f),
+            timer: self.timer.clone(),
+        }
+    }
+}
+
+impl<E> fmt::Debug for ChaosPolicy<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ChaosPolicy(fn(&StationSpec<E>, VisitOp, ChaosPoint) -> ChaosAction<E>)")
+    }
+}
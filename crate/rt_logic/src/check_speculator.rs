@@ -0,0 +1,206 @@
+use std::{
+    collections::HashSet,
+    fmt,
+    num::NonZeroUsize,
+    sync::{Mutex, PoisonError},
+};
+
+use choochoo_cfg_model::{
+    daggy::Walker,
+    fn_graph::FnGraphBuilder,
+    rt::{CheckStatusesSpeculative, OpStatus, StationMutRef, StationRtId, TrainResources, VisitOp},
+};
+use choochoo_rt_model::Destination;
+use futures::{
+    future::{self, FutureExt},
+    stream::StreamExt,
+};
+
+use crate::{train::TrainStop, ChaosPolicy, CreateDriver, StationQueue};
+
+/// Runs the create check functions of stations ahead of their parents.
+///
+/// Only stations that enable [`StationSpecBuilder::with_check_speculative`]
+/// and are waiting for their parents are checked. Checks run alongside the
+/// visits of other stations, and their results are stored in
+/// [`CheckStatusesSpeculative`] as soon as each completes, to be used by the
+/// [`CreateDriver`] in place of running the check again.
+///
+/// Checks are run through [`CreateDriver`], so panics are caught, the chaos
+/// policy is consulted, and stations whose dependencies or file inputs are not
+/// yet available, or whose spec version changed, are not checked ahead. They
+/// are also subject to the train's concurrency limit, resource limits, and
+/// deadline. Unlike [`FastPathStrat`], which only checks stations whose
+/// parents are already in their desired state, stations are checked before
+/// their parents' checks, and results are discarded when an ancestor's visit
+/// does anything other than find its work unnecessary.
+///
+/// [`CreateDriver`]: crate::CreateDriver
+/// [`FastPathStrat`]: crate::FastPathStrat
+/// [`StationSpecBuilder::with_check_speculative`]: choochoo_cfg_model::StationSpecBuilder::with_check_speculative
+#[derive(Debug)]
+pub(crate) struct CheckSpeculator {
+    /// Stations whose speculative check results are no longer valid.
+    stations_invalidated: Mutex<HashSet<StationRtId>>,
+}
+
+impl CheckSpeculator {
+    /// Returns a new `CheckSpeculator`.
+    pub(crate) fn new() -> Self {
+        Self {
+            stations_invalidated: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Runs the check functions of speculative stations concurrently.
+    ///
+    /// This is meant to run alongside the station visits. Stations that are
+    /// already being visited or are no longer waiting for their parents are not
+    /// checked ahead, and a station's visit waits for its speculative check to
+    /// complete before using the result.
+    ///
+    /// Stations whose check fails or panics are not recorded, so they are
+    /// checked again when they are visited. Neither are stations that are
+    /// invalidated while they are being checked. When the deadline is reached
+    /// or the train is stopped, the remaining stations are not checked ahead.
+    ///
+    /// # Parameters
+    ///
+    /// * `dest`: Destination whose stations to check.
+    /// * `train_resources`: Resources produced by setting up the stations.
+    /// * `station_queue`: Limits access to resources shared between stations.
+    /// * `train_stop`: Signals shared between stations to stop the train.
+    /// * `concurrency_max`: Maximum number of stations to check at once.
+    /// * `chaos_policy`: Injects faults before each check, if any.
+    pub(crate) async fn checks_run<E>(
+        &self,
+        dest: &Destination<E>,
+        train_resources: &TrainResources<E>,
+        station_queue: &StationQueue,
+        train_stop: &TrainStop,
+        concurrency_max: Option<NonZeroUsize>,
+        chaos_policy: Option<&ChaosPolicy<E>>,
+    ) where
+        E: fmt::Debug + Send + Sync + 'static,
+    {
+        // The speculative stations are placed in a graph without edges, so that
+        // they are streamed without waiting for their parents.
+        let mut fn_graph_builder = FnGraphBuilder::default();
+        dest.station_specs()
+            .iter_insertion()
+            .filter(|station_spec| station_spec.check_speculative())
+            .for_each(|station_spec| {
+                let _station_fn_id = fn_graph_builder.add_fn(station_spec.clone());
+            });
+        let station_specs_speculative = fn_graph_builder.build();
+        if station_specs_speculative.node_count() == 0 {
+            return;
+        }
+
+        let checks_fut = station_specs_speculative
+            .stream()
+            .for_each_concurrent(
+                concurrency_max.map(NonZeroUsize::get),
+                |station_spec| async move {
+                    if train_stop.is_stopped() || train_stop.is_deadline_exceeded() {
+                        return;
+                    }
+                    let mut station = match dest.try_station_mut_ref(station_spec) {
+                        Some(Ok(station)) => station,
+                        Some(Err(_)) | None => return,
+                    };
+                    if station.progress.op_status != OpStatus::ParentPending
+                        || self.is_invalidated(station.rt_id)
+                    {
+                        return;
+                    }
+
+                    let _permits = station_queue
+                        .acquire(&station.spec, station.rt_id, VisitOp::Create)
+                        .await;
+                    let check_status = CreateDriver::check_ahead(
+                        &mut station,
+                        train_resources,
+                        dest.file_system(),
+                        chaos_policy,
+                    )
+                    .await;
+
+                    // An ancestor may have completed while the check was running.
+                    if let Some(check_status) = check_status {
+                        if !self.is_invalidated(station.rt_id) {
+                            if let Ok(mut check_statuses_speculative) =
+                                train_resources.try_borrow_mut::<CheckStatusesSpeculative>()
+                            {
+                                check_statuses_speculative.insert(station.rt_id, check_status);
+                            }
+                        }
+                    }
+                },
+            )
+            .boxed_local();
+        let deadline_reached = train_stop.deadline_reached().boxed_local();
+        let _ = future::select(checks_fut, deadline_reached).await;
+    }
+
+    /// Removes the speculative check results of a station's descendants,
+    /// unless the station's work was unnecessary.
+    ///
+    /// Any other outcome, including failures, may have changed what the
+    /// descendants' checks observe, so their results are discarded and they
+    /// are not checked ahead again.
+    ///
+    /// # Parameters
+    ///
+    /// * `dest`: Destination with all the stations.
+    /// * `train_resources`: Resources with the speculative check results.
+    /// * `station`: Station whose visit has completed.
+    pub(crate) fn station_complete<E>(
+        &self,
+        dest: &Destination<E>,
+        train_resources: &TrainResources<E>,
+        station: &StationMutRef<'_, E>,
+    ) where
+        E: 'static,
+    {
+        if station.progress.op_status == OpStatus::WorkUnnecessary {
+            return;
+        }
+
+        let station_specs = dest.station_specs();
+        let mut stations_invalidated = self
+            .stations_invalidated
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let mut descendants = Vec::new();
+        let mut stations_to_walk = vec![station.rt_id];
+        while let Some(station_rt_id) = stations_to_walk.pop() {
+            station_specs
+                .children(station_rt_id)
+                .iter(station_specs)
+                .for_each(|(_edge, child_rt_id)| {
+                    if stations_invalidated.insert(child_rt_id) {
+                        descendants.push(child_rt_id);
+                        stations_to_walk.push(child_rt_id);
+                    }
+                });
+        }
+
+        if let Ok(mut check_statuses_speculative) =
+            train_resources.try_borrow_mut::<CheckStatusesSpeculative>()
+        {
+            descendants.iter().for_each(|descendant_rt_id| {
+                check_statuses_speculative.remove(descendant_rt_id);
+            });
+        }
+    }
+
+    /// Returns whether the station's speculative check results are no longer
+    /// valid.
+    fn is_invalidated(&self, station_rt_id: StationRtId) -> bool {
+        self.stations_invalidated
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains(&station_rt_id)
+    }
+}
@@ -1,7 +1,14 @@
-use std::{fmt, marker::PhantomData};
+use std::{fmt, io, marker::PhantomData, path::Path};
 
-use choochoo_cfg_model::rt::{CheckStatus, StationMutRef, TrainResources};
-use choochoo_rt_model::{error::StationSpecError, CleanEnsureOutcomeErr, CleanEnsureOutcomeOk};
+use choochoo_cfg_model::rt::{
+    CheckStatus, StationInputsDigests, StationMutRef, StationVersionRecord, TrainResources, VisitOp,
+};
+use choochoo_rt_model::{
+    error::StationSpecError, CleanEnsureOutcomeErr, CleanEnsureOutcomeOk, FileSystem,
+};
+use tokio::sync::Semaphore;
+
+use crate::{ChaosAction, ChaosPoint, ChaosPolicy, PanicCatcher};
 
 /// Logic that conditionally executes an operation's clean functions.
 #[derive(Debug)]
@@ -22,16 +29,68 @@ where
     /// 2. If it is, return `Ok`.
     /// 3. If it isn't, run the clean function.
     /// 4. If it fails, return the error.
-    /// 5. If it succeeds, check that the station is in the desired state.
+    /// 5. If it succeeds, remove the recorded digest of the station's inputs,
+    ///    and check that the station is in the desired state.
     /// 6. If it isn't, store this as an error to return to the caller.
     /// 7. Return `Ok`.
+    ///
+    /// The recorded inputs digest and spec version are removed from the
+    /// station's directory through the given file system.
+    ///
+    /// If a work lane is provided, a permit is acquired from it before the
+    /// clean function is run, and held until the station is checked again.
+    ///
+    /// If a chaos policy is provided and the station has clean functions, it
+    /// is consulted before the first check, and before the work function is
+    /// run.
+    ///
+    /// If any of the station's clean functions panics, the panic is caught
+    /// and returned as [`CleanEnsureOutcomeErr::Panicked`].
     pub async fn ensure(
         station: &mut StationMutRef<'_, E>,
         train_resources: &TrainResources<E>,
+        file_system: &dyn FileSystem,
+        work_lane: Option<&Semaphore>,
+        chaos_policy: Option<&ChaosPolicy<E>>,
+    ) -> Result<CleanEnsureOutcomeOk, CleanEnsureOutcomeErr<E>>
+    where
+        E: From<StationSpecError>,
+    {
+        let ensure_fut = Self::ensure_unguarded(
+            station,
+            train_resources,
+            file_system,
+            work_lane,
+            chaos_policy,
+        );
+        let ensure_result = PanicCatcher::catch(ensure_fut).await;
+        match ensure_result {
+            Ok(ensure_outcome) => ensure_outcome,
+            Err(message) => Err(CleanEnsureOutcomeErr::Panicked(E::from(
+                StationSpecError::Panicked {
+                    id: station.spec.id().clone(),
+                    name: station.spec.name().to_string(),
+                    message,
+                    location: station.spec.location(),
+                },
+            ))),
+        }
+    }
+
+    /// Processes a station clean operation, without catching panics.
+    async fn ensure_unguarded(
+        station: &mut StationMutRef<'_, E>,
+        train_resources: &TrainResources<E>,
+        file_system: &dyn FileSystem,
+        work_lane: Option<&Semaphore>,
+        chaos_policy: Option<&ChaosPolicy<E>>,
     ) -> Result<CleanEnsureOutcomeOk, CleanEnsureOutcomeErr<E>>
     where
         E: From<StationSpecError>,
     {
+        if station.spec.station_op().clean_fns().is_some() {
+            Self::chaos_inject(station, chaos_policy, ChaosPoint::Check).await?;
+        }
         if let Some(check_fns) = station.clean_check(train_resources).await {
             let work_required = if let Some(check_status) = check_fns {
                 check_status
@@ -44,6 +103,12 @@ where
             };
 
             if work_required {
+                // The semaphore is never closed, so acquiring a permit does not fail.
+                let _work_permit = match work_lane {
+                    Some(work_lane) => work_lane.acquire().await.ok(),
+                    None => None,
+                };
+                Self::chaos_inject(station, chaos_policy, ChaosPoint::Work).await?;
                 station
                     .clean_visit(train_resources)
                     .await
@@ -51,6 +116,46 @@ where
                     .map_err(CleanEnsureOutcomeErr::VisitBorrowFail)?
                     .map_err(|error| CleanEnsureOutcomeErr::WorkFail { error })?;
 
+                // The next create visit should do the work regardless of
+                // whether the station's inputs have changed.
+                if !station.spec.inputs().is_empty() {
+                    let digest_path = StationInputsDigests::digest_path(station.dir);
+                    if let Err(error) = Self::file_remove(file_system, &digest_path).await {
+                        let id = station.spec.id().clone();
+                        let name = station.spec.name().to_string();
+                        let detail = error.to_string();
+                        let location = station.spec.location();
+                        return Ok(CleanEnsureOutcomeOk::Changed {
+                            station_spec_error: Some(StationSpecError::InputsDigestRecordFail {
+                                id,
+                                name,
+                                detail,
+                                location,
+                            }),
+                        });
+                    }
+                }
+
+                // The next create visit should not migrate state that was
+                // cleaned.
+                if station.spec.version().is_some() {
+                    let version_path = StationVersionRecord::version_path(station.dir);
+                    if let Err(error) = Self::file_remove(file_system, &version_path).await {
+                        let id = station.spec.id().clone();
+                        let name = station.spec.name().to_string();
+                        let detail = error.to_string();
+                        let location = station.spec.location();
+                        return Ok(CleanEnsureOutcomeOk::Changed {
+                            station_spec_error: Some(StationSpecError::VersionRecordFail {
+                                id,
+                                name,
+                                detail,
+                                location,
+                            }),
+                        });
+                    }
+                }
+
                 // After we visit, if the check function reports we still
                 // need to visit, then the visit function or the check
                 // function needs to be corrected.
@@ -68,7 +173,8 @@ where
                 let station_spec_error = if let Some(CheckStatus::WorkRequired) = check_status {
                     let id = station.spec.id().clone();
                     let name = station.spec.name().to_string();
-                    Some(StationSpecError::WorkRequiredAfterVisit { id, name })
+                    let location = station.spec.location();
+                    Some(StationSpecError::WorkNotIdempotent { id, name, location })
                 } else {
                     None
                 };
@@ -81,4 +187,35 @@ where
             Ok(CleanEnsureOutcomeOk::NothingToDo)
         }
     }
+
+    /// Removes a file from the station's directory.
+    ///
+    /// It is not an error if the file does not exist.
+    async fn file_remove(file_system: &dyn FileSystem, path: &Path) -> io::Result<()> {
+        match file_system.remove_file(path).await {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+            Ok(()) | Err(_) => Ok(()),
+        }
+    }
+
+    /// Returns an error if the chaos policy injects a failure or cancellation
+    /// at the given chaos point.
+    async fn chaos_inject(
+        station: &StationMutRef<'_, E>,
+        chaos_policy: Option<&ChaosPolicy<E>>,
+        chaos_point: ChaosPoint,
+    ) -> Result<(), CleanEnsureOutcomeErr<E>> {
+        let chaos_action =
+            ChaosPolicy::inject(chaos_policy, &station.spec, VisitOp::Clean, chaos_point).await;
+        match (chaos_action, chaos_point) {
+            (ChaosAction::Proceed | ChaosAction::Delay(_), _) => Ok(()),
+            (ChaosAction::Fail(error), ChaosPoint::Check) => {
+                Err(CleanEnsureOutcomeErr::CheckFail(error))
+            }
+            (ChaosAction::Fail(error), ChaosPoint::Work) => {
+                Err(CleanEnsureOutcomeErr::WorkFail { error })
+            }
+            (ChaosAction::Cancel, _) => Err(CleanEnsureOutcomeErr::Interrupted),
+        }
+    }
 }
@@ -6,18 +6,25 @@ use choochoo_cfg_model::{
 };
 use choochoo_rt_model::Destination;
 
-/// Updates the [`OpStatus`]es for all [`StationMutRef`]s.
+/// Updates the [`OpStatus`]es for all [`StationMutRef`]s when cleaning.
 ///
 /// The new op status is calculated based on the station's visit result
 /// and its predecessors' [`OpStatus`]es.
 ///
+/// When cleaning, stations are visited in reverse dependency order, so a
+/// station's *predecessors* are the stations that depend on it. A station is
+/// only cleaned after all of its dependents have been cleaned, and is not
+/// cleaned if any of its dependents failed to be cleaned.
+///
 /// # `OpStatus` State Machine
 ///
 /// ## `ParentPending` Stations
 ///
 /// * If all predecessors are `WorkSuccess`, switch to `OpQueued`.
-/// * If at least one predecessor has `WorkFailed` or `ParentFail`, switch to
-///   `ParentFail`.
+/// * If at least one predecessor has `WorkFailed`, `WorkInterrupted` or
+///   `ParentFail`, switch to `ParentFail`.
+/// * If a predecessor is `OpSkipped`, switch to `OpSkipped`.
+/// * If a predecessor is `DeadlineExceeded`, switch to `DeadlineExceeded`.
 ///
 /// ## `ParentFail` Stations
 ///
@@ -41,8 +48,14 @@ use choochoo_rt_model::Destination;
 ///
 /// No transitions.
 ///
+/// ## `OpSkipped` / `WorkInterrupted` / `DeadlineExceeded`
+///
+/// No transitions -- [`Train::reach`] sets these when the train stops early
+/// according to its [`ExecutionPolicy`] or deadline.
+///
 /// [`StationMutRef::visit`]: crate::cfg_model::rt::StationMutRef::visit
 /// [`StationMutRef`]: crate::cfg_model::rt::StationMutRef
+/// [`ExecutionPolicy`]: crate::ExecutionPolicy
 /// [`Train::reach`]: crate::Train::reach
 #[derive(Debug)]
 pub struct CleanOpStatusUpdater<E> {
@@ -56,8 +69,9 @@ where
 {
     /// Updates the [`OpStatus`]es for all [`StationMutRef`]s.
     ///
-    /// `ParentFail` transitions are propagated through to all later stations,
-    /// on the condition that the nodes are added in order.
+    /// Stations are updated in reverse dependency order, as returned by
+    /// [`Destination::clean_order`], so `ParentFail` transitions are propagated
+    /// through to all stations that are cleaned later.
     ///
     /// # Parameters
     ///
@@ -66,24 +80,19 @@ where
     ///
     /// [`StationMutRef`]: crate::cfg_model::rt::StationMutRef
     pub fn update(dest: &Destination<E>) {
-        let station_specs = dest.station_specs();
-        let station_id_to_rt_id = dest.station_id_to_rt_id();
-
-        station_specs.iter().for_each(|station_spec| {
-            if let Some(station_rt_id) = station_id_to_rt_id.get(station_spec.id()) {
-                let op_status_next = Self::op_status_next(dest, *station_rt_id);
+        dest.clean_order().into_iter().for_each(|station_rt_id| {
+            let op_status_next = Self::op_status_next(dest, station_rt_id);
 
-                if let Some(op_status_next) = op_status_next {
-                    let station_progress = dest
-                        .station_progresses()
-                        .get(station_rt_id)
-                        .map(|station_progress| station_progress.borrow_mut());
+            if let Some(op_status_next) = op_status_next {
+                let station_progress = dest
+                    .station_progresses()
+                    .get(&station_rt_id)
+                    .map(|station_progress| station_progress.borrow_mut());
 
-                    if let Some(mut station_progress) = station_progress {
-                        station_progress.op_status = op_status_next
-                    }
-                };
-            }
+                if let Some(mut station_progress) = station_progress {
+                    station_progress.op_status = op_status_next
+                }
+            };
         });
     }
 
@@ -93,6 +102,10 @@ where
     /// `ParentFail` transitions are propagated through to all later stations,
     /// on the condition that the nodes are added in order.
     ///
+    /// This reads the status of every predecessor of each updated station. When
+    /// updating stations as each one completes, [`OpStatusPropagator`] only
+    /// reads the completed station's status.
+    ///
     /// # Parameters
     ///
     /// * `dest`: `Destination` with all the stations and their progress
@@ -100,6 +113,7 @@ where
     /// * `station_rt_id`: Runtime ID of the predecessor station, whose
     ///   successors to update.
     ///
+    /// [`OpStatusPropagator`]: crate::OpStatusPropagator
     /// [`StationMutRef`]: crate::cfg_model::rt::StationMutRef
     pub fn update_successors(dest: &Destination<E>, station_rt_id: StationRtId) {
         let station_specs = dest.station_specs();
@@ -150,7 +164,12 @@ where
                     | OpStatus::ParentFail
                     | OpStatus::WorkSuccess
                     | OpStatus::WorkUnnecessary
-                    | OpStatus::WorkFail => None,
+                    | OpStatus::WorkFail
+                    | OpStatus::VerifyFail
+                    | OpStatus::Panicked
+                    | OpStatus::OpSkipped
+                    | OpStatus::WorkInterrupted
+                    | OpStatus::DeadlineExceeded => None,
                 }
             })
     }
@@ -188,10 +207,15 @@ where
                         OpStatus::CheckFail
                         | OpStatus::OpQueued
                         | OpStatus::WorkFail
+                        | OpStatus::VerifyFail
+                        | OpStatus::Panicked
                         | OpStatus::ParentPending
                         | OpStatus::WorkUnnecessary
                         | OpStatus::WorkSuccess
-                        | OpStatus::WorkInProgress => unreachable!(
+                        | OpStatus::WorkInProgress
+                        | OpStatus::OpSkipped
+                        | OpStatus::WorkInterrupted
+                        | OpStatus::DeadlineExceeded => unreachable!(
                             "Parent station status should not be {:?} during setup phase. This is a bug.",
                             predecessor_station_progress.op_status
                         ),
@@ -251,9 +275,20 @@ where
                             // If predecessor / ancestor has failed, indicate it in this station.
                             OpStatus::CheckFail
                             | OpStatus::WorkFail
-                            | OpStatus::ParentFail => {
+                            | OpStatus::VerifyFail
+                            | OpStatus::Panicked
+                            | OpStatus::ParentFail
+                            | OpStatus::WorkInterrupted => {
                                 return Err(Some(OpStatus::ParentFail));
                             }
+                            // If parent / ancestor was skipped, this station is also skipped.
+                            OpStatus::OpSkipped => {
+                                return Err(Some(OpStatus::OpSkipped));
+                            }
+                            // If parent / ancestor missed the deadline, so does this station.
+                            OpStatus::DeadlineExceeded => {
+                                return Err(Some(OpStatus::DeadlineExceeded));
+                            }
                             // Don't change `OpStatus` if predecessor is on any other `OpStatus`.
                             OpStatus::ParentPending
                             | OpStatus::OpQueued
@@ -1,7 +1,17 @@
-use std::{fmt, marker::PhantomData};
+use std::{fmt, io, marker::PhantomData};
 
-use choochoo_cfg_model::rt::{CheckStatus, StationMutRef, TrainResources};
-use choochoo_rt_model::{error::StationSpecError, CreateEnsureOutcomeErr, CreateEnsureOutcomeOk};
+use choochoo_cfg_model::rt::{
+    CheckStatus, CheckStatusesSpeculative, ResIds, StationErrorPhase, StationInputsDigests,
+    StationMutRef, StationResources, StationVersionRecord, TrainResources, VisitOp,
+};
+use choochoo_rt_model::{
+    error::StationSpecError, CreateEnsureOutcomeErr, CreateEnsureOutcomeOk, FileSystem,
+};
+use tokio::sync::Semaphore;
+
+#[cfg(feature = "native")]
+use crate::ExternalDependencyResolver;
+use crate::{ChaosAction, ChaosPoint, ChaosPolicy, FileInputResolver, PanicCatcher};
 
 /// Logic that conditionally executes an operation's create functions.
 #[derive(Debug)]
@@ -19,12 +29,38 @@ where
     /// The algorithm is as follows:
     ///
     /// 1. Check whether the station is already in the desired state.
-    /// 2. If it is, return `Ok`.
+    /// 2. If it is, run the output function if there is one, and return `Ok`.
     /// 3. If it isn't, run the operation function.
     /// 4. If it fails, return the error.
-    /// 5. If it succeeds, check that the station is in the desired state.
-    /// 6. If it isn't, store this as an error to return to the caller.
-    /// 7. Return `Ok`.
+    /// 5. If it succeeds, run the verify function if there is one, and return
+    ///    the error if it fails.
+    /// 6. Record the digest of the station's inputs, and check that the station
+    ///    is in the desired state.
+    /// 7. If it isn't, store this as an error to return to the caller.
+    /// 8. Return `Ok`.
+    ///
+    /// Before the first check, the station's external dependencies and file
+    /// inputs are resolved, and the check fails if any of them is not
+    /// satisfied.
+    ///
+    /// The inputs digest and spec version are read from and written to the
+    /// station's directory through the given file system.
+    ///
+    /// If the station's spec version differs from the version recorded by its
+    /// last successful visit, the station's migration function is run before
+    /// the first check. If it has no migration function, the work is required.
+    /// The spec version is recorded after the work succeeds, or is found to be
+    /// unnecessary.
+    ///
+    /// If a work lane is provided, a permit is acquired from it before the
+    /// work function is run, and held until the work is verified.
+    ///
+    /// If a chaos policy is provided, it is consulted before the first check,
+    /// and before the work function is run.
+    ///
+    /// If any of the station's functions panics, the panic is caught and
+    /// returned as [`CreateEnsureOutcomeErr::Panicked`], with the phase of the
+    /// function that panicked.
     ///
     /// # Implementation Note
     ///
@@ -36,12 +72,65 @@ where
     pub async fn ensure(
         station: &mut StationMutRef<'_, E>,
         train_resources: &TrainResources<E>,
+        file_system: &dyn FileSystem,
+        work_lane: Option<&Semaphore>,
+        chaos_policy: Option<&ChaosPolicy<E>>,
     ) -> Result<CreateEnsureOutcomeOk, CreateEnsureOutcomeErr<E>>
     where
         E: From<StationSpecError>,
     {
-        let work_required = if let Some(check_status) = station.create_check(train_resources).await
+        let mut phase = StationErrorPhase::Check;
+        let ensure_fut = Self::ensure_unguarded(
+            station,
+            train_resources,
+            file_system,
+            work_lane,
+            chaos_policy,
+            &mut phase,
+        );
+        let ensure_result = PanicCatcher::catch(ensure_fut).await;
+        match ensure_result {
+            Ok(ensure_outcome) => ensure_outcome,
+            Err(message) => Err(CreateEnsureOutcomeErr::Panicked {
+                phase,
+                error: E::from(StationSpecError::Panicked {
+                    id: station.spec.id().clone(),
+                    name: station.spec.name().to_string(),
+                    message,
+                    location: station.spec.location(),
+                }),
+            }),
+        }
+    }
+
+    /// Processes a station create operation, without catching panics.
+    ///
+    /// `phase` is updated before each station function is run, so that it
+    /// holds the phase of the function that panicked if one does.
+    async fn ensure_unguarded(
+        station: &mut StationMutRef<'_, E>,
+        train_resources: &TrainResources<E>,
+        file_system: &dyn FileSystem,
+        work_lane: Option<&Semaphore>,
+        chaos_policy: Option<&ChaosPolicy<E>>,
+        phase: &mut StationErrorPhase,
+    ) -> Result<CreateEnsureOutcomeOk, CreateEnsureOutcomeErr<E>>
+    where
+        E: From<StationSpecError>,
+    {
+        Self::dependencies_resolve(station, train_resources)
+            .await
+            .map_err(|station_spec_error| {
+                CreateEnsureOutcomeErr::CheckFail(E::from(station_spec_error))
+            })?;
+        Self::chaos_inject(station, chaos_policy, ChaosPoint::Check).await?;
+        let work_required = if Self::version_migrate(station, train_resources, file_system).await? {
+            // The spec version changed, and there is no migration function.
+            true
+        } else if let Some(check_status) = Self::check_status_speculative(station, train_resources)
         {
+            check_status == CheckStatus::WorkRequired
+        } else if let Some(check_status) = station.create_check(train_resources).await {
             check_status
                 .map_err(CreateEnsureOutcomeErr::CheckBorrowFail)?
                 .map_err(CreateEnsureOutcomeErr::CheckFail)?
@@ -52,15 +141,66 @@ where
         };
 
         if work_required {
+            // The semaphore is never closed, so acquiring a permit does not fail.
+            let _work_permit = match work_lane {
+                Some(work_lane) => work_lane.acquire().await.ok(),
+                None => None,
+            };
+            Self::chaos_inject(station, chaos_policy, ChaosPoint::Work).await?;
+            *phase = StationErrorPhase::Work;
             let res_ids = station
                 .create_visit(train_resources)
                 .await
                 .map_err(CreateEnsureOutcomeErr::VisitBorrowFail)?
                 .map_err(|(res_ids, error)| CreateEnsureOutcomeErr::WorkFail { res_ids, error })?;
 
+            // Verify the resources before the work is recorded as done, so that
+            // it is attempted again if verification fails.
+            *phase = StationErrorPhase::Verify;
+            match station.create_verify(train_resources).await {
+                None | Some(Ok(Ok(()))) => {}
+                Some(Ok(Err(error))) => {
+                    return Err(CreateEnsureOutcomeErr::VerifyFail { res_ids, error });
+                }
+                Some(Err(borrow_fail)) => {
+                    return Err(CreateEnsureOutcomeErr::VerifyBorrowFail {
+                        res_ids,
+                        borrow_fail,
+                    });
+                }
+            }
+
+            // Record the inputs that the work was done with, so the work is
+            // only required again when they change.
+            if let Err(error) =
+                Self::inputs_digest_record(station, train_resources, file_system).await
+            {
+                let id = station.spec.id().clone();
+                let name = station.spec.name().to_string();
+                let detail = error.to_string();
+                let location = station.spec.location();
+                return Ok(CreateEnsureOutcomeOk::Changed {
+                    res_ids,
+                    station_spec_error: Some(StationSpecError::InputsDigestRecordFail {
+                        id,
+                        name,
+                        detail,
+                        location,
+                    }),
+                });
+            }
+
+            if let Some(station_spec_error) = Self::version_record(station, file_system).await {
+                return Ok(CreateEnsureOutcomeOk::Changed {
+                    res_ids,
+                    station_spec_error: Some(station_spec_error),
+                });
+            }
+
             // After we visit, if the check function reports we still
             // need to visit, then the visit function or the check
             // function needs to be corrected.
+            *phase = StationErrorPhase::Check;
             let check_status =
                 if let Some(check_status) = station.create_check(train_resources).await {
                     Some(
@@ -75,7 +215,8 @@ where
             let station_spec_error = if let Some(CheckStatus::WorkRequired) = check_status {
                 let id = station.spec.id().clone();
                 let name = station.spec.name().to_string();
-                Some(StationSpecError::WorkRequiredAfterVisit { id, name })
+                let location = station.spec.location();
+                Some(StationSpecError::WorkNotIdempotent { id, name, location })
             } else {
                 None
             };
@@ -85,7 +226,251 @@ where
                 station_spec_error,
             })
         } else {
+            // The work function is not run, so the output it would produce for
+            // downstream stations is provided by the output function.
+            match station.create_output(train_resources).await {
+                None | Some(Ok(Ok(()))) => {}
+                Some(Ok(Err(error))) => return Err(CreateEnsureOutcomeErr::CheckFail(error)),
+                Some(Err(borrow_fail)) => {
+                    return Err(CreateEnsureOutcomeErr::CheckBorrowFail(borrow_fail));
+                }
+            }
+
+            // `Unchanged` carries no error, so failing to record the version
+            // only means the version is compared again on the next visit.
+            let _station_spec_error = Self::version_record(station, file_system).await;
             Ok(CreateEnsureOutcomeOk::Unchanged)
         }
     }
+
+    /// Runs the station's check function ahead of its visit, returning the
+    /// check status if it completes successfully.
+    ///
+    /// The same guards as [`ensure`] are applied before the check: the
+    /// station's external dependencies and file inputs are resolved, the chaos
+    /// policy is consulted, and the station's spec version is compared with the
+    /// version recorded by its last successful visit. If a dependency is not
+    /// satisfied, the chaos policy injects a fault, or the spec version
+    /// differs, the check is not run, and `None` is returned. `None` is also
+    /// returned if the check fails or panics. In each case, the check is run
+    /// again when the station is visited, and any failure is recorded then.
+    ///
+    /// # Parameters
+    ///
+    /// * `station`: Station whose check function to run.
+    /// * `train_resources`: Resources produced by setting up the stations.
+    /// * `file_system`: File system that the spec version is recorded in.
+    /// * `chaos_policy`: Injects faults before the check, if any.
+    ///
+    /// [`ensure`]: Self::ensure
+    pub(crate) async fn check_ahead(
+        station: &mut StationMutRef<'_, E>,
+        train_resources: &TrainResources<E>,
+        file_system: &dyn FileSystem,
+        chaos_policy: Option<&ChaosPolicy<E>>,
+    ) -> Option<CheckStatus> {
+        Self::dependencies_resolve(station, train_resources)
+            .await
+            .ok()?;
+
+        let chaos_action = ChaosPolicy::inject(
+            chaos_policy,
+            &station.spec,
+            VisitOp::Create,
+            ChaosPoint::Check,
+        )
+        .await;
+        if !matches!(chaos_action, ChaosAction::Proceed) {
+            return None;
+        }
+
+        // The migration, or the work if there is none, is run when the station
+        // is visited.
+        if Self::version_changed(station, file_system).await.is_some() {
+            return None;
+        }
+
+        let check_fut = station.create_check(train_resources);
+        match PanicCatcher::catch(check_fut).await {
+            Ok(Some(Ok(Ok(check_status)))) => Some(check_status),
+            Ok(None | Some(Ok(Err(_)) | Some(Err(_)))) | Err(_) => None,
+        }
+    }
+
+    /// Resolves the station's external dependencies and file inputs, returning
+    /// an error for the first one that is not satisfied.
+    async fn dependencies_resolve(
+        station: &mut StationMutRef<'_, E>,
+        train_resources: &TrainResources<E>,
+    ) -> Result<(), StationSpecError> {
+        #[cfg(feature = "native")]
+        ExternalDependencyResolver::resolve(&station.spec, train_resources)?;
+        FileInputResolver::resolve(station, train_resources).await
+    }
+
+    /// Takes the result of the station's speculative check, if it is still
+    /// valid.
+    ///
+    /// Results are removed when an ancestor's visit does anything other than
+    /// find its work unnecessary, so the check is run again in that case.
+    fn check_status_speculative(
+        station: &StationMutRef<'_, E>,
+        train_resources: &TrainResources<E>,
+    ) -> Option<CheckStatus> {
+        train_resources
+            .try_borrow_mut::<CheckStatusesSpeculative>()
+            .ok()
+            .and_then(|mut check_statuses_speculative| {
+                check_statuses_speculative.remove(&station.rt_id)
+            })
+    }
+
+    /// Returns whether the work is required because the station's spec
+    /// version differs from the version recorded by its last successful visit.
+    ///
+    /// If the station has a migration function, it is run instead, and the
+    /// spec version is recorded when it succeeds.
+    async fn version_migrate(
+        station: &mut StationMutRef<'_, E>,
+        train_resources: &TrainResources<E>,
+        file_system: &dyn FileSystem,
+    ) -> Result<bool, CreateEnsureOutcomeErr<E>>
+    where
+        E: From<StationSpecError>,
+    {
+        let version_recorded = match Self::version_changed(station, file_system).await {
+            Some(version_recorded) => version_recorded,
+            None => return Ok(false),
+        };
+
+        match station.spec.migrate_fn().cloned() {
+            Some(migrate_fn) => {
+                let station_resources =
+                    StationResources::new(&station.spec, station.rt_id, train_resources);
+                (migrate_fn.0)(station, version_recorded, &station_resources)
+                    .await
+                    .map_err(CreateEnsureOutcomeErr::CheckFail)?;
+
+                match Self::version_record(station, file_system).await {
+                    Some(station_spec_error) => Err(CreateEnsureOutcomeErr::CheckFail(E::from(
+                        station_spec_error,
+                    ))),
+                    None => Ok(false),
+                }
+            }
+            None => Ok(true),
+        }
+    }
+
+    /// Records the digest of the station's current inputs in its directory,
+    /// if it has one.
+    async fn inputs_digest_record(
+        station: &StationMutRef<'_, E>,
+        train_resources: &TrainResources<E>,
+        file_system: &dyn FileSystem,
+    ) -> io::Result<()> {
+        let digest = train_resources
+            .try_borrow::<StationInputsDigests>()
+            .ok()
+            .and_then(|station_inputs_digests| station_inputs_digests.get(&station.rt_id).cloned());
+        let digest = match digest {
+            Some(digest) => digest,
+            None => return Ok(()),
+        };
+
+        file_system.create_dir_all(station.dir).await?;
+        file_system
+            .write(
+                &StationInputsDigests::digest_path(station.dir),
+                digest.as_bytes(),
+            )
+            .await?;
+        if let Ok(station_inputs_digests) = train_resources.try_borrow::<StationInputsDigests>() {
+            station_inputs_digests.recorded_insert(station.rt_id, digest);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the spec version recorded by the station's last successful
+    /// visit, if it differs from the station's current spec version.
+    async fn version_changed(
+        station: &StationMutRef<'_, E>,
+        file_system: &dyn FileSystem,
+    ) -> Option<u32> {
+        let version = station.spec.version()?;
+        Self::version_recorded(station, file_system)
+            .await
+            .filter(|version_recorded| *version_recorded != version)
+    }
+
+    /// Returns the spec version recorded in the station's directory.
+    ///
+    /// Returns `None` if no version has been recorded, or it cannot be read.
+    async fn version_recorded(
+        station: &StationMutRef<'_, E>,
+        file_system: &dyn FileSystem,
+    ) -> Option<u32> {
+        file_system
+            .read(&StationVersionRecord::version_path(station.dir))
+            .await
+            .ok()
+            .and_then(|contents| StationVersionRecord::version_parse(&contents))
+    }
+
+    /// Records the station's spec version if it differs from the recorded
+    /// version, returning an error if it cannot be recorded.
+    async fn version_record(
+        station: &StationMutRef<'_, E>,
+        file_system: &dyn FileSystem,
+    ) -> Option<StationSpecError> {
+        let version = station.spec.version()?;
+        if Self::version_recorded(station, file_system).await == Some(version) {
+            return None;
+        }
+
+        let version_write = async {
+            file_system.create_dir_all(station.dir).await?;
+            file_system
+                .write(
+                    &StationVersionRecord::version_path(station.dir),
+                    version.to_string().as_bytes(),
+                )
+                .await
+        };
+        version_write.await.err().map(|error: io::Error| {
+            let id = station.spec.id().clone();
+            let name = station.spec.name().to_string();
+            let detail = error.to_string();
+            let location = station.spec.location();
+            StationSpecError::VersionRecordFail {
+                id,
+                name,
+                detail,
+                location,
+            }
+        })
+    }
+
+    /// Returns an error if the chaos policy injects a failure or cancellation
+    /// at the given chaos point.
+    async fn chaos_inject(
+        station: &StationMutRef<'_, E>,
+        chaos_policy: Option<&ChaosPolicy<E>>,
+        chaos_point: ChaosPoint,
+    ) -> Result<(), CreateEnsureOutcomeErr<E>> {
+        let chaos_action =
+            ChaosPolicy::inject(chaos_policy, &station.spec, VisitOp::Create, chaos_point).await;
+        match (chaos_action, chaos_point) {
+            (ChaosAction::Proceed | ChaosAction::Delay(_), _) => Ok(()),
+            (ChaosAction::Fail(error), ChaosPoint::Check) => {
+                Err(CreateEnsureOutcomeErr::CheckFail(error))
+            }
+            (ChaosAction::Fail(error), ChaosPoint::Work) => Err(CreateEnsureOutcomeErr::WorkFail {
+                res_ids: ResIds::new(),
+                error,
+            }),
+            (ChaosAction::Cancel, _) => Err(CreateEnsureOutcomeErr::Interrupted),
+        }
+    }
 }
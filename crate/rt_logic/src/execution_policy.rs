@@ -0,0 +1,46 @@
+/// How a train proceeds when a station fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExecutionPolicy {
+    /// Stations that do not depend on the failed station continue to be
+    /// visited.
+    ///
+    /// Stations that depend on the failed station are marked
+    /// [`OpStatus::ParentFail`].
+    ///
+    /// This is the default.
+    ///
+    /// [`OpStatus::ParentFail`]: choochoo_cfg_model::rt::OpStatus::ParentFail
+    ContinueIndependent,
+    /// No more stations are started, but stations already in progress are
+    /// visited to completion.
+    ///
+    /// Stations that are not started are marked [`OpStatus::OpSkipped`].
+    ///
+    /// [`OpStatus::OpSkipped`]: choochoo_cfg_model::rt::OpStatus::OpSkipped
+    FailFast,
+    /// No more stations are started, and stations in progress are interrupted.
+    ///
+    /// Stations that are not started are marked [`OpStatus::OpSkipped`], and
+    /// stations in progress are marked [`OpStatus::WorkInterrupted`].
+    ///
+    /// [`OpStatus::OpSkipped`]: choochoo_cfg_model::rt::OpStatus::OpSkipped
+    /// [`OpStatus::WorkInterrupted`]: choochoo_cfg_model::rt::OpStatus::WorkInterrupted
+    StopImmediately,
+}
+
+impl ExecutionPolicy {
+    /// Returns whether the train stops visiting stations after a station
+    /// fails.
+    pub fn stops_on_failure(self) -> bool {
+        match self {
+            Self::ContinueIndependent => false,
+            Self::FailFast | Self::StopImmediately => true,
+        }
+    }
+}
+
+impl Default for ExecutionPolicy {
+    fn default() -> Self {
+        Self::ContinueIndependent
+    }
+}
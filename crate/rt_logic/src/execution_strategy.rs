@@ -0,0 +1,44 @@
+use std::fmt;
+
+use choochoo_cfg_model::rt::{TrainResources, VisitOp};
+use choochoo_rt_model::{Destination, Error};
+use futures::future::LocalBoxFuture;
+
+use crate::ChaosPolicy;
+
+pub use self::{fast_path_strat::FastPathStrat, integrity_strat::IntegrityStrat};
+
+mod fast_path_strat;
+mod integrity_strat;
+
+/// Prepares stations before a train visits them.
+///
+/// The strategy runs after stations are set up, and before they are visited.
+/// Check results that the strategy records in [`CheckStatusesSpeculative`]
+/// are used when the station is visited, after the station's dependencies,
+/// file inputs, and spec version are checked, in place of running its check
+/// function again.
+///
+/// The default strategy is [`IntegrityStrat`], which runs each station's check
+/// when it is visited, after all of its parents have been visited.
+/// [`FastPathStrat`] checks stations that are already in their desired state
+/// up front.
+///
+/// [`CheckStatusesSpeculative`]: choochoo_cfg_model::rt::CheckStatusesSpeculative
+pub trait ExecutionStrategy<E>: fmt::Debug + Send + Sync {
+    /// Prepares the destination's stations to be visited.
+    ///
+    /// # Parameters
+    ///
+    /// * `dest`: Destination whose stations will be visited.
+    /// * `train_resources`: Resources produced by setting up the stations.
+    /// * `chaos_policy`: Injects faults into the stations, if any.
+    /// * `visit_op`: Operation the stations will be visited for.
+    fn stations_prepare<'f>(
+        &'f self,
+        dest: &'f Destination<E>,
+        train_resources: &'f TrainResources<E>,
+        chaos_policy: Option<&'f ChaosPolicy<E>>,
+        visit_op: VisitOp,
+    ) -> LocalBoxFuture<'f, Result<(), Error<E>>>;
+}
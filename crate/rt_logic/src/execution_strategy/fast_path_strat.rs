@@ -0,0 +1,130 @@
+use std::{
+    fmt,
+    sync::{Mutex, PoisonError},
+};
+
+use choochoo_cfg_model::{
+    daggy::Walker,
+    rt::{CheckStatus, CheckStatusesSpeculative, OpStatus, TrainResources, VisitOp},
+};
+use choochoo_rt_model::{Destination, Error};
+use futures::{
+    future::{self, FutureExt, LocalBoxFuture},
+    stream::{StreamExt, TryStreamExt},
+};
+
+use crate::{ChaosPolicy, CreateDriver, ExecutionStrategy, FileInputResolver};
+
+/// Checks stations that are already in their desired state before stations
+/// are visited.
+///
+/// When creating, each station's check function is run once the check
+/// functions of its parents have run, instead of once its parents have been
+/// visited. Checks run concurrently, and their results are used when the
+/// station is visited in place of running the check again, so visiting a
+/// station that is already in its desired state does not wait on its check.
+///
+/// Checks are run through the same guards as when the station is visited:
+/// the station's external dependencies and file inputs must be resolved, the
+/// chaos policy is consulted, and its spec version must not have changed.
+/// When the station is visited, these guards are applied again before the
+/// check result is used, and the station's hooks are run and its visit
+/// recorded as usual.
+///
+/// Only stations whose parents' checks also reported
+/// [`CheckStatus::WorkNotRequired`] are checked ahead, as a parent's work may
+/// change whether the station's work is required. Stations without a check
+/// function, or whose check fails or panics, are checked when they are
+/// visited.
+///
+/// When cleaning, stations are visited as with [`IntegrityStrat`].
+///
+/// [`IntegrityStrat`]: crate::IntegrityStrat
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FastPathStrat;
+
+impl<E> ExecutionStrategy<E> for FastPathStrat
+where
+    E: fmt::Debug + Send + Sync + 'static,
+{
+    fn stations_prepare<'f>(
+        &'f self,
+        dest: &'f Destination<E>,
+        train_resources: &'f TrainResources<E>,
+        chaos_policy: Option<&'f ChaosPolicy<E>>,
+        visit_op: VisitOp,
+    ) -> LocalBoxFuture<'f, Result<(), Error<E>>> {
+        if visit_op == VisitOp::Clean {
+            return future::ok(()).boxed_local();
+        }
+
+        async move {
+            let station_specs = dest.station_specs();
+            let stations_unchanged = &Mutex::new(vec![false; station_specs.node_count()]);
+            let check_statuses = &Mutex::new(CheckStatusesSpeculative::new());
+
+            dest.stations_mut_stream()
+                .map(Ok)
+                .try_for_each_concurrent(None, |mut station| async move {
+                    if station.progress.op_status != OpStatus::SetupSuccess {
+                        return Ok(());
+                    }
+
+                    let parents_unchanged = {
+                        let stations_unchanged = stations_unchanged
+                            .lock()
+                            .unwrap_or_else(PoisonError::into_inner);
+                        station_specs
+                            .parents(station.rt_id)
+                            .iter(station_specs)
+                            .all(|(_edge, parent_rt_id)| stations_unchanged[parent_rt_id.index()])
+                    };
+                    if !parents_unchanged {
+                        return Ok(());
+                    }
+
+                    let check_status = CreateDriver::check_ahead(
+                        &mut station,
+                        train_resources,
+                        dest.file_system(),
+                        chaos_policy,
+                    )
+                    .await;
+                    if let Some(check_status) = check_status {
+                        check_statuses
+                            .lock()
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .insert(station.rt_id, check_status);
+                    }
+                    if check_status == Some(CheckStatus::WorkNotRequired) {
+                        // Children's file inputs are resolved before they are
+                        // checked, which is before this station is visited.
+                        FileInputResolver::publish(
+                            station.spec.id(),
+                            &station.progress,
+                            train_resources,
+                        )
+                        .await;
+                        stations_unchanged
+                            .lock()
+                            .unwrap_or_else(PoisonError::into_inner)[station.rt_id.index()] = true;
+                    }
+
+                    Ok(())
+                })
+                .await?;
+
+            if let Ok(mut check_statuses_speculative) =
+                train_resources.try_borrow_mut::<CheckStatusesSpeculative>()
+            {
+                let check_statuses = check_statuses
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner);
+                check_statuses_speculative.extend(check_statuses.iter());
+            }
+
+            Ok(())
+        }
+        .boxed_local()
+    }
+}
@@ -0,0 +1,24 @@
+use choochoo_cfg_model::rt::{TrainResources, VisitOp};
+use choochoo_rt_model::{Destination, Error};
+use futures::future::{self, FutureExt, LocalBoxFuture};
+
+use crate::{ChaosPolicy, ExecutionStrategy};
+
+/// Visits every station after all of its parents have been visited.
+///
+/// Each station's check function is run when the station is visited, so it
+/// sees the changes made by its parents' work functions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IntegrityStrat;
+
+impl<E> ExecutionStrategy<E> for IntegrityStrat {
+    fn stations_prepare<'f>(
+        &'f self,
+        _dest: &'f Destination<E>,
+        _train_resources: &'f TrainResources<E>,
+        _chaos_policy: Option<&'f ChaosPolicy<E>>,
+        _visit_op: VisitOp,
+    ) -> LocalBoxFuture<'f, Result<(), Error<E>>> {
+        future::ok(()).boxed_local()
+    }
+}
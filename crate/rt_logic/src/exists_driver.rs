@@ -0,0 +1,53 @@
+use std::marker::PhantomData;
+
+use choochoo_cfg_model::{
+    indexmap::IndexMap,
+    rt::{ResIdsPrevious, StationMutRef, StationResources, TrainResources},
+};
+use choochoo_rt_model::StationReconciliation;
+
+/// Logic that runs a station's exists function, to reconcile its persisted
+/// resource IDs with the resources that exist.
+#[derive(Debug)]
+pub struct ExistsDriver<E> {
+    /// Marker.
+    marker: PhantomData<E>,
+}
+
+impl<E> ExistsDriver<E>
+where
+    E: 'static,
+{
+    /// Returns the differences between the station's persisted resource IDs
+    /// and the resources that exist.
+    ///
+    /// Returns `None` if the station has no exists function.
+    ///
+    /// # Parameters
+    ///
+    /// * `station`: The station to reconcile.
+    /// * `res_ids_previous`: Resource IDs persisted by each station.
+    /// * `train_resources`: Resources that the exists function may borrow from,
+    ///   limited to those declared by the station's functions.
+    pub async fn reconcile(
+        station: &StationMutRef<'_, E>,
+        res_ids_previous: &ResIdsPrevious,
+        train_resources: &TrainResources<E>,
+    ) -> Option<Result<StationReconciliation, E>> {
+        let exists_fn = station.spec.exists_fn()?.clone();
+
+        let res_ids_empty = IndexMap::new();
+        let res_ids_persisted = res_ids_previous
+            .station_res_ids(station.rt_id)
+            .unwrap_or(&res_ids_empty);
+        let station_resources =
+            StationResources::new(&station.spec, station.rt_id, train_resources);
+        let reconciliation = (exists_fn.0)(station, res_ids_persisted, &station_resources)
+            .await
+            .map(|res_ids_existing| {
+                StationReconciliation::new(res_ids_persisted, &res_ids_existing)
+            });
+
+        Some(reconciliation)
+    }
+}
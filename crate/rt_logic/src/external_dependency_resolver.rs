@@ -0,0 +1,92 @@
+use std::marker::PhantomData;
+
+use choochoo_cfg_model::{
+    rt::{OpStatus, TrainResources, VisitOp},
+    ExternalDependency, StationSpec,
+};
+use choochoo_resource::{HistoryDir, ProfileHistoryDir};
+use choochoo_rt_model::{error::StationSpecError, AuditQuery};
+
+use crate::AuditLog;
+
+/// Checks whether stations in other profiles that a station depends on have
+/// been successfully created.
+///
+/// Dependencies are resolved from the [`AuditLog`] of each dependency's
+/// profile, which must be in the same workspace. A dependency is satisfied
+/// when the latest work recorded for its station is a successful create.
+#[derive(Debug)]
+pub struct ExternalDependencyResolver<E>(PhantomData<E>);
+
+impl<E> ExternalDependencyResolver<E>
+where
+    E: 'static,
+{
+    /// Returns an error for the first of the station's external dependencies
+    /// that is not satisfied.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_spec`: Station whose dependencies to resolve.
+    /// * `train_resources`: Resources containing the [`HistoryDir`].
+    pub fn resolve(
+        station_spec: &StationSpec<E>,
+        train_resources: &TrainResources<E>,
+    ) -> Result<(), StationSpecError> {
+        if station_spec.external_dependencies().is_empty() {
+            return Ok(());
+        }
+
+        let history_dir = train_resources.try_borrow::<HistoryDir>().ok();
+        station_spec
+            .external_dependencies()
+            .iter()
+            .try_for_each(|external_dependency| {
+                let detail = match history_dir.as_deref() {
+                    Some(history_dir) => Self::dependency_check(history_dir, external_dependency),
+                    None => Some(String::from(
+                        "could not be located as there is no history directory",
+                    )),
+                };
+
+                match detail {
+                    Some(detail) => Err(StationSpecError::ExternalDependencyUnsatisfied {
+                        id: station_spec.id().clone(),
+                        name: station_spec.name().to_string(),
+                        dependency_profile: external_dependency.profile().clone(),
+                        dependency_station_id: external_dependency.station_id().clone(),
+                        detail,
+                        location: station_spec.location(),
+                    }),
+                    None => Ok(()),
+                }
+            })
+    }
+
+    /// Returns why the dependency is not satisfied, or `None` if it is.
+    fn dependency_check(
+        history_dir: &HistoryDir,
+        external_dependency: &ExternalDependency,
+    ) -> Option<String> {
+        let profile_history_dir =
+            ProfileHistoryDir::new(history_dir.join(&**external_dependency.profile()));
+        let audit_query =
+            AuditQuery::new().with_station_id(external_dependency.station_id().clone());
+
+        match AuditLog::<E>::query(&profile_history_dir, &audit_query) {
+            Ok(audit_entries) => match audit_entries.last() {
+                Some(audit_entry) => match (audit_entry.visit_op, audit_entry.op_status) {
+                    (VisitOp::Create, OpStatus::WorkSuccess) => None,
+                    (VisitOp::Clean, OpStatus::WorkSuccess) => {
+                        Some(String::from("has been cleaned"))
+                    }
+                    (_, op_status) => {
+                        Some(format!("did not complete its last visit: {op_status:?}"))
+                    }
+                },
+                None => Some(String::from("has not been created")),
+            },
+            Err(error) => Some(format!("could not be read from the audit log: {error}")),
+        }
+    }
+}
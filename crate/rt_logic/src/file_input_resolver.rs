@@ -0,0 +1,90 @@
+use std::marker::PhantomData;
+
+use choochoo_cfg_model::{
+    rt::{StationFiles, StationMutRef, StationProgress, TrainResources},
+    StationId,
+};
+use choochoo_rt_model::error::StationSpecError;
+
+/// Records files that stations publish, and resolves the paths of files that
+/// stations declare as inputs.
+///
+/// Files are published by station ID and name into the [`StationFiles`]
+/// resource, so a station that reads another station's output does not need
+/// to look up that station's runtime ID or directory.
+#[derive(Debug)]
+pub struct FileInputResolver<E>(PhantomData<E>);
+
+impl<E> FileInputResolver<E>
+where
+    E: 'static,
+{
+    /// Records the files that a station has published.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_id`: Unique identifier of the station.
+    /// * `station_progress`: Progress of the station, which holds its published
+    ///   files.
+    /// * `train_resources`: Resources containing the [`StationFiles`].
+    pub async fn publish(
+        station_id: &StationId,
+        station_progress: &StationProgress,
+        train_resources: &TrainResources<E>,
+    ) {
+        if station_progress.files_published().is_empty() {
+            return;
+        }
+
+        if let Ok(station_files) = train_resources.try_borrow::<StationFiles>() {
+            station_files
+                .publish(station_id, station_progress.files_published())
+                .await;
+        }
+    }
+
+    /// Resolves the paths of the files that a station declares as inputs.
+    ///
+    /// Returns an error for the first declared file that has not been
+    /// published.
+    ///
+    /// # Parameters
+    ///
+    /// * `station`: Station whose file inputs to resolve.
+    /// * `train_resources`: Resources containing the [`StationFiles`].
+    pub async fn resolve(
+        station: &mut StationMutRef<'_, E>,
+        train_resources: &TrainResources<E>,
+    ) -> Result<(), StationSpecError> {
+        if station.spec.file_inputs().is_empty() {
+            return Ok(());
+        }
+
+        let station_files = train_resources.try_borrow::<StationFiles>().ok();
+        for file_input in station.spec.file_inputs().iter() {
+            let path = match station_files.as_deref() {
+                Some(station_files) => {
+                    station_files
+                        .get(file_input.station_id(), file_input.name())
+                        .await
+                }
+                None => None,
+            };
+
+            match path {
+                Some(path) => station.progress.file_input_set(file_input.clone(), path),
+                None => {
+                    return Err(StationSpecError::FileInputUnpublished {
+                        id: station.spec.id().clone(),
+                        name: station.spec.name().to_string(),
+                        input_station_id: file_input.station_id().clone(),
+                        input_name: file_input.name().to_string(),
+                        location: station.spec.location(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
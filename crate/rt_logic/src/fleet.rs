@@ -0,0 +1,143 @@
+use std::{fmt, num::NonZeroUsize, sync::Arc};
+
+use choochoo_cfg_model::{indexmap::IndexMap, rt::VisitOp};
+use choochoo_resource::Profile;
+use choochoo_rt_model::{
+    error::StationSpecError, Destination, DestinationBuilder, Error, FleetReport,
+};
+use futures::future;
+
+use crate::Train;
+
+/// Function that builds a destination from a builder with the profile set.
+type DestFn<E> = dyn Fn(DestinationBuilder<E>) -> Result<Destination<E>, Error<E>>;
+
+// **Note:** `Debug` is manually implemented as the destination function does
+// not implement `Debug`.
+/// Drives trains to the same destination in multiple profiles concurrently,
+/// such as to deploy to `dev`, `stage`, and `perf` environments.
+///
+/// A destination is built for each profile using the destination function,
+/// which receives a [`DestinationBuilder`] with the profile already set. Each
+/// profile has its own profile directory, so the trains do not share state.
+///
+/// Every train is driven by the same [`Train`], so its progress backend and
+/// metrics are shared between profiles. Use a [`NoopProgressBackend`] to
+/// avoid interleaved progress bars.
+///
+/// [`NoopProgressBackend`]: crate::NoopProgressBackend
+pub struct Fleet<E> {
+    /// Train to drive in each profile.
+    train: Train<E>,
+    /// Profiles to drive the train in.
+    profiles: Vec<Profile>,
+    /// Builds the destination for each profile.
+    dest_fn: Arc<DestFn<E>>,
+}
+
+impl<E> Fleet<E>
+where
+    E: From<StationSpecError> + fmt::Debug + Send + Sync + 'static,
+{
+    /// Returns a new `Fleet` with no profiles.
+    ///
+    /// # Parameters
+    ///
+    /// * `train`: Train to drive in each profile.
+    /// * `dest_fn`: Builds the destination from a builder with the profile set.
+    pub fn new<F>(train: Train<E>, dest_fn: F) -> Self
+    where
+        F: Fn(DestinationBuilder<E>) -> Result<Destination<E>, Error<E>> + 'static,
+    {
+        Self {
+            train,
+            profiles: Vec::new(),
+            dest_fn: Arc::new(dest_fn),
+        }
+    }
+
+    /// Adds a profile to drive the train in.
+    #[must_use]
+    pub fn with_profile(mut self, profile: Profile) -> Self {
+        self.profiles.push(profile);
+        self
+    }
+
+    /// Adds profiles to drive the train in.
+    #[must_use]
+    pub fn with_profiles<I>(mut self, profiles: I) -> Self
+    where
+        I: IntoIterator<Item = Profile>,
+    {
+        self.profiles.extend(profiles);
+        self
+    }
+
+    /// Sets the maximum number of stations to visit concurrently in each
+    /// profile.
+    ///
+    /// The limit applies to each profile independently, so up to `n` stations
+    /// are visited in every profile at the same time.
+    #[must_use]
+    pub fn with_concurrency_max_per_profile(mut self, concurrency_max: NonZeroUsize) -> Self {
+        self.train = self.train.with_concurrency_max(concurrency_max);
+        self
+    }
+
+    /// Returns the profiles to drive the train in.
+    pub fn profiles(&self) -> &[Profile] {
+        &self.profiles
+    }
+
+    /// Drives the train to the destination in every profile concurrently.
+    ///
+    /// A failure in one profile does not stop the trains in other profiles.
+    pub async fn reach(&self, visit_op: VisitOp) -> FleetReport<E> {
+        let (mut dests, mut train_reports) = self.profiles.iter().cloned().fold(
+            (IndexMap::new(), IndexMap::new()),
+            |(mut dests, mut train_reports), profile| {
+                let dest_builder = Destination::builder().with_profile(profile.clone());
+                match (self.dest_fn)(dest_builder) {
+                    Ok(dest) => {
+                        dests.insert(profile, dest);
+                    }
+                    Err(error) => {
+                        train_reports.insert(profile, Err(error));
+                    }
+                }
+                (dests, train_reports)
+            },
+        );
+
+        let train = &self.train;
+        let reach_results = future::join_all(dests.iter_mut().map(|(profile, dest)| async move {
+            let train_report = train.reach(dest, visit_op).await;
+            (profile.clone(), train_report)
+        }))
+        .await;
+        train_reports.extend(reach_results);
+
+        // Keep the reports in the order the profiles were added.
+        let profiles = &self.profiles;
+        train_reports.sort_by(|profile_a, _, profile_b, _| {
+            let index_a = profiles.iter().position(|profile| profile == profile_a);
+            let index_b = profiles.iter().position(|profile| profile == profile_b);
+            index_a.cmp(&index_b)
+        });
+
+        FleetReport::new(dests, train_reports)
+    }
+}
+
+impl<E> fmt::Debug for Fleet<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Fleet")
+            .field("train", &self.train)
+            .field("profiles", &self.profiles)
+            .field(
+                "dest_fn",
+                &"Arc<dyn Fn(DestinationBuilder<E>) -> Result<..>>",
+            )
+            .finish()
+    }
+}
@@ -0,0 +1,140 @@
+use std::{
+    collections::HashSet,
+    ffi::OsStr,
+    fs,
+    marker::PhantomData,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+use choochoo_cfg_model::StationSpecs;
+use choochoo_resource::ProfileHistoryDir;
+use choochoo_rt_model::Error;
+
+use crate::HistoryRetention;
+
+/// Prunes entries in the profile history directory according to a
+/// [`HistoryRetention`].
+///
+/// Each entry is a directory of resource IDs persisted by a station:
+///
+/// ```text
+/// ${workspace}/target/.history/${profile}/${station_id}
+/// ```
+///
+/// Only entries of stations that are not in the destination are pruned. Files
+/// in the profile history directory, such as the audit log, are kept.
+#[derive(Debug)]
+pub struct HistoryGc<E>(PhantomData<E>);
+
+impl<E> HistoryGc<E>
+where
+    E: 'static,
+{
+    /// Returns the entries that would be pruned, without removing them.
+    ///
+    /// Entries are returned in order of most recently modified first.
+    ///
+    /// # Parameters
+    ///
+    /// * `profile_history_dir`: Directory containing the entries.
+    /// * `station_specs`: Stations in the destination, whose entries are kept.
+    /// * `history_retention`: How long entries are kept.
+    pub fn prunable(
+        profile_history_dir: &ProfileHistoryDir,
+        station_specs: &StationSpecs<E>,
+        history_retention: HistoryRetention,
+    ) -> Result<Vec<PathBuf>, Error<E>> {
+        if history_retention == HistoryRetention::KeepAll || !profile_history_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        // Hierarchical station IDs are persisted in nested directories, so
+        // their entry is the directory of the first segment.
+        let station_ids = station_specs
+            .iter()
+            .filter_map(|station_spec| station_spec.id().segments().next())
+            .collect::<HashSet<&str>>();
+        let history_read_error = |error| Error::HistoryRead {
+            profile_history_dir: profile_history_dir.clone(),
+            error,
+        };
+        let mut entries_stale = fs::read_dir(&**profile_history_dir)
+            .map_err(history_read_error)?
+            .filter(|dir_entry| {
+                dir_entry
+                    .as_ref()
+                    .map_or(true, |dir_entry| dir_entry.path().is_dir())
+            })
+            .map(|dir_entry| {
+                let dir_entry = dir_entry.map_err(history_read_error)?;
+                let modified = dir_entry
+                    .metadata()
+                    .and_then(|metadata| metadata.modified())
+                    .map_err(history_read_error)?;
+                Ok((dir_entry.path(), modified))
+            })
+            .filter(
+                |entry: &Result<(PathBuf, SystemTime), Error<E>>| match entry {
+                    Ok((path, _)) => path
+                        .file_name()
+                        .and_then(OsStr::to_str)
+                        .map_or(true, |file_name| !station_ids.contains(file_name)),
+                    Err(_) => true,
+                },
+            )
+            .collect::<Result<Vec<_>, Error<E>>>()?;
+        entries_stale.sort_by(|(_, modified_a), (_, modified_b)| modified_b.cmp(modified_a));
+
+        let entries_prunable = match history_retention {
+            HistoryRetention::KeepAll => Vec::new(),
+            HistoryRetention::KeepLast(n) => entries_stale
+                .into_iter()
+                .skip(n)
+                .map(|(path, _)| path)
+                .collect(),
+            HistoryRetention::MaxAge(max_age) => {
+                let now = SystemTime::now();
+                entries_stale
+                    .into_iter()
+                    .filter(|(_, modified)| {
+                        now.duration_since(*modified).unwrap_or(Duration::ZERO) > max_age
+                    })
+                    .map(|(path, _)| path)
+                    .collect()
+            }
+        };
+
+        Ok(entries_prunable)
+    }
+
+    /// Removes entries according to the retention policy, and returns the
+    /// removed entries.
+    ///
+    /// # Parameters
+    ///
+    /// * `profile_history_dir`: Directory containing the entries.
+    /// * `station_specs`: Stations in the destination, whose entries are kept.
+    /// * `history_retention`: How long entries are kept.
+    pub fn prune(
+        profile_history_dir: &ProfileHistoryDir,
+        station_specs: &StationSpecs<E>,
+        history_retention: HistoryRetention,
+    ) -> Result<Vec<PathBuf>, Error<E>> {
+        let entries_prunable =
+            Self::prunable(profile_history_dir, station_specs, history_retention)?;
+        entries_prunable.iter().try_for_each(|path| {
+            let result = if path.is_dir() {
+                fs::remove_dir_all(path)
+            } else {
+                fs::remove_file(path)
+            };
+            result.map_err(|error| Error::HistoryRemove {
+                path: path.clone(),
+                error,
+            })
+        })?;
+
+        Ok(entries_prunable)
+    }
+}
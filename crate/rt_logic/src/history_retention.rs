@@ -0,0 +1,25 @@
+use std::time::Duration;
+
+/// How long entries in the profile history directory are kept.
+///
+/// Entries belong to stations that are no longer in the destination, such as
+/// stations that have been renamed or removed. Entries of stations in the
+/// destination are never pruned, as they are needed to clean up the stations'
+/// resources.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HistoryRetention {
+    /// All entries are kept.
+    ///
+    /// This is the default.
+    KeepAll,
+    /// The most recently modified `n` entries are kept.
+    KeepLast(usize),
+    /// Entries modified within the duration are kept.
+    MaxAge(Duration),
+}
+
+impl Default for HistoryRetention {
+    fn default() -> Self {
+        Self::KeepAll
+    }
+}
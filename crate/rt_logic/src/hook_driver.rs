@@ -0,0 +1,59 @@
+use std::marker::PhantomData;
+
+use choochoo_cfg_model::rt::{OpStatus, ResIds, StationMutRef, StationResources, TrainResources};
+
+/// Logic that runs a station's hooks after its operation completes.
+#[derive(Debug)]
+pub struct HookDriver<E> {
+    /// Marker.
+    marker: PhantomData<E>,
+}
+
+impl<E> HookDriver<E>
+where
+    E: 'static,
+{
+    /// Runs the station's `on_success` or `on_failure` hook, based on the
+    /// station's status.
+    ///
+    /// Hooks are not run when the operation was not attempted, or was
+    /// interrupted. Errors returned by the hook are recorded in
+    /// [`StationWarnings`], so they do not fail the station.
+    ///
+    /// # Parameters
+    ///
+    /// * `station`: The station whose operation completed.
+    /// * `res_ids`: Resource IDs produced by the operation, if any.
+    /// * `train_resources`: Resources that the hook may borrow from, limited to
+    ///   those declared by the station's functions.
+    ///
+    /// [`StationWarnings`]: choochoo_cfg_model::rt::StationWarnings
+    pub async fn run(
+        station: &StationMutRef<'_, E>,
+        res_ids: Option<&ResIds>,
+        train_resources: &TrainResources<E>,
+    ) {
+        let hook_fn = match station.progress.op_status {
+            OpStatus::WorkSuccess | OpStatus::WorkUnnecessary => station.spec.on_success_fn(),
+            OpStatus::CheckFail
+            | OpStatus::WorkFail
+            | OpStatus::VerifyFail
+            | OpStatus::Panicked => station.spec.on_failure_fn(),
+            _ => None,
+        };
+        let hook_fn = if let Some(hook_fn) = hook_fn {
+            hook_fn.clone()
+        } else {
+            return;
+        };
+
+        let res_ids_empty = ResIds::new();
+        let res_ids = res_ids.unwrap_or(&res_ids_empty);
+        let station_resources =
+            StationResources::new(&station.spec, station.rt_id, train_resources);
+        if let Err(warning) = (hook_fn.0)(station, res_ids, &station_resources).await {
+            let station_warnings = train_resources.station_warnings();
+            station_warnings.insert(station.rt_id, warning).await;
+        }
+    }
+}
@@ -1,15 +1,91 @@
 //! Runtime visit logic for the choochoo automation library.
 
+#[cfg(feature = "http")]
+pub use reqwest;
+
+pub use crate::{
+    chaos_action::ChaosAction,
+    chaos_point::ChaosPoint,
+    chaos_policy::ChaosPolicy,
+    clean_driver::CleanDriver,
+    clean_op_status_updater::CleanOpStatusUpdater,
+    create_driver::CreateDriver,
+    execution_policy::ExecutionPolicy,
+    execution_strategy::{ExecutionStrategy, FastPathStrat, IntegrityStrat},
+    exists_driver::ExistsDriver,
+    file_input_resolver::FileInputResolver,
+    fleet::Fleet,
+    history_retention::HistoryRetention,
+    hook_driver::HookDriver,
+    op_status_propagator::OpStatusPropagator,
+    op_status_updater::OpStatusUpdater,
+    progress_backend::{NoopProgressBackend, ProgressBackend},
+    res_id_persister::ResIdPersister,
+    resource_initializer::ResourceInitializer,
+    resource_limits::ResourceLimits,
+    rollback_policy::RollbackPolicy,
+    timer::Timer,
+    train::Train,
+};
+
+#[cfg(feature = "metrics")]
+pub use crate::train_metrics::TrainMetrics;
+#[cfg(feature = "native")]
 pub use crate::{
-    clean_driver::CleanDriver, clean_op_status_updater::CleanOpStatusUpdater,
-    create_driver::CreateDriver, op_status_updater::OpStatusUpdater,
-    res_id_persister::ResIdPersister, resource_initializer::ResourceInitializer, train::Train,
+    artifact_collector::ArtifactCollector, audit_log::AuditLog,
+    external_dependency_resolver::ExternalDependencyResolver, history_gc::HistoryGc,
+    profile_lock::ProfileLock, progress_backend::IndicatifProgressBackend,
+    resource_snapshot::ResourceSnapshot, station_inputs_hasher::StationInputsHasher,
+    timer::TokioTimer,
+};
+
+pub(crate) use crate::{
+    check_speculator::CheckSpeculator, panic_catcher::PanicCatcher,
+    station_priority_stream::StationPriorityStream, station_queue::StationQueue,
+    station_stall_watchdog::StationStallWatchdog, station_workers::StationWorkers,
 };
 
+#[cfg(feature = "native")]
+mod artifact_collector;
+#[cfg(feature = "native")]
+mod audit_log;
+mod chaos_action;
+mod chaos_point;
+mod chaos_policy;
+mod check_speculator;
 mod clean_driver;
 mod clean_op_status_updater;
 mod create_driver;
+mod execution_policy;
+mod execution_strategy;
+mod exists_driver;
+#[cfg(feature = "native")]
+mod external_dependency_resolver;
+mod file_input_resolver;
+mod fleet;
+#[cfg(feature = "native")]
+mod history_gc;
+mod history_retention;
+mod hook_driver;
+mod op_status_propagator;
 mod op_status_updater;
+mod panic_catcher;
+#[cfg(feature = "native")]
+mod profile_lock;
+mod progress_backend;
 mod res_id_persister;
 mod resource_initializer;
+mod resource_limits;
+#[cfg(feature = "native")]
+mod resource_snapshot;
+mod rollback_policy;
+#[cfg(feature = "native")]
+mod station_inputs_hasher;
+mod station_priority_stream;
+mod station_queue;
+mod station_stall_watchdog;
+mod station_workers;
+mod timer;
 mod train;
+#[cfg(feature = "metrics")]
+mod train_metrics;
@@ -0,0 +1,203 @@
+use std::sync::{Mutex, PoisonError};
+
+use choochoo_cfg_model::{
+    daggy::Walker,
+    rt::{OpStatus, StationRtId, VisitOp},
+};
+use choochoo_rt_model::Destination;
+
+/// Incrementally updates [`OpStatus`]es as stations complete.
+///
+/// Instead of recomputing a station's [`OpStatus`] from all of its
+/// predecessors each time one of them completes, this tracks the number of
+/// predecessors that have not completed, and the status to transition to once
+/// they have. Each completion only visits the completed station's direct
+/// successors, so updating all stations over a train's drive is linear in the
+/// number of dependencies.
+///
+/// A station's predecessors depend on the [`VisitOp`]:
+///
+/// * [`VisitOp::Create`]: The station's parents.
+/// * [`VisitOp::Clean`]: The station's children.
+///
+/// # `OpStatus` Transitions
+///
+/// Once all of a `ParentPending` station's predecessors have completed:
+///
+/// * If all predecessors are `WorkSuccess` or `WorkUnnecessary`, switch to
+///   `OpQueued`.
+/// * If the first predecessor to not succeed is `CheckFail`, `WorkFail`,
+///   `WorkInterrupted` or `ParentFail`, switch to `ParentFail`.
+/// * If the first predecessor to not succeed is `OpSkipped`, switch to
+///   `OpSkipped`.
+/// * If the first predecessor to not succeed is `DeadlineExceeded`, switch to
+///   `DeadlineExceeded`.
+///
+/// This matches the transitions of [`OpStatusUpdater`] and
+/// [`CleanOpStatusUpdater`].
+///
+/// [`OpStatusUpdater`]: crate::OpStatusUpdater
+/// [`CleanOpStatusUpdater`]: crate::CleanOpStatusUpdater
+#[derive(Debug)]
+pub struct OpStatusPropagator {
+    /// Operation the stations are visited for.
+    visit_op: VisitOp,
+    /// Predecessors that have not completed, indexed by station runtime ID.
+    predecessors_pending: Mutex<Vec<PredecessorsPending>>,
+}
+
+/// Predecessors of a station that have not completed.
+#[derive(Clone, Copy, Debug)]
+struct PredecessorsPending {
+    /// Number of predecessors that have not completed.
+    count: usize,
+    /// Status to transition to once all predecessors complete.
+    ///
+    /// This is `None` if a predecessor completed with a status that does not
+    /// determine this station's status.
+    op_status_next: Option<OpStatus>,
+}
+
+impl OpStatusPropagator {
+    /// Returns a new `OpStatusPropagator` for the destination's stations.
+    ///
+    /// # Parameters
+    ///
+    /// * `dest`: `Destination` with all the stations.
+    /// * `visit_op`: Operation the stations are visited for.
+    pub fn new<E>(dest: &Destination<E>, visit_op: VisitOp) -> Self
+    where
+        E: 'static,
+    {
+        let station_specs = dest.station_specs();
+        let predecessors_pending = station_specs
+            .graph()
+            .node_indices()
+            .map(|station_rt_id| {
+                let count = match visit_op {
+                    VisitOp::Create => station_specs
+                        .parents(station_rt_id)
+                        .iter(station_specs)
+                        .count(),
+                    VisitOp::Clean => station_specs
+                        .children(station_rt_id)
+                        .iter(station_specs)
+                        .count(),
+                };
+                PredecessorsPending {
+                    count,
+                    op_status_next: Some(OpStatus::OpQueued),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Self {
+            visit_op,
+            predecessors_pending: Mutex::new(predecessors_pending),
+        }
+    }
+
+    /// Updates the [`OpStatus`]es of the successors of a completed station.
+    ///
+    /// Successors whose predecessors have all completed are transitioned from
+    /// `ParentPending` to their next status. If a successor's progress is
+    /// being accessed, such as when its check is run speculatively, this waits
+    /// for it to be released so that the transition is never dropped.
+    ///
+    /// # Parameters
+    ///
+    /// * `dest`: `Destination` with all the stations and their progress
+    ///   information.
+    /// * `station_rt_id`: Runtime ID of the station that completed.
+    pub async fn station_complete<E>(&self, dest: &Destination<E>, station_rt_id: StationRtId)
+    where
+        E: 'static,
+    {
+        let station_specs = dest.station_specs();
+        let station_progresses = dest.station_progresses();
+        let op_status = match station_progresses.try_borrow(&station_rt_id) {
+            Ok(station_progress) => station_progress.op_status,
+            Err(_) => match station_progresses.read(&station_rt_id).await {
+                Some(station_progress) => station_progress.op_status,
+                None => return,
+            },
+        };
+        let successors = match self.visit_op {
+            VisitOp::Create => station_specs
+                .children(station_rt_id)
+                .iter(station_specs)
+                .map(|(_edge, successor_rt_id)| successor_rt_id)
+                .collect::<Vec<_>>(),
+            VisitOp::Clean => station_specs
+                .parents(station_rt_id)
+                .iter(station_specs)
+                .map(|(_edge, successor_rt_id)| successor_rt_id)
+                .collect::<Vec<_>>(),
+        };
+
+        // The pending counts are updated before waiting for any successor's
+        // progress, so the lock is not held across an `.await`.
+        let successors_ready = {
+            let mut predecessors_pending = self
+                .predecessors_pending
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            successors
+                .into_iter()
+                .filter_map(|successor_rt_id| {
+                    let successor_pending =
+                        predecessors_pending.get_mut(successor_rt_id.index())?;
+                    successor_pending.count = successor_pending.count.saturating_sub(1);
+                    if successor_pending.op_status_next == Some(OpStatus::OpQueued) {
+                        successor_pending.op_status_next = Self::op_status_next(op_status);
+                    }
+
+                    if successor_pending.count == 0 {
+                        successor_pending
+                            .op_status_next
+                            .map(|op_status_next| (successor_rt_id, op_status_next))
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Successors are updated without yielding when they are not accessed, so
+        // that they are transitioned before they are streamed to be visited.
+        // Otherwise, we queue for the lock before the stream does.
+        for (successor_rt_id, op_status_next) in successors_ready {
+            let successor_progress = match station_progresses.try_borrow_mut(&successor_rt_id) {
+                Ok(successor_progress) => Some(successor_progress),
+                Err(_) => station_progresses.write(&successor_rt_id).await,
+            };
+            if let Some(mut successor_progress) = successor_progress {
+                if successor_progress.op_status == OpStatus::ParentPending {
+                    successor_progress.op_status = op_status_next;
+                }
+            }
+        }
+    }
+
+    /// Returns the status a successor transitions to, given a completed
+    /// predecessor's status.
+    fn op_status_next(predecessor_op_status: OpStatus) -> Option<OpStatus> {
+        match predecessor_op_status {
+            OpStatus::WorkSuccess | OpStatus::WorkUnnecessary => Some(OpStatus::OpQueued),
+            OpStatus::CheckFail
+            | OpStatus::WorkFail
+            | OpStatus::VerifyFail
+            | OpStatus::Panicked
+            | OpStatus::ParentFail
+            | OpStatus::WorkInterrupted => Some(OpStatus::ParentFail),
+            OpStatus::OpSkipped => Some(OpStatus::OpSkipped),
+            OpStatus::DeadlineExceeded => Some(OpStatus::DeadlineExceeded),
+            OpStatus::SetupQueued
+            | OpStatus::SetupSuccess
+            | OpStatus::SetupFail
+            | OpStatus::ParentPending
+            | OpStatus::OpQueued
+            | OpStatus::WorkInProgress => None,
+        }
+    }
+}
@@ -16,8 +16,10 @@ use choochoo_rt_model::Destination;
 /// ## `ParentPending` Stations
 ///
 /// * If all parents are `WorkSuccess`, switch to `OpQueued`.
-/// * If at least one parent has `WorkFailed` or `ParentFail`, switch to
-///   `ParentFail`.
+/// * If at least one parent has `WorkFailed`, `WorkInterrupted` or
+///   `ParentFail`, switch to `ParentFail`.
+/// * If a parent is `OpSkipped`, switch to `OpSkipped`.
+/// * If a parent is `DeadlineExceeded`, switch to `DeadlineExceeded`.
 ///
 /// ## `ParentFail` Stations
 ///
@@ -41,8 +43,14 @@ use choochoo_rt_model::Destination;
 ///
 /// No transitions.
 ///
+/// ## `OpSkipped` / `WorkInterrupted` / `DeadlineExceeded`
+///
+/// No transitions -- [`Train::reach`] sets these when the train stops early
+/// according to its [`ExecutionPolicy`] or deadline.
+///
 /// [`StationMutRef::visit`]: crate::cfg_model::rt::StationMutRef::visit
 /// [`StationMutRef`]: crate::cfg_model::rt::StationMutRef
+/// [`ExecutionPolicy`]: crate::ExecutionPolicy
 /// [`Train::reach`]: crate::Train::reach
 #[derive(Debug)]
 pub struct OpStatusUpdater<E> {
@@ -93,6 +101,10 @@ where
     /// `ParentFail` transitions are propagated through to all later stations,
     /// on the condition that the nodes are added in order.
     ///
+    /// This reads the status of every parent of each updated station. When
+    /// updating stations as each one completes, [`OpStatusPropagator`] only
+    /// reads the completed station's status.
+    ///
     /// # Parameters
     ///
     /// * `dest`: `Destination` with all the stations and their progress
@@ -100,6 +112,7 @@ where
     /// * `station_rt_id`: Runtime ID of the parent station, whose children to
     ///   update.
     ///
+    /// [`OpStatusPropagator`]: crate::OpStatusPropagator
     /// [`StationMutRef`]: crate::cfg_model::rt::StationMutRef
     pub fn update_children(dest: &Destination<E>, station_rt_id: StationRtId) {
         let station_specs = dest.station_specs();
@@ -150,7 +163,12 @@ where
                     | OpStatus::ParentFail
                     | OpStatus::WorkSuccess
                     | OpStatus::WorkUnnecessary
-                    | OpStatus::WorkFail => None,
+                    | OpStatus::WorkFail
+                    | OpStatus::VerifyFail
+                    | OpStatus::Panicked
+                    | OpStatus::OpSkipped
+                    | OpStatus::WorkInterrupted
+                    | OpStatus::DeadlineExceeded => None,
                 }
             })
     }
@@ -188,10 +206,15 @@ where
                         OpStatus::CheckFail
                         | OpStatus::OpQueued
                         | OpStatus::WorkFail
+                        | OpStatus::VerifyFail
+                        | OpStatus::Panicked
                         | OpStatus::ParentPending
                         | OpStatus::WorkUnnecessary
                         | OpStatus::WorkSuccess
-                        | OpStatus::WorkInProgress => unreachable!(
+                        | OpStatus::WorkInProgress
+                        | OpStatus::OpSkipped
+                        | OpStatus::WorkInterrupted
+                        | OpStatus::DeadlineExceeded => unreachable!(
                             "Parent station status should not be {:?} during setup phase. This is a bug.",
                             parent_station_progress.op_status
                         ),
@@ -251,9 +274,20 @@ where
                             // If parent / ancestor has failed, indicate it in this station.
                             OpStatus::CheckFail
                             | OpStatus::WorkFail
-                            | OpStatus::ParentFail => {
+                            | OpStatus::VerifyFail
+                            | OpStatus::Panicked
+                            | OpStatus::ParentFail
+                            | OpStatus::WorkInterrupted => {
                                 return Err(Some(OpStatus::ParentFail));
                             }
+                            // If parent / ancestor was skipped, this station is also skipped.
+                            OpStatus::OpSkipped => {
+                                return Err(Some(OpStatus::OpSkipped));
+                            }
+                            // If parent / ancestor missed the deadline, so does this station.
+                            OpStatus::DeadlineExceeded => {
+                                return Err(Some(OpStatus::DeadlineExceeded));
+                            }
                             // Don't change `OpStatus` if parent is on any other `OpStatus`.
                             OpStatus::ParentPending
                             | OpStatus::OpQueued
@@ -0,0 +1,41 @@
+use std::{any::Any, future::Future, panic::AssertUnwindSafe};
+
+use futures::future::FutureExt;
+
+/// Catches panics in station function futures, so that a station that panics
+/// fails instead of aborting the train.
+///
+/// The panic hook still runs, so the panic is printed as usual.
+#[derive(Debug)]
+pub(crate) struct PanicCatcher;
+
+impl PanicCatcher {
+    /// Awaits the future, returning the panic message if it panics.
+    ///
+    /// # Parameters
+    ///
+    /// * `fut`: Future to await.
+    pub(crate) async fn catch<Fut>(fut: Fut) -> Result<Fut::Output, String>
+    where
+        Fut: Future,
+    {
+        AssertUnwindSafe(fut)
+            .catch_unwind()
+            .await
+            .map_err(|payload| Self::message(&*payload))
+    }
+
+    /// Returns the message of a panic payload.
+    ///
+    /// `panic!` payloads are either `&'static str` or `String`, other payloads
+    /// are described by their type.
+    fn message(payload: &(dyn Any + Send)) -> String {
+        if let Some(message) = payload.downcast_ref::<&'static str>() {
+            String::from(*message)
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            String::from("Box<dyn Any>")
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    process,
+    sync::{Mutex, PoisonError},
+};
+
+use choochoo_resource::ProfileDir;
+use choochoo_rt_model::Error;
+
+/// Number of [`ProfileLock`]s held by this process for each lock file.
+static LOCKS_HELD: Mutex<BTreeMap<PathBuf, usize>> = Mutex::new(BTreeMap::new());
+
+// **Note:** `Debug` is manually implemented to avoid the trait bound on `E`.
+/// Advisory lock on a profile directory, so that two trains do not mutate the
+/// same environment simultaneously.
+///
+/// The lock is a file in the profile directory containing the ID of the
+/// process that holds the lock, and the host it runs on. The file is removed
+/// when the lock is released or dropped.
+///
+/// A lock file is stale when it was written by a process on this host that is
+/// no longer running, such as when a train was killed. Stale locks are
+/// detected on hosts with a `/proc` file system, and replaced when acquiring
+/// the lock.
+///
+/// # Re-entrancy
+///
+/// The lock guards the profile against trains in *other processes*. Within a
+/// process, the lock is re-entrant: acquiring a lock that this process already
+/// holds succeeds, and increments a count of holders. The lock file is removed
+/// when the last holder releases it.
+///
+/// This is deliberate, as trains within one process -- such as tests whose
+/// destinations share the default workspace -- are expected to be coordinated
+/// by the application. Trains in the same process that visit the same profile
+/// concurrently are **not** prevented from doing so by this lock.
+pub struct ProfileLock<E> {
+    /// Path to the lock file.
+    lock_path: PathBuf,
+    /// Whether the lock is still held.
+    held: bool,
+    /// Marker.
+    marker: PhantomData<E>,
+}
+
+impl<E> ProfileLock<E> {
+    /// Name of the lock file in the profile directory.
+    pub const LOCK_FILE_NAME: &'static str = ".choochoo.lock";
+
+    /// Acquires the lock on the profile directory.
+    ///
+    /// Returns [`Error::ProfileLocked`] if another process holds the lock. If
+    /// this process already holds the lock, this returns another holder of
+    /// it -- see [re-entrancy](Self#re-entrancy).
+    ///
+    /// # Parameters
+    ///
+    /// * `profile_dir`: Profile directory to lock, which must exist.
+    pub fn acquire(profile_dir: &ProfileDir) -> Result<Self, Error<E>> {
+        let lock_path = profile_dir.join(Self::LOCK_FILE_NAME);
+        let mut locks_held = LOCKS_HELD.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(lock_count) = locks_held.get_mut(&lock_path) {
+            *lock_count += 1;
+            return Ok(Self::new(lock_path));
+        }
+
+        let lock_contents = format!("{}\n{}\n", process::id(), host_name());
+
+        // Retry once after removing a stale lock file.
+        for _ in 0..2 {
+            match OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&lock_path)
+            {
+                Ok(mut lock_file) => {
+                    return match lock_file.write_all(lock_contents.as_bytes()) {
+                        Ok(()) => {
+                            locks_held.insert(lock_path.clone(), 1);
+                            Ok(Self::new(lock_path))
+                        }
+                        Err(error) => Err(Error::ProfileLockAccess { lock_path, error }),
+                    };
+                }
+                Err(error) if error.kind() == ErrorKind::AlreadyExists => {
+                    let (pid, host) = Self::owner_read(&lock_path)?;
+                    if !Self::is_stale(pid, &host) {
+                        return Err(Error::ProfileLocked {
+                            profile_dir: profile_dir.clone(),
+                            lock_path,
+                            pid,
+                            host,
+                        });
+                    }
+
+                    match fs::remove_file(&lock_path) {
+                        Err(error) if error.kind() != ErrorKind::NotFound => {
+                            return Err(Error::ProfileLockAccess { lock_path, error });
+                        }
+                        Ok(()) | Err(_) => {}
+                    }
+                }
+                Err(error) => return Err(Error::ProfileLockAccess { lock_path, error }),
+            }
+        }
+
+        let (pid, host) = Self::owner_read(&lock_path)?;
+        Err(Error::ProfileLocked {
+            profile_dir: profile_dir.clone(),
+            lock_path,
+            pid,
+            host,
+        })
+    }
+
+    fn new(lock_path: PathBuf) -> Self {
+        Self {
+            lock_path,
+            held: true,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the path to the lock file.
+    pub fn lock_path(&self) -> &Path {
+        &self.lock_path
+    }
+
+    /// Releases the lock, removing the lock file if no other train in this
+    /// process holds it.
+    ///
+    /// This does nothing if the lock has already been released.
+    pub fn release(&mut self) -> Result<(), Error<E>> {
+        if !self.held {
+            return Ok(());
+        }
+        self.held = false;
+
+        let mut locks_held = LOCKS_HELD.lock().unwrap_or_else(PoisonError::into_inner);
+        match locks_held.get_mut(&self.lock_path) {
+            Some(lock_count) if *lock_count > 1 => {
+                *lock_count -= 1;
+                return Ok(());
+            }
+            _ => {
+                locks_held.remove(&self.lock_path);
+            }
+        }
+
+        match fs::remove_file(&self.lock_path) {
+            Err(error) if error.kind() != ErrorKind::NotFound => Err(Error::ProfileLockAccess {
+                lock_path: self.lock_path.clone(),
+                error,
+            }),
+            Ok(()) | Err(_) => Ok(()),
+        }
+    }
+
+    /// Returns the process ID and host recorded in the lock file.
+    fn owner_read(lock_path: &Path) -> Result<(u32, String), Error<E>> {
+        let lock_contents =
+            fs::read_to_string(lock_path).map_err(|error| Error::ProfileLockAccess {
+                lock_path: lock_path.to_path_buf(),
+                error,
+            })?;
+        let mut lines = lock_contents.lines();
+        // An unparseable lock file is treated as owned by an unknown process, which is
+        // never considered stale.
+        let pid = lines
+            .next()
+            .and_then(|pid| pid.trim().parse::<u32>().ok())
+            .unwrap_or(0);
+        let host = lines.next().unwrap_or_default().trim().to_string();
+
+        Ok((pid, host))
+    }
+
+    /// Returns whether the lock was written by a process on this host that is
+    /// no longer running.
+    ///
+    /// A lock written by this process that is not recorded as held is left
+    /// over from a process with the same ID, so it is also stale. Locks
+    /// written on other hosts are never considered stale, as the process
+    /// cannot be checked.
+    fn is_stale(pid: u32, host: &str) -> bool {
+        if pid == 0 || host != host_name() {
+            return false;
+        }
+
+        let proc_dir = Path::new("/proc");
+        pid == process::id() || (proc_dir.is_dir() && !proc_dir.join(pid.to_string()).exists())
+    }
+}
+
+impl<E> Drop for ProfileLock<E> {
+    fn drop(&mut self) {
+        // Errors cannot be returned from `drop`, and the lock file is detected as
+        // stale by the next train on this host.
+        let _ = self.release();
+    }
+}
+
+impl<E> std::fmt::Debug for ProfileLock<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProfileLock")
+            .field("lock_path", &self.lock_path)
+            .field("held", &self.held)
+            .field("marker", &self.marker)
+            .finish()
+    }
+}
+
+/// Returns the name of this host, or an empty string if it cannot be
+/// determined.
+pub(crate) fn host_name() -> String {
+    std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|host_name| host_name.trim().to_string())
+        .unwrap_or_default()
+}
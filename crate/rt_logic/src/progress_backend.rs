@@ -0,0 +1,52 @@
+use std::fmt;
+
+use choochoo_cfg_model::rt::StationProgress;
+use choochoo_rt_model::{Destination, TrainPlan};
+use tokio::task::JoinHandle;
+
+#[cfg(feature = "native")]
+pub use self::indicatif_progress_backend::IndicatifProgressBackend;
+pub use self::noop_progress_backend::NoopProgressBackend;
+
+#[cfg(feature = "native")]
+mod indicatif_progress_backend;
+mod noop_progress_backend;
+
+/// Renders station progress while a train visits stations.
+///
+/// The default backend is [`IndicatifProgressBackend`], which renders a
+/// progress bar per station. For large destinations where progress is not
+/// displayed, [`NoopProgressBackend`] avoids the cost of styling and joining
+/// the progress bars.
+///
+/// When the `native` feature is disabled, the default backend is
+/// [`NoopProgressBackend`], and progress may be rendered by a custom backend.
+///
+/// [`IndicatifProgressBackend`]: crate::IndicatifProgressBackend
+pub trait ProgressBackend<E>: fmt::Debug + Send + Sync {
+    /// Begins rendering the progress of the destination's stations.
+    ///
+    /// Returns a handle to the task that renders progress, if one is spawned.
+    fn init(&self, dest: &Destination<E>) -> Option<JoinHandle<std::io::Result<()>>>;
+
+    /// Renders a station's progress after its status changes.
+    fn update(&self, station_progress: &StationProgress);
+
+    /// Finishes rendering the progress of the destination's stations.
+    ///
+    /// This is called before waiting for the task returned by [`init`] to
+    /// complete.
+    ///
+    /// [`init`]: Self::init
+    fn finish(&self, dest: &Destination<E>);
+
+    /// Renders the stations that are about to be visited, before any station
+    /// is set up.
+    ///
+    /// This is called when only some of the destination's stations are
+    /// visited, such as by [`Train::clean_selection`]. The default
+    /// implementation does not render anything.
+    ///
+    /// [`Train::clean_selection`]: crate::Train::clean_selection
+    fn plan(&self, _dest: &Destination<E>, _train_plan: &TrainPlan) {}
+}
@@ -0,0 +1,50 @@
+use choochoo_cfg_model::{indicatif::MultiProgress, rt::StationProgress};
+use choochoo_rt_model::Destination;
+use tokio::task::JoinHandle;
+
+use crate::ProgressBackend;
+
+/// Renders a progress bar for each station using [`indicatif`].
+///
+/// This is the default progress backend.
+///
+/// [`indicatif`]: choochoo_cfg_model::indicatif
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IndicatifProgressBackend;
+
+impl<E> ProgressBackend<E> for IndicatifProgressBackend
+where
+    E: 'static,
+{
+    fn init(&self, dest: &Destination<E>) -> Option<JoinHandle<std::io::Result<()>>> {
+        let multi_progress = MultiProgress::new();
+        dest.station_specs()
+            .graph()
+            .node_indices()
+            .filter_map(|station_rt_id| dest.station_progresses().get(&station_rt_id))
+            .for_each(|station_progress| {
+                let progress_bar = station_progress.borrow().progress_bar().clone();
+                let progress_bar_for_tick = progress_bar.clone();
+                multi_progress.add(progress_bar);
+
+                // Needed to render all progress bars.
+                progress_bar_for_tick.tick();
+            });
+
+        Some(tokio::task::spawn_blocking(move || multi_progress.join()))
+    }
+
+    fn update(&self, station_progress: &StationProgress) {
+        station_progress.progress_style_update();
+    }
+
+    fn finish(&self, dest: &Destination<E>) {
+        // We need to finish / abandon all progress bars, otherwise the `MultiProgress`
+        // will never finish.
+        dest.stations_mut().for_each(|station| {
+            if !station.progress.progress_bar().is_finished() {
+                station.progress.progress_bar().finish_at_current_pos();
+            }
+        });
+    }
+}
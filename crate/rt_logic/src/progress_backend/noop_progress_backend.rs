@@ -0,0 +1,25 @@
+use choochoo_cfg_model::rt::StationProgress;
+use choochoo_rt_model::Destination;
+use tokio::task::JoinHandle;
+
+use crate::ProgressBackend;
+
+/// Does not render station progress.
+///
+/// Station progress bars are not styled, ticked, or joined while the train
+/// visits stations, which reduces overhead for destinations with many
+/// stations. Each station's [`OpStatus`] is still recorded.
+///
+/// [`OpStatus`]: choochoo_cfg_model::rt::OpStatus
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NoopProgressBackend;
+
+impl<E> ProgressBackend<E> for NoopProgressBackend {
+    fn init(&self, _dest: &Destination<E>) -> Option<JoinHandle<std::io::Result<()>>> {
+        None
+    }
+
+    fn update(&self, _station_progress: &StationProgress) {}
+
+    fn finish(&self, _dest: &Destination<E>) {}
+}
@@ -1,8 +1,17 @@
-use std::{fs::File, io::BufWriter, marker::PhantomData};
+use std::{
+    ffi::OsStr,
+    io,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
 
-use choochoo_cfg_model::{rt::ResIds, StationId};
+use choochoo_cfg_model::{
+    indexmap::IndexMap,
+    rt::{ResIdLogical, ResIdMetadata, ResIds, ResIdsPrevious},
+    StationId, StationSpecs,
+};
 use choochoo_resource::ProfileHistoryDir;
-use choochoo_rt_model::Error;
+use choochoo_rt_model::{Error, FileSystem, ResIdCosts};
 
 /// Persists resource IDs produced by stations into the profile history
 /// directory.
@@ -10,11 +19,21 @@ use choochoo_rt_model::Error;
 /// The path to each resource ID file is:
 ///
 /// ```text
-/// ${workspace}/target/.history/${profile}/${station_id}/${res_id_logical}
+/// ${workspace}/target/.history/${profile}/${station_id}/${res_id_logical}.json
 /// ```
 ///
 /// This is intended to record the resources created during each execution to
 /// help discovery of what resources exist.
+///
+/// [`ResIdMetadata`] attached to a resource ID is persisted alongside it, in
+/// `${res_id_logical}.meta`.
+///
+/// Each file is written to a temporary file which is then renamed, so that a
+/// crash while persisting does not corrupt the record of resources needed to
+/// clean up. Temporary files left over from a crash are detected when loading.
+///
+/// When the `native` feature is disabled, resource IDs are neither persisted
+/// nor loaded, as there may be no file system.
 #[derive(Debug)]
 pub struct ResIdPersister<E>(PhantomData<E>);
 
@@ -22,34 +41,300 @@ impl<E> ResIdPersister<E>
 where
     E: Send + Sync + 'static,
 {
+    /// Extension of each resource ID file.
+    const RES_ID_FILE_EXTENSION: &'static str = "json";
+    /// Extension appended to a resource ID file's name while it is written.
+    const RES_ID_FILE_TMP_EXTENSION: &'static str = "tmp";
+    /// Extension of each resource ID metadata file.
+    const RES_ID_METADATA_FILE_EXTENSION: &'static str = "meta";
+
     /// Persists resource IDs produced by stations into the profile history
     /// directory.
     ///
     /// The path to each resource ID file is:
     ///
     /// ```text
-    /// ${workspace}/target/.history/${profile}/${station_id}/${res_id_logical}
+    /// ${workspace}/target/.history/${profile}/${station_id}/${res_id_logical}.json
     /// ```
     pub async fn persist(
+        file_system: &dyn FileSystem,
         profile_history_dir: &ProfileHistoryDir,
         station_id: &StationId,
         res_ids: &ResIds,
     ) -> Result<(), Error<E>> {
-        res_ids
-            .iter()
-            .try_for_each(|(res_id_logical, res_id_physical)| {
-                let mut res_id_path = profile_history_dir.join(res_id_logical.as_str());
-                res_id_path.set_extension("json");
+        if (res_ids.is_empty() && res_ids.metadata().is_empty()) || cfg!(not(feature = "native")) {
+            return Ok(());
+        }
 
-                let res_id_path = File::create(&res_id_path).map_err(|error| {
+        let res_id_write_error = |error: io::Error| {
+            let station_id = station_id.clone();
+            Error::<E>::ResIdWrite { station_id, error }
+        };
+        let station_history_dir = profile_history_dir.join(station_id.as_ref());
+        file_system
+            .create_dir_all(&station_history_dir)
+            .await
+            .map_err(res_id_write_error)?;
+
+        for (res_id_logical, res_id_physical) in res_ids.iter() {
+            let res_id_path = station_history_dir
+                .join(format!("{res_id_logical}.{}", Self::RES_ID_FILE_EXTENSION));
+            let res_id_serialized =
+                serde_json::to_vec_pretty(res_id_physical).map_err(|error| {
                     let station_id = station_id.clone();
-                    Error::<E>::ResIdWrite { station_id, error }
+                    Error::ResIdSerialize { station_id, error }
                 })?;
-                let writer = BufWriter::new(res_id_path);
-                serde_json::to_writer_pretty(writer, res_id_physical).map_err(|error| {
+
+            Self::res_id_write_atomic(file_system, &res_id_path, &res_id_serialized)
+                .await
+                .map_err(res_id_write_error)?;
+        }
+
+        for (res_id_logical, res_id_metadata) in res_ids.metadata().iter() {
+            let res_id_metadata_path = station_history_dir.join(format!(
+                "{res_id_logical}.{}",
+                Self::RES_ID_METADATA_FILE_EXTENSION
+            ));
+            let res_id_metadata_serialized =
+                serde_json::to_vec_pretty(res_id_metadata).map_err(|error| {
                     let station_id = station_id.clone();
                     Error::ResIdSerialize { station_id, error }
-                })
-            })
+                })?;
+
+            Self::res_id_write_atomic(
+                file_system,
+                &res_id_metadata_path,
+                &res_id_metadata_serialized,
+            )
+            .await
+            .map_err(res_id_write_error)?;
+        }
+
+        // Persist the renames.
+        file_system
+            .dir_sync(&station_history_dir)
+            .await
+            .map_err(res_id_write_error)
+    }
+
+    /// Removes the resource IDs persisted by a station, such as after its
+    /// resources are rolled back.
+    ///
+    /// It is not an error if the station has not persisted any resource IDs.
+    pub async fn clear(
+        file_system: &dyn FileSystem,
+        profile_history_dir: &ProfileHistoryDir,
+        station_id: &StationId,
+    ) -> Result<(), Error<E>> {
+        if cfg!(not(feature = "native")) {
+            return Ok(());
+        }
+
+        let station_history_dir = profile_history_dir.join(station_id.as_ref());
+        match file_system.remove_dir_all(&station_history_dir).await {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => Err(Error::ResIdWrite {
+                station_id: station_id.clone(),
+                error,
+            }),
+            Ok(()) | Err(_) => Ok(()),
+        }
+    }
+
+    /// Writes a serialized resource ID to a temporary file, then renames it to
+    /// the resource ID path.
+    async fn res_id_write_atomic(
+        file_system: &dyn FileSystem,
+        res_id_path: &Path,
+        res_id_serialized: &[u8],
+    ) -> io::Result<()> {
+        let res_id_path_tmp = Self::res_id_path_tmp(res_id_path);
+
+        file_system
+            .write(&res_id_path_tmp, res_id_serialized)
+            .await?;
+        file_system.rename(&res_id_path_tmp, res_id_path).await
+    }
+
+    /// Returns the path of the temporary file that a resource ID is written to.
+    fn res_id_path_tmp(res_id_path: &Path) -> PathBuf {
+        let mut res_id_file_name_tmp = res_id_path
+            .file_name()
+            .map(OsStr::to_os_string)
+            .unwrap_or_default();
+        res_id_file_name_tmp.push(".");
+        res_id_file_name_tmp.push(Self::RES_ID_FILE_TMP_EXTENSION);
+        res_id_path.with_file_name(res_id_file_name_tmp)
+    }
+
+    /// Loads resource IDs persisted by each station in previous executions.
+    ///
+    /// Stations that have not persisted any resource IDs are not included.
+    pub async fn load(
+        file_system: &dyn FileSystem,
+        profile_history_dir: &ProfileHistoryDir,
+        station_specs: &StationSpecs<E>,
+    ) -> Result<ResIdsPrevious, Error<E>> {
+        let mut res_ids_previous = ResIdsPrevious::new();
+        if cfg!(not(feature = "native")) {
+            return Ok(res_ids_previous);
+        }
+
+        for (station_rt_id, station_spec) in station_specs.iter_insertion_with_indices() {
+            let station_res_ids = Self::station_files_load(
+                file_system,
+                profile_history_dir,
+                station_spec.id(),
+                Self::RES_ID_FILE_EXTENSION,
+            )
+            .await?;
+            if !station_res_ids.is_empty() {
+                res_ids_previous.insert(station_rt_id, station_res_ids);
+            }
+        }
+
+        Ok(res_ids_previous)
+    }
+
+    /// Loads the estimated monthly cost of the resources persisted by each
+    /// station, from the [`ResIdMetadata`] attached to each resource ID.
+    ///
+    /// Stations whose resources have no cost are not included.
+    pub async fn res_id_costs_load(
+        file_system: &dyn FileSystem,
+        profile_history_dir: &ProfileHistoryDir,
+        station_specs: &StationSpecs<E>,
+    ) -> Result<ResIdCosts, Error<E>> {
+        let mut res_id_costs = ResIdCosts::new();
+        if cfg!(not(feature = "native")) {
+            return Ok(res_id_costs);
+        }
+
+        for station_spec in station_specs.iter_insertion() {
+            let station_id = station_spec.id();
+            let station_metadata = Self::station_files_load(
+                file_system,
+                profile_history_dir,
+                station_id,
+                Self::RES_ID_METADATA_FILE_EXTENSION,
+            )
+            .await?;
+            for (_, res_id_metadata) in station_metadata {
+                let res_id_metadata = serde_json::from_value::<ResIdMetadata>(res_id_metadata)
+                    .map_err(|error| {
+                        let station_id = station_id.clone();
+                        Error::ResIdDeserialize { station_id, error }
+                    })?;
+                res_id_costs.metadata_add(station_id, &res_id_metadata);
+            }
+        }
+
+        Ok(res_id_costs)
+    }
+
+    /// Loads the resource IDs or resource ID metadata persisted by a station
+    /// in previous executions, from files with the given extension.
+    async fn station_files_load(
+        file_system: &dyn FileSystem,
+        profile_history_dir: &ProfileHistoryDir,
+        station_id: &StationId,
+        file_extension: &str,
+    ) -> Result<IndexMap<ResIdLogical, serde_json::Value>, Error<E>> {
+        let station_history_dir = profile_history_dir.join(station_id.as_ref());
+        if !file_system.is_dir(&station_history_dir).await {
+            return Ok(IndexMap::new());
+        }
+        Self::res_id_tmp_files_recover(file_system, &station_history_dir, station_id).await?;
+
+        let res_id_read_error = |error: io::Error| {
+            let station_id = station_id.clone();
+            Error::<E>::ResIdRead { station_id, error }
+        };
+        let res_id_logicals_and_paths = file_system
+            .read_dir(&station_history_dir)
+            .await
+            .map_err(res_id_read_error)?
+            .into_iter()
+            .filter_map(|res_id_path| {
+                let is_station_file =
+                    res_id_path.extension().and_then(|ext| ext.to_str()) == Some(file_extension);
+                let res_id_logical = res_id_path
+                    .file_stem()
+                    .and_then(|file_stem| file_stem.to_str())
+                    .map(ResIdLogical::new);
+
+                res_id_logical
+                    .filter(|_| is_station_file)
+                    .map(|res_id_logical| (res_id_logical, res_id_path))
+            });
+
+        let mut station_files = IndexMap::new();
+        for (res_id_logical, res_id_path) in res_id_logicals_and_paths {
+            let res_id_serialized = file_system
+                .read(&res_id_path)
+                .await
+                .map_err(res_id_read_error)?;
+            let value = serde_json::from_slice(&res_id_serialized).map_err(|error| {
+                let station_id = station_id.clone();
+                Error::ResIdDeserialize { station_id, error }
+            })?;
+
+            station_files.insert(res_id_logical, value);
+        }
+        station_files.sort_keys();
+
+        Ok(station_files)
+    }
+
+    /// Renames temporary resource ID files that were completely written
+    /// before a crash, and returns an error if any were partially written.
+    async fn res_id_tmp_files_recover(
+        file_system: &dyn FileSystem,
+        station_history_dir: &Path,
+        station_id: &StationId,
+    ) -> Result<(), Error<E>> {
+        let res_id_read_error = |error: io::Error| {
+            let station_id = station_id.clone();
+            Error::<E>::ResIdRead { station_id, error }
+        };
+        let res_id_paths_tmp = file_system
+            .read_dir(station_history_dir)
+            .await
+            .map_err(res_id_read_error)?
+            .into_iter()
+            .filter(|res_id_path| Self::is_res_id_path_tmp(res_id_path));
+
+        for res_id_path_tmp in res_id_paths_tmp {
+            let res_id_serialized = file_system
+                .read(&res_id_path_tmp)
+                .await
+                .map_err(res_id_read_error)?;
+            if serde_json::from_slice::<serde_json::Value>(&res_id_serialized).is_err() {
+                return Err(Error::ResIdPartiallyWritten {
+                    station_id: station_id.clone(),
+                    res_id_path: res_id_path_tmp,
+                });
+            }
+
+            // Strips the temporary extension.
+            let res_id_path = res_id_path_tmp.with_extension("");
+            file_system
+                .rename(&res_id_path_tmp, &res_id_path)
+                .await
+                .map_err(res_id_read_error)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether the path is a temporary resource ID or resource ID
+    /// metadata file.
+    fn is_res_id_path_tmp(res_id_path: &Path) -> bool {
+        let extension_inner = res_id_path
+            .file_stem()
+            .map(Path::new)
+            .and_then(Path::extension);
+        res_id_path.extension() == Some(OsStr::new(Self::RES_ID_FILE_TMP_EXTENSION))
+            && (extension_inner == Some(OsStr::new(Self::RES_ID_FILE_EXTENSION))
+                || extension_inner == Some(OsStr::new(Self::RES_ID_METADATA_FILE_EXTENSION)))
     }
 }
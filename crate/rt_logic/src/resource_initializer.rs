@@ -1,9 +1,24 @@
 use std::marker::PhantomData;
+#[cfg(feature = "native")]
+use std::path::Path;
 
-use choochoo_cfg_model::rt::TrainResources;
-use choochoo_rt_model::{Destination, DestinationDirCalc, DestinationDirs, Error};
+#[cfg(feature = "native")]
+use choochoo_cfg_model::rt::StationDir;
+use choochoo_cfg_model::{
+    rt::{StationEnv, TrainResources},
+    ResourceInitFn,
+};
+#[cfg(feature = "native")]
+use choochoo_rt_model::FileSystem;
+use choochoo_rt_model::{
+    Destination, DestinationDirs, Error, ProfileStore, StationDirs, StationEnvs,
+};
+#[cfg(feature = "native")]
 use futures::stream::{self, StreamExt, TryStreamExt};
-use tokio::fs;
+
+#[cfg(feature = "native")]
+use crate::ProfileLock;
+use crate::ResIdPersister;
 
 /// Initializes execution resources and adds them to the train resources.
 ///
@@ -15,15 +30,25 @@ use tokio::fs;
 /// * [`ProfileHistoryStationDirs`]
 /// * [`Profile`]
 /// * [`ProfileDir`]
+/// * [`CacheDir`]
 /// * [`StationDirs`]
+/// * [`StationEnvs`]
+/// * [`ResIdsPrevious`]
+/// * [`ProfileStore`]
+/// * [`ProfileLock`], when the `native` feature is enabled and the
+///   destination's file system is native.
+/// * `reqwest::Client`, when the `http` feature is enabled.
+///
+/// All directories are ensured to exist. When the `native` feature is
+/// disabled, directories are not created.
 ///
-/// All directories are ensured to exist.
+/// [`ResIdsPrevious`]: choochoo_cfg_model::rt::ResIdsPrevious
 #[derive(Debug)]
 pub struct ResourceInitializer<E>(PhantomData<E>);
 
 impl<E> ResourceInitializer<E>
 where
-    E: 'static,
+    E: Send + Sync + 'static,
 {
     /// Initializes execution resources and adds them to the train resources.
     ///
@@ -35,9 +60,31 @@ where
     /// * [`ProfileHistoryStationDirs`]
     /// * [`Profile`]
     /// * [`ProfileDir`]
+    /// * [`CacheDir`]
+    /// * [`CacheDir`]
     /// * [`StationDirs`]
+    /// * [`StationEnvs`]
+    /// * [`ResIdsPrevious`]
+    /// * [`ProfileStore`]
+    /// * [`ProfileLock`], when the `native` feature is enabled and the
+    ///   destination's file system is native.
+    /// * `reqwest::Client`, when the `http` feature is enabled.
+    ///
+    /// All directories are ensured to exist. When the `native` feature is
+    /// disabled, directories are not created.
+    ///
+    /// The profile directory is locked so that other trains cannot use it until
+    /// the lock is released. Returns [`Error::ProfileLocked`] if another
+    /// process holds the lock.
     ///
-    /// All directories are ensured to exist.
+    /// Each station's environment is the [`StationEnv`] in the train
+    /// resources, with the working directory defaulting to the station's
+    /// directory, and the station's overrides applied.
+    ///
+    /// If there is already a `reqwest::Client` in the train resources, it is
+    /// shared with stations instead of a client with the default settings.
+    ///
+    /// [`ResIdsPrevious`]: choochoo_cfg_model::rt::ResIdsPrevious
     pub async fn initialize(
         dest: &Destination<E>,
         train_resources: &mut TrainResources<E>,
@@ -47,20 +94,137 @@ where
             history_dir,
             profile_history_dir,
             profile_dir,
+            cache_dir,
             station_dirs,
         } = dest.dirs().clone();
         let profile = dest.profile().clone();
-        let target_dir = workspace_dir.join(DestinationDirCalc::<E>::TARGET_DIR_NAME);
+
+        #[cfg(feature = "native")]
+        Self::dirs_ensure_exist(dest.file_system(), dest.dirs()).await?;
+        // The lock is a file on disk, so it is only needed when other
+        // processes can see the profile directory.
+        #[cfg(feature = "native")]
+        let profile_lock = if dest.file_system().is_native() {
+            Some(ProfileLock::<E>::acquire(&profile_dir)?)
+        } else {
+            None
+        };
+
+        let res_ids_previous = ResIdPersister::<E>::load(
+            dest.file_system(),
+            &profile_history_dir,
+            dest.station_specs(),
+        )
+        .await?;
+        let station_envs = Self::station_envs_resolve(dest, train_resources, &station_dirs);
+        let profile_store = ProfileStore::new(&profile_dir);
+
+        train_resources.insert(workspace_dir);
+        train_resources.insert(history_dir);
+        train_resources.insert(profile_history_dir);
+        train_resources.insert(profile);
+        train_resources.insert(profile_dir);
+        train_resources.insert(cache_dir);
+        train_resources.insert(station_dirs);
+        train_resources.insert(station_envs);
+        train_resources.insert(res_ids_previous);
+        train_resources.insert(profile_store);
+        #[cfg(feature = "native")]
+        if let Some(profile_lock) = profile_lock {
+            train_resources.insert(profile_lock);
+        }
+        #[cfg(feature = "http")]
+        if train_resources.try_borrow::<reqwest::Client>().is_err() {
+            train_resources.insert(reqwest::Client::new());
+        }
+
+        Ok(())
+    }
+
+    /// Runs user registered resource initializers in order, after the
+    /// execution resources are initialized.
+    ///
+    /// Each initializer may insert resources such as API clients,
+    /// configuration, and secrets, and may borrow resources inserted by
+    /// earlier initializers. If an initializer fails, the remaining
+    /// initializers are not run, and [`Error::ResourceInit`] is returned.
+    pub async fn initialize_user(
+        resource_init_fns: &[ResourceInitFn<E>],
+        train_resources: &mut TrainResources<E>,
+    ) -> Result<(), Error<E>> {
+        for resource_init_fn in resource_init_fns {
+            resource_init_fn
+                .call(train_resources)
+                .await
+                .map_err(|error| Error::ResourceInit {
+                    name: resource_init_fn.name().to_string(),
+                    error,
+                })?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the environment for each station's external commands.
+    fn station_envs_resolve(
+        dest: &Destination<E>,
+        train_resources: &TrainResources<E>,
+        station_dirs: &StationDirs,
+    ) -> StationEnvs {
+        let station_env_base = train_resources
+            .try_borrow::<StationEnv>()
+            .map(|station_env| StationEnv::clone(&station_env))
+            .unwrap_or_default();
+
+        let station_specs = dest.station_specs();
+        let mut station_envs = StationEnvs::with_capacity(station_specs.node_count());
+        station_specs
+            .iter_insertion_with_indices()
+            .for_each(|(station_rt_id, station_spec)| {
+                let station_env_base = match station_dirs.get(&station_rt_id) {
+                    Some(station_dir) if station_env_base.cwd().is_none() => {
+                        station_env_base.clone().with_cwd(station_dir.to_path_buf())
+                    }
+                    _ => station_env_base.clone(),
+                };
+                let station_env = station_env_base.merge(station_spec.env());
+                station_envs.insert(station_rt_id, station_env);
+            });
+
+        station_envs
+    }
+
+    /// Ensures the workspace, target, history, profile, cache, and station
+    /// directories exist, along with each station's standard subdirectories.
+    #[cfg(feature = "native")]
+    async fn dirs_ensure_exist(
+        file_system: &dyn FileSystem,
+        dest_dirs: &DestinationDirs,
+    ) -> Result<(), Error<E>> {
+        let DestinationDirs {
+            workspace_dir,
+            history_dir,
+            profile_history_dir,
+            profile_dir,
+            cache_dir,
+            station_dirs,
+        } = dest_dirs;
+        // The profile directory may be outside the workspace, so the target
+        // directory is its parent rather than `${workspace}/target`.
+        let target_dir = profile_dir
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| profile_dir.to_path_buf());
 
         macro_rules! ensure_dir_exists {
             ($dir:ident, $error_variant:ident) => {
-                if !$dir.exists() {
-                    fs::create_dir_all(&$dir)
-                        .await
-                        .map_err(|error| Error::$error_variant {
+                if !file_system.exists(&$dir).await {
+                    file_system.create_dir_all(&$dir).await.map_err(|error| {
+                        Error::$error_variant {
                             $dir: $dir.clone(),
                             error,
-                        })?;
+                        }
+                    })?;
                 }
             };
         }
@@ -71,21 +235,19 @@ where
         ensure_dir_exists!(profile_history_dir, ProfileHistoryDirCreate);
 
         ensure_dir_exists!(profile_dir, ProfileDirCreate);
+        ensure_dir_exists!(cache_dir, CacheDirCreate);
         stream::iter(station_dirs.iter())
             .map(Result::<_, Error<E>>::Ok)
             .try_for_each_concurrent(4, |(_, station_dir)| async move {
                 ensure_dir_exists!(station_dir, StationDirCreate);
+                for station_subdir in station_dir.subdirs() {
+                    let station_dir = StationDir::new(station_subdir);
+                    ensure_dir_exists!(station_dir, StationDirCreate);
+                }
                 Ok(())
             })
             .await?;
 
-        train_resources.insert(workspace_dir);
-        train_resources.insert(history_dir);
-        train_resources.insert(profile_history_dir);
-        train_resources.insert(profile);
-        train_resources.insert(profile_dir);
-        train_resources.insert(station_dirs);
-
         Ok(())
     }
 }
@@ -0,0 +1,71 @@
+use std::{any::TypeId, num::NonZeroUsize};
+
+use choochoo_cfg_model::indexmap::IndexMap;
+
+/// Maximum number of stations that may access each resource concurrently.
+///
+/// A station accesses a resource when one of its functions borrows the
+/// resource from [`TrainResources`]. Stations that are ready to be visited
+/// wait until the number of stations accessing each of their limited
+/// resources is below the limit.
+///
+/// This is useful when a resource represents something with limited
+/// capacity, such as a connection pool or a rate limited API client, while
+/// stations that don't access it are visited without waiting.
+///
+/// [`TrainResources`]: choochoo_cfg_model::rt::TrainResources
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// Maximum number of concurrent accesses of each resource.
+    limits: IndexMap<TypeId, NonZeroUsize>,
+    /// Type name of each limited resource.
+    type_names: IndexMap<TypeId, &'static str>,
+}
+
+impl ResourceLimits {
+    /// Returns new, empty `ResourceLimits`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of stations that may access resource `R`
+    /// concurrently.
+    #[must_use]
+    pub fn with_limit<R>(mut self, limit: NonZeroUsize) -> Self
+    where
+        R: 'static,
+    {
+        self.limits.insert(TypeId::of::<R>(), limit);
+        self.type_names
+            .insert(TypeId::of::<R>(), std::any::type_name::<R>());
+        self
+    }
+
+    /// Returns the maximum number of stations that may access resource `R`
+    /// concurrently, if limited.
+    pub fn limit<R>(&self) -> Option<NonZeroUsize>
+    where
+        R: 'static,
+    {
+        self.limits.get(&TypeId::of::<R>()).copied()
+    }
+
+    /// Returns an iterator over the limited resources' [`TypeId`]s and their
+    /// limits.
+    pub fn iter(&self) -> impl Iterator<Item = (&TypeId, &NonZeroUsize)> + '_ {
+        self.limits.iter()
+    }
+
+    /// Returns an iterator over the limited resources' [`TypeId`]s and type
+    /// names.
+    pub fn type_names(&self) -> impl Iterator<Item = (TypeId, &'static str)> + '_ {
+        self.type_names
+            .iter()
+            .map(|(type_id, type_name)| (*type_id, *type_name))
+    }
+
+    /// Returns whether no resources are limited.
+    pub fn is_empty(&self) -> bool {
+        self.limits.is_empty()
+    }
+}
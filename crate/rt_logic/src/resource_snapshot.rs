@@ -0,0 +1,218 @@
+use std::{
+    fmt,
+    fs::{self, File},
+    io::{self, ErrorKind, Write},
+    path::{Path, PathBuf},
+};
+
+use choochoo_cfg_model::rt::{ResourceSerialize, TrainResources};
+use choochoo_resource::ProfileHistoryDir;
+use choochoo_rt_model::Error;
+use serde_json::{Map, Value};
+
+/// Serializes a resource in the train resources, if it is present.
+type ResourceExportFn<E> = fn(&TrainResources<E>) -> Option<Result<Value, serde_json::Error>>;
+/// Deserializes a resource and inserts it into the train resources.
+type ResourceImportFn<E> = fn(&mut TrainResources<E>, Value) -> Result<(), serde_json::Error>;
+
+// **Note:** `Clone` and `Debug` are manually implemented to avoid the trait
+// bound on `E`.
+/// Snapshot of selected [`TrainResources`] that is saved at the end of a
+/// train's drive, and loaded at the start of the next.
+///
+/// Resources must implement [`ResourceSerialize`], and are stored under their
+/// [`SNAPSHOT_KEY`] in:
+///
+/// ```text
+/// ${workspace}/target/.history/${profile}/resource_snapshot.json
+/// ```
+///
+/// Resources that are not present in the train resources when the snapshot is
+/// saved are left out of it, and keys in the snapshot that are not selected
+/// are ignored when it is loaded.
+///
+/// [`SNAPSHOT_KEY`]: ResourceSerialize::SNAPSHOT_KEY
+pub struct ResourceSnapshot<E> {
+    /// Key, export, and import functions of each selected resource.
+    resource_fns: Vec<(&'static str, ResourceExportFn<E>, ResourceImportFn<E>)>,
+}
+
+impl<E> ResourceSnapshot<E>
+where
+    E: 'static,
+{
+    /// Name of the snapshot file in the profile history directory.
+    pub const FILE_NAME: &'static str = "resource_snapshot.json";
+
+    /// Returns a new `ResourceSnapshot` with no resources selected.
+    pub fn new() -> Self {
+        Self {
+            resource_fns: Vec::new(),
+        }
+    }
+
+    /// Selects a resource to include in the snapshot.
+    #[must_use]
+    pub fn with_resource<R>(mut self) -> Self
+    where
+        R: ResourceSerialize,
+    {
+        self.resource_fns.push((
+            R::SNAPSHOT_KEY,
+            Self::resource_export::<R>,
+            Self::resource_import::<R>,
+        ));
+        self
+    }
+
+    /// Returns the keys of the selected resources.
+    pub fn keys(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.resource_fns.iter().map(|(key, _, _)| *key)
+    }
+
+    /// Returns the path to the snapshot in the profile history directory.
+    pub fn path(profile_history_dir: &ProfileHistoryDir) -> PathBuf {
+        profile_history_dir.join(Self::FILE_NAME)
+    }
+
+    /// Inserts the selected resources from the snapshot into the train
+    /// resources, replacing existing resources of the same type.
+    ///
+    /// Does nothing if the snapshot does not exist.
+    pub fn load(
+        &self,
+        profile_history_dir: &ProfileHistoryDir,
+        train_resources: &mut TrainResources<E>,
+    ) -> Result<(), Error<E>> {
+        if self.resource_fns.is_empty() {
+            return Ok(());
+        }
+
+        let path = Self::path(profile_history_dir);
+        let snapshot = match fs::read(&path) {
+            Ok(snapshot) => snapshot,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(error) => return Err(Error::ResourceSnapshotRead { path, error }),
+        };
+        let mut snapshot =
+            serde_json::from_slice::<Map<String, Value>>(&snapshot).map_err(|error| {
+                Error::ResourceSnapshotDeserialize {
+                    path: path.clone(),
+                    key: None,
+                    error,
+                }
+            })?;
+
+        self.resource_fns
+            .iter()
+            .try_for_each(|(key, _resource_export_fn, resource_import_fn)| {
+                match snapshot.remove(*key) {
+                    Some(value) => resource_import_fn(train_resources, value).map_err(|error| {
+                        Error::ResourceSnapshotDeserialize {
+                            path: path.clone(),
+                            key: Some(*key),
+                            error,
+                        }
+                    }),
+                    None => Ok(()),
+                }
+            })
+    }
+
+    /// Saves the selected resources that are present in the train resources to
+    /// the snapshot.
+    ///
+    /// The snapshot is written to a temporary file which is then renamed, so
+    /// that a crash while saving does not corrupt the previous snapshot.
+    pub fn save(
+        &self,
+        profile_history_dir: &ProfileHistoryDir,
+        train_resources: &TrainResources<E>,
+    ) -> Result<(), Error<E>> {
+        if self.resource_fns.is_empty() {
+            return Ok(());
+        }
+
+        let snapshot = self
+            .resource_fns
+            .iter()
+            .filter_map(|(key, resource_export_fn, _resource_import_fn)| {
+                resource_export_fn(train_resources).map(|value| {
+                    value
+                        .map(|value| (key.to_string(), value))
+                        .map_err(|error| Error::ResourceSnapshotSerialize { key: *key, error })
+                })
+            })
+            .collect::<Result<Map<String, Value>, Error<E>>>()?;
+
+        let path = Self::path(profile_history_dir);
+        Self::snapshot_write_atomic(&path, Value::Object(snapshot).to_string().as_bytes())
+            .map_err(|error| Error::ResourceSnapshotWrite { path, error })
+    }
+
+    /// Writes the serialized snapshot to a temporary file, then renames it to
+    /// the snapshot path.
+    fn snapshot_write_atomic(path: &Path, snapshot_serialized: &[u8]) -> io::Result<()> {
+        let path_tmp = path.with_extension("json.tmp");
+
+        let mut snapshot_file = File::create(&path_tmp)?;
+        snapshot_file.write_all(snapshot_serialized)?;
+        snapshot_file.sync_all()?;
+
+        fs::rename(&path_tmp, path)
+    }
+
+    fn resource_export<R>(
+        train_resources: &TrainResources<E>,
+    ) -> Option<Result<Value, serde_json::Error>>
+    where
+        R: ResourceSerialize,
+    {
+        train_resources
+            .try_borrow::<R>()
+            .ok()
+            .map(|resource| serde_json::to_value(&*resource))
+    }
+
+    fn resource_import<R>(
+        train_resources: &mut TrainResources<E>,
+        value: Value,
+    ) -> Result<(), serde_json::Error>
+    where
+        R: ResourceSerialize,
+    {
+        let resource = serde_json::from_value::<R>(value)?;
+        train_resources.insert(resource);
+        Ok(())
+    }
+}
+
+impl<E> Default for ResourceSnapshot<E>
+where
+    E: 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> Clone for ResourceSnapshot<E> {
+    fn clone(&self) -> Self {
+        Self {
+            resource_fns: self.resource_fns.clone(),
+        }
+    }
+}
+
+impl<E> fmt::Debug for ResourceSnapshot<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keys = self
+            .resource_fns
+            .iter()
+            .map(|(key, _, _)| *key)
+            .collect::<Vec<&'static str>>();
+        f.debug_struct("ResourceSnapshot")
+            .field("resource_fns", &keys)
+            .finish()
+    }
+}
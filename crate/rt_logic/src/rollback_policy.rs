@@ -0,0 +1,25 @@
+/// Whether a station's partially created resources are cleaned when its
+/// create visit fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RollbackPolicy {
+    /// Resources created by a failed station are left in place.
+    ///
+    /// This is the default.
+    Never,
+    /// When a station's work or verify function fails and returns resource
+    /// IDs, the station's clean functions are run.
+    ///
+    /// The resource IDs are available to the clean functions through
+    /// [`ResIdsPrevious`]. The station's clean setup function is not run, so
+    /// the clean functions may only borrow resources that are inserted by the
+    /// create setup function.
+    ///
+    /// [`ResIdsPrevious`]: choochoo_cfg_model::rt::ResIdsPrevious
+    OnFailure,
+}
+
+impl Default for RollbackPolicy {
+    fn default() -> Self {
+        Self::Never
+    }
+}
@@ -0,0 +1,132 @@
+use std::{
+    collections::BTreeSet,
+    fmt,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+use choochoo_cfg_model::{
+    rt::{StationInputsDigests, StationMut, TrainResources},
+    StationSpec,
+};
+use choochoo_ops::{Checksum, ChecksumAlgorithm, OpsError};
+use choochoo_resource::WorkspaceDir;
+use choochoo_rt_model::{error::StationSpecError, FileSystem};
+use glob::Pattern;
+
+/// Computes the digest of a station's declared input files.
+///
+/// Each input pattern is matched relative to the workspace directory. Only
+/// files are included -- directories that match a pattern are not traversed.
+///
+/// The digest is a SHA-256 [`Checksum`] over each matched file's path relative
+/// to the workspace directory and its contents, in path order, so renaming,
+/// adding, or removing an input file also changes the digest.
+#[derive(Debug)]
+pub struct StationInputsHasher<E>(PhantomData<E>);
+
+impl<E> StationInputsHasher<E>
+where
+    E: From<StationSpecError> + fmt::Debug + Send + Sync + 'static,
+{
+    /// Computes the digest of the station's declared inputs, and inserts it
+    /// into the [`StationInputsDigests`].
+    ///
+    /// The digest recorded by the station's last successful work is read
+    /// through the file system, and inserted as well. This does nothing if
+    /// the station does not declare any inputs.
+    ///
+    /// # Parameters
+    ///
+    /// * `file_system`: File system that the recorded digest is read from.
+    /// * `train_resources`: Train resources, which must contain the
+    ///   [`WorkspaceDir`] and [`StationInputsDigests`].
+    /// * `station`: Station whose inputs to hash.
+    pub async fn digest_insert(
+        file_system: &dyn FileSystem,
+        train_resources: &TrainResources<E>,
+        station: &StationMut<'_, E>,
+    ) -> Result<(), E> {
+        if station.spec.inputs().is_empty() {
+            return Ok(());
+        }
+
+        let workspace_dir = train_resources.borrow::<WorkspaceDir>().to_path_buf();
+        let digest = Self::digest(&workspace_dir, station.spec)
+            .await
+            .map_err(E::from)?;
+        // A digest that cannot be read is treated as changed inputs.
+        let digest_recorded = file_system
+            .read(&StationInputsDigests::digest_path(station.dir))
+            .await
+            .ok()
+            .and_then(|digest_recorded| String::from_utf8(digest_recorded).ok());
+
+        let mut station_inputs_digests = train_resources.borrow_mut::<StationInputsDigests>();
+        station_inputs_digests.insert(station.rt_id, digest);
+        if let Some(digest_recorded) = digest_recorded {
+            station_inputs_digests.recorded_insert(station.rt_id, digest_recorded);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the hex encoded digest of the station's input files.
+    ///
+    /// # Parameters
+    ///
+    /// * `workspace_dir`: Directory that input patterns are relative to.
+    /// * `station_spec`: Specification of the station whose inputs to hash.
+    pub async fn digest(
+        workspace_dir: &Path,
+        station_spec: &StationSpec<E>,
+    ) -> Result<String, StationSpecError> {
+        let digest_fail =
+            |input: &str, detail: &dyn fmt::Display| StationSpecError::InputsDigestFail {
+                id: station_spec.id().clone(),
+                name: station_spec.name().to_string(),
+                input: input.to_string(),
+                detail: detail.to_string(),
+                location: station_spec.location(),
+            };
+
+        let workspace_dir_pattern = Pattern::escape(&workspace_dir.to_string_lossy());
+        let mut input_paths = BTreeSet::new();
+        for input in station_spec.inputs() {
+            let pattern = format!("{workspace_dir_pattern}/{input}");
+            let entries = glob::glob(&pattern).map_err(|error| digest_fail(input, &error))?;
+            for entry in entries {
+                let input_path = entry.map_err(|error| digest_fail(input, &error))?;
+                if input_path.is_file() {
+                    input_paths.insert(input_path);
+                }
+            }
+        }
+
+        let input_paths_relative = input_paths
+            .iter()
+            .map(|input_path| {
+                input_path
+                    .strip_prefix(workspace_dir)
+                    .unwrap_or(input_path)
+                    .to_path_buf()
+            })
+            .collect::<Vec<PathBuf>>();
+        let checksum = Checksum::files(
+            ChecksumAlgorithm::Sha256,
+            workspace_dir,
+            &input_paths_relative,
+            None,
+        )
+        .await
+        .map_err(|error| match &error {
+            OpsError::FileOpen { path, error } | OpsError::FileRead { path, error } => {
+                let input_path = path.strip_prefix(workspace_dir).unwrap_or(path);
+                digest_fail(&input_path.display().to_string(), error)
+            }
+            error => digest_fail(&station_spec.inputs().join(", "), error),
+        })?;
+
+        Ok(checksum.hex().to_string())
+    }
+}
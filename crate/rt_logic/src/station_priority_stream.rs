@@ -0,0 +1,118 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use choochoo_cfg_model::rt::StationMutRef;
+use choochoo_rt_model::Error;
+use futures::stream::{Stream, StreamExt};
+
+/// Stream of stations that yields the highest priority station of those that
+/// are ready to be visited.
+///
+/// Stations become ready when the stations they depend on have been visited.
+/// When more stations are ready than are being polled for, such as when
+/// several stations become ready at once, the ready stations are held until
+/// they are polled for. Stations with the same priority are yielded in the
+/// order they became ready.
+pub(crate) struct StationPriorityStream<'s, S, E> {
+    /// Stream of stations in the order they become ready.
+    stream: S,
+    /// Whether the underlying stream has ended.
+    stream_ended: bool,
+    /// Stations that are ready to be visited.
+    stations_ready: BinaryHeap<StationReady<'s, E>>,
+    /// Number of stations that have become ready, used to order stations with
+    /// the same priority.
+    ready_count: u64,
+}
+
+impl<'s, S, E> StationPriorityStream<'s, S, E>
+where
+    S: Stream<Item = Result<StationMutRef<'s, E>, Error<E>>> + Unpin,
+    E: 'static,
+{
+    /// Returns a new `StationPriorityStream`.
+    ///
+    /// # Parameters
+    ///
+    /// * `stream`: Stream of stations in the order they become ready.
+    pub(crate) fn new(stream: S) -> Self {
+        Self {
+            stream,
+            stream_ended: false,
+            stations_ready: BinaryHeap::new(),
+            ready_count: 0,
+        }
+    }
+}
+
+impl<'s, S, E> Stream for StationPriorityStream<'s, S, E>
+where
+    S: Stream<Item = Result<StationMutRef<'s, E>, Error<E>>> + Unpin,
+    E: 'static,
+{
+    type Item = Result<StationMutRef<'s, E>, Error<E>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        // Drain every station that is currently ready, so that the highest
+        // priority one may be chosen.
+        while !this.stream_ended {
+            match this.stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(station))) => {
+                    let station_ready = StationReady {
+                        priority: station.spec.priority(),
+                        ready_index: Reverse(this.ready_count),
+                        station,
+                    };
+                    this.ready_count += 1;
+                    this.stations_ready.push(station_ready);
+                }
+                Poll::Ready(Some(Err(error))) => return Poll::Ready(Some(Err(error))),
+                Poll::Ready(None) => this.stream_ended = true,
+                Poll::Pending => break,
+            }
+        }
+
+        match this.stations_ready.pop() {
+            Some(station_ready) => Poll::Ready(Some(Ok(station_ready.station))),
+            None if this.stream_ended => Poll::Ready(None),
+            None => Poll::Pending,
+        }
+    }
+}
+
+/// Station that is ready to be visited, ordered by its priority.
+struct StationReady<'s, E> {
+    /// Priority of the station.
+    priority: i32,
+    /// Order in which the station became ready, reversed so that earlier
+    /// stations are greater.
+    ready_index: Reverse<u64>,
+    /// The station.
+    station: StationMutRef<'s, E>,
+}
+
+impl<'s, E> PartialEq for StationReady<'s, E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'s, E> Eq for StationReady<'s, E> {}
+
+impl<'s, E> PartialOrd for StationReady<'s, E> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'s, E> Ord for StationReady<'s, E> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.priority, self.ready_index).cmp(&(other.priority, other.ready_index))
+    }
+}
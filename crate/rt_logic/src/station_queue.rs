@@ -0,0 +1,294 @@
+use std::{
+    any::TypeId,
+    convert::TryFrom,
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    time::Instant,
+};
+
+use choochoo_cfg_model::{
+    fn_graph::FnMeta,
+    indexmap::IndexMap,
+    rt::{StationRtId, VisitOp},
+    StationSpec,
+};
+use choochoo_rt_model::{Destination, QueueMetrics, ResourceConflictPolicy};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::ResourceLimits;
+
+/// Holds stations that are ready to be visited until their resources are
+/// available.
+///
+/// Each limited resource has a semaphore with as many permits as its limit. A
+/// station acquires a permit for each limited resource its functions access,
+/// and holds them until its visit completes.
+///
+/// Permits are always acquired in the same order, so stations that access
+/// multiple limited resources do not deadlock.
+///
+/// When the destination's [`ResourceConflictPolicy`] is
+/// [`ResourceConflictPolicy::Serialize`], each resource that a station mutably
+/// borrows also has a borrow semaphore with a permit for each station. A
+/// station that borrows the resource acquires one permit, and a station that
+/// mutably borrows it acquires all of them, so conflicting stations are
+/// visited one after the other.
+///
+/// When the number of concurrent work functions is limited, stations acquire
+/// a permit from the work lane after their resource permits, just before
+/// their work function runs.
+#[derive(Debug)]
+pub(crate) struct StationQueue {
+    /// Semaphore for each limited resource.
+    semaphores: IndexMap<TypeId, Arc<Semaphore>>,
+    /// Semaphore for each resource that is mutably borrowed, and the number
+    /// of permits it has.
+    borrow_semaphores: IndexMap<TypeId, (Arc<Semaphore>, u32)>,
+    /// Number of stations currently waiting for permits.
+    queue_depth: AtomicUsize,
+    /// Maximum number of stations to visit concurrently.
+    concurrency_max: Option<NonZeroUsize>,
+    /// Number of stations currently being visited.
+    stations_visiting: AtomicUsize,
+    /// Semaphore limiting the number of concurrent work functions.
+    work_lane: Option<Arc<Semaphore>>,
+    /// Measurements of stations waiting to be visited.
+    queue_metrics: Mutex<QueueMetrics>,
+}
+
+impl StationQueue {
+    /// Returns a new `StationQueue` for the given resource limits.
+    ///
+    /// # Parameters
+    ///
+    /// * `dest`: Destination whose stations are visited.
+    /// * `resource_limits`: Limits on concurrent access to resources.
+    /// * `concurrency_max`: Maximum number of stations to visit concurrently.
+    /// * `work_concurrency_max`: Maximum number of work functions to run
+    ///   concurrently.
+    pub(crate) fn new<E>(
+        dest: &Destination<E>,
+        resource_limits: &ResourceLimits,
+        concurrency_max: Option<NonZeroUsize>,
+        work_concurrency_max: Option<NonZeroUsize>,
+    ) -> Self
+    where
+        E: 'static,
+    {
+        let semaphores = resource_limits
+            .iter()
+            .map(|(type_id, limit)| {
+                (
+                    *type_id,
+                    Arc::new(Semaphore::new(NonZeroUsize::get(*limit))),
+                )
+            })
+            .collect::<IndexMap<_, _>>();
+        let borrow_semaphores = Self::borrow_semaphores(dest);
+
+        Self {
+            semaphores,
+            borrow_semaphores,
+            queue_depth: AtomicUsize::new(0),
+            concurrency_max,
+            stations_visiting: AtomicUsize::new(0),
+            work_lane: work_concurrency_max
+                .map(|work_concurrency_max| Arc::new(Semaphore::new(work_concurrency_max.get()))),
+            queue_metrics: Mutex::new(QueueMetrics::new()),
+        }
+    }
+
+    /// Waits until the resources accessed by a station are available, and
+    /// returns the permits to hold while the station is visited.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_spec`: Specification of the station to visit.
+    /// * `station_rt_id`: Runtime ID of the station to visit.
+    /// * `visit_op`: Operation the station is visited for.
+    pub(crate) async fn acquire<E>(
+        &self,
+        station_spec: &StationSpec<E>,
+        station_rt_id: StationRtId,
+        visit_op: VisitOp,
+    ) -> Vec<OwnedSemaphorePermit> {
+        let semaphores = self.semaphores_for(station_spec, visit_op);
+
+        // The current time is only read when there are limited resources, as reading
+        // it is not supported on all targets.
+        let wait_begin = (!semaphores.is_empty()).then(Instant::now);
+
+        let mut permits = Vec::with_capacity(semaphores.len());
+        let mut queued = false;
+        for (semaphore, permit_count) in semaphores {
+            let permit = match Arc::clone(&semaphore).try_acquire_many_owned(permit_count) {
+                Ok(permit) => Ok(permit),
+                Err(_) => {
+                    if !queued {
+                        queued = true;
+                        let queue_depth = self.queue_depth.fetch_add(1, Ordering::SeqCst) + 1;
+                        self.queue_metrics_update(|queue_metrics| {
+                            queue_metrics.queue_depth_record(queue_depth)
+                        });
+                    }
+                    semaphore.acquire_many_owned(permit_count).await
+                }
+            };
+
+            // The semaphores are never closed, so acquiring a permit does not fail.
+            if let Ok(permit) = permit {
+                permits.push(permit);
+            }
+        }
+        if queued {
+            self.queue_depth.fetch_sub(1, Ordering::SeqCst);
+        }
+
+        let wait_duration = wait_begin
+            .map(|wait_begin| wait_begin.elapsed())
+            .unwrap_or_default();
+        self.queue_metrics_update(|queue_metrics| {
+            queue_metrics.station_wait_record(station_rt_id, wait_duration)
+        });
+
+        permits
+    }
+
+    /// Returns the semaphore limiting the number of concurrent work
+    /// functions, if any.
+    pub(crate) fn work_lane(&self) -> Option<&Semaphore> {
+        self.work_lane.as_deref()
+    }
+
+    /// Records that a ready station is about to be visited.
+    ///
+    /// If the train is then visiting its maximum number of stations, the
+    /// remaining ready stations are held until a visit ends, so their number
+    /// is recorded in the queue metrics.
+    ///
+    /// # Parameters
+    ///
+    /// * `stations_ready`: Number of stations that remain ready to be visited.
+    pub(crate) fn station_dequeue(&self, stations_ready: usize) {
+        let stations_visiting = self.stations_visiting.fetch_add(1, Ordering::SeqCst) + 1;
+        if self.concurrency_max.map(NonZeroUsize::get) == Some(stations_visiting) {
+            self.queue_metrics_update(|queue_metrics| {
+                queue_metrics.ready_depth_record(stations_ready)
+            });
+        }
+    }
+
+    /// Records that a worker took a station from another worker's queue.
+    pub(crate) fn station_steal_record(&self) {
+        self.queue_metrics_update(QueueMetrics::station_steal_record);
+    }
+
+    /// Records that a station's visit has ended.
+    pub(crate) fn visit_end(&self) {
+        self.stations_visiting.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    /// Returns the measurements of stations waiting to be visited.
+    pub(crate) fn into_queue_metrics(self) -> QueueMetrics {
+        self.queue_metrics
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the semaphores to acquire permits from before visiting a
+    /// station, with the number of permits to acquire from each.
+    ///
+    /// Limited resources come first, then borrowed resources, each in the same
+    /// order for every station.
+    fn semaphores_for<E>(
+        &self,
+        station_spec: &StationSpec<E>,
+        visit_op: VisitOp,
+    ) -> Vec<(Arc<Semaphore>, u32)> {
+        if self.semaphores.is_empty() && self.borrow_semaphores.is_empty() {
+            return Vec::new();
+        }
+
+        let station_op = station_spec.station_op();
+        let (borrows, borrow_muts) = match visit_op {
+            VisitOp::Create => {
+                let create_fns = station_op.create_fns();
+                (create_fns.borrows(), create_fns.borrow_muts())
+            }
+            VisitOp::Clean => station_op
+                .clean_fns()
+                .map(|clean_fns| (clean_fns.borrows(), clean_fns.borrow_muts()))
+                .unwrap_or_default(),
+        };
+
+        let semaphores_limited = self
+            .semaphores
+            .iter()
+            .filter(|(type_id, _)| borrows.contains(type_id) || borrow_muts.contains(type_id))
+            .map(|(_, semaphore)| (Arc::clone(semaphore), 1));
+        let semaphores_borrowed =
+            self.borrow_semaphores
+                .iter()
+                .filter_map(|(type_id, (semaphore, permits_total))| {
+                    if borrow_muts.contains(type_id) {
+                        Some((Arc::clone(semaphore), *permits_total))
+                    } else if borrows.contains(type_id) {
+                        Some((Arc::clone(semaphore), 1))
+                    } else {
+                        None
+                    }
+                });
+
+        semaphores_limited.chain(semaphores_borrowed).collect()
+    }
+
+    /// Returns a semaphore for each resource that a station mutably borrows,
+    /// if the destination serializes stations with conflicting borrows.
+    ///
+    /// Each semaphore has a permit for each station, so every station can
+    /// borrow the resource at the same time.
+    fn borrow_semaphores<E>(dest: &Destination<E>) -> IndexMap<TypeId, (Arc<Semaphore>, u32)>
+    where
+        E: 'static,
+    {
+        if dest.resource_conflict_policy() != ResourceConflictPolicy::Serialize {
+            return IndexMap::new();
+        }
+
+        let station_specs = dest.station_specs();
+        let permits_total = station_specs.node_count().min(Semaphore::MAX_PERMITS);
+        let permits_total = u32::try_from(permits_total).unwrap_or(u32::MAX);
+        station_specs
+            .iter_insertion()
+            .flat_map(|station_spec| {
+                let station_op = station_spec.station_op();
+                let mut borrow_muts = station_op.create_fns().borrow_muts();
+                if let Some(clean_fns) = station_op.clean_fns() {
+                    borrow_muts.extend(clean_fns.borrow_muts());
+                }
+                borrow_muts.iter().copied().collect::<Vec<TypeId>>()
+            })
+            .fold(IndexMap::new(), |mut borrow_semaphores, type_id| {
+                borrow_semaphores.entry(type_id).or_insert_with(|| {
+                    let semaphore = Arc::new(Semaphore::new(permits_total as usize));
+                    (semaphore, permits_total)
+                });
+                borrow_semaphores
+            })
+    }
+
+    /// Runs the given function with the queue metrics.
+    fn queue_metrics_update<F>(&self, f: F)
+    where
+        F: FnOnce(&mut QueueMetrics),
+    {
+        let mut queue_metrics = self
+            .queue_metrics
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        f(&mut queue_metrics);
+    }
+}
@@ -0,0 +1,159 @@
+use std::{
+    sync::{Mutex, PoisonError},
+    task::Poll,
+};
+
+use choochoo_cfg_model::{
+    indexmap::IndexMap,
+    rt::{StationMutRef, StationRtId},
+};
+use choochoo_rt_model::{Destination, Error};
+use futures::stream::{self, Stream, StreamExt};
+
+/// Detects when the train stalls, instead of waiting for it forever.
+///
+/// The train is stalled when no station is being visited and none is ready to
+/// be visited, yet some stations have not been visited. This should not
+/// happen, but a bug in `OpStatus` transitions would otherwise leave the train
+/// waiting with nothing to wake it. Stations whose progress is being accessed,
+/// such as by a speculative check, will be released, so the train is not
+/// stalled while waiting for them.
+#[derive(Debug)]
+pub(crate) struct StationStallWatchdog<'d, E> {
+    /// Destination whose stations are visited.
+    dest: &'d Destination<E>,
+    /// Stations that have begun being visited, and whether their visit has
+    /// ended.
+    stations_visited: Mutex<IndexMap<StationRtId, bool>>,
+}
+
+impl<'d, E> StationStallWatchdog<'d, E>
+where
+    E: 'static,
+{
+    /// Returns a new `StationStallWatchdog` for the destination's stations.
+    ///
+    /// # Parameters
+    ///
+    /// * `dest`: `Destination` with all the stations.
+    pub(crate) fn new(dest: &'d Destination<E>) -> Self {
+        Self {
+            dest,
+            stations_visited: Mutex::new(IndexMap::new()),
+        }
+    }
+
+    /// Returns a stream that yields the stations of the given stream, or
+    /// [`Error::StationsStalled`] if the train stalls.
+    ///
+    /// [`StationStallWatchdog::visit_end`] must be called once each yielded
+    /// station's visit has ended.
+    ///
+    /// # Parameters
+    ///
+    /// * `stations`: Stream of stations that are ready to be visited.
+    pub(crate) fn watch<'w, 's, S>(
+        &'w self,
+        mut stations: S,
+    ) -> impl Stream<Item = Result<StationMutRef<'s, E>, Error<E>>> + 'w
+    where
+        S: Stream<Item = Result<StationMutRef<'s, E>, Error<E>>> + Unpin + 'w,
+        's: 'w,
+    {
+        // The underlying stream may be pending without being stalled, such as when the
+        // runtime asks the task to yield, so the stream is polled once more before the
+        // train is considered stalled.
+        let mut stall_suspected = false;
+        stream::poll_fn(move |cx| match stations.poll_next_unpin(cx) {
+            Poll::Ready(Some(Ok(station))) => {
+                stall_suspected = false;
+                self.stations_visited_update(|stations_visited| {
+                    stations_visited.insert(station.rt_id, false);
+                });
+                Poll::Ready(Some(Ok(station)))
+            }
+            Poll::Pending if self.is_stalled() => {
+                if stall_suspected {
+                    Poll::Ready(Some(Err(self.stalled_error())))
+                } else {
+                    stall_suspected = true;
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                }
+            }
+            poll => {
+                stall_suspected = false;
+                poll
+            }
+        })
+    }
+
+    /// Records that a station's visit has ended.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_rt_id`: Runtime ID of the station whose visit has ended.
+    pub(crate) fn visit_end(&self, station_rt_id: StationRtId) {
+        self.stations_visited_update(|stations_visited| {
+            stations_visited.insert(station_rt_id, true);
+        });
+    }
+
+    /// Returns whether no station is being visited or accessed, yet some
+    /// stations have not been visited.
+    fn is_stalled(&self) -> bool {
+        let stations_visited = self
+            .stations_visited
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let stations_in_progress = stations_visited.values().any(|visit_ended| !visit_ended);
+        let station_count = self.dest.station_specs().graph().node_count();
+        let stations_accessed = || {
+            self.dest
+                .station_progresses()
+                .iter()
+                .filter(|(station_rt_id, _)| !stations_visited.contains_key(*station_rt_id))
+                .any(|(_, station_progress)| station_progress.try_borrow().is_err())
+        };
+
+        !stations_in_progress && stations_visited.len() < station_count && !stations_accessed()
+    }
+
+    /// Returns the error listing the stations that have not been visited,
+    /// with their `OpStatus`es.
+    fn stalled_error(&self) -> Error<E> {
+        let stations_visited = self
+            .stations_visited
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let station_progresses = self.dest.station_progresses();
+        let stations = self
+            .dest
+            .station_specs()
+            .iter_insertion_with_indices()
+            .filter(|(station_rt_id, _)| !stations_visited.contains_key(station_rt_id))
+            .map(|(station_rt_id, station_spec)| {
+                let op_status = station_progresses
+                    .get(&station_rt_id)
+                    .and_then(|station_progress| station_progress.try_borrow().ok())
+                    .map(|station_progress| station_progress.op_status);
+                (station_spec.id().clone(), op_status)
+            })
+            .collect::<Vec<_>>();
+
+        Error::StationsStalled { stations }
+    }
+
+    /// Runs the given function with the stations that have begun being
+    /// visited.
+    fn stations_visited_update<F>(&self, f: F)
+    where
+        F: FnOnce(&mut IndexMap<StationRtId, bool>),
+    {
+        let mut stations_visited = self
+            .stations_visited
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        f(&mut stations_visited);
+    }
+}
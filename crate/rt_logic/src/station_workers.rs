@@ -0,0 +1,189 @@
+use std::{
+    collections::VecDeque,
+    future::Future,
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Mutex, MutexGuard, PoisonError,
+    },
+};
+
+use futures::{
+    future,
+    stream::{Stream, TryStreamExt},
+};
+use tokio::sync::Notify;
+
+use crate::StationQueue;
+
+/// Visits ready stations on a fixed number of workers, which steal stations
+/// from each other when they are idle.
+///
+/// Stations that become ready are queued on the worker that most recently
+/// finished a visit, as that visit is usually what made them ready. Each
+/// worker visits the stations in its own queue one at a time. When its queue
+/// is empty, it takes a station from the longest queue of another worker, so
+/// a worker that is waiting for a limited resource, or visiting a slow
+/// station, does not hold up the stations queued behind it.
+///
+/// Stations are taken from the front of each queue, so higher priority
+/// stations are still visited first.
+///
+/// Workers run on the task that drives the train, as station visits borrow
+/// the destination. The futures of station functions constructed using
+/// [`StationFn::new_send`] are spawned from the workers, so with a
+/// multi-threaded runtime, their work runs in parallel.
+///
+/// [`StationFn::new_send`]: choochoo_cfg_model::StationFn::new_send
+pub(crate) struct StationWorkers<'q, T> {
+    /// Stations queued on each worker.
+    queues: Vec<Mutex<VecDeque<T>>>,
+    /// Index of the worker that most recently finished a visit.
+    worker_recent: AtomicUsize,
+    /// Whether every ready station has been queued.
+    stations_ended: AtomicBool,
+    /// Wakes idle workers when a station is queued, or there are no more
+    /// stations.
+    notify: Notify,
+    /// Queue that tracks the number of stations being visited, and records
+    /// queue metrics.
+    station_queue: &'q StationQueue,
+}
+
+impl<'q, T> StationWorkers<'q, T> {
+    /// Returns new `StationWorkers`.
+    ///
+    /// There is a worker for each station that may be visited concurrently.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_queue`: Queue that tracks the number of stations being
+    ///   visited.
+    /// * `concurrency_max`: Maximum number of stations to visit concurrently.
+    /// * `station_count`: Number of stations in the destination, used as the
+    ///   number of workers when concurrency is not limited.
+    pub(crate) fn new(
+        station_queue: &'q StationQueue,
+        concurrency_max: Option<NonZeroUsize>,
+        station_count: usize,
+    ) -> Self {
+        let worker_count = concurrency_max
+            .map(NonZeroUsize::get)
+            .unwrap_or(station_count)
+            .max(1);
+        let queues = (0..worker_count)
+            .map(|_| Mutex::new(VecDeque::new()))
+            .collect::<Vec<_>>();
+
+        Self {
+            queues,
+            worker_recent: AtomicUsize::new(0),
+            stations_ended: AtomicBool::new(false),
+            notify: Notify::new(),
+            station_queue,
+        }
+    }
+
+    /// Queues each station of the stream as it becomes ready, and visits the
+    /// queued stations on the workers.
+    ///
+    /// Returns the first error from the stream or a visit.
+    ///
+    /// # Parameters
+    ///
+    /// * `stations`: Stream of stations that are ready to be visited.
+    /// * `visit`: Visits a station.
+    pub(crate) async fn run<S, F, Fut, Err>(&self, stations: S, visit: F) -> Result<(), Err>
+    where
+        S: Stream<Item = Result<T, Err>>,
+        F: Fn(T) -> Fut,
+        Fut: Future<Output = Result<(), Err>>,
+    {
+        let stations_queue = async {
+            futures::pin_mut!(stations);
+            while let Some(station) = stations.try_next().await? {
+                self.station_push(station);
+            }
+
+            self.stations_ended.store(true, Ordering::SeqCst);
+            self.notify.notify_waiters();
+            Ok(())
+        };
+        let workers = future::try_join_all(
+            (0..self.queues.len()).map(|worker_index| self.worker_run(worker_index, &visit)),
+        );
+
+        futures::try_join!(stations_queue, workers).map(|((), _)| ())
+    }
+
+    /// Visits stations until there are no more stations.
+    ///
+    /// # Parameters
+    ///
+    /// * `worker_index`: Index of this worker.
+    /// * `visit`: Visits a station.
+    async fn worker_run<F, Fut, Err>(&self, worker_index: usize, visit: &F) -> Result<(), Err>
+    where
+        F: Fn(T) -> Fut,
+        Fut: Future<Output = Result<(), Err>>,
+    {
+        loop {
+            // Created before checking the queues, so that stations queued after the check
+            // wake this worker.
+            let notified = self.notify.notified();
+            match self.station_take(worker_index) {
+                Some(station) => {
+                    visit(station).await?;
+                    self.worker_recent.store(worker_index, Ordering::SeqCst);
+                }
+                None if self.stations_ended.load(Ordering::SeqCst) => return Ok(()),
+                None => notified.await,
+            }
+        }
+    }
+
+    /// Queues a station on the worker that most recently finished a visit.
+    fn station_push(&self, station: T) {
+        let worker_index = self.worker_recent.load(Ordering::SeqCst);
+        self.queue_lock(worker_index).push_back(station);
+        self.notify.notify_waiters();
+    }
+
+    /// Takes the next station from the worker's queue, or steals one from
+    /// another worker if its queue is empty.
+    fn station_take(&self, worker_index: usize) -> Option<T> {
+        let station_own = self.queue_lock(worker_index).pop_front();
+        let station = station_own.or_else(|| {
+            let station_stolen = self.station_steal(worker_index);
+            if station_stolen.is_some() {
+                self.station_queue.station_steal_record();
+            }
+            station_stolen
+        });
+
+        if station.is_some() {
+            let stations_queued = (0..self.queues.len())
+                .map(|index| self.queue_lock(index).len())
+                .sum();
+            self.station_queue.station_dequeue(stations_queued);
+        }
+
+        station
+    }
+
+    /// Takes the next station from the longest queue of the other workers.
+    fn station_steal(&self, worker_index: usize) -> Option<T> {
+        (0..self.queues.len())
+            .filter(|index| *index != worker_index)
+            .map(|index| self.queue_lock(index))
+            .max_by_key(|queue| queue.len())
+            .and_then(|mut queue| queue.pop_front())
+    }
+
+    /// Returns the queue of the given worker.
+    fn queue_lock(&self, worker_index: usize) -> MutexGuard<'_, VecDeque<T>> {
+        self.queues[worker_index]
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
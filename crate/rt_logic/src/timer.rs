@@ -0,0 +1,38 @@
+use std::{fmt, sync::Arc, time::Duration};
+
+use futures::future::LocalBoxFuture;
+
+#[cfg(feature = "native")]
+pub use self::tokio_timer::TokioTimer;
+
+#[cfg(feature = "native")]
+mod tokio_timer;
+
+/// Waits for durations to elapse.
+///
+/// The train uses a timer to interrupt stations when its deadline is reached,
+/// and chaos policies use one to delay station visits.
+///
+/// [`TokioTimer`] is used by default when the `native` feature is enabled.
+/// On targets without tokio's time driver, such as `wasm32-unknown-unknown`,
+/// a timer backed by the platform's event loop may be used instead.
+pub trait Timer: fmt::Debug + Send + Sync {
+    /// Returns a future that completes after the duration has elapsed.
+    fn sleep(&self, duration: Duration) -> LocalBoxFuture<'static, ()>;
+}
+
+/// Returns the timer to use when none is provided.
+///
+/// This is [`TokioTimer`] when the `native` feature is enabled, and `None`
+/// otherwise.
+pub(crate) fn timer_default() -> Option<Arc<dyn Timer>> {
+    #[cfg(feature = "native")]
+    {
+        Some(Arc::new(TokioTimer))
+    }
+
+    #[cfg(not(feature = "native"))]
+    {
+        None
+    }
+}
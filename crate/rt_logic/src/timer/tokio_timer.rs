@@ -0,0 +1,17 @@
+use std::time::Duration;
+
+use futures::future::{FutureExt, LocalBoxFuture};
+
+use crate::Timer;
+
+/// Waits for durations to elapse using tokio's time driver.
+///
+/// The tokio runtime must have the time driver enabled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TokioTimer;
+
+impl Timer for TokioTimer {
+    fn sleep(&self, duration: Duration) -> LocalBoxFuture<'static, ()> {
+        tokio::time::sleep(duration).boxed_local()
+    }
+}
@@ -1,25 +1,119 @@
-use std::{fmt, marker::PhantomData, num::NonZeroUsize};
+#[cfg(feature = "native")]
+use std::time::SystemTime;
+use std::{
+    fmt,
+    marker::PhantomData,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
 
 use choochoo_cfg_model::{
-    indicatif::MultiProgress,
-    rt::{OpStatus, ResIds, StationRtId, TrainResources, VisitOp},
+    daggy::Walker,
+    indexmap::IndexMap,
+    rt::{
+        CheckStatusesSpeculative, Effect, ExecutionModel, OpStatus, ProgressLimit, ProgressTheme,
+        ResIds, ResIdsPrevious, Spawner, StationArtifact, StationEnv, StationErrorPhase,
+        StationInputsDigests, StationMutRef, StationProgress, StationRtId, SubStep, TrainResources,
+        VisitOp,
+    },
+    srcerr::codespan_reporting::diagnostic::Severity,
+    ResourceInitFn, StationId,
+};
+#[cfg(feature = "native")]
+use choochoo_resource::ProfileHistoryDir;
+use choochoo_rt_model::{
+    error::StationSpecError, CleanSelection, Destination, Error, ReconciliationReport, TrainPlan,
+    TrainReport, Verbosity,
+};
+#[cfg(feature = "native")]
+use choochoo_rt_model::{AuditEntry, AuditQuery};
+use futures::{
+    future::{self, Either, FutureExt, LocalBoxFuture},
+    stream::{self, StreamExt, TryStreamExt},
 };
-use choochoo_rt_model::{error::StationSpecError, Destination, Error, TrainReport};
-use futures::stream::{self, TryStreamExt};
 use tokio::task::JoinHandle;
 
-use crate::ResourceInitializer;
+#[cfg(not(feature = "native"))]
+use crate::NoopProgressBackend;
+#[cfg(feature = "metrics")]
+use crate::TrainMetrics;
+#[cfg(feature = "native")]
+use crate::{
+    profile_lock, ArtifactCollector, AuditLog, HistoryGc, IndicatifProgressBackend, ProfileLock,
+    ResourceSnapshot, StationInputsHasher,
+};
+use crate::{
+    timer, ChaosPolicy, ExecutionPolicy, ExecutionStrategy, ExistsDriver, FileInputResolver,
+    HistoryRetention, IntegrityStrat, ProgressBackend, ResIdPersister, ResourceInitializer,
+    ResourceLimits, RollbackPolicy, Timer,
+};
+
+pub(crate) use self::train_stop::TrainStop;
 
 use self::{train_clean::TrainClean, train_create::TrainCreate};
 
 mod train_clean;
 mod train_create;
+mod train_stop;
 
 /// Ensures all carriages are at the destination.
 #[derive(Debug)]
 pub struct Train<E> {
     /// Maximum number of stations to run concurrently.
     concurrency_max: Option<NonZeroUsize>,
+    /// Maximum number of stations whose work functions run concurrently.
+    work_concurrency_max: Option<NonZeroUsize>,
+    /// Minimum severity of recorded diagnostics that fails the train.
+    severity_threshold: Severity,
+    /// Severity of a station's check function reporting that work is still
+    /// required after its work succeeded.
+    work_not_idempotent_severity: Severity,
+    /// How the train proceeds when a station fails.
+    execution_policy: ExecutionPolicy,
+    /// Whether resources of stations that fail to be created are cleaned.
+    rollback_policy: RollbackPolicy,
+    /// Decides which stations need to be visited.
+    execution_strategy: Arc<dyn ExecutionStrategy<E>>,
+    /// Time by which the train must reach its destination.
+    deadline: Option<Instant>,
+    /// Waits for the deadline to be reached.
+    timer: Option<Arc<dyn Timer>>,
+    /// Duration from the beginning of [`Train::reach`] within which the train
+    /// must reach its destination.
+    timeout: Option<Duration>,
+    /// Appearance of progress bars for stations without their own theme.
+    progress_theme: Option<ProgressTheme>,
+    /// Renders station progress while stations are visited.
+    progress_backend: Arc<dyn ProgressBackend<E>>,
+    /// Amount of detail in progress output.
+    verbosity: Verbosity,
+    /// Injects failures, delays, or cancellations into station visits.
+    chaos_policy: Option<ChaosPolicy<E>>,
+    /// How station functions are executed.
+    execution_model: ExecutionModel,
+    /// Spawns the futures of `Send` station functions.
+    spawner: Option<Spawner>,
+    /// Maximum number of stations that may access each resource concurrently.
+    resource_limits: ResourceLimits,
+    /// Base environment of external commands run by stations.
+    station_env: StationEnv,
+    /// Whether side effects run through [`Effect`] are recorded instead of run.
+    dry_run: bool,
+    /// How long entries in the profile history directory are kept.
+    history_retention: HistoryRetention,
+    /// User registered logic to insert resources before stations are set up.
+    resource_init_fns: Vec<ResourceInitFn<E>>,
+    /// Resources saved at the end of each drive and loaded at the start of the
+    /// next.
+    #[cfg(feature = "native")]
+    resource_snapshot: ResourceSnapshot<E>,
+    /// HTTP client shared between stations.
+    #[cfg(feature = "http")]
+    http_client: Option<reqwest::Client>,
+    /// Counters and gauges of the train's execution.
+    #[cfg(feature = "metrics")]
+    metrics: Option<TrainMetrics>,
     /// Marker.
     marker: PhantomData<E>,
 }
@@ -48,27 +142,510 @@ where
     fn new(concurrency_max: Option<NonZeroUsize>) -> Self {
         Self {
             concurrency_max,
+            work_concurrency_max: None,
+            severity_threshold: Severity::Error,
+            work_not_idempotent_severity: Severity::Warning,
+            execution_policy: ExecutionPolicy::default(),
+            rollback_policy: RollbackPolicy::default(),
+            execution_strategy: Arc::new(IntegrityStrat),
+            deadline: None,
+            timer: timer::timer_default(),
+            timeout: None,
+            progress_theme: None,
+            #[cfg(feature = "native")]
+            progress_backend: Arc::new(IndicatifProgressBackend),
+            #[cfg(not(feature = "native"))]
+            progress_backend: Arc::new(NoopProgressBackend),
+            verbosity: Verbosity::default(),
+            chaos_policy: None,
+            execution_model: ExecutionModel::default(),
+            spawner: None,
+            resource_limits: ResourceLimits::default(),
+            station_env: StationEnv::default(),
+            dry_run: false,
+            history_retention: HistoryRetention::default(),
+            resource_init_fns: Vec::new(),
+            #[cfg(feature = "native")]
+            resource_snapshot: ResourceSnapshot::new(),
+            #[cfg(feature = "http")]
+            http_client: None,
+            #[cfg(feature = "metrics")]
+            metrics: None,
             marker: PhantomData,
         }
     }
 
+    /// Sets the maximum number of stations to visit concurrently.
+    ///
+    /// Stations are visited by this many workers, each visiting one station at
+    /// a time. A worker with no stations queued takes stations queued on
+    /// other workers, which is recorded in the train report's
+    /// [`QueueMetrics`].
+    ///
+    /// Defaults to no limit.
+    ///
+    /// [`QueueMetrics`]: choochoo_rt_model::QueueMetrics
+    #[must_use]
+    pub fn with_concurrency_max(mut self, concurrency_max: NonZeroUsize) -> Self {
+        self.concurrency_max = Some(concurrency_max);
+        self
+    }
+
+    /// Sets the maximum number of stations whose work functions run
+    /// concurrently.
+    ///
+    /// Check functions are not limited, so while a few stations run heavy work
+    /// functions, other stations can run their checks, and those whose work is
+    /// unnecessary complete without waiting for the work to finish. Stations
+    /// that wait for a work slot keep their [`ResourceLimits`] permits.
+    ///
+    /// For this to take effect, the [concurrency limit] should be greater than
+    /// this limit.
+    ///
+    /// Defaults to no limit.
+    ///
+    /// [concurrency limit]: Self::with_concurrency_max
+    #[must_use]
+    pub fn with_work_concurrency_max(mut self, work_concurrency_max: NonZeroUsize) -> Self {
+        self.work_concurrency_max = Some(work_concurrency_max);
+        self
+    }
+
+    /// Sets the minimum severity of recorded diagnostics that fails the train.
+    ///
+    /// Station errors always fail the train. When this is [`Severity::Warning`]
+    /// or lower, warnings recorded in [`StationWarnings`] also fail the train.
+    ///
+    /// Defaults to [`Severity::Error`].
+    ///
+    /// [`StationWarnings`]: choochoo_cfg_model::rt::StationWarnings
+    #[must_use]
+    pub fn with_severity_threshold(mut self, severity_threshold: Severity) -> Self {
+        self.severity_threshold = severity_threshold;
+        self
+    }
+
+    /// Sets the severity of a station's check function reporting that work is
+    /// still required after its work succeeded.
+    ///
+    /// This is recorded as [`StationSpecError::WorkNotIdempotent`]. When the
+    /// severity is [`Severity::Error`] or higher, it is recorded as a station
+    /// error, otherwise it is recorded as a warning.
+    ///
+    /// Defaults to [`Severity::Warning`].
+    #[must_use]
+    pub fn with_work_not_idempotent_severity(
+        mut self,
+        work_not_idempotent_severity: Severity,
+    ) -> Self {
+        self.work_not_idempotent_severity = work_not_idempotent_severity;
+        self
+    }
+
+    /// Sets how the train proceeds when a station fails.
+    ///
+    /// Defaults to [`ExecutionPolicy::ContinueIndependent`].
+    #[must_use]
+    pub fn with_execution_policy(mut self, execution_policy: ExecutionPolicy) -> Self {
+        self.execution_policy = execution_policy;
+        self
+    }
+
+    /// Sets whether the resources of a station are cleaned when its create
+    /// visit fails.
+    ///
+    /// The result of each rollback is recorded in the [`TrainReport`].
+    ///
+    /// Defaults to [`RollbackPolicy::Never`].
+    #[must_use]
+    pub fn with_rollback_policy(mut self, rollback_policy: RollbackPolicy) -> Self {
+        self.rollback_policy = rollback_policy;
+        self
+    }
+
+    /// Sets the strategy that decides which stations need to be visited.
+    ///
+    /// Defaults to [`IntegrityStrat`]. Use [`FastPathStrat`] to check stations
+    /// that are already in their desired state up front, without waiting for
+    /// their parents to be visited.
+    ///
+    /// [`FastPathStrat`]: crate::FastPathStrat
+    #[must_use]
+    pub fn with_execution_strategy<S>(mut self, execution_strategy: S) -> Self
+    where
+        S: ExecutionStrategy<E> + 'static,
+    {
+        self.execution_strategy = Arc::new(execution_strategy);
+        self
+    }
+
+    /// Sets the time by which the train must reach its destination.
+    ///
+    /// Stations that have not completed by the deadline are marked
+    /// [`OpStatus::DeadlineExceeded`], including stations that are in
+    /// progress.
+    ///
+    /// When using the default timer, the tokio runtime must have the time
+    /// driver enabled.
+    #[must_use]
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Sets the timer that waits for the deadline to be reached.
+    ///
+    /// The timer is also used to wait for delays injected by the chaos policy.
+    ///
+    /// Defaults to [`TokioTimer`] when the `native` feature is enabled. When
+    /// it is disabled, there is no default timer, and stations in progress are
+    /// not interrupted when the deadline is reached.
+    ///
+    /// [`TokioTimer`]: crate::TokioTimer
+    #[must_use]
+    pub fn with_timer<T>(mut self, timer: T) -> Self
+    where
+        T: Timer + 'static,
+    {
+        let timer: Arc<dyn Timer> = Arc::new(timer);
+        if let Some(chaos_policy) = self.chaos_policy.as_mut() {
+            chaos_policy.timer_set(Some(Arc::clone(&timer)));
+        }
+        self.timer = Some(timer);
+        self
+    }
+
+    /// Sets the duration within which the train must reach its destination.
+    ///
+    /// The duration is measured from the beginning of each [`Train::reach`]
+    /// call. If a deadline is also set, the earlier of the two is used.
+    ///
+    /// When using the default timer, the tokio runtime must have the time
+    /// driver enabled.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the appearance of progress bars for all stations.
+    ///
+    /// Stations with their own [`ProgressTheme`] keep that theme.
+    #[must_use]
+    pub fn with_progress_theme(mut self, progress_theme: ProgressTheme) -> Self {
+        self.progress_theme = Some(progress_theme);
+        self
+    }
+
+    /// Sets the backend that renders station progress.
+    ///
+    /// Defaults to [`IndicatifProgressBackend`]. Use [`NoopProgressBackend`] to
+    /// skip rendering progress bars for destinations with many stations.
+    ///
+    /// [`NoopProgressBackend`]: crate::NoopProgressBackend
+    #[must_use]
+    pub fn with_progress_backend<B>(mut self, progress_backend: B) -> Self
+    where
+        B: ProgressBackend<E> + 'static,
+    {
+        self.progress_backend = Arc::new(progress_backend);
+        self
+    }
+
+    /// Sets the amount of detail in progress output.
+    ///
+    /// * [`Verbosity::Quiet`]: Progress is not rendered.
+    /// * [`Verbosity::Normal`]: Progress is rendered by the progress backend.
+    /// * [`Verbosity::Verbose`]: Each station's status and visit duration is
+    ///   also written above the progress bars when its visit completes.
+    ///
+    /// Defaults to [`Verbosity::Normal`].
+    #[must_use]
+    pub fn with_verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
+
+    /// Sets the policy that injects failures, delays, or cancellations into
+    /// station visits.
+    ///
+    /// The policy is consulted before each station's check and work
+    /// functions. Injected failures are recorded as station errors, and
+    /// cancelled stations are marked [`OpStatus::WorkInterrupted`], so
+    /// dependent stations are marked [`OpStatus::ParentFail`].
+    ///
+    /// This is intended for testing how flows behave under partial failure.
+    #[must_use]
+    pub fn with_chaos_policy(mut self, mut chaos_policy: ChaosPolicy<E>) -> Self {
+        chaos_policy.timer_set(self.timer.clone());
+        self.chaos_policy = Some(chaos_policy);
+        self
+    }
+
+    /// Sets how station functions are executed.
+    ///
+    /// With [`ExecutionModel::MultiThread`], the futures of station functions
+    /// constructed using [`StationFn::new_send`] are spawned as tokio tasks, so
+    /// that station work may run in parallel when the train is driven on a
+    /// multi-threaded runtime. Other station functions are still awaited on
+    /// the task that drives the train.
+    ///
+    /// Defaults to [`ExecutionModel::CurrentThread`].
+    ///
+    /// [`StationFn::new_send`]: choochoo_cfg_model::StationFn::new_send
+    #[must_use]
+    pub fn with_execution_model(mut self, execution_model: ExecutionModel) -> Self {
+        self.execution_model = execution_model;
+        self
+    }
+
+    /// Sets the spawner for the futures of `Send` station functions.
+    ///
+    /// When the execution model is [`ExecutionModel::MultiThread`], this is
+    /// used instead of `tokio::spawn`, which allows station work to be spawned
+    /// on other executors, such as the browser's event loop.
+    #[must_use]
+    pub fn with_spawner(mut self, spawner: Spawner) -> Self {
+        self.spawner = Some(spawner);
+        self
+    }
+
+    /// Sets the maximum number of stations that may access each resource
+    /// concurrently.
+    ///
+    /// Stations that are ready to be visited wait while a resource they access
+    /// is at its limit, without blocking stations in independent parts of the
+    /// graph. The time each station waited is recorded in the train report's
+    /// [`QueueMetrics`].
+    ///
+    /// When the execution model is [`ExecutionModel::MultiThread`], the work
+    /// of stations that acquired their resources is distributed across the
+    /// runtime's worker threads by tokio's work-stealing scheduler.
+    ///
+    /// [`QueueMetrics`]: choochoo_rt_model::QueueMetrics
+    #[must_use]
+    pub fn with_resource_limits(mut self, resource_limits: ResourceLimits) -> Self {
+        self.resource_limits = resource_limits;
+        self
+    }
+
+    /// Sets the base environment of external commands run by stations.
+    ///
+    /// Each station's environment is resolved from this, with the working
+    /// directory defaulting to the station's directory, and the station's own
+    /// overrides applied. The resolved environments are available in the
+    /// [`StationEnvs`] resource.
+    ///
+    /// [`StationEnvs`]: choochoo_rt_model::StationEnvs
+    #[must_use]
+    pub fn with_station_env(mut self, station_env: StationEnv) -> Self {
+        self.station_env = station_env;
+        self
+    }
+
+    /// Sets whether the train is a dry run.
+    ///
+    /// In a dry run, side effects that stations run through the [`Effect`]
+    /// resource are not run. Their descriptions are recorded instead, and are
+    /// available through [`TrainReport::effects_skipped`]. Station functions
+    /// still run, so they should only change the outside world through
+    /// [`Effect::run`].
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [`TrainReport::effects_skipped`]: choochoo_rt_model::TrainReport::effects_skipped
+    #[must_use]
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Sets how long entries in the profile history directory are kept.
+    ///
+    /// Entries of stations that are no longer in the destination are pruned
+    /// after each run. Use [`HistoryGc::prunable`] to list the entries that
+    /// would be pruned without removing them. When the `native` feature is
+    /// disabled, entries are not pruned.
+    ///
+    /// Defaults to [`HistoryRetention::KeepAll`].
+    ///
+    /// [`HistoryGc::prunable`]: crate::HistoryGc::prunable
+    #[must_use]
+    pub fn with_history_retention(mut self, history_retention: HistoryRetention) -> Self {
+        self.history_retention = history_retention;
+        self
+    }
+
+    /// Adds logic to insert resources before stations are set up.
+    ///
+    /// Initializers run in the order they are added, after the built in
+    /// resources and any snapshotted resources are inserted, so later
+    /// initializers may use resources inserted by earlier ones. If an
+    /// initializer fails, the train stops with [`Error::ResourceInit`] before
+    /// any station is set up.
+    #[must_use]
+    pub fn with_resource_init_fn(mut self, resource_init_fn: ResourceInitFn<E>) -> Self {
+        self.resource_init_fns.push(resource_init_fn);
+        self
+    }
+
+    /// Sets the resources to save at the end of each drive, and load at the
+    /// start of the next.
+    ///
+    /// Loaded resources are inserted before stations are set up, so setup
+    /// functions may use them to skip expensive discovery.
+    ///
+    /// Defaults to no resources.
+    #[cfg(feature = "native")]
+    #[must_use]
+    pub fn with_resource_snapshot(mut self, resource_snapshot: ResourceSnapshot<E>) -> Self {
+        self.resource_snapshot = resource_snapshot;
+        self
+    }
+
+    /// Sets the HTTP client shared between stations.
+    ///
+    /// The client is inserted into the train resources, so station functions
+    /// can borrow a `&reqwest::Client` instead of constructing a new one for
+    /// each call, which reuses connections across stations. Timeouts, proxies,
+    /// and TLS settings are configured using [`reqwest::Client::builder`].
+    ///
+    /// Defaults to a client with the default settings.
+    #[cfg(feature = "http")]
+    #[must_use]
+    pub fn with_http_client(mut self, http_client: reqwest::Client) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    /// Sets the metrics to update while stations are visited.
+    ///
+    /// Keep a clone of the metrics to render or push them while the train is
+    /// running.
+    #[cfg(feature = "metrics")]
+    #[must_use]
+    pub fn with_metrics(mut self, metrics: TrainMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
     /// Ensures the given destination is reached.
     pub async fn reach(
         &self,
         dest: &mut Destination<E>,
         visit_op: VisitOp,
     ) -> Result<TrainReport<E>, Error<E>> {
-        let progress_fut = Self::progress_tracker_init(dest);
+        self.reach_stations(dest, visit_op, None).await
+    }
+
+    /// Cleans the selected stations, and the stations that depend on them.
+    ///
+    /// Stations that are not selected are marked [`OpStatus::OpSkipped`], and
+    /// their resources are left intact. The clean plan of the selected stations
+    /// is passed to the [`ProgressBackend`] before any station is cleaned.
+    ///
+    /// To show the plan before cleaning, render the plan returned by
+    /// [`Train::clean_selection_plan`], such as with the `choochoo_cli_fmt`
+    /// `PlainTextFormatter::fmt_train_plan`.
+    ///
+    /// # Parameters
+    ///
+    /// * `dest`: Destination whose stations to clean.
+    /// * `clean_selection`: Stations and resources to clean.
+    pub async fn clean_selection(
+        &self,
+        dest: &mut Destination<E>,
+        clean_selection: &CleanSelection,
+    ) -> Result<TrainReport<E>, Error<E>> {
+        let stations_selected = self.stations_selected(dest, clean_selection).await?;
+        let train_plan = self.plan_stations(dest, VisitOp::Clean, stations_selected.clone());
+        self.progress_backend.plan(dest, &train_plan);
+
+        self.reach_stations(dest, VisitOp::Clean, Some(&stations_selected))
+            .await
+    }
+
+    /// Returns the stations that would be cleaned for the selection, without
+    /// running any station functions.
+    ///
+    /// See [`Train::plan`] for how stations are grouped.
+    ///
+    /// # Parameters
+    ///
+    /// * `dest`: Destination whose stations to plan.
+    /// * `clean_selection`: Stations and resources to clean.
+    pub async fn clean_selection_plan(
+        &self,
+        dest: &Destination<E>,
+        clean_selection: &CleanSelection,
+    ) -> Result<TrainPlan, Error<E>> {
+        let stations_selected = self.stations_selected(dest, clean_selection).await?;
+        Ok(self.plan_stations(dest, VisitOp::Clean, stations_selected))
+    }
+
+    /// Returns the stations in the clean selection, in clean order.
+    async fn stations_selected(
+        &self,
+        dest: &Destination<E>,
+        clean_selection: &CleanSelection,
+    ) -> Result<Vec<StationRtId>, Error<E>> {
+        let res_ids_previous = ResIdPersister::<E>::load(
+            dest.file_system(),
+            dest.dirs().profile_history_dir(),
+            dest.station_specs(),
+        )
+        .await?;
+
+        Ok(clean_selection.stations_selected(dest, &res_ids_previous))
+    }
+
+    /// Ensures the given destination is reached, only visiting the given
+    /// stations if any.
+    async fn reach_stations(
+        &self,
+        dest: &mut Destination<E>,
+        visit_op: VisitOp,
+        stations_selected: Option<&[StationRtId]>,
+    ) -> Result<TrainReport<E>, Error<E>> {
+        let deadline = self.deadline_calc();
+        #[cfg(feature = "native")]
+        let execution = Self::execution_name();
+        Self::station_states_clear(dest)?;
+        self.progress_theme_apply(dest)?;
+        // Estimates are only displayed, so a history that cannot be read does not stop
+        // the train.
+        if let Ok(estimated_durations) = self.estimated_durations(dest, visit_op) {
+            Self::estimated_durations_apply(dest, &estimated_durations)?;
+        }
+        let progress_fut = self.progress_tracker_init(dest);
 
         if dest.station_specs().node_count() == 0 {
-            Self::progress_tracker_join(dest, progress_fut).await?;
-            return Ok(TrainReport::default());
+            self.progress_tracker_join(dest, progress_fut).await?;
+            self.history_gc(dest)?;
+            return Ok(TrainReport::default().with_severity_threshold(self.severity_threshold));
         }
 
         let mut train_resources = TrainResources::new();
+        train_resources.insert(self.station_env.clone());
+        train_resources.insert(Effect::new(self.dry_run));
+        #[cfg(feature = "http")]
+        if let Some(http_client) = self.http_client.clone() {
+            train_resources.insert(http_client);
+        }
         ResourceInitializer::initialize(dest, &mut train_resources).await?;
+        #[cfg(feature = "native")]
+        if dest.file_system().is_native() {
+            self.resource_snapshot
+                .load(dest.dirs().profile_history_dir(), &mut train_resources)?;
+        }
+        ResourceInitializer::initialize_user(&self.resource_init_fns, &mut train_resources).await?;
+        train_resources.insert(self.execution_model);
+        if let Some(spawner) = self.spawner.clone() {
+            train_resources.insert(spawner);
+        }
 
-        train_resources = Self::stations_setup(dest, visit_op, train_resources)
+        train_resources = self
+            .stations_setup(dest, visit_op, train_resources, stations_selected)
             .await
             .or_else(|error| {
                 if let Error::StationSetup { train_resources } = error {
@@ -79,95 +656,612 @@ where
             })?;
 
         // If here are no errors during setup, then we visit each station.
-        let train_report = if train_resources.station_errors().read().await.is_empty() {
+        let train_report = if train_resources.station_errors().is_empty().await {
+            self.execution_strategy
+                .stations_prepare(dest, &train_resources, self.chaos_policy.as_ref(), visit_op)
+                .await?;
+
             let train_report = match visit_op {
-                VisitOp::Create => TrainCreate::stations_visit(self, dest, train_resources).await?,
-                VisitOp::Clean => TrainClean::stations_visit(self, dest, train_resources).await?,
+                VisitOp::Create => {
+                    TrainCreate::stations_visit(self, dest, train_resources, deadline).await?
+                }
+                VisitOp::Clean => {
+                    TrainClean::stations_visit(self, dest, train_resources, deadline).await?
+                }
             };
-            Self::progress_tracker_join(dest, progress_fut).await?;
+            self.progress_tracker_join(dest, progress_fut).await?;
+            let critical_path = self
+                .plan(dest, visit_op)
+                .critical_path(train_report.station_durations());
             train_report
+                .with_stations_deadline_exceeded(Self::stations_deadline_exceeded(dest))
+                .with_station_sub_steps(Self::station_sub_steps(dest))
+                .with_station_artifacts(Self::station_artifacts(dest))
+                .with_critical_path(critical_path)
         } else {
-            Self::progress_tracker_join(dest, progress_fut).await?;
+            self.progress_tracker_join(dest, progress_fut).await?;
             TrainReport::new(train_resources, ResIds::new())
         };
+        #[cfg(feature = "native")]
+        if dest.file_system().is_native() {
+            self.resource_snapshot.save(
+                dest.dirs().profile_history_dir(),
+                train_report.train_resources(),
+            )?;
+        }
+        #[cfg(feature = "native")]
+        let train_report = if dest.file_system().is_native() {
+            let artifacts_dir = ArtifactCollector::<E>::collect(
+                dest.dirs().profile_history_dir(),
+                &execution,
+                dest.station_specs(),
+                train_report.station_artifacts(),
+            )?;
+            match artifacts_dir {
+                Some(artifacts_dir) => train_report.with_artifacts_dir(artifacts_dir),
+                None => train_report,
+            }
+        } else {
+            train_report
+        };
+        self.history_gc(dest)?;
+        let train_report = train_report.resources_teardown().await;
+        #[cfg(feature = "native")]
+        if let Ok(mut profile_lock) = train_report
+            .train_resources()
+            .try_borrow_mut::<ProfileLock<E>>()
+        {
+            profile_lock.release()?;
+        }
 
-        Ok(train_report)
+        Ok(train_report.with_severity_threshold(self.severity_threshold))
     }
 
-    /// Initializes the progress tracker.
-    fn progress_tracker_init(dest: &Destination<E>) -> JoinHandle<std::io::Result<()>> {
-        let multi_progress = MultiProgress::new();
+    /// Prunes entries in the profile history directory according to the
+    /// history retention policy.
+    #[cfg_attr(not(feature = "native"), allow(unused_variables))]
+    fn history_gc(&self, dest: &Destination<E>) -> Result<(), Error<E>> {
+        #[cfg(feature = "native")]
+        if dest.file_system().is_native() {
+            HistoryGc::prune(
+                dest.dirs().profile_history_dir(),
+                dest.station_specs(),
+                self.history_retention,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Ensures the given destination is reached, visiting one station at a
+    /// time.
+    ///
+    /// Stations are set up in insertion order, and visited one after another
+    /// in dependency order, so the order in which stations are visited -- and
+    /// the progress and errors recorded -- is the same for each run.
+    ///
+    /// This is useful for tests that assert on the order of events or on
+    /// formatted output. All other train settings, such as the deadline and
+    /// execution policy, still apply.
+    pub async fn reach_deterministic(
+        &self,
+        dest: &mut Destination<E>,
+        visit_op: VisitOp,
+    ) -> Result<TrainReport<E>, Error<E>> {
+        let train = Self {
+            concurrency_max: NonZeroUsize::new(1),
+            work_concurrency_max: self.work_concurrency_max,
+            severity_threshold: self.severity_threshold,
+            work_not_idempotent_severity: self.work_not_idempotent_severity,
+            execution_policy: self.execution_policy,
+            rollback_policy: self.rollback_policy,
+            execution_strategy: Arc::clone(&self.execution_strategy),
+            deadline: self.deadline,
+            timer: self.timer.clone(),
+            timeout: self.timeout,
+            progress_theme: self.progress_theme.clone(),
+            progress_backend: Arc::clone(&self.progress_backend),
+            verbosity: self.verbosity,
+            chaos_policy: self.chaos_policy.clone(),
+            execution_model: self.execution_model,
+            spawner: self.spawner.clone(),
+            resource_limits: self.resource_limits.clone(),
+            station_env: self.station_env.clone(),
+            dry_run: self.dry_run,
+            history_retention: self.history_retention,
+            resource_init_fns: self.resource_init_fns.clone(),
+            #[cfg(feature = "native")]
+            resource_snapshot: self.resource_snapshot.clone(),
+            #[cfg(feature = "http")]
+            http_client: self.http_client.clone(),
+            #[cfg(feature = "metrics")]
+            metrics: self.metrics.clone(),
+            marker: PhantomData,
+        };
+
+        train.reach(dest, visit_op).await
+    }
+
+    /// Compares the resources that exist with the resource IDs each station
+    /// persisted in previous executions.
+    ///
+    /// Stations are set up as they are for creation, then each station's
+    /// [`exists_fn`] is run with its persisted resource IDs. Stations without
+    /// an exists function are not audited, and no station's work is run.
+    ///
+    /// [`exists_fn`]: choochoo_cfg_model::StationSpec::exists_fn
+    pub async fn audit(
+        &self,
+        dest: &mut Destination<E>,
+    ) -> Result<ReconciliationReport<E>, Error<E>> {
+        let mut train_resources = TrainResources::new();
+        train_resources.insert(self.station_env.clone());
+        #[cfg(feature = "http")]
+        if let Some(http_client) = self.http_client.clone() {
+            train_resources.insert(http_client);
+        }
+        ResourceInitializer::initialize(dest, &mut train_resources).await?;
+        ResourceInitializer::initialize_user(&self.resource_init_fns, &mut train_resources).await?;
+        train_resources.insert(self.execution_model);
+        if let Some(spawner) = self.spawner.clone() {
+            train_resources.insert(spawner);
+        }
+
+        // Setup functions may insert resources that exists functions borrow.
+        let mut stations_setup_failed = Vec::new();
+        for station in dest.try_stations_mut() {
+            let mut station = station?;
+            if let Err(station_error) = station.create_setup(&mut train_resources).await {
+                stations_setup_failed.push(station.rt_id);
+                Self::station_error_insert(
+                    &train_resources,
+                    station.rt_id,
+                    StationErrorPhase::Setup,
+                    station_error,
+                )
+                .await;
+            }
+        }
+
+        let mut station_reconciliations = IndexMap::new();
+        {
+            let res_ids_previous = train_resources.borrow::<ResIdsPrevious>();
+            let mut stations = dest.stations_mut_stream().boxed_local();
+            while let Some(station) = stations.next().await {
+                if stations_setup_failed.contains(&station.rt_id) {
+                    continue;
+                }
+
+                match ExistsDriver::reconcile(&station, &res_ids_previous, &train_resources).await {
+                    Some(Ok(station_reconciliation)) => {
+                        station_reconciliations.insert(station.rt_id, station_reconciliation);
+                    }
+                    Some(Err(station_error)) => {
+                        Self::station_error_insert(
+                            &train_resources,
+                            station.rt_id,
+                            StationErrorPhase::Check,
+                            station_error,
+                        )
+                        .await;
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        let reconciliation_report =
+            ReconciliationReport::new(train_resources, station_reconciliations)
+                .resources_teardown()
+                .await;
+        #[cfg(feature = "native")]
+        if let Ok(mut profile_lock) = reconciliation_report
+            .train_resources()
+            .try_borrow_mut::<ProfileLock<E>>()
+        {
+            profile_lock.release()?;
+        }
+
+        Ok(reconciliation_report)
+    }
+
+    /// Returns the stations that would be visited to reach the destination,
+    /// without running any station functions.
+    ///
+    /// Stations are grouped into waves of stations that may be visited
+    /// concurrently. The number of stations visited at the same time is still
+    /// limited by the train's maximum concurrency, and higher priority
+    /// stations are preferred when more stations are ready than this limit.
+    ///
+    /// The plan includes the destination's [`DestinationReachability`], which
+    /// lists stations that are not connected to the rest of the destination,
+    /// and resources limited by the train's [`ResourceLimits`] that no station
+    /// accesses.
+    ///
+    /// # Parameters
+    ///
+    /// * `dest`: Destination whose stations to plan.
+    /// * `visit_op`: Operation the stations would be visited for.
+    ///
+    /// [`DestinationReachability`]: choochoo_rt_model::DestinationReachability
+    pub fn plan(&self, dest: &Destination<E>, visit_op: VisitOp) -> TrainPlan {
+        let station_order = match visit_op {
+            VisitOp::Create => dest
+                .station_specs()
+                .iter()
+                .filter_map(|station_spec| dest.station_id_to_rt_id().get(station_spec.id()))
+                .copied()
+                .collect::<Vec<_>>(),
+            VisitOp::Clean => dest.clean_order(),
+        };
+
+        self.plan_stations(dest, visit_op, station_order)
+    }
+
+    /// Returns the plan to visit the given stations.
+    ///
+    /// `station_order` must list each station after the stations it depends on
+    /// for the given operation.
+    fn plan_stations(
+        &self,
+        dest: &Destination<E>,
+        visit_op: VisitOp,
+        station_order: Vec<StationRtId>,
+    ) -> TrainPlan {
+        let station_specs = dest.station_specs();
+        let reachability = dest.reachability(self.resource_limits.type_names());
+        let train_plan = TrainPlan::new(visit_op).with_reachability(reachability);
+
+        station_order
+            .into_iter()
+            .fold(train_plan, |mut train_plan, station_rt_id| {
+                // When cleaning, a station is visited after the stations that depend on it.
+                let dependencies = match visit_op {
+                    VisitOp::Create => station_specs
+                        .parents(station_rt_id)
+                        .iter(station_specs)
+                        .map(|(_edge, dependency_rt_id)| dependency_rt_id)
+                        .collect::<Vec<_>>(),
+                    VisitOp::Clean => station_specs
+                        .children(station_rt_id)
+                        .iter(station_specs)
+                        .map(|(_edge, dependency_rt_id)| dependency_rt_id)
+                        .collect::<Vec<_>>(),
+                };
+                let progress_limit = dest
+                    .station_progresses()
+                    .get(&station_rt_id)
+                    .and_then(|station_progress| station_progress.try_borrow().ok())
+                    .map(|station_progress| station_progress.progress_limit())
+                    .unwrap_or_default();
+
+                let priority = station_specs[station_rt_id].priority();
+
+                train_plan.station_add(station_rt_id, dependencies, progress_limit, priority);
+                train_plan
+            })
+    }
+
+    /// Returns the estimated time each station's work takes.
+    ///
+    /// A station's estimated duration is the one declared on its spec. When
+    /// it is not declared, the duration of the station's last successful
+    /// visit for the same operation is used, as recorded in the profile's
+    /// audit log. Stations without either are not included.
+    ///
+    /// # Parameters
+    ///
+    /// * `dest`: Destination whose stations to estimate.
+    /// * `visit_op`: Operation the stations would be visited for.
+    pub fn estimated_durations(
+        &self,
+        dest: &Destination<E>,
+        visit_op: VisitOp,
+    ) -> Result<IndexMap<StationRtId, Duration>, Error<E>> {
+        #[cfg(feature = "native")]
+        let durations_recorded = {
+            let audit_query = AuditQuery::new().with_visit_op(visit_op);
+            AuditLog::<E>::query(dest.dirs().profile_history_dir(), &audit_query)?
+                .into_iter()
+                .filter(|audit_entry| audit_entry.op_status == OpStatus::WorkSuccess)
+                .filter_map(|audit_entry| {
+                    let duration = audit_entry.end.duration_since(audit_entry.start).ok()?;
+                    Some((audit_entry.station_id, duration))
+                })
+                .collect::<IndexMap<StationId, Duration>>()
+        };
+        #[cfg(not(feature = "native"))]
+        let durations_recorded = {
+            let _visit_op = visit_op;
+            IndexMap::<StationId, Duration>::new()
+        };
+
+        let estimated_durations = dest
+            .station_specs()
+            .iter_insertion_with_indices()
+            .filter_map(|(station_rt_id, station_spec)| {
+                station_spec
+                    .estimated_duration()
+                    .or_else(|| durations_recorded.get(station_spec.id()).copied())
+                    .map(|estimated_duration| (station_rt_id, estimated_duration))
+            })
+            .collect::<IndexMap<StationRtId, Duration>>();
+
+        Ok(estimated_durations)
+    }
+
+    /// Returns the estimated time the train takes to reach the destination.
+    ///
+    /// This is the duration of the [critical path] through the stations'
+    /// [estimated durations], so it assumes the train visits enough stations
+    /// concurrently, and that stations without an estimate take no time.
+    ///
+    /// # Parameters
+    ///
+    /// * `dest`: Destination whose stations to estimate.
+    /// * `visit_op`: Operation the stations would be visited for.
+    ///
+    /// [critical path]: TrainPlan::critical_path
+    /// [estimated durations]: Self::estimated_durations
+    pub fn eta(&self, dest: &Destination<E>, visit_op: VisitOp) -> Result<Duration, Error<E>> {
+        let estimated_durations = self.estimated_durations(dest, visit_op)?;
+        let critical_path = self
+            .plan(dest, visit_op)
+            .critical_path(&estimated_durations);
+
+        Ok(critical_path.duration())
+    }
+
+    /// Sets the estimated duration shown in each station's progress bar.
+    fn estimated_durations_apply(
+        dest: &Destination<E>,
+        estimated_durations: &IndexMap<StationRtId, Duration>,
+    ) -> Result<(), Error<E>> {
+        dest.try_stations_mut().try_for_each(|station| {
+            station.map(|mut station| {
+                let estimated_duration = estimated_durations.get(&station.rt_id).copied();
+                if station.progress.estimated_duration() != estimated_duration {
+                    station.progress.estimated_duration_set(estimated_duration);
+                }
+            })
+        })
+    }
+
+    /// Returns the earlier of the configured deadline and timeout.
+    ///
+    /// The current time is only read when there is a timeout, as reading it
+    /// is not supported on all targets.
+    fn deadline_calc(&self) -> Option<Instant> {
+        let timeout_deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+        match (self.deadline, timeout_deadline) {
+            (Some(deadline), Some(timeout_deadline)) => Some(deadline.min(timeout_deadline)),
+            (deadline @ Some(_), None) | (None, deadline @ Some(_)) => deadline,
+            (None, None) => None,
+        }
+    }
+
+    /// Returns the stations that did not complete before the deadline.
+    fn stations_deadline_exceeded(dest: &Destination<E>) -> Vec<StationRtId> {
         dest.station_specs()
-            .graph()
-            .node_indices()
-            .filter_map(|station_rt_id| dest.station_progresses().get(&station_rt_id))
-            .for_each(|station_progress| {
-                let progress_bar = station_progress.borrow().progress_bar().clone();
-                let progress_bar_for_tick = progress_bar.clone();
-                multi_progress.add(progress_bar);
-
-                // Needed to render all progress bars.
-                progress_bar_for_tick.tick();
-            });
+            .iter_insertion_with_indices()
+            .map(|(station_rt_id, _)| station_rt_id)
+            .filter(|station_rt_id| {
+                dest.station_progresses()
+                    .get(station_rt_id)
+                    .map(|station_progress| {
+                        station_progress.borrow().op_status == OpStatus::DeadlineExceeded
+                    })
+                    .unwrap_or(false)
+            })
+            .collect()
+    }
 
-        tokio::task::spawn_blocking(move || multi_progress.join())
+    /// Returns the sub-steps of each station that has them.
+    fn station_sub_steps(dest: &Destination<E>) -> IndexMap<StationRtId, Vec<SubStep>> {
+        dest.station_specs()
+            .iter_insertion_with_indices()
+            .filter_map(|(station_rt_id, _)| {
+                dest.station_progresses()
+                    .get(&station_rt_id)
+                    .map(|station_progress| station_progress.borrow().sub_steps().to_vec())
+                    .filter(|sub_steps| !sub_steps.is_empty())
+                    .map(|sub_steps| (station_rt_id, sub_steps))
+            })
+            .collect()
+    }
+
+    /// Returns the artifacts registered by each station that registered any.
+    fn station_artifacts(dest: &Destination<E>) -> IndexMap<StationRtId, Vec<StationArtifact>> {
+        dest.station_specs()
+            .iter_insertion_with_indices()
+            .filter_map(|(station_rt_id, _)| {
+                dest.station_progresses()
+                    .get(&station_rt_id)
+                    .map(|station_progress| station_progress.borrow().artifacts().to_vec())
+                    .filter(|artifacts| !artifacts.is_empty())
+                    .map(|artifacts| (station_rt_id, artifacts))
+            })
+            .collect()
+    }
+
+    /// Returns the name of the directory that this drive's artifacts are
+    /// collected into, which is the number of milliseconds since the UNIX
+    /// epoch.
+    #[cfg(feature = "native")]
+    fn execution_name() -> String {
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis()
+            .to_string()
+    }
+
+    /// Clears each station's operation state and artifacts from previous runs.
+    fn station_states_clear(dest: &Destination<E>) -> Result<(), Error<E>> {
+        dest.try_stations_mut().try_for_each(|station| {
+            station.map(|mut station| {
+                station.state.clear();
+                station.progress.artifacts_clear();
+            })
+        })
+    }
+
+    /// Applies the train's progress theme to stations without their own theme.
+    fn progress_theme_apply(&self, dest: &Destination<E>) -> Result<(), Error<E>> {
+        if let Some(progress_theme) = self.progress_theme.as_ref() {
+            dest.try_stations_mut().try_for_each(|station| {
+                station.map(|mut station| {
+                    if station.spec.progress_theme().is_none() {
+                        station
+                            .progress
+                            .progress_theme_set(station.spec, progress_theme.clone());
+                    }
+                })
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Initializes the progress tracker.
+    fn progress_tracker_init(
+        &self,
+        dest: &Destination<E>,
+    ) -> Option<JoinHandle<std::io::Result<()>>> {
+        if self.verbosity == Verbosity::Quiet {
+            return None;
+        }
+        self.progress_backend.init(dest)
     }
 
     /// Waits for the progress tracker to complete.
     async fn progress_tracker_join(
+        &self,
         dest: &mut Destination<E>,
-        progress_fut: JoinHandle<Result<(), std::io::Error>>,
+        progress_fut: Option<JoinHandle<Result<(), std::io::Error>>>,
     ) -> Result<(), Error<E>> {
-        // We need to finish / abandon all progress bars, otherwise the `MultiProgress`
-        // will never finish.
-        dest.stations_mut().for_each(|station| {
-            if !station.progress.progress_bar().is_finished() {
-                station.progress.progress_bar().finish_at_current_pos();
-            }
-        });
+        if self.verbosity != Verbosity::Quiet {
+            self.progress_backend.finish(dest);
+        }
 
-        progress_fut
-            .await
-            .map_err(Error::MultiProgressTaskJoin)?
-            .map_err(Error::MultiProgressJoin)?;
+        if let Some(progress_fut) = progress_fut {
+            progress_fut
+                .await
+                .map_err(Error::MultiProgressTaskJoin)?
+                .map_err(Error::MultiProgressJoin)?;
+        }
 
         Ok(())
     }
 
+    /// Renders a station's progress after its status changes.
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    pub(crate) fn progress_update(
+        &self,
+        station_id: &StationId,
+        station_progress: &StationProgress,
+    ) {
+        if self.verbosity != Verbosity::Quiet {
+            self.progress_backend.update(station_progress);
+        }
+
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = self.metrics.as_ref() {
+            metrics.station_update(station_id, station_progress);
+        }
+    }
+
+    /// Records how long a station's visit took, and writes it above the
+    /// progress bars when the verbosity is [`Verbosity::Verbose`].
+    pub(crate) fn station_duration_record(
+        &self,
+        station_durations: &Mutex<IndexMap<StationRtId, Duration>>,
+        station: &StationMutRef<'_, E>,
+        duration: Duration,
+    ) {
+        station_durations
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(station.rt_id, duration);
+
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = self.metrics.as_ref() {
+            metrics.station_duration_record(station.spec.id(), duration);
+        }
+
+        if self.verbosity == Verbosity::Verbose {
+            station.progress.progress_bar().println(format!(
+                "{id}: {op_status:?} in {duration:.2?}",
+                id = station.spec.id(),
+                op_status = station.progress.op_status,
+                duration = duration
+            ));
+        }
+    }
+
     async fn stations_setup(
+        &self,
         dest: &mut Destination<E>,
         visit_op: VisitOp,
         train_resources: TrainResources<E>,
+        stations_selected: Option<&[StationRtId]>,
     ) -> Result<TrainResources<E>, Error<E>> {
         match visit_op {
-            VisitOp::Create => Self::stations_setup_create(dest, train_resources).await,
-            VisitOp::Clean => Self::stations_setup_clean(dest, train_resources).await,
+            VisitOp::Create => self.stations_setup_create(dest, train_resources).await,
+            VisitOp::Clean => {
+                self.stations_setup_clean(dest, train_resources, stations_selected)
+                    .await
+            }
         }
     }
 
     async fn stations_setup_create(
+        &self,
         dest: &mut Destination<E>,
-        train_resources: TrainResources<E>,
+        mut train_resources: TrainResources<E>,
     ) -> Result<TrainResources<E>, Error<E>> {
-        stream::iter(dest.stations_mut().map(Result::<_, Error<E>>::Ok))
+        train_resources.insert(StationInputsDigests::new());
+        train_resources.insert(CheckStatusesSpeculative::new());
+
+        #[cfg(feature = "native")]
+        let file_system = dest.file_system();
+        stream::iter(dest.try_stations_mut())
             .try_fold(
                 train_resources,
                 |mut train_resources, mut station| async move {
                     let setup_result = station.create_setup(&mut train_resources).await;
+                    #[cfg(feature = "native")]
+                    let setup_result = match setup_result {
+                        Ok(progress_limit) => StationInputsHasher::digest_insert(
+                            file_system,
+                            &train_resources,
+                            &station,
+                        )
+                        .await
+                        .map(|()| progress_limit),
+                        Err(station_error) => Err(station_error),
+                    };
 
                     match setup_result {
                         Ok(progress_limit) => {
                             station.progress.op_status = OpStatus::SetupSuccess;
                             station.progress.progress_limit_set(progress_limit);
-                            station.progress.progress_style_update();
+                            self.progress_update(station.spec.id(), &station.progress);
+                            FileInputResolver::publish(
+                                station.spec.id(),
+                                &station.progress,
+                                &train_resources,
+                            )
+                            .await;
                             Ok(train_resources)
                         }
                         Err(station_error) => {
                             station.progress.op_status = OpStatus::SetupFail;
-                            station.progress.progress_style_update();
+                            self.progress_update(station.spec.id(), &station.progress);
                             Self::station_error_insert(
                                 &train_resources,
                                 station.rt_id,
+                                StationErrorPhase::Setup,
                                 station_error,
                             )
                             .await;
@@ -180,28 +1274,45 @@ where
     }
 
     async fn stations_setup_clean(
+        &self,
         dest: &mut Destination<E>,
         train_resources: TrainResources<E>,
+        stations_selected: Option<&[StationRtId]>,
     ) -> Result<TrainResources<E>, Error<E>> {
-        stream::iter(dest.stations_mut().map(Result::<_, Error<E>>::Ok))
+        stream::iter(dest.try_stations_mut())
             .try_fold(
                 train_resources,
                 |mut train_resources, mut station| async move {
+                    let is_selected = stations_selected.map_or(true, |stations_selected| {
+                        stations_selected.contains(&station.rt_id)
+                    });
+                    if !is_selected {
+                        station.progress.op_status = OpStatus::OpSkipped;
+                        self.progress_update(station.spec.id(), &station.progress);
+                        return Ok(train_resources);
+                    }
+
                     let setup_result = station.clean_setup(&mut train_resources).await;
 
                     match setup_result {
                         Some(Ok(progress_limit)) => {
+                            let progress_limit = Self::clean_progress_limit(
+                                &train_resources,
+                                station.rt_id,
+                                progress_limit,
+                            );
                             station.progress.op_status = OpStatus::SetupSuccess;
                             station.progress.progress_limit_set(progress_limit);
-                            station.progress.progress_style_update();
+                            self.progress_update(station.spec.id(), &station.progress);
                             Ok(train_resources)
                         }
                         Some(Err(station_error)) => {
                             station.progress.op_status = OpStatus::SetupFail;
-                            station.progress.progress_style_update();
+                            self.progress_update(station.spec.id(), &station.progress);
                             Self::station_error_insert(
                                 &train_resources,
                                 station.rt_id,
+                                StationErrorPhase::Setup,
                                 station_error,
                             )
                             .await;
@@ -217,14 +1328,222 @@ where
             .await
     }
 
+    /// Returns the progress limit for a station's clean operation.
+    ///
+    /// When the station's clean setup function returns
+    /// [`ProgressLimit::Unknown`], one step is used for each resource ID the
+    /// station persisted in previous executions.
+    fn clean_progress_limit(
+        train_resources: &TrainResources<E>,
+        station_rt_id: StationRtId,
+        progress_limit: ProgressLimit,
+    ) -> ProgressLimit {
+        if progress_limit != ProgressLimit::Unknown {
+            return progress_limit;
+        }
+
+        train_resources
+            .try_borrow::<ResIdsPrevious>()
+            .ok()
+            .and_then(|res_ids_previous| {
+                res_ids_previous
+                    .station_res_ids(station_rt_id)
+                    .map(|station_res_ids| station_res_ids.len())
+            })
+            .filter(|res_ids_count| *res_ids_count > 0)
+            .map_or(progress_limit, |res_ids_count| {
+                ProgressLimit::Steps(res_ids_count as u64)
+            })
+    }
+
+    /// Returns the visit result, or the [`OpStatus`] to set if the visit was
+    /// interrupted.
+    ///
+    /// Visits are interrupted when:
+    ///
+    /// * The execution policy is [`ExecutionPolicy::StopImmediately`] and the
+    ///   train is stopped, returning [`OpStatus::WorkInterrupted`].
+    /// * The deadline is reached, returning [`OpStatus::DeadlineExceeded`].
+    async fn station_visit_interruptible<'f, T>(
+        &self,
+        train_stop: &TrainStop,
+        visit_fut: LocalBoxFuture<'f, T>,
+    ) -> Result<T, OpStatus> {
+        let failure_stop = async {
+            match self.execution_policy {
+                ExecutionPolicy::StopImmediately => train_stop.stopped().await,
+                ExecutionPolicy::ContinueIndependent | ExecutionPolicy::FailFast => {
+                    future::pending::<()>().await
+                }
+            }
+        }
+        .boxed_local();
+        let deadline_stop = train_stop.deadline_reached().boxed_local();
+        let interrupt = future::select(failure_stop, deadline_stop).map(|either| match either {
+            Either::Left(_) => OpStatus::WorkInterrupted,
+            Either::Right(_) => OpStatus::DeadlineExceeded,
+        });
+
+        match future::select(visit_fut, interrupt).await {
+            Either::Left((visit_result, _)) => Ok(visit_result),
+            Either::Right((op_status, _)) => Err(op_status),
+        }
+    }
+
+    /// Stops the train if the station failed and the execution policy stops on
+    /// failure.
+    fn station_failure_handle(&self, train_stop: &TrainStop, op_status: OpStatus) {
+        let station_failed = matches!(
+            op_status,
+            OpStatus::CheckFail | OpStatus::WorkFail | OpStatus::VerifyFail | OpStatus::Panicked
+        );
+        if station_failed && self.execution_policy.stops_on_failure() {
+            train_stop.stop();
+        }
+    }
+
+    /// Appends an audit entry for a station whose work or clean function was
+    /// invoked.
+    ///
+    /// Stations whose visit only ran the check function are not recorded, and
+    /// nothing is recorded when the destination's file system is not native.
+    /// Failure to append the entry is recorded as a station error.
+    #[cfg(feature = "native")]
+    async fn station_audit_record(
+        dest: &Destination<E>,
+        station: &StationMutRef<'_, E>,
+        visit_op: VisitOp,
+        visit_start: SystemTime,
+        res_ids: Option<&ResIds>,
+        train_resources: &TrainResources<E>,
+    ) {
+        let work_invoked = matches!(
+            station.progress.op_status,
+            OpStatus::WorkSuccess
+                | OpStatus::WorkFail
+                | OpStatus::VerifyFail
+                | OpStatus::Panicked
+                | OpStatus::WorkInterrupted
+        );
+        if !work_invoked || !dest.file_system().is_native() {
+            return;
+        }
+
+        let res_ids = res_ids
+            .map(|res_ids| {
+                res_ids
+                    .iter()
+                    .filter_map(|(res_id_logical, res_id_physical)| {
+                        serde_json::to_value(res_id_physical)
+                            .ok()
+                            .map(|res_id_physical| (res_id_logical.clone(), res_id_physical))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let audit_entry = AuditEntry {
+            station_id: station.spec.id().clone(),
+            visit_op,
+            start: visit_start,
+            end: SystemTime::now(),
+            op_status: station.progress.op_status,
+            res_ids,
+            profile: dest.profile().to_string(),
+            user: std::env::var("USER")
+                .or_else(|_| std::env::var("USERNAME"))
+                .ok(),
+            host: Some(profile_lock::host_name()).filter(|host_name| !host_name.is_empty()),
+        };
+
+        let append_result = match train_resources.try_borrow::<ProfileHistoryDir>() {
+            Ok(profile_history_dir) => AuditLog::<E>::append(&profile_history_dir, &audit_entry)
+                .map_err(|error| error.to_string()),
+            Err(borrow_fail) => Err(format!("{borrow_fail:?}")),
+        };
+        if let Err(detail) = append_result {
+            let station_error = E::from(StationSpecError::AuditLogWriteFail {
+                id: station.spec.id().clone(),
+                name: station.spec.name().to_string(),
+                detail,
+                location: station.spec.location(),
+            });
+            Self::station_error_insert(
+                train_resources,
+                station.rt_id,
+                StationErrorPhase::from(visit_op),
+                station_error,
+            )
+            .await;
+        }
+    }
+
+    /// Removes the `tmp/` directory of a station whose visit has completed.
+    ///
+    /// Failure to remove the directory is recorded as a station error.
+    async fn station_tmp_dir_clean(
+        dest: &Destination<E>,
+        station: &StationMutRef<'_, E>,
+        visit_op: VisitOp,
+        train_resources: &TrainResources<E>,
+    ) {
+        let file_system = dest.file_system();
+        let tmp_dir = station.dir.tmp_dir();
+        if !file_system.exists(&tmp_dir).await {
+            return;
+        }
+        if let Err(error) = file_system.remove_dir_all(&tmp_dir).await {
+            let station_error = E::from(StationSpecError::TmpDirCleanFail {
+                id: station.spec.id().clone(),
+                name: station.spec.name().to_string(),
+                detail: error.to_string(),
+                location: station.spec.location(),
+            });
+            Self::station_error_insert(
+                train_resources,
+                station.rt_id,
+                StationErrorPhase::from(visit_op),
+                station_error,
+            )
+            .await;
+        }
+    }
+
     async fn station_error_insert(
         train_resources: &TrainResources<E>,
         station_rt_id: StationRtId,
+        phase: StationErrorPhase,
         station_error: E,
     ) {
         let station_errors = train_resources.station_errors();
-        let mut station_errors = station_errors.write().await;
-        station_errors.insert(station_rt_id, station_error);
+        station_errors
+            .insert(station_rt_id, phase, station_error)
+            .await;
+    }
+
+    /// Records a station spec error found after a station's work succeeded.
+    ///
+    /// [`StationSpecError::WorkNotIdempotent`] is recorded as a warning when
+    /// `work_not_idempotent_severity` is lower than [`Severity::Error`]. Other
+    /// errors are recorded as station errors.
+    async fn station_spec_error_insert(
+        train_resources: &TrainResources<E>,
+        station_rt_id: StationRtId,
+        phase: StationErrorPhase,
+        station_spec_error: StationSpecError,
+        work_not_idempotent_severity: Severity,
+    ) {
+        let is_warning = matches!(
+            station_spec_error,
+            StationSpecError::WorkNotIdempotent { .. }
+        ) && work_not_idempotent_severity < Severity::Error;
+        let station_error = E::from(station_spec_error);
+
+        if is_warning {
+            let station_warnings = train_resources.station_warnings();
+            station_warnings.insert(station_rt_id, station_error).await;
+        } else {
+            Self::station_error_insert(train_resources, station_rt_id, phase, station_error).await;
+        }
     }
 }
 
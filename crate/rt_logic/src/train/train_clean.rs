@@ -1,13 +1,32 @@
-use std::{fmt, marker::PhantomData, num::NonZeroUsize};
+#[cfg(feature = "native")]
+use std::time::SystemTime;
+use std::{
+    fmt,
+    marker::PhantomData,
+    sync::{Mutex, PoisonError},
+    time::{Duration, Instant},
+};
 
-use choochoo_cfg_model::rt::{OpStatus, ResIds, StationMutRef, TrainResources};
+use choochoo_cfg_model::{
+    indexmap::IndexMap,
+    rt::{
+        OpStatus, ResIds, ResourceScope, StationErrorPhase, StationMutRef, StationRtId,
+        TrainResources, VisitOp,
+    },
+};
 use choochoo_rt_model::{
     error::StationSpecError, CleanEnsureOutcomeErr, CleanEnsureOutcomeOk, Destination, Error,
-    TrainReport,
+    FileSystem, TrainReport,
+};
+use futures::{
+    future::FutureExt,
+    stream::{StreamExt, TryStreamExt},
 };
-use futures::stream::StreamExt;
 
-use crate::{CleanDriver, CleanOpStatusUpdater, Train};
+use crate::{
+    train::TrainStop, CleanDriver, CleanOpStatusUpdater, HookDriver, OpStatusPropagator,
+    StationPriorityStream, StationQueue, StationStallWatchdog, StationWorkers, Train,
+};
 
 /// Logic to manage resource cleaning.
 pub(crate) struct TrainClean<E>(PhantomData<E>);
@@ -21,13 +40,34 @@ where
         train: &Train<E>,
         dest: &mut Destination<E>,
         train_resources: TrainResources<E>,
+        deadline: Option<Instant>,
     ) -> Result<TrainReport<E>, Error<E>> {
         // Set `ParentPending` stations to `OpQueued` if they have no dependencies.
         CleanOpStatusUpdater::update(dest);
 
-        Self::stations_visit_each(train, dest, &train_resources).await;
+        let station_queue = StationQueue::new(
+            dest,
+            &train.resource_limits,
+            train.concurrency_max,
+            train.work_concurrency_max,
+        );
+        let station_durations = Mutex::new(IndexMap::new());
+        Self::stations_visit_each(
+            train,
+            dest,
+            &train_resources,
+            &station_queue,
+            &station_durations,
+            deadline,
+        )
+        .await?;
 
-        let train_report = TrainReport::new(train_resources, ResIds::new());
+        let station_durations = station_durations
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner);
+        let train_report = TrainReport::new(train_resources, ResIds::new())
+            .with_queue_metrics(station_queue.into_queue_metrics())
+            .with_station_durations(station_durations);
         Ok(train_report)
     }
 
@@ -35,40 +75,137 @@ where
         train: &Train<E>,
         dest: &Destination<E>,
         train_resources: &TrainResources<E>,
-    ) {
-        dest.stations_mut_stream_rev()
-            .map(|mut station| async move {
-                station.progress.progress_style_update();
-                if station.progress.op_status == OpStatus::OpQueued
-                    || station.progress.op_status == OpStatus::SetupSuccess
-                {
-                    // Because this is in an async block, concurrent tasks may access this
-                    // station's `op_status` while the `visit()` is
-                    // `await`ed.
-                    station.progress.op_status = OpStatus::WorkInProgress;
-                    station.progress.progress_style_update();
-
-                    Self::stations_visit_station_ensure(&mut station, train_resources).await
-                };
-                station.progress.progress_style_update();
+        station_queue: &StationQueue,
+        station_durations: &Mutex<IndexMap<StationRtId, Duration>>,
+        deadline: Option<Instant>,
+    ) -> Result<(), Error<E>> {
+        let train_stop = &TrainStop::new(deadline, train.timer.clone());
+        let op_status_propagator = &OpStatusPropagator::new(dest, VisitOp::Clean);
+        let stall_watchdog = &StationStallWatchdog::new(dest);
+        let stations =
+            StationPriorityStream::new(dest.stations_mut_stream_rev().map(Ok).boxed_local());
+        let station_workers = &StationWorkers::new(
+            station_queue,
+            train.concurrency_max,
+            dest.station_specs().node_count(),
+        );
+        let stations = stall_watchdog
+            .watch(stations)
+            .map_ok(|mut station| async move {
+                train.progress_update(station.spec.id(), &station.progress);
+                match station.progress.op_status {
+                    OpStatus::OpQueued | OpStatus::SetupSuccess | OpStatus::ParentPending
+                        if train_stop.is_deadline_exceeded() =>
+                    {
+                        station.progress.op_status = OpStatus::DeadlineExceeded;
+                    }
+                    OpStatus::OpQueued | OpStatus::SetupSuccess | OpStatus::ParentPending
+                        if train_stop.is_stopped() =>
+                    {
+                        station.progress.op_status = OpStatus::OpSkipped;
+                    }
+                    OpStatus::OpQueued | OpStatus::SetupSuccess => {
+                        let visit_fut = async {
+                            // Stations remain queued while waiting for limited resources.
+                            let _permits = station_queue
+                                .acquire(&station.spec, station.rt_id, VisitOp::Clean)
+                                .await;
+
+                            // Because this is in an async block, concurrent tasks may access this
+                            // station's `op_status` while the `visit()` is `await`ed.
+                            station.progress.op_status = OpStatus::WorkInProgress;
+                            train.progress_update(station.spec.id(), &station.progress);
+
+                            let visit_begin = Instant::now();
+                            #[cfg(feature = "native")]
+                            let visit_start = SystemTime::now();
+                            let visit_result = Self::stations_visit_station_ensure(
+                                train,
+                                &mut station,
+                                train_resources,
+                                dest.file_system(),
+                                station_queue,
+                            )
+                            .await;
+                            train.station_duration_record(
+                                station_durations,
+                                &station,
+                                visit_begin.elapsed(),
+                            );
+                            HookDriver::run(&station, None, train_resources).await;
+                            Train::station_tmp_dir_clean(
+                                dest,
+                                &station,
+                                VisitOp::Clean,
+                                train_resources,
+                            )
+                            .await;
+                            #[cfg(feature = "native")]
+                            Train::station_audit_record(
+                                dest,
+                                &station,
+                                VisitOp::Clean,
+                                visit_start,
+                                None,
+                                train_resources,
+                            )
+                            .await;
+
+                            visit_result
+                        }
+                        .boxed_local();
+                        let visit_result = train
+                            .station_visit_interruptible(train_stop, visit_fut)
+                            .await;
+                        match visit_result {
+                            Ok(()) => {
+                                train
+                                    .station_failure_handle(train_stop, station.progress.op_status);
+                            }
+                            Err(op_status) => {
+                                station.progress.op_status = op_status;
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+                train.progress_update(station.spec.id(), &station.progress);
+                train_resources.scope_drop(ResourceScope::Op(station.rt_id));
+                train_resources.scope_drop(ResourceScope::Station(station.rt_id));
 
                 station.rt_id
+            });
+        station_workers
+            .run(stations, |station_rt_id| async {
+                let station_rt_id = station_rt_id.await;
+                op_status_propagator
+                    .station_complete(dest, station_rt_id)
+                    .await;
+                stall_watchdog.visit_end(station_rt_id);
+                station_queue.visit_end();
+                Ok(())
             })
-            .for_each_concurrent(
-                train.concurrency_max.map(NonZeroUsize::get),
-                |station_rt_id| async {
-                    CleanOpStatusUpdater::update_successors(dest, station_rt_id.await);
-                },
-            )
-            .await;
+            .await
     }
 
     async fn stations_visit_station_ensure(
+        train: &Train<E>,
         station: &mut StationMutRef<'_, E>,
         train_resources: &TrainResources<E>,
+        file_system: &dyn FileSystem,
+        station_queue: &StationQueue,
     ) {
-        eprintln!("{}", station.spec.id());
-        match dbg!(CleanDriver::ensure(station, train_resources).await) {
+        let ensure_outcome = CleanDriver::ensure(
+            station,
+            train_resources,
+            file_system,
+            station_queue.work_lane(),
+            train.chaos_policy.as_ref(),
+        )
+        .await;
+        train_resources.scope_drop(ResourceScope::Op(station.rt_id));
+
+        match ensure_outcome {
             Ok(CleanEnsureOutcomeOk::NothingToDo) => {
                 station.progress.op_status = OpStatus::WorkUnnecessary;
             }
@@ -76,10 +213,14 @@ where
                 station.progress.op_status = OpStatus::WorkSuccess;
 
                 if let Some(station_spec_error) = station_spec_error {
-                    let station_error = E::from(station_spec_error);
-
-                    Train::station_error_insert(train_resources, station.rt_id, station_error)
-                        .await;
+                    Train::station_spec_error_insert(
+                        train_resources,
+                        station.rt_id,
+                        StationErrorPhase::Clean,
+                        station_spec_error,
+                        train.work_not_idempotent_severity,
+                    )
+                    .await;
                 }
             }
             Ok(CleanEnsureOutcomeOk::Unchanged) => {
@@ -96,7 +237,13 @@ where
             Err(CleanEnsureOutcomeErr::CheckFail(station_error)) => {
                 station.progress.op_status = OpStatus::CheckFail;
 
-                Train::station_error_insert(train_resources, station.rt_id, station_error).await;
+                Train::station_error_insert(
+                    train_resources,
+                    station.rt_id,
+                    StationErrorPhase::Clean,
+                    station_error,
+                )
+                .await;
             }
             Err(CleanEnsureOutcomeErr::VisitBorrowFail(_borrow_fail)) => {
                 station.progress.op_status = OpStatus::WorkFail;
@@ -108,7 +255,27 @@ where
             }) => {
                 station.progress.op_status = OpStatus::WorkFail;
 
-                Train::station_error_insert(train_resources, station.rt_id, station_error).await;
+                Train::station_error_insert(
+                    train_resources,
+                    station.rt_id,
+                    StationErrorPhase::Clean,
+                    station_error,
+                )
+                .await;
+            }
+            Err(CleanEnsureOutcomeErr::Interrupted) => {
+                station.progress.op_status = OpStatus::WorkInterrupted;
+            }
+            Err(CleanEnsureOutcomeErr::Panicked(station_error)) => {
+                station.progress.op_status = OpStatus::Panicked;
+
+                Train::station_error_insert(
+                    train_resources,
+                    station.rt_id,
+                    StationErrorPhase::Clean,
+                    station_error,
+                )
+                .await;
             }
         }
     }
@@ -1,18 +1,37 @@
-use std::{fmt, marker::PhantomData, num::NonZeroUsize};
+#[cfg(feature = "native")]
+use std::time::SystemTime;
+use std::{
+    collections::HashSet,
+    fmt,
+    marker::PhantomData,
+    sync::{Mutex, PoisonError},
+    time::{Duration, Instant},
+};
 
 use choochoo_cfg_model::{
-    rt::{OpStatus, ResIds, StationMutRef, StationRtId, TrainResources},
+    indexmap::IndexMap,
+    rt::{
+        OpStatus, ResIds, ResIdsPrevious, ResourceScope, StationErrorPhase, StationMutRef,
+        StationRtId, TrainResources, VisitOp,
+    },
     StationSpecs,
 };
 use choochoo_resource::ProfileHistoryDir;
 use choochoo_rt_model::{
-    error::StationSpecError, CreateEnsureOutcomeErr, CreateEnsureOutcomeOk, Destination, Error,
-    TrainReport,
+    error::StationSpecError, CleanEnsureOutcomeOk, CreateEnsureOutcomeErr, CreateEnsureOutcomeOk,
+    Destination, Error, FileSystem, RollbackOutcome, TrainReport,
+};
+use futures::{
+    future::{self, Either, FutureExt},
+    stream::{self, StreamExt, TryStreamExt},
 };
-use futures::stream::{self, StreamExt, TryStreamExt};
 use tokio::sync::mpsc::{self, UnboundedReceiver};
 
-use crate::{CreateDriver, OpStatusUpdater, ResIdPersister, Train};
+use crate::{
+    train::TrainStop, CheckSpeculator, CleanDriver, CreateDriver, FileInputResolver, HookDriver,
+    OpStatusPropagator, OpStatusUpdater, ResIdPersister, RollbackPolicy, StationPriorityStream,
+    StationQueue, StationStallWatchdog, StationWorkers, Train,
+};
 
 /// Logic to manage resource creation.
 pub(crate) struct TrainCreate<E>(PhantomData<E>);
@@ -26,25 +45,62 @@ where
         train: &Train<E>,
         dest: &mut Destination<E>,
         train_resources: TrainResources<E>,
+        deadline: Option<Instant>,
     ) -> Result<TrainReport<E>, Error<E>> {
         // Set `ParentPending` stations to `OpQueued` if they have no dependencies.
         OpStatusUpdater::update(dest);
 
+        let station_queue = StationQueue::new(
+            dest,
+            &train.resource_limits,
+            train.concurrency_max,
+            train.work_concurrency_max,
+        );
+        let station_durations = Mutex::new(IndexMap::new());
+        let station_rollbacks = Mutex::new(IndexMap::new());
         let (res_ids_tx, res_ids_rx) = mpsc::unbounded_channel::<(StationRtId, ResIds)>();
-        let stations_visit_each =
-            Self::stations_visit_each(train, dest, &train_resources, res_ids_tx);
+        let stations_visit_each = Self::stations_visit_each(
+            train,
+            dest,
+            &train_resources,
+            &station_queue,
+            &station_durations,
+            &station_rollbacks,
+            deadline,
+            res_ids_tx,
+        );
 
         let profile_history_dir = train_resources.borrow::<ProfileHistoryDir>();
         let stations_visit_res_ids_wait = Self::stations_visit_res_ids_wait(
+            dest.file_system(),
             dest.station_specs(),
             &profile_history_dir,
             res_ids_rx,
         );
 
-        let ((), res_ids) = futures::try_join!(stations_visit_each, stations_visit_res_ids_wait)?;
+        let ((), (res_ids, station_res_ids)) =
+            futures::try_join!(stations_visit_each, stations_visit_res_ids_wait)?;
         drop(profile_history_dir);
 
-        let train_report = TrainReport::new(train_resources, res_ids);
+        let station_durations = station_durations
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner);
+        let mut station_rollbacks = station_rollbacks
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner);
+        Self::checkpoint_groups_rollback(
+            dest,
+            &train_resources,
+            &station_res_ids,
+            &mut station_rollbacks,
+        )
+        .await?;
+        Self::rolled_back_res_ids_clear(dest, &train_resources, &station_rollbacks).await?;
+        let train_report = TrainReport::new(train_resources, res_ids)
+            .with_queue_metrics(station_queue.into_queue_metrics())
+            .with_station_durations(station_durations)
+            .with_station_res_ids(station_res_ids)
+            .with_station_rollbacks(station_rollbacks);
         Ok(train_report)
     }
 
@@ -52,27 +108,123 @@ where
         train: &Train<E>,
         dest: &Destination<E>,
         train_resources: &TrainResources<E>,
+        station_queue: &StationQueue,
+        station_durations: &Mutex<IndexMap<StationRtId, Duration>>,
+        station_rollbacks: &Mutex<IndexMap<StationRtId, RollbackOutcome<E>>>,
+        deadline: Option<Instant>,
         res_ids_tx: mpsc::UnboundedSender<(StationRtId, ResIds)>,
     ) -> Result<(), Error<E>> {
         let res_ids_tx_ref = &res_ids_tx;
-        dest.stations_mut_stream()
-            .map(Result::<_, Error<E>>::Ok)
+        let train_stop = &TrainStop::new(deadline, train.timer.clone());
+        let op_status_propagator = &OpStatusPropagator::new(dest, VisitOp::Create);
+        let stall_watchdog = &StationStallWatchdog::new(dest);
+        let check_speculator = &CheckSpeculator::new();
+        let checks_fut = check_speculator
+            .checks_run(
+                dest,
+                train_resources,
+                station_queue,
+                train_stop,
+                train.concurrency_max,
+                train.chaos_policy.as_ref(),
+            )
+            .boxed_local();
+        let stations = StationPriorityStream::new(dest.stations_mut_stream().map(Ok).boxed_local());
+        let station_workers = &StationWorkers::new(
+            station_queue,
+            train.concurrency_max,
+            dest.station_specs().node_count(),
+        );
+        let stations = stall_watchdog
+            .watch(stations)
             .map_ok(|mut station| async move {
-                station.progress.progress_style_update();
-                let res_ids = if station.progress.op_status == OpStatus::OpQueued
-                    || station.progress.op_status == OpStatus::SetupSuccess
-                {
-                    // Because this is in an async block, concurrent tasks may access this
-                    // station's `op_status` while the `visit()` is
-                    // `await`ed.
-                    station.progress.op_status = OpStatus::WorkInProgress;
-                    station.progress.progress_style_update();
-
-                    Self::stations_visit_station_ensure(&mut station, train_resources).await
-                } else {
-                    None
+                train.progress_update(station.spec.id(), &station.progress);
+                let res_ids = match station.progress.op_status {
+                    OpStatus::OpQueued | OpStatus::SetupSuccess | OpStatus::ParentPending
+                        if train_stop.is_deadline_exceeded() =>
+                    {
+                        station.progress.op_status = OpStatus::DeadlineExceeded;
+                        None
+                    }
+                    OpStatus::OpQueued | OpStatus::SetupSuccess | OpStatus::ParentPending
+                        if train_stop.is_stopped() =>
+                    {
+                        station.progress.op_status = OpStatus::OpSkipped;
+                        None
+                    }
+                    OpStatus::OpQueued | OpStatus::SetupSuccess => {
+                        let visit_fut = async {
+                            // Stations remain queued while waiting for limited resources.
+                            let _permits = station_queue
+                                .acquire(&station.spec, station.rt_id, VisitOp::Create)
+                                .await;
+
+                            // Because this is in an async block, concurrent tasks may access this
+                            // station's `op_status` while the `visit()` is `await`ed.
+                            station.progress.op_status = OpStatus::WorkInProgress;
+                            train.progress_update(station.spec.id(), &station.progress);
+
+                            let visit_begin = Instant::now();
+                            #[cfg(feature = "native")]
+                            let visit_start = SystemTime::now();
+                            let visit_result = Self::stations_visit_station_ensure(
+                                train,
+                                &mut station,
+                                train_resources,
+                                dest.file_system(),
+                                station_queue,
+                                station_rollbacks,
+                            )
+                            .await;
+                            train.station_duration_record(
+                                station_durations,
+                                &station,
+                                visit_begin.elapsed(),
+                            );
+                            HookDriver::run(&station, visit_result.as_ref(), train_resources).await;
+                            Train::station_tmp_dir_clean(
+                                dest,
+                                &station,
+                                VisitOp::Create,
+                                train_resources,
+                            )
+                            .await;
+                            #[cfg(feature = "native")]
+                            Train::station_audit_record(
+                                dest,
+                                &station,
+                                VisitOp::Create,
+                                visit_start,
+                                visit_result.as_ref(),
+                                train_resources,
+                            )
+                            .await;
+
+                            visit_result
+                        }
+                        .boxed_local();
+                        let visit_result = train
+                            .station_visit_interruptible(train_stop, visit_fut)
+                            .await;
+                        match visit_result {
+                            Ok(res_ids) => {
+                                train
+                                    .station_failure_handle(train_stop, station.progress.op_status);
+                                Self::checkpoint_group_failure_handle(dest, train_stop, &station);
+                                res_ids
+                            }
+                            Err(op_status) => {
+                                station.progress.op_status = op_status;
+                                None
+                            }
+                        }
+                    }
+                    _ => None,
                 };
-                station.progress.progress_style_update();
+                check_speculator.station_complete(dest, train_resources, &station);
+                train.progress_update(station.spec.id(), &station.progress);
+                train_resources.scope_drop(ResourceScope::Op(station.rt_id));
+                train_resources.scope_drop(ResourceScope::Station(station.rt_id));
 
                 let res_ids_result = res_ids.map(|res_ids| {
                     res_ids_tx_ref
@@ -84,37 +236,66 @@ where
                 });
 
                 (station.rt_id, res_ids_result)
+            });
+        let stations_visit_fut = station_workers
+            .run(stations, |station_rt_id_and_res_ids_result| async {
+                let (station_rt_id, res_ids_result) = station_rt_id_and_res_ids_result.await;
+
+                op_status_propagator
+                    .station_complete(dest, station_rt_id)
+                    .await;
+                stall_watchdog.visit_end(station_rt_id);
+                station_queue.visit_end();
+                res_ids_result.unwrap_or(Result::Ok(()))
             })
-            .try_for_each_concurrent(
-                train.concurrency_max.map(NonZeroUsize::get),
-                |station_rt_id_and_res_ids_result| async {
-                    let (station_rt_id, res_ids_result) = station_rt_id_and_res_ids_result.await;
-
-                    OpStatusUpdater::update_children(dest, station_rt_id);
-                    res_ids_result.unwrap_or(Result::Ok(()))
-                },
-            )
-            .await?;
+            .boxed_local();
+
+        // Speculative checks run alongside the visits, and are abandoned once all
+        // stations have been visited.
+        match future::select(checks_fut, stations_visit_fut).await {
+            Either::Left(((), stations_visit_fut)) => stations_visit_fut.await?,
+            Either::Right((stations_visit_result, _checks_fut)) => stations_visit_result?,
+        }
         drop(res_ids_tx);
         Ok(())
     }
 
     async fn stations_visit_station_ensure(
+        train: &Train<E>,
         station: &mut StationMutRef<'_, E>,
         train_resources: &TrainResources<E>,
+        file_system: &dyn FileSystem,
+        station_queue: &StationQueue,
+        station_rollbacks: &Mutex<IndexMap<StationRtId, RollbackOutcome<E>>>,
     ) -> Option<ResIds> {
-        match CreateDriver::ensure(station, train_resources).await {
+        let ensure_outcome = CreateDriver::ensure(
+            station,
+            train_resources,
+            file_system,
+            station_queue.work_lane(),
+            train.chaos_policy.as_ref(),
+        )
+        .await;
+        train_resources.scope_drop(ResourceScope::Op(station.rt_id));
+        FileInputResolver::publish(station.spec.id(), &station.progress, train_resources).await;
+
+        match ensure_outcome {
             Ok(CreateEnsureOutcomeOk::Changed {
                 res_ids,
                 station_spec_error,
             }) => {
                 station.progress.op_status = OpStatus::WorkSuccess;
 
+                // The check function is what reports that work is still required.
                 if let Some(station_spec_error) = station_spec_error {
-                    let station_error = E::from(station_spec_error);
-
-                    Train::station_error_insert(train_resources, station.rt_id, station_error)
-                        .await;
+                    Train::station_spec_error_insert(
+                        train_resources,
+                        station.rt_id,
+                        StationErrorPhase::Check,
+                        station_spec_error,
+                        train.work_not_idempotent_severity,
+                    )
+                    .await;
                 }
 
                 Some(res_ids)
@@ -133,7 +314,13 @@ where
             Err(CreateEnsureOutcomeErr::CheckFail(station_error)) => {
                 station.progress.op_status = OpStatus::CheckFail;
 
-                Train::station_error_insert(train_resources, station.rt_id, station_error).await;
+                Train::station_error_insert(
+                    train_resources,
+                    station.rt_id,
+                    StationErrorPhase::Check,
+                    station_error,
+                )
+                .await;
 
                 None
             }
@@ -150,35 +337,277 @@ where
             }) => {
                 station.progress.op_status = OpStatus::WorkFail;
 
-                Train::station_error_insert(train_resources, station.rt_id, station_error).await;
+                Train::station_error_insert(
+                    train_resources,
+                    station.rt_id,
+                    StationErrorPhase::Work,
+                    station_error,
+                )
+                .await;
+                Self::station_rollback(
+                    train,
+                    station,
+                    train_resources,
+                    file_system,
+                    station_rollbacks,
+                    &res_ids,
+                )
+                .await;
+                Some(res_ids)
+            }
+            Err(CreateEnsureOutcomeErr::VerifyBorrowFail {
+                res_ids,
+                borrow_fail: _,
+            }) => {
+                station.progress.op_status = OpStatus::VerifyFail;
+
+                // TODO: insert borrow fail error somewhere
+
                 Some(res_ids)
             }
+            Err(CreateEnsureOutcomeErr::VerifyFail {
+                res_ids,
+                error: station_error,
+            }) => {
+                station.progress.op_status = OpStatus::VerifyFail;
+
+                Train::station_error_insert(
+                    train_resources,
+                    station.rt_id,
+                    StationErrorPhase::Verify,
+                    station_error,
+                )
+                .await;
+                Self::station_rollback(
+                    train,
+                    station,
+                    train_resources,
+                    file_system,
+                    station_rollbacks,
+                    &res_ids,
+                )
+                .await;
+                Some(res_ids)
+            }
+            Err(CreateEnsureOutcomeErr::Interrupted) => {
+                station.progress.op_status = OpStatus::WorkInterrupted;
+                None
+            }
+            Err(CreateEnsureOutcomeErr::Panicked {
+                phase,
+                error: station_error,
+            }) => {
+                station.progress.op_status = OpStatus::Panicked;
+
+                Train::station_error_insert(train_resources, station.rt_id, phase, station_error)
+                    .await;
+
+                None
+            }
+        }
+    }
+
+    /// Runs the clean functions of a station whose create visit failed, if
+    /// the train's rollback policy requires it.
+    ///
+    /// The station's persisted resource IDs are removed once the visits end,
+    /// if the rollback succeeds.
+    async fn station_rollback(
+        train: &Train<E>,
+        station: &mut StationMutRef<'_, E>,
+        train_resources: &TrainResources<E>,
+        file_system: &dyn FileSystem,
+        station_rollbacks: &Mutex<IndexMap<StationRtId, RollbackOutcome<E>>>,
+        res_ids: &ResIds,
+    ) {
+        if train.rollback_policy != RollbackPolicy::OnFailure || res_ids.is_empty() {
+            return;
+        }
+
+        let rollback_outcome =
+            Self::station_clean(station, train_resources, file_system, res_ids).await;
+        station_rollbacks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(station.rt_id, rollback_outcome);
+    }
+
+    /// Stops the train if the station failed and is in a checkpoint group, so
+    /// that the group is rolled back before further stations are visited.
+    fn checkpoint_group_failure_handle(
+        dest: &Destination<E>,
+        train_stop: &TrainStop,
+        station: &StationMutRef<'_, E>,
+    ) {
+        let station_failed = matches!(
+            station.progress.op_status,
+            OpStatus::CheckFail | OpStatus::WorkFail | OpStatus::VerifyFail | OpStatus::Panicked
+        );
+        let in_checkpoint_group = dest
+            .checkpoint_groups()
+            .iter()
+            .any(|checkpoint_group| checkpoint_group.contains(station.rt_id));
+        if station_failed && in_checkpoint_group {
+            train_stop.stop();
+        }
+    }
+
+    /// Runs the clean functions of every station in each checkpoint group that
+    /// has a failed station, in reverse order.
+    ///
+    /// Stations that were already rolled back are not cleaned again. As the
+    /// group's resources are removed, the next visit creates the group from
+    /// its beginning.
+    ///
+    /// Returns [`Error::StationBorrowConflict`] if a station is still accessed,
+    /// rather than waiting for it to be released.
+    async fn checkpoint_groups_rollback(
+        dest: &Destination<E>,
+        train_resources: &TrainResources<E>,
+        station_res_ids: &IndexMap<StationRtId, ResIds>,
+        station_rollbacks: &mut IndexMap<StationRtId, RollbackOutcome<E>>,
+    ) -> Result<(), Error<E>> {
+        let station_progresses = dest.station_progresses();
+        let station_rt_ids_rollback = dest
+            .checkpoint_groups()
+            .iter()
+            .filter(|checkpoint_group| {
+                checkpoint_group
+                    .station_rt_ids()
+                    .iter()
+                    .any(|station_rt_id| {
+                        station_progresses
+                            .try_borrow(station_rt_id)
+                            .map(|station_progress| {
+                                matches!(
+                                    station_progress.op_status,
+                                    OpStatus::CheckFail
+                                        | OpStatus::WorkFail
+                                        | OpStatus::VerifyFail
+                                        | OpStatus::Panicked
+                                )
+                            })
+                            .unwrap_or(false)
+                    })
+            })
+            .flat_map(|checkpoint_group| checkpoint_group.station_rt_ids().iter().copied())
+            .filter(|station_rt_id| !station_rollbacks.contains_key(station_rt_id))
+            .collect::<HashSet<StationRtId>>();
+        if station_rt_ids_rollback.is_empty() {
+            return Ok(());
+        }
+
+        let res_ids_empty = ResIds::new();
+        let mut stations = Box::pin(dest.try_stations_mut_stream_rev());
+        while let Some(mut station) = stations.try_next().await? {
+            if !station_rt_ids_rollback.contains(&station.rt_id) {
+                continue;
+            }
+
+            let res_ids = station_res_ids
+                .get(&station.rt_id)
+                .unwrap_or(&res_ids_empty);
+            let rollback_outcome =
+                Self::station_clean(&mut station, train_resources, dest.file_system(), res_ids)
+                    .await;
+            station_rollbacks.insert(station.rt_id, rollback_outcome);
+        }
+
+        Ok(())
+    }
+
+    /// Removes the persisted resource IDs of each station whose resources were
+    /// rolled back, so that the next execution does not try to clean them.
+    ///
+    /// This runs after the resource IDs produced by the visits are persisted,
+    /// so they are not written again afterwards.
+    async fn rolled_back_res_ids_clear(
+        dest: &Destination<E>,
+        train_resources: &TrainResources<E>,
+        station_rollbacks: &IndexMap<StationRtId, RollbackOutcome<E>>,
+    ) -> Result<(), Error<E>> {
+        let profile_history_dir = train_resources.borrow::<ProfileHistoryDir>();
+        let station_ids_rolled_back = station_rollbacks
+            .iter()
+            .filter(|(_station_rt_id, rollback_outcome)| {
+                matches!(
+                    rollback_outcome,
+                    RollbackOutcome::Success | RollbackOutcome::Unnecessary
+                )
+            })
+            .map(|(station_rt_id, _rollback_outcome)| dest.station_specs()[*station_rt_id].id());
+        for station_id in station_ids_rolled_back {
+            ResIdPersister::<E>::clear(dest.file_system(), &profile_history_dir, station_id)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Runs the clean functions of a station to remove the resources created by
+    /// its create visit.
+    ///
+    /// The station's resource IDs are inserted into [`ResIdsPrevious`] so that
+    /// the clean functions can find the resources to clean.
+    async fn station_clean(
+        station: &mut StationMutRef<'_, E>,
+        train_resources: &TrainResources<E>,
+        file_system: &dyn FileSystem,
+        res_ids: &ResIds,
+    ) -> RollbackOutcome<E> {
+        if let Ok(mut res_ids_previous) = train_resources.try_borrow_mut::<ResIdsPrevious>() {
+            let station_res_ids_previous = res_ids_previous.entry(station.rt_id).or_default();
+            res_ids
+                .iter()
+                .for_each(|(res_id_logical, res_id_physical)| {
+                    if let Ok(res_id_physical) = serde_json::to_value(res_id_physical) {
+                        station_res_ids_previous.insert(res_id_logical.clone(), res_id_physical);
+                    }
+                });
+        }
+
+        match CleanDriver::ensure(station, train_resources, file_system, None, None).await {
+            Ok(CleanEnsureOutcomeOk::NothingToDo) => RollbackOutcome::Unsupported,
+            Ok(CleanEnsureOutcomeOk::Unchanged) => RollbackOutcome::Unnecessary,
+            Ok(CleanEnsureOutcomeOk::Changed { .. }) => RollbackOutcome::Success,
+            Err(error) => RollbackOutcome::Fail(error),
         }
     }
 
     async fn stations_visit_res_ids_wait(
+        file_system: &dyn FileSystem,
         station_specs: &StationSpecs<E>,
         profile_history_dir: &ProfileHistoryDir,
         mut res_ids_rx: UnboundedReceiver<(StationRtId, ResIds)>,
-    ) -> Result<ResIds, Error<E>> {
-        let res_ids = stream::poll_fn(|ctx| res_ids_rx.poll_recv(ctx))
-            .map(Result::<_, Error<E>>::Ok)
-            .and_then(|(station_rt_id, res_ids_current)| async move {
-                let station_id = station_specs[station_rt_id].id();
-                ResIdPersister::<E>::persist(profile_history_dir, station_id, &res_ids_current)
+    ) -> Result<(ResIds, IndexMap<StationRtId, ResIds>), Error<E>> {
+        let res_ids_and_station_res_ids =
+            stream::poll_fn(|ctx| res_ids_rx.poll_recv(ctx))
+                .map(Result::<_, Error<E>>::Ok)
+                .and_then(|(station_rt_id, res_ids_current)| async move {
+                    let station_id = station_specs[station_rt_id].id();
+                    ResIdPersister::<E>::persist(
+                        file_system,
+                        profile_history_dir,
+                        station_id,
+                        &res_ids_current,
+                    )
                     .await?;
-                Ok(res_ids_current)
-            })
-            .try_fold(
-                ResIds::new(),
-                |mut res_ids_all, mut res_ids_current| async move {
-                    res_ids_all.extend(res_ids_current.drain(..));
+                    Ok((station_rt_id, res_ids_current))
+                })
+                .try_fold(
+                    (ResIds::new(), IndexMap::new()),
+                    |(mut res_ids_all, mut station_res_ids),
+                     (station_rt_id, mut res_ids_current)| async move {
+                        if !res_ids_current.is_empty() {
+                            station_res_ids.insert(station_rt_id, res_ids_current.clone());
+                        }
+                        res_ids_all.append(&mut res_ids_current);
 
-                    Ok(res_ids_all)
-                },
-            )
-            .await?;
+                        Ok((res_ids_all, station_res_ids))
+                    },
+                )
+                .await?;
 
-        Ok(res_ids)
+        Ok(res_ids_and_station_res_ids)
     }
 }
@@ -0,0 +1,79 @@
+use std::{sync::Arc, time::Instant};
+
+use futures::future;
+use tokio::sync::watch;
+
+use crate::Timer;
+
+/// Signals shared between stations to stop the train early.
+#[derive(Debug)]
+pub(crate) struct TrainStop {
+    /// Sends the stop signal.
+    tx: watch::Sender<bool>,
+    /// Held so that sending never fails, and cloned to wait for the signal.
+    rx: watch::Receiver<bool>,
+    /// Time by which stations must complete.
+    deadline: Option<Instant>,
+    /// Waits for the deadline to be reached.
+    timer: Option<Arc<dyn Timer>>,
+}
+
+impl TrainStop {
+    /// Returns a new `TrainStop` that has not been signalled.
+    ///
+    /// # Parameters
+    ///
+    /// * `deadline`: Time by which stations must complete.
+    /// * `timer`: Waits for the deadline to be reached.
+    pub(crate) fn new(deadline: Option<Instant>, timer: Option<Arc<dyn Timer>>) -> Self {
+        let (tx, rx) = watch::channel(false);
+        Self {
+            tx,
+            rx,
+            deadline,
+            timer,
+        }
+    }
+
+    /// Signals the train to stop.
+    pub(crate) fn stop(&self) {
+        // Sending only fails when there are no receivers, but `self.rx` is held.
+        let _ = self.tx.send(true);
+    }
+
+    /// Returns whether the train has been signalled to stop.
+    pub(crate) fn is_stopped(&self) -> bool {
+        *self.rx.borrow()
+    }
+
+    /// Returns whether the deadline has been reached.
+    pub(crate) fn is_deadline_exceeded(&self) -> bool {
+        self.deadline
+            .map(|deadline| Instant::now() >= deadline)
+            .unwrap_or(false)
+    }
+
+    /// Waits until the train is signalled to stop.
+    pub(crate) async fn stopped(&self) {
+        let mut rx = self.rx.clone();
+        while !*rx.borrow() {
+            if rx.changed().await.is_err() {
+                future::pending::<()>().await;
+            }
+        }
+    }
+
+    /// Waits until the deadline is reached.
+    ///
+    /// If there is no deadline or timer, this never completes.
+    pub(crate) async fn deadline_reached(&self) {
+        match (self.deadline, self.timer.as_ref()) {
+            (Some(deadline), Some(timer)) => {
+                timer
+                    .sleep(deadline.saturating_duration_since(Instant::now()))
+                    .await
+            }
+            (Some(_), None) | (None, _) => future::pending::<()>().await,
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use std::{
+    fmt::Write,
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
+
+use choochoo_cfg_model::{
+    indexmap::IndexMap,
+    rt::{OpStatus, ProgressLimit, StationProgress},
+    StationId,
+};
+
+/// Counters and gauges of a train's execution, for dashboards of scheduled
+/// trains.
+///
+/// The metrics are updated whenever a station's status changes while the
+/// train visits stations, and are rendered in the [Prometheus text format]
+/// by [`TrainMetrics::render`]. They may be served from a pull endpoint, or
+/// sent to a Prometheus Pushgateway using [`TrainMetrics::push`] when the
+/// `http` feature is enabled.
+///
+/// Clones of `TrainMetrics` share the same metrics, so a clone may be read
+/// while the train is running.
+///
+/// [Prometheus text format]: https://prometheus.io/docs/instrumenting/exposition_formats/
+#[derive(Clone, Debug, Default)]
+pub struct TrainMetrics {
+    /// Latest status of each station.
+    station_statuses: Arc<Mutex<IndexMap<StationId, OpStatus>>>,
+    /// Bytes processed by each station whose progress is measured in bytes.
+    station_bytes: Arc<Mutex<IndexMap<StationId, u64>>>,
+    /// Duration of each station's visit.
+    station_durations: Arc<Mutex<IndexMap<StationId, Duration>>>,
+}
+
+impl TrainMetrics {
+    /// Content type of the rendered metrics.
+    pub const CONTENT_TYPE: &'static str = "text/plain; version=0.0.4";
+
+    /// Returns new, empty `TrainMetrics`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a station's status, and the bytes it has processed if its
+    /// progress is measured in bytes.
+    pub(crate) fn station_update(
+        &self,
+        station_id: &StationId,
+        station_progress: &StationProgress,
+    ) {
+        self.station_statuses
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(station_id.clone(), station_progress.op_status);
+
+        if let ProgressLimit::Bytes(_) = station_progress.progress_limit() {
+            self.station_bytes
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert(
+                    station_id.clone(),
+                    station_progress.progress_bar().position(),
+                );
+        }
+    }
+
+    /// Records how long a station's visit took.
+    pub(crate) fn station_duration_record(&self, station_id: &StationId, duration: Duration) {
+        self.station_durations
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(station_id.clone(), duration);
+    }
+
+    /// Returns the number of stations in each status.
+    pub fn stations_by_status(&self) -> IndexMap<OpStatus, usize> {
+        self.station_statuses
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .values()
+            .fold(IndexMap::new(), |mut stations_by_status, op_status| {
+                *stations_by_status.entry(*op_status).or_insert(0) += 1;
+                stations_by_status
+            })
+    }
+
+    /// Returns the total bytes processed by stations whose progress is
+    /// measured in bytes.
+    pub fn bytes_transferred(&self) -> u64 {
+        self.station_bytes
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .values()
+            .sum()
+    }
+
+    /// Returns the duration of each station's visit.
+    pub fn station_durations(&self) -> IndexMap<StationId, Duration> {
+        self.station_durations
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Returns the metrics in the Prometheus text format.
+    pub fn render(&self) -> String {
+        let mut rendered = String::new();
+
+        // Writing to a `String` does not fail.
+        let _ = writeln!(
+            rendered,
+            "# HELP choochoo_stations Number of stations in each status."
+        );
+        let _ = writeln!(rendered, "# TYPE choochoo_stations gauge");
+        self.stations_by_status()
+            .iter()
+            .for_each(|(op_status, count)| {
+                let _ = writeln!(
+                    rendered,
+                    "choochoo_stations{{status=\"{:?}\"}} {}",
+                    op_status, count
+                );
+            });
+
+        let _ = writeln!(
+            rendered,
+            "# HELP choochoo_bytes_transferred_total Bytes processed by stations."
+        );
+        let _ = writeln!(rendered, "# TYPE choochoo_bytes_transferred_total counter");
+        let _ = writeln!(
+            rendered,
+            "choochoo_bytes_transferred_total {}",
+            self.bytes_transferred()
+        );
+
+        let _ = writeln!(
+            rendered,
+            "# HELP choochoo_station_duration_seconds Duration of each station's visit."
+        );
+        let _ = writeln!(rendered, "# TYPE choochoo_station_duration_seconds gauge");
+        self.station_durations()
+            .iter()
+            .for_each(|(station_id, duration)| {
+                let _ = writeln!(
+                    rendered,
+                    "choochoo_station_duration_seconds{{station_id=\"{}\"}} {}",
+                    station_id,
+                    duration.as_secs_f64()
+                );
+            });
+
+        rendered
+    }
+
+    /// Sends the metrics to a Prometheus Pushgateway.
+    ///
+    /// This replaces the metrics previously pushed to the same URL, so it may
+    /// be called periodically while the train is running.
+    ///
+    /// The URL includes the job name, such as:
+    ///
+    /// ```text
+    /// http://localhost:9091/metrics/job/deploy
+    /// ```
+    ///
+    /// # Parameters
+    ///
+    /// * `client`: HTTP client to send the metrics with.
+    /// * `url`: Pushgateway URL to send the metrics to.
+    #[cfg(feature = "http")]
+    pub async fn push(&self, client: &reqwest::Client, url: &str) -> Result<(), reqwest::Error> {
+        client
+            .put(url)
+            .header(reqwest::header::CONTENT_TYPE, Self::CONTENT_TYPE)
+            .body(self.render())
+            .send()
+            .await?
+            .error_for_status()
+            .map(|_response| ())
+    }
+}
@@ -0,0 +1,37 @@
+use std::{collections::BTreeMap, time::SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use choochoo_cfg_model::{
+    rt::{OpStatus, ResIdLogical, VisitOp},
+    StationId,
+};
+
+/// Record of a station's work or clean function being invoked.
+///
+/// Audit entries are appended to the profile's audit log, and are never
+/// modified afterwards.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct AuditEntry {
+    /// Unique identifier of the station.
+    pub station_id: StationId,
+    /// Operation whose function was invoked.
+    pub visit_op: VisitOp,
+    /// Time the station's visit began.
+    pub start: SystemTime,
+    /// Time the station's visit ended.
+    pub end: SystemTime,
+    /// Status of the station after its visit.
+    pub op_status: OpStatus,
+    /// Serialized resource IDs produced by the station.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub res_ids: BTreeMap<ResIdLogical, serde_json::Value>,
+    /// Execution profile the station was visited in.
+    pub profile: String,
+    /// Name of the user that ran the train, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// Name of the host that ran the train, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+}
@@ -0,0 +1,70 @@
+use std::time::SystemTime;
+
+use choochoo_cfg_model::{rt::VisitOp, StationId};
+
+use crate::AuditEntry;
+
+/// Filters [`AuditEntry`]s read from an audit log.
+///
+/// An empty query matches every entry.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AuditQuery {
+    /// Only match entries for this station.
+    station_id: Option<StationId>,
+    /// Only match entries for this operation.
+    visit_op: Option<VisitOp>,
+    /// Only match entries whose visit began at or after this time.
+    since: Option<SystemTime>,
+    /// Only match entries whose visit began before this time.
+    until: Option<SystemTime>,
+}
+
+impl AuditQuery {
+    /// Returns a new `AuditQuery` that matches every entry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only match entries for the given station.
+    #[must_use]
+    pub fn with_station_id(mut self, station_id: StationId) -> Self {
+        self.station_id = Some(station_id);
+        self
+    }
+
+    /// Only match entries for the given operation.
+    #[must_use]
+    pub fn with_visit_op(mut self, visit_op: VisitOp) -> Self {
+        self.visit_op = Some(visit_op);
+        self
+    }
+
+    /// Only match entries whose visit began at or after the given time.
+    #[must_use]
+    pub fn with_since(mut self, since: SystemTime) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    /// Only match entries whose visit began before the given time.
+    #[must_use]
+    pub fn with_until(mut self, until: SystemTime) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    /// Returns whether the entry matches this query.
+    pub fn matches(&self, audit_entry: &AuditEntry) -> bool {
+        let station_id_matches = self
+            .station_id
+            .as_ref()
+            .map_or(true, |station_id| station_id == &audit_entry.station_id);
+        let visit_op_matches = self
+            .visit_op
+            .map_or(true, |visit_op| visit_op == audit_entry.visit_op);
+        let since_matches = self.since.map_or(true, |since| since <= audit_entry.start);
+        let until_matches = self.until.map_or(true, |until| audit_entry.start < until);
+
+        station_id_matches && visit_op_matches && since_matches && until_matches
+    }
+}
@@ -0,0 +1,47 @@
+use choochoo_cfg_model::rt::StationRtId;
+
+/// Stations whose resources are created as a unit.
+///
+/// If any station in the group fails to be created, the clean functions of
+/// every station in the group are run in reverse order, so that the next
+/// visit creates the group from its beginning.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CheckpointGroup {
+    /// Name of the group, used when reporting its rollback.
+    name: String,
+    /// Runtime IDs of the stations in the group.
+    station_rt_ids: Vec<StationRtId>,
+}
+
+impl CheckpointGroup {
+    /// Returns a new `CheckpointGroup`.
+    ///
+    /// # Parameters
+    ///
+    /// * `name`: Name of the group, used when reporting its rollback.
+    /// * `station_rt_ids`: Runtime IDs of the stations in the group.
+    pub fn new<I>(name: impl Into<String>, station_rt_ids: I) -> Self
+    where
+        I: IntoIterator<Item = StationRtId>,
+    {
+        Self {
+            name: name.into(),
+            station_rt_ids: station_rt_ids.into_iter().collect(),
+        }
+    }
+
+    /// Returns the name of the group.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the runtime IDs of the stations in the group.
+    pub fn station_rt_ids(&self) -> &[StationRtId] {
+        &self.station_rt_ids
+    }
+
+    /// Returns whether the station is in this group.
+    pub fn contains(&self, station_rt_id: StationRtId) -> bool {
+        self.station_rt_ids.contains(&station_rt_id)
+    }
+}
@@ -53,4 +53,12 @@ pub enum CleanEnsureOutcomeErr<E> {
         /// The visit error.
         error: E,
     },
+    /// The visit was cancelled before it completed.
+    ///
+    /// This is returned when the train's chaos policy cancels the visit.
+    Interrupted,
+    /// A station function panicked.
+    ///
+    /// The error is built from [`StationSpecError::Panicked`].
+    Panicked(E),
 }
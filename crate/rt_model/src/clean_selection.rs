@@ -0,0 +1,169 @@
+use std::collections::{HashSet, VecDeque};
+
+use choochoo_cfg_model::{
+    daggy::Walker,
+    rt::{ResIdsPrevious, StationRtId},
+    StationSpec,
+};
+
+use crate::Destination;
+
+/// Stations to clean, instead of every station in the destination.
+///
+/// Stations are selected when any of the following match:
+///
+/// * The station's ID has one of the station ID prefixes.
+/// * The station has one of the tags.
+/// * The station persisted a resource ID with one of the resource ID prefixes
+///   in a previous execution.
+///
+/// Stations that depend on a selected station are also selected, as they must
+/// be cleaned before the stations they depend on.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CleanSelection {
+    /// Prefixes of station IDs to select.
+    station_ids: Vec<String>,
+    /// Tags of stations to select.
+    tags: Vec<String>,
+    /// Prefixes of logical resource IDs whose stations to select.
+    res_id_prefixes: Vec<String>,
+}
+
+impl CleanSelection {
+    /// Returns an empty `CleanSelection`, which selects no stations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Selects stations whose ID is equal to, or is nested within, the given
+    /// prefix.
+    ///
+    /// See [`StationId::has_prefix`] for how prefixes are matched.
+    ///
+    /// [`StationId::has_prefix`]: choochoo_cfg_model::StationId::has_prefix
+    #[must_use]
+    pub fn with_station_id<S>(mut self, station_id: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.station_ids.push(station_id.into());
+        self
+    }
+
+    /// Selects stations with the given tag.
+    #[must_use]
+    pub fn with_tag<S>(mut self, tag: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Selects stations that persisted a resource ID equal to, or nested
+    /// within, the given prefix.
+    ///
+    /// See [`ResIdLogical::has_prefix`] for how prefixes are matched.
+    ///
+    /// [`ResIdLogical::has_prefix`]: choochoo_cfg_model::rt::ResIdLogical::has_prefix
+    #[must_use]
+    pub fn with_res_id_prefix<S>(mut self, res_id_prefix: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.res_id_prefixes.push(res_id_prefix.into());
+        self
+    }
+
+    /// Returns the prefixes of station IDs to select.
+    pub fn station_ids(&self) -> &[String] {
+        &self.station_ids
+    }
+
+    /// Returns the tags of stations to select.
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Returns the prefixes of logical resource IDs whose stations to select.
+    pub fn res_id_prefixes(&self) -> &[String] {
+        &self.res_id_prefixes
+    }
+
+    /// Returns the selected stations and the stations that depend on them, in
+    /// the order they are cleaned.
+    ///
+    /// # Parameters
+    ///
+    /// * `dest`: Destination whose stations to select.
+    /// * `res_ids_previous`: Resource IDs persisted by each station in previous
+    ///   executions.
+    pub fn stations_selected<E>(
+        &self,
+        dest: &Destination<E>,
+        res_ids_previous: &ResIdsPrevious,
+    ) -> Vec<StationRtId>
+    where
+        E: 'static,
+    {
+        let station_specs = dest.station_specs();
+
+        let mut stations_pending = station_specs
+            .iter_insertion_with_indices()
+            .filter(|(station_rt_id, station_spec)| {
+                self.station_matches(*station_rt_id, station_spec, res_ids_previous)
+            })
+            .map(|(station_rt_id, _station_spec)| station_rt_id)
+            .collect::<VecDeque<_>>();
+        let mut stations_selected = stations_pending.iter().copied().collect::<HashSet<_>>();
+
+        // Dependents must be cleaned before the stations they depend on.
+        while let Some(station_rt_id) = stations_pending.pop_front() {
+            station_specs
+                .children(station_rt_id)
+                .iter(station_specs)
+                .for_each(|(_edge, station_rt_id_dependent)| {
+                    if stations_selected.insert(station_rt_id_dependent) {
+                        stations_pending.push_back(station_rt_id_dependent);
+                    }
+                });
+        }
+
+        dest.clean_order()
+            .into_iter()
+            .filter(|station_rt_id| stations_selected.contains(station_rt_id))
+            .collect()
+    }
+
+    /// Returns whether a station is directly selected.
+    fn station_matches<E>(
+        &self,
+        station_rt_id: StationRtId,
+        station_spec: &StationSpec<E>,
+        res_ids_previous: &ResIdsPrevious,
+    ) -> bool
+    where
+        E: 'static,
+    {
+        let station_id_matches = self
+            .station_ids
+            .iter()
+            .any(|station_id| station_spec.id().has_prefix(station_id));
+        let tag_matches = self
+            .tags
+            .iter()
+            .any(|tag| station_spec.tags().contains(tag));
+
+        station_id_matches
+            || tag_matches
+            || res_ids_previous
+                .station_res_ids(station_rt_id)
+                .map_or(false, |station_res_ids| {
+                    station_res_ids.keys().any(|res_id_logical| {
+                        self.res_id_prefixes
+                            .iter()
+                            .any(|res_id_prefix| res_id_logical.has_prefix(res_id_prefix))
+                    })
+                })
+    }
+}
@@ -1,4 +1,7 @@
-use choochoo_cfg_model::{resman::BorrowFail, rt::ResIds};
+use choochoo_cfg_model::{
+    resman::BorrowFail,
+    rt::{ResIds, StationErrorPhase},
+};
 
 use crate::error::StationSpecError;
 
@@ -42,4 +45,31 @@ pub enum CreateEnsureOutcomeErr<E> {
         /// The visit error.
         error: E,
     },
+    /// Failed to borrow resources for the verify function.
+    VerifyBorrowFail {
+        /// Resource IDs generated during the visit.
+        res_ids: ResIds,
+        /// The borrow error.
+        borrow_fail: BorrowFail,
+    },
+    /// The operation's verify function failed.
+    VerifyFail {
+        /// Resource IDs generated during the visit.
+        res_ids: ResIds,
+        /// The verify error.
+        error: E,
+    },
+    /// The visit was cancelled before it completed.
+    ///
+    /// This is returned when the train's chaos policy cancels the visit.
+    Interrupted,
+    /// A station function panicked.
+    ///
+    /// The error is built from [`StationSpecError::Panicked`].
+    Panicked {
+        /// Phase of the function that panicked.
+        phase: StationErrorPhase,
+        /// The panic error.
+        error: E,
+    },
 }
@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+use choochoo_cfg_model::rt::StationRtId;
+
+/// Chain of dependent stations with the longest total duration.
+///
+/// Because each station in the chain can only be visited after the previous
+/// one, the critical path bounds how quickly a train can reach its
+/// destination, regardless of how many stations are visited concurrently.
+/// Shortening stations on this path, or removing dependencies between them,
+/// is what reduces the train's total time.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CriticalPath {
+    /// Stations on the path, in the order they are visited.
+    station_rt_ids: Vec<StationRtId>,
+    /// Sum of the durations of the stations on the path.
+    duration: Duration,
+}
+
+impl CriticalPath {
+    /// Returns a new `CriticalPath`.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_rt_ids`: Stations on the path, in the order they are visited.
+    /// * `duration`: Sum of the durations of the stations on the path.
+    pub fn new(station_rt_ids: Vec<StationRtId>, duration: Duration) -> Self {
+        Self {
+            station_rt_ids,
+            duration,
+        }
+    }
+
+    /// Returns the stations on the path, in the order they are visited.
+    pub fn station_rt_ids(&self) -> &[StationRtId] {
+        &self.station_rt_ids
+    }
+
+    /// Returns the sum of the durations of the stations on the path.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Returns whether the path has no stations.
+    pub fn is_empty(&self) -> bool {
+        self.station_rt_ids.is_empty()
+    }
+}
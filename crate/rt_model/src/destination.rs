@@ -1,13 +1,20 @@
-use std::collections::HashMap;
+use std::{any::TypeId, collections::HashMap, sync::Arc};
 
 use choochoo_cfg_model::{
-    rt::{Station, StationMut, StationMutRef, StationRtId},
-    StationId, StationSpecs,
+    fn_graph::FnRef,
+    rt::{
+        Station, StationDir, StationMut, StationMutRef, StationProgress, StationRtId, StationState,
+    },
+    StationId, StationSpec, StationSpecs,
 };
 use choochoo_resource::Profile;
 use futures::{stream::Stream, StreamExt};
+use tokio::sync::RwLockWriteGuard;
 
-use crate::{DestinationBuilder, DestinationDirs, StationProgresses};
+use crate::{
+    CheckpointGroup, DestinationBuilder, DestinationDirs, DestinationReachability, Error,
+    FileSystem, ResourceConflictPolicy, StationProgresses, StationStates,
+};
 
 /// Specification of a desired state.
 #[derive(Debug)]
@@ -16,6 +23,8 @@ pub struct Destination<E> {
     pub(crate) profile: Profile,
     /// Map from [`StationRtId`] to the station's execution directory.
     pub(crate) dirs: DestinationDirs,
+    /// File system that directories and resource IDs are written to.
+    pub(crate) file_system: Arc<dyn FileSystem>,
     /// The stations along the way to the destination.
     pub(crate) station_specs: StationSpecs<E>,
     /// Map from station ID to station runtime ID.
@@ -24,6 +33,13 @@ pub struct Destination<E> {
     pub(crate) station_id_to_rt_id: HashMap<StationId, StationRtId>,
     /// Progress information for each `Station`.
     pub(crate) station_progresses: StationProgresses,
+    /// Operation state for each `Station`.
+    pub(crate) station_states: StationStates,
+    /// Groups of stations that are created as a unit.
+    pub(crate) checkpoint_groups: Vec<CheckpointGroup>,
+    /// How stations that may be visited concurrently, but have conflicting
+    /// resource borrows, are handled.
+    pub(crate) resource_conflict_policy: ResourceConflictPolicy,
 }
 
 impl<E> Destination<E>
@@ -45,13 +61,19 @@ where
         &self.dirs
     }
 
+    /// Returns the file system that directories and resource IDs are written
+    /// to.
+    pub fn file_system(&self) -> &dyn FileSystem {
+        &*self.file_system
+    }
+
     /// Returns an iterator over the [`Station`]s in this destination.
     ///
-    /// This uses runtime borrowing ([`RtMap::try_borrow`]) to retrieve the
-    /// station progress, so if a station's progress is already accessed
-    /// mutably, then it will not be returned by the iterator.
+    /// This uses [`StationLock::try_borrow`] to retrieve the station progress,
+    /// so if a station's progress is already accessed mutably, then it will not
+    /// be returned by the iterator.
     ///
-    /// [`RtMap::try_borrow`]: rt_map::RtMap::try_borrow
+    /// [`StationLock::try_borrow`]: crate::StationLock::try_borrow
     pub fn stations(&self) -> impl Iterator<Item = Station<'_, E>> + '_ {
         self.station_specs
             .iter_insertion()
@@ -72,51 +94,68 @@ where
             })
     }
 
+    /// Returns an iterator over the [`Station`]s in this destination whose ID
+    /// is equal to, or is nested within, the given prefix.
+    ///
+    /// See [`StationId::has_prefix`] for how prefixes are matched.
+    pub fn stations_with_prefix<'f>(
+        &'f self,
+        prefix: &'f str,
+    ) -> impl Iterator<Item = Station<'f, E>> + 'f {
+        self.stations()
+            .filter(move |station| station.spec.id().has_prefix(prefix))
+    }
+
     /// Returns an iterator over the [`StationMut`]s in this destination.
     ///
-    /// This uses runtime borrowing ([`RtMap::try_borrow_mut`]) to retrieve the
-    /// station progress, so if a station's progress is already accessed, then
-    /// it will not be returned by the iterator.
+    /// This uses [`StationLock::try_borrow_mut`] to retrieve the station
+    /// progress, so if a station's progress is already accessed, then it will
+    /// not be returned by the iterator.
+    ///
+    /// Use [`Destination::try_stations_mut`] to be informed of stations whose
+    /// progress is already accessed, or [`Destination::stations_mut_stream`]
+    /// to wait for it to be released.
     ///
-    /// [`RtMap::try_borrow_mut`]: rt_map::RtMap::try_borrow_mut
+    /// [`StationLock::try_borrow_mut`]: crate::StationLock::try_borrow_mut
     pub fn stations_mut(&self) -> impl Iterator<Item = StationMut<'_, E>> + '_ {
+        self.try_stations_mut().filter_map(Result::ok)
+    }
+
+    /// Returns an iterator over the [`StationMut`]s in this destination, or an
+    /// error for each station whose progress is already accessed.
+    ///
+    /// Unlike [`Destination::stations_mut`], stations are never skipped --
+    /// contention is reported as [`Error::StationBorrowConflict`].
+    pub fn try_stations_mut(
+        &self,
+    ) -> impl Iterator<Item = Result<StationMut<'_, E>, Error<E>>> + '_ {
         self.station_specs
             .iter_insertion()
             .filter_map(move |station_spec| {
-                self.station_id_to_rt_id
-                    .get(station_spec.id())
-                    .and_then(|station_rt_id| {
-                        let station_dir = self.dirs.station_dirs.get(station_rt_id);
-                        let station_progress =
-                            self.station_progresses.try_borrow_mut(station_rt_id);
-
-                        if let (Some(station_dir), Ok(station_progress)) =
-                            (station_dir, station_progress)
-                        {
-                            Some((*station_rt_id, station_dir, station_progress))
-                        } else {
-                            None
-                        }
-                    })
-                    .map(
-                        |(station_rt_id, station_dir, station_progress)| StationMut {
-                            spec: station_spec,
-                            rt_id: station_rt_id,
-                            dir: station_dir,
-                            progress: station_progress,
+                self.station_borrow_mut(station_spec).map(|station_borrow| {
+                    station_borrow.map(
+                        |(station_rt_id, station_dir, station_progress, station_state)| {
+                            StationMut {
+                                spec: station_spec,
+                                rt_id: station_rt_id,
+                                dir: station_dir,
+                                progress: station_progress,
+                                state: station_state,
+                            }
                         },
                     )
+                })
             })
     }
 
     /// Returns an iterator over the [`Station`]s in this destination in
     /// dependency order.
     ///
-    /// This uses runtime borrowing ([`RtMap::try_borrow`]) to retrieve the
-    /// station progress, so if a station's progress is already accessed
-    /// mutably, then it will not be returned by the iterator.
+    /// This uses [`StationLock::try_borrow`] to retrieve the station progress,
+    /// so if a station's progress is already accessed mutably, then it will not
+    /// be returned by the iterator.
     ///
-    /// [`RtMap::try_borrow`]: rt_map::RtMap::try_borrow
+    /// [`StationLock::try_borrow`]: crate::StationLock::try_borrow
     pub fn stations_iter(&self) -> impl Iterator<Item = Station<'_, E>> + '_ {
         self.station_specs.iter().filter_map(move |station_spec| {
             self.station_id_to_rt_id
@@ -135,78 +174,191 @@ where
         })
     }
 
-    /// Returns an iterator over the [`StationMutRef`]s in this destination.
+    /// Returns an iterator over the [`Station`]s in this destination in
+    /// reverse dependency order.
+    ///
+    /// This is the order in which stations are cleaned -- a station is only
+    /// cleaned after all stations that depend on it have been cleaned.
     ///
-    /// This uses runtime borrowing ([`RtMap::try_borrow_mut`]) to retrieve the
-    /// station progress, so if a station's progress is already accessed, then
-    /// it will not be returned by the iterator.
+    /// This uses [`StationLock::try_borrow`] to retrieve the station progress,
+    /// so if a station's progress is already accessed mutably, then it will not
+    /// be returned by the iterator.
     ///
-    /// [`RtMap::try_borrow_mut`]: rt_map::RtMap::try_borrow_mut
+    /// [`StationLock::try_borrow`]: crate::StationLock::try_borrow
+    pub fn stations_iter_rev(&self) -> impl Iterator<Item = Station<'_, E>> + '_ {
+        let station_specs = self.station_specs.iter().collect::<Vec<_>>();
+        station_specs
+            .into_iter()
+            .rev()
+            .filter_map(move |station_spec| {
+                self.station_id_to_rt_id
+                    .get(station_spec.id())
+                    .and_then(|station_rt_id| {
+                        self.station_progresses
+                            .try_borrow(station_rt_id)
+                            .map(|station_progress| (*station_rt_id, station_progress))
+                            .ok()
+                    })
+                    .map(|(station_rt_id, station_progress)| Station {
+                        spec: station_spec,
+                        rt_id: station_rt_id,
+                        progress: station_progress,
+                    })
+            })
+    }
+
+    /// Returns the runtime IDs of stations in the order they are cleaned.
+    ///
+    /// This is the reverse dependency order, which is the reverse of the order
+    /// that stations are created. When stations are cleaned concurrently, a
+    /// station may be cleaned before others earlier in this list, but never
+    /// before the stations that depend on it.
+    ///
+    /// Unlike [`Destination::stations_iter_rev`], this does not borrow each
+    /// station's progress, so it may be used to display the clean plan while
+    /// stations are being accessed.
+    pub fn clean_order(&self) -> Vec<StationRtId> {
+        let station_specs = self.station_specs.iter().collect::<Vec<_>>();
+        station_specs
+            .into_iter()
+            .rev()
+            .filter_map(|station_spec| self.station_id_to_rt_id.get(station_spec.id()).copied())
+            .collect()
+    }
+
+    /// Returns a stream of the [`StationMutRef`]s in this destination.
+    ///
+    /// If a station's progress is already accessed, the stream waits for it to
+    /// be released, so stations are never skipped.
+    ///
+    /// Use [`Destination::try_stations_mut_stream`] to be informed of stations
+    /// whose progress is already accessed instead of waiting.
     pub fn stations_mut_stream(&self) -> impl Stream<Item = StationMutRef<'_, E>> + '_ {
         self.station_specs
             .stream()
             .filter_map(move |station_spec| async move {
-                self.station_id_to_rt_id
-                    .get(station_spec.id())
-                    .and_then(|station_rt_id| {
-                        let station_dir = self.dirs.station_dirs.get(station_rt_id);
-                        let station_progress =
-                            self.station_progresses.try_borrow_mut(station_rt_id);
-
-                        if let (Some(station_dir), Ok(station_progress)) =
-                            (station_dir, station_progress)
-                        {
-                            Some((*station_rt_id, station_dir, station_progress))
-                        } else {
-                            None
-                        }
+                self.station_lock_mut(&station_spec).await.map(
+                    |(station_rt_id, station_dir, station_progress, station_state)| StationMutRef {
+                        spec: station_spec,
+                        rt_id: station_rt_id,
+                        dir: station_dir,
+                        progress: station_progress,
+                        state: station_state,
+                    },
+                )
+            })
+    }
+
+    /// Returns a stream of the [`StationMutRef`]s in this destination, or an
+    /// error for each station whose progress is already accessed.
+    ///
+    /// Unlike [`Destination::stations_mut_stream`], this does not wait for
+    /// stations to be released -- contention is reported as
+    /// [`Error::StationBorrowConflict`].
+    pub fn try_stations_mut_stream(
+        &self,
+    ) -> impl Stream<Item = Result<StationMutRef<'_, E>, Error<E>>> + '_ {
+        self.station_specs
+            .stream()
+            .filter_map(move |station_spec| async move {
+                self.station_borrow_mut(&station_spec)
+                    .map(|station_borrow| {
+                        station_borrow.map(
+                            |(station_rt_id, station_dir, station_progress, station_state)| {
+                                StationMutRef {
+                                    spec: station_spec,
+                                    rt_id: station_rt_id,
+                                    dir: station_dir,
+                                    progress: station_progress,
+                                    state: station_state,
+                                }
+                            },
+                        )
                     })
-                    .map(
-                        |(station_rt_id, station_dir, station_progress)| StationMutRef {
-                            spec: station_spec,
-                            rt_id: station_rt_id,
-                            dir: station_dir,
-                            progress: station_progress,
-                        },
-                    )
             })
     }
 
-    /// Returns an iterator over the [`StationMutRef`]s in this destination in
+    /// Returns a stream of the [`StationMutRef`]s in this destination in
     /// reverse order.
     ///
-    /// This uses runtime borrowing ([`RtMap::try_borrow_mut`]) to retrieve the
-    /// station progress, so if a station's progress is already accessed, then
-    /// it will not be returned by the iterator.
+    /// If a station's progress is already accessed, the stream waits for it to
+    /// be released, so stations are never skipped.
     ///
-    /// [`RtMap::try_borrow_mut`]: rt_map::RtMap::try_borrow_mut
+    /// Use [`Destination::try_stations_mut_stream_rev`] to be informed of
+    /// stations whose progress is already accessed instead of waiting.
     pub fn stations_mut_stream_rev(&self) -> impl Stream<Item = StationMutRef<'_, E>> + '_ {
         self.station_specs
             .stream_rev()
             .filter_map(move |station_spec| async move {
-                self.station_id_to_rt_id
-                    .get(station_spec.id())
-                    .and_then(|station_rt_id| {
-                        let station_dir = self.dirs.station_dirs.get(station_rt_id);
-                        let station_progress =
-                            self.station_progresses.try_borrow_mut(station_rt_id);
-
-                        if let (Some(station_dir), Ok(station_progress)) =
-                            (station_dir, station_progress)
-                        {
-                            Some((*station_rt_id, station_dir, station_progress))
-                        } else {
-                            None
-                        }
+                self.station_lock_mut(&station_spec).await.map(
+                    |(station_rt_id, station_dir, station_progress, station_state)| StationMutRef {
+                        spec: station_spec,
+                        rt_id: station_rt_id,
+                        dir: station_dir,
+                        progress: station_progress,
+                        state: station_state,
+                    },
+                )
+            })
+    }
+
+    /// Returns a stream of the [`StationMutRef`]s in this destination in
+    /// reverse order, or an error for each station whose progress is already
+    /// accessed.
+    ///
+    /// Unlike [`Destination::stations_mut_stream_rev`], this does not wait for
+    /// stations to be released -- contention is reported as
+    /// [`Error::StationBorrowConflict`].
+    pub fn try_stations_mut_stream_rev(
+        &self,
+    ) -> impl Stream<Item = Result<StationMutRef<'_, E>, Error<E>>> + '_ {
+        self.station_specs
+            .stream_rev()
+            .filter_map(move |station_spec| async move {
+                self.station_borrow_mut(&station_spec)
+                    .map(|station_borrow| {
+                        station_borrow.map(
+                            |(station_rt_id, station_dir, station_progress, station_state)| {
+                                StationMutRef {
+                                    spec: station_spec,
+                                    rt_id: station_rt_id,
+                                    dir: station_dir,
+                                    progress: station_progress,
+                                    state: station_state,
+                                }
+                            },
+                        )
                     })
-                    .map(
-                        |(station_rt_id, station_dir, station_progress)| StationMutRef {
-                            spec: station_spec,
-                            rt_id: station_rt_id,
-                            dir: station_dir,
-                            progress: station_progress,
-                        },
-                    )
+            })
+    }
+
+    /// Returns the [`StationMutRef`] for a station spec streamed from another
+    /// graph, or an error if the station's progress is already accessed.
+    ///
+    /// The spec is matched to this destination's station by its ID. This
+    /// allows some of the stations to be streamed without waiting for the
+    /// stations they depend on, such as to check them speculatively.
+    ///
+    /// Returns `None` if the station is not in this destination.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_spec`: Spec of the station, streamed from another graph.
+    pub fn try_station_mut_ref<'s>(
+        &'s self,
+        station_spec: FnRef<'s, StationSpec<E>>,
+    ) -> Option<Result<StationMutRef<'s, E>, Error<E>>> {
+        self.station_borrow_mut(&station_spec)
+            .map(|station_borrow| {
+                station_borrow.map(
+                    |(station_rt_id, station_dir, station_progress, station_state)| StationMutRef {
+                        spec: station_spec,
+                        rt_id: station_rt_id,
+                        dir: station_dir,
+                        progress: station_progress,
+                        state: station_state,
+                    },
+                )
             })
     }
 
@@ -225,8 +377,105 @@ where
         &mut self.station_progresses
     }
 
+    /// Returns a reference to the station operation states.
+    pub fn station_states(&self) -> &StationStates {
+        &self.station_states
+    }
+
+    /// Returns a mutable reference to the station operation states.
+    pub fn station_states_mut(&mut self) -> &mut StationStates {
+        &mut self.station_states
+    }
+
+    /// Returns the groups of stations that are created as a unit.
+    pub fn checkpoint_groups(&self) -> &[CheckpointGroup] {
+        &self.checkpoint_groups
+    }
+
+    /// Returns how stations that may be visited concurrently, but have
+    /// conflicting resource borrows, are handled.
+    pub fn resource_conflict_policy(&self) -> ResourceConflictPolicy {
+        self.resource_conflict_policy
+    }
+
     /// Returns a reference to the station ID to runtime ID map.
     pub fn station_id_to_rt_id(&self) -> &HashMap<StationId, StationRtId> {
         &self.station_id_to_rt_id
     }
+
+    /// Returns the islands of connected stations, the stations that have no
+    /// path from any root, and declared resources that no station accesses.
+    ///
+    /// # Parameters
+    ///
+    /// * `resources_declared`: `TypeId` and type name of each resource that
+    ///   stations are expected to access.
+    pub fn reachability<I>(&self, resources_declared: I) -> DestinationReachability
+    where
+        I: IntoIterator<Item = (TypeId, &'static str)>,
+    {
+        DestinationReachability::new(self, resources_declared)
+    }
+
+    /// Locks a station's progress and state mutably, waiting until they are
+    /// released.
+    ///
+    /// Returns `None` if the station is not part of this destination.
+    #[allow(clippy::type_complexity)]
+    async fn station_lock_mut(
+        &self,
+        station_spec: &StationSpec<E>,
+    ) -> Option<(
+        StationRtId,
+        &StationDir,
+        RwLockWriteGuard<'_, StationProgress>,
+        RwLockWriteGuard<'_, StationState>,
+    )> {
+        let station_rt_id = self.station_id_to_rt_id.get(station_spec.id())?;
+        let station_dir = self.dirs.station_dirs.get(station_rt_id)?;
+        let station_progress = self.station_progresses.write(station_rt_id).await?;
+        let station_state = self.station_states.write(station_rt_id).await?;
+
+        Some((*station_rt_id, station_dir, station_progress, station_state))
+    }
+
+    /// Borrows a station's progress and state mutably.
+    ///
+    /// Returns `None` if the station is not part of this destination, and an
+    /// error if the station's progress or state is already accessed.
+    #[allow(clippy::type_complexity)]
+    fn station_borrow_mut(
+        &self,
+        station_spec: &StationSpec<E>,
+    ) -> Option<
+        Result<
+            (
+                StationRtId,
+                &StationDir,
+                RwLockWriteGuard<'_, StationProgress>,
+                RwLockWriteGuard<'_, StationState>,
+            ),
+            Error<E>,
+        >,
+    > {
+        let station_rt_id = self.station_id_to_rt_id.get(station_spec.id())?;
+        let station_dir = self.dirs.station_dirs.get(station_rt_id)?;
+        let station_borrow = self
+            .station_progresses
+            .try_borrow_mut(station_rt_id)
+            .and_then(|station_progress| {
+                self.station_states
+                    .try_borrow_mut(station_rt_id)
+                    .map(|station_state| (station_progress, station_state))
+            })
+            .map(|(station_progress, station_state)| {
+                (*station_rt_id, station_dir, station_progress, station_state)
+            })
+            .map_err(|borrow_fail| Error::StationBorrowConflict {
+                station_id: station_spec.id().clone(),
+                borrow_fail,
+            });
+
+        Some(station_borrow)
+    }
 }
@@ -1,14 +1,22 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    sync::Arc,
+};
 
 use choochoo_cfg_model::{
-    daggy::WouldCycle,
-    fn_graph::{Edge, EdgeId, FnGraphBuilder},
-    rt::{ProgressLimit, StationProgress, StationRtId},
-    StationSpec, StationSpecs,
+    daggy::{petgraph::algo::has_path_connecting, Walker, WouldCycle},
+    fn_graph::{Edge, EdgeId, FnGraph, FnGraphBuilder, FnMeta, TypeIds},
+    indexmap::IndexMap,
+    rt::{ProgressLimit, StationProgress, StationRtId, StationState},
+    StationId, StationIdInvalidFmt, StationOp, StationSpec, StationSpecTemplate, StationSpecs,
 };
 use choochoo_resource::Profile;
 
-use crate::{Destination, DestinationDirCalc, Error, StationProgresses, WorkspaceSpec};
+use crate::{
+    CheckpointGroup, Destination, DestinationDirCalc, Error, FileSystem, NativeFileSystem,
+    ProfileDirSpec, StationProgresses, StationStates, WorkspaceSpec,
+};
 
 #[derive(Debug)]
 pub struct DestinationBuilder<E> {
@@ -18,8 +26,27 @@ pub struct DestinationBuilder<E> {
     ///
     /// By default the execution working directory is used.
     workspace_spec: Option<WorkspaceSpec>,
+    /// Describes where profile data is stored.
+    ///
+    /// By default the workspace's `target` directory is used.
+    profile_dir_spec: Option<ProfileDirSpec>,
+    /// File system that directories and resource IDs are written to.
+    ///
+    /// By default the native file system is used.
+    file_system: Option<Arc<dyn FileSystem>>,
     /// Builder for the stations along the way to the destination.
     fn_graph_builder: FnGraphBuilder<StationSpec<E>>,
+    /// IDs of the stations added to this builder.
+    ///
+    /// This is used to detect collisions when merging builders.
+    station_ids: HashSet<StationId>,
+    /// Groups of stations that are created as a unit.
+    checkpoint_groups: Vec<CheckpointGroup>,
+    /// Operations to substitute for the operations of stations, by station ID.
+    station_op_replacements: IndexMap<StationId, StationOp<E>>,
+    /// How stations that may be visited concurrently, but have conflicting
+    /// resource borrows, are handled.
+    resource_conflict_policy: ResourceConflictPolicy,
 }
 
 impl<E> DestinationBuilder<E>
@@ -47,6 +74,45 @@ where
         self
     }
 
+    /// Specifies where profile data is stored.
+    ///
+    /// By default the workspace's `target` directory is used.
+    #[must_use]
+    pub fn with_profile_dir_spec(mut self, profile_dir_spec: ProfileDirSpec) -> Self {
+        self.profile_dir_spec = Some(profile_dir_spec);
+        self
+    }
+
+    /// Specifies the file system that directories and resource IDs are
+    /// written to.
+    ///
+    /// By default the native file system is used. Use [`MemoryFileSystem`] so
+    /// that tests and dry runs do not write to disk.
+    ///
+    /// [`MemoryFileSystem`]: crate::MemoryFileSystem
+    #[must_use]
+    pub fn with_file_system<Fs>(mut self, file_system: Fs) -> Self
+    where
+        Fs: FileSystem + 'static,
+    {
+        self.file_system = Some(Arc::new(file_system));
+        self
+    }
+
+    /// Specifies how stations that may be visited concurrently, but have
+    /// conflicting resource borrows, are handled.
+    ///
+    /// By default, such stations are visited one after the other, without
+    /// adding edges between them.
+    #[must_use]
+    pub fn with_resource_conflict_policy(
+        mut self,
+        resource_conflict_policy: ResourceConflictPolicy,
+    ) -> Self {
+        self.resource_conflict_policy = resource_conflict_policy;
+        self
+    }
+
     /// Adds a station to this destination.
     ///
     /// The returned station ID is used to specify dependencies between stations
@@ -54,6 +120,7 @@ where
     ///
     /// [`add_edge`]: Self::add_edge
     pub fn add_station(&mut self, station_spec: StationSpec<E>) -> StationRtId {
+        self.station_ids.insert(station_spec.id().clone());
         self.fn_graph_builder.add_fn(station_spec)
     }
 
@@ -68,6 +135,11 @@ where
         &mut self,
         station_specs: [StationSpec<E>; N],
     ) -> [StationRtId; N] {
+        self.station_ids.extend(
+            station_specs
+                .iter()
+                .map(|station_spec| station_spec.id().clone()),
+        );
         self.fn_graph_builder.add_fns(station_specs)
     }
 
@@ -95,19 +167,213 @@ where
         self.fn_graph_builder.add_edges(edges)
     }
 
+    /// Adds stations instantiated from a template, one per parameter.
+    ///
+    /// Each instantiated station depends on every station in `parents`, and
+    /// every station in `children` depends on each instantiated station.
+    ///
+    /// The returned station IDs are in the same order as the parameters.
+    ///
+    /// # Parameters
+    ///
+    /// * `template`: Template to instantiate stations from.
+    /// * `params`: Parameters to instantiate a station for.
+    /// * `parents`: Stations that each instantiated station depends on.
+    /// * `children`: Stations that depend on each instantiated station.
+    pub fn add_station_template<'p, P, I>(
+        &mut self,
+        template: &StationSpecTemplate<P, E>,
+        params: I,
+        parents: &[StationRtId],
+        children: &[StationRtId],
+    ) -> Result<Vec<StationRtId>, Error<E>>
+    where
+        I: IntoIterator<Item = &'p P>,
+        P: 'p,
+    {
+        let station_specs =
+            template
+                .instantiate(params)
+                .map_err(|error| Error::StationTemplateInstantiate {
+                    id_prefix: template.id_prefix().to_string(),
+                    error,
+                })?;
+
+        let station_rt_ids = station_specs
+            .into_iter()
+            .map(|station_spec| self.add_station(station_spec))
+            .collect::<Vec<StationRtId>>();
+
+        for station_rt_id in station_rt_ids.iter().copied() {
+            let parent_edges = parents.iter().map(|parent| (*parent, station_rt_id));
+            let child_edges = children.iter().map(|child| (station_rt_id, *child));
+
+            for (station_from, station_to) in parent_edges.chain(child_edges) {
+                self.add_edge(station_from, station_to).map_err(|error| {
+                    Error::StationTemplateEdgeWouldCycle {
+                        id_prefix: template.id_prefix().to_string(),
+                        error,
+                    }
+                })?;
+            }
+        }
+
+        Ok(station_rt_ids)
+    }
+
+    /// Adds the stations, edges, and checkpoint groups of another builder to
+    /// this destination.
+    ///
+    /// This allows destinations to be composed from stations constructed
+    /// separately, such as by different library crates. The profile and
+    /// workspace spec of `other` are not used.
+    ///
+    /// The returned map is from each station's runtime ID in `other` to its
+    /// runtime ID in this builder, so that edges may be added between the
+    /// merged stations.
+    ///
+    /// If any station ID in `other` is already used in this builder, nothing
+    /// is merged, and [`Error::DestinationMergeStationIdCollision`] is returned
+    /// with every colliding ID.
+    pub fn merge(
+        &mut self,
+        other: DestinationBuilder<E>,
+    ) -> Result<HashMap<StationRtId, StationRtId>, Error<E>> {
+        let Self {
+            fn_graph_builder: other_fn_graph_builder,
+            checkpoint_groups: other_checkpoint_groups,
+            station_op_replacements: other_station_op_replacements,
+            ..
+        } = other;
+        let other_station_specs = other_fn_graph_builder.build();
+
+        let station_ids_colliding = other_station_specs
+            .iter_insertion_with_indices()
+            .map(|(_, station_spec)| station_spec.id())
+            .filter(|station_id| self.station_ids.contains(*station_id))
+            .cloned()
+            .collect::<Vec<StationId>>();
+        if !station_ids_colliding.is_empty() {
+            return Err(Error::DestinationMergeStationIdCollision {
+                station_ids: station_ids_colliding,
+            });
+        }
+
+        let station_rt_id_map = other_station_specs
+            .iter_insertion_with_indices()
+            .map(|(station_rt_id_other, station_spec)| {
+                let station_rt_id = self.add_station(station_spec.clone());
+                (station_rt_id_other, station_rt_id)
+            })
+            .collect::<HashMap<StationRtId, StationRtId>>();
+
+        // `other`'s graph is acyclic, and none of its stations are connected
+        // to the existing stations, so adding its edges cannot cause a cycle.
+        other_station_specs
+            .iter_insertion_with_indices()
+            .flat_map(|(station_rt_id_other, _)| {
+                other_station_specs
+                    .children(station_rt_id_other)
+                    .iter(&other_station_specs)
+                    .map(move |(_edge, child_rt_id_other)| (station_rt_id_other, child_rt_id_other))
+                    .collect::<Vec<_>>()
+            })
+            .for_each(|(station_from_other, station_to_other)| {
+                let station_from = station_rt_id_map[&station_from_other];
+                let station_to = station_rt_id_map[&station_to_other];
+                let _edge_id = self.add_edge(station_from, station_to);
+            });
+
+        other_checkpoint_groups
+            .into_iter()
+            .for_each(|checkpoint_group| {
+                let station_rt_ids = checkpoint_group
+                    .station_rt_ids()
+                    .iter()
+                    .filter_map(|station_rt_id_other| station_rt_id_map.get(station_rt_id_other))
+                    .copied()
+                    .collect::<Vec<StationRtId>>();
+                self.add_checkpoint_group(checkpoint_group.name(), station_rt_ids);
+            });
+        self.station_op_replacements
+            .extend(other_station_op_replacements);
+
+        Ok(station_rt_id_map)
+    }
+
+    /// Tags a set of stations as a checkpoint group.
+    ///
+    /// If any station in the group fails to be created, the clean functions of
+    /// every station in the group are run in reverse order before the train
+    /// stops. Because this removes the group's resources, the next visit
+    /// creates the group from its beginning.
+    pub fn add_checkpoint_group<I>(&mut self, name: impl Into<String>, station_rt_ids: I)
+    where
+        I: IntoIterator<Item = StationRtId>,
+    {
+        self.checkpoint_groups
+            .push(CheckpointGroup::new(name, station_rt_ids));
+    }
+
+    /// Replaces the operations of a station when the destination is built.
+    ///
+    /// The station keeps its ID, other properties, and edges, so tests can
+    /// substitute stations that are expensive to run, such as ones that call
+    /// cloud APIs, while still exercising how the destination is reached.
+    ///
+    /// The station may be added before or after this is called. If no station
+    /// has the ID when the destination is built,
+    /// [`Error::StationOpReplaceNotFound`] is returned.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_id`: ID of the station whose operations to replace.
+    /// * `station_op`: Operations to use instead.
+    pub fn replace_op<Id>(
+        &mut self,
+        station_id: Id,
+        station_op: StationOp<E>,
+    ) -> Result<(), StationIdInvalidFmt<'static>>
+    where
+        StationId: TryFrom<Id, Error = StationIdInvalidFmt<'static>>,
+    {
+        let station_id = StationId::try_from(station_id)?;
+        self.station_op_replacements.insert(station_id, station_op);
+
+        Ok(())
+    }
+
     /// Builds and returns the [`Destination`].
     pub fn build(self) -> Result<Destination<E>, Error<E>> {
         let Self {
             profile,
             workspace_spec,
+            profile_dir_spec,
+            file_system,
             fn_graph_builder,
+            station_ids: _,
+            checkpoint_groups,
+            station_op_replacements,
+            resource_conflict_policy,
         } = self;
 
         let profile = profile.unwrap_or_default();
         let workspace_spec = workspace_spec.unwrap_or_default();
-        let station_specs = StationSpecs::new(fn_graph_builder.build());
+        let profile_dir_spec = profile_dir_spec.unwrap_or_default();
+        let file_system = file_system.unwrap_or_else(|| Arc::new(NativeFileSystem));
+        let station_specs =
+            Self::station_ops_replace(fn_graph_builder.build(), station_op_replacements)?;
+        if resource_conflict_policy == ResourceConflictPolicy::Error {
+            Self::resource_conflicts_check(&station_specs)?;
+        }
+        let station_specs = StationSpecs::new(station_specs);
 
-        let destination_dirs = DestinationDirCalc::calc(&workspace_spec, &profile, &station_specs)?;
+        let destination_dirs = DestinationDirCalc::calc_with_profile_dir_spec(
+            &workspace_spec,
+            &profile_dir_spec,
+            &profile,
+            &station_specs,
+        )?;
 
         let mut station_id_to_rt_id = HashMap::with_capacity(station_specs.node_count());
         station_specs
@@ -130,15 +396,143 @@ where
                 },
             );
 
+        let station_states = station_specs.iter_insertion_with_indices().fold(
+            StationStates::with_capacity(station_specs.node_count()),
+            |mut station_states, (station_rt_id, _)| {
+                station_states.insert(station_rt_id, StationState::new());
+                station_states
+            },
+        );
+
         let dest = Destination {
             profile,
             station_specs,
             dirs: destination_dirs,
+            file_system,
             station_id_to_rt_id,
             station_progresses,
+            station_states,
+            checkpoint_groups,
+            resource_conflict_policy,
         };
         Ok(dest)
     }
+
+    /// Returns the station graph with the operations of stations replaced.
+    fn station_ops_replace(
+        station_specs: FnGraph<StationSpec<E>>,
+        mut station_op_replacements: IndexMap<StationId, StationOp<E>>,
+    ) -> Result<FnGraph<StationSpec<E>>, Error<E>> {
+        if station_op_replacements.is_empty() {
+            return Ok(station_specs);
+        }
+
+        let station_specs =
+            Self::fn_graph_rebuild(&station_specs, |station_spec| match station_op_replacements
+                .shift_remove(station_spec.id())
+            {
+                Some(station_op) => station_spec.clone().with_station_op(station_op),
+                None => station_spec.clone(),
+            });
+
+        if let Some((station_id, _)) = station_op_replacements.into_iter().next() {
+            return Err(Error::StationOpReplaceNotFound { station_id });
+        }
+
+        Ok(station_specs)
+    }
+
+    /// Returns an error if stations that may be visited concurrently have
+    /// conflicting resource borrows.
+    ///
+    /// Stations conflict when, within the same operation, one mutably borrows a
+    /// resource that the other borrows. Conflicts are found in station
+    /// insertion order.
+    fn resource_conflicts_check(station_specs: &FnGraph<StationSpec<E>>) -> Result<(), Error<E>> {
+        let station_borrows = station_specs
+            .iter_insertion_with_indices()
+            .map(|(station_rt_id, station_spec)| {
+                let station_op = station_spec.station_op();
+                let create_fns = station_op.create_fns();
+                let create_borrows = (create_fns.borrows(), create_fns.borrow_muts());
+                let clean_borrows = station_op
+                    .clean_fns()
+                    .map(|clean_fns| (clean_fns.borrows(), clean_fns.borrow_muts()))
+                    .unwrap_or_default();
+                (station_rt_id, create_borrows, clean_borrows)
+            })
+            .collect::<Vec<(StationRtId, (TypeIds, TypeIds), (TypeIds, TypeIds))>>();
+
+        let graph = station_specs.graph();
+        for (index, (rt_id_a, create_a, clean_a)) in station_borrows.iter().enumerate() {
+            for (rt_id_b, create_b, clean_b) in station_borrows.iter().skip(index + 1) {
+                let conflicts = Self::borrows_conflict(create_a, create_b)
+                    || Self::borrows_conflict(clean_a, clean_b);
+                if !conflicts
+                    || has_path_connecting(graph, *rt_id_a, *rt_id_b, None)
+                    || has_path_connecting(graph, *rt_id_b, *rt_id_a, None)
+                {
+                    continue;
+                }
+
+                return Err(Error::StationResourceConflict {
+                    station_a: station_specs[*rt_id_a].id().clone(),
+                    station_b: station_specs[*rt_id_b].id().clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether one of the given `(borrows, borrow_muts)` mutably
+    /// borrows a resource that the other borrows.
+    fn borrows_conflict(
+        (borrows_a, borrow_muts_a): &(TypeIds, TypeIds),
+        (borrows_b, borrow_muts_b): &(TypeIds, TypeIds),
+    ) -> bool {
+        borrow_muts_a
+            .iter()
+            .any(|type_id| borrows_b.contains(type_id) || borrow_muts_b.contains(type_id))
+            || borrow_muts_b
+                .iter()
+                .any(|type_id| borrows_a.contains(type_id))
+    }
+
+    /// Returns a new station graph with each station mapped, and the same
+    /// edges.
+    ///
+    /// Stations are added to the new graph in insertion order, so each
+    /// station's runtime ID is unchanged.
+    fn fn_graph_rebuild<F>(
+        station_specs: &FnGraph<StationSpec<E>>,
+        mut station_spec_map: F,
+    ) -> FnGraph<StationSpec<E>>
+    where
+        F: FnMut(&StationSpec<E>) -> StationSpec<E>,
+    {
+        let mut fn_graph_builder = FnGraphBuilder::default();
+        station_specs
+            .iter_insertion_with_indices()
+            .for_each(|(_, station_spec)| {
+                let _station_rt_id = fn_graph_builder.add_fn(station_spec_map(station_spec));
+            });
+
+        station_specs
+            .iter_insertion_with_indices()
+            .flat_map(|(station_rt_id, _)| {
+                station_specs
+                    .children(station_rt_id)
+                    .iter(station_specs)
+                    .map(move |(_edge, child_rt_id)| (station_rt_id, child_rt_id))
+                    .collect::<Vec<_>>()
+            })
+            .for_each(|(station_from, station_to)| {
+                let _edge_id = fn_graph_builder.add_edge(station_from, station_to);
+            });
+
+        fn_graph_builder.build()
+    }
 }
 
 impl<E> Default for DestinationBuilder<E> {
@@ -146,7 +540,13 @@ impl<E> Default for DestinationBuilder<E> {
         Self {
             profile: None,
             workspace_spec: None,
+            profile_dir_spec: None,
+            file_system: None,
             fn_graph_builder: FnGraphBuilder::default(),
+            station_ids: HashSet::new(),
+            checkpoint_groups: Vec::new(),
+            station_op_replacements: IndexMap::new(),
+            resource_conflict_policy: ResourceConflictPolicy::default(),
         }
     }
 }
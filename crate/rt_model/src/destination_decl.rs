@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use choochoo_cfg_model::{rt::StationRtId, StationId, StationSpecBuilder};
+
+use crate::{DestinationBuilder, Error, StationOpRegistry};
+
+/// Declarative description of the stations and edges of a destination.
+///
+/// This can be deserialized from any `serde` format such as YAML or TOML, and
+/// turned into a [`DestinationBuilder`] using a [`StationOpRegistry`] that maps
+/// each station's logical operation name to its Rust implementation.
+///
+/// ```yaml
+/// stations:
+///   - id: download
+///     name: Download
+///     description: Downloads the application.
+///     tags: [fetch]
+///     op: file_download
+///   - id: extract
+///     op: extract
+/// edges:
+///   - from: download
+///     to: extract
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DestinationDecl {
+    /// Stations along the way to the destination.
+    #[serde(default)]
+    pub stations: Vec<StationDecl>,
+    /// Dependencies between stations.
+    #[serde(default)]
+    pub edges: Vec<EdgeDecl>,
+}
+
+/// Declaration of a single station.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct StationDecl {
+    /// Unique identifier of the station.
+    pub id: StationId,
+    /// Human readable name of the station, defaults to the station ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Short description of the station's purpose.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Labels to group and select stations.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Logical name of the operation registered in the [`StationOpRegistry`].
+    pub op: String,
+}
+
+/// Declaration of a dependency between two stations.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct EdgeDecl {
+    /// Station that must be visited first.
+    pub from: StationId,
+    /// Station that depends on the `from` station.
+    pub to: StationId,
+}
+
+impl DestinationDecl {
+    /// Returns a [`DestinationBuilder`] with the declared stations and edges.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_op_registry`: Operations referenced by the declared stations.
+    pub fn into_builder<E>(
+        self,
+        station_op_registry: &StationOpRegistry<E>,
+    ) -> Result<DestinationBuilder<E>, Error<E>>
+    where
+        E: 'static,
+    {
+        let Self { stations, edges } = self;
+
+        let mut dest_builder = DestinationBuilder::new();
+        let mut station_id_to_rt_id =
+            HashMap::<StationId, StationRtId>::with_capacity(stations.len());
+
+        for station_decl in stations {
+            let StationDecl {
+                id,
+                name,
+                description,
+                tags,
+                op,
+            } = station_decl;
+
+            if station_id_to_rt_id.contains_key(&id) {
+                return Err(Error::DeclStationIdDuplicate { station_id: id });
+            }
+
+            let station_op = station_op_registry.get(&op).cloned().ok_or_else(|| {
+                Error::DeclStationOpNotFound {
+                    station_id: id.clone(),
+                    op_name: op,
+                }
+            })?;
+
+            let mut station_spec_builder =
+                StationSpecBuilder::from_station_id(id.clone(), station_op);
+            if let Some(name) = name {
+                station_spec_builder = station_spec_builder.with_name(name);
+            }
+            if let Some(description) = description {
+                station_spec_builder = station_spec_builder.with_description(description);
+            }
+            let station_spec = station_spec_builder.with_tags(tags).build();
+
+            let station_rt_id = dest_builder.add_station(station_spec);
+            station_id_to_rt_id.insert(id, station_rt_id);
+        }
+
+        for EdgeDecl { from, to } in edges {
+            let station_rt_id_lookup = |station_id: &StationId| {
+                station_id_to_rt_id.get(station_id).copied().ok_or_else(|| {
+                    Error::DeclEdgeStationNotFound {
+                        station_id: station_id.clone(),
+                    }
+                })
+            };
+            let station_from = station_rt_id_lookup(&from)?;
+            let station_to = station_rt_id_lookup(&to)?;
+
+            dest_builder
+                .add_edge(station_from, station_to)
+                .map_err(|error| Error::DeclEdgeWouldCycle {
+                    station_from: from,
+                    station_to: to,
+                    error,
+                })?;
+        }
+
+        Ok(dest_builder)
+    }
+}
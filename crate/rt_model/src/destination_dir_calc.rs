@@ -5,9 +5,11 @@ use std::{
 };
 
 use choochoo_cfg_model::{rt::StationDir, StationSpecs};
-use choochoo_resource::{HistoryDir, Profile, ProfileDir, ProfileHistoryDir, WorkspaceDir};
+use choochoo_resource::{
+    CacheDir, HistoryDir, Profile, ProfileDir, ProfileHistoryDir, WorkspaceDir,
+};
 
-use crate::{DestinationDirs, Error, StationDirs, WorkspaceSpec};
+use crate::{DestinationDirs, Error, ProfileDirSpec, StationDirs, WorkspaceSpec};
 
 /// Computes directories for a destination.
 #[derive(Debug)]
@@ -17,6 +19,8 @@ impl<E> DestinationDirCalc<E>
 where
     E: 'static,
 {
+    /// Directory to contain data cached across profiles.
+    pub const CACHE_DIR_NAME: &'static str = ".cache";
     /// Directory to contain all profile history directories.
     pub const HISTORY_DIR_NAME: &'static str = ".history";
     /// Directory to contain all profile directories.
@@ -30,11 +34,34 @@ where
     /// * [`HistoryDir`]: `${workspace}/target/.history`
     /// * [`ProfileHistoryDir`]: `${workspace}/target/.history/${profile}`
     /// * [`ProfileDir`]: `${workspace}/target/${profile}`
+    /// * [`CacheDir`]: `${workspace}/target/.cache`
     /// * [`StationDirs`]: `${workspace}/target/${profile}/${station_id}`
+    ///
+    /// Use [`DestinationDirCalc::calc_with_profile_dir_spec`] to store profile
+    /// data outside the `target` directory.
     pub fn calc(
         workspace_spec: &WorkspaceSpec,
         profile: &Profile,
         station_specs: &StationSpecs<E>,
+    ) -> Result<DestinationDirs, Error<E>> {
+        Self::calc_with_profile_dir_spec(
+            workspace_spec,
+            &ProfileDirSpec::WorkspaceTarget,
+            profile,
+            station_specs,
+        )
+    }
+
+    /// Computes directories for a destination, storing profile data in the
+    /// location described by the profile directory spec.
+    ///
+    /// For [`ProfileDirSpec::Path`], `target` in the directories listed in
+    /// [`DestinationDirCalc::calc`] is replaced by the given path.
+    pub fn calc_with_profile_dir_spec(
+        workspace_spec: &WorkspaceSpec,
+        profile_dir_spec: &ProfileDirSpec,
+        profile: &Profile,
+        station_specs: &StationSpecs<E>,
     ) -> Result<DestinationDirs, Error<E>> {
         let workspace_dir = {
             let working_dir = std::env::current_dir().map_err(Error::WorkingDirRead)?;
@@ -55,18 +82,17 @@ where
             WorkspaceDir::new(workspace_dir)
         };
 
-        let history_dir = HistoryDir::new(
-            workspace_dir
-                .join(Self::TARGET_DIR_NAME)
-                .join(Self::HISTORY_DIR_NAME),
-        );
+        // `Path::join` returns the given path if it is absolute.
+        let target_dir = match profile_dir_spec {
+            ProfileDirSpec::WorkspaceTarget => workspace_dir.join(Self::TARGET_DIR_NAME),
+            ProfileDirSpec::Path(path) => workspace_dir.join(path),
+        };
+
+        let history_dir = HistoryDir::new(target_dir.join(Self::HISTORY_DIR_NAME));
         let profile_history_dir = ProfileHistoryDir::new(history_dir.join(profile.as_ref()));
 
-        let profile_dir = ProfileDir::new(
-            workspace_dir
-                .join(Self::TARGET_DIR_NAME)
-                .join(profile.as_ref()),
-        );
+        let profile_dir = ProfileDir::new(target_dir.join(profile.as_ref()));
+        let cache_dir = CacheDir::new(target_dir.join(Self::CACHE_DIR_NAME));
         let station_dirs = {
             let station_dirs = station_specs.iter_insertion_with_indices().fold(
                 HashMap::with_capacity(station_specs.node_count()),
@@ -86,6 +112,7 @@ where
             history_dir,
             profile_history_dir,
             profile_dir,
+            cache_dir,
             station_dirs,
         })
     }
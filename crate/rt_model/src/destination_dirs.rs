@@ -1,4 +1,4 @@
-use choochoo_resource::{HistoryDir, ProfileDir, ProfileHistoryDir, WorkspaceDir};
+use choochoo_resource::{CacheDir, HistoryDir, ProfileDir, ProfileHistoryDir, WorkspaceDir};
 
 use crate::StationDirs;
 
@@ -19,6 +19,8 @@ pub struct DestinationDirs {
     pub profile_history_dir: ProfileHistoryDir,
     /// Directory to store all data produced by the current profile's execution.
     pub profile_dir: ProfileDir,
+    /// Directory to cache data shared across profiles.
+    pub cache_dir: CacheDir,
     /// Map from [`StationRtId`] to each station's execution directory.
     pub station_dirs: StationDirs,
 }
@@ -44,6 +46,11 @@ impl DestinationDirs {
         &self.profile_dir
     }
 
+    /// Returns a reference to the cache dir.
+    pub fn cache_dir(&self) -> &CacheDir {
+        &self.cache_dir
+    }
+
     /// Returns a reference to the station dirs.
     pub fn station_dirs(&self) -> &StationDirs {
         &self.station_dirs
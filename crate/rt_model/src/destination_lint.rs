@@ -0,0 +1,163 @@
+use std::{borrow::Cow, fmt, panic::Location};
+
+use choochoo_cfg_model::{
+    srcerr::{
+        self,
+        codespan_reporting::{
+            diagnostic::{Diagnostic, Severity},
+            files::Files,
+        },
+    },
+    StationId,
+};
+
+use crate::error::AsDiagnostic;
+
+/// Common mistake in a destination's station specifications.
+///
+/// These are reported by the [`DestinationValidator`] before the destination
+/// is reached.
+///
+/// [`DestinationValidator`]: crate::DestinationValidator
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DestinationLint {
+    /// The station's create work function has no check function and the
+    /// station declares no inputs, so its work is run every time the station
+    /// is visited.
+    CreateCheckFnMissing {
+        /// Unique identifier of the station.
+        id: StationId,
+        /// Human readable name of the station.
+        name: String,
+        /// Source code location that the station was defined at.
+        location: &'static Location<'static>,
+    },
+    /// The station has no clean functions, so any resources created by its
+    /// create work function are never cleaned up.
+    ///
+    /// Station specifications do not declare whether the work creates
+    /// resources, so this is reported for every station without clean
+    /// functions. Stations that only compute values may ignore this lint.
+    CleanFnsMissing {
+        /// Unique identifier of the station.
+        id: StationId,
+        /// Human readable name of the station.
+        name: String,
+        /// Source code location that the station was defined at.
+        location: &'static Location<'static>,
+    },
+    /// Multiple stations have the same name.
+    StationNameDuplicate {
+        /// The duplicated name.
+        name: String,
+        /// Unique identifiers of the stations with the name.
+        ids: Vec<StationId>,
+    },
+    /// Multiple stations have the same ID.
+    ///
+    /// Only one of these stations can be looked up by its ID.
+    StationIdDuplicate {
+        /// The duplicated ID.
+        id: StationId,
+    },
+    /// The station has no dependencies on, and no dependents from, any other
+    /// station.
+    StationUnreachable {
+        /// Unique identifier of the station.
+        id: StationId,
+        /// Human readable name of the station.
+        name: String,
+        /// Source code location that the station was defined at.
+        location: &'static Location<'static>,
+    },
+}
+
+impl DestinationLint {
+    /// Returns the code of this lint, e.g. `"L001"`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::CreateCheckFnMissing { .. } => "L001",
+            Self::CleanFnsMissing { .. } => "L002",
+            Self::StationNameDuplicate { .. } => "L003",
+            Self::StationIdDuplicate { .. } => "L004",
+            Self::StationUnreachable { .. } => "L005",
+        }
+    }
+
+    /// Returns the severity of this lint.
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::CreateCheckFnMissing { .. }
+            | Self::CleanFnsMissing { .. }
+            | Self::StationNameDuplicate { .. }
+            | Self::StationUnreachable { .. } => Severity::Warning,
+            Self::StationIdDuplicate { .. } => Severity::Error,
+        }
+    }
+
+    /// Returns a suggestion to fix this lint.
+    pub fn help(&self) -> &'static str {
+        match self {
+            Self::CreateCheckFnMissing { .. } => {
+                "Add a check function using `StationSpecBuilder::with_create_check_fn` so work is skipped when unnecessary."
+            }
+            Self::CleanFnsMissing { .. } => {
+                "If the station creates resources, add clean functions using `StationSpecBuilder::with_clean_fns`. Stations that do not create resources may ignore this."
+            }
+            Self::StationNameDuplicate { .. } => {
+                "Give each station a distinct name using `StationSpecBuilder::with_name`."
+            }
+            Self::StationIdDuplicate { .. } => "Give each station a distinct ID.",
+            Self::StationUnreachable { .. } => {
+                "Add an edge between this station and the station it relates to."
+            }
+        }
+    }
+}
+
+impl fmt::Display for DestinationLint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::CreateCheckFnMissing { id, name, location } => write!(
+                f,
+                "Station `{id}: {name}` has a create work function but no check function. (defined at {location})"
+            ),
+            Self::CleanFnsMissing { id, name, location } => write!(
+                f,
+                "Station `{id}: {name}` has no clean functions, so resources created by its work are not cleaned up. (defined at {location})"
+            ),
+            Self::StationNameDuplicate { name, ids } => {
+                write!(f, "Station name `{name}` is used by multiple stations: ")?;
+                ids.iter().enumerate().try_for_each(|(index, id)| {
+                    if index == 0 {
+                        write!(f, "`{id}`")
+                    } else {
+                        write!(f, ", `{id}`")
+                    }
+                })?;
+                write!(f, ".")
+            }
+            Self::StationIdDuplicate { id } => {
+                write!(f, "Station ID `{id}` is used by multiple stations.")
+            }
+            Self::StationUnreachable { id, name, location } => write!(
+                f,
+                "Station `{id}: {name}` is not connected to any other station. (defined at {location})"
+            ),
+        }
+    }
+}
+
+impl<'a> AsDiagnostic<'a> for DestinationLint {
+    type Files = srcerr::codespan::Files<Cow<'a, str>>;
+
+    fn as_diagnostic(
+        &self,
+        _files: &Self::Files,
+    ) -> Diagnostic<<Self::Files as Files<'a>>::FileId> {
+        Diagnostic::new(self.severity())
+            .with_code(self.code())
+            .with_message(self.to_string())
+            .with_notes(vec![format!("help: {}", self.help())])
+    }
+}
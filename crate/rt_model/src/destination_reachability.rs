@@ -0,0 +1,178 @@
+use std::{
+    any::TypeId,
+    collections::{HashSet, VecDeque},
+};
+
+use choochoo_cfg_model::{daggy::Walker, fn_graph::FnMeta, rt::StationRtId};
+
+use crate::Destination;
+
+/// Connectivity of a destination's stations, and declared resources that no
+/// station accesses.
+///
+/// Stations that are connected through dependencies -- in either direction --
+/// form an island. A destination usually has one island; stations outside the
+/// largest island are orphans, as there is no path to them from the roots of
+/// the rest of the destination. These are often stations that were added
+/// without their edges.
+///
+/// This is computed without running any station functions.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DestinationReachability {
+    /// Groups of connected stations, ordered by their earliest inserted
+    /// station.
+    islands: Vec<Vec<StationRtId>>,
+    /// Stations in the largest island that have no dependencies.
+    roots: Vec<StationRtId>,
+    /// Stations that are not in the largest island.
+    stations_orphaned: Vec<StationRtId>,
+    /// Type names of declared resources that no station function accesses.
+    resources_unused: Vec<&'static str>,
+}
+
+impl DestinationReachability {
+    /// Returns the `DestinationReachability` of a destination.
+    ///
+    /// A resource is accessed when one of a station's create or clean
+    /// functions borrows it. Resources inserted or read by setup functions are
+    /// not known, so they are not counted as accessed.
+    ///
+    /// # Parameters
+    ///
+    /// * `dest`: Destination whose stations to analyze.
+    /// * `resources_declared`: `TypeId` and type name of each resource that
+    ///   stations are expected to access.
+    pub(crate) fn new<E, I>(dest: &Destination<E>, resources_declared: I) -> Self
+    where
+        E: 'static,
+        I: IntoIterator<Item = (TypeId, &'static str)>,
+    {
+        let station_specs = dest.station_specs();
+
+        let mut stations_visited = HashSet::<StationRtId>::new();
+        let mut islands = Vec::<Vec<StationRtId>>::new();
+        station_specs
+            .iter_insertion_with_indices()
+            .for_each(|(station_rt_id, _station_spec)| {
+                if !stations_visited.insert(station_rt_id) {
+                    return;
+                }
+
+                let mut island = Vec::new();
+                let mut stations_pending = VecDeque::from([station_rt_id]);
+                while let Some(station_rt_id) = stations_pending.pop_front() {
+                    island.push(station_rt_id);
+
+                    let parents = station_specs.parents(station_rt_id).iter(station_specs);
+                    let children = station_specs.children(station_rt_id).iter(station_specs);
+                    parents
+                        .chain(children)
+                        .for_each(|(_edge, station_rt_id_neighbour)| {
+                            if stations_visited.insert(station_rt_id_neighbour) {
+                                stations_pending.push_back(station_rt_id_neighbour);
+                            }
+                        });
+                }
+                island.sort();
+
+                islands.push(island);
+            });
+
+        // When islands have the same size, the earlier island is the main one.
+        let island_main_index = islands
+            .iter()
+            .enumerate()
+            .fold(
+                None,
+                |island_largest, (index, island)| match island_largest {
+                    Some((_, len)) if len >= island.len() => island_largest,
+                    _ => Some((index, island.len())),
+                },
+            )
+            .map(|(index, _)| index);
+
+        let roots = island_main_index
+            .map(|index| {
+                islands[index]
+                    .iter()
+                    .copied()
+                    .filter(|station_rt_id| {
+                        station_specs
+                            .parents(*station_rt_id)
+                            .iter(station_specs)
+                            .next()
+                            .is_none()
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let mut stations_orphaned = islands
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| Some(*index) != island_main_index)
+            .flat_map(|(_, island)| island.iter().copied())
+            .collect::<Vec<_>>();
+        stations_orphaned.sort();
+
+        let resources_accessed = station_specs
+            .iter_insertion()
+            .flat_map(|station_spec| {
+                let station_op = station_spec.station_op();
+                let create_fns = station_op.create_fns();
+                let mut type_ids = create_fns.borrows();
+                type_ids.extend(create_fns.borrow_muts());
+                if let Some(clean_fns) = station_op.clean_fns() {
+                    type_ids.extend(clean_fns.borrows());
+                    type_ids.extend(clean_fns.borrow_muts());
+                }
+                type_ids
+            })
+            .collect::<HashSet<TypeId>>();
+        let resources_unused = resources_declared
+            .into_iter()
+            .filter(|(type_id, _)| !resources_accessed.contains(type_id))
+            .map(|(_, type_name)| type_name)
+            .collect::<Vec<_>>();
+
+        Self {
+            islands,
+            roots,
+            stations_orphaned,
+            resources_unused,
+        }
+    }
+
+    /// Returns the groups of connected stations, ordered by their earliest
+    /// inserted station.
+    ///
+    /// Stations within each island are in insertion order.
+    pub fn islands(&self) -> &[Vec<StationRtId>] {
+        &self.islands
+    }
+
+    /// Returns whether all stations are connected, i.e. there is at most one
+    /// island.
+    pub fn is_connected(&self) -> bool {
+        self.islands.len() <= 1
+    }
+
+    /// Returns the stations in the largest island that have no dependencies.
+    ///
+    /// When islands have the same number of stations, the earliest island is
+    /// used.
+    pub fn roots(&self) -> &[StationRtId] {
+        &self.roots
+    }
+
+    /// Returns the stations that have no path from any root, in insertion
+    /// order.
+    pub fn stations_orphaned(&self) -> &[StationRtId] {
+        &self.stations_orphaned
+    }
+
+    /// Returns the type names of declared resources that no station function
+    /// accesses.
+    pub fn resources_unused(&self) -> &[&'static str] {
+        &self.resources_unused
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+use choochoo_cfg_model::{daggy::Walker, StationId};
+
+use crate::{Destination, DestinationLint};
+
+/// Checks a destination's station specifications for common mistakes.
+///
+/// This should be run before reaching the destination, as these mistakes
+/// otherwise surface as confusing runtime behaviour.
+#[derive(Debug)]
+pub struct DestinationValidator;
+
+impl DestinationValidator {
+    /// Returns the lints found in the destination's station specifications.
+    ///
+    /// Lints are returned in station insertion order, followed by lints that
+    /// span multiple stations.
+    pub fn validate<E>(dest: &Destination<E>) -> Vec<DestinationLint>
+    where
+        E: 'static,
+    {
+        let station_specs = dest.station_specs();
+        let station_count = station_specs.node_count();
+
+        let mut lints = Vec::new();
+        let mut name_to_ids = HashMap::<&str, Vec<StationId>>::new();
+        let mut id_counts = HashMap::<&StationId, usize>::new();
+        let mut names_in_order = Vec::<&str>::new();
+        let mut ids_in_order = Vec::<&StationId>::new();
+
+        station_specs
+            .iter_insertion_with_indices()
+            .for_each(|(station_rt_id, station_spec)| {
+                let id = station_spec.id();
+                let name = station_spec.name();
+                let location = station_spec.location();
+                let station_op = station_spec.station_op();
+
+                // Stations that declare inputs skip work when the inputs are
+                // unchanged.
+                if station_op.create_fns().check_fn.is_none() && station_spec.inputs().is_empty() {
+                    lints.push(DestinationLint::CreateCheckFnMissing {
+                        id: id.clone(),
+                        name: name.to_string(),
+                        location,
+                    });
+                }
+
+                if station_op.clean_fns().is_none() {
+                    lints.push(DestinationLint::CleanFnsMissing {
+                        id: id.clone(),
+                        name: name.to_string(),
+                        location,
+                    });
+                }
+
+                if station_count > 1 {
+                    let has_parents = station_specs
+                        .parents(station_rt_id)
+                        .walk_next(station_specs)
+                        .is_some();
+                    let has_children = station_specs
+                        .children(station_rt_id)
+                        .walk_next(station_specs)
+                        .is_some();
+
+                    if !has_parents && !has_children {
+                        lints.push(DestinationLint::StationUnreachable {
+                            id: id.clone(),
+                            name: name.to_string(),
+                            location,
+                        });
+                    }
+                }
+
+                let ids = name_to_ids.entry(name).or_insert_with(|| {
+                    names_in_order.push(name);
+                    Vec::new()
+                });
+                ids.push(id.clone());
+
+                let id_count = id_counts.entry(id).or_insert_with(|| {
+                    ids_in_order.push(id);
+                    0
+                });
+                *id_count += 1;
+            });
+
+        names_in_order.into_iter().for_each(|name| {
+            if let Some(ids) = name_to_ids.remove(name) {
+                if ids.len() > 1 {
+                    lints.push(DestinationLint::StationNameDuplicate {
+                        name: name.to_string(),
+                        ids,
+                    });
+                }
+            }
+        });
+
+        ids_in_order.into_iter().for_each(|id| {
+            if id_counts.get(id).copied().unwrap_or_default() > 1 {
+                lints.push(DestinationLint::StationIdDuplicate { id: id.clone() });
+            }
+        });
+
+        lints
+    }
+}
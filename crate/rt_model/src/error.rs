@@ -1,19 +1,40 @@
 //! Types representing errors and their details.
 
-use std::{fmt, path::PathBuf};
+use std::{borrow::Cow, fmt, path::PathBuf};
 
 use tokio::task::JoinError;
 
 use choochoo_cfg_model::{
-    rt::{ResIds, StationDir, StationRtId, TrainResources},
-    StationId,
+    daggy::WouldCycle,
+    fn_graph::Edge,
+    rt::{OpStatus, ResIds, StationDir, StationRtId, TrainResources},
+    rt_map::BorrowFail,
+    srcerr::{
+        self,
+        codespan_reporting::{diagnostic::Diagnostic, files::Files},
+    },
+    StationId, StationIdInvalidFmt,
 };
-use choochoo_resource::{HistoryDir, ProfileDir, ProfileHistoryDir, WorkspaceDir};
+use choochoo_resource::{CacheDir, HistoryDir, ProfileDir, ProfileHistoryDir, WorkspaceDir};
 
-pub use self::{as_diagnostic::AsDiagnostic, station_spec_error::StationSpecError};
+#[cfg(feature = "anyhow")]
+pub use self::anyhow_station_error::AnyhowStationError;
+pub use self::{
+    as_diagnostic::AsDiagnostic, profile_store_error::ProfileStoreError,
+    simple_station_error::SimpleStationError, station_context_error::StationContextError,
+    station_error_context::StationErrorContext, station_spec_error::StationSpecError,
+    workspace_path_source_error::WorkspacePathSourceError,
+};
 
+#[cfg(feature = "anyhow")]
+mod anyhow_station_error;
 mod as_diagnostic;
+mod profile_store_error;
+mod simple_station_error;
+mod station_context_error;
+mod station_error_context;
 mod station_spec_error;
+mod workspace_path_source_error;
 
 /// Error while using `choochoo`.
 #[derive(Debug)]
@@ -22,6 +43,49 @@ pub enum Error<E> {
     MultiProgressTaskJoin(JoinError),
     /// Failed to join the multi-progress bar.
     MultiProgressJoin(std::io::Error),
+    /// An edge in a [`DestinationDecl`] references a station that is not
+    /// declared.
+    ///
+    /// [`DestinationDecl`]: crate::DestinationDecl
+    DeclEdgeStationNotFound {
+        /// ID of the station referenced by the edge.
+        station_id: StationId,
+    },
+    /// An edge in a [`DestinationDecl`] would cause a cycle.
+    ///
+    /// [`DestinationDecl`]: crate::DestinationDecl
+    DeclEdgeWouldCycle {
+        /// ID of the station the edge begins from.
+        station_from: StationId,
+        /// ID of the station the edge ends at.
+        station_to: StationId,
+        /// Underlying graph error.
+        error: WouldCycle<Edge>,
+    },
+    /// A station ID is declared multiple times in a [`DestinationDecl`].
+    ///
+    /// [`DestinationDecl`]: crate::DestinationDecl
+    DeclStationIdDuplicate {
+        /// ID of the duplicated station.
+        station_id: StationId,
+    },
+    /// A station in a [`DestinationDecl`] references an operation that is not
+    /// registered.
+    ///
+    /// [`DestinationDecl`]: crate::DestinationDecl
+    DeclStationOpNotFound {
+        /// ID of the station.
+        station_id: StationId,
+        /// Logical name of the operation.
+        op_name: String,
+    },
+    /// Failed to create cache directory.
+    CacheDirCreate {
+        /// The directory that was attempted to be created.
+        cache_dir: CacheDir,
+        /// Underlying IO error.
+        error: std::io::Error,
+    },
     /// Failed to create history directory.
     HistoryDirCreate {
         /// The directory that was attempted to be created.
@@ -29,6 +93,20 @@ pub enum Error<E> {
         /// Underlying IO error.
         error: std::io::Error,
     },
+    /// Failed to read the profile history directory.
+    HistoryRead {
+        /// The profile history directory.
+        profile_history_dir: ProfileHistoryDir,
+        /// Underlying IO error.
+        error: std::io::Error,
+    },
+    /// Failed to remove an entry from the profile history directory.
+    HistoryRemove {
+        /// Path of the entry.
+        path: PathBuf,
+        /// Underlying IO error.
+        error: std::io::Error,
+    },
     /// Failed to create profile directory.
     ProfileDirCreate {
         /// The directory that was attempted to be created.
@@ -36,6 +114,24 @@ pub enum Error<E> {
         /// Underlying IO error.
         error: std::io::Error,
     },
+    /// Another process holds the lock on the profile directory.
+    ProfileLocked {
+        /// The locked profile directory.
+        profile_dir: ProfileDir,
+        /// Path to the lock file.
+        lock_path: PathBuf,
+        /// ID of the process that holds the lock.
+        pid: u32,
+        /// Host that the process runs on.
+        host: String,
+    },
+    /// Failed to read, write, or remove the profile lock file.
+    ProfileLockAccess {
+        /// Path to the lock file.
+        lock_path: PathBuf,
+        /// Underlying IO error.
+        error: std::io::Error,
+    },
     /// Failed to create profile history directory.
     ProfileHistoryDirCreate {
         /// The directory that was attempted to be created.
@@ -43,6 +139,30 @@ pub enum Error<E> {
         /// Underlying IO error.
         error: std::io::Error,
     },
+    /// Failed to deserialize a resource ID persisted by a previous execution.
+    ResIdDeserialize {
+        /// Runtime ID of the station.
+        station_id: StationId,
+        /// Underlying deserialization error.
+        error: serde_json::error::Error,
+    },
+    /// A resource ID file was partially written by a previous execution, such
+    /// as when the process was killed while writing it.
+    ///
+    /// The resource may exist, but its ID is not known.
+    ResIdPartiallyWritten {
+        /// Runtime ID of the station.
+        station_id: StationId,
+        /// Path to the partially written file.
+        res_id_path: PathBuf,
+    },
+    /// Failed to read [`ResIds`] persisted by a previous execution.
+    ResIdRead {
+        /// Runtime ID of the station.
+        station_id: StationId,
+        /// Underlying IO error.
+        error: std::io::Error,
+    },
     /// Channel receiver for [`ResIds`] produced by stations was closed.
     ///
     /// Should be impossible to hit.
@@ -66,6 +186,16 @@ pub enum Error<E> {
         /// Underlying IO error.
         error: std::io::Error,
     },
+    /// A station's progress or state was already accessed when it was needed.
+    ///
+    /// This indicates concurrent access to the same station, which is usually
+    /// a bug.
+    StationBorrowConflict {
+        /// ID of the station.
+        station_id: StationId,
+        /// Underlying borrow failure.
+        borrow_fail: BorrowFail,
+    },
     /// Failed to create station directory.
     StationDirCreate {
         /// The directory that was attempted to be created.
@@ -81,6 +211,21 @@ pub enum Error<E> {
         /// The train resources.
         train_resources: TrainResources<E>,
     },
+    /// Adding an edge to a station instantiated from a template would cause a
+    /// cycle.
+    StationTemplateEdgeWouldCycle {
+        /// Prefix of the template's generated station IDs.
+        id_prefix: String,
+        /// Underlying graph error.
+        error: WouldCycle<Edge>,
+    },
+    /// Failed to instantiate stations from a template.
+    StationTemplateInstantiate {
+        /// Prefix of the template's generated station IDs.
+        id_prefix: String,
+        /// Underlying station ID format error.
+        error: StationIdInvalidFmt<'static>,
+    },
     /// Failed to create target directory.
     TargetDirCreate {
         /// The directory that was attempted to be created.
@@ -104,6 +249,229 @@ pub enum Error<E> {
         /// File or directory name searched for.
         file_name: PathBuf,
     },
+    /// Failed to read the audit log.
+    AuditLogRead {
+        /// Path to the audit log.
+        path: PathBuf,
+        /// Underlying IO error.
+        error: std::io::Error,
+    },
+    /// Failed to deserialize an entry in the audit log.
+    AuditEntryDeserialize {
+        /// Path to the audit log.
+        path: PathBuf,
+        /// Line number of the entry, starting from 1.
+        line_number: usize,
+        /// Underlying deserialization error.
+        error: serde_json::error::Error,
+    },
+    /// Failed to read the snapshot of train resources.
+    ResourceSnapshotRead {
+        /// Path to the resource snapshot.
+        path: PathBuf,
+        /// Underlying IO error.
+        error: std::io::Error,
+    },
+    /// Failed to deserialize the snapshot of train resources.
+    ResourceSnapshotDeserialize {
+        /// Path to the resource snapshot.
+        path: PathBuf,
+        /// Key of the resource that failed to be deserialized.
+        ///
+        /// This is `None` if the snapshot itself is not valid.
+        key: Option<&'static str>,
+        /// Underlying deserialization error.
+        error: serde_json::error::Error,
+    },
+    /// Failed to serialize a resource into the snapshot of train resources.
+    ResourceSnapshotSerialize {
+        /// Key of the resource that failed to be serialized.
+        key: &'static str,
+        /// Underlying serialization error.
+        error: serde_json::error::Error,
+    },
+    /// Failed to write the snapshot of train resources.
+    ResourceSnapshotWrite {
+        /// Path to the resource snapshot.
+        path: PathBuf,
+        /// Underlying IO error.
+        error: std::io::Error,
+    },
+    /// Stations in a merged [`DestinationBuilder`] use IDs that are already
+    /// used in the builder it is merged into.
+    ///
+    /// [`DestinationBuilder`]: crate::DestinationBuilder
+    DestinationMergeStationIdCollision {
+        /// IDs used by stations in both builders.
+        station_ids: Vec<StationId>,
+    },
+    /// A resource initializer registered on the train failed.
+    ResourceInit {
+        /// Name of the resource initializer.
+        name: String,
+        /// Error returned by the initializer.
+        error: E,
+    },
+    /// A station whose operations are replaced is not in the destination.
+    StationOpReplaceNotFound {
+        /// ID of the station whose operations are replaced.
+        station_id: StationId,
+    },
+    /// Two stations that may be visited concurrently access the same
+    /// resource, and at least one of them accesses it mutably.
+    ///
+    /// This is only returned when the [`ResourceConflictPolicy`] is
+    /// [`ResourceConflictPolicy::Error`].
+    ///
+    /// [`ResourceConflictPolicy`]: crate::ResourceConflictPolicy
+    /// [`ResourceConflictPolicy::Error`]: crate::ResourceConflictPolicy::Error
+    StationResourceConflict {
+        /// ID of the station that was added first.
+        station_a: StationId,
+        /// ID of the station that was added second.
+        station_b: StationId,
+    },
+    /// Failed to copy an artifact registered by a station.
+    ArtifactCopy {
+        /// ID of the station that registered the artifact.
+        station_id: StationId,
+        /// Path to the artifact.
+        path: PathBuf,
+        /// Underlying IO error.
+        error: std::io::Error,
+    },
+    /// Failed to write the index of collected artifacts.
+    ArtifactIndexWrite {
+        /// Path to the artifact index.
+        path: PathBuf,
+        /// Underlying IO error.
+        error: std::io::Error,
+    },
+    /// No station is being visited and none can be queued, yet some stations
+    /// have not been visited.
+    ///
+    /// This indicates a bug in `OpStatus` transitions, or contention on a
+    /// station's progress.
+    StationsStalled {
+        /// IDs of the stations that have not been visited, and their
+        /// `OpStatus`es, or `None` if a station's progress was already
+        /// accessed.
+        stations: Vec<(StationId, Option<OpStatus>)>,
+    },
+}
+
+/// String form of each error code, indexed by [`Error::code_number`] minus
+/// one.
+const ERROR_CODES: [&str; 42] = [
+    "E001", "E002", "E003", "E004", "E005", "E006", "E007", "E008", "E009", "E010", "E011", "E012",
+    "E013", "E014", "E015", "E016", "E017", "E018", "E019", "E020", "E021", "E022", "E023", "E024",
+    "E025", "E026", "E027", "E028", "E029", "E030", "E031", "E032", "E033", "E034", "E035", "E036",
+    "E037", "E038", "E039", "E040", "E041", "E042",
+];
+
+impl<E> Error<E> {
+    /// Returns the stable numeric code of this error.
+    ///
+    /// Codes are never reused, so they may be matched on by tools that read
+    /// `choochoo`'s output.
+    pub fn code_number(&self) -> u16 {
+        match self {
+            Self::MultiProgressTaskJoin(..) => 1,
+            Self::MultiProgressJoin(..) => 2,
+            Self::DeclEdgeStationNotFound { .. } => 3,
+            Self::DeclEdgeWouldCycle { .. } => 4,
+            Self::DeclStationIdDuplicate { .. } => 5,
+            Self::DeclStationOpNotFound { .. } => 6,
+            Self::CacheDirCreate { .. } => 7,
+            Self::HistoryDirCreate { .. } => 8,
+            Self::HistoryRead { .. } => 9,
+            Self::HistoryRemove { .. } => 10,
+            Self::ProfileDirCreate { .. } => 11,
+            Self::ProfileLocked { .. } => 12,
+            Self::ProfileLockAccess { .. } => 13,
+            Self::ProfileHistoryDirCreate { .. } => 14,
+            Self::ResIdDeserialize { .. } => 15,
+            Self::ResIdPartiallyWritten { .. } => 16,
+            Self::ResIdRead { .. } => 17,
+            Self::ResIdsChannelClosed { .. } => 18,
+            Self::ResIdSerialize { .. } => 19,
+            Self::ResIdWrite { .. } => 20,
+            Self::StationBorrowConflict { .. } => 21,
+            Self::StationDirCreate { .. } => 22,
+            Self::StationSetup { .. } => 23,
+            Self::StationTemplateEdgeWouldCycle { .. } => 24,
+            Self::StationTemplateInstantiate { .. } => 25,
+            Self::TargetDirCreate { .. } => 26,
+            Self::WorkingDirRead(..) => 27,
+            Self::WorkspaceDirCreate { .. } => 28,
+            Self::WorkspaceFileNotFound { .. } => 29,
+            Self::AuditLogRead { .. } => 30,
+            Self::AuditEntryDeserialize { .. } => 31,
+            Self::ResourceSnapshotRead { .. } => 32,
+            Self::ResourceSnapshotDeserialize { .. } => 33,
+            Self::ResourceSnapshotSerialize { .. } => 34,
+            Self::ResourceSnapshotWrite { .. } => 35,
+            Self::DestinationMergeStationIdCollision { .. } => 36,
+            Self::ResourceInit { .. } => 37,
+            Self::StationOpReplaceNotFound { .. } => 38,
+            Self::StationResourceConflict { .. } => 39,
+            Self::ArtifactCopy { .. } => 40,
+            Self::ArtifactIndexWrite { .. } => 41,
+            Self::StationsStalled { .. } => 42,
+        }
+    }
+
+    /// Returns the stable code of this error, e.g. `"E001"`.
+    pub fn code(&self) -> &'static str {
+        ERROR_CODES[usize::from(self.code_number()) - 1]
+    }
+}
+
+impl<'a, E> AsDiagnostic<'a> for Error<E>
+where
+    E: fmt::Debug + 'static,
+{
+    type Files = srcerr::codespan::Files<Cow<'a, str>>;
+
+    fn as_diagnostic(
+        &self,
+        _files: &Self::Files,
+    ) -> Diagnostic<<Self::Files as Files<'a>>::FileId> {
+        let mut notes = Vec::new();
+        let mut source = std::error::Error::source(self);
+        while let Some(error) = source {
+            notes.push(format!("caused by: {}", error));
+            source = error.source();
+        }
+        if let Self::StationSetup { .. } = self {
+            notes.push(String::from(
+                "note: details of each station's failure are recorded in the station errors",
+            ));
+        }
+        if let Self::ResourceInit { error, .. } = self {
+            notes.push(format!("caused by: {:?}", error));
+        }
+        if let Self::DestinationMergeStationIdCollision { .. } = self {
+            notes.push(String::from(
+                "help: give each builder's stations a distinct namespace, e.g. `app/upload`",
+            ));
+        }
+        if let Self::StationsStalled { .. } = self {
+            notes.push(String::from(
+                "note: this is likely a bug in `choochoo`, please report it with the stations' dependencies",
+            ));
+        }
+        if let Self::StationResourceConflict { .. } = self {
+            notes.push(String::from(
+                "help: add an edge between the stations, or use `ResourceConflictPolicy::Serialize`",
+            ));
+        }
+
+        Diagnostic::error()
+            .with_code(self.code())
+            .with_message(self.to_string())
+            .with_notes(notes)
+    }
 }
 
 impl<E> fmt::Display for Error<E>
@@ -116,16 +484,73 @@ where
                 write!(f, "Failed to join the multi-progress bar task.")
             }
             Self::MultiProgressJoin(_) => write!(f, "Failed to join the multi-progress bar."),
+            Self::DeclEdgeStationNotFound { station_id } => write!(
+                f,
+                "Edge references station `{station_id}` which is not declared."
+            ),
+            Self::DeclEdgeWouldCycle {
+                station_from,
+                station_to,
+                ..
+            } => write!(
+                f,
+                "Edge from station `{station_from}` to `{station_to}` would cause a cycle."
+            ),
+            Self::DeclStationIdDuplicate { station_id } => {
+                write!(f, "Station `{station_id}` is declared multiple times.")
+            }
+            Self::DeclStationOpNotFound {
+                station_id,
+                op_name,
+            } => write!(
+                f,
+                "Station `{station_id}` references operation `{op_name}` which is not registered."
+            ),
+            Self::CacheDirCreate { cache_dir, .. } => write!(
+                f,
+                "Failed to create cache directory: `{}`.",
+                cache_dir.display()
+            ),
             Self::HistoryDirCreate { history_dir, .. } => write!(
                 f,
                 "Failed to create history directory: `{}`.",
                 history_dir.display()
             ),
+            Self::HistoryRead {
+                profile_history_dir,
+                ..
+            } => write!(
+                f,
+                "Failed to read profile history directory: `{}`.",
+                profile_history_dir.display()
+            ),
+            Self::HistoryRemove { path, .. } => write!(
+                f,
+                "Failed to remove profile history entry: `{}`.",
+                path.display()
+            ),
             Self::ProfileDirCreate { profile_dir, .. } => write!(
                 f,
                 "Failed to create profile directory: `{}`.",
                 profile_dir.display()
             ),
+            Self::ProfileLocked {
+                profile_dir,
+                lock_path,
+                pid,
+                host,
+            } => write!(
+                f,
+                "Profile directory `{}` is locked by process {pid} on host `{host}`. \
+                If that process is no longer running, remove `{}`.",
+                profile_dir.display(),
+                lock_path.display()
+            ),
+            Self::ProfileLockAccess { lock_path, .. } => write!(
+                f,
+                "Failed to access profile lock file: `{}`.",
+                lock_path.display()
+            ),
             Self::ProfileHistoryDirCreate {
                 profile_history_dir,
                 ..
@@ -134,6 +559,23 @@ where
                 "Failed to create profile history directory: `{}`.",
                 profile_history_dir.display()
             ),
+            Self::ResIdDeserialize { station_id, .. } => write!(
+                f,
+                "Failed to deserialize resource ID persisted by station {station_id}."
+            ),
+            Self::ResIdPartiallyWritten {
+                station_id,
+                res_id_path,
+            } => write!(
+                f,
+                "Resource ID file `{}` for station {station_id} was partially written by a previous execution. \
+                Check whether the resource exists, then remove the file.",
+                res_id_path.display()
+            ),
+            Self::ResIdRead { station_id, .. } => write!(
+                f,
+                "Failed to read `ResIds` persisted by station {station_id}."
+            ),
             Self::ResIdsChannelClosed { station_id, .. } => write!(
                 f,
                 "Channel receiver for `ResIds` produced by stations was closed while sending resource IDs for {station_id}"
@@ -146,12 +588,27 @@ where
                 f,
                 "Failed to write `ResIds` produced by station {station_id}."
             ),
+            Self::StationBorrowConflict {
+                station_id,
+                borrow_fail,
+            } => write!(
+                f,
+                "Station {station_id} was already accessed when it was needed: {borrow_fail:?}."
+            ),
             Self::StationDirCreate { station_dir, .. } => write!(
                 f,
                 "Failed to create station directory: `{}`.",
                 station_dir.display()
             ),
             Self::StationSetup { .. } => write!(f, "Station setup failed"),
+            Self::StationTemplateEdgeWouldCycle { id_prefix, .. } => write!(
+                f,
+                "Adding an edge to stations instantiated from the `{id_prefix}` template would cause a cycle."
+            ),
+            Self::StationTemplateInstantiate { id_prefix, .. } => write!(
+                f,
+                "Failed to instantiate stations from the `{id_prefix}` template."
+            ),
             Self::TargetDirCreate { target_dir, .. } => write!(
                 f,
                 "Failed to create target directory: `{}`.",
@@ -175,6 +632,83 @@ where
                 file_name = file_name.display(),
                 working_dir = working_dir.display(),
             ),
+            Self::AuditLogRead { path, .. } => {
+                write!(f, "Failed to read audit log: `{}`.", path.display())
+            }
+            Self::AuditEntryDeserialize {
+                path, line_number, ..
+            } => write!(
+                f,
+                "Failed to deserialize audit log entry at `{}:{line_number}`.",
+                path.display()
+            ),
+            Self::ResourceSnapshotRead { path, .. } => {
+                write!(f, "Failed to read resource snapshot: `{}`.", path.display())
+            }
+            Self::ResourceSnapshotDeserialize { path, key, .. } => match key {
+                Some(key) => write!(
+                    f,
+                    "Failed to deserialize resource `{key}` from resource snapshot: `{}`.",
+                    path.display()
+                ),
+                None => write!(
+                    f,
+                    "Failed to deserialize resource snapshot: `{}`.",
+                    path.display()
+                ),
+            },
+            Self::ResourceSnapshotSerialize { key, .. } => {
+                write!(f, "Failed to serialize resource `{key}` into resource snapshot.")
+            }
+            Self::ResourceSnapshotWrite { path, .. } => {
+                write!(f, "Failed to write resource snapshot: `{}`.", path.display())
+            }
+            Self::DestinationMergeStationIdCollision { station_ids } => {
+                let station_ids = station_ids
+                    .iter()
+                    .map(|station_id| format!("`{station_id}`"))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(f, "Merged stations use IDs that are already used: {station_ids}.")
+            }
+            Self::ResourceInit { name, .. } => {
+                write!(f, "Failed to initialize resources with `{name}`.")
+            }
+            Self::StationOpReplaceNotFound { station_id } => write!(
+                f,
+                "Cannot replace the operations of station `{station_id}`, as it is not in the destination."
+            ),
+            Self::StationResourceConflict {
+                station_a,
+                station_b,
+            } => write!(
+                f,
+                "Stations `{station_a}` and `{station_b}` may run concurrently, but one mutably borrows a resource that the other borrows."
+            ),
+            Self::ArtifactCopy {
+                station_id, path, ..
+            } => write!(
+                f,
+                "Failed to copy artifact of station `{station_id}`: `{}`.",
+                path.display()
+            ),
+            Self::ArtifactIndexWrite { path, .. } => {
+                write!(f, "Failed to write artifact index: `{}`.", path.display())
+            }
+            Self::StationsStalled { stations } => {
+                let stations = stations
+                    .iter()
+                    .map(|(station_id, op_status)| match op_status {
+                        Some(op_status) => format!("`{station_id}` ({op_status:?})"),
+                        None => format!("`{station_id}` (progress already accessed)"),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(
+                    f,
+                    "No station is being visited and none can be queued, but stations remain: {stations}."
+                )
+            }
         }
     }
 }
@@ -187,18 +721,46 @@ where
         match self {
             Self::MultiProgressTaskJoin(error) => Some(error),
             Self::MultiProgressJoin(error) => Some(error),
+            Self::DeclEdgeStationNotFound { .. } => None,
+            Self::DeclEdgeWouldCycle { error, .. } => Some(error),
+            Self::DeclStationIdDuplicate { .. } => None,
+            Self::DeclStationOpNotFound { .. } => None,
+            Self::CacheDirCreate { error, .. } => Some(error),
             Self::HistoryDirCreate { error, .. } => Some(error),
+            Self::HistoryRead { error, .. } => Some(error),
+            Self::HistoryRemove { error, .. } => Some(error),
             Self::ProfileDirCreate { error, .. } => Some(error),
+            Self::ProfileLocked { .. } => None,
+            Self::ProfileLockAccess { error, .. } => Some(error),
             Self::ProfileHistoryDirCreate { error, .. } => Some(error),
+            Self::ResIdDeserialize { error, .. } => Some(error),
+            Self::ResIdPartiallyWritten { .. } => None,
+            Self::ResIdRead { error, .. } => Some(error),
             Self::ResIdsChannelClosed { error, .. } => Some(error),
             Self::ResIdSerialize { error, .. } => Some(error),
             Self::ResIdWrite { error, .. } => Some(error),
+            Self::StationBorrowConflict { .. } => None,
             Self::StationDirCreate { error, .. } => Some(error),
             Self::StationSetup { .. } => None,
+            Self::StationTemplateEdgeWouldCycle { error, .. } => Some(error),
+            Self::StationTemplateInstantiate { error, .. } => Some(error),
             Self::TargetDirCreate { error, .. } => Some(error),
             Self::WorkingDirRead(error) => Some(error),
             Self::WorkspaceDirCreate { error, .. } => Some(error),
             Self::WorkspaceFileNotFound { .. } => None,
+            Self::AuditLogRead { error, .. } => Some(error),
+            Self::AuditEntryDeserialize { error, .. } => Some(error),
+            Self::ResourceSnapshotRead { error, .. } => Some(error),
+            Self::ResourceSnapshotDeserialize { error, .. } => Some(error),
+            Self::ResourceSnapshotSerialize { error, .. } => Some(error),
+            Self::ResourceSnapshotWrite { error, .. } => Some(error),
+            Self::DestinationMergeStationIdCollision { .. } => None,
+            Self::ResourceInit { .. } => None,
+            Self::StationOpReplaceNotFound { .. } => None,
+            Self::StationResourceConflict { .. } => None,
+            Self::ArtifactCopy { error, .. } => Some(error),
+            Self::ArtifactIndexWrite { error, .. } => Some(error),
+            Self::StationsStalled { .. } => None,
         }
     }
 }
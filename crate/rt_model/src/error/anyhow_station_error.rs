@@ -0,0 +1,95 @@
+use std::{borrow::Cow, fmt, ops::Deref};
+
+use choochoo_cfg_model::{
+    rt::{StationFnTaskError, StationResourceError},
+    srcerr::{
+        self,
+        codespan_reporting::{diagnostic::Diagnostic, files::Files},
+    },
+};
+
+use crate::error::{AsDiagnostic, StationSpecError};
+
+/// Station error type that wraps an [`anyhow::Error`].
+///
+/// This lets stations return errors without designing an error enum with
+/// source spans. The diagnostic shows the error's message, and each error in
+/// its chain as a note.
+#[derive(Debug)]
+pub struct AnyhowStationError(anyhow::Error);
+
+impl AnyhowStationError {
+    /// Returns a new `AnyhowStationError`.
+    pub fn new(error: anyhow::Error) -> Self {
+        Self(error)
+    }
+
+    /// Returns the underlying `anyhow::Error`.
+    pub fn into_inner(self) -> anyhow::Error {
+        self.0
+    }
+}
+
+impl Deref for AnyhowStationError {
+    type Target = anyhow::Error;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<anyhow::Error> for AnyhowStationError {
+    fn from(error: anyhow::Error) -> Self {
+        Self(error)
+    }
+}
+
+impl From<StationSpecError> for AnyhowStationError {
+    fn from(error: StationSpecError) -> Self {
+        Self(anyhow::Error::new(error))
+    }
+}
+
+impl From<StationFnTaskError> for AnyhowStationError {
+    fn from(error: StationFnTaskError) -> Self {
+        Self(anyhow::Error::new(error))
+    }
+}
+
+impl From<StationResourceError> for AnyhowStationError {
+    fn from(error: StationResourceError) -> Self {
+        Self(anyhow::Error::new(error))
+    }
+}
+
+impl<'a> AsDiagnostic<'a> for AnyhowStationError {
+    type Files = srcerr::codespan::Files<Cow<'a, str>>;
+
+    fn as_diagnostic(
+        &self,
+        _files: &Self::Files,
+    ) -> Diagnostic<<Self::Files as Files<'a>>::FileId> {
+        let notes = self
+            .0
+            .chain()
+            .skip(1)
+            .map(|error| format!("caused by: {}", error))
+            .collect::<Vec<String>>();
+
+        Diagnostic::error()
+            .with_message(self.0.to_string())
+            .with_notes(notes)
+    }
+}
+
+impl fmt::Display for AnyhowStationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for AnyhowStationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.0.source()
+    }
+}
@@ -0,0 +1,95 @@
+use std::{fmt, io, path::PathBuf};
+
+/// Error reading or writing the [`ProfileStore`].
+///
+/// [`ProfileStore`]: crate::ProfileStore
+#[derive(Debug)]
+pub enum ProfileStoreError {
+    /// Failed to read the store file.
+    Read {
+        /// Path to the store file.
+        path: PathBuf,
+        /// The underlying error.
+        error: io::Error,
+    },
+    /// Failed to deserialize the store file.
+    Deserialize {
+        /// Path to the store file.
+        path: PathBuf,
+        /// The underlying error.
+        error: serde_json::Error,
+    },
+    /// Failed to write the store file.
+    Write {
+        /// Path to the store file.
+        path: PathBuf,
+        /// The underlying error.
+        error: io::Error,
+    },
+    /// Failed to serialize a value to store.
+    ValueSerialize {
+        /// Key of the value.
+        key: String,
+        /// The underlying error.
+        error: serde_json::Error,
+    },
+    /// Failed to deserialize a stored value as the requested type.
+    ValueDeserialize {
+        /// Key of the value.
+        key: String,
+        /// The underlying error.
+        error: serde_json::Error,
+    },
+    /// The value was changed since the version that the caller read.
+    VersionConflict {
+        /// Key of the value.
+        key: String,
+        /// Version of the value that the caller read.
+        version_expected: u64,
+        /// Version of the value in the store.
+        version_actual: u64,
+    },
+}
+
+impl fmt::Display for ProfileStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Read { path, .. } => {
+                write!(f, "Failed to read profile store `{}`.", path.display())
+            }
+            Self::Deserialize { path, .. } => {
+                write!(f, "Failed to deserialize profile store `{}`.", path.display())
+            }
+            Self::Write { path, .. } => {
+                write!(f, "Failed to write profile store `{}`.", path.display())
+            }
+            Self::ValueSerialize { key, .. } => {
+                write!(f, "Failed to serialize value for profile store key `{key}`.")
+            }
+            Self::ValueDeserialize { key, .. } => {
+                write!(f, "Failed to deserialize value of profile store key `{key}`.")
+            }
+            Self::VersionConflict {
+                key,
+                version_expected,
+                version_actual,
+            } => write!(
+                f,
+                "Profile store key `{key}` was changed: expected version {version_expected}, but it is at version {version_actual}."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ProfileStoreError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Read { error, .. } => Some(error),
+            Self::Deserialize { error, .. } => Some(error),
+            Self::Write { error, .. } => Some(error),
+            Self::ValueSerialize { error, .. } => Some(error),
+            Self::ValueDeserialize { error, .. } => Some(error),
+            Self::VersionConflict { .. } => None,
+        }
+    }
+}
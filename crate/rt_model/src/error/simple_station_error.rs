@@ -0,0 +1,128 @@
+use std::{borrow::Cow, fmt};
+
+use choochoo_cfg_model::{
+    rt::{StationFnTaskError, StationResourceError},
+    srcerr::{
+        self,
+        codespan_reporting::{
+            diagnostic::{Diagnostic, Severity},
+            files::Files,
+        },
+    },
+};
+
+use crate::error::{AsDiagnostic, StationSpecError};
+
+/// Station error type with a message, optional source, and severity.
+///
+/// This is for stations that do not need source spans in their diagnostics.
+/// [`StationSpecError`]s are converted with [`Severity::Bug`], as they
+/// indicate a bug in the station specification.
+#[derive(Debug)]
+pub struct SimpleStationError {
+    /// Description of what failed.
+    message: String,
+    /// The underlying error.
+    source: Option<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    /// Severity of the error.
+    severity: Severity,
+}
+
+impl SimpleStationError {
+    /// Returns a new `SimpleStationError` with [`Severity::Error`].
+    ///
+    /// # Parameters
+    ///
+    /// * `message`: Description of what failed.
+    pub fn new<M>(message: M) -> Self
+    where
+        M: Into<String>,
+    {
+        Self {
+            message: message.into(),
+            source: None,
+            severity: Severity::Error,
+        }
+    }
+
+    /// Sets the underlying error.
+    #[must_use]
+    pub fn with_source<Err>(mut self, source: Err) -> Self
+    where
+        Err: std::error::Error + Send + Sync + 'static,
+    {
+        self.source = Some(Box::new(source));
+        self
+    }
+
+    /// Sets the severity of the error.
+    #[must_use]
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Returns the description of what failed.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the severity of the error.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+}
+
+impl From<StationSpecError> for SimpleStationError {
+    fn from(error: StationSpecError) -> Self {
+        Self::new(error.to_string())
+            .with_severity(Severity::Bug)
+            .with_source(error)
+    }
+}
+
+impl From<StationFnTaskError> for SimpleStationError {
+    fn from(error: StationFnTaskError) -> Self {
+        Self::new(error.to_string()).with_source(error)
+    }
+}
+
+impl From<StationResourceError> for SimpleStationError {
+    fn from(error: StationResourceError) -> Self {
+        Self::new(error.to_string()).with_source(error)
+    }
+}
+
+impl<'a> AsDiagnostic<'a> for SimpleStationError {
+    type Files = srcerr::codespan::Files<Cow<'a, str>>;
+
+    fn as_diagnostic(
+        &self,
+        _files: &Self::Files,
+    ) -> Diagnostic<<Self::Files as Files<'a>>::FileId> {
+        let mut notes = Vec::new();
+        let mut source = std::error::Error::source(self);
+        while let Some(error) = source {
+            notes.push(format!("caused by: {}", error));
+            source = error.source();
+        }
+
+        Diagnostic::new(self.severity)
+            .with_message(self.message.clone())
+            .with_notes(notes)
+    }
+}
+
+impl fmt::Display for SimpleStationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.message.fmt(f)
+    }
+}
+
+impl std::error::Error for SimpleStationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|source| source as &(dyn std::error::Error + 'static))
+    }
+}
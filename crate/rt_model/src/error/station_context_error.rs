@@ -0,0 +1,103 @@
+use std::{borrow::Cow, fmt};
+
+use choochoo_cfg_model::srcerr::{
+    self,
+    codespan_reporting::{diagnostic::Diagnostic, files::Files},
+};
+
+use crate::error::AsDiagnostic;
+
+/// Lower level error, wrapped with what the station was doing when it
+/// occurred.
+///
+/// This is usually created through [`StationErrorContext`], and lets station
+/// code report failures without declaring an error variant for each one.
+///
+/// [`StationErrorContext`]: crate::error::StationErrorContext
+#[derive(Debug)]
+pub struct StationContextError {
+    /// What the station was doing, innermost first.
+    contexts: Vec<String>,
+    /// The underlying error.
+    error: Box<dyn std::error::Error + Send + Sync + 'static>,
+}
+
+impl StationContextError {
+    /// Returns a new `StationContextError`.
+    ///
+    /// # Parameters
+    ///
+    /// * `context`: What the station was doing, e.g. `"uploading app.zip"`.
+    /// * `error`: The underlying error.
+    pub fn new<C>(context: C, error: Box<dyn std::error::Error + Send + Sync + 'static>) -> Self
+    where
+        C: Into<String>,
+    {
+        Self {
+            contexts: vec![context.into()],
+            error,
+        }
+    }
+
+    /// Adds what the station was doing around the existing context.
+    #[must_use]
+    pub fn with_context<C>(mut self, context: C) -> Self
+    where
+        C: Into<String>,
+    {
+        self.contexts.push(context.into());
+        self
+    }
+
+    /// Returns what the station was doing, innermost first.
+    pub fn contexts(&self) -> &[String] {
+        &self.contexts
+    }
+
+    /// Returns the underlying error.
+    pub fn error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+        &*self.error
+    }
+}
+
+impl<'a> AsDiagnostic<'a> for StationContextError {
+    type Files = srcerr::codespan::Files<Cow<'a, str>>;
+
+    fn as_diagnostic(
+        &self,
+        _files: &Self::Files,
+    ) -> Diagnostic<<Self::Files as Files<'a>>::FileId> {
+        let mut notes = self
+            .contexts
+            .iter()
+            .rev()
+            .skip(1)
+            .map(|context| format!("while {}", context))
+            .collect::<Vec<String>>();
+        let mut source = Some(self.error() as &(dyn std::error::Error + 'static));
+        while let Some(error) = source {
+            notes.push(format!("caused by: {}", error));
+            source = error.source();
+        }
+
+        Diagnostic::error()
+            .with_message(self.to_string())
+            .with_notes(notes)
+    }
+}
+
+impl fmt::Display for StationContextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // `contexts` always has at least one entry.
+        if let Some(context) = self.contexts.last() {
+            write!(f, "Failed while {}.", context)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for StationContextError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&*self.error)
+    }
+}
@@ -0,0 +1,44 @@
+use crate::error::StationContextError;
+
+/// Adds what a station was doing to errors, for [`Result`]s in station
+/// functions.
+///
+/// The context is rendered as notes in the error's diagnostic. Adding context
+/// to a [`StationContextError`] adds to its existing context, instead of
+/// wrapping it again.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let file = File::open(&app_zip_path)
+///     .await
+///     .with_station_context("opening app.zip")?;
+/// ```
+pub trait StationErrorContext<T> {
+    /// Wraps the error with what the station was doing when it occurred.
+    ///
+    /// # Parameters
+    ///
+    /// * `context`: What the station was doing, e.g. `"uploading app.zip"`.
+    fn with_station_context<C>(self, context: C) -> Result<T, StationContextError>
+    where
+        C: Into<String>;
+}
+
+impl<T, Err> StationErrorContext<T> for Result<T, Err>
+where
+    Err: std::error::Error + Send + Sync + 'static,
+{
+    fn with_station_context<C>(self, context: C) -> Result<T, StationContextError>
+    where
+        C: Into<String>,
+    {
+        self.map_err(|error| {
+            let error: Box<dyn std::error::Error + Send + Sync + 'static> = Box::new(error);
+            match error.downcast::<StationContextError>() {
+                Ok(station_context_error) => (*station_context_error).with_context(context),
+                Err(error) => StationContextError::new(context, error),
+            }
+        })
+    }
+}
@@ -1,37 +1,230 @@
-use std::fmt;
+use std::{fmt, panic::Location};
 
 use choochoo_cfg_model::StationId;
+use choochoo_resource::Profile;
 
 /// There is a bug with the station specification.
 #[derive(Clone, Debug, PartialEq)]
 pub enum StationSpecError {
+    /// Failed to append the record of the station's work or clean function
+    /// being invoked to the audit log.
+    AuditLogWriteFail {
+        /// Unique identifier of the station.
+        id: StationId,
+        /// Human readable name of the station.
+        name: String,
+        /// Description of the underlying error.
+        detail: String,
+        /// Source code location that the station was defined at.
+        location: &'static Location<'static>,
+    },
+    /// A station in another profile that this station depends on has not been
+    /// successfully created.
+    ExternalDependencyUnsatisfied {
+        /// Unique identifier of the station.
+        id: StationId,
+        /// Human readable name of the station.
+        name: String,
+        /// Profile of the destination that contains the dependency.
+        dependency_profile: Profile,
+        /// Unique identifier of the dependency station.
+        dependency_station_id: StationId,
+        /// Description of why the dependency is not satisfied.
+        detail: String,
+        /// Source code location that the station was defined at.
+        location: &'static Location<'static>,
+    },
+    /// A file that the station declares as an input has not been published by
+    /// the station it is declared on.
+    FileInputUnpublished {
+        /// Unique identifier of the station.
+        id: StationId,
+        /// Human readable name of the station.
+        name: String,
+        /// Unique identifier of the station the file is declared on.
+        input_station_id: StationId,
+        /// Name the file is declared under.
+        input_name: String,
+        /// Source code location that the station was defined at.
+        location: &'static Location<'static>,
+    },
+    /// Failed to compute the digest of the station's declared input files.
+    InputsDigestFail {
+        /// Unique identifier of the station.
+        id: StationId,
+        /// Human readable name of the station.
+        name: String,
+        /// Input pattern or path that could not be read.
+        input: String,
+        /// Description of the underlying error.
+        detail: String,
+        /// Source code location that the station was defined at.
+        location: &'static Location<'static>,
+    },
+    /// Failed to record the digest of the station's input files after its
+    /// work succeeded, or to remove it after the station was cleaned.
+    InputsDigestRecordFail {
+        /// Unique identifier of the station.
+        id: StationId,
+        /// Human readable name of the station.
+        name: String,
+        /// Description of the underlying error.
+        detail: String,
+        /// Source code location that the station was defined at.
+        location: &'static Location<'static>,
+    },
+    /// One of the station's functions panicked.
+    Panicked {
+        /// Unique identifier of the station.
+        id: StationId,
+        /// Human readable name of the station.
+        name: String,
+        /// Message that the function panicked with.
+        message: String,
+        /// Source code location that the station was defined at.
+        location: &'static Location<'static>,
+    },
+    /// Failed to remove the station's `tmp/` directory after it was visited.
+    TmpDirCleanFail {
+        /// Unique identifier of the station.
+        id: StationId,
+        /// Human readable name of the station.
+        name: String,
+        /// Description of the underlying error.
+        detail: String,
+        /// Source code location that the station was defined at.
+        location: &'static Location<'static>,
+    },
+    /// Failed to record the station's spec version after it was visited, or
+    /// to remove it after the station was cleaned.
+    VersionRecordFail {
+        /// Unique identifier of the station.
+        id: StationId,
+        /// Human readable name of the station.
+        name: String,
+        /// Description of the underlying error.
+        detail: String,
+        /// Source code location that the station was defined at.
+        location: &'static Location<'static>,
+    },
     /// The `check_fn` provided in the station spec functions returned
-    /// [`CheckStatus::WorkRequired`] after the work was executed.
+    /// [`CheckStatus::WorkRequired`] after the work was executed successfully.
+    ///
+    /// This means the work function does not update what the check function
+    /// reads, so the work would be run again on every visit.
     ///
     /// [`CheckStatus::WorkRequired`]: choochoo_cfg_model::CheckStatus::WorkRequired
-    WorkRequiredAfterVisit {
+    WorkNotIdempotent {
         /// Unique identifier of the station.
         id: StationId,
         /// Human readable name of the station.
         name: String,
+        /// Source code location that the station was defined at.
+        location: &'static Location<'static>,
     },
 }
 
+impl StationSpecError {
+    /// Returns the source code location that the station was defined at.
+    pub fn location(&self) -> &'static Location<'static> {
+        match self {
+            Self::AuditLogWriteFail { location, .. }
+            | Self::ExternalDependencyUnsatisfied { location, .. }
+            | Self::FileInputUnpublished { location, .. }
+            | Self::InputsDigestFail { location, .. }
+            | Self::InputsDigestRecordFail { location, .. }
+            | Self::Panicked { location, .. }
+            | Self::TmpDirCleanFail { location, .. }
+            | Self::VersionRecordFail { location, .. }
+            | Self::WorkNotIdempotent { location, .. } => location,
+        }
+    }
+}
+
 impl fmt::Display for StationSpecError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Self::WorkRequiredAfterVisit { id, name } => write!(
+            Self::AuditLogWriteFail {
+                id, name, detail, ..
+            } => write!(
                 f,
-                "Station `{id}: {name}`'s check function reported the station still requires work after the work function was run."
+                "Failed to record station `{id}: {name}`'s visit in the audit log: {detail}"
             ),
-        }
+            Self::ExternalDependencyUnsatisfied {
+                id,
+                name,
+                dependency_profile,
+                dependency_station_id,
+                detail,
+                ..
+            } => write!(
+                f,
+                "Station `{id}: {name}` depends on station `{dependency_station_id}` in profile `{dependency_profile}`, which {detail}.",
+                dependency_profile = &**dependency_profile,
+            ),
+            Self::FileInputUnpublished {
+                id,
+                name,
+                input_station_id,
+                input_name,
+                ..
+            } => write!(
+                f,
+                "Station `{id}: {name}` reads file `{input_name}` from station `{input_station_id}`, which has not published it."
+            ),
+            Self::InputsDigestFail {
+                id,
+                name,
+                input,
+                detail,
+                ..
+            } => write!(
+                f,
+                "Failed to compute the digest of station `{id}: {name}`'s input `{input}`: {detail}"
+            ),
+            Self::InputsDigestRecordFail {
+                id, name, detail, ..
+            } => write!(
+                f,
+                "Failed to update the recorded digest of station `{id}: {name}`'s inputs: {detail}"
+            ),
+            Self::Panicked {
+                id, name, message, ..
+            } => write!(f, "Station `{id}: {name}` panicked: {message}"),
+            Self::TmpDirCleanFail {
+                id, name, detail, ..
+            } => write!(
+                f,
+                "Failed to remove the `tmp` directory of station `{id}: {name}`: {detail}"
+            ),
+            Self::VersionRecordFail {
+                id, name, detail, ..
+            } => write!(
+                f,
+                "Failed to update the recorded spec version of station `{id}: {name}`: {detail}"
+            ),
+            Self::WorkNotIdempotent { id, name, .. } => write!(
+                f,
+                "Station `{id}: {name}`'s check function reported the station still requires work after the work function succeeded. Make sure the work function updates what the check function reads."
+            ),
+        }?;
+
+        write!(f, " (defined at {})", self.location())
     }
 }
 
 impl std::error::Error for StationSpecError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            Self::WorkRequiredAfterVisit { .. } => None,
+            Self::AuditLogWriteFail { .. } => None,
+            Self::ExternalDependencyUnsatisfied { .. } => None,
+            Self::FileInputUnpublished { .. } => None,
+            Self::InputsDigestFail { .. } => None,
+            Self::InputsDigestRecordFail { .. } => None,
+            Self::Panicked { .. } => None,
+            Self::TmpDirCleanFail { .. } => None,
+            Self::VersionRecordFail { .. } => None,
+            Self::WorkNotIdempotent { .. } => None,
         }
     }
 }
@@ -0,0 +1,88 @@
+use std::{borrow::Cow, fmt};
+
+use choochoo_cfg_model::srcerr::{
+    self,
+    codespan::{FileId, Span},
+    codespan_reporting::{
+        diagnostic::{Diagnostic, Label},
+        files::Files,
+    },
+};
+use choochoo_resource::WorkspacePathError;
+
+use crate::error::AsDiagnostic;
+
+/// A path from a source file, such as configuration, that resolves outside
+/// the workspace directory.
+///
+/// The diagnostic for this error points at where the path is defined.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WorkspacePathSourceError {
+    /// The path that resolves outside the workspace directory.
+    error: WorkspacePathError,
+    /// ID of the file that the path is defined in.
+    file_id: FileId,
+    /// Byte begin and end indices where the path is defined.
+    path_span: Span,
+}
+
+impl WorkspacePathSourceError {
+    /// Returns a new `WorkspacePathSourceError`.
+    ///
+    /// # Parameters
+    ///
+    /// * `error`: The path that resolves outside the workspace directory.
+    /// * `file_id`: ID of the file that the path is defined in.
+    /// * `path_span`: Byte begin and end indices where the path is defined.
+    pub fn new(error: WorkspacePathError, file_id: FileId, path_span: Span) -> Self {
+        Self {
+            error,
+            file_id,
+            path_span,
+        }
+    }
+
+    /// Returns the path that resolves outside the workspace directory.
+    pub fn error(&self) -> &WorkspacePathError {
+        &self.error
+    }
+
+    /// Returns the ID of the file that the path is defined in.
+    pub fn file_id(&self) -> FileId {
+        self.file_id
+    }
+
+    /// Returns the byte begin and end indices where the path is defined.
+    pub fn path_span(&self) -> Span {
+        self.path_span
+    }
+}
+
+impl<'a> AsDiagnostic<'a> for WorkspacePathSourceError {
+    type Files = srcerr::codespan::Files<Cow<'a, str>>;
+
+    fn as_diagnostic(
+        &self,
+        _files: &Self::Files,
+    ) -> Diagnostic<<Self::Files as Files<'a>>::FileId> {
+        Diagnostic::error()
+            .with_message(self.error.to_string())
+            .with_labels(vec![Label::primary(self.file_id, self.path_span)
+                .with_message("this path resolves outside the workspace directory")])
+            .with_notes(vec![String::from(
+                "help: use a path within the workspace directory, without `..` leaving it",
+            )])
+    }
+}
+
+impl fmt::Display for WorkspacePathSourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl std::error::Error for WorkspacePathSourceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
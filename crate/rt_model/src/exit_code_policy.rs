@@ -0,0 +1,68 @@
+use crate::TrainOutcome;
+
+/// Mapping from a train's [`TrainOutcome`] to a process exit code.
+///
+/// Command line applications that embed a train may use this to exit with
+/// codes that match their own conventions.
+///
+/// Defaults to:
+///
+/// * `0` for [`TrainOutcome::Success`].
+/// * `1` for [`TrainOutcome::Failed`].
+/// * `2` for [`TrainOutcome::DeadlineExceeded`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExitCodePolicy {
+    /// Exit code when all stations reached the destination.
+    success: i32,
+    /// Exit code when a station failed.
+    failed: i32,
+    /// Exit code when a station did not complete before the deadline.
+    deadline_exceeded: i32,
+}
+
+impl ExitCodePolicy {
+    /// Returns a new `ExitCodePolicy` with the default exit codes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the exit code when all stations reached the destination.
+    #[must_use]
+    pub fn with_success(mut self, success: i32) -> Self {
+        self.success = success;
+        self
+    }
+
+    /// Sets the exit code when a station failed.
+    #[must_use]
+    pub fn with_failed(mut self, failed: i32) -> Self {
+        self.failed = failed;
+        self
+    }
+
+    /// Sets the exit code when a station did not complete before the deadline.
+    #[must_use]
+    pub fn with_deadline_exceeded(mut self, deadline_exceeded: i32) -> Self {
+        self.deadline_exceeded = deadline_exceeded;
+        self
+    }
+
+    /// Returns the exit code for the given outcome.
+    pub fn exit_code(&self, train_outcome: TrainOutcome) -> i32 {
+        match train_outcome {
+            TrainOutcome::Success => self.success,
+            TrainOutcome::Failed => self.failed,
+            TrainOutcome::DeadlineExceeded => self.deadline_exceeded,
+        }
+    }
+}
+
+impl Default for ExitCodePolicy {
+    fn default() -> Self {
+        Self {
+            success: 0,
+            failed: 1,
+            deadline_exceeded: 2,
+        }
+    }
+}
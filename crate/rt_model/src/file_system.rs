@@ -0,0 +1,63 @@
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+use futures::future::LocalBoxFuture;
+
+/// File system operations used while a train reaches a destination.
+///
+/// This includes creating the destination's directories, persisting resource
+/// IDs, recording each station's inputs digest and spec version, and cleaning
+/// each station's `tmp/` directory.
+///
+/// The default is [`NativeFileSystem`]. Use [`MemoryFileSystem`] so that
+/// tests and dry runs do not write to disk.
+///
+/// [`MemoryFileSystem`]: crate::MemoryFileSystem
+/// [`NativeFileSystem`]: crate::NativeFileSystem
+pub trait FileSystem: fmt::Debug + Send + Sync {
+    /// Returns whether this reads and writes the native file system.
+    ///
+    /// When this returns `false`, the train does not lock the profile
+    /// directory, record audit entries, snapshot resources, or prune the
+    /// history directory, as those always use the native file system.
+    fn is_native(&self) -> bool;
+
+    /// Returns whether a file or directory exists at the path.
+    fn exists<'f>(&'f self, path: &'f Path) -> LocalBoxFuture<'f, bool>;
+
+    /// Returns whether a directory exists at the path.
+    fn is_dir<'f>(&'f self, path: &'f Path) -> LocalBoxFuture<'f, bool>;
+
+    /// Creates a directory and all of its missing parents.
+    fn create_dir_all<'f>(&'f self, path: &'f Path) -> LocalBoxFuture<'f, io::Result<()>>;
+
+    /// Returns the contents of a file.
+    fn read<'f>(&'f self, path: &'f Path) -> LocalBoxFuture<'f, io::Result<Vec<u8>>>;
+
+    /// Writes the contents to a file, replacing it if it exists.
+    ///
+    /// The contents are flushed to storage before this returns.
+    fn write<'f>(
+        &'f self,
+        path: &'f Path,
+        contents: &'f [u8],
+    ) -> LocalBoxFuture<'f, io::Result<()>>;
+
+    /// Renames a file or directory, replacing the destination if it exists.
+    fn rename<'f>(&'f self, from: &'f Path, to: &'f Path) -> LocalBoxFuture<'f, io::Result<()>>;
+
+    /// Returns the paths of the entries in a directory.
+    fn read_dir<'f>(&'f self, path: &'f Path) -> LocalBoxFuture<'f, io::Result<Vec<PathBuf>>>;
+
+    /// Removes a file.
+    fn remove_file<'f>(&'f self, path: &'f Path) -> LocalBoxFuture<'f, io::Result<()>>;
+
+    /// Removes a directory and all of its contents.
+    fn remove_dir_all<'f>(&'f self, path: &'f Path) -> LocalBoxFuture<'f, io::Result<()>>;
+
+    /// Flushes a directory's entries to storage, so that renames within it
+    /// survive a crash.
+    fn dir_sync<'f>(&'f self, path: &'f Path) -> LocalBoxFuture<'f, io::Result<()>>;
+}
@@ -0,0 +1,88 @@
+use std::fmt;
+
+use choochoo_cfg_model::indexmap::IndexMap;
+use choochoo_resource::Profile;
+
+use crate::{Destination, Error, TrainOutcome, TrainReport};
+
+/// Record of what happened when a fleet drove trains to the same destination
+/// in multiple profiles.
+#[derive(Debug)]
+pub struct FleetReport<E> {
+    /// Destination built for each profile.
+    dests: IndexMap<Profile, Destination<E>>,
+    /// Result of driving the train in each profile.
+    ///
+    /// This contains an error for profiles whose destination failed to be
+    /// built, or whose train failed to start.
+    train_reports: IndexMap<Profile, Result<TrainReport<E>, Error<E>>>,
+}
+
+impl<E> FleetReport<E>
+where
+    E: fmt::Debug + Send + Sync + 'static,
+{
+    /// Returns a new `FleetReport`.
+    ///
+    /// # Parameters
+    ///
+    /// * `dests`: Destination built for each profile.
+    /// * `train_reports`: Result of driving the train in each profile.
+    pub fn new(
+        dests: IndexMap<Profile, Destination<E>>,
+        train_reports: IndexMap<Profile, Result<TrainReport<E>, Error<E>>>,
+    ) -> Self {
+        Self {
+            dests,
+            train_reports,
+        }
+    }
+
+    /// Returns the profiles the fleet drove trains in.
+    pub fn profiles(&self) -> impl Iterator<Item = &Profile> + '_ {
+        self.train_reports.keys()
+    }
+
+    /// Returns the destination built for the profile, if it was built.
+    pub fn dest(&self, profile: &Profile) -> Option<&Destination<E>> {
+        self.dests.get(profile)
+    }
+
+    /// Returns the result of driving the train in the profile.
+    pub fn train_report(&self, profile: &Profile) -> Option<&Result<TrainReport<E>, Error<E>>> {
+        self.train_reports.get(profile)
+    }
+
+    /// Returns the result of driving the train in each profile.
+    pub fn train_reports(&self) -> &IndexMap<Profile, Result<TrainReport<E>, Error<E>>> {
+        &self.train_reports
+    }
+
+    /// Returns the overall result of the fleet's drives.
+    ///
+    /// [`TrainOutcome::DeadlineExceeded`] takes precedence over
+    /// [`TrainOutcome::Failed`]. A profile whose train could not start counts
+    /// as [`TrainOutcome::Failed`].
+    pub async fn outcome(&self) -> TrainOutcome {
+        let mut outcome = TrainOutcome::Success;
+        for train_report in self.train_reports.values() {
+            let train_outcome = match train_report {
+                Ok(train_report) => train_report.outcome().await,
+                Err(_error) => TrainOutcome::Failed,
+            };
+            match train_outcome {
+                TrainOutcome::DeadlineExceeded => return TrainOutcome::DeadlineExceeded,
+                TrainOutcome::Failed => outcome = TrainOutcome::Failed,
+                TrainOutcome::Success => {}
+            }
+        }
+
+        outcome
+    }
+
+    /// Returns whether the train failed to reach its destination in any
+    /// profile.
+    pub async fn is_failed(&self) -> bool {
+        self.outcome().await != TrainOutcome::Success
+    }
+}
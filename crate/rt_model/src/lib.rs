@@ -4,28 +4,94 @@
 //! analogous to build artifacts.
 
 pub use crate::{
+    audit_entry::AuditEntry,
+    audit_query::AuditQuery,
+    checkpoint_group::CheckpointGroup,
     clean_ensure_outcome::{CleanEnsureOutcomeErr, CleanEnsureOutcomeOk},
+    clean_selection::CleanSelection,
     create_ensure_outcome::{CreateEnsureOutcomeErr, CreateEnsureOutcomeOk},
+    critical_path::CriticalPath,
     destination::Destination,
     destination_builder::DestinationBuilder,
+    destination_decl::{DestinationDecl, EdgeDecl, StationDecl},
     destination_dir_calc::DestinationDirCalc,
     destination_dirs::DestinationDirs,
+    destination_lint::DestinationLint,
+    destination_reachability::DestinationReachability,
+    destination_validator::DestinationValidator,
     error::Error,
+    exit_code_policy::ExitCodePolicy,
+    file_system::FileSystem,
+    fleet_report::FleetReport,
+    memory_file_system::MemoryFileSystem,
+    native_file_system::NativeFileSystem,
+    profile_dir_spec::ProfileDirSpec,
+    profile_store::ProfileStore,
+    queue_metrics::QueueMetrics,
+    reconciliation_report::ReconciliationReport,
+    res_id_costs::ResIdCosts,
+    resource_conflict_policy::ResourceConflictPolicy,
+    rollback_outcome::RollbackOutcome,
     station_dirs::StationDirs,
+    station_duration_regression::StationDurationRegression,
+    station_envs::StationEnvs,
+    station_lock::StationLock,
+    station_op_registry::StationOpRegistry,
     station_progresses::StationProgresses,
+    station_reconciliation::StationReconciliation,
+    station_record::StationRecord,
+    station_states::StationStates,
+    train_outcome::TrainOutcome,
+    train_plan::TrainPlan,
     train_report::TrainReport,
+    train_report_diff::TrainReportDiff,
+    train_report_record::TrainReportRecord,
+    verbosity::Verbosity,
     workspace_spec::WorkspaceSpec,
 };
 
 pub mod error;
 
+mod audit_entry;
+mod audit_query;
+mod checkpoint_group;
 mod clean_ensure_outcome;
+mod clean_selection;
 mod create_ensure_outcome;
+mod critical_path;
 mod destination;
 mod destination_builder;
+mod destination_decl;
 mod destination_dir_calc;
 mod destination_dirs;
+mod destination_lint;
+mod destination_reachability;
+mod destination_validator;
+mod exit_code_policy;
+mod file_system;
+mod fleet_report;
+mod memory_file_system;
+mod native_file_system;
+mod profile_dir_spec;
+mod profile_store;
+mod queue_metrics;
+mod reconciliation_report;
+mod res_id_costs;
+mod resource_conflict_policy;
+mod rollback_outcome;
 mod station_dirs;
+mod station_duration_regression;
+mod station_envs;
+mod station_lock;
+mod station_op_registry;
 mod station_progresses;
+mod station_reconciliation;
+mod station_record;
+mod station_states;
+mod train_outcome;
+mod train_plan;
 mod train_report;
+mod train_report_diff;
+mod train_report_record;
+mod verbosity;
 mod workspace_spec;
@@ -0,0 +1,235 @@
+use std::{
+    collections::BTreeMap,
+    io,
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use futures::future::{FutureExt, LocalBoxFuture};
+
+use crate::FileSystem;
+
+/// [`FileSystem`] that holds files in memory.
+///
+/// Nothing is written to disk, so this is useful for tests and dry runs.
+/// Files are discarded when the file system is dropped.
+#[derive(Debug, Default)]
+pub struct MemoryFileSystem {
+    /// Files and directories, keyed by path.
+    entries: Mutex<BTreeMap<PathBuf, MemoryEntry>>,
+}
+
+/// A file or directory in a [`MemoryFileSystem`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum MemoryEntry {
+    /// A directory.
+    Dir,
+    /// A file with the given contents.
+    File(Vec<u8>),
+}
+
+impl MemoryFileSystem {
+    /// Returns a new, empty `MemoryFileSystem`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn entries(&self) -> MutexGuard<'_, BTreeMap<PathBuf, MemoryEntry>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn not_found(path: &Path) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("`{}` does not exist", path.display()),
+        )
+    }
+
+    fn not_a_dir(path: &Path) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::Other,
+            format!("`{}` is not a directory", path.display()),
+        )
+    }
+
+    /// Returns an error if the path's parent is not a directory.
+    fn parent_dir_ensure(entries: &BTreeMap<PathBuf, MemoryEntry>, path: &Path) -> io::Result<()> {
+        match path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            Some(parent) => match entries.get(parent) {
+                Some(MemoryEntry::Dir) => Ok(()),
+                Some(MemoryEntry::File(_)) => Err(Self::not_a_dir(parent)),
+                None => Err(Self::not_found(parent)),
+            },
+            None => Ok(()),
+        }
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn is_native(&self) -> bool {
+        false
+    }
+
+    fn exists<'f>(&'f self, path: &'f Path) -> LocalBoxFuture<'f, bool> {
+        async move { self.entries().contains_key(path) }.boxed_local()
+    }
+
+    fn is_dir<'f>(&'f self, path: &'f Path) -> LocalBoxFuture<'f, bool> {
+        async move { self.entries().get(path) == Some(&MemoryEntry::Dir) }.boxed_local()
+    }
+
+    fn create_dir_all<'f>(&'f self, path: &'f Path) -> LocalBoxFuture<'f, io::Result<()>> {
+        async move {
+            let mut entries = self.entries();
+            let dirs = path
+                .ancestors()
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .collect::<Vec<_>>();
+            if let Some(file_path) = dirs
+                .iter()
+                .find(|dir| matches!(entries.get(**dir), Some(MemoryEntry::File(_))))
+            {
+                return Err(Self::not_a_dir(file_path));
+            }
+            dirs.into_iter().for_each(|dir| {
+                entries.insert(dir.to_path_buf(), MemoryEntry::Dir);
+            });
+
+            Ok(())
+        }
+        .boxed_local()
+    }
+
+    fn read<'f>(&'f self, path: &'f Path) -> LocalBoxFuture<'f, io::Result<Vec<u8>>> {
+        async move {
+            match self.entries().get(path) {
+                Some(MemoryEntry::File(contents)) => Ok(contents.clone()),
+                Some(MemoryEntry::Dir) => Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("`{}` is a directory", path.display()),
+                )),
+                None => Err(Self::not_found(path)),
+            }
+        }
+        .boxed_local()
+    }
+
+    fn write<'f>(
+        &'f self,
+        path: &'f Path,
+        contents: &'f [u8],
+    ) -> LocalBoxFuture<'f, io::Result<()>> {
+        async move {
+            let mut entries = self.entries();
+            Self::parent_dir_ensure(&entries, path)?;
+            if entries.get(path) == Some(&MemoryEntry::Dir) {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("`{}` is a directory", path.display()),
+                ));
+            }
+            entries.insert(path.to_path_buf(), MemoryEntry::File(contents.to_vec()));
+
+            Ok(())
+        }
+        .boxed_local()
+    }
+
+    fn rename<'f>(&'f self, from: &'f Path, to: &'f Path) -> LocalBoxFuture<'f, io::Result<()>> {
+        async move {
+            let mut entries = self.entries();
+            if !entries.contains_key(from) {
+                return Err(Self::not_found(from));
+            }
+            Self::parent_dir_ensure(&entries, to)?;
+            if from == to {
+                return Ok(());
+            }
+
+            // Directories are renamed with their contents.
+            let paths_from = entries
+                .keys()
+                .filter(|path| path.starts_with(from))
+                .cloned()
+                .collect::<Vec<_>>();
+            entries.retain(|path, _| !path.starts_with(to));
+            paths_from.into_iter().for_each(|path_from| {
+                if let Some(entry) = entries.remove(&path_from) {
+                    let path_to = match path_from.strip_prefix(from) {
+                        Ok(path_relative) if !path_relative.as_os_str().is_empty() => {
+                            to.join(path_relative)
+                        }
+                        _ => to.to_path_buf(),
+                    };
+                    entries.insert(path_to, entry);
+                }
+            });
+
+            Ok(())
+        }
+        .boxed_local()
+    }
+
+    fn read_dir<'f>(&'f self, path: &'f Path) -> LocalBoxFuture<'f, io::Result<Vec<PathBuf>>> {
+        async move {
+            let entries = self.entries();
+            match entries.get(path) {
+                Some(MemoryEntry::Dir) => Ok(entries
+                    .keys()
+                    .filter(|entry_path| entry_path.parent() == Some(path))
+                    .cloned()
+                    .collect()),
+                Some(MemoryEntry::File(_)) => Err(Self::not_a_dir(path)),
+                None => Err(Self::not_found(path)),
+            }
+        }
+        .boxed_local()
+    }
+
+    fn remove_file<'f>(&'f self, path: &'f Path) -> LocalBoxFuture<'f, io::Result<()>> {
+        async move {
+            let mut entries = self.entries();
+            match entries.get(path) {
+                Some(MemoryEntry::File(_)) => {
+                    entries.remove(path);
+                    Ok(())
+                }
+                Some(MemoryEntry::Dir) => Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("`{}` is a directory", path.display()),
+                )),
+                None => Err(Self::not_found(path)),
+            }
+        }
+        .boxed_local()
+    }
+
+    fn remove_dir_all<'f>(&'f self, path: &'f Path) -> LocalBoxFuture<'f, io::Result<()>> {
+        async move {
+            let mut entries = self.entries();
+            match entries.get(path) {
+                Some(MemoryEntry::Dir) => {
+                    entries.retain(|entry_path, _| !entry_path.starts_with(path));
+                    Ok(())
+                }
+                Some(MemoryEntry::File(_)) => Err(Self::not_a_dir(path)),
+                None => Err(Self::not_found(path)),
+            }
+        }
+        .boxed_local()
+    }
+
+    fn dir_sync<'f>(&'f self, path: &'f Path) -> LocalBoxFuture<'f, io::Result<()>> {
+        async move {
+            match self.entries().get(path) {
+                Some(MemoryEntry::Dir) => Ok(()),
+                Some(MemoryEntry::File(_)) => Err(Self::not_a_dir(path)),
+                None => Err(Self::not_found(path)),
+            }
+        }
+        .boxed_local()
+    }
+}
@@ -0,0 +1,82 @@
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use futures::future::{FutureExt, LocalBoxFuture};
+
+use crate::FileSystem;
+
+/// [`FileSystem`] that reads and writes the native file system.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NativeFileSystem;
+
+impl FileSystem for NativeFileSystem {
+    fn is_native(&self) -> bool {
+        true
+    }
+
+    fn exists<'f>(&'f self, path: &'f Path) -> LocalBoxFuture<'f, bool> {
+        async move { path.exists() }.boxed_local()
+    }
+
+    fn is_dir<'f>(&'f self, path: &'f Path) -> LocalBoxFuture<'f, bool> {
+        async move { path.is_dir() }.boxed_local()
+    }
+
+    fn create_dir_all<'f>(&'f self, path: &'f Path) -> LocalBoxFuture<'f, io::Result<()>> {
+        async move { fs::create_dir_all(path) }.boxed_local()
+    }
+
+    fn read<'f>(&'f self, path: &'f Path) -> LocalBoxFuture<'f, io::Result<Vec<u8>>> {
+        async move { fs::read(path) }.boxed_local()
+    }
+
+    fn write<'f>(
+        &'f self,
+        path: &'f Path,
+        contents: &'f [u8],
+    ) -> LocalBoxFuture<'f, io::Result<()>> {
+        async move {
+            let mut file = File::create(path)?;
+            file.write_all(contents)?;
+            file.sync_all()
+        }
+        .boxed_local()
+    }
+
+    fn rename<'f>(&'f self, from: &'f Path, to: &'f Path) -> LocalBoxFuture<'f, io::Result<()>> {
+        async move { fs::rename(from, to) }.boxed_local()
+    }
+
+    fn read_dir<'f>(&'f self, path: &'f Path) -> LocalBoxFuture<'f, io::Result<Vec<PathBuf>>> {
+        async move {
+            fs::read_dir(path)?
+                .map(|dir_entry| dir_entry.map(|dir_entry| dir_entry.path()))
+                .collect()
+        }
+        .boxed_local()
+    }
+
+    fn remove_file<'f>(&'f self, path: &'f Path) -> LocalBoxFuture<'f, io::Result<()>> {
+        async move { fs::remove_file(path) }.boxed_local()
+    }
+
+    fn remove_dir_all<'f>(&'f self, path: &'f Path) -> LocalBoxFuture<'f, io::Result<()>> {
+        async move { fs::remove_dir_all(path) }.boxed_local()
+    }
+
+    /// Directories cannot be opened as files on Windows, where this does
+    /// nothing.
+    #[cfg_attr(not(unix), allow(unused_variables))]
+    fn dir_sync<'f>(&'f self, path: &'f Path) -> LocalBoxFuture<'f, io::Result<()>> {
+        async move {
+            #[cfg(unix)]
+            File::open(path)?.sync_all()?;
+
+            Ok(())
+        }
+        .boxed_local()
+    }
+}
@@ -0,0 +1,38 @@
+use std::path::PathBuf;
+
+/// Describes where profile data is stored.
+///
+/// Profile data includes each profile's directory, the history directory,
+/// and the cache directory.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProfileDirSpec {
+    /// Store profile data in the workspace's `target` directory.
+    ///
+    /// * [`ProfileDir`]: `${workspace}/target/${profile}`
+    /// * [`HistoryDir`]: `${workspace}/target/.history`
+    /// * [`CacheDir`]: `${workspace}/target/.cache`
+    ///
+    /// [`CacheDir`]: choochoo_resource::CacheDir
+    /// [`HistoryDir`]: choochoo_resource::HistoryDir
+    /// [`ProfileDir`]: choochoo_resource::ProfileDir
+    WorkspaceTarget,
+    /// Store profile data in the given directory, such as an OS data directory
+    /// or a mounted volume.
+    ///
+    /// Relative paths are resolved against the workspace directory.
+    ///
+    /// * [`ProfileDir`]: `${path}/${profile}`
+    /// * [`HistoryDir`]: `${path}/.history`
+    /// * [`CacheDir`]: `${path}/.cache`
+    ///
+    /// [`CacheDir`]: choochoo_resource::CacheDir
+    /// [`HistoryDir`]: choochoo_resource::HistoryDir
+    /// [`ProfileDir`]: choochoo_resource::ProfileDir
+    Path(PathBuf),
+}
+
+impl Default for ProfileDirSpec {
+    fn default() -> Self {
+        Self::WorkspaceTarget
+    }
+}
@@ -0,0 +1,230 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use choochoo_resource::ProfileDir;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::error::ProfileStoreError;
+
+/// Small values that stations remember across executions of a profile, such
+/// as the last deployed version.
+///
+/// Values are stored by key as JSON in `${profile_dir}/store.json`, alongside
+/// a version that is incremented each time the value is written. Stations may
+/// access the store by taking `&ProfileStore` as a function parameter.
+///
+/// To avoid overwriting a value that was changed by another station or
+/// process, read its [`version`] and write it with [`put_if_version`].
+///
+/// [`version`]: Self::version
+/// [`put_if_version`]: Self::put_if_version
+#[derive(Clone, Debug)]
+pub struct ProfileStore {
+    /// Path to the store file.
+    path: PathBuf,
+    /// Serializes reads and writes of the store file within this process.
+    lock: Arc<Mutex<()>>,
+}
+
+impl ProfileStore {
+    /// Name of the store file within the profile directory.
+    pub const FILE_NAME: &'static str = "store.json";
+
+    /// Returns a new `ProfileStore` for the given profile directory.
+    ///
+    /// The store file is not read or created until a value is accessed.
+    pub fn new(profile_dir: &ProfileDir) -> Self {
+        Self {
+            path: profile_dir.join(Self::FILE_NAME),
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Returns the path to the store file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the value stored under the key, if any.
+    ///
+    /// # Parameters
+    ///
+    /// * `key`: Key of the value.
+    pub fn get<T>(&self, key: &str) -> Result<Option<T>, ProfileStoreError>
+    where
+        T: DeserializeOwned,
+    {
+        let _guard = self.lock.lock().unwrap_or_else(PoisonError::into_inner);
+        let entries = self.entries_read()?;
+        entries
+            .get(key)
+            .and_then(|entry| entry.get("value"))
+            .map(|value| {
+                serde_json::from_value(value.clone()).map_err(|error| {
+                    ProfileStoreError::ValueDeserialize {
+                        key: key.to_string(),
+                        error,
+                    }
+                })
+            })
+            .transpose()
+    }
+
+    /// Returns the version of the value stored under the key.
+    ///
+    /// This is `0` if no value is stored under the key.
+    ///
+    /// # Parameters
+    ///
+    /// * `key`: Key of the value.
+    pub fn version(&self, key: &str) -> Result<u64, ProfileStoreError> {
+        let _guard = self.lock.lock().unwrap_or_else(PoisonError::into_inner);
+        let entries = self.entries_read()?;
+        Ok(Self::entry_version(&entries, key))
+    }
+
+    /// Stores a value under the key, regardless of its current version.
+    ///
+    /// Returns the new version of the value.
+    ///
+    /// # Parameters
+    ///
+    /// * `key`: Key of the value.
+    /// * `value`: Value to store.
+    pub fn put<T>(&self, key: &str, value: &T) -> Result<u64, ProfileStoreError>
+    where
+        T: Serialize,
+    {
+        self.put_checked(key, value, None)
+    }
+
+    /// Stores a value under the key, if its version is the expected version.
+    ///
+    /// Returns the new version of the value, or
+    /// [`ProfileStoreError::VersionConflict`] if the value was changed since
+    /// the expected version was read. Use `0` as the expected version when
+    /// the key should not have a value.
+    ///
+    /// # Parameters
+    ///
+    /// * `key`: Key of the value.
+    /// * `value`: Value to store.
+    /// * `version_expected`: Version of the value that was read.
+    pub fn put_if_version<T>(
+        &self,
+        key: &str,
+        value: &T,
+        version_expected: u64,
+    ) -> Result<u64, ProfileStoreError>
+    where
+        T: Serialize,
+    {
+        self.put_checked(key, value, Some(version_expected))
+    }
+
+    /// Removes the value stored under the key, returning whether there was
+    /// one.
+    ///
+    /// # Parameters
+    ///
+    /// * `key`: Key of the value.
+    pub fn remove(&self, key: &str) -> Result<bool, ProfileStoreError> {
+        let _guard = self.lock.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut entries = self.entries_read()?;
+        if entries.remove(key).is_some() {
+            self.entries_write(&entries)?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn put_checked<T>(
+        &self,
+        key: &str,
+        value: &T,
+        version_expected: Option<u64>,
+    ) -> Result<u64, ProfileStoreError>
+    where
+        T: Serialize,
+    {
+        let value =
+            serde_json::to_value(value).map_err(|error| ProfileStoreError::ValueSerialize {
+                key: key.to_string(),
+                error,
+            })?;
+
+        let _guard = self.lock.lock().unwrap_or_else(PoisonError::into_inner);
+        // The file is read again before writing, so that changes made by other
+        // processes since the value was read are detected.
+        let mut entries = self.entries_read()?;
+        let version_actual = Self::entry_version(&entries, key);
+        if let Some(version_expected) = version_expected {
+            if version_expected != version_actual {
+                return Err(ProfileStoreError::VersionConflict {
+                    key: key.to_string(),
+                    version_expected,
+                    version_actual,
+                });
+            }
+        }
+
+        let version = version_actual + 1;
+        entries.insert(
+            key.to_string(),
+            json!({
+                "version": version,
+                "value": value,
+            }),
+        );
+        self.entries_write(&entries)?;
+
+        Ok(version)
+    }
+
+    fn entry_version(entries: &Map<String, Value>, key: &str) -> u64 {
+        entries
+            .get(key)
+            .and_then(|entry| entry.get("version"))
+            .and_then(Value::as_u64)
+            .unwrap_or(0)
+    }
+
+    fn entries_read(&self) -> Result<Map<String, Value>, ProfileStoreError> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Map::new()),
+            Err(error) => {
+                return Err(ProfileStoreError::Read {
+                    path: self.path.clone(),
+                    error,
+                });
+            }
+        };
+
+        serde_json::from_str(&contents).map_err(|error| ProfileStoreError::Deserialize {
+            path: self.path.clone(),
+            error,
+        })
+    }
+
+    /// Writes the entries to a temporary file, then renames it over the store
+    /// file, so that the store file is never partially written.
+    fn entries_write(&self, entries: &Map<String, Value>) -> Result<(), ProfileStoreError> {
+        let path_tmp = self.path.with_extension("json.tmp");
+        let contents = Value::Object(entries.clone()).to_string();
+        self.path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(&path_tmp, contents))
+            .and_then(|()| fs::rename(&path_tmp, &self.path))
+            .map_err(|error| ProfileStoreError::Write {
+                path: self.path.clone(),
+                error,
+            })
+    }
+}
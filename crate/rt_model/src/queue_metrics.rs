@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use choochoo_cfg_model::{indexmap::IndexMap, rt::StationRtId};
+
+/// Measurements of stations waiting to be visited.
+///
+/// Stations wait when a resource they access is limited, and the limit is
+/// reached by other stations being visited. Stations also wait when they are
+/// ready to be visited, but the train is already visiting its maximum number of
+/// stations concurrently.
+///
+/// Ready stations are queued on the train's workers, and a worker whose queue
+/// is empty takes stations from the queues of other workers. The number of
+/// stations taken this way is recorded as the steal count.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct QueueMetrics {
+    /// Maximum number of stations waiting at the same time.
+    queue_depth_max: usize,
+    /// Maximum number of ready stations held at the same time.
+    ready_depth_max: usize,
+    /// Time each visited station waited before its visit began.
+    station_wait_durations: IndexMap<StationRtId, Duration>,
+    /// Number of stations a worker took from another worker's queue.
+    steal_count: usize,
+}
+
+impl QueueMetrics {
+    /// Returns new, empty `QueueMetrics`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the number of stations that are currently waiting.
+    ///
+    /// The maximum recorded depth is kept.
+    pub fn queue_depth_record(&mut self, queue_depth: usize) {
+        self.queue_depth_max = self.queue_depth_max.max(queue_depth);
+    }
+
+    /// Records the number of stations that are ready to be visited, but are
+    /// held because the train is visiting its maximum number of stations.
+    ///
+    /// The maximum recorded depth is kept.
+    pub fn ready_depth_record(&mut self, ready_depth: usize) {
+        self.ready_depth_max = self.ready_depth_max.max(ready_depth);
+    }
+
+    /// Records the time a station waited before its visit began.
+    pub fn station_wait_record(&mut self, station_rt_id: StationRtId, wait_duration: Duration) {
+        self.station_wait_durations
+            .insert(station_rt_id, wait_duration);
+    }
+
+    /// Records that a worker took a station from another worker's queue.
+    pub fn station_steal_record(&mut self) {
+        self.steal_count += 1;
+    }
+
+    /// Returns the maximum number of stations waiting at the same time.
+    pub fn queue_depth_max(&self) -> usize {
+        self.queue_depth_max
+    }
+
+    /// Returns the maximum number of ready stations held at the same time.
+    ///
+    /// A value close to the number of stations that may be visited
+    /// concurrently means the concurrency limit is saturated, and raising it
+    /// may reduce the time taken to reach the destination.
+    pub fn ready_depth_max(&self) -> usize {
+        self.ready_depth_max
+    }
+
+    /// Returns the time each visited station waited before its visit began.
+    pub fn station_wait_durations(&self) -> &IndexMap<StationRtId, Duration> {
+        &self.station_wait_durations
+    }
+
+    /// Returns the number of stations a worker took from another worker's
+    /// queue.
+    ///
+    /// A high count relative to the number of stations means stations often
+    /// became ready while the worker that queued them was busy, and were
+    /// visited by idle workers instead.
+    pub fn steal_count(&self) -> usize {
+        self.steal_count
+    }
+
+    /// Returns the longest time any station waited.
+    pub fn wait_duration_max(&self) -> Duration {
+        self.station_wait_durations
+            .values()
+            .copied()
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Returns the total time all stations waited.
+    pub fn wait_duration_total(&self) -> Duration {
+        self.station_wait_durations.values().sum()
+    }
+}
@@ -0,0 +1,88 @@
+use std::fmt;
+
+use choochoo_cfg_model::{
+    indexmap::IndexMap,
+    resman::Ref,
+    rt::{StationErrors, StationRtId, TrainResources},
+};
+
+use crate::StationReconciliation;
+
+/// Record of a train's audit of the destination's resources.
+///
+/// Each station with an exists function has a [`StationReconciliation`].
+/// Errors from setting up stations or running their exists functions are
+/// recorded in the train resources' [`StationErrors`].
+#[derive(Debug)]
+pub struct ReconciliationReport<E> {
+    /// Resources used during the audit.
+    train_resources: TrainResources<E>,
+    /// Differences between persisted and existing resource IDs, per station.
+    station_reconciliations: IndexMap<StationRtId, StationReconciliation>,
+    /// Errors from tearing down the train resources.
+    teardown_warnings: Vec<E>,
+}
+
+impl<E> ReconciliationReport<E>
+where
+    E: fmt::Debug + Send + Sync + 'static,
+{
+    /// Returns a new `ReconciliationReport`.
+    ///
+    /// # Parameters
+    ///
+    /// * `train_resources`: Resources used during the audit.
+    /// * `station_reconciliations`: Differences between persisted and existing
+    ///   resource IDs, per station.
+    pub fn new(
+        train_resources: TrainResources<E>,
+        station_reconciliations: IndexMap<StationRtId, StationReconciliation>,
+    ) -> Self {
+        Self {
+            train_resources,
+            station_reconciliations,
+            teardown_warnings: Vec::new(),
+        }
+    }
+
+    /// Tears down the train resources that implement [`ResourceTeardown`],
+    /// recording errors as teardown warnings.
+    ///
+    /// [`ResourceTeardown`]: choochoo_cfg_model::rt::ResourceTeardown
+    pub async fn resources_teardown(mut self) -> Self {
+        let teardown_warnings = self.train_resources.teardown().await;
+        self.teardown_warnings.extend(teardown_warnings);
+        self
+    }
+
+    /// Returns the resources used during the audit.
+    pub fn train_resources(&self) -> &TrainResources<E> {
+        &self.train_resources
+    }
+
+    /// Returns the errors from setting up stations or running their exists
+    /// functions.
+    pub fn station_errors(&self) -> Ref<StationErrors<E>> {
+        self.train_resources.station_errors()
+    }
+
+    /// Returns the differences between persisted and existing resource IDs,
+    /// per station.
+    pub fn station_reconciliations(&self) -> &IndexMap<StationRtId, StationReconciliation> {
+        &self.station_reconciliations
+    }
+
+    /// Returns the stations whose existing resources differ from their
+    /// persisted resource IDs.
+    pub fn stations_drifted(&self) -> impl Iterator<Item = StationRtId> + '_ {
+        self.station_reconciliations
+            .iter()
+            .filter(|(_, station_reconciliation)| station_reconciliation.is_drifted())
+            .map(|(station_rt_id, _)| *station_rt_id)
+    }
+
+    /// Returns the errors from tearing down the train resources.
+    pub fn teardown_warnings(&self) -> &[E] {
+        &self.teardown_warnings
+    }
+}
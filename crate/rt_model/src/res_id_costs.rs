@@ -0,0 +1,45 @@
+use std::ops::{Deref, DerefMut};
+
+use choochoo_cfg_model::{indexmap::IndexMap, rt::ResIdMetadata, StationId};
+
+/// Estimated monthly cost of the resources produced by each station.
+///
+/// This aggregates the cost in each resource ID's [`ResIdMetadata`].
+/// Resources without a cost are not counted.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ResIdCosts(IndexMap<StationId, f64>);
+
+impl ResIdCosts {
+    /// Returns empty `ResIdCosts`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the cost in a resource ID's metadata to the station's cost.
+    ///
+    /// Nothing is added if the metadata has no cost.
+    pub fn metadata_add(&mut self, station_id: &StationId, res_id_metadata: &ResIdMetadata) {
+        if let Some(cost_monthly) = res_id_metadata.cost_monthly() {
+            *self.0.entry(station_id.clone()).or_insert(0.0) += cost_monthly;
+        }
+    }
+
+    /// Returns the estimated monthly cost of all resources.
+    pub fn total(&self) -> f64 {
+        self.0.values().sum()
+    }
+}
+
+impl Deref for ResIdCosts {
+    type Target = IndexMap<StationId, f64>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for ResIdCosts {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
@@ -0,0 +1,33 @@
+/// How a [`DestinationBuilder`] handles stations that may be visited
+/// concurrently, but access the same resource where at least one of them
+/// accesses it mutably.
+///
+/// Stations access resources through the parameters of their check, work, and
+/// verify functions. Without handling, such stations fail with a borrow
+/// conflict when they happen to be visited at the same time.
+///
+/// [`DestinationBuilder`]: crate::DestinationBuilder
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResourceConflictPolicy {
+    /// Conflicting stations are visited one after the other.
+    ///
+    /// When a station is about to be visited, it waits until no other station
+    /// that is being visited mutably borrows a resource it borrows, or borrows
+    /// a resource it mutably borrows. Only the borrows of the operation being
+    /// visited are considered. No edges are added, so a station's failure
+    /// does not affect the stations it conflicts with.
+    ///
+    /// This is the default.
+    Serialize,
+    /// [`Error::StationResourceConflict`] is returned when the destination is
+    /// built, if the create or clean functions of stations conflict.
+    ///
+    /// [`Error::StationResourceConflict`]: crate::Error::StationResourceConflict
+    Error,
+}
+
+impl Default for ResourceConflictPolicy {
+    fn default() -> Self {
+        Self::Serialize
+    }
+}
@@ -0,0 +1,15 @@
+use crate::CleanEnsureOutcomeErr;
+
+/// Result of cleaning the resources of a station whose create visit failed.
+#[derive(Clone, Debug)]
+pub enum RollbackOutcome<E> {
+    /// The station's clean functions removed the created resources.
+    Success,
+    /// The station's clean check function reported there was nothing to
+    /// clean.
+    Unnecessary,
+    /// The station does not have clean functions.
+    Unsupported,
+    /// The station's clean functions failed.
+    Fail(CleanEnsureOutcomeErr<E>),
+}
@@ -0,0 +1,14 @@
+use std::time::Duration;
+
+use choochoo_cfg_model::StationId;
+
+/// Station whose visit took significantly longer than in a previous run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StationDurationRegression {
+    /// Unique identifier of the station.
+    pub station_id: StationId,
+    /// Time the station's check and work took in the previous run.
+    pub duration_previous: Duration,
+    /// Time the station's check and work took in the current run.
+    pub duration_current: Duration,
+}
@@ -0,0 +1,40 @@
+use std::{
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+};
+
+use choochoo_cfg_model::rt::{StationEnv, StationRtId};
+
+/// Map from [`StationRtId`] to each station's environment for external
+/// commands.
+#[derive(Clone, Debug, Default)]
+pub struct StationEnvs(pub HashMap<StationRtId, StationEnv>);
+
+impl StationEnvs {
+    /// Returns an empty `StationEnvs` map.
+    pub fn new() -> Self {
+        Self(HashMap::new())
+    }
+
+    /// Creates an empty `StationEnvs` map with the specified capacity.
+    ///
+    /// The map will be able to hold at least `capacity` elements without
+    /// reallocating. If `capacity` is 0, the map will not allocate.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(HashMap::with_capacity(capacity))
+    }
+}
+
+impl Deref for StationEnvs {
+    type Target = HashMap<StationRtId, StationEnv>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for StationEnvs {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
@@ -0,0 +1,84 @@
+use choochoo_cfg_model::rt_map::BorrowFail;
+use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// Lock around runtime data of a single station.
+///
+/// Each station's data is locked independently, so accessing one station never
+/// contends with accessing another. Borrowing may either wait for the data to
+/// be released, or report the contention immediately.
+#[derive(Debug, Default)]
+pub struct StationLock<T>(RwLock<T>);
+
+impl<T> StationLock<T> {
+    /// Returns a new `StationLock` around the given value.
+    pub fn new(value: T) -> Self {
+        Self(RwLock::new(value))
+    }
+
+    /// Returns the value, waiting until it is not accessed mutably.
+    pub async fn read(&self) -> RwLockReadGuard<'_, T> {
+        self.0.read().await
+    }
+
+    /// Returns the value mutably, waiting until it is not accessed.
+    pub async fn write(&self) -> RwLockWriteGuard<'_, T> {
+        self.0.write().await
+    }
+
+    /// Returns the value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is accessed mutably. Use [`try_borrow`] to be
+    /// informed of the contention instead, or [`read`] to wait for the value to
+    /// be released.
+    ///
+    /// [`try_borrow`]: Self::try_borrow
+    /// [`read`]: Self::read
+    pub fn borrow(&self) -> RwLockReadGuard<'_, T> {
+        self.try_borrow()
+            .unwrap_or_else(|borrow_fail| panic!("Failed to borrow station data: {borrow_fail:?}"))
+    }
+
+    /// Returns the value mutably.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is already accessed. Use [`try_borrow_mut`] to be
+    /// informed of the contention instead, or [`write`] to wait for the value
+    /// to be released.
+    ///
+    /// [`try_borrow_mut`]: Self::try_borrow_mut
+    /// [`write`]: Self::write
+    pub fn borrow_mut(&self) -> RwLockWriteGuard<'_, T> {
+        self.try_borrow_mut().unwrap_or_else(|borrow_fail| {
+            panic!("Failed to borrow station data mutably: {borrow_fail:?}")
+        })
+    }
+
+    /// Returns the value, or an error if it is accessed mutably.
+    pub fn try_borrow(&self) -> Result<RwLockReadGuard<'_, T>, BorrowFail> {
+        self.0
+            .try_read()
+            .map_err(|_try_lock_error| BorrowFail::BorrowConflictImm)
+    }
+
+    /// Returns the value mutably, or an error if it is already accessed.
+    pub fn try_borrow_mut(&self) -> Result<RwLockWriteGuard<'_, T>, BorrowFail> {
+        self.0
+            .try_write()
+            .map_err(|_try_lock_error| BorrowFail::BorrowConflictMut)
+    }
+
+    /// Returns a mutable reference to the value.
+    ///
+    /// This does not lock, as the `&mut self` guarantees exclusive access.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.0.get_mut()
+    }
+
+    /// Returns the value, consuming the lock.
+    pub fn into_inner(self) -> T {
+        self.0.into_inner()
+    }
+}
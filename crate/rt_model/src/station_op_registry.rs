@@ -0,0 +1,52 @@
+use std::{
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+};
+
+use choochoo_cfg_model::StationOp;
+
+/// Map from logical operation name to the [`StationOp`] that implements it.
+///
+/// This is used to look up the Rust logic for stations declared in a
+/// [`DestinationDecl`].
+///
+/// [`DestinationDecl`]: crate::DestinationDecl
+#[derive(Debug)]
+pub struct StationOpRegistry<E>(pub HashMap<String, StationOp<E>>);
+
+impl<E> StationOpRegistry<E> {
+    /// Returns an empty `StationOpRegistry`.
+    pub fn new() -> Self {
+        Self(HashMap::new())
+    }
+
+    /// Registers a [`StationOp`] under the given logical operation name.
+    #[must_use]
+    pub fn with_station_op<S>(mut self, op_name: S, station_op: StationOp<E>) -> Self
+    where
+        S: Into<String>,
+    {
+        self.0.insert(op_name.into(), station_op);
+        self
+    }
+}
+
+impl<E> Default for StationOpRegistry<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> Deref for StationOpRegistry<E> {
+    type Target = HashMap<String, StationOp<E>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<E> DerefMut for StationOpRegistry<E> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
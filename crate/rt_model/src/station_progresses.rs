@@ -1,18 +1,29 @@
-use std::ops::{Deref, DerefMut};
+use std::{
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+};
 
 use choochoo_cfg_model::{
     rt::{StationProgress, StationRtId},
-    rt_map::RtMap,
+    rt_map::BorrowFail,
 };
+use tokio::sync::{RwLockReadGuard, RwLockWriteGuard};
+
+use crate::StationLock;
 
 /// Map from [`StationRtId`] to the runtime data.
+///
+/// Each station's progress is behind its own [`StationLock`], so stations may
+/// be updated concurrently. Updates wait for the station's progress to be
+/// released instead of being skipped, and the `try_*` methods report
+/// contention for callers that cannot wait.
 #[derive(Debug, Default)]
-pub struct StationProgresses(pub RtMap<StationRtId, StationProgress>);
+pub struct StationProgresses(pub HashMap<StationRtId, StationLock<StationProgress>>);
 
 impl StationProgresses {
     /// Returns an empty `StationProgresses` map.
     pub fn new() -> Self {
-        Self(RtMap::new())
+        Self(HashMap::new())
     }
 
     /// Creates an empty `StationProgresses` map with the specified capacity.
@@ -20,12 +31,74 @@ impl StationProgresses {
     /// The map will be able to hold at least `capacity` elements without
     /// reallocating. If `capacity` is 0, the map will not allocate.
     pub fn with_capacity(capacity: usize) -> Self {
-        Self(RtMap::with_capacity(capacity))
+        Self(HashMap::with_capacity(capacity))
+    }
+
+    /// Inserts a station's progress into the map.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_rt_id`: Runtime ID of the station.
+    /// * `station_progress`: Progress of the station.
+    pub fn insert(&mut self, station_rt_id: StationRtId, station_progress: StationProgress) {
+        self.0
+            .insert(station_rt_id, StationLock::new(station_progress));
+    }
+
+    /// Returns a station's progress, waiting until it is not accessed mutably.
+    ///
+    /// Returns `None` if the station is not in the map.
+    pub async fn read(
+        &self,
+        station_rt_id: &StationRtId,
+    ) -> Option<RwLockReadGuard<'_, StationProgress>> {
+        match self.0.get(station_rt_id) {
+            Some(station_progress) => Some(station_progress.read().await),
+            None => None,
+        }
+    }
+
+    /// Returns a station's progress mutably, waiting until it is not
+    /// accessed.
+    ///
+    /// Returns `None` if the station is not in the map.
+    pub async fn write(
+        &self,
+        station_rt_id: &StationRtId,
+    ) -> Option<RwLockWriteGuard<'_, StationProgress>> {
+        match self.0.get(station_rt_id) {
+            Some(station_progress) => Some(station_progress.write().await),
+            None => None,
+        }
+    }
+
+    /// Returns a station's progress, or an error if it is not in the map or
+    /// is accessed mutably.
+    pub fn try_borrow(
+        &self,
+        station_rt_id: &StationRtId,
+    ) -> Result<RwLockReadGuard<'_, StationProgress>, BorrowFail> {
+        self.0
+            .get(station_rt_id)
+            .ok_or(BorrowFail::ValueNotFound)
+            .and_then(StationLock::try_borrow)
+    }
+
+    /// Returns a station's progress mutably, or an error if it is not in the
+    /// map or is already accessed.
+    pub fn try_borrow_mut(
+        &self,
+        station_rt_id: &StationRtId,
+    ) -> Result<RwLockWriteGuard<'_, StationProgress>, BorrowFail> {
+        self.0
+            .get(station_rt_id)
+            .ok_or(BorrowFail::ValueNotFound)
+            .and_then(StationLock::try_borrow_mut)
     }
 }
 
 impl Deref for StationProgresses {
-    type Target = RtMap<StationRtId, StationProgress>;
+    type Target = HashMap<StationRtId, StationLock<StationProgress>>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
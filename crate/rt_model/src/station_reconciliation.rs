@@ -0,0 +1,83 @@
+use choochoo_cfg_model::{indexmap::IndexMap, rt::ResIdLogical};
+
+/// Differences between the resource IDs a station persisted, and the
+/// resources that exist.
+///
+/// This is computed per resource ID, so drift is visible even when the
+/// station's check function would report that no work is required.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StationReconciliation {
+    /// Resource IDs that were persisted, but whose resources do not exist.
+    res_ids_missing: Vec<ResIdLogical>,
+    /// Resource IDs of resources that exist, but were not persisted.
+    res_ids_unrecorded: Vec<ResIdLogical>,
+    /// Resource IDs whose physical ID differs from the persisted one.
+    res_ids_changed: Vec<ResIdLogical>,
+}
+
+impl StationReconciliation {
+    /// Returns the differences between persisted and existing resource IDs.
+    ///
+    /// # Parameters
+    ///
+    /// * `res_ids_persisted`: Serialized resource IDs the station persisted.
+    /// * `res_ids_existing`: Serialized resource IDs of resources that exist.
+    pub fn new(
+        res_ids_persisted: &IndexMap<ResIdLogical, serde_json::Value>,
+        res_ids_existing: &IndexMap<ResIdLogical, serde_json::Value>,
+    ) -> Self {
+        let res_ids_missing = res_ids_persisted
+            .keys()
+            .filter(|res_id_logical| !res_ids_existing.contains_key(*res_id_logical))
+            .cloned()
+            .collect::<Vec<_>>();
+        let res_ids_unrecorded = res_ids_existing
+            .keys()
+            .filter(|res_id_logical| !res_ids_persisted.contains_key(*res_id_logical))
+            .cloned()
+            .collect::<Vec<_>>();
+        let res_ids_changed = res_ids_persisted
+            .iter()
+            .filter(|(res_id_logical, res_id_physical)| {
+                res_ids_existing
+                    .get(*res_id_logical)
+                    .map_or(false, |res_id_physical_existing| {
+                        res_id_physical_existing != *res_id_physical
+                    })
+            })
+            .map(|(res_id_logical, _)| res_id_logical.clone())
+            .collect::<Vec<_>>();
+
+        Self {
+            res_ids_missing,
+            res_ids_unrecorded,
+            res_ids_changed,
+        }
+    }
+
+    /// Returns the resource IDs that were persisted, but whose resources do
+    /// not exist.
+    pub fn res_ids_missing(&self) -> &[ResIdLogical] {
+        &self.res_ids_missing
+    }
+
+    /// Returns the resource IDs of resources that exist, but were not
+    /// persisted.
+    pub fn res_ids_unrecorded(&self) -> &[ResIdLogical] {
+        &self.res_ids_unrecorded
+    }
+
+    /// Returns the resource IDs whose physical ID differs from the persisted
+    /// one.
+    pub fn res_ids_changed(&self) -> &[ResIdLogical] {
+        &self.res_ids_changed
+    }
+
+    /// Returns whether the existing resources differ from the persisted
+    /// resource IDs.
+    pub fn is_drifted(&self) -> bool {
+        !self.res_ids_missing.is_empty()
+            || !self.res_ids_unrecorded.is_empty()
+            || !self.res_ids_changed.is_empty()
+    }
+}
@@ -0,0 +1,20 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use choochoo_cfg_model::{rt::ResIdLogical, StationId};
+
+/// Persistable record of what happened to a station during a train's drive.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct StationRecord {
+    /// Unique identifier of the station.
+    pub station_id: StationId,
+    /// Whether the station's setup, check, work, or verification failed.
+    pub failed: bool,
+    /// Time the station's check and work took, if it was visited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration: Option<Duration>,
+    /// Serialized resource IDs produced by the station.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub res_ids: BTreeMap<ResIdLogical, serde_json::Value>,
+}
@@ -0,0 +1,86 @@
+use std::{
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+};
+
+use choochoo_cfg_model::{
+    rt::{StationRtId, StationState},
+    rt_map::BorrowFail,
+};
+use tokio::sync::RwLockWriteGuard;
+
+use crate::StationLock;
+
+/// Map from [`StationRtId`] to the station's operation state.
+///
+/// Like [`StationProgresses`], each station's state is behind its own
+/// [`StationLock`].
+///
+/// [`StationProgresses`]: crate::StationProgresses
+#[derive(Debug, Default)]
+pub struct StationStates(pub HashMap<StationRtId, StationLock<StationState>>);
+
+impl StationStates {
+    /// Returns an empty `StationStates` map.
+    pub fn new() -> Self {
+        Self(HashMap::new())
+    }
+
+    /// Creates an empty `StationStates` map with the specified capacity.
+    ///
+    /// The map will be able to hold at least `capacity` elements without
+    /// reallocating. If `capacity` is 0, the map will not allocate.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(HashMap::with_capacity(capacity))
+    }
+
+    /// Inserts a station's state into the map.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_rt_id`: Runtime ID of the station.
+    /// * `station_state`: State of the station's operation.
+    pub fn insert(&mut self, station_rt_id: StationRtId, station_state: StationState) {
+        self.0
+            .insert(station_rt_id, StationLock::new(station_state));
+    }
+
+    /// Returns a station's state mutably, waiting until it is not accessed.
+    ///
+    /// Returns `None` if the station is not in the map.
+    pub async fn write(
+        &self,
+        station_rt_id: &StationRtId,
+    ) -> Option<RwLockWriteGuard<'_, StationState>> {
+        match self.0.get(station_rt_id) {
+            Some(station_state) => Some(station_state.write().await),
+            None => None,
+        }
+    }
+
+    /// Returns a station's state mutably, or an error if it is not in the map
+    /// or is already accessed.
+    pub fn try_borrow_mut(
+        &self,
+        station_rt_id: &StationRtId,
+    ) -> Result<RwLockWriteGuard<'_, StationState>, BorrowFail> {
+        self.0
+            .get(station_rt_id)
+            .ok_or(BorrowFail::ValueNotFound)
+            .and_then(StationLock::try_borrow_mut)
+    }
+}
+
+impl Deref for StationStates {
+    type Target = HashMap<StationRtId, StationLock<StationState>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for StationStates {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
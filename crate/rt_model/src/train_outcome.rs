@@ -0,0 +1,18 @@
+/// Overall result of a train's drive.
+///
+/// This is used to choose the process exit code through an
+/// [`ExitCodePolicy`].
+///
+/// [`ExitCodePolicy`]: crate::ExitCodePolicy
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TrainOutcome {
+    /// All stations reached the destination.
+    Success,
+    /// A station recorded an error, or a warning at or above the report's
+    /// severity threshold.
+    Failed,
+    /// A station did not complete before the train's deadline.
+    ///
+    /// This takes precedence over [`TrainOutcome::Failed`].
+    DeadlineExceeded,
+}
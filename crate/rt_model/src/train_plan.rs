@@ -0,0 +1,231 @@
+use std::time::Duration;
+
+use choochoo_cfg_model::{
+    indexmap::IndexMap,
+    rt::{ProgressLimit, StationRtId, VisitOp},
+};
+
+use crate::{CriticalPath, DestinationReachability};
+
+/// Stations that would be visited when a train reaches its destination.
+///
+/// Stations are grouped into waves -- stations in the same wave do not depend
+/// on each other, and may be visited concurrently once all stations in
+/// earlier waves that they depend on have been visited. Within a wave,
+/// stations are ordered by descending priority, which is the order they are
+/// preferred in when not all of them may be visited at the same time.
+///
+/// The plan also records the [`DestinationReachability`] of the destination,
+/// so that orphaned stations and unused resources are noticed before the
+/// destination is reached.
+///
+/// This is computed without running any station functions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrainPlan {
+    /// Operation the stations would be visited for.
+    visit_op: VisitOp,
+    /// Stations grouped by the wave in which they may be visited.
+    waves: Vec<Vec<StationRtId>>,
+    /// Stations that must be visited before each station.
+    station_dependencies: IndexMap<StationRtId, Vec<StationRtId>>,
+    /// Progress limit of each station, as currently known.
+    station_progress_limits: IndexMap<StationRtId, ProgressLimit>,
+    /// Priority of each station when multiple stations are ready to be
+    /// visited.
+    station_priorities: IndexMap<StationRtId, i32>,
+    /// Connectivity of the destination's stations.
+    reachability: DestinationReachability,
+}
+
+impl TrainPlan {
+    /// Returns a new empty `TrainPlan`.
+    pub fn new(visit_op: VisitOp) -> Self {
+        Self {
+            visit_op,
+            waves: Vec::new(),
+            station_dependencies: IndexMap::new(),
+            station_progress_limits: IndexMap::new(),
+            station_priorities: IndexMap::new(),
+            reachability: DestinationReachability::default(),
+        }
+    }
+
+    /// Sets the connectivity of the destination's stations.
+    #[must_use]
+    pub fn with_reachability(mut self, reachability: DestinationReachability) -> Self {
+        self.reachability = reachability;
+        self
+    }
+
+    /// Adds a station to the plan.
+    ///
+    /// The station is placed in the wave after the latest wave of its
+    /// dependencies, so dependencies must be added before the stations that
+    /// depend on them. The station is placed after stations in the same wave
+    /// with the same or a higher priority.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_rt_id`: Runtime ID of the station.
+    /// * `dependencies`: Stations that must be visited before this station.
+    /// * `progress_limit`: Progress limit of the station, as currently known.
+    /// * `priority`: Priority of the station when multiple stations are ready
+    ///   to be visited.
+    pub fn station_add(
+        &mut self,
+        station_rt_id: StationRtId,
+        dependencies: Vec<StationRtId>,
+        progress_limit: ProgressLimit,
+        priority: i32,
+    ) {
+        let wave = dependencies
+            .iter()
+            .filter_map(|dependency| self.station_wave(*dependency))
+            .map(|wave| wave + 1)
+            .max()
+            .unwrap_or(0);
+
+        if self.waves.len() <= wave {
+            self.waves.resize_with(wave + 1, Vec::new);
+        }
+        let station_priorities = &self.station_priorities;
+        let index = self.waves[wave].partition_point(|station_rt_id_existing| {
+            station_priorities
+                .get(station_rt_id_existing)
+                .copied()
+                .unwrap_or_default()
+                >= priority
+        });
+        self.waves[wave].insert(index, station_rt_id);
+        self.station_dependencies
+            .insert(station_rt_id, dependencies);
+        self.station_progress_limits
+            .insert(station_rt_id, progress_limit);
+        self.station_priorities.insert(station_rt_id, priority);
+    }
+
+    /// Returns the operation the stations would be visited for.
+    pub fn visit_op(&self) -> VisitOp {
+        self.visit_op
+    }
+
+    /// Returns the stations grouped by the wave in which they may be visited.
+    pub fn waves(&self) -> &[Vec<StationRtId>] {
+        &self.waves
+    }
+
+    /// Returns the index of the wave in which a station may be visited.
+    pub fn station_wave(&self, station_rt_id: StationRtId) -> Option<usize> {
+        self.waves
+            .iter()
+            .position(|wave| wave.contains(&station_rt_id))
+    }
+
+    /// Returns an iterator over the stations in the order they may be visited.
+    pub fn station_order(&self) -> impl Iterator<Item = StationRtId> + '_ {
+        self.waves.iter().flatten().copied()
+    }
+
+    /// Returns the stations that must be visited before each station.
+    pub fn station_dependencies(&self) -> &IndexMap<StationRtId, Vec<StationRtId>> {
+        &self.station_dependencies
+    }
+
+    /// Returns the progress limit of each station, as currently known.
+    ///
+    /// Progress limits are calculated by each station's setup function, so
+    /// this is [`ProgressLimit::Unknown`] for stations that have not been set
+    /// up.
+    pub fn station_progress_limits(&self) -> &IndexMap<StationRtId, ProgressLimit> {
+        &self.station_progress_limits
+    }
+
+    /// Returns the priority of each station when multiple stations are ready
+    /// to be visited.
+    pub fn station_priorities(&self) -> &IndexMap<StationRtId, i32> {
+        &self.station_priorities
+    }
+
+    /// Returns the connectivity of the destination's stations.
+    pub fn reachability(&self) -> &DestinationReachability {
+        &self.reachability
+    }
+
+    /// Returns the chain of dependent stations with the longest total
+    /// duration.
+    ///
+    /// The durations may be recorded from a previous drive, such as from
+    /// [`TrainReport::station_durations`], or estimated. Stations without a
+    /// duration are treated as taking no time. When multiple chains have the
+    /// same duration, the chain through earlier stations is returned.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_durations`: Recorded or estimated duration of each station.
+    ///
+    /// [`TrainReport::station_durations`]: crate::TrainReport::station_durations
+    pub fn critical_path(
+        &self,
+        station_durations: &IndexMap<StationRtId, Duration>,
+    ) -> CriticalPath {
+        // Longest duration of a chain that ends at each station, and the
+        // previous station in that chain.
+        let mut chain_ends = IndexMap::<StationRtId, (Duration, Option<StationRtId>)>::new();
+        self.station_order().for_each(|station_rt_id| {
+            let chain_longest = self
+                .station_dependencies
+                .get(&station_rt_id)
+                .into_iter()
+                .flatten()
+                .filter_map(|dependency| {
+                    chain_ends
+                        .get(dependency)
+                        .map(|(chain_duration, _)| (*dependency, *chain_duration))
+                })
+                .fold(None, Self::chain_longer);
+            let (dependency, dependency_duration) = match chain_longest {
+                Some((dependency, chain_duration)) => (Some(dependency), chain_duration),
+                None => (None, Duration::ZERO),
+            };
+            let station_duration = station_durations
+                .get(&station_rt_id)
+                .copied()
+                .unwrap_or_default();
+            chain_ends.insert(
+                station_rt_id,
+                (dependency_duration + station_duration, dependency),
+            );
+        });
+
+        let chain_end = chain_ends
+            .iter()
+            .map(|(station_rt_id, (chain_duration, _))| (*station_rt_id, *chain_duration))
+            .fold(None, Self::chain_longer);
+        let (station_rt_id_last, duration) = match chain_end {
+            Some(chain_end) => chain_end,
+            None => return CriticalPath::default(),
+        };
+
+        let mut station_rt_ids = vec![station_rt_id_last];
+        let mut station_rt_id = station_rt_id_last;
+        while let Some((_, Some(dependency))) = chain_ends.get(&station_rt_id) {
+            station_rt_ids.push(*dependency);
+            station_rt_id = *dependency;
+        }
+        station_rt_ids.reverse();
+
+        CriticalPath::new(station_rt_ids, duration)
+    }
+
+    /// Returns the chain with the longer duration, preferring the earlier
+    /// chain when they are equal.
+    fn chain_longer(
+        chain_longest: Option<(StationRtId, Duration)>,
+        chain: (StationRtId, Duration),
+    ) -> Option<(StationRtId, Duration)> {
+        match chain_longest {
+            Some((_, chain_longest_duration)) if chain_longest_duration >= chain.1 => chain_longest,
+            _ => Some(chain),
+        }
+    }
+}
@@ -1,6 +1,20 @@
-use std::fmt;
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
-use choochoo_cfg_model::rt::{ResIds, TrainResources};
+use choochoo_cfg_model::{
+    indexmap::IndexMap,
+    rt::{
+        Effect, EffectRecord, ResIds, StationArtifact, StationErrorPhase, StationErrorRecord,
+        StationErrors, StationRtId, SubStep, TrainResources,
+    },
+    srcerr::codespan_reporting::diagnostic::Severity,
+};
+use tokio::sync::OwnedRwLockReadGuard;
+
+use crate::{CriticalPath, ExitCodePolicy, QueueMetrics, RollbackOutcome, TrainOutcome};
 
 /// Record of what happened during a train's drive.
 #[derive(Debug)]
@@ -9,6 +23,30 @@ pub struct TrainReport<E> {
     train_resources: TrainResources<E>,
     /// Resource IDs produced by visiting each station.
     res_ids: ResIds,
+    /// Resource IDs produced by each station, grouped by station.
+    station_res_ids: IndexMap<StationRtId, ResIds>,
+    /// Minimum severity of recorded diagnostics that fails the train.
+    severity_threshold: Severity,
+    /// Stations that did not complete before the train's deadline.
+    stations_deadline_exceeded: Vec<StationRtId>,
+    /// Sub-steps of each station that has them.
+    station_sub_steps: IndexMap<StationRtId, Vec<SubStep>>,
+    /// Measurements of stations waiting to be visited.
+    queue_metrics: QueueMetrics,
+    /// Time each visited station's check and work took.
+    station_durations: IndexMap<StationRtId, Duration>,
+    /// Chain of dependent stations that took the longest.
+    critical_path: CriticalPath,
+    /// Mapping from the train's outcome to a process exit code.
+    exit_code_policy: ExitCodePolicy,
+    /// Result of cleaning each station whose create visit failed.
+    station_rollbacks: IndexMap<StationRtId, RollbackOutcome<E>>,
+    /// Errors from tearing down the train resources.
+    teardown_warnings: Vec<E>,
+    /// Outputs registered by each station that registered any.
+    station_artifacts: IndexMap<StationRtId, Vec<StationArtifact>>,
+    /// Directory that station artifacts were collected into.
+    artifacts_dir: Option<PathBuf>,
 }
 
 impl<E> TrainReport<E>
@@ -20,9 +58,128 @@ where
         Self {
             train_resources,
             res_ids,
+            station_res_ids: IndexMap::new(),
+            severity_threshold: Severity::Error,
+            stations_deadline_exceeded: Vec::new(),
+            station_sub_steps: IndexMap::new(),
+            queue_metrics: QueueMetrics::new(),
+            station_durations: IndexMap::new(),
+            critical_path: CriticalPath::default(),
+            exit_code_policy: ExitCodePolicy::default(),
+            station_rollbacks: IndexMap::new(),
+            teardown_warnings: Vec::new(),
+            station_artifacts: IndexMap::new(),
+            artifacts_dir: None,
         }
     }
 
+    /// Sets the resource IDs produced by each station, grouped by station.
+    #[must_use]
+    pub fn with_station_res_ids(mut self, station_res_ids: IndexMap<StationRtId, ResIds>) -> Self {
+        self.station_res_ids = station_res_ids;
+        self
+    }
+
+    /// Sets the minimum severity of recorded diagnostics that fails the train.
+    ///
+    /// Station errors always fail the train. When this is [`Severity::Warning`]
+    /// or lower, station warnings also fail the train.
+    ///
+    /// Defaults to [`Severity::Error`].
+    #[must_use]
+    pub fn with_severity_threshold(mut self, severity_threshold: Severity) -> Self {
+        self.severity_threshold = severity_threshold;
+        self
+    }
+
+    /// Sets the stations that did not complete before the train's deadline.
+    #[must_use]
+    pub fn with_stations_deadline_exceeded(
+        mut self,
+        stations_deadline_exceeded: Vec<StationRtId>,
+    ) -> Self {
+        self.stations_deadline_exceeded = stations_deadline_exceeded;
+        self
+    }
+
+    /// Sets the sub-steps of each station that has them.
+    #[must_use]
+    pub fn with_station_sub_steps(
+        mut self,
+        station_sub_steps: IndexMap<StationRtId, Vec<SubStep>>,
+    ) -> Self {
+        self.station_sub_steps = station_sub_steps;
+        self
+    }
+
+    /// Sets the measurements of stations waiting to be visited.
+    #[must_use]
+    pub fn with_queue_metrics(mut self, queue_metrics: QueueMetrics) -> Self {
+        self.queue_metrics = queue_metrics;
+        self
+    }
+
+    /// Sets the time each visited station's check and work took.
+    #[must_use]
+    pub fn with_station_durations(
+        mut self,
+        station_durations: IndexMap<StationRtId, Duration>,
+    ) -> Self {
+        self.station_durations = station_durations;
+        self
+    }
+
+    /// Sets the chain of dependent stations that took the longest.
+    #[must_use]
+    pub fn with_critical_path(mut self, critical_path: CriticalPath) -> Self {
+        self.critical_path = critical_path;
+        self
+    }
+
+    /// Sets the mapping from the train's outcome to a process exit code.
+    #[must_use]
+    pub fn with_exit_code_policy(mut self, exit_code_policy: ExitCodePolicy) -> Self {
+        self.exit_code_policy = exit_code_policy;
+        self
+    }
+
+    /// Sets the result of cleaning each station whose create visit failed.
+    #[must_use]
+    pub fn with_station_rollbacks(
+        mut self,
+        station_rollbacks: IndexMap<StationRtId, RollbackOutcome<E>>,
+    ) -> Self {
+        self.station_rollbacks = station_rollbacks;
+        self
+    }
+
+    /// Sets the outputs registered by each station that registered any.
+    #[must_use]
+    pub fn with_station_artifacts(
+        mut self,
+        station_artifacts: IndexMap<StationRtId, Vec<StationArtifact>>,
+    ) -> Self {
+        self.station_artifacts = station_artifacts;
+        self
+    }
+
+    /// Sets the directory that station artifacts were collected into.
+    #[must_use]
+    pub fn with_artifacts_dir(mut self, artifacts_dir: PathBuf) -> Self {
+        self.artifacts_dir = Some(artifacts_dir);
+        self
+    }
+
+    /// Tears down the train resources that implement [`ResourceTeardown`],
+    /// recording errors as teardown warnings.
+    ///
+    /// [`ResourceTeardown`]: choochoo_cfg_model::rt::ResourceTeardown
+    pub async fn resources_teardown(mut self) -> Self {
+        let teardown_warnings = self.train_resources.teardown().await;
+        self.teardown_warnings.extend(teardown_warnings);
+        self
+    }
+
     /// Record of what happened during a train's drive.
     pub fn train_resources(&self) -> &TrainResources<E> {
         &self.train_resources
@@ -32,6 +189,213 @@ where
     pub fn res_ids(&self) -> &ResIds {
         &self.res_ids
     }
+
+    /// Resource IDs produced by each station, grouped by station.
+    ///
+    /// Stations that did not produce resource IDs are not included.
+    pub fn station_res_ids(&self) -> &IndexMap<StationRtId, ResIds> {
+        &self.station_res_ids
+    }
+
+    /// Minimum severity of recorded diagnostics that fails the train.
+    pub fn severity_threshold(&self) -> Severity {
+        self.severity_threshold
+    }
+
+    /// Stations that did not complete before the train's deadline.
+    ///
+    /// These stations have the [`OpStatus::DeadlineExceeded`] status.
+    ///
+    /// [`OpStatus::DeadlineExceeded`]: choochoo_cfg_model::rt::OpStatus::DeadlineExceeded
+    pub fn stations_deadline_exceeded(&self) -> &[StationRtId] {
+        &self.stations_deadline_exceeded
+    }
+
+    /// Sub-steps of each station that has them.
+    ///
+    /// Stations without sub-steps are not included.
+    pub fn station_sub_steps(&self) -> &IndexMap<StationRtId, Vec<SubStep>> {
+        &self.station_sub_steps
+    }
+
+    /// Measurements of stations waiting to be visited.
+    pub fn queue_metrics(&self) -> &QueueMetrics {
+        &self.queue_metrics
+    }
+
+    /// Time each visited station's check and work took.
+    ///
+    /// This excludes time spent waiting for limited resources. Stations that
+    /// were not visited, or whose visit was interrupted, are not included.
+    pub fn station_durations(&self) -> &IndexMap<StationRtId, Duration> {
+        &self.station_durations
+    }
+
+    /// Chain of dependent stations that took the longest.
+    ///
+    /// This is calculated from the [`station_durations`], so stations that
+    /// were not visited are treated as taking no time.
+    ///
+    /// [`station_durations`]: Self::station_durations
+    pub fn critical_path(&self) -> &CriticalPath {
+        &self.critical_path
+    }
+
+    /// Mapping from the train's outcome to a process exit code.
+    pub fn exit_code_policy(&self) -> ExitCodePolicy {
+        self.exit_code_policy
+    }
+
+    /// Result of cleaning each station whose create visit failed.
+    ///
+    /// Stations are only rolled back when the train's rollback policy is
+    /// `RollbackPolicy::OnFailure`, and they returned resource IDs.
+    pub fn station_rollbacks(&self) -> &IndexMap<StationRtId, RollbackOutcome<E>> {
+        &self.station_rollbacks
+    }
+
+    /// Errors from tearing down the train resources.
+    ///
+    /// Like station warnings, these only fail the train when the severity
+    /// threshold is at or below [`Severity::Warning`].
+    pub fn teardown_warnings(&self) -> &[E] {
+        &self.teardown_warnings
+    }
+
+    /// Outputs registered by each station that registered any.
+    pub fn station_artifacts(&self) -> &IndexMap<StationRtId, Vec<StationArtifact>> {
+        &self.station_artifacts
+    }
+
+    /// Directory that station artifacts were collected into.
+    ///
+    /// This is `None` when no station registered an artifact, or when the
+    /// destination's file system is not native.
+    pub fn artifacts_dir(&self) -> Option<&Path> {
+        self.artifacts_dir.as_deref()
+    }
+
+    /// Returns the side effects that stations did not run because the train
+    /// was a dry run, in the order they were recorded.
+    pub async fn effects_skipped(&self) -> Vec<EffectRecord> {
+        let effect = self
+            .train_resources
+            .try_borrow::<Effect>()
+            .map(|effect| Effect::clone(&effect));
+        match effect {
+            Ok(effect) => effect.records().await,
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Returns the errors recorded when visiting stations, in the order they
+    /// were recorded.
+    ///
+    /// The returned guard does not borrow the report, but errors cannot be
+    /// recorded while it is held.
+    pub async fn station_error_records(&self) -> OwnedRwLockReadGuard<Vec<StationErrorRecord<E>>> {
+        let station_errors = StationErrors::clone(&self.train_resources.station_errors());
+        station_errors.read_owned().await
+    }
+
+    /// Returns the errors recorded for a station, in the order they were
+    /// recorded.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_rt_id`: Runtime ID of the station whose errors to return.
+    pub async fn station_errors_for(&self, station_rt_id: StationRtId) -> Vec<StationErrorRecord<E>>
+    where
+        E: Clone,
+    {
+        self.station_error_records()
+            .await
+            .iter()
+            .filter(|record| record.station_rt_id() == station_rt_id)
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the number of errors recorded for a station in a phase.
+    ///
+    /// This is the number of failed attempts of the phase.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_rt_id`: Runtime ID of the station whose errors to count.
+    /// * `phase`: Phase of the visit whose errors to count.
+    pub async fn station_error_count(
+        &self,
+        station_rt_id: StationRtId,
+        phase: StationErrorPhase,
+    ) -> usize {
+        self.station_error_records()
+            .await
+            .iter()
+            .filter(|record| record.station_rt_id() == station_rt_id && record.phase() == phase)
+            .count()
+    }
+
+    /// Returns the most recent error recorded for each station that recorded
+    /// any, in the order the stations first recorded an error.
+    pub async fn station_errors_latest(&self) -> IndexMap<StationRtId, E>
+    where
+        E: Clone,
+    {
+        self.station_error_records().await.iter().fold(
+            IndexMap::new(),
+            |mut station_errors_latest, record| {
+                station_errors_latest.insert(record.station_rt_id(), record.error().clone());
+                station_errors_latest
+            },
+        )
+    }
+
+    /// Returns the overall result of the train's drive.
+    ///
+    /// [`TrainOutcome::DeadlineExceeded`] takes precedence over
+    /// [`TrainOutcome::Failed`].
+    pub async fn outcome(&self) -> TrainOutcome {
+        if !self.stations_deadline_exceeded.is_empty() {
+            TrainOutcome::DeadlineExceeded
+        } else if self.is_failed().await {
+            TrainOutcome::Failed
+        } else {
+            TrainOutcome::Success
+        }
+    }
+
+    /// Returns the process exit code for the train's outcome.
+    ///
+    /// The exit code is chosen by the report's [`ExitCodePolicy`].
+    pub async fn exit_code(&self) -> i32 {
+        self.exit_code_policy.exit_code(self.outcome().await)
+    }
+
+    /// Returns whether the train failed to reach its destination.
+    ///
+    /// This is `true` when any station recorded an error, when any station did
+    /// not complete before the deadline, or when any station or resource
+    /// teardown recorded a warning and the severity threshold is at or below
+    /// [`Severity::Warning`].
+    pub async fn is_failed(&self) -> bool {
+        if !self.stations_deadline_exceeded.is_empty() {
+            return true;
+        }
+
+        if !self.train_resources.station_errors().is_empty().await {
+            return true;
+        }
+
+        self.severity_threshold <= Severity::Warning
+            && (!self.teardown_warnings.is_empty()
+                || !self
+                    .train_resources
+                    .station_warnings()
+                    .read()
+                    .await
+                    .is_empty())
+    }
 }
 
 impl<E> Default for TrainReport<E>
@@ -42,6 +406,18 @@ where
         Self {
             train_resources: TrainResources::<E>::new(),
             res_ids: ResIds::default(),
+            station_res_ids: IndexMap::new(),
+            severity_threshold: Severity::Error,
+            stations_deadline_exceeded: Vec::new(),
+            station_sub_steps: IndexMap::new(),
+            queue_metrics: QueueMetrics::new(),
+            station_durations: IndexMap::new(),
+            critical_path: CriticalPath::default(),
+            exit_code_policy: ExitCodePolicy::default(),
+            station_rollbacks: IndexMap::new(),
+            teardown_warnings: Vec::new(),
+            station_artifacts: IndexMap::new(),
+            artifacts_dir: None,
         }
     }
 }
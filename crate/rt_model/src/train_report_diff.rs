@@ -0,0 +1,146 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use choochoo_cfg_model::{indexmap::IndexMap, rt::ResIdLogical, StationId};
+
+use crate::{StationDurationRegression, TrainReportRecord};
+
+/// Differences between the reports of two train drives.
+///
+/// This is intended to summarize what changed between a previous and the
+/// current execution, such as to comment on a pull request in CI.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TrainReportDiff {
+    /// Resource IDs in the current run that were not in the previous run, or
+    /// whose physical ID changed.
+    res_ids_created: IndexMap<StationId, BTreeMap<ResIdLogical, serde_json::Value>>,
+    /// Stations that failed in the current run, but not in the previous run.
+    stations_newly_failed: Vec<StationId>,
+    /// Stations whose visit took significantly longer in the current run.
+    stations_duration_regressed: Vec<StationDurationRegression>,
+}
+
+impl TrainReportDiff {
+    /// Default minimum increase in duration for a station's duration to be
+    /// regarded as regressed.
+    pub const REGRESSION_INCREASE_MIN_DEFAULT: Duration = Duration::from_secs(1);
+    /// Default ratio of the current to previous duration above which a
+    /// station's duration is regarded as regressed.
+    pub const REGRESSION_RATIO_DEFAULT: f64 = 1.5;
+
+    /// Returns the differences between two train reports, using the default
+    /// regression thresholds.
+    ///
+    /// # Parameters
+    ///
+    /// * `previous`: Record of the earlier run.
+    /// * `current`: Record of the later run.
+    pub fn new(previous: &TrainReportRecord, current: &TrainReportRecord) -> Self {
+        Self::compare(
+            previous,
+            current,
+            Self::REGRESSION_RATIO_DEFAULT,
+            Self::REGRESSION_INCREASE_MIN_DEFAULT,
+        )
+    }
+
+    /// Returns the differences between two train reports.
+    ///
+    /// A station's duration is regressed when the current duration is more
+    /// than `regression_ratio` times the previous duration, and at least
+    /// `regression_increase_min` longer. The minimum increase avoids reporting
+    /// noise from stations that take very little time.
+    ///
+    /// # Parameters
+    ///
+    /// * `previous`: Record of the earlier run.
+    /// * `current`: Record of the later run.
+    /// * `regression_ratio`: Ratio of current to previous duration above which
+    ///   a station is regressed.
+    /// * `regression_increase_min`: Minimum increase in duration for a station
+    ///   to be regressed.
+    pub fn compare(
+        previous: &TrainReportRecord,
+        current: &TrainReportRecord,
+        regression_ratio: f64,
+        regression_increase_min: Duration,
+    ) -> Self {
+        let mut train_report_diff = Self::default();
+
+        current.stations.iter().for_each(|station_current| {
+            let station_id = &station_current.station_id;
+            let station_previous = previous.station(station_id);
+
+            let res_ids_created = station_current
+                .res_ids
+                .iter()
+                .filter(|&(res_id_logical, res_id_physical)| {
+                    station_previous
+                        .and_then(|station_previous| station_previous.res_ids.get(res_id_logical))
+                        != Some(res_id_physical)
+                })
+                .map(|(res_id_logical, res_id_physical)| {
+                    (res_id_logical.clone(), res_id_physical.clone())
+                })
+                .collect::<BTreeMap<_, _>>();
+            if !res_ids_created.is_empty() {
+                train_report_diff
+                    .res_ids_created
+                    .insert(station_id.clone(), res_ids_created);
+            }
+
+            let failed_previous = station_previous
+                .map(|station_previous| station_previous.failed)
+                .unwrap_or(false);
+            if station_current.failed && !failed_previous {
+                train_report_diff
+                    .stations_newly_failed
+                    .push(station_id.clone());
+            }
+
+            let durations = station_previous
+                .and_then(|station_previous| station_previous.duration)
+                .zip(station_current.duration);
+            if let Some((duration_previous, duration_current)) = durations {
+                let is_regressed = duration_current > duration_previous.mul_f64(regression_ratio)
+                    && duration_current.saturating_sub(duration_previous)
+                        >= regression_increase_min;
+                if is_regressed {
+                    train_report_diff
+                        .stations_duration_regressed
+                        .push(StationDurationRegression {
+                            station_id: station_id.clone(),
+                            duration_previous,
+                            duration_current,
+                        });
+                }
+            }
+        });
+
+        train_report_diff
+    }
+
+    /// Resource IDs in the current run that were not in the previous run, or
+    /// whose physical ID changed, grouped by station.
+    pub fn res_ids_created(
+        &self,
+    ) -> &IndexMap<StationId, BTreeMap<ResIdLogical, serde_json::Value>> {
+        &self.res_ids_created
+    }
+
+    /// Stations that failed in the current run, but not in the previous run.
+    pub fn stations_newly_failed(&self) -> &[StationId] {
+        &self.stations_newly_failed
+    }
+
+    /// Stations whose visit took significantly longer in the current run.
+    pub fn stations_duration_regressed(&self) -> &[StationDurationRegression] {
+        &self.stations_duration_regressed
+    }
+
+    /// Returns whether there are no differences worth reporting.
+    pub fn is_empty(&self) -> bool {
+        self.res_ids_created.is_empty()
+            && self.stations_newly_failed.is_empty()
+            && self.stations_duration_regressed.is_empty()
+    }
+}
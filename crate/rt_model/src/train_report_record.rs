@@ -0,0 +1,80 @@
+use std::{collections::BTreeMap, fmt};
+
+use serde::{Deserialize, Serialize};
+
+use choochoo_cfg_model::{rt::OpStatus, StationId};
+
+use crate::{Destination, Error, StationRecord, TrainReport};
+
+/// Persistable record of a train's drive.
+///
+/// Unlike [`TrainReport`], this can be serialized, so that reports from
+/// different executions can be compared with a [`TrainReportDiff`].
+///
+/// [`TrainReportDiff`]: crate::TrainReportDiff
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct TrainReportRecord {
+    /// Record of each station, in insertion order.
+    pub stations: Vec<StationRecord>,
+}
+
+impl TrainReportRecord {
+    /// Returns a record of the stations in the destination and the report of
+    /// the train's drive.
+    ///
+    /// # Parameters
+    ///
+    /// * `dest`: Destination the train drove to.
+    /// * `train_report`: Report of the train's drive.
+    pub fn new<E>(dest: &Destination<E>, train_report: &TrainReport<E>) -> Result<Self, Error<E>>
+    where
+        E: fmt::Debug + Send + Sync + 'static,
+    {
+        let station_durations = train_report.station_durations();
+        let station_res_ids = train_report.station_res_ids();
+        let stations = dest
+            .stations()
+            .map(|station| {
+                let station_id = station.spec.id().clone();
+                let failed = matches!(
+                    station.progress.op_status,
+                    OpStatus::SetupFail
+                        | OpStatus::CheckFail
+                        | OpStatus::WorkFail
+                        | OpStatus::VerifyFail
+                        | OpStatus::Panicked
+                );
+                let duration = station_durations.get(&station.rt_id).copied();
+                let res_ids = station_res_ids
+                    .get(&station.rt_id)
+                    .into_iter()
+                    .flat_map(|res_ids| res_ids.iter())
+                    .map(|(res_id_logical, res_id_physical)| {
+                        let res_id_physical =
+                            serde_json::to_value(res_id_physical).map_err(|error| {
+                                let station_id = station_id.clone();
+                                Error::ResIdSerialize { station_id, error }
+                            })?;
+                        Ok((res_id_logical.clone(), res_id_physical))
+                    })
+                    .collect::<Result<BTreeMap<_, _>, Error<E>>>()?;
+
+                Ok(StationRecord {
+                    station_id,
+                    failed,
+                    duration,
+                    res_ids,
+                })
+            })
+            .collect::<Result<Vec<StationRecord>, Error<E>>>()?;
+
+        Ok(Self { stations })
+    }
+
+    /// Returns the record of the station with the given ID.
+    pub fn station(&self, station_id: &StationId) -> Option<&StationRecord> {
+        self.stations
+            .iter()
+            .find(|station_record| &station_record.station_id == station_id)
+    }
+}
@@ -0,0 +1,21 @@
+/// Amount of detail in progress and report output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Verbosity {
+    /// Only errors and the final summary are shown.
+    ///
+    /// Live progress is not rendered.
+    Quiet,
+    /// Each station's status is shown.
+    ///
+    /// This is the default.
+    Normal,
+    /// Each station's status is shown, along with how long it took, the
+    /// result of its check, and the resource IDs produced.
+    Verbose,
+}
+
+impl Default for Verbosity {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
@@ -0,0 +1,85 @@
+use std::fmt;
+
+/// Way in which a station's check and work functions disagree, found by a
+/// [`CheckFnFuzzer`].
+///
+/// Each variant records the index of the iteration it was found in, starting
+/// at `0`.
+///
+/// [`CheckFnFuzzer`]: crate::CheckFnFuzzer
+#[derive(Debug)]
+pub enum CheckFnDivergence<E> {
+    /// The check function returned `WorkRequired` after the work function
+    /// succeeded.
+    WorkNotConverged {
+        /// Index of the iteration.
+        iteration: usize,
+    },
+    /// The check function returned `WorkRequired` after it returned
+    /// `WorkNotRequired` at the end of the previous iteration, with nothing
+    /// run in between.
+    CheckNotStable {
+        /// Index of the iteration.
+        iteration: usize,
+    },
+    /// The check function returned an error.
+    CheckFail {
+        /// Index of the iteration.
+        iteration: usize,
+        /// Error returned by the check function.
+        error: E,
+    },
+    /// The work function returned an error.
+    WorkFail {
+        /// Index of the iteration.
+        iteration: usize,
+        /// Whether the check function reported the work was required before
+        /// the work function was run.
+        work_required: bool,
+        /// Error returned by the work function.
+        error: E,
+    },
+}
+
+impl<E> CheckFnDivergence<E> {
+    /// Returns the index of the iteration the divergence was found in.
+    pub fn iteration(&self) -> usize {
+        match self {
+            Self::WorkNotConverged { iteration }
+            | Self::CheckNotStable { iteration }
+            | Self::CheckFail { iteration, .. }
+            | Self::WorkFail { iteration, .. } => *iteration,
+        }
+    }
+}
+
+impl<E> fmt::Display for CheckFnDivergence<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WorkNotConverged { iteration } => write!(
+                f,
+                "Iteration {iteration}: the check function returned `WorkRequired` after the work function succeeded. Make sure the work function updates what the check function reads."
+            ),
+            Self::CheckNotStable { iteration } => write!(
+                f,
+                "Iteration {iteration}: the check function returned `WorkRequired` after it returned `WorkNotRequired`, with nothing run in between. Make sure the check function only depends on the station's state."
+            ),
+            Self::CheckFail { iteration, .. } => {
+                write!(f, "Iteration {iteration}: the check function failed.")
+            }
+            Self::WorkFail {
+                iteration,
+                work_required: true,
+                ..
+            } => write!(f, "Iteration {iteration}: the work function failed."),
+            Self::WorkFail {
+                iteration,
+                work_required: false,
+                ..
+            } => write!(
+                f,
+                "Iteration {iteration}: the work function failed when run again after the work was done. Make sure the work function succeeds when the station is already in its desired state."
+            ),
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use choochoo_cfg_model::rt::CheckStatus;
+
+use crate::CheckFnDivergence;
+
+/// Outcome of running a station's functions using a [`CheckFnFuzzer`].
+///
+/// [`CheckFnFuzzer`]: crate::CheckFnFuzzer
+#[derive(Debug)]
+pub struct CheckFnFuzzReport<E> {
+    /// Number of iterations that completed without divergence.
+    iterations: usize,
+    /// Statuses returned by the check function, in the order they were
+    /// returned.
+    check_statuses: Vec<CheckStatus>,
+    /// First divergence found, if any.
+    divergence: Option<CheckFnDivergence<E>>,
+}
+
+impl<E> CheckFnFuzzReport<E> {
+    /// Returns a new `CheckFnFuzzReport`.
+    pub(crate) fn new(
+        iterations: usize,
+        check_statuses: Vec<CheckStatus>,
+        divergence: Option<CheckFnDivergence<E>>,
+    ) -> Self {
+        Self {
+            iterations,
+            check_statuses,
+            divergence,
+        }
+    }
+
+    /// Returns the number of iterations that completed without divergence.
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Returns the statuses returned by the check function, in the order they
+    /// were returned.
+    ///
+    /// Each iteration checks the station before and after its work function
+    /// is run.
+    pub fn check_statuses(&self) -> &[CheckStatus] {
+        &self.check_statuses
+    }
+
+    /// Returns the first divergence found, if any.
+    pub fn divergence(&self) -> Option<&CheckFnDivergence<E>> {
+        self.divergence.as_ref()
+    }
+
+    /// Returns the first divergence found, if any.
+    pub fn into_divergence(self) -> Option<CheckFnDivergence<E>> {
+        self.divergence
+    }
+
+    /// Returns whether the check and work functions were consistent for every
+    /// iteration.
+    pub fn is_consistent(&self) -> bool {
+        self.divergence.is_none()
+    }
+}
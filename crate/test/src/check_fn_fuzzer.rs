@@ -0,0 +1,176 @@
+use std::fmt;
+
+use choochoo_cfg_model::{
+    rt::{CheckStatus, StationMutRef, TrainResources},
+    StationSpec,
+};
+use choochoo_rt_logic::ResourceInitializer;
+use choochoo_rt_model::error::StationSpecError;
+use futures::stream::StreamExt;
+
+use crate::{CheckFnDivergence, CheckFnFuzzReport, StationHarness, StationHarnessError};
+
+/// Default number of iterations to run.
+const ITERATIONS_DEFAULT: usize = 10;
+
+/// Repeatedly runs a station's create check and work functions in a temporary
+/// workspace, to verify that they are consistent.
+///
+/// Each iteration runs the check function, the work function, then the check
+/// function again. The work function is run even when the check function
+/// reports that the work is not required, so that each iteration also verifies
+/// that the work is idempotent.
+///
+/// The fuzzer stops at the first [`CheckFnDivergence`], which is when:
+///
+/// * The check function returns `WorkRequired` after the work succeeds.
+/// * The check function returns `WorkRequired` at the start of an iteration,
+///   after it returned `WorkNotRequired` at the end of the previous one.
+/// * The check or work function returns an error.
+///
+/// ```rust,ignore
+/// let report = CheckFnFuzzer::new(station_spec)?.run().await?;
+///
+/// assert!(report.is_consistent(), "{}", report.divergence().unwrap());
+/// ```
+#[derive(Debug)]
+pub struct CheckFnFuzzer<E> {
+    /// Harness holding the temporary workspace and the station under test.
+    harness: StationHarness<E>,
+    /// Number of iterations to run.
+    iterations: usize,
+}
+
+impl<E> CheckFnFuzzer<E>
+where
+    E: From<StationSpecError> + fmt::Debug + Send + Sync + 'static,
+{
+    /// Returns a new `CheckFnFuzzer` for the given station.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_spec`: Specification of the station under test.
+    pub fn new(station_spec: StationSpec<E>) -> Result<Self, StationHarnessError<E>> {
+        let harness = StationHarness::new(station_spec)?;
+
+        Ok(Self {
+            harness,
+            iterations: ITERATIONS_DEFAULT,
+        })
+    }
+
+    /// Sets the number of iterations to run.
+    ///
+    /// Defaults to `10`.
+    #[must_use]
+    pub fn with_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Returns the harness holding the temporary workspace and the station
+    /// under test.
+    pub fn harness(&self) -> &StationHarness<E> {
+        &self.harness
+    }
+
+    /// Runs the station's setup function, then alternates its check and work
+    /// functions for each iteration.
+    ///
+    /// Errors returned by the station's check and work functions are recorded
+    /// as a [`CheckFnDivergence`] in the report.
+    pub async fn run(&self) -> Result<CheckFnFuzzReport<E>, StationHarnessError<E>> {
+        let dest = self.harness.dest();
+        let mut train_resources = TrainResources::new();
+        ResourceInitializer::initialize(dest, &mut train_resources)
+            .await
+            .map_err(StationHarnessError::ResourceInit)?;
+
+        for station in dest.try_stations_mut() {
+            let mut station = station.map_err(StationHarnessError::ResourceInit)?;
+            station
+                .create_setup(&mut train_resources)
+                .await
+                .map_err(StationHarnessError::StationSetup)?;
+        }
+
+        let mut stations = Box::pin(dest.try_stations_mut_stream());
+        let mut station = stations
+            .next()
+            .await
+            .expect("Expected harness destination to contain the station under test.")
+            .map_err(StationHarnessError::ResourceInit)?;
+
+        let mut check_statuses = Vec::with_capacity(self.iterations * 2);
+        let divergence = self
+            .iterations_run(&mut station, &train_resources, &mut check_statuses)
+            .await?;
+        let iterations = divergence
+            .as_ref()
+            .map(CheckFnDivergence::iteration)
+            .unwrap_or(self.iterations);
+
+        Ok(CheckFnFuzzReport::new(
+            iterations,
+            check_statuses,
+            divergence,
+        ))
+    }
+
+    /// Runs each iteration, returning the first divergence found.
+    async fn iterations_run(
+        &self,
+        station: &mut StationMutRef<'_, E>,
+        train_resources: &TrainResources<E>,
+        check_statuses: &mut Vec<CheckStatus>,
+    ) -> Result<Option<CheckFnDivergence<E>>, StationHarnessError<E>> {
+        for iteration in 0..self.iterations {
+            let work_required = match Self::check(station, train_resources).await? {
+                Ok(check_status) => {
+                    check_statuses.push(check_status);
+                    check_status == CheckStatus::WorkRequired
+                }
+                Err(error) => return Ok(Some(CheckFnDivergence::CheckFail { iteration, error })),
+            };
+            if work_required && iteration > 0 {
+                return Ok(Some(CheckFnDivergence::CheckNotStable { iteration }));
+            }
+
+            let work_result = station
+                .create_visit(train_resources)
+                .await
+                .map_err(StationHarnessError::StationFnBorrow)?;
+            if let Err((_res_ids, error)) = work_result {
+                return Ok(Some(CheckFnDivergence::WorkFail {
+                    iteration,
+                    work_required,
+                    error,
+                }));
+            }
+
+            match Self::check(station, train_resources).await? {
+                Ok(check_status) => {
+                    check_statuses.push(check_status);
+                    if check_status == CheckStatus::WorkRequired {
+                        return Ok(Some(CheckFnDivergence::WorkNotConverged { iteration }));
+                    }
+                }
+                Err(error) => return Ok(Some(CheckFnDivergence::CheckFail { iteration, error })),
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Runs the station's check function.
+    async fn check(
+        station: &mut StationMutRef<'_, E>,
+        train_resources: &TrainResources<E>,
+    ) -> Result<Result<CheckStatus, E>, StationHarnessError<E>> {
+        station
+            .create_check(train_resources)
+            .await
+            .ok_or(StationHarnessError::CheckFnNone)?
+            .map_err(StationHarnessError::StationFnBorrow)
+    }
+}
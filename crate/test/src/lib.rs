@@ -0,0 +1,20 @@
+//! Test helpers for authors of choochoo stations.
+//!
+//! Use [`StationHarness`] to visit a single station in a temporary workspace,
+//! without constructing a [`Destination`] by hand, and [`CheckFnFuzzer`] to
+//! verify that a station's check and work functions are consistent.
+//!
+//! [`Destination`]: choochoo_rt_model::Destination
+
+pub use crate::{
+    check_fn_divergence::CheckFnDivergence, check_fn_fuzz_report::CheckFnFuzzReport,
+    check_fn_fuzzer::CheckFnFuzzer, station_harness::StationHarness,
+    station_harness_error::StationHarnessError, station_harness_report::StationHarnessReport,
+};
+
+mod check_fn_divergence;
+mod check_fn_fuzz_report;
+mod check_fn_fuzzer;
+mod station_harness;
+mod station_harness_error;
+mod station_harness_report;
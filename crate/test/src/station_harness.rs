@@ -0,0 +1,135 @@
+use std::{fmt, path::Path};
+
+use choochoo_cfg_model::{
+    rt::{OpStatus, ProgressLimit, StationRtId, VisitOp},
+    StationSpec,
+};
+use choochoo_resource::Profile;
+use choochoo_rt_logic::Train;
+use choochoo_rt_model::{error::StationSpecError, Destination, WorkspaceSpec};
+use tempfile::TempDir;
+
+use crate::{StationHarnessError, StationHarnessReport};
+
+/// Visits a single station in a temporary workspace.
+///
+/// The workspace directory, and the profile and history directories within
+/// it, are removed when the harness is dropped.
+///
+/// ```rust,ignore
+/// let report = StationHarness::run_create(station_spec).await?;
+///
+/// assert_eq!(OpStatus::WorkSuccess, report.op_status());
+/// ```
+#[derive(Debug)]
+pub struct StationHarness<E> {
+    /// Temporary directory used as the workspace.
+    workspace_dir: TempDir,
+    /// Destination containing only the station under test.
+    dest: Destination<E>,
+    /// Runtime ID of the station under test.
+    station_rt_id: StationRtId,
+}
+
+impl<E> StationHarness<E>
+where
+    E: From<StationSpecError> + fmt::Debug + Send + Sync + 'static,
+{
+    /// Returns a new `StationHarness` for the given station.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_spec`: Specification of the station under test.
+    pub fn new(station_spec: StationSpec<E>) -> Result<Self, StationHarnessError<E>> {
+        let workspace_dir = tempfile::tempdir().map_err(StationHarnessError::WorkspaceDirCreate)?;
+
+        let mut dest_builder = Destination::<E>::builder()
+            .with_workspace_spec(WorkspaceSpec::Path(workspace_dir.path().to_path_buf()))
+            .with_profile(Profile::default());
+        let station_rt_id = dest_builder.add_station(station_spec);
+        let dest = dest_builder
+            .build()
+            .map_err(StationHarnessError::Destination)?;
+
+        Ok(Self {
+            workspace_dir,
+            dest,
+            station_rt_id,
+        })
+    }
+
+    /// Runs the station's create functions in a new temporary workspace.
+    ///
+    /// This runs the setup, check, and work functions as [`Train::reach`]
+    /// does.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_spec`: Specification of the station under test.
+    pub async fn run_create(
+        station_spec: StationSpec<E>,
+    ) -> Result<StationHarnessReport<E>, StationHarnessError<E>> {
+        Self::new(station_spec)?.run(VisitOp::Create).await
+    }
+
+    /// Runs the station's clean functions in a new temporary workspace.
+    ///
+    /// # Parameters
+    ///
+    /// * `station_spec`: Specification of the station under test.
+    pub async fn run_clean(
+        station_spec: StationSpec<E>,
+    ) -> Result<StationHarnessReport<E>, StationHarnessError<E>> {
+        Self::new(station_spec)?.run(VisitOp::Clean).await
+    }
+
+    /// Visits the station, returning the harness with the train's report.
+    ///
+    /// To visit the station again in the same workspace, such as to clean
+    /// after creating, call [`StationHarnessReport::into_harness`] and run the
+    /// harness again.
+    ///
+    /// # Parameters
+    ///
+    /// * `visit_op`: Operation to visit the station for.
+    pub async fn run(
+        mut self,
+        visit_op: VisitOp,
+    ) -> Result<StationHarnessReport<E>, StationHarnessError<E>> {
+        let train_report = Train::default()
+            .reach_deterministic(&mut self.dest, visit_op)
+            .await
+            .map_err(StationHarnessError::Train)?;
+
+        Ok(StationHarnessReport::new(self, train_report))
+    }
+
+    /// Returns the directory used as the workspace.
+    pub fn workspace_dir(&self) -> &Path {
+        self.workspace_dir.path()
+    }
+
+    /// Returns the destination containing only the station under test.
+    pub fn dest(&self) -> &Destination<E> {
+        &self.dest
+    }
+
+    /// Returns the runtime ID of the station under test.
+    pub fn station_rt_id(&self) -> StationRtId {
+        self.station_rt_id
+    }
+
+    /// Returns the operation status of the station under test.
+    pub fn op_status(&self) -> OpStatus {
+        self.dest.station_progresses()[&self.station_rt_id]
+            .borrow()
+            .op_status
+    }
+
+    /// Returns the progress limit of the station under test.
+    pub fn progress_limit(&self) -> ProgressLimit {
+        self.dest.station_progresses()[&self.station_rt_id]
+            .borrow()
+            .progress_limit()
+    }
+}
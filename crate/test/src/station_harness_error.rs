@@ -0,0 +1,71 @@
+use std::fmt;
+
+use choochoo_cfg_model::rt_map::BorrowFail;
+use choochoo_rt_model::Error;
+
+/// Errors when visiting a station using a [`StationHarness`] or a
+/// [`CheckFnFuzzer`].
+///
+/// [`CheckFnFuzzer`]: crate::CheckFnFuzzer
+/// [`StationHarness`]: crate::StationHarness
+#[derive(Debug)]
+pub enum StationHarnessError<E> {
+    /// Failed to create the temporary workspace directory.
+    WorkspaceDirCreate(std::io::Error),
+    /// Failed to build the destination for the station.
+    Destination(Error<E>),
+    /// The train failed to visit the station.
+    ///
+    /// Errors returned by the station's functions are recorded in the
+    /// [`StationHarnessReport`] instead.
+    ///
+    /// [`StationHarnessReport`]: crate::StationHarnessReport
+    Train(Error<E>),
+    /// Failed to initialize the resources for the station's functions.
+    ResourceInit(Error<E>),
+    /// The station's setup function failed.
+    StationSetup(E),
+    /// The station has no create check function to fuzz.
+    CheckFnNone,
+    /// Failed to borrow the resources for a station function.
+    StationFnBorrow(BorrowFail),
+}
+
+impl<E> fmt::Display for StationHarnessError<E>
+where
+    E: 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WorkspaceDirCreate(_) => {
+                write!(f, "Failed to create temporary workspace directory.")
+            }
+            Self::Destination(_) => write!(f, "Failed to build destination for the station."),
+            Self::Train(_) => write!(f, "Train failed to visit the station."),
+            Self::ResourceInit(_) => write!(f, "Failed to initialize resources for the station."),
+            Self::StationSetup(_) => write!(f, "Station setup function failed."),
+            Self::CheckFnNone => write!(f, "Station does not have a create check function."),
+            Self::StationFnBorrow(borrow_fail) => write!(
+                f,
+                "Failed to borrow resources for a station function: {borrow_fail:?}."
+            ),
+        }
+    }
+}
+
+impl<E> std::error::Error for StationHarnessError<E>
+where
+    E: fmt::Debug + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::WorkspaceDirCreate(error) => Some(error),
+            Self::Destination(error) => Some(error),
+            Self::Train(error) => Some(error),
+            Self::ResourceInit(error) => Some(error),
+            Self::StationSetup(_) => None,
+            Self::CheckFnNone => None,
+            Self::StationFnBorrow(_) => None,
+        }
+    }
+}
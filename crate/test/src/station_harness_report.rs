@@ -0,0 +1,77 @@
+use std::fmt;
+
+use choochoo_cfg_model::rt::{OpStatus, ResIds, TrainResources};
+use choochoo_rt_model::{error::StationSpecError, TrainReport};
+
+use crate::StationHarness;
+
+/// Outcome of visiting a station using a [`StationHarness`].
+#[derive(Debug)]
+pub struct StationHarnessReport<E> {
+    /// Harness used to visit the station.
+    harness: StationHarness<E>,
+    /// Record of what happened during the train's drive.
+    train_report: TrainReport<E>,
+}
+
+impl<E> StationHarnessReport<E>
+where
+    E: From<StationSpecError> + fmt::Debug + Send + Sync + 'static,
+{
+    /// Returns a new `StationHarnessReport`.
+    pub(crate) fn new(harness: StationHarness<E>, train_report: TrainReport<E>) -> Self {
+        Self {
+            harness,
+            train_report,
+        }
+    }
+
+    /// Returns the operation status of the station.
+    pub fn op_status(&self) -> OpStatus {
+        self.harness.op_status()
+    }
+
+    /// Returns the resource IDs produced by the station.
+    pub fn res_ids(&self) -> &ResIds {
+        self.train_report.res_ids()
+    }
+
+    /// Returns the resources present after the station was visited.
+    ///
+    /// This includes the errors and warnings recorded by the station.
+    pub fn train_resources(&self) -> &TrainResources<E> {
+        self.train_report.train_resources()
+    }
+
+    /// Returns the most recent error recorded by the station, if any.
+    pub async fn station_error(&self) -> Option<E>
+    where
+        E: Clone,
+    {
+        let station_rt_id = self.harness.station_rt_id();
+        self.train_resources()
+            .station_errors()
+            .read()
+            .await
+            .iter()
+            .rev()
+            .find(|record| record.station_rt_id() == station_rt_id)
+            .map(|record| record.error().clone())
+    }
+
+    /// Returns the harness used to visit the station.
+    pub fn harness(&self) -> &StationHarness<E> {
+        &self.harness
+    }
+
+    /// Returns the record of what happened during the train's drive.
+    pub fn train_report(&self) -> &TrainReport<E> {
+        &self.train_report
+    }
+
+    /// Returns the harness, so the station may be visited again in the same
+    /// workspace.
+    pub fn into_harness(self) -> StationHarness<E> {
+        self.harness
+    }
+}
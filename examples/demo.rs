@@ -1,9 +1,19 @@
 use choochoo::{
-    cfg_model::rt::VisitOp, cli_fmt::PlainTextFormatter, rt_logic::Train, rt_model::Destination,
+    cfg_model::{
+        rt::{TrainResources, VisitOp},
+        ResourceInitFn,
+    },
+    cli_fmt::{FormatterOpts, PlainTextFormatter},
+    resource::ProfileDir,
+    rt_logic::Train,
+    rt_model::Destination,
 };
+use futures::future::FutureExt;
+use reqwest::redirect::Policy;
 use tokio::runtime;
 
 use crate::{
+    artifact_server_dir::ArtifactServerDir,
     demo_error::DemoError,
     dependency_mode::DependencyMode,
     error::{ErrorCode, ErrorDetail},
@@ -86,7 +96,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 station_h,
             ] = dest_builder.add_stations([
                 StationB::build()?,
-                StationC::build(station_a)?,
+                StationC::build()?,
                 StationD::build()?,
                 StationE::build()?,
                 StationF::build()?,
@@ -121,10 +131,40 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
             Result::<_, Box<dyn std::error::Error>>::Ok(dest)
         }?;
-        let train_report = Train::default().reach(&mut dest, VisitOp::Create).await?;
+        // Shared by every station that makes HTTP requests.
+        let http_client = reqwest::Client::builder()
+            .redirect(Policy::none())
+            .build()?;
+        let train = Train::default()
+            .with_http_client(http_client)
+            .with_resource_init_fn(ResourceInitFn::new(
+                "artifact_server_dir",
+                |train_resources: &mut TrainResources<DemoError>| {
+                    let artifact_server_dir = {
+                        let profile_dir = train_resources.borrow::<ProfileDir>();
+                        ArtifactServerDir::new(profile_dir.to_path_buf())
+                    };
+                    train_resources.insert(artifact_server_dir);
+                    async { Ok(()) }.boxed_local()
+                },
+            ));
 
         let mut stdout = tokio::io::stdout();
-        PlainTextFormatter::fmt_errors(&mut stdout, &train_report.train_resources()).await?;
+        let train_report = match train.reach(&mut dest, VisitOp::Create).await {
+            Ok(train_report) => train_report,
+            Err(error) => {
+                PlainTextFormatter::fmt_error(&mut stdout, &error, &FormatterOpts::default())
+                    .await?;
+                std::process::exit(1);
+            }
+        };
+
+        PlainTextFormatter::fmt_errors(
+            &mut stdout,
+            &train_report.train_resources(),
+            &FormatterOpts::default(),
+        )
+        .await?;
 
         Result::<_, Box<dyn std::error::Error>>::Ok(())
     })?;
@@ -1,4 +1,6 @@
 pub const APP_ZIP_NAME: &'static str = "app.zip";
+/// Name that `StationA` publishes `app.zip` under, for other stations to read.
+pub const APP_ZIP_FILE: &'static str = "app_zip";
 
 /// Resource indicating the application's file length.
 #[derive(Debug)]
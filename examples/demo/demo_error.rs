@@ -1,10 +1,9 @@
 use std::fmt;
 
 use choochoo::{
-    cfg_model::srcerr::{
-        self,
-        codespan_reporting::diagnostic::{Diagnostic, Severity},
-        SourceError,
+    cfg_model::{
+        rt::StationResourceError,
+        srcerr::{codespan_reporting::diagnostic::Severity, SourceError},
     },
     resource::Files,
     rt_model::error::StationSpecError,
@@ -12,7 +11,7 @@ use choochoo::{
 
 use crate::{ErrorCode, ErrorDetail};
 
-#[derive(Debug)]
+#[derive(Debug, choochoo_derive::AsDiagnostic)]
 pub struct DemoError(pub SourceError<'static, ErrorCode, ErrorDetail, Files>);
 
 impl DemoError {
@@ -21,18 +20,6 @@ impl DemoError {
     }
 }
 
-impl choochoo::rt_model::error::AsDiagnostic<'static> for DemoError {
-    type Files = Files;
-
-    fn as_diagnostic(
-        &self,
-        files: &Self::Files,
-    ) -> Diagnostic<<Self::Files as srcerr::codespan_reporting::files::Files<'static>>::FileId>
-    {
-        SourceError::as_diagnostic(&self.0, files)
-    }
-}
-
 impl From<StationSpecError> for DemoError {
     fn from(error: StationSpecError) -> DemoError {
         let code = ErrorCode::StationSpecError;
@@ -42,6 +29,15 @@ impl From<StationSpecError> for DemoError {
     }
 }
 
+impl From<StationResourceError> for DemoError {
+    fn from(error: StationResourceError) -> DemoError {
+        let code = ErrorCode::StationResourceError;
+        let detail = ErrorDetail::StationResourceError(error);
+
+        DemoError::new(code, detail, Severity::Bug)
+    }
+}
+
 impl fmt::Display for DemoError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         self.0.fmt(f)
@@ -1,22 +1,23 @@
 use std::{borrow::Cow, fmt, path::Path};
 
 use choochoo::{
-    cfg_model::srcerr::{
-        self,
-        codespan::{FileId, Files, Span},
-        codespan_reporting::diagnostic::Label,
-        ErrorCode as _,
+    cfg_model::{
+        rt::StationResourceError,
+        srcerr::{
+            self,
+            codespan::{FileId, Files, Span},
+            codespan_reporting::diagnostic::Label,
+            ErrorCode as _,
+        },
     },
     rt_model::error::StationSpecError,
 };
 
 /// Error codes for simple example.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, choochoo_derive::ErrorCode)]
 pub enum ErrorCode {
     /// There is a bug with the station specification.
     StationSpecError,
-    /// Failed to build `reqwest::Client`.
-    ReqwestClientBuild,
     /// Unable to determine station directory.
     StationDirDiscover,
     /// Unable to determine file name of station file.
@@ -41,64 +42,20 @@ pub enum ErrorCode {
     AppZipWrite,
     /// Failed to link application to database.
     ApplicationDatabaseLink,
+    /// Station setup failed to borrow a resource.
+    StationResourceError,
 
     // === Errors during cleaning === //
     /// Failed to clean app.zip from artifact server.
+    #[error_code(code = 20)]
     CleanArtifactServerAppZip,
 }
 
-impl srcerr::ErrorCode for ErrorCode {
-    const ERROR_CODE_MAX: usize = 99;
-    const PREFIX: &'static str = "E";
-
-    fn code(self) -> usize {
-        match self {
-            Self::StationSpecError => 1,
-            Self::ReqwestClientBuild => 2,
-            Self::StationDirDiscover => 3,
-            Self::StationFileNameDiscover => 4,
-            Self::AppZipOpen => 5,
-            Self::ArtifactServerConnect => 6,
-            Self::AppZipReject => 7,
-            Self::DatabaseCreate => 8,
-            Self::WebServerAppZipOpen => 9,
-            Self::WebServerAppZipMetadata => 10,
-            Self::AppZipDownload => 11,
-            Self::AppZipStream => 12,
-            Self::AppZipWrite => 13,
-            Self::ApplicationDatabaseLink => 14,
-            Self::CleanArtifactServerAppZip => 20,
-        }
-    }
-
-    fn description(self) -> &'static str {
-        match self {
-            Self::StationSpecError => "There is a bug with the station specification.",
-            Self::ReqwestClientBuild => "Failed to build `reqwest::Client`.",
-            Self::StationDirDiscover => "Unable to determine station directory.",
-            Self::StationFileNameDiscover => "Unable to determine file name of station file.",
-            Self::AppZipOpen => "Failed to open `app.zip` to upload.",
-            Self::ArtifactServerConnect => "Failed to connect to artifact server.",
-            Self::AppZipReject => "Artifact server rejected `app.zip`.",
-            Self::DatabaseCreate => "Failed to create application database.",
-            Self::WebServerAppZipOpen => "Failed to open `app.zip` to check state.",
-            Self::WebServerAppZipMetadata => "Failed to read `app.zip` metadata.",
-            Self::AppZipDownload => "Application server failed to get `app.zip`.",
-            Self::AppZipStream => "`app.zip` download connection broke.",
-            Self::AppZipWrite => "Web server failed to write `app.zip` to disk.",
-            Self::ApplicationDatabaseLink => "Failed to link application to database.",
-            Self::CleanArtifactServerAppZip => "Failed to clean app.zip from artifact server.",
-        }
-    }
-}
-
 /// Error detail for demo.
 #[derive(Debug)]
 pub enum ErrorDetail {
     /// There is a bug with the station specification.
     StationSpecError(StationSpecError),
-    /// Failed to build `reqwest::Client`.
-    ReqwestClientBuild(reqwest::Error),
     /// Unable to determine station directory.
     StationDirDiscover {
         /// File path whose parent should be the station directory.
@@ -211,6 +168,8 @@ pub enum ErrorDetail {
         /// Underlying IO error.
         error: std::io::Error,
     },
+    /// Station setup failed to borrow a resource.
+    StationResourceError(StationResourceError),
 
     /// Failed to clean app.zip from artifact server.
     ///
@@ -232,7 +191,6 @@ impl<'files> srcerr::ErrorDetail<'files> for ErrorDetail {
     fn labels(&self) -> Vec<Label<FileId>> {
         match self {
             Self::StationSpecError(_error) => vec![],
-            Self::ReqwestClientBuild(_error) => vec![],
             Self::StationDirDiscover { .. } => vec![],
             Self::StationFileNameDiscover { .. } => vec![],
             Self::AppZipOpen {
@@ -339,6 +297,7 @@ impl<'files> srcerr::ErrorDetail<'files> for ErrorDetail {
                         .with_message("failed to link application to database"),
                 ]
             }
+            Self::StationResourceError(_error) => vec![],
             Self::CleanArtifactServerAppZip {
                 app_zip_path_file_id,
                 app_zip_path_span,
@@ -356,10 +315,6 @@ impl<'files> srcerr::ErrorDetail<'files> for ErrorDetail {
                 String::from("Make sure the `work_fn` updates what the `check_fn` is reading."),
                 error.to_string(),
             ],
-            Self::ReqwestClientBuild(error) => vec![
-                String::from("Make sure the `work_fn` updates what the `check_fn` is reading."),
-                error.to_string(),
-            ],
             Self::StationDirDiscover { station_file_path } => vec![
                 format!(
                     "`{}` is an invalid station file path.",
@@ -509,6 +464,12 @@ impl<'files> srcerr::ErrorDetail<'files> for ErrorDetail {
             Self::ApplicationDatabaseLink { .. } => {
                 vec![]
             }
+            Self::StationResourceError(error) => vec![
+                String::from(
+                    "Make sure the station's check, work, or verify function takes the resource as a parameter.",
+                ),
+                error.to_string(),
+            ],
             Self::CleanArtifactServerAppZip {
                 app_zip_path_file_id,
                 app_zip_path_span,
@@ -530,7 +491,6 @@ impl fmt::Display for ErrorDetail {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::StationSpecError(error) => error.fmt(f),
-            Self::ReqwestClientBuild(error) => error.fmt(f),
             Self::StationDirDiscover { .. } => {
                 write!(f, "{}", ErrorCode::StationDirDiscover.description())
             }
@@ -555,6 +515,7 @@ impl fmt::Display for ErrorDetail {
             Self::ApplicationDatabaseLink { .. } => {
                 write!(f, "{}", ErrorCode::ApplicationDatabaseLink.description())
             }
+            Self::StationResourceError(error) => error.fmt(f),
             Self::CleanArtifactServerAppZip { .. } => {
                 write!(f, "{}", ErrorCode::CleanArtifactServerAppZip.description())
             }
@@ -566,7 +527,6 @@ impl std::error::Error for ErrorDetail {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Self::StationSpecError(error) => Some(error),
-            Self::ReqwestClientBuild(error) => Some(error),
             Self::StationDirDiscover { .. } => None,
             Self::StationFileNameDiscover { .. } => None,
             Self::AppZipOpen { error, .. } => Some(error),
@@ -579,6 +539,7 @@ impl std::error::Error for ErrorDetail {
             Self::AppZipStream { error, .. } => Some(error),
             Self::AppZipWrite { error, .. } => Some(error),
             Self::ApplicationDatabaseLink { error, .. } => Some(error),
+            Self::StationResourceError(error) => Some(error),
             Self::CleanArtifactServerAppZip { error, .. } => Some(error),
         }
     }
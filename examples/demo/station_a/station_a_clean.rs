@@ -37,8 +37,8 @@ impl StationAClean {
         _station: &'f mut StationMutRef<'_, DemoError>,
         files: &'f FilesRw,
         artifact_server_dir: &'f ArtifactServerDir,
+        client: &'f reqwest::Client,
     ) -> LocalBoxFuture<'f, Result<CheckStatus, DemoError>> {
-        let client = reqwest::Client::new();
         Box::pin(async move {
             let mut files = files.write().await;
 
@@ -57,7 +57,6 @@ impl StationAClean {
                 );
                 let address = files.source(address_file_id);
                 StationAErrors::connect_error(
-                    &SERVER_PARAMS_DEFAULT,
                     artifact_server_dir_file_id,
                     address,
                     address_file_id,
@@ -6,18 +6,15 @@ use choochoo::{
         srcerr::{codespan::Span, codespan_reporting::diagnostic::Severity},
         CreateFns, SetupFn, StationFn,
     },
-    resource::{Files, FilesRw, ProfileDir},
+    resource::{Files, FilesRw},
 };
 use futures::future::LocalBoxFuture;
-use reqwest::{
-    multipart::{Form, Part},
-    redirect::Policy,
-};
+use reqwest::multipart::{Form, Part};
 use tokio::fs::File;
 use tokio_util::codec::{BytesCodec, FramedRead};
 
 use crate::{
-    app_zip::{AppZipFileLength, APP_ZIP_NAME},
+    app_zip::{AppZipFileLength, APP_ZIP_FILE, APP_ZIP_NAME},
     artifact_server_dir::ArtifactServerDir,
     error::{ErrorCode, ErrorDetail},
     server_params::SERVER_PARAMS_DEFAULT,
@@ -38,7 +35,7 @@ impl StationACreate {
         SetupFn::new(|station, train_resources| {
             Box::pin(async move {
                 let local_file_length = {
-                    let files = train_resources.borrow::<FilesRw>();
+                    let files = train_resources.borrow::<FilesRw>()?;
                     let mut files = files.write().await;
 
                     let app_zip_build_agent_path = station.dir.join(APP_ZIP_NAME);
@@ -59,12 +56,7 @@ impl StationACreate {
                 };
 
                 train_resources.insert(AppZipFileLength(local_file_length));
-
-                let artifact_server_dir = {
-                    let profile_dir = train_resources.borrow::<ProfileDir>();
-                    ArtifactServerDir::new(profile_dir.to_path_buf())
-                };
-                train_resources.insert(artifact_server_dir);
+                station.publish_file(APP_ZIP_FILE, APP_ZIP_NAME);
 
                 Ok(ProgressLimit::Bytes(local_file_length))
             })
@@ -76,8 +68,8 @@ impl StationACreate {
         files: &'f FilesRw,
         artifact_server_dir: &'f ArtifactServerDir,
         local_file_length: &'f AppZipFileLength,
+        client: &'f reqwest::Client,
     ) -> LocalBoxFuture<'f, Result<CheckStatus, DemoError>> {
-        let client = reqwest::Client::new();
         Box::pin(async move {
             let mut files = files.write().await;
 
@@ -98,7 +90,6 @@ impl StationACreate {
                 );
                 let address = files.source(address_file_id);
                 StationAErrors::connect_error(
-                    &SERVER_PARAMS_DEFAULT,
                     artifact_server_dir_file_id,
                     address,
                     address_file_id,
@@ -132,16 +123,12 @@ impl StationACreate {
     fn work_fn<'f>(
         station: &'f mut StationMutRef<'_, DemoError>,
         files: &'f FilesRw,
+        client: &'f reqwest::Client,
     ) -> LocalBoxFuture<'f, Result<ResIds, (ResIds, DemoError)>> {
         station.progress.progress_bar().reset();
         station.progress.tick();
         Box::pin(async move {
             let mut res_ids = ResIds::new();
-            let client = reqwest::Client::builder()
-                .redirect(Policy::none())
-                .build()
-                .map_err(|error| Self::client_build_error(error))
-                .map_err(|e| (res_ids.clone(), e))?;
 
             let mut files = files.write().await;
 
@@ -171,7 +158,6 @@ impl StationACreate {
                         Cow::Owned(station.dir.to_string_lossy().into_owned()),
                     );
                     StationAErrors::connect_error(
-                        &SERVER_PARAMS_DEFAULT,
                         app_zip_dir_file_id,
                         &address,
                         address_file_id,
@@ -251,13 +237,6 @@ impl StationACreate {
         Ok(FramedRead::new(app_zip_read, BytesCodec::new()))
     }
 
-    fn client_build_error(error: reqwest::Error) -> DemoError {
-        let code = ErrorCode::ReqwestClientBuild;
-        let detail = ErrorDetail::ReqwestClientBuild(error);
-
-        DemoError::new(code, detail, Severity::Bug)
-    }
-
     fn file_open_error(
         station: &mut StationMut<'_, DemoError>,
         files: &mut Files,
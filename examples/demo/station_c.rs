@@ -4,8 +4,8 @@ use bytes::Bytes;
 use choochoo::{
     cfg_model::{
         rt::{
-            CheckStatus, ProgressLimit, ResIdLogical, ResIds, StationMutRef, StationProgress,
-            StationRtId,
+            CheckStatus, ProgressLimit, ProgressStream, ResIdLogical, ResIds, StationMutRef,
+            StationProgress,
         },
         srcerr::{
             codespan::{FileId, Span},
@@ -13,8 +13,7 @@ use choochoo::{
         },
         CreateFns, SetupFn, StationFn, StationId, StationIdInvalidFmt, StationOp, StationSpec,
     },
-    resource::{Files, FilesRw},
-    rt_model::StationDirs,
+    resource::{Files, FilesRw, SpanFinder},
 };
 use futures::{future::LocalBoxFuture, Stream, StreamExt, TryStreamExt};
 use tokio::{
@@ -23,10 +22,10 @@ use tokio::{
 };
 
 use crate::{
-    app_zip::{AppZipFileLength, APP_ZIP_NAME},
+    app_zip::{AppZipFileLength, APP_ZIP_FILE, APP_ZIP_NAME},
     artifact_server_dir::ArtifactServerDir,
     error::{ErrorCode, ErrorDetail},
-    server_params::{ServerParams, SERVER_PARAMS_DEFAULT},
+    server_params::SERVER_PARAMS_DEFAULT,
     DemoError,
 };
 
@@ -36,33 +35,26 @@ pub struct StationC;
 impl StationC {
     /// Returns a station that downloads `app.zip` to a server.
     ///
-    /// # Parameters
-    ///
-    /// * `station_a_rt_id`: Runtime ID of [`StationA`].
+    /// The station reads the `app.zip` published by [`StationA`].
     ///
     /// [`StationA`]: crate::StationA
-    pub fn build(
-        station_a_rt_id: StationRtId,
-    ) -> Result<StationSpec<DemoError>, StationIdInvalidFmt<'static>> {
-        let create_fns = CreateFns::new(Self::setup_fn(), Self::work_fn(station_a_rt_id))
+    pub fn build() -> Result<StationSpec<DemoError>, StationIdInvalidFmt<'static>> {
+        let station_a_id = StationId::new("a")?;
+        let create_fns = CreateFns::new(Self::setup_fn(), Self::work_fn(station_a_id.clone()))
             .with_check_fn(StationFn::new(Self::check_fn));
         let station_op = StationOp::new(create_fns, None);
 
-        let station_id = StationId::new("c")?;
-        let station_name = String::from("Download App");
-        let station_description = String::from("Downloads web application onto web server.");
-        Ok(StationSpec::new(
-            station_id,
-            station_name,
-            station_description,
-            station_op,
-        ))
+        Ok(StationSpec::builder("c", station_op)?
+            .with_name("Download App")
+            .with_description("Downloads web application onto web server.")
+            .with_file_input(station_a_id, APP_ZIP_FILE)
+            .build())
     }
 
     fn setup_fn() -> SetupFn<DemoError> {
         SetupFn::new(move |_station, train_resources| {
             Box::pin(async move {
-                let app_zip_file_length = train_resources.borrow::<AppZipFileLength>().0;
+                let app_zip_file_length = train_resources.borrow::<AppZipFileLength>()?.0;
                 Ok(ProgressLimit::Bytes(app_zip_file_length))
             })
         })
@@ -72,8 +64,8 @@ impl StationC {
         station: &'f mut StationMutRef<'_, DemoError>,
         files: &'f FilesRw,
         artifact_server_dir: &'f ArtifactServerDir,
+        client: &'f reqwest::Client,
     ) -> LocalBoxFuture<'f, Result<CheckStatus, DemoError>> {
-        let client = reqwest::Client::new();
         Box::pin(async move {
             let app_zip_app_server_path = station.dir.join(APP_ZIP_NAME);
             // Short circuit in case the file doesn't exist locally.
@@ -112,7 +104,6 @@ impl StationC {
                     Cow::Owned(artifact_server_dir.to_string_lossy().into_owned()),
                 );
                 Self::get_error(
-                    &SERVER_PARAMS_DEFAULT,
                     artifact_server_dir_file_id,
                     &address,
                     address_file_id,
@@ -145,15 +136,20 @@ impl StationC {
         })
     }
 
-    fn work_fn(station_a_rt_id: StationRtId) -> StationFn<ResIds, (ResIds, DemoError), DemoError> {
-        StationFn::new2(
+    fn work_fn(station_a_id: StationId) -> StationFn<ResIds, (ResIds, DemoError), DemoError> {
+        StationFn::new3(
             move |station: &mut StationMutRef<'_, DemoError>,
-                  station_dirs: &StationDirs,
-                  files: &FilesRw|
+                  files: &FilesRw,
+                  app_zip_file_length: &AppZipFileLength,
+                  client: &reqwest::Client|
                   -> LocalBoxFuture<'_, Result<ResIds, (ResIds, DemoError)>> {
-                let client = reqwest::Client::new();
+                let station_a_id = station_a_id.clone();
                 Box::pin(async move {
                     station.progress.progress_bar().reset();
+                    station
+                        .progress
+                        .progress_bar()
+                        .set_length(app_zip_file_length.0);
                     let mut res_ids = ResIds::new();
                     let mut files = files.write().await;
 
@@ -170,21 +166,16 @@ impl StationC {
                         .send()
                         .await
                         .map_err(|error| {
-                            let station_a_dir = station_dirs
-                                .get(&station_a_rt_id)
+                            let station_a_dir = station
+                                .file_input(&station_a_id, APP_ZIP_FILE)
+                                .and_then(Path::parent)
                                 .expect("Failed to find `StationA` directory");
                             let app_zip_dir_file_id = files.add(
                                 station_a_dir,
                                 Cow::Owned(station_a_dir.to_string_lossy().into_owned()),
                             );
                             let address = files.source(address_file_id);
-                            Self::get_error(
-                                &SERVER_PARAMS_DEFAULT,
-                                app_zip_dir_file_id,
-                                address,
-                                address_file_id,
-                                error,
-                            )
+                            Self::get_error(app_zip_dir_file_id, address, address_file_id, error)
                         })
                         .map_err(|e| (res_ids.clone(), e))?;
 
@@ -254,7 +245,7 @@ impl StationC {
             .map_err(|error| Self::write_error(app_zip_path_file_id, app_zip_path, error))?;
 
         let buffer = BufWriter::new(app_zip_file);
-        let mut buffer = byte_stream
+        let mut buffer = ProgressStream::new(station_progress, Box::pin(byte_stream))
             .map(|bytes_result| {
                 bytes_result.map_err(|error| {
                     let app_zip_url_span = Span::from_str(app_zip_url);
@@ -269,7 +260,6 @@ impl StationC {
                 })
             })
             .try_fold(buffer, |mut buffer, bytes| async move {
-                station_progress.progress_bar().inc(bytes.len() as u64);
                 buffer.write_all(&bytes).await.map_err(|error| {
                     Self::write_error(app_zip_path_file_id, app_zip_path, error)
                 })?;
@@ -347,25 +337,15 @@ impl StationC {
     }
 
     fn get_error(
-        server_params: &ServerParams,
         artifact_server_dir_file_id: FileId,
         address: &str,
         address_file_id: FileId,
         error: reqwest::Error,
     ) -> DemoError {
-        let ServerParams {
-            protocol,
-            host,
-            port,
-        } = server_params;
-
-        let address_span = Span::new(protocol.len() as u32, address.len() as u32);
-        let host_start = protocol.len();
-        let host_end = host_start + host.len();
-        let port_start = host_end + 1;
-        let port_end = port_start + port.len();
-        let host_span = Span::new(host_start as u32, host_end as u32);
-        let port_span = Span::new(port_start as u32, port_end as u32);
+        let span_finder = SpanFinder::new(address);
+        let address_span = span_finder.url_authority();
+        let host_span = span_finder.url_host().unwrap_or(address_span);
+        let port_span = span_finder.url_port().unwrap_or(address_span);
 
         let code = ErrorCode::ArtifactServerConnect;
         let detail = ErrorDetail::ArtifactServerConnect {
@@ -11,7 +11,7 @@ use choochoo::{
         },
         CreateFns, SetupFn, StationFn, StationId, StationIdInvalidFmt, StationOp, StationSpec,
     },
-    cli_fmt::PlainTextFormatter,
+    cli_fmt::{FormatterOpts, PlainTextFormatter},
     resource::FilesRw,
     rt_logic::Train,
     rt_model::{error::StationSpecError, Destination},
@@ -35,7 +35,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
         let mut stdout = tokio::io::stdout();
 
-        PlainTextFormatter::fmt(&mut stdout, &dest, &train_resources).await?;
+        PlainTextFormatter::fmt(
+            &mut stdout,
+            &dest,
+            &train_resources,
+            &FormatterOpts::default(),
+        )
+        .await?;
 
         Result::<(), Box<dyn std::error::Error>>::Ok(())
     })?;
@@ -4,6 +4,11 @@
 
 pub use choochoo_cfg_model as cfg_model;
 pub use choochoo_cli_fmt as cli_fmt;
+#[cfg(feature = "derive")]
+pub use choochoo_derive as derive;
+pub use choochoo_ops as ops;
 pub use choochoo_resource as resource;
 pub use choochoo_rt_logic as rt_logic;
 pub use choochoo_rt_model as rt_model;
+#[cfg(feature = "test")]
+pub use choochoo_test as test;
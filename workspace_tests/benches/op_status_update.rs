@@ -0,0 +1,123 @@
+//! Benchmarks updating station `OpStatus`es as stations complete.
+//!
+//! Stations are arranged in layers, where each station depends on every
+//! station in the previous layer.
+
+use choochoo_cfg_model::{
+    rt::{OpStatus, StationRtId, VisitOp},
+    StationSpec,
+};
+use choochoo_rt_logic::{OpStatusPropagator, OpStatusUpdater};
+use choochoo_rt_model::Destination;
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use futures::executor;
+
+/// Number of stations in each layer.
+const LAYER_WIDTH: usize = 10;
+/// Numbers of layers to benchmark.
+const LAYER_COUNTS: [usize; 3] = [10, 100, 1000];
+
+fn op_status_update(c: &mut Criterion) {
+    let mut group = c.benchmark_group("op_status_update");
+    LAYER_COUNTS.iter().copied().for_each(|layer_count| {
+        let (dest, station_rt_ids) = dest_layered(layer_count);
+        let station_count = layer_count * LAYER_WIDTH;
+
+        group.bench_with_input(
+            BenchmarkId::new("update_children", station_count),
+            &station_rt_ids,
+            |b, station_rt_ids| {
+                b.iter_batched(
+                    || op_statuses_reset(&dest, station_rt_ids),
+                    |()| {
+                        station_rt_ids.iter().for_each(|station_rt_id| {
+                            dest.station_progresses()[station_rt_id]
+                                .borrow_mut()
+                                .op_status = OpStatus::WorkSuccess;
+                            OpStatusUpdater::update_children(&dest, *station_rt_id);
+                        })
+                    },
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("op_status_propagator", station_count),
+            &station_rt_ids,
+            |b, station_rt_ids| {
+                b.iter_batched(
+                    || {
+                        op_statuses_reset(&dest, station_rt_ids);
+                        OpStatusPropagator::new(&dest, VisitOp::Create)
+                    },
+                    |op_status_propagator| {
+                        station_rt_ids.iter().for_each(|station_rt_id| {
+                            dest.station_progresses()[station_rt_id]
+                                .borrow_mut()
+                                .op_status = OpStatus::WorkSuccess;
+                            executor::block_on(
+                                op_status_propagator.station_complete(&dest, *station_rt_id),
+                            );
+                        })
+                    },
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+    });
+    group.finish();
+}
+
+/// Returns a destination with `layer_count` layers of stations, and the
+/// stations' runtime IDs in dependency order.
+fn dest_layered(layer_count: usize) -> (Destination<()>, Vec<StationRtId>) {
+    let mut dest_builder = Destination::<()>::builder();
+    let layers = (0..layer_count)
+        .map(|layer| {
+            (0..LAYER_WIDTH)
+                .map(|index| {
+                    let station_spec = StationSpec::mock(format!("station_{}_{}", layer, index))
+                        .expect("Expected station ID to be valid.")
+                        .build();
+                    dest_builder.add_station(station_spec)
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    layers.windows(2).for_each(|layer_pair| {
+        layer_pair[0].iter().for_each(|parent| {
+            layer_pair[1].iter().for_each(|child| {
+                dest_builder
+                    .add_edge(*parent, *child)
+                    .expect("Expected edge to not create a cycle.");
+            });
+        });
+    });
+    let dest = dest_builder
+        .build()
+        .expect("Expected destination to be valid.");
+    let station_rt_ids = layers.into_iter().flatten().collect::<Vec<_>>();
+
+    (dest, station_rt_ids)
+}
+
+/// Sets the first layer of stations to `OpQueued`, and the rest to
+/// `ParentPending`.
+fn op_statuses_reset(dest: &Destination<()>, station_rt_ids: &[StationRtId]) {
+    station_rt_ids
+        .iter()
+        .enumerate()
+        .for_each(|(index, station_rt_id)| {
+            let op_status = if index < LAYER_WIDTH {
+                OpStatus::OpQueued
+            } else {
+                OpStatus::ParentPending
+            };
+            dest.station_progresses()[station_rt_id]
+                .borrow_mut()
+                .op_status = op_status;
+        });
+}
+
+criterion_group!(benches, op_status_update);
+criterion_main!(benches);
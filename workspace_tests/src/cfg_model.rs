@@ -1,5 +1,13 @@
+mod progress_reader;
+mod progress_stream;
+mod progress_writer;
+mod res_ids;
+mod station_dir;
 mod station_fn;
 mod station_id;
 mod station_id_invalid_fmt;
 mod station_progress;
+mod station_resources;
+mod station_resources_mut;
 mod station_spec;
+mod train_resources;
@@ -0,0 +1,20 @@
+use choochoo_cfg_model::{
+    rt::{ProgressLimit, ProgressReader, StationProgress},
+    StationSpec,
+};
+use tokio::{io::AsyncReadExt, runtime};
+
+#[test]
+fn read_advances_progress_bar_by_bytes_read() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let station_spec = StationSpec::<()>::mock("station_id")?.build();
+    let station_progress = StationProgress::new(&station_spec, ProgressLimit::Bytes(5));
+    let mut progress_reader = ProgressReader::new(&station_progress, &b"hello"[..]);
+
+    let mut bytes = Vec::new();
+    rt.block_on(progress_reader.read_to_end(&mut bytes))?;
+
+    assert_eq!(b"hello", bytes.as_slice());
+    assert_eq!(5, station_progress.progress_bar().position());
+    Ok(())
+}
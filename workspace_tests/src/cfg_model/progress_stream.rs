@@ -0,0 +1,24 @@
+use choochoo_cfg_model::{
+    rt::{ProgressLimit, ProgressStream, StationProgress},
+    StationSpec,
+};
+use futures::{stream, TryStreamExt};
+use tokio::runtime;
+
+#[test]
+fn stream_advances_progress_bar_by_bytes_in_successful_items()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let station_spec = StationSpec::<()>::mock("station_id")?.build();
+    let station_progress = StationProgress::new(&station_spec, ProgressLimit::Bytes(5));
+    let byte_stream = stream::iter(vec![Ok::<_, ()>(b"hel".to_vec()), Ok(b"lo".to_vec())]);
+    let progress_stream = ProgressStream::new(&station_progress, byte_stream);
+
+    let chunks = rt
+        .block_on(progress_stream.try_collect::<Vec<_>>())
+        .map_err(|()| "stream failed")?;
+
+    assert_eq!(2, chunks.len());
+    assert_eq!(5, station_progress.progress_bar().position());
+    Ok(())
+}
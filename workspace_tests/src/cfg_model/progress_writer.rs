@@ -0,0 +1,19 @@
+use choochoo_cfg_model::{
+    rt::{ProgressLimit, ProgressWriter, StationProgress},
+    StationSpec,
+};
+use tokio::{io::AsyncWriteExt, runtime};
+
+#[test]
+fn write_advances_progress_bar_by_bytes_written() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let station_spec = StationSpec::<()>::mock("station_id")?.build();
+    let station_progress = StationProgress::new(&station_spec, ProgressLimit::Bytes(5));
+    let mut progress_writer = ProgressWriter::new(&station_progress, Vec::new());
+
+    rt.block_on(progress_writer.write_all(b"hello"))?;
+
+    assert_eq!(b"hello", progress_writer.get_ref().as_slice());
+    assert_eq!(5, station_progress.progress_bar().position());
+    Ok(())
+}
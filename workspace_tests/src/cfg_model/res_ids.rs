@@ -0,0 +1,21 @@
+use choochoo_cfg_model::rt::{ResIdLogical, ResIds};
+
+#[test]
+fn keys_with_prefix_returns_ids_grouped_within_prefix() {
+    let mut res_ids = ResIds::new();
+    res_ids.insert(ResIdLogical::new("network/vpc"), String::from("vpc-1"));
+    res_ids.insert(ResIdLogical::new("networks"), String::from("2"));
+    res_ids.insert(ResIdLogical::new("network/subnet/0"), String::from("subnet-0"));
+
+    let keys = res_ids.keys_with_prefix("network").collect::<Vec<_>>();
+
+    assert_eq!(
+        vec![
+            &ResIdLogical::new("network/vpc"),
+            &ResIdLogical::new("network/subnet/0")
+        ],
+        keys
+    );
+    assert_eq!(Some("network/subnet"), keys[1].namespace());
+    assert_eq!("0", keys[1].name());
+}
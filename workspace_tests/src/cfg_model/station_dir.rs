@@ -0,0 +1,34 @@
+use std::path::PathBuf;
+
+use choochoo_cfg_model::rt::StationDir;
+
+#[test]
+fn subdirs_are_joined_to_station_dir() {
+    let station_dir = StationDir::new(PathBuf::from("station_a"));
+
+    assert_eq!(PathBuf::from("station_a/inputs"), station_dir.inputs_dir());
+    assert_eq!(PathBuf::from("station_a/outputs"), station_dir.outputs_dir());
+    assert_eq!(PathBuf::from("station_a/logs"), station_dir.logs_dir());
+    assert_eq!(PathBuf::from("station_a/tmp"), station_dir.tmp_dir());
+}
+
+#[test]
+fn subdirs_are_not_created_when_requested() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let station_dir = StationDir::new(tempdir.path().join("station_a"));
+
+    let subdirs = station_dir.subdirs();
+
+    assert_eq!(
+        [
+            station_dir.inputs_dir(),
+            station_dir.outputs_dir(),
+            station_dir.logs_dir(),
+            station_dir.tmp_dir(),
+        ],
+        subdirs
+    );
+    assert!(subdirs.iter().all(|subdir| !subdir.exists()));
+
+    Ok(())
+}
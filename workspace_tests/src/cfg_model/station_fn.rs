@@ -1,4 +1,7 @@
-use choochoo_cfg_model::StationFn;
+use std::any::TypeId;
+
+use choochoo_cfg_model::{fn_graph::FnMeta, rt::StationMutRef, StationFn};
+use futures::future::{BoxFuture, FutureExt, LocalBoxFuture};
 
 #[test]
 fn debug_impl_includes_all_fields() {
@@ -24,3 +27,68 @@ fn partial_eq_returns_false_for_different_instance() {
 
     assert_ne!(&work_fn_0, &work_fn_1);
 }
+
+#[test]
+fn new_supports_twelve_resource_arguments() {
+    let work_fn = StationFn::<(), (), ()>::new(work_fn_twelve_args);
+
+    assert_eq!(
+        vec![
+            TypeId::of::<u8>(),
+            TypeId::of::<u32>(),
+            TypeId::of::<u128>(),
+            TypeId::of::<i16>(),
+            TypeId::of::<i64>(),
+            TypeId::of::<f32>(),
+        ],
+        work_fn.borrows().iter().copied().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![
+            TypeId::of::<u16>(),
+            TypeId::of::<u64>(),
+            TypeId::of::<i8>(),
+            TypeId::of::<i32>(),
+            TypeId::of::<i128>(),
+            TypeId::of::<f64>(),
+        ],
+        work_fn.borrow_muts().iter().copied().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn new_send_records_resource_argument_borrows() {
+    let work_fn = StationFn::<(), (), ()>::new_send(
+        |_: &mut StationMutRef<'_, ()>, _: &u8, _: &mut u16| -> BoxFuture<'static, _> {
+            async { Ok(()) }.boxed()
+        },
+    );
+
+    assert_eq!(
+        vec![TypeId::of::<u8>()],
+        work_fn.borrows().iter().copied().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![TypeId::of::<u16>()],
+        work_fn.borrow_muts().iter().copied().collect::<Vec<_>>()
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
+fn work_fn_twelve_args<'f>(
+    _station: &'f mut StationMutRef<'_, ()>,
+    _a0: &'f u8,
+    _a1: &'f mut u16,
+    _a2: &'f u32,
+    _a3: &'f mut u64,
+    _a4: &'f u128,
+    _a5: &'f mut i8,
+    _a6: &'f i16,
+    _a7: &'f mut i32,
+    _a8: &'f i64,
+    _a9: &'f mut i128,
+    _a10: &'f f32,
+    _a11: &'f mut f64,
+) -> LocalBoxFuture<'f, Result<(), ()>> {
+    async { Ok(()) }.boxed_local()
+}
@@ -70,3 +70,32 @@ fn display_returns_inner_str() -> Result<(), StationIdInvalidFmt<'static>> {
     assert_eq!("good_id", station_id.to_string());
     Ok(())
 }
+
+#[test]
+fn try_from_str_validates_hierarchical_id_segments() {
+    assert!(StationId::try_from("app/upload").is_ok());
+    assert!(StationId::try_from("app/db/create").is_ok());
+
+    assert!(StationId::try_from("app//upload").is_err());
+    assert!(StationId::try_from("app/").is_err());
+    assert!(StationId::try_from("/upload").is_err());
+    assert!(StationId::try_from("app/has space").is_err());
+}
+
+#[test]
+fn namespace_and_has_prefix_match_whole_segments() -> Result<(), StationIdInvalidFmt<'static>> {
+    let station_id = StationId::try_from("app/db/create")?;
+
+    assert_eq!(
+        vec!["app", "db", "create"],
+        station_id.segments().collect::<Vec<_>>()
+    );
+    assert_eq!(Some("app/db"), station_id.namespace());
+    assert_eq!(None, StationId::try_from("create")?.namespace());
+    assert!(station_id.has_prefix("app"));
+    assert!(station_id.has_prefix("app/db/"));
+    assert!(station_id.has_prefix("app/db/create"));
+    assert!(!station_id.has_prefix("ap"));
+    assert!(!station_id.has_prefix("app/d"));
+    Ok(())
+}
@@ -7,7 +7,7 @@ fn display_returns_readable_message() {
     let station_id_invalid_fmt = StationIdInvalidFmt::new(Cow::Borrowed("a b c"));
 
     assert_eq!(
-        "`a b c` is not a valid station ID. Station IDs can only contain letters, numbers, and underscores.",
+        "`a b c` is not a valid station ID. Station IDs can only contain letters, numbers, and underscores, with `/` separating non-empty segments.",
         station_id_invalid_fmt.to_string()
     );
 }
@@ -1,5 +1,5 @@
 use choochoo_cfg_model::{
-    rt::{OpStatus, ProgressLimit, ResIds, StationProgress},
+    rt::{OpStatus, ProgressLimit, ProgressTheme, ResIds, StationProgress, SubStepStatus},
     OpFns, SetupFn, StationFn, StationId, StationIdInvalidFmt, StationOp, StationSpec,
 };
 
@@ -23,3 +23,120 @@ fn display_returns_readable_informative_message() -> Result<(), StationIdInvalid
     );
     Ok(())
 }
+
+#[test]
+fn new_uses_default_progress_theme_when_station_spec_has_none()
+-> Result<(), StationIdInvalidFmt<'static>> {
+    let station_spec = StationSpec::<()>::mock("station_id")?.build();
+    let station_progress = StationProgress::new(&station_spec, ProgressLimit::Unknown);
+
+    assert_eq!(&ProgressTheme::default(), station_progress.progress_theme());
+    Ok(())
+}
+
+#[test]
+fn new_uses_station_spec_progress_theme() -> Result<(), StationIdInvalidFmt<'static>> {
+    let station_spec = StationSpec::<()>::mock("station_id")?
+        .with_progress_theme(ProgressTheme::spinner())
+        .build();
+    let station_progress = StationProgress::new(&station_spec, ProgressLimit::Unknown);
+
+    assert_eq!(&ProgressTheme::spinner(), station_progress.progress_theme());
+    Ok(())
+}
+
+#[test]
+fn progress_theme_set_updates_progress_theme() -> Result<(), StationIdInvalidFmt<'static>> {
+    let station_spec = StationSpec::<()>::mock("station_id")?.build();
+    let mut station_progress = StationProgress::new(&station_spec, ProgressLimit::Items(3));
+    let progress_theme = ProgressTheme::new()
+        .with_template("{op_symbol} {msg} {op_units}")
+        .with_message_template("{name}")
+        .with_bar_width(20);
+
+    station_progress.progress_theme_set(&station_spec, progress_theme.clone());
+
+    assert_eq!(&progress_theme, station_progress.progress_theme());
+    Ok(())
+}
+
+#[test]
+fn message_records_latest_message_when_throttled() -> Result<(), StationIdInvalidFmt<'static>> {
+    let station_spec = StationSpec::<()>::mock("station_id")?.build();
+    let mut station_progress = StationProgress::new(&station_spec, ProgressLimit::Unknown);
+    station_progress.op_status = OpStatus::WorkInProgress;
+
+    station_progress.message("uploading chunk 1/2");
+    station_progress.message("uploading chunk 2/2");
+    assert_eq!(
+        Some("uploading chunk 2/2"),
+        station_progress.message_current()
+    );
+
+    station_progress.message_clear();
+    assert_eq!(None, station_progress.message_current());
+    Ok(())
+}
+
+#[test]
+fn progress_limit_set_updates_progress_limit_while_work_in_progress()
+-> Result<(), StationIdInvalidFmt<'static>> {
+    let station_spec = StationSpec::<()>::mock("station_id")?.build();
+    let mut station_progress = StationProgress::new(&station_spec, ProgressLimit::Unknown);
+    station_progress.op_status = OpStatus::WorkInProgress;
+    station_progress.progress_style_update();
+    station_progress.progress_bar().inc(10);
+
+    station_progress.progress_limit_set(ProgressLimit::Bytes(100));
+
+    assert_eq!(ProgressLimit::Bytes(100), station_progress.progress_limit());
+    assert_eq!(100, station_progress.progress_bar().length());
+    assert_eq!(10, station_progress.progress_bar().position());
+    Ok(())
+}
+
+#[test]
+fn sub_step_progress_is_aggregated_into_station_progress_bar()
+-> Result<(), StationIdInvalidFmt<'static>> {
+    let station_spec = StationSpec::<()>::mock("station_id")?
+        .with_sub_step("download")
+        .with_sub_step("extract")
+        .build();
+    let mut station_progress = StationProgress::new(&station_spec, ProgressLimit::Unknown);
+
+    station_progress.sub_step_begin("download", ProgressLimit::Bytes(100));
+    station_progress.sub_step_inc("download", 50);
+
+    let resolution = StationProgress::SUB_STEP_RESOLUTION;
+    assert_eq!(2 * resolution, station_progress.progress_bar().length());
+    assert_eq!(resolution / 2, station_progress.progress_bar().position());
+
+    station_progress.sub_step_complete("download");
+
+    assert_eq!(resolution, station_progress.progress_bar().position());
+    assert_eq!(
+        vec![SubStepStatus::Success, SubStepStatus::Queued],
+        station_progress
+            .sub_steps()
+            .iter()
+            .map(|sub_step| sub_step.status())
+            .collect::<Vec<_>>()
+    );
+    Ok(())
+}
+
+#[test]
+fn sub_step_begin_appends_undeclared_sub_step() -> Result<(), StationIdInvalidFmt<'static>> {
+    let station_spec = StationSpec::<()>::mock("station_id")?.build();
+    let mut station_progress = StationProgress::new(&station_spec, ProgressLimit::Unknown);
+
+    station_progress.sub_step_begin("verify", ProgressLimit::Steps(1));
+
+    assert_eq!(1, station_progress.sub_steps().len());
+    assert_eq!("verify", station_progress.sub_steps()[0].name());
+    assert_eq!(
+        SubStepStatus::InProgress,
+        station_progress.sub_steps()[0].status()
+    );
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use choochoo_cfg_model::{
+    resman::BorrowFail,
+    rt::{
+        ResIds, ResourceUndeclared, StationMutRef, StationResourceError, StationResources,
+        StationRtId, TrainResources,
+    },
+    StationFn, StationSpec,
+};
+use futures::future::{FutureExt, LocalBoxFuture};
+
+#[test]
+fn borrow_returns_resources_declared_by_station_fns() -> Result<(), Box<dyn std::error::Error>> {
+    let station_spec = StationSpec::<()>::mock("a")?
+        .with_create_work_fn(StationFn::new(work_fn))
+        .build();
+    let train_resources = TrainResources::<()>::new()
+        .with_resource(1u32)
+        .with_resource(2u64);
+    let station_resources =
+        StationResources::new(&station_spec, StationRtId::new(0), &train_resources);
+
+    assert_eq!(1u32, *station_resources.borrow::<u32>()?);
+    assert_eq!(2u64, *station_resources.borrow::<u64>()?);
+    *station_resources.borrow_mut::<u64>()? = 3u64;
+    assert_eq!(3u64, *train_resources.borrow::<u64>());
+
+    Ok(())
+}
+
+#[test]
+fn borrow_returns_err_for_undeclared_resources() -> Result<(), Box<dyn std::error::Error>> {
+    let station_spec = StationSpec::<()>::mock("a")?
+        .with_name("Station A")
+        .with_create_work_fn(StationFn::new(work_fn))
+        .build();
+    let train_resources = TrainResources::<()>::new()
+        .with_resource(1u32)
+        .with_resource(4u8);
+    let station_resources =
+        StationResources::new(&station_spec, StationRtId::new(0), &train_resources);
+
+    let error = station_resources
+        .borrow::<u8>()
+        .expect_err("Expected `u8` to be undeclared.");
+    assert_eq!(
+        StationResourceError::ResourceUndeclared(ResourceUndeclared::new(
+            station_spec.id().clone(),
+            String::from("Station A"),
+            "u8",
+            false,
+        )),
+        error
+    );
+    assert_eq!(
+        "Station `a: Station A` borrowed `&u8`, but no check, work, or verify function of the station takes it as a parameter.",
+        error.to_string()
+    );
+
+    let error = station_resources
+        .borrow_mut::<u32>()
+        .expect_err("Expected `&mut u32` to be undeclared.");
+    assert_eq!(
+        StationResourceError::ResourceUndeclared(ResourceUndeclared::new(
+            station_spec.id().clone(),
+            String::from("Station A"),
+            "u32",
+            true,
+        )),
+        error
+    );
+
+    Ok(())
+}
+
+#[test]
+fn borrow_returns_err_when_declared_resource_cannot_be_borrowed()
+-> Result<(), Box<dyn std::error::Error>> {
+    let station_spec = StationSpec::<()>::mock("a")?
+        .with_create_work_fn(StationFn::new(work_fn))
+        .build();
+    let train_resources = TrainResources::<()>::new().with_resource(2u64);
+    let station_resources =
+        StationResources::new(&station_spec, StationRtId::new(0), &train_resources);
+
+    let error = station_resources
+        .borrow::<u32>()
+        .expect_err("Expected `u32` to not exist.");
+    assert_eq!(
+        StationResourceError::BorrowFail {
+            type_name: "u32",
+            borrow_fail: BorrowFail::ValueNotFound,
+        },
+        error
+    );
+
+    let _u64 = station_resources.borrow::<u64>()?;
+    let error = station_resources
+        .borrow_mut::<u64>()
+        .expect_err("Expected `u64` to already be borrowed.");
+    assert_eq!(
+        StationResourceError::BorrowFail {
+            type_name: "u64",
+            borrow_fail: BorrowFail::BorrowConflictMut,
+        },
+        error
+    );
+
+    Ok(())
+}
+
+fn work_fn<'f>(
+    _station: &'f mut StationMutRef<'_, ()>,
+    _a: &'f u32,
+    _b: &'f mut u64,
+) -> LocalBoxFuture<'f, Result<ResIds, (ResIds, ())>> {
+    async { Ok(ResIds::new()) }.boxed_local()
+}
@@ -0,0 +1,88 @@
+use choochoo_cfg_model::{
+    rt::{
+        ResIds, ResourceUndeclared, StationMutRef, StationResourceError, StationResourcesMut,
+        StationRtId, TrainResources,
+    },
+    StationFn, StationSpec,
+};
+use futures::future::{FutureExt, LocalBoxFuture};
+
+#[test]
+fn insert_inserts_resource_into_train_resources() -> Result<(), Box<dyn std::error::Error>> {
+    let station_spec = StationSpec::<()>::mock("a")?.build();
+    let mut train_resources = TrainResources::<()>::new();
+    let mut station_resources =
+        StationResourcesMut::new(&station_spec, StationRtId::new(0), &mut train_resources);
+
+    station_resources.insert(1u32);
+
+    assert_eq!(1u32, *train_resources.borrow::<u32>());
+
+    Ok(())
+}
+
+#[test]
+fn borrow_returns_resources_declared_by_station_fns() -> Result<(), Box<dyn std::error::Error>> {
+    let station_spec = StationSpec::<()>::mock("a")?
+        .with_create_work_fn(StationFn::new(work_fn))
+        .build();
+    let mut train_resources = TrainResources::<()>::new().with_resource(2u64);
+    let mut station_resources =
+        StationResourcesMut::new(&station_spec, StationRtId::new(0), &mut train_resources);
+
+    station_resources.insert(1u32);
+    assert_eq!(1u32, *station_resources.borrow::<u32>()?);
+    *station_resources.borrow_mut::<u64>()? = 3u64;
+    assert_eq!(3u64, *station_resources.borrow::<u64>()?);
+
+    Ok(())
+}
+
+#[test]
+fn borrow_returns_err_for_undeclared_resources() -> Result<(), Box<dyn std::error::Error>> {
+    let station_spec = StationSpec::<()>::mock("a")?
+        .with_name("Station A")
+        .with_create_work_fn(StationFn::new(work_fn))
+        .build();
+    let mut train_resources = TrainResources::<()>::new()
+        .with_resource(1u32)
+        .with_resource(4u8);
+    let station_resources =
+        StationResourcesMut::new(&station_spec, StationRtId::new(0), &mut train_resources);
+
+    let error = station_resources
+        .borrow::<u8>()
+        .expect_err("Expected `u8` to be undeclared.");
+    assert_eq!(
+        StationResourceError::ResourceUndeclared(ResourceUndeclared::new(
+            station_spec.id().clone(),
+            String::from("Station A"),
+            "u8",
+            false,
+        )),
+        error
+    );
+
+    let error = station_resources
+        .borrow_mut::<u32>()
+        .expect_err("Expected `&mut u32` to be undeclared.");
+    assert_eq!(
+        StationResourceError::ResourceUndeclared(ResourceUndeclared::new(
+            station_spec.id().clone(),
+            String::from("Station A"),
+            "u32",
+            true,
+        )),
+        error
+    );
+
+    Ok(())
+}
+
+fn work_fn<'f>(
+    _station: &'f mut StationMutRef<'_, ()>,
+    _a: &'f u32,
+    _b: &'f mut u64,
+) -> LocalBoxFuture<'f, Result<ResIds, (ResIds, ())>> {
+    async { Ok(ResIds::new()) }.boxed_local()
+}
@@ -18,3 +18,21 @@ fn display_returns_readable_informative_message() -> Result<(), StationIdInvalid
     assert_eq!("Station Name: One liner.", station_spec.to_string());
     Ok(())
 }
+
+#[test]
+fn location_is_where_station_and_fns_are_constructed() -> Result<(), StationIdInvalidFmt<'static>>
+{
+    let line = line!() + 1;
+    let station_spec = StationSpec::<()>::mock("a")?
+        .with_create_work_fn(StationFn::ok(ResIds::new()))
+        .build();
+
+    let location = station_spec.location();
+    assert_eq!(file!(), location.file());
+    assert_eq!(line, location.line());
+
+    let work_fn = &station_spec.station_op().create_fns().work_fn;
+    assert_eq!(file!(), work_fn.location().file());
+    assert_eq!(line + 1, work_fn.location().line());
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use choochoo_cfg_model::rt::{
+    ResIdsPrevious, ResourceScope, ResourceTeardown, StationRtId, TrainResources,
+};
+use choochoo_resource::{
+    FilesRw, HistoryDir, Profile, ProfileDir, ProfileHistoryDir, WorkspaceDir,
+};
+use futures::future::{FutureExt, LocalBoxFuture};
+use tokio::runtime;
+
+#[test]
+fn with_mocks_inserts_execution_resources() {
+    let train_resources = TrainResources::<()>::with_mocks("/workspace");
+
+    assert_eq!(
+        Path::new("/workspace"),
+        &**train_resources.borrow::<WorkspaceDir>()
+    );
+    assert_eq!(
+        Path::new("/workspace/target/.history"),
+        &**train_resources.borrow::<HistoryDir>()
+    );
+    assert_eq!(
+        Path::new("/workspace/target/.history/default"),
+        &**train_resources.borrow::<ProfileHistoryDir>()
+    );
+    assert_eq!(
+        Path::new("/workspace/target/default"),
+        &**train_resources.borrow::<ProfileDir>()
+    );
+    assert_eq!("default", &**train_resources.borrow::<Profile>());
+    assert!(train_resources.borrow::<ResIdsPrevious>().is_empty());
+    assert!(train_resources.try_borrow::<FilesRw>().is_ok());
+}
+
+#[test]
+fn with_resource_inserts_resource() {
+    let train_resources = TrainResources::<()>::new().with_resource(123u32);
+
+    assert_eq!(123u32, *train_resources.borrow::<u32>());
+}
+
+#[test]
+fn station_borrow_returns_narrowest_scoped_resource() {
+    let station_rt_id = StationRtId::new(0);
+    let mut train_resources = TrainResources::<()>::new().with_resource(1u32);
+    train_resources.insert_scoped(ResourceScope::Station(station_rt_id), 2u32);
+    train_resources.insert_scoped(ResourceScope::Op(station_rt_id), 3u32);
+
+    assert_eq!(3u32, *train_resources.station_borrow::<u32>(station_rt_id));
+    assert_eq!(1u32, *train_resources.station_borrow::<u32>(StationRtId::new(1)));
+
+    train_resources.scope_drop(ResourceScope::Op(station_rt_id));
+    assert_eq!(2u32, *train_resources.station_borrow::<u32>(station_rt_id));
+
+    *train_resources.station_borrow_mut::<u32>(station_rt_id) = 4u32;
+    assert_eq!(4u32, *train_resources.station_borrow::<u32>(station_rt_id));
+    assert_eq!(1u32, *train_resources.borrow::<u32>());
+
+    train_resources.scope_drop(ResourceScope::Station(station_rt_id));
+    assert_eq!(1u32, *train_resources.station_borrow::<u32>(station_rt_id));
+}
+
+#[test]
+fn teardown_tears_down_resources_in_reverse_insertion_order()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let teardowns = Arc::new(Mutex::new(Vec::new()));
+    let mut train_resources = TrainResources::<String>::new();
+    train_resources.insert_teardown(TeardownOk(Arc::clone(&teardowns)));
+    train_resources.insert_teardown(TeardownErr(Arc::clone(&teardowns)));
+
+    let errors = rt.block_on(train_resources.teardown());
+
+    assert_eq!(vec![String::from("teardown_err")], errors);
+    assert_eq!(vec!["teardown_err", "teardown_ok"], *teardowns.lock().unwrap());
+    assert!(rt.block_on(train_resources.teardown()).is_empty());
+    assert_eq!(2, teardowns.lock().unwrap().len());
+
+    Ok(())
+}
+
+struct TeardownOk(Arc<Mutex<Vec<&'static str>>>);
+
+impl ResourceTeardown<String> for TeardownOk {
+    fn teardown(&mut self) -> LocalBoxFuture<'_, Result<(), String>> {
+        self.0.lock().unwrap().push("teardown_ok");
+        async { Ok(()) }.boxed_local()
+    }
+}
+
+struct TeardownErr(Arc<Mutex<Vec<&'static str>>>);
+
+impl ResourceTeardown<String> for TeardownErr {
+    fn teardown(&mut self) -> LocalBoxFuture<'_, Result<(), String>> {
+        self.0.lock().unwrap().push("teardown_err");
+        async { Err(String::from("teardown_err")) }.boxed_local()
+    }
+}
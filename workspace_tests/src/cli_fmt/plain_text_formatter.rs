@@ -1,11 +1,17 @@
+use std::{borrow::Cow, time::Duration};
+
 use tokio::runtime;
 
 use choochoo_cfg_model::{
-    rt::{OpStatus, StationErrors, StationRtId, TrainResources},
+    indexmap::IndexMap,
+    rt::{
+        OpStatus, ProgressLimit, ResIdLogical, ResIds, StationErrorPhase, StationErrors,
+        StationRtId, TrainResources, VisitOp,
+    },
     StationSpec,
 };
-use choochoo_cli_fmt::PlainTextFormatter;
-use choochoo_rt_model::{Destination, TrainReport};
+use choochoo_cli_fmt::{ColorMode, FormatterOpts, MessageCatalog, PlainTextFormatter};
+use choochoo_rt_model::{Destination, TrainPlan, TrainReport, Verbosity};
 
 #[test]
 fn writes_station_status_name_and_description() -> Result<(), Box<dyn std::error::Error>> {
@@ -92,7 +98,12 @@ fn writes_station_status_name_and_description() -> Result<(), Box<dyn std::error
     }
     let train_report = TrainReport::default();
 
-    rt.block_on(PlainTextFormatter::fmt(&mut output, &dest, &train_report))?;
+    rt.block_on(PlainTextFormatter::fmt(
+        &mut output,
+        &dest,
+        &train_report,
+        &FormatterOpts::default(),
+    ))?;
 
     assert_eq!(
         "\
@@ -107,6 +118,210 @@ fn writes_station_status_name_and_description() -> Result<(), Box<dyn std::error
         ✅ I: i_desc\n\
         ❌ J: j_desc\n\
         ❌ K: k_desc\n\
+        \n\
+        Summary: 1 succeeded, 1 unnecessary, 3 failed, 1 parent failed\n\
+        ",
+        String::from_utf8(output)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn writes_ascii_icons_truncated_to_width_when_configured() -> Result<(), Box<dyn std::error::Error>>
+{
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let mut output = Vec::with_capacity(1024);
+    let (mut dest, [station_a, station_b]) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let station_ids = dest_builder.add_stations([
+            StationSpec::mock("a")?
+                .with_name("A")
+                .with_description("a_desc")
+                .build(),
+            StationSpec::mock("b")?
+                .with_name("B")
+                .with_description("b_description_that_is_long")
+                .build(),
+        ]);
+        (dest_builder.build()?, station_ids)
+    };
+    {
+        let station_progresses = dest.station_progresses_mut();
+        station_progresses[&station_a].borrow_mut().op_status = OpStatus::WorkSuccess;
+        station_progresses[&station_b].borrow_mut().op_status = OpStatus::WorkFail;
+    }
+    let train_report = TrainReport::default();
+    let opts = FormatterOpts::new()
+        .with_color_mode(ColorMode::Never)
+        .with_ascii_only(true)
+        .with_width(20);
+
+    rt.block_on(PlainTextFormatter::fmt(
+        &mut output,
+        &dest,
+        &train_report,
+        &opts,
+    ))?;
+
+    assert_eq!(
+        "\
+        [ok] A: a_desc\n\
+        [!!] B: b_descrip...\n\
+        \n\
+        Summary: 1 succeeded, 0 unnecessary, 1 failed, 0 parent failed\n\
+        ",
+        String::from_utf8(output)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn writes_only_summary_when_quiet() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let mut output = Vec::with_capacity(1024);
+    let (mut dest, station_a) = dest_with_station_a()?;
+    dest.station_progresses_mut()[&station_a]
+        .borrow_mut()
+        .op_status = OpStatus::WorkSuccess;
+    let train_report = TrainReport::default();
+    let opts = FormatterOpts::new().with_verbosity(Verbosity::Quiet);
+
+    rt.block_on(PlainTextFormatter::fmt(
+        &mut output,
+        &dest,
+        &train_report,
+        &opts,
+    ))?;
+
+    assert_eq!(
+        "\
+        \n\
+        Summary: 1 succeeded, 0 unnecessary, 0 failed, 0 parent failed\n\
+        ",
+        String::from_utf8(output)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn writes_station_result_and_duration_when_verbose() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let mut output = Vec::with_capacity(1024);
+    let (mut dest, station_a) = dest_with_station_a()?;
+    dest.station_progresses_mut()[&station_a]
+        .borrow_mut()
+        .op_status = OpStatus::WorkSuccess;
+    let train_report = {
+        let mut station_durations = IndexMap::new();
+        station_durations.insert(station_a, Duration::from_millis(5));
+        TrainReport::default().with_station_durations(station_durations)
+    };
+    let opts = FormatterOpts::new().with_verbosity(Verbosity::Verbose);
+
+    rt.block_on(PlainTextFormatter::fmt(
+        &mut output,
+        &dest,
+        &train_report,
+        &opts,
+    ))?;
+
+    assert_eq!(
+        "\
+        ✅ A: a_desc\n\
+        \x20   work done in 5.00ms\n\
+        \n\
+        Summary: 1 succeeded, 0 unnecessary, 0 failed, 0 parent failed\n\
+        ",
+        String::from_utf8(output)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn writes_res_ids_grouped_by_station_when_verbose() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let mut output = Vec::with_capacity(1024);
+    let (mut dest, station_a) = dest_with_station_a()?;
+    dest.station_progresses_mut()[&station_a]
+        .borrow_mut()
+        .op_status = OpStatus::WorkSuccess;
+    let train_report = {
+        let mut res_ids = ResIds::new();
+        res_ids.insert(ResIdLogical::new("instance_id"), String::from("i-123"));
+        let mut station_res_ids = IndexMap::new();
+        station_res_ids.insert(station_a, res_ids);
+        TrainReport::default().with_station_res_ids(station_res_ids)
+    };
+    let opts = FormatterOpts::new().with_verbosity(Verbosity::Verbose);
+
+    rt.block_on(PlainTextFormatter::fmt(
+        &mut output,
+        &dest,
+        &train_report,
+        &opts,
+    ))?;
+
+    assert_eq!(
+        "\
+        ✅ A: a_desc\n\
+        \x20   work done\n\
+        \n\
+        Resource IDs:\n\
+        * A:\n\
+        \x20   instance_id: \"i-123\"\n\
+        \n\
+        Summary: 1 succeeded, 0 unnecessary, 0 failed, 0 parent failed\n\
+        ",
+        String::from_utf8(output)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn writes_grouped_res_ids_as_tree_when_verbose() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let mut output = Vec::with_capacity(1024);
+    let (mut dest, station_a) = dest_with_station_a()?;
+    dest.station_progresses_mut()[&station_a]
+        .borrow_mut()
+        .op_status = OpStatus::WorkSuccess;
+    let train_report = {
+        let mut res_ids = ResIds::new();
+        res_ids.insert(ResIdLogical::new("network/subnet/0"), String::from("subnet-0"));
+        res_ids.insert(ResIdLogical::new("instance_id"), String::from("i-123"));
+        res_ids.insert(ResIdLogical::new("network/vpc"), String::from("vpc-1"));
+        let mut station_res_ids = IndexMap::new();
+        station_res_ids.insert(station_a, res_ids);
+        TrainReport::default().with_station_res_ids(station_res_ids)
+    };
+    let opts = FormatterOpts::new().with_verbosity(Verbosity::Verbose);
+
+    rt.block_on(PlainTextFormatter::fmt(
+        &mut output,
+        &dest,
+        &train_report,
+        &opts,
+    ))?;
+
+    assert_eq!(
+        "\
+        ✅ A: a_desc\n\
+        \x20   work done\n\
+        \n\
+        Resource IDs:\n\
+        * A:\n\
+        \x20   instance_id: \"i-123\"\n\
+        \x20   network/\n\
+        \x20       vpc: \"vpc-1\"\n\
+        \x20       subnet/\n\
+        \x20           0: \"subnet-0\"\n\
+        \n\
+        Summary: 1 succeeded, 0 unnecessary, 0 failed, 0 parent failed\n\
         ",
         String::from_utf8(output)?
     );
@@ -114,6 +329,93 @@ fn writes_station_status_name_and_description() -> Result<(), Box<dyn std::error
     Ok(())
 }
 
+#[test]
+fn writes_messages_from_message_catalog() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let mut output = Vec::with_capacity(1024);
+    let (mut dest, station_a) = dest_with_station_a()?;
+    dest.station_progresses_mut()[&station_a]
+        .borrow_mut()
+        .op_status = OpStatus::WorkSuccess;
+    let train_report = TrainReport::default();
+    let opts = FormatterOpts::new().with_message_catalog(TerseCatalog);
+
+    rt.block_on(PlainTextFormatter::fmt(
+        &mut output,
+        &dest,
+        &train_report,
+        &opts,
+    ))?;
+
+    assert_eq!(
+        "\
+        done A: a_desc\n\
+        \n\
+        1/0/0/0\n\
+        ",
+        String::from_utf8(output)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn writes_first_error_of_each_failing_subgraph() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let mut output = Vec::with_capacity(1024);
+    let (mut dest, [station_a, station_b, station_c, station_d]) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let station_ids = dest_builder.add_stations([
+            StationSpec::mock("a")?.with_name("A").build(),
+            StationSpec::mock("b")?.with_name("B").build(),
+            StationSpec::mock("c")?.with_name("C").build(),
+            StationSpec::mock("d")?.with_name("D").build(),
+        ]);
+        let [station_a, station_b, _station_c, _station_d] = station_ids;
+        dest_builder.add_edge(station_a, station_b)?;
+        (dest_builder.build()?, station_ids)
+    };
+    {
+        let station_progresses = dest.station_progresses_mut();
+        station_progresses[&station_a].borrow_mut().op_status = OpStatus::WorkFail;
+        station_progresses[&station_b].borrow_mut().op_status = OpStatus::ParentFail;
+        station_progresses[&station_c].borrow_mut().op_status = OpStatus::CheckFail;
+        station_progresses[&station_d].borrow_mut().op_status = OpStatus::WorkSuccess;
+    }
+    let train_report = TrainReport::default();
+    let station_summary = rt.block_on(async {
+        {
+            let errors = train_report.train_resources().station_errors();
+            errors.insert(station_c, StationErrorPhase::Check, ()).await;
+            errors.insert(station_a, StationErrorPhase::Work, ()).await;
+        }
+
+        PlainTextFormatter::fmt(&mut output, &dest, &train_report, &FormatterOpts::default())
+            .await?;
+
+        let output = String::from_utf8(output).expect("Expected output to be valid UTF8.");
+        Result::<_, std::io::Error>::Ok(
+            output
+                .lines()
+                .skip_while(|line| !line.starts_with("Summary"))
+                .take(4)
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    })?;
+
+    assert_eq!(
+        "\
+        Summary: 1 succeeded, 0 unnecessary, 2 failed, 1 parent failed\n\
+        First error of each failing subgraph:\n\
+        * a: A\n\
+        * c: C",
+        station_summary
+    );
+
+    Ok(())
+}
+
 #[test]
 fn formats_errors_as_human_readable_text() -> Result<(), Box<dyn std::error::Error>> {
     let mut output = Vec::with_capacity(1024);
@@ -123,11 +425,13 @@ fn formats_errors_as_human_readable_text() -> Result<(), Box<dyn std::error::Err
         let train_resources = TrainResources::<()>::new();
         {
             let errors = train_resources.borrow::<StationErrors<()>>();
-            let mut errors = errors.write().await;
-            errors.insert(StationRtId::new(0), ());
+            errors
+                .insert(StationRtId::new(0), StationErrorPhase::Work, ())
+                .await;
         }
 
-        PlainTextFormatter::fmt_errors(&mut output, &train_resources).await
+        let opts = FormatterOpts::new().with_color_mode(ColorMode::Always);
+        PlainTextFormatter::fmt_errors(&mut output, &train_resources, &opts).await
     })?;
 
     let output_expected = "\u{1b}[0m\u{1b}[1m\u{1b}[38;5;9merror\u{1b}[0m\u{1b}[1m: \u{1b}[0m\n\n";
@@ -138,3 +442,72 @@ fn formats_errors_as_human_readable_text() -> Result<(), Box<dyn std::error::Err
 
     Ok(())
 }
+
+#[test]
+fn writes_train_plan_waves() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let mut output = Vec::with_capacity(1024);
+    let (dest, [station_a, station_b, station_c]) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let station_rt_ids = dest_builder.add_stations([
+            StationSpec::mock("a")?.build(),
+            StationSpec::mock("b")?.build(),
+            StationSpec::mock("c")?.build(),
+        ]);
+        (dest_builder.build()?, station_rt_ids)
+    };
+    let mut train_plan = TrainPlan::new(VisitOp::Clean);
+    train_plan.station_add(station_c, vec![], ProgressLimit::Unknown, 0);
+    train_plan.station_add(station_b, vec![], ProgressLimit::Unknown, 0);
+    train_plan.station_add(station_a, vec![station_b], ProgressLimit::Unknown, 0);
+
+    rt.block_on(PlainTextFormatter::fmt_train_plan(
+        &mut output,
+        &dest,
+        &train_plan,
+        &FormatterOpts::default(),
+    ))?;
+
+    assert_eq!(
+        "\
+        Stations to clean:\n  \
+          1. c, b\n  \
+          2. a\n",
+        String::from_utf8(output)?
+    );
+
+    Ok(())
+}
+
+fn dest_with_station_a() -> Result<(Destination<()>, StationRtId), Box<dyn std::error::Error>> {
+    let mut dest_builder = Destination::<()>::builder();
+    let station_a = dest_builder.add_station(
+        StationSpec::mock("a")?
+            .with_name("A")
+            .with_description("a_desc")
+            .build(),
+    );
+    Ok((dest_builder.build()?, station_a))
+}
+
+#[derive(Debug)]
+struct TerseCatalog;
+
+impl MessageCatalog for TerseCatalog {
+    fn status_label(&self, _op_status: OpStatus) -> Cow<'_, str> {
+        Cow::Borrowed("done")
+    }
+
+    fn summary(
+        &self,
+        succeeded: usize,
+        unnecessary: usize,
+        failed: usize,
+        parent_failed: usize,
+    ) -> Cow<'_, str> {
+        Cow::Owned(format!(
+            "{}/{}/{}/{}",
+            succeeded, unnecessary, failed, parent_failed
+        ))
+    }
+}
@@ -0,0 +1,2 @@
+mod error_code;
+mod error_detail;
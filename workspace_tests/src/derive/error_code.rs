@@ -0,0 +1,42 @@
+use choochoo::derive::ErrorCode;
+use choochoo_cfg_model::srcerr::ErrorCode as _;
+
+/// Error codes for tests.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ErrorCode)]
+#[error_code(prefix = "T", max = 999)]
+enum TestErrorCode {
+    /// Failed to open `app.zip`.
+    AppZipOpen,
+    /// Failed to connect to the artifact server.
+    ///
+    /// Only the first paragraph is used as the description.
+    ArtifactServerConnect,
+    /// Failed to clean `app.zip`.
+    #[error_code(code = 20, description = "Failed to clean `app.zip` from the server.")]
+    AppZipClean,
+    /// Failed to clean the artifact server directory.
+    ArtifactServerDirClean,
+}
+
+#[test]
+fn derives_codes_sequentially_and_descriptions_from_docs() {
+    assert_eq!("T", TestErrorCode::PREFIX);
+    assert_eq!(999, TestErrorCode::ERROR_CODE_MAX);
+    assert_eq!(
+        vec![1, 2, 20, 21],
+        vec![
+            TestErrorCode::AppZipOpen.code(),
+            TestErrorCode::ArtifactServerConnect.code(),
+            TestErrorCode::AppZipClean.code(),
+            TestErrorCode::ArtifactServerDirClean.code(),
+        ]
+    );
+    assert_eq!(
+        "Failed to connect to the artifact server.",
+        TestErrorCode::ArtifactServerConnect.description()
+    );
+    assert_eq!(
+        "Failed to clean `app.zip` from the server.",
+        TestErrorCode::AppZipClean.description()
+    );
+}
@@ -0,0 +1,85 @@
+use std::borrow::Cow;
+
+use choochoo::derive::{AsDiagnostic, ErrorCode, ErrorDetail};
+use choochoo_cfg_model::srcerr::{
+    codespan::{FileId, Files, Span},
+    codespan_reporting::diagnostic::{Label, Severity},
+    ErrorDetail as _, SourceError,
+};
+use choochoo_rt_model::error::AsDiagnostic as _;
+
+/// Error codes for tests.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ErrorCode)]
+enum TestErrorCode {
+    /// Failed to connect to the artifact server.
+    ArtifactServerConnect,
+}
+
+/// Error details for tests.
+#[derive(Debug, ErrorDetail)]
+enum TestErrorDetail {
+    #[label(primary, file_id = address_file_id, span = address_span,
+        message = "failed to connect to server")]
+    #[label(secondary, file_id = address_file_id, span = port_span)]
+    #[note("Try running `simple-http-server --port {}`.",
+        files.source_slice(*address_file_id, *port_span).expect("Expected file to exist."))]
+    #[note("Retried {retry_count} times.")]
+    ArtifactServerConnect {
+        address_file_id: FileId,
+        address_span: Span,
+        port_span: Span,
+        retry_count: u32,
+    },
+}
+
+#[derive(Debug, AsDiagnostic)]
+struct TestError(SourceError<'static, TestErrorCode, TestErrorDetail, Files<Cow<'static, str>>>);
+
+#[test]
+fn derives_labels_and_notes_from_attributes() {
+    let mut files = Files::new();
+    let address_file_id = files.add("address", Cow::Borrowed("127.0.0.1:8000"));
+    let error_detail = TestErrorDetail::ArtifactServerConnect {
+        address_file_id,
+        address_span: Span::new(0, 14),
+        port_span: Span::new(10, 14),
+        retry_count: 3,
+    };
+
+    assert_eq!(
+        vec![
+            Label::primary(address_file_id, Span::new(0, 14))
+                .with_message("failed to connect to server"),
+            Label::secondary(address_file_id, Span::new(10, 14)),
+        ],
+        error_detail.labels()
+    );
+    assert_eq!(
+        vec![
+            String::from("Try running `simple-http-server --port 8000`."),
+            String::from("Retried 3 times."),
+        ],
+        error_detail.notes(&files)
+    );
+}
+
+#[test]
+fn as_diagnostic_delegates_to_wrapped_error() {
+    let mut files = Files::new();
+    let address_file_id = files.add("address", Cow::Borrowed("127.0.0.1:8000"));
+    let error = TestError(SourceError::new(
+        TestErrorCode::ArtifactServerConnect,
+        TestErrorDetail::ArtifactServerConnect {
+            address_file_id,
+            address_span: Span::new(0, 14),
+            port_span: Span::new(10, 14),
+            retry_count: 3,
+        },
+        Severity::Error,
+    ));
+
+    let diagnostic = error.as_diagnostic(&files);
+
+    assert_eq!(2, diagnostic.labels.len());
+    assert!(diagnostic.notes.contains(&String::from("Retried 3 times.")));
+}
@@ -2,6 +2,9 @@
 
 mod cfg_model;
 mod cli_fmt;
+mod derive;
+mod ops;
 mod resource;
 mod rt_logic;
 mod rt_model;
+mod test;
@@ -0,0 +1,9 @@
+mod archive_unpack_op;
+mod checksum;
+mod container;
+mod file_download_op;
+mod file_upload_op;
+mod http_server;
+mod migration_op;
+mod ssh;
+mod template_params;
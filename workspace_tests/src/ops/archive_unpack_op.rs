@@ -0,0 +1,52 @@
+use std::fs;
+
+use choochoo_cfg_model::{
+    rt::{OpStatus, VisitOp},
+    StationSpec,
+};
+use choochoo_ops::{ArchiveFormat, ArchivePackOp, ArchiveUnpackOp};
+use choochoo_test::StationHarness;
+use tokio::runtime;
+
+#[test]
+fn unpacks_packed_archive_and_cleans_extracted_files() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let tempdir = tempfile::tempdir()?;
+    let src_dir = tempdir.path().join("src");
+    let archive_path = tempdir.path().join("app.tar.gz");
+    let dest_dir = tempdir.path().join("dest");
+    fs::create_dir_all(src_dir.join("config"))?;
+    fs::write(src_dir.join("app.txt"), "app")?;
+    fs::write(src_dir.join("config").join("app.toml"), "key = 1")?;
+
+    let pack_spec = StationSpec::<()>::builder(
+        "pack",
+        ArchivePackOp::new(ArchiveFormat::TarGz, &src_dir, &archive_path).build(),
+    )?
+    .build();
+    let pack_report = rt.block_on(StationHarness::run_create(pack_spec))?;
+    assert_eq!(OpStatus::WorkSuccess, pack_report.op_status());
+
+    let unpack_spec = StationSpec::<()>::builder(
+        "unpack",
+        ArchiveUnpackOp::new(ArchiveFormat::TarGz, &archive_path, &dest_dir).build(),
+    )?
+    .build();
+    let unpack_report = rt.block_on(StationHarness::run_create(unpack_spec))?;
+    assert_eq!(OpStatus::WorkSuccess, unpack_report.op_status());
+    assert_eq!("app", fs::read_to_string(dest_dir.join("app.txt"))?);
+    assert_eq!(
+        "key = 1",
+        fs::read_to_string(dest_dir.join("config").join("app.toml"))?
+    );
+
+    let unpack_report = rt.block_on(unpack_report.into_harness().run(VisitOp::Create))?;
+    assert_eq!(OpStatus::WorkUnnecessary, unpack_report.op_status());
+
+    let clean_report = rt.block_on(unpack_report.into_harness().run(VisitOp::Clean))?;
+    assert_eq!(OpStatus::WorkSuccess, clean_report.op_status());
+    assert!(!dest_dir.join("app.txt").exists());
+    assert!(!dest_dir.join("config").exists());
+
+    Ok(())
+}
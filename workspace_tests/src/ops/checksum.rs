@@ -0,0 +1,42 @@
+use std::fs;
+
+use choochoo_cfg_model::rt::CheckStatus;
+use choochoo_ops::{Checksum, ChecksumAlgorithm};
+use tokio::runtime;
+
+#[test]
+fn file_check_compares_file_hash_with_recorded_checksum() -> Result<(), Box<dyn std::error::Error>>
+{
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let tempdir = tempfile::tempdir()?;
+    let file_path = tempdir.path().join("abc.txt");
+    let checksum_path = tempdir.path().join("abc.txt.checksum");
+    let expected = Checksum::new(
+        ChecksumAlgorithm::Sha256,
+        "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD",
+    );
+
+    let check_status = rt.block_on(Checksum::file_check(&file_path, &expected, None))?;
+    assert_eq!(CheckStatus::WorkRequired, check_status);
+
+    fs::write(&file_path, "abc")?;
+    rt.block_on(expected.record(&checksum_path))?;
+    let recorded = rt
+        .block_on(Checksum::recorded(&checksum_path))?
+        .expect("Expected checksum to be recorded.");
+    assert_eq!(expected, recorded);
+    let check_status = rt.block_on(Checksum::file_check(&file_path, &recorded, None))?;
+    assert_eq!(CheckStatus::WorkNotRequired, check_status);
+
+    let blake3 = rt.block_on(Checksum::file(ChecksumAlgorithm::Blake3, &file_path, None))?;
+    assert_eq!(
+        "blake3:6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85",
+        blake3.to_string()
+    );
+
+    fs::write(&file_path, "abcd")?;
+    let check_status = rt.block_on(Checksum::file_check(&file_path, &recorded, None))?;
+    assert_eq!(CheckStatus::WorkRequired, check_status);
+
+    Ok(())
+}
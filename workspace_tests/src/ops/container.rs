@@ -0,0 +1,3 @@
+mod container_run_op;
+mod image_build_op;
+mod image_push_op;
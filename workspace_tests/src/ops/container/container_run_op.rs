@@ -0,0 +1,36 @@
+use choochoo_cfg_model::StationOp;
+use choochoo_ops::container::ContainerRunOp;
+
+#[test]
+fn docker_args_pass_run_args_before_image() {
+    let container_run_op = ContainerRunOp::new("app:1.0.0", "app")
+        .with_run_arg("--publish")
+        .with_run_arg("8080:80");
+
+    assert_eq!(
+        vec![
+            "container",
+            "run",
+            "--detach",
+            "--name",
+            "app",
+            "--publish",
+            "8080:80",
+            "app:1.0.0",
+        ],
+        container_run_op.docker_args()
+    );
+}
+
+#[test]
+fn build_has_check_and_clean_fns() {
+    let station_op: StationOp<()> = ContainerRunOp::new("app:1.0.0", "app").build();
+
+    assert!(station_op.create_fns().check_fn.is_some());
+    assert!(
+        station_op
+            .clean_fns()
+            .map(|clean_fns| clean_fns.check_fn.is_some())
+            .unwrap_or(false)
+    );
+}
@@ -0,0 +1,46 @@
+use choochoo_ops::container::ImageBuildOp;
+
+#[test]
+fn docker_args_use_default_dockerfile() {
+    let image_build_op = ImageBuildOp::new("app", "app:1.0.0");
+
+    assert_eq!(
+        vec!["build", "--tag", "app:1.0.0", "app"],
+        image_build_op.docker_args()
+    );
+}
+
+#[test]
+fn docker_args_pass_dockerfile_before_context_dir() {
+    let image_build_op =
+        ImageBuildOp::new("app", "app:1.0.0").with_dockerfile_path("docker/Dockerfile");
+
+    assert_eq!(
+        vec![
+            "build",
+            "--tag",
+            "app:1.0.0",
+            "--file",
+            "docker/Dockerfile",
+            "app",
+        ],
+        image_build_op.docker_args()
+    );
+}
+
+#[test]
+fn record_parse_returns_formatted_record() {
+    let record = ImageBuildOp::record_format("abc123", "sha256:def456");
+
+    assert_eq!(
+        Some(("abc123", "sha256:def456")),
+        ImageBuildOp::record_parse(&record)
+    );
+}
+
+#[test]
+fn record_parse_returns_none_for_incomplete_record() {
+    assert_eq!(None, ImageBuildOp::record_parse(""));
+    assert_eq!(None, ImageBuildOp::record_parse("abc123\n"));
+    assert_eq!(None, ImageBuildOp::record_parse("abc123\n\n"));
+}
@@ -0,0 +1,43 @@
+use choochoo_cfg_model::StationOp;
+use choochoo_ops::container::ImagePushOp;
+
+#[test]
+fn repo_digest_find_returns_digest_in_image_repository() {
+    let repo_digests = "\
+        registry.example.com:5000/other@sha256:000\n\
+        registry.example.com:5000/app@sha256:abc\n";
+
+    assert_eq!(
+        Some("sha256:abc"),
+        ImagePushOp::repo_digest_find("registry.example.com:5000/app:1.0.0", repo_digests)
+    );
+}
+
+#[test]
+fn repo_digest_find_ignores_image_digest_suffix() {
+    let repo_digests = "app@sha256:abc\n";
+
+    assert_eq!(
+        Some("sha256:abc"),
+        ImagePushOp::repo_digest_find("app@sha256:old", repo_digests)
+    );
+}
+
+#[test]
+fn repo_digest_find_returns_none_when_not_pushed_to_repository() {
+    let repo_digests = "registry.example.com/app@sha256:abc\n";
+
+    assert_eq!(
+        None,
+        ImagePushOp::repo_digest_find("other.example.com/app:1.0.0", repo_digests)
+    );
+    assert_eq!(None, ImagePushOp::repo_digest_find("app:1.0.0", ""));
+}
+
+#[test]
+fn build_has_no_clean_fns() {
+    let station_op: StationOp<()> = ImagePushOp::new("app:1.0.0").build();
+
+    assert!(station_op.create_fns().check_fn.is_some());
+    assert!(station_op.clean_fns().is_none());
+}
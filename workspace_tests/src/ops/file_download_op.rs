@@ -0,0 +1,119 @@
+use std::{fs, path::Path};
+
+use choochoo_cfg_model::{
+    rt::{OpStatus, StationRtId, TrainResources, VisitOp},
+    ResourceInitFn, StationSpec,
+};
+use choochoo_ops::{Checksum, ChecksumAlgorithm, FileDownloadOp};
+use choochoo_rt_logic::Train;
+use choochoo_rt_model::{Destination, WorkspaceSpec};
+use futures::FutureExt;
+use tokio::runtime;
+
+use super::http_server::HttpServer;
+
+#[derive(Debug)]
+struct FileUrl(String);
+
+impl AsRef<str> for FileUrl {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[test]
+fn downloads_file_again_when_local_contents_change_with_same_length()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().enable_all().build()?;
+    let server = HttpServer::start()?;
+    server.file_insert("/greeting.txt", b"hello");
+    let tempdir = tempfile::tempdir()?;
+    let dest_path = tempdir.path().join("greeting.txt");
+    let (mut dest, station_rt_id) =
+        dest_build(tempdir.path(), FileDownloadOp::<FileUrl>::new(&dest_path))?;
+    let train = train(server.url("/greeting.txt"));
+
+    rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+    assert_eq!(OpStatus::WorkSuccess, op_status(&dest, station_rt_id));
+    assert_eq!("hello", fs::read_to_string(&dest_path)?);
+
+    rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+    assert_eq!(OpStatus::WorkUnnecessary, op_status(&dest, station_rt_id));
+
+    // Same length as the remote file, so only the checksum detects the change.
+    fs::write(&dest_path, "jello")?;
+    rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+    assert_eq!(OpStatus::WorkSuccess, op_status(&dest, station_rt_id));
+    assert_eq!("hello", fs::read_to_string(&dest_path)?);
+
+    rt.block_on(train.reach(&mut dest, VisitOp::Clean))?;
+    assert_eq!(OpStatus::WorkSuccess, op_status(&dest, station_rt_id));
+    assert!(!dest_path.exists());
+
+    Ok(())
+}
+
+#[test]
+fn skips_download_when_local_file_matches_checksum() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().enable_all().build()?;
+    let server = HttpServer::start()?;
+    server.file_insert("/greeting.txt", b"hello");
+    let tempdir = tempfile::tempdir()?;
+    let dest_path = tempdir.path().join("greeting.txt");
+    fs::write(&dest_path, "hello")?;
+    let file_download_op = FileDownloadOp::<FileUrl>::new(&dest_path)
+        .with_checksum(Checksum::bytes(ChecksumAlgorithm::Sha256, b"hello"));
+    let (mut dest, station_rt_id) = dest_build(tempdir.path(), file_download_op)?;
+    let train = train(server.url("/greeting.txt"));
+
+    rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+    assert_eq!(OpStatus::WorkUnnecessary, op_status(&dest, station_rt_id));
+
+    Ok(())
+}
+
+#[test]
+fn fails_when_downloaded_file_does_not_match_checksum() -> Result<(), Box<dyn std::error::Error>>
+{
+    let rt = runtime::Builder::new_current_thread().enable_all().build()?;
+    let server = HttpServer::start()?;
+    server.file_insert("/greeting.txt", b"hello");
+    let tempdir = tempfile::tempdir()?;
+    let dest_path = tempdir.path().join("greeting.txt");
+    let file_download_op = FileDownloadOp::<FileUrl>::new(&dest_path)
+        .with_checksum(Checksum::bytes(ChecksumAlgorithm::Sha256, b"jello"));
+    let (mut dest, station_rt_id) = dest_build(tempdir.path(), file_download_op)?;
+    let train = train(server.url("/greeting.txt"));
+
+    rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+    assert_eq!(OpStatus::WorkFail, op_status(&dest, station_rt_id));
+
+    Ok(())
+}
+
+fn dest_build(
+    workspace_dir: &Path,
+    file_download_op: FileDownloadOp<FileUrl>,
+) -> Result<(Destination<()>, StationRtId), Box<dyn std::error::Error>> {
+    let mut dest_builder = Destination::<()>::builder()
+        .with_workspace_spec(WorkspaceSpec::Path(workspace_dir.to_path_buf()));
+    let station_rt_id = dest_builder
+        .add_station(StationSpec::<()>::builder("download", file_download_op.build())?.build());
+    let dest = dest_builder.build()?;
+
+    Ok((dest, station_rt_id))
+}
+
+fn train(url: String) -> Train<()> {
+    Train::default().with_resource_init_fn(ResourceInitFn::new(
+        "file_url",
+        move |train_resources: &mut TrainResources<()>| {
+            train_resources.insert(FileUrl(url.clone()));
+            async { Ok(()) }.boxed_local()
+        },
+    ))
+}
+
+fn op_status(dest: &Destination<()>, station_rt_id: StationRtId) -> OpStatus {
+    dest.station_progresses()[&station_rt_id].borrow().op_status
+}
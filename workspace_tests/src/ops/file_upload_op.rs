@@ -0,0 +1,102 @@
+use std::{fs, path::Path};
+
+use choochoo_cfg_model::{
+    rt::{OpStatus, StationRtId, TrainResources, VisitOp},
+    ResourceInitFn, StationSpec,
+};
+use choochoo_ops::FileUploadOp;
+use choochoo_rt_logic::Train;
+use choochoo_rt_model::{Destination, WorkspaceSpec};
+use futures::FutureExt;
+use tokio::runtime;
+
+use super::http_server::HttpServer;
+
+#[derive(Debug)]
+struct FileUrl(String);
+
+impl AsRef<str> for FileUrl {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[test]
+fn uploads_file_again_when_local_contents_change_with_same_length()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().enable_all().build()?;
+    let server = HttpServer::start()?;
+    let tempdir = tempfile::tempdir()?;
+    let src_path = tempdir.path().join("greeting.txt");
+    fs::write(&src_path, "hello")?;
+    let (mut dest, station_rt_id) =
+        dest_build(tempdir.path(), FileUploadOp::<FileUrl>::new(&src_path))?;
+    let train = train(server.url("/greeting.txt"));
+
+    rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+    assert_eq!(OpStatus::WorkSuccess, op_status(&dest, station_rt_id));
+    assert_eq!(Some(b"hello".to_vec()), server.file("/greeting.txt"));
+
+    rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+    assert_eq!(OpStatus::WorkUnnecessary, op_status(&dest, station_rt_id));
+
+    // Same length as the uploaded file, so only the checksum detects the change.
+    fs::write(&src_path, "jello")?;
+    rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+    assert_eq!(OpStatus::WorkSuccess, op_status(&dest, station_rt_id));
+    assert_eq!(Some(b"jello".to_vec()), server.file("/greeting.txt"));
+
+    rt.block_on(train.reach(&mut dest, VisitOp::Clean))?;
+    assert_eq!(OpStatus::WorkSuccess, op_status(&dest, station_rt_id));
+    assert_eq!(None, server.file("/greeting.txt"));
+
+    Ok(())
+}
+
+#[test]
+fn uploads_file_when_server_does_not_have_it() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().enable_all().build()?;
+    let server = HttpServer::start()?;
+    let tempdir = tempfile::tempdir()?;
+    let src_path = tempdir.path().join("greeting.txt");
+    fs::write(&src_path, "hello")?;
+    let (mut dest, station_rt_id) =
+        dest_build(tempdir.path(), FileUploadOp::<FileUrl>::new(&src_path))?;
+    let train = train(server.url("/greeting.txt"));
+    rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+
+    // Removed from the server, though the local file still matches the record.
+    server.file_remove("/greeting.txt");
+    rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+    assert_eq!(OpStatus::WorkSuccess, op_status(&dest, station_rt_id));
+    assert_eq!(Some(b"hello".to_vec()), server.file("/greeting.txt"));
+
+    Ok(())
+}
+
+fn dest_build(
+    workspace_dir: &Path,
+    file_upload_op: FileUploadOp<FileUrl>,
+) -> Result<(Destination<()>, StationRtId), Box<dyn std::error::Error>> {
+    let mut dest_builder = Destination::<()>::builder()
+        .with_workspace_spec(WorkspaceSpec::Path(workspace_dir.to_path_buf()));
+    let station_rt_id = dest_builder
+        .add_station(StationSpec::<()>::builder("upload", file_upload_op.build())?.build());
+    let dest = dest_builder.build()?;
+
+    Ok((dest, station_rt_id))
+}
+
+fn train(url: String) -> Train<()> {
+    Train::default().with_resource_init_fn(ResourceInitFn::new(
+        "file_url",
+        move |train_resources: &mut TrainResources<()>| {
+            train_resources.insert(FileUrl(url.clone()));
+            async { Ok(()) }.boxed_local()
+        },
+    ))
+}
+
+fn op_status(dest: &Destination<()>, station_rt_id: StationRtId) -> OpStatus {
+    dest.station_progresses()[&station_rt_id].borrow().op_status
+}
@@ -0,0 +1,167 @@
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+/// Minimal HTTP/1.1 server that stores files in memory.
+///
+/// Supports `GET`, `HEAD`, `PUT`, and `DELETE`, which is enough to exercise
+/// the HTTP file transfer operations without network access.
+#[derive(Clone, Debug)]
+pub struct HttpServer {
+    /// Address the server listens on.
+    addr: String,
+    /// Files served, keyed by request path.
+    files: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+}
+
+impl HttpServer {
+    /// Starts a server on an ephemeral local port.
+    pub fn start() -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?.to_string();
+        let files = Arc::new(Mutex::new(HashMap::new()));
+
+        let server_files = files.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let files = server_files.clone();
+                thread::spawn(move || {
+                    let _ = Self::connection_handle(stream, &files);
+                });
+            }
+        });
+
+        Ok(Self { addr, files })
+    }
+
+    /// Returns the URL of a file on the server.
+    pub fn url(&self, path: &str) -> String {
+        format!("http://{}{}", self.addr, path)
+    }
+
+    /// Stores a file on the server.
+    pub fn file_insert(&self, path: &str, contents: &[u8]) {
+        self.files
+            .lock()
+            .unwrap()
+            .insert(path.to_string(), contents.to_vec());
+    }
+
+    /// Removes a file from the server.
+    pub fn file_remove(&self, path: &str) {
+        self.files.lock().unwrap().remove(path);
+    }
+
+    /// Returns the contents of a file on the server.
+    pub fn file(&self, path: &str) -> Option<Vec<u8>> {
+        self.files.lock().unwrap().get(path).cloned()
+    }
+
+    fn connection_handle(
+        stream: TcpStream,
+        files: &Mutex<HashMap<String, Vec<u8>>>,
+    ) -> io::Result<()> {
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut writer = stream;
+
+        loop {
+            let mut request_line = String::new();
+            if reader.read_line(&mut request_line)? == 0 {
+                return Ok(());
+            }
+            let mut parts = request_line.split_whitespace();
+            let method = parts.next().unwrap_or_default().to_string();
+            let path = parts.next().unwrap_or_default().to_string();
+
+            let mut content_length = None;
+            let mut chunked = false;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header)?;
+                let header = header.trim_end();
+                if header.is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = header.split_once(':') {
+                    let value = value.trim();
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.parse::<usize>().ok();
+                    } else if name.eq_ignore_ascii_case("transfer-encoding") {
+                        chunked = value.eq_ignore_ascii_case("chunked");
+                    }
+                }
+            }
+
+            let body = if chunked {
+                Self::chunked_body_read(&mut reader)?
+            } else {
+                let mut body = vec![0u8; content_length.unwrap_or(0)];
+                reader.read_exact(&mut body)?;
+                body
+            };
+
+            let mut files = files.lock().unwrap();
+            match method.as_str() {
+                "GET" | "HEAD" => match files.get(&path) {
+                    Some(contents) => {
+                        write!(
+                            writer,
+                            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n",
+                            contents.len()
+                        )?;
+                        if method == "GET" {
+                            writer.write_all(contents)?;
+                        }
+                    }
+                    None => Self::empty_response_write(&mut writer, "404 Not Found")?,
+                },
+                "PUT" => {
+                    files.insert(path, body);
+                    Self::empty_response_write(&mut writer, "201 Created")?;
+                }
+                "DELETE" => match files.remove(&path) {
+                    Some(_) => Self::empty_response_write(&mut writer, "204 No Content")?,
+                    None => Self::empty_response_write(&mut writer, "404 Not Found")?,
+                },
+                _ => Self::empty_response_write(&mut writer, "405 Method Not Allowed")?,
+            }
+            writer.flush()?;
+        }
+    }
+
+    fn chunked_body_read(reader: &mut impl BufRead) -> io::Result<Vec<u8>> {
+        let mut body = Vec::new();
+        loop {
+            let mut size_line = String::new();
+            reader.read_line(&mut size_line)?;
+            let size_hex = size_line.trim_end().split(';').next().unwrap_or_default();
+            let size = usize::from_str_radix(size_hex, 16)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+            if size == 0 {
+                // Trailers, ending with an empty line.
+                loop {
+                    let mut trailer = String::new();
+                    reader.read_line(&mut trailer)?;
+                    if trailer.trim_end().is_empty() {
+                        return Ok(body);
+                    }
+                }
+            }
+
+            let mut chunk = vec![0u8; size];
+            reader.read_exact(&mut chunk)?;
+            body.extend_from_slice(&chunk);
+
+            let mut crlf = String::new();
+            reader.read_line(&mut crlf)?;
+        }
+    }
+
+    fn empty_response_write(writer: &mut impl Write, status: &str) -> io::Result<()> {
+        write!(writer, "HTTP/1.1 {}\r\nContent-Length: 0\r\n\r\n", status)
+    }
+}
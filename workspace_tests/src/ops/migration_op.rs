@@ -0,0 +1,56 @@
+use std::fs;
+
+use choochoo_cfg_model::{
+    rt::{OpStatus, VisitOp},
+    StationSpec,
+};
+use choochoo_ops::{Migration, MigrationOp};
+use choochoo_resource::ProfileDir;
+use choochoo_test::StationHarness;
+use tokio::runtime;
+
+#[test]
+fn applies_migrations_once_and_reverts_them_on_clean() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let migration_op = MigrationOp::<ProfileDir, ()>::new()
+        .with_migration(
+            Migration::<ProfileDir, ()>::new(2, "add_index", |profile_dir: &ProfileDir| {
+                let path = profile_dir.join("2_add_index");
+                Box::pin(async move { fs::write(path, "2").map_err(|_| ()) })
+            })
+            .with_down_fn(|profile_dir: &ProfileDir| {
+                let path = profile_dir.join("2_add_index");
+                Box::pin(async move { fs::remove_file(path).map_err(|_| ()) })
+            }),
+        )
+        .with_migration(
+            Migration::<ProfileDir, ()>::new(1, "create_table", |profile_dir: &ProfileDir| {
+                let path = profile_dir.join("1_create_table");
+                Box::pin(async move { fs::write(path, "1").map_err(|_| ()) })
+            })
+            .with_down_fn(|profile_dir: &ProfileDir| {
+                let path = profile_dir.join("1_create_table");
+                Box::pin(async move { fs::remove_file(path).map_err(|_| ()) })
+            }),
+        );
+    let spec = StationSpec::<()>::builder("migrate", migration_op.build())?.build();
+
+    let report = rt.block_on(StationHarness::run_create(spec))?;
+    assert_eq!(OpStatus::WorkSuccess, report.op_status());
+    let profile_dir = report
+        .train_resources()
+        .borrow::<ProfileDir>()
+        .to_path_buf();
+    assert!(profile_dir.join("1_create_table").exists());
+    assert!(profile_dir.join("2_add_index").exists());
+
+    let report = rt.block_on(report.into_harness().run(VisitOp::Create))?;
+    assert_eq!(OpStatus::WorkUnnecessary, report.op_status());
+
+    let report = rt.block_on(report.into_harness().run(VisitOp::Clean))?;
+    assert_eq!(OpStatus::WorkSuccess, report.op_status());
+    assert!(!profile_dir.join("1_create_table").exists());
+    assert!(!profile_dir.join("2_add_index").exists());
+
+    Ok(())
+}
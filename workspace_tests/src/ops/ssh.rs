@@ -0,0 +1,2 @@
+mod ssh_command_op;
+mod ssh_target;
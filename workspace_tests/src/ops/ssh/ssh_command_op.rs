@@ -0,0 +1,16 @@
+use choochoo_cfg_model::StationOp;
+use choochoo_ops::ssh::{SshCommandOp, SshTarget};
+
+#[test]
+fn build_has_clean_fns_only_when_clean_command_is_set() {
+    let station_op: StationOp<()> = SshCommandOp::<SshTarget>::new("systemctl start app").build();
+    assert!(station_op.create_fns().check_fn.is_none());
+    assert!(station_op.clean_fns().is_none());
+
+    let station_op: StationOp<()> = SshCommandOp::<SshTarget>::new("systemctl start app")
+        .with_check_command("systemctl is-active app")
+        .with_clean_command("systemctl stop app")
+        .build();
+    assert!(station_op.create_fns().check_fn.is_some());
+    assert!(station_op.clean_fns().is_some());
+}
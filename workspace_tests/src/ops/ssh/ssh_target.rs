@@ -0,0 +1,81 @@
+use std::{ffi::OsString, path::Path};
+
+use choochoo_ops::ssh::SshTarget;
+
+#[test]
+fn ssh_args_place_destination_and_command_after_double_dash() {
+    let ssh_target = SshTarget::new("example.com")
+        .with_port(2222)
+        .with_user("deploy")
+        .with_identity_file("id_ed25519");
+
+    let ssh_args = ssh_target.ssh_args("uptime");
+
+    assert_eq!(
+        os_strings(&[
+            "-p",
+            "2222",
+            "-i",
+            "id_ed25519",
+            "-o",
+            "BatchMode=yes",
+            "--",
+            "deploy@example.com",
+            "uptime",
+        ]),
+        ssh_args
+    );
+}
+
+#[test]
+fn ssh_args_do_not_pass_host_beginning_with_dash_as_option() {
+    let ssh_target = SshTarget::new("-oProxyCommand=touch pwned");
+
+    let ssh_args = ssh_target.ssh_args("uptime");
+
+    assert_eq!(
+        os_strings(&[
+            "-o",
+            "BatchMode=yes",
+            "--",
+            "-oProxyCommand=touch pwned",
+            "uptime",
+        ]),
+        ssh_args
+    );
+}
+
+#[test]
+fn scp_args_place_source_and_destination_after_double_dash() {
+    let ssh_target = SshTarget::new("example.com").with_port(2222);
+
+    let scp_args = ssh_target.scp_args(Path::new("-app.tar.gz"), "/opt/app.tar.gz");
+
+    assert_eq!(
+        os_strings(&[
+            "-P",
+            "2222",
+            "-o",
+            "BatchMode=yes",
+            "--",
+            "-app.tar.gz",
+            "example.com:/opt/app.tar.gz",
+        ]),
+        scp_args
+    );
+}
+
+#[test]
+fn destination_includes_user_when_set() {
+    assert_eq!("example.com", SshTarget::new("example.com").destination());
+    assert_eq!(
+        "deploy@example.com",
+        SshTarget::new("example.com")
+            .with_user("deploy")
+            .destination()
+    );
+}
+
+fn os_strings(args: &[&str]) -> Vec<OsString> {
+    args.iter().map(OsString::from).collect()
+}
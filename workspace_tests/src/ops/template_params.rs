@@ -0,0 +1,32 @@
+use std::path::Path;
+
+use choochoo_ops::{OpsError, TemplateParams};
+
+#[test]
+fn render_substitutes_placeholders_with_param_values() -> Result<(), OpsError> {
+    let template_params = TemplateParams::new()
+        .with_param("host", "localhost")
+        .with_param("port", "8080");
+
+    let rendered = template_params.render(
+        Path::new("app.toml.tmpl"),
+        "url = \"http://{{ host }}:{{port}}\"\n",
+    )?;
+
+    assert_eq!("url = \"http://localhost:8080\"\n", rendered);
+    Ok(())
+}
+
+#[test]
+fn render_returns_error_when_param_not_found() {
+    let template_params = TemplateParams::new().with_param("host", "localhost");
+
+    let error = template_params
+        .render(Path::new("app.toml.tmpl"), "port = {{ port }}")
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        OpsError::TemplateParamNotFound { name, .. } if name == "port"
+    ));
+}
@@ -1 +1,5 @@
+mod files_store;
 mod profile;
+mod source_registry;
+mod span_finder;
+mod workspace_path;
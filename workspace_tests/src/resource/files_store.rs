@@ -0,0 +1,40 @@
+use std::borrow::Cow;
+
+use choochoo_resource::FilesStore;
+
+#[test]
+fn add_returns_existing_file_id_for_identical_source() {
+    let mut files_store = FilesStore::new();
+
+    let file_id_0 = files_store.add("address", Cow::Borrowed("127.0.0.1:8000"));
+    let file_id_1 = files_store.add("address", Cow::Borrowed("127.0.0.1:8000"));
+    let file_id_2 = files_store.add("address", Cow::Borrowed("127.0.0.1:8001"));
+
+    assert_eq!(file_id_0, file_id_1);
+    assert_ne!(file_id_0, file_id_2);
+    assert_eq!(2, files_store.len());
+    assert_eq!(Some(file_id_2), files_store.file_id("address"));
+    assert_eq!(None, files_store.file_id("path"));
+}
+
+#[test]
+fn add_evicts_oldest_source_and_reuses_its_file_id() {
+    let mut files_store = FilesStore::new().with_sources_max(2);
+
+    let file_id_0 = files_store.add("address", Cow::Borrowed("127.0.0.1:8000"));
+    let file_id_1 = files_store.add("path", Cow::Borrowed("/srv/app.zip"));
+    let file_id_2 = files_store.add("address", Cow::Borrowed("127.0.0.1:8001"));
+
+    assert_eq!(2, files_store.len());
+    assert_eq!("", &**files_store.source(file_id_0));
+    assert_eq!(Some(file_id_1), files_store.file_id("path"));
+    assert_eq!(Some(file_id_2), files_store.file_id("address"));
+
+    // `file_id_1` is evicted, and its `FileId` is reused for the next `path`.
+    let file_id_3 = files_store.add("address", Cow::Borrowed("127.0.0.1:8002"));
+    let file_id_4 = files_store.add("path", Cow::Borrowed("/srv/app_2.zip"));
+
+    assert_eq!(file_id_0, file_id_3);
+    assert_eq!(file_id_1, file_id_4);
+    assert_eq!("/srv/app_2.zip", &**files_store.source(file_id_4));
+}
@@ -0,0 +1,56 @@
+use choochoo_cfg_model::srcerr::codespan::Span;
+use choochoo_resource::{FilesRw, SourceProvenance, SourceRegistry};
+use tokio::runtime;
+
+#[test]
+fn register_stores_source_in_files_rw() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let files_rw = FilesRw::new();
+    let source_registry = SourceRegistry::new(&files_rw);
+
+    rt.block_on(async {
+        let source_ref = source_registry
+            .register(
+                "artifact_server_address",
+                "http://127.0.0.1:8000",
+                SourceProvenance::EnvVar {
+                    name: String::from("ARTIFACT_SERVER_ADDRESS"),
+                },
+            )
+            .await;
+
+        let files_store = files_rw.read().await;
+        assert_eq!(
+            "http://127.0.0.1:8000",
+            &**files_store.source(source_ref.file_id())
+        );
+        assert_eq!(
+            "`artifact_server_address` is from the environment variable \
+            `ARTIFACT_SERVER_ADDRESS`.",
+            source_ref.provenance_note()
+        );
+    });
+
+    Ok(())
+}
+
+#[test]
+fn span_of_returns_span_of_part_of_source() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let source_registry = SourceRegistry::new(&FilesRw::new());
+
+    let source_ref = rt.block_on(source_registry.register(
+        "artifact_server_address",
+        "http://127.0.0.1:1",
+        SourceProvenance::Computed,
+    ));
+    let host_span = source_ref.span_of("127.0.0.1");
+    let port_span = host_span.and_then(|host_span| source_ref.span_of_after("1", host_span));
+
+    assert_eq!(Some(Span::new(7, 16)), host_span);
+    assert_eq!(Some(Span::new(17, 18)), port_span);
+    assert_eq!(None, source_ref.span_of("localhost"));
+    assert_eq!(Span::new(0, 18), source_ref.span());
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use choochoo_cfg_model::srcerr::codespan::Span;
+use choochoo_resource::SpanFinder;
+
+#[test]
+fn url_components_return_spans_of_host_port_and_path() {
+    let span_finder = SpanFinder::new("http://user@127.0.0.1:8000/app.zip?q=1");
+
+    assert_eq!(Span::new(12, 26), span_finder.url_authority());
+    assert_eq!(Some(Span::new(12, 21)), span_finder.url_host());
+    assert_eq!(Some(Span::new(22, 26)), span_finder.url_port());
+    assert_eq!(Some(Span::new(26, 34)), span_finder.url_path());
+    assert_eq!(None, SpanFinder::new("http://127.0.0.1").url_port());
+}
+
+#[test]
+fn key_value_and_toml_path_return_span_of_value() {
+    let source = "# comment\n\
+        name = app\n\
+        [server]\n\
+        address = \"127.0.0.1\" # local\n";
+    let span_finder = SpanFinder::new(source);
+
+    assert_eq!(Some(Span::new(17, 20)), span_finder.key_value("name"));
+    assert_eq!(Some(Span::new(17, 20)), span_finder.toml_path("name"));
+    assert_eq!(
+        Some(Span::new(40, 51)),
+        span_finder.toml_path("server.address")
+    );
+    assert_eq!(None, span_finder.toml_path("server.port"));
+}
+
+#[test]
+fn json_pointer_returns_span_of_value() {
+    let source = r#"{ "name": "app", "servers": [{ "port": 1 }, { "port": 8000 }] }"#;
+    let span_finder = SpanFinder::new(source);
+
+    assert_eq!(Some(Span::new(10, 15)), span_finder.json_pointer("/name"));
+    assert_eq!(
+        Some(Span::new(54, 58)),
+        span_finder.json_pointer("/servers/1/port")
+    );
+    assert_eq!(None, span_finder.json_pointer("/servers/2/port"));
+    assert_eq!(Some(span_finder.all()), span_finder.json_pointer(""));
+}
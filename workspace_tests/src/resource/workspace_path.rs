@@ -0,0 +1,41 @@
+use std::path::{Path, PathBuf};
+
+use choochoo_resource::{WorkspaceDir, WorkspacePath, WorkspacePathError};
+
+#[test]
+fn resolve_joins_relative_path_to_workspace_dir() {
+    let workspace_dir = WorkspaceDir::new(PathBuf::from("/workspace"));
+
+    let workspace_path = WorkspacePath::resolve(&workspace_dir, "./app/../config/app.toml");
+
+    assert_eq!(
+        Ok(Path::new("/workspace/config/app.toml")),
+        workspace_path.as_deref()
+    );
+}
+
+#[test]
+fn resolve_accepts_absolute_path_within_workspace_dir() {
+    let workspace_dir = WorkspaceDir::new(PathBuf::from("/workspace"));
+
+    let workspace_path = workspace_dir.path_resolve("/workspace/target/app.zip");
+
+    assert_eq!(
+        Ok(Path::new("/workspace/target/app.zip")),
+        workspace_path.as_deref()
+    );
+}
+
+#[test]
+fn resolve_returns_error_when_path_leaves_workspace_dir() {
+    let workspace_dir = WorkspaceDir::new(PathBuf::from("/workspace"));
+
+    assert_eq!(
+        Err(WorkspacePathError(PathBuf::from("app/../../etc/passwd"))),
+        workspace_dir.path_resolve("app/../../etc/passwd")
+    );
+    assert_eq!(
+        Err(WorkspacePathError(PathBuf::from("/etc/passwd"))),
+        workspace_dir.path_resolve("/etc/passwd")
+    );
+}
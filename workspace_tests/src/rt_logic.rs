@@ -1,4 +1,12 @@
+mod artifact_collector;
+mod audit_log;
+mod fleet;
+mod history_gc;
+mod op_status_propagator;
 mod op_status_updater;
+mod profile_lock;
 mod res_id_persister;
 mod resource_initializer;
+mod resource_snapshot;
 mod train;
+mod train_metrics;
@@ -0,0 +1,60 @@
+use std::fs;
+
+use choochoo_cfg_model::{indexmap::IndexMap, rt::StationArtifact, StationSpec};
+use choochoo_resource::ProfileHistoryDir;
+use choochoo_rt_logic::ArtifactCollector;
+use choochoo_rt_model::Destination;
+
+#[test]
+fn collect_copies_artifacts_and_writes_index() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let artifact_path = tempdir.path().join("app.zip");
+    fs::write(&artifact_path, b"app")?;
+    let profile_history_dir = ProfileHistoryDir::new(tempdir.path().join("history"));
+    let (dest, station_rt_id) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let station_rt_id = dest_builder.add_station(StationSpec::mock("build")?.build());
+        (dest_builder.build()?, station_rt_id)
+    };
+    let mut station_artifacts = IndexMap::new();
+    station_artifacts.insert(
+        station_rt_id,
+        vec![StationArtifact::new(&artifact_path, "App archive")],
+    );
+
+    let artifacts_dir = ArtifactCollector::collect(
+        &profile_history_dir,
+        "1",
+        dest.station_specs(),
+        &station_artifacts,
+    )?;
+
+    let artifacts_dir = artifacts_dir.expect("Expected artifacts to be collected.");
+    assert_eq!(ArtifactCollector::<()>::dir(&profile_history_dir, "1"), artifacts_dir);
+    assert_eq!(b"app".to_vec(), fs::read(artifacts_dir.join("build").join("app.zip"))?);
+    let index =
+        fs::read_to_string(artifacts_dir.join(ArtifactCollector::<()>::INDEX_FILE_NAME))?;
+    assert!(index.contains(r#""station_id":"build""#));
+    assert!(index.contains(r#""label":"App archive""#));
+
+    Ok(())
+}
+
+#[test]
+fn collect_returns_none_when_no_artifacts_registered() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile_history_dir = ProfileHistoryDir::new(tempdir.path().to_path_buf());
+    let dest = Destination::<()>::builder().build()?;
+
+    let artifacts_dir = ArtifactCollector::collect(
+        &profile_history_dir,
+        "1",
+        dest.station_specs(),
+        &IndexMap::new(),
+    )?;
+
+    assert_eq!(None, artifacts_dir);
+    assert!(!profile_history_dir.join("1").exists());
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use std::path::Path;
+
+use choochoo_cfg_model::{
+    rt::{CheckStatus, OpStatus, ResIdLogical, ResIds, VisitOp},
+    StationFn, StationId, StationSpec,
+};
+use choochoo_resource::{Profile, ProfileHistoryDir};
+use choochoo_rt_logic::{AuditLog, Train};
+use choochoo_rt_model::{AuditQuery, Destination, WorkspaceSpec};
+use tokio::runtime;
+
+#[test]
+fn records_work_invocations_and_queries_by_station() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let tempdir = tempfile::tempdir()?;
+    let mut dest = {
+        let mut res_ids = ResIds::new();
+        res_ids.insert(ResIdLogical::new("res_a"), 123u32);
+
+        let mut dest_builder = Destination::<()>::builder()
+            .with_workspace_spec(WorkspaceSpec::Path(Path::new(tempdir.path()).to_path_buf()))
+            .with_profile(Profile::new("profile")?);
+        dest_builder.add_stations([
+            StationSpec::mock("a")?
+                .with_create_work_fn(StationFn::ok(res_ids))
+                .build(),
+            StationSpec::mock("b")?
+                .with_create_check_fn(StationFn::ok(CheckStatus::WorkNotRequired))
+                .build(),
+        ]);
+        dest_builder.build()?
+    };
+    let train_report = rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+    let profile_history_dir = train_report.train_resources().borrow::<ProfileHistoryDir>();
+
+    let audit_entries = AuditLog::<()>::query(&profile_history_dir, &AuditQuery::new())?;
+    let station_ids = audit_entries
+        .iter()
+        .map(|audit_entry| audit_entry.station_id.to_string())
+        .collect::<Vec<String>>();
+    // `b`'s work is not required, so only its check function is invoked.
+    assert_eq!(vec![String::from("a")], station_ids);
+
+    let audit_query = AuditQuery::new().with_station_id(StationId::new("a")?);
+    let audit_entries = AuditLog::<()>::query(&profile_history_dir, &audit_query)?;
+    let audit_entry = audit_entries
+        .first()
+        .ok_or("Expected audit entry for `a`.")?;
+    assert_eq!(1, audit_entries.len());
+    assert_eq!(VisitOp::Create, audit_entry.visit_op);
+    assert_eq!(OpStatus::WorkSuccess, audit_entry.op_status);
+    assert_eq!("profile", audit_entry.profile);
+    assert_eq!(
+        Some(123),
+        audit_entry
+            .res_ids
+            .get(&ResIdLogical::new("res_a"))
+            .and_then(|res_id_physical| res_id_physical.as_u64())
+    );
+    assert!(audit_entry.start <= audit_entry.end);
+
+    Ok(())
+}
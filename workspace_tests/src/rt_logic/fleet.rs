@@ -0,0 +1,45 @@
+use std::path::Path;
+
+use choochoo_cfg_model::{rt::VisitOp, StationSpec};
+use choochoo_resource::Profile;
+use choochoo_rt_logic::{Fleet, NoopProgressBackend, Train};
+use choochoo_rt_model::{TrainOutcome, WorkspaceSpec};
+use tokio::runtime;
+
+#[test]
+fn reach_drives_train_in_each_profile() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let tempdir = tempfile::tempdir()?;
+    let workspace_dir = Path::new(tempdir.path()).to_path_buf();
+    let train = Train::<()>::default().with_progress_backend(NoopProgressBackend);
+    let fleet = Fleet::new(train, move |dest_builder| {
+        let mut dest_builder =
+            dest_builder.with_workspace_spec(WorkspaceSpec::Path(workspace_dir.clone()));
+        let station_spec = StationSpec::mock("a")
+            .expect("Expected station spec to be built.")
+            .build();
+        dest_builder.add_station(station_spec);
+        dest_builder.build()
+    })
+    .with_profiles([Profile::new("dev")?, Profile::new("stage")?]);
+
+    let fleet_report = rt.block_on(fleet.reach(VisitOp::Create));
+
+    assert_eq!(TrainOutcome::Success, rt.block_on(fleet_report.outcome()));
+    assert_eq!(
+        vec!["dev", "stage"],
+        fleet_report
+            .profiles()
+            .map(|profile| &**profile)
+            .collect::<Vec<&str>>()
+    );
+    let profile_dirs = fleet_report
+        .profiles()
+        .filter_map(|profile| fleet_report.dest(profile))
+        .map(|dest| dest.dirs().profile_dir().to_path_buf())
+        .collect::<Vec<_>>();
+    assert_eq!(2, profile_dirs.len());
+    assert_ne!(profile_dirs[0], profile_dirs[1]);
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use std::{fs, time::Duration};
+
+use choochoo_cfg_model::StationSpec;
+use choochoo_resource::ProfileHistoryDir;
+use choochoo_rt_logic::{HistoryGc, HistoryRetention};
+use choochoo_rt_model::Destination;
+
+#[test]
+fn prunable_lists_stale_entries_without_removing_them() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile_history_dir = ProfileHistoryDir::new(tempdir.path().to_path_buf());
+    ["a", "removed_b", "removed_c"]
+        .iter()
+        .try_for_each(|station_id| fs::create_dir(profile_history_dir.join(station_id)))?;
+    let dest = {
+        let mut dest_builder = Destination::<()>::builder();
+        dest_builder.add_station(StationSpec::mock("a")?.build());
+        dest_builder.build()?
+    };
+
+    let mut prunable = HistoryGc::prunable(
+        &profile_history_dir,
+        dest.station_specs(),
+        HistoryRetention::KeepLast(0),
+    )?;
+    prunable.sort();
+
+    assert_eq!(
+        vec![
+            profile_history_dir.join("removed_b"),
+            profile_history_dir.join("removed_c")
+        ],
+        prunable
+    );
+    assert!(profile_history_dir.join("removed_b").exists());
+
+    Ok(())
+}
+
+#[test]
+fn prune_keeps_entries_within_retention() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile_history_dir = ProfileHistoryDir::new(tempdir.path().to_path_buf());
+    ["a", "removed_b"]
+        .iter()
+        .try_for_each(|station_id| fs::create_dir(profile_history_dir.join(station_id)))?;
+    let dest = {
+        let mut dest_builder = Destination::<()>::builder();
+        dest_builder.add_station(StationSpec::mock("a")?.build());
+        dest_builder.build()?
+    };
+
+    let pruned = HistoryGc::prune(
+        &profile_history_dir,
+        dest.station_specs(),
+        HistoryRetention::MaxAge(Duration::from_secs(3600)),
+    )?;
+    assert!(pruned.is_empty());
+
+    let pruned = HistoryGc::prune(
+        &profile_history_dir,
+        dest.station_specs(),
+        HistoryRetention::KeepLast(0),
+    )?;
+    assert_eq!(vec![profile_history_dir.join("removed_b")], pruned);
+    assert!(profile_history_dir.join("a").exists());
+    assert!(!profile_history_dir.join("removed_b").exists());
+
+    Ok(())
+}
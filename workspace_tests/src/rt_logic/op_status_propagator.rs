@@ -0,0 +1,150 @@
+use choochoo_cfg_model::{
+    rt::{OpStatus, VisitOp},
+    StationSpec,
+};
+use choochoo_rt_logic::OpStatusPropagator;
+use choochoo_rt_model::Destination;
+use futures::future;
+use tokio::{runtime, task};
+
+#[test]
+fn station_complete_queues_child_when_all_parents_complete()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    // a -> c
+    //      ^
+    // b --/
+    let mut dest_builder = Destination::<()>::builder();
+    let [station_a, station_b, station_c] = dest_builder.add_stations([
+        StationSpec::mock("a")?.build(),
+        StationSpec::mock("b")?.build(),
+        StationSpec::mock("c")?.build(),
+    ]);
+    dest_builder.add_edges([(station_a, station_c), (station_b, station_c)])?;
+    let mut dest = dest_builder.build()?;
+    {
+        let station_progresses = dest.station_progresses_mut();
+        station_progresses[&station_a].borrow_mut().op_status = OpStatus::WorkSuccess;
+        station_progresses[&station_b].borrow_mut().op_status = OpStatus::WorkInProgress;
+        station_progresses[&station_c].borrow_mut().op_status = OpStatus::ParentPending;
+    }
+    let op_status_propagator = OpStatusPropagator::new(&dest, VisitOp::Create);
+
+    rt.block_on(op_status_propagator.station_complete(&dest, station_a));
+    assert_eq!(
+        OpStatus::ParentPending,
+        dest.station_progresses()[&station_c].borrow().op_status
+    );
+
+    dest.station_progresses()[&station_b].borrow_mut().op_status = OpStatus::WorkUnnecessary;
+    rt.block_on(op_status_propagator.station_complete(&dest, station_b));
+    assert_eq!(
+        OpStatus::OpQueued,
+        dest.station_progresses()[&station_c].borrow().op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn station_complete_marks_child_parent_fail_when_any_parent_fails()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    // a -> c
+    //      ^
+    // b --/
+    let mut dest_builder = Destination::<()>::builder();
+    let [station_a, station_b, station_c] = dest_builder.add_stations([
+        StationSpec::mock("a")?.build(),
+        StationSpec::mock("b")?.build(),
+        StationSpec::mock("c")?.build(),
+    ]);
+    dest_builder.add_edges([(station_a, station_c), (station_b, station_c)])?;
+    let mut dest = dest_builder.build()?;
+    {
+        let station_progresses = dest.station_progresses_mut();
+        station_progresses[&station_a].borrow_mut().op_status = OpStatus::WorkFail;
+        station_progresses[&station_b].borrow_mut().op_status = OpStatus::WorkSuccess;
+        station_progresses[&station_c].borrow_mut().op_status = OpStatus::ParentPending;
+    }
+    let op_status_propagator = OpStatusPropagator::new(&dest, VisitOp::Create);
+
+    rt.block_on(op_status_propagator.station_complete(&dest, station_a));
+    rt.block_on(op_status_propagator.station_complete(&dest, station_b));
+
+    assert_eq!(
+        OpStatus::ParentFail,
+        dest.station_progresses()[&station_c].borrow().op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn station_complete_updates_parents_when_visit_op_is_clean()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    // a -> b
+    let mut dest_builder = Destination::<()>::builder();
+    let [station_a, station_b] = dest_builder.add_stations([
+        StationSpec::mock("a")?.build(),
+        StationSpec::mock("b")?.build(),
+    ]);
+    dest_builder.add_edge(station_a, station_b)?;
+    let mut dest = dest_builder.build()?;
+    {
+        let station_progresses = dest.station_progresses_mut();
+        station_progresses[&station_a].borrow_mut().op_status = OpStatus::ParentPending;
+        station_progresses[&station_b].borrow_mut().op_status = OpStatus::OpSkipped;
+    }
+    let op_status_propagator = OpStatusPropagator::new(&dest, VisitOp::Clean);
+
+    rt.block_on(op_status_propagator.station_complete(&dest, station_b));
+
+    assert_eq!(
+        OpStatus::OpSkipped,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn station_complete_waits_for_child_progress_that_is_already_accessed()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    // a -> b
+    let mut dest_builder = Destination::<()>::builder();
+    let [station_a, station_b] = dest_builder.add_stations([
+        StationSpec::mock("a")?.build(),
+        StationSpec::mock("b")?.build(),
+    ]);
+    dest_builder.add_edge(station_a, station_b)?;
+    let mut dest = dest_builder.build()?;
+    {
+        let station_progresses = dest.station_progresses_mut();
+        station_progresses[&station_a].borrow_mut().op_status = OpStatus::WorkSuccess;
+        station_progresses[&station_b].borrow_mut().op_status = OpStatus::ParentPending;
+    }
+    let op_status_propagator = OpStatusPropagator::new(&dest, VisitOp::Create);
+
+    rt.block_on(async {
+        let station_b_progress = dest.station_progresses()[&station_b].borrow_mut();
+        let station_b_release = async move {
+            task::yield_now().await;
+            drop(station_b_progress);
+        };
+        future::join(
+            op_status_propagator.station_complete(&dest, station_a),
+            station_b_release,
+        )
+        .await
+    });
+
+    assert_eq!(
+        OpStatus::OpQueued,
+        dest.station_progresses()[&station_b].borrow().op_status
+    );
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use std::fs;
+
+use choochoo_resource::ProfileDir;
+use choochoo_rt_logic::ProfileLock;
+use choochoo_rt_model::Error;
+
+#[test]
+fn acquire_writes_lock_file_removed_on_release() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile_dir = ProfileDir::new(tempdir.path().to_path_buf());
+
+    let mut profile_lock = ProfileLock::<()>::acquire(&profile_dir)?;
+    let lock_contents = fs::read_to_string(profile_lock.lock_path())?;
+    assert_eq!(
+        Some(std::process::id().to_string().as_str()),
+        lock_contents.lines().next()
+    );
+
+    profile_lock.release()?;
+    assert!(!profile_dir.join(ProfileLock::<()>::LOCK_FILE_NAME).exists());
+
+    Ok(())
+}
+
+#[test]
+fn acquire_returns_profile_locked_when_lock_held_by_another_host()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile_dir = ProfileDir::new(tempdir.path().to_path_buf());
+    let lock_path = profile_dir.join(ProfileLock::<()>::LOCK_FILE_NAME);
+    fs::write(&lock_path, "1234\nanother_host\n")?;
+
+    let result = ProfileLock::<()>::acquire(&profile_dir);
+
+    match result {
+        Err(Error::ProfileLocked { pid, host, .. }) => {
+            assert_eq!(1234, pid);
+            assert_eq!("another_host", host);
+        }
+        result => panic!("Expected `Error::ProfileLocked`, but was {result:?}"),
+    }
+    assert!(lock_path.exists());
+
+    Ok(())
+}
+
+#[test]
+fn acquire_is_reentrant_within_process_and_removes_lock_file_on_last_release()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile_dir = ProfileDir::new(tempdir.path().to_path_buf());
+    let lock_path = profile_dir.join(ProfileLock::<()>::LOCK_FILE_NAME);
+
+    let mut profile_lock_0 = ProfileLock::<()>::acquire(&profile_dir)?;
+    let mut profile_lock_1 = ProfileLock::<()>::acquire(&profile_dir)?;
+    assert_eq!(profile_lock_0.lock_path(), profile_lock_1.lock_path());
+
+    profile_lock_0.release()?;
+    // Releasing twice does not release the other holder's share of the lock.
+    profile_lock_0.release()?;
+    assert!(lock_path.exists());
+
+    profile_lock_1.release()?;
+    assert!(!lock_path.exists());
+
+    Ok(())
+}
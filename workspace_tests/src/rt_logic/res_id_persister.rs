@@ -1,12 +1,12 @@
 use std::path::Path;
 
 use choochoo_cfg_model::{
-    rt::{ResIdLogical, ResIds, StationRtId, TrainResources},
+    rt::{ResIdLogical, ResIdMetadata, ResIds, StationRtId, TrainResources},
     StationSpec,
 };
 use choochoo_resource::{Profile, ProfileHistoryDir};
 use choochoo_rt_logic::{ResIdPersister, ResourceInitializer};
-use choochoo_rt_model::{Destination, WorkspaceSpec};
+use choochoo_rt_model::{Destination, Error, MemoryFileSystem, WorkspaceSpec};
 use serde::{Deserialize, Serialize};
 use tempfile::TempDir;
 use tokio::runtime;
@@ -27,14 +27,21 @@ fn writes_res_ids_in_profile_history_dir() -> Result<(), Box<dyn std::error::Err
                 value: "a string".to_string(),
             },
         );
-        ResIdPersister::<()>::persist(&profile_history_dir, &station_id, &res_ids).await?;
+        ResIdPersister::<()>::persist(
+            dest.file_system(),
+            &profile_history_dir,
+            &station_id,
+            &res_ids,
+        )
+        .await?;
 
+        let station_history_dir = profile_history_dir.join(station_id.as_ref());
         let res_a_serialized =
-            tokio::fs::read_to_string(profile_history_dir.join("res_a.json")).await?;
+            tokio::fs::read_to_string(station_history_dir.join("res_a.json")).await?;
         assert_eq!("123", res_a_serialized);
 
         let res_b_serialized =
-            tokio::fs::read_to_string(profile_history_dir.join("res_b.json")).await?;
+            tokio::fs::read_to_string(station_history_dir.join("res_b.json")).await?;
         assert_eq!(
             r#"{
   "value": "a string"
@@ -46,6 +53,272 @@ fn writes_res_ids_in_profile_history_dir() -> Result<(), Box<dyn std::error::Err
     })
 }
 
+#[test]
+fn loads_res_ids_persisted_by_each_station() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    rt.block_on(async {
+        let (_tempdir, dest, train_resources, station_rt_id) = setup().await?;
+
+        let profile_history_dir = train_resources.borrow::<ProfileHistoryDir>();
+        let station_id = dest.station_specs()[station_rt_id].id();
+        let mut res_ids = ResIds::new();
+        res_ids.insert(ResIdLogical::new("res_a"), ResA(123));
+        res_ids.insert(
+            ResIdLogical::new("res_b"),
+            ResB {
+                value: "a string".to_string(),
+            },
+        );
+        ResIdPersister::<()>::persist(
+            dest.file_system(),
+            &profile_history_dir,
+            &station_id,
+            &res_ids,
+        )
+        .await?;
+
+        let res_ids_previous = ResIdPersister::<()>::load(
+            dest.file_system(),
+            &profile_history_dir,
+            dest.station_specs(),
+        )
+        .await?;
+
+        assert_eq!(
+            Some(ResA(123)),
+            res_ids_previous.get::<ResA>(station_rt_id, "res_a")?
+        );
+        assert_eq!(
+            Some(ResB {
+                value: "a string".to_string()
+            }),
+            res_ids_previous.get::<ResB>(station_rt_id, "res_b")?
+        );
+        assert_eq!(None, res_ids_previous.get::<ResA>(station_rt_id, "res_c")?);
+
+        Result::<_, Box<dyn std::error::Error>>::Ok(())
+    })
+}
+
+#[test]
+fn clear_removes_res_ids_persisted_by_station() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    rt.block_on(async {
+        let (_tempdir, dest, train_resources, station_rt_id) = setup().await?;
+
+        let profile_history_dir = train_resources.borrow::<ProfileHistoryDir>();
+        let station_id = dest.station_specs()[station_rt_id].id();
+        let mut res_ids = ResIds::new();
+        res_ids.insert(ResIdLogical::new("res_a"), ResA(123));
+        ResIdPersister::<()>::persist(
+            dest.file_system(),
+            &profile_history_dir,
+            &station_id,
+            &res_ids,
+        )
+        .await?;
+
+        ResIdPersister::<()>::clear(dest.file_system(), &profile_history_dir, &station_id).await?;
+        // Clearing again is not an error.
+        ResIdPersister::<()>::clear(dest.file_system(), &profile_history_dir, &station_id).await?;
+
+        let res_ids_previous = ResIdPersister::<()>::load(
+            dest.file_system(),
+            &profile_history_dir,
+            dest.station_specs(),
+        )
+        .await?;
+        assert_eq!(None, res_ids_previous.get::<ResA>(station_rt_id, "res_a")?);
+
+        Result::<_, Box<dyn std::error::Error>>::Ok(())
+    })
+}
+
+#[test]
+fn loads_res_id_costs_from_persisted_metadata() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    rt.block_on(async {
+        let (_tempdir, dest, train_resources, station_rt_id) = setup().await?;
+
+        let profile_history_dir = train_resources.borrow::<ProfileHistoryDir>();
+        let station_id = dest.station_specs()[station_rt_id].id();
+        let mut res_ids = ResIds::new();
+        res_ids.insert(ResIdLogical::new("res_a"), ResA(123));
+        res_ids.metadata_insert(
+            ResIdLogical::new("res_a"),
+            ResIdMetadata::new()
+                .with_cost_monthly(12.5)
+                .with_region("ap-southeast-2"),
+        );
+        res_ids.metadata_insert(
+            ResIdLogical::new("res_b"),
+            ResIdMetadata::new().with_cost_monthly(2.5),
+        );
+        ResIdPersister::<()>::persist(
+            dest.file_system(),
+            &profile_history_dir,
+            &station_id,
+            &res_ids,
+        )
+        .await?;
+
+        let res_id_costs = ResIdPersister::<()>::res_id_costs_load(
+            dest.file_system(),
+            &profile_history_dir,
+            dest.station_specs(),
+        )
+        .await?;
+        let res_ids_previous = ResIdPersister::<()>::load(
+            dest.file_system(),
+            &profile_history_dir,
+            dest.station_specs(),
+        )
+        .await?;
+
+        assert_eq!(Some(&15.0), res_id_costs.get(station_id));
+        assert_eq!(15.0, res_id_costs.total());
+        assert_eq!(
+            Some(ResA(123)),
+            res_ids_previous.get::<ResA>(station_rt_id, "res_a")?
+        );
+        assert_eq!(
+            None,
+            res_ids_previous.get::<ResA>(station_rt_id, "res_a.meta")?
+        );
+
+        Result::<_, Box<dyn std::error::Error>>::Ok(())
+    })
+}
+
+#[test]
+fn loads_empty_res_ids_previous_when_nothing_persisted() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    rt.block_on(async {
+        let (_tempdir, dest, train_resources, _station_rt_id) = setup().await?;
+
+        let profile_history_dir = train_resources.borrow::<ProfileHistoryDir>();
+        let res_ids_previous = ResIdPersister::<()>::load(
+            dest.file_system(),
+            &profile_history_dir,
+            dest.station_specs(),
+        )
+        .await?;
+
+        assert!(res_ids_previous.is_empty());
+
+        Result::<_, Box<dyn std::error::Error>>::Ok(())
+    })
+}
+
+#[test]
+fn load_recovers_completely_written_tmp_files() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    rt.block_on(async {
+        let (_tempdir, dest, train_resources, station_rt_id) = setup().await?;
+
+        let profile_history_dir = train_resources.borrow::<ProfileHistoryDir>();
+        let station_id = dest.station_specs()[station_rt_id].id();
+        let station_history_dir = profile_history_dir.join(station_id.as_ref());
+        tokio::fs::create_dir_all(&station_history_dir).await?;
+        tokio::fs::write(station_history_dir.join("res_a.json.tmp"), "123").await?;
+
+        let res_ids_previous = ResIdPersister::<()>::load(
+            dest.file_system(),
+            &profile_history_dir,
+            dest.station_specs(),
+        )
+        .await?;
+
+        assert_eq!(
+            Some(ResA(123)),
+            res_ids_previous.get::<ResA>(station_rt_id, "res_a")?
+        );
+        assert!(station_history_dir.join("res_a.json").exists());
+        assert!(!station_history_dir.join("res_a.json.tmp").exists());
+
+        Result::<_, Box<dyn std::error::Error>>::Ok(())
+    })
+}
+
+#[test]
+fn load_returns_error_for_partially_written_tmp_files() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    rt.block_on(async {
+        let (_tempdir, dest, train_resources, station_rt_id) = setup().await?;
+
+        let profile_history_dir = train_resources.borrow::<ProfileHistoryDir>();
+        let station_id = dest.station_specs()[station_rt_id].id();
+        let station_history_dir = profile_history_dir.join(station_id.as_ref());
+        tokio::fs::create_dir_all(&station_history_dir).await?;
+        tokio::fs::write(station_history_dir.join("res_b.json.tmp"), "{\n  \"val").await?;
+
+        let result = ResIdPersister::<()>::load(
+            dest.file_system(),
+            &profile_history_dir,
+            dest.station_specs(),
+        )
+        .await;
+
+        assert!(
+            matches!(
+                &result,
+                Err(Error::ResIdPartiallyWritten { res_id_path, .. })
+                    if res_id_path.ends_with("res_b.json.tmp")
+            ),
+            "Expected `Error::ResIdPartiallyWritten`, but was {result:?}"
+        );
+
+        Result::<_, Box<dyn std::error::Error>>::Ok(())
+    })
+}
+
+#[test]
+fn persists_and_loads_res_ids_in_memory_file_system() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    rt.block_on(async {
+        let tempdir = tempfile::tempdir()?;
+        let mut dest_builder = Destination::<()>::builder()
+            .with_workspace_spec(WorkspaceSpec::Path(Path::new(tempdir.path()).to_path_buf()))
+            .with_profile(Profile::new("profile")?)
+            .with_file_system(MemoryFileSystem::new());
+        let station_rt_id = dest_builder.add_station(StationSpec::mock("station_a")?.build());
+        let dest = dest_builder.build()?;
+        let mut train_resources = TrainResources::new();
+        ResourceInitializer::initialize(&dest, &mut train_resources).await?;
+
+        let profile_history_dir = train_resources.borrow::<ProfileHistoryDir>();
+        let station_id = dest.station_specs()[station_rt_id].id();
+        let mut res_ids = ResIds::new();
+        res_ids.insert(ResIdLogical::new("res_a"), ResA(123));
+        ResIdPersister::<()>::persist(
+            dest.file_system(),
+            &profile_history_dir,
+            &station_id,
+            &res_ids,
+        )
+        .await?;
+        let res_ids_previous = ResIdPersister::<()>::load(
+            dest.file_system(),
+            &profile_history_dir,
+            dest.station_specs(),
+        )
+        .await?;
+
+        assert_eq!(
+            Some(ResA(123)),
+            res_ids_previous.get::<ResA>(station_rt_id, "res_a")?
+        );
+        assert!(
+            dest.file_system()
+                .is_dir(&profile_history_dir.join(station_id.as_ref()))
+                .await
+        );
+        assert!(!tempdir.path().join("target").exists());
+
+        Result::<_, Box<dyn std::error::Error>>::Ok(())
+    })
+}
+
 async fn setup()
 -> Result<(TempDir, Destination<()>, TrainResources<()>, StationRtId), Box<dyn std::error::Error>> {
     let tempdir = tempfile::tempdir()?;
@@ -59,10 +332,10 @@ async fn setup()
     Ok((tempdir, dest, train_resources, station_rt_id))
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 struct ResA(u32);
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 struct ResB {
     value: String,
 }
@@ -1,9 +1,22 @@
 use std::path::Path;
 
-use choochoo_cfg_model::{fn_graph::FnId, rt::TrainResources, StationSpec};
-use choochoo_resource::{HistoryDir, Profile, ProfileDir, ProfileHistoryDir, WorkspaceDir};
-use choochoo_rt_logic::ResourceInitializer;
-use choochoo_rt_model::{Destination, StationDirs, WorkspaceSpec};
+use choochoo_cfg_model::{
+    fn_graph::FnId,
+    rt::{StationEnv, TrainResources},
+    ResourceInitFn, StationSpec,
+};
+use choochoo_resource::{
+    CacheDir, HistoryDir, Profile, ProfileDir, ProfileHistoryDir, WorkspaceDir,
+};
+use choochoo_rt_logic::{
+    reqwest::{
+        self,
+        header::{HeaderMap, HeaderValue},
+    },
+    ResourceInitializer,
+};
+use choochoo_rt_model::{Destination, Error, StationDirs, StationEnvs, WorkspaceSpec};
+use futures::FutureExt;
 use tokio::runtime;
 
 #[test]
@@ -63,6 +76,33 @@ fn inserts_history_dir() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn inserts_cache_dir_shared_across_profiles() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let dest = Destination::<()>::builder()
+        .with_workspace_spec(WorkspaceSpec::Path(Path::new(tempdir.path()).to_path_buf()))
+        .with_profile(Profile::new("profile")?)
+        .build()?;
+    let mut train_resources = TrainResources::new();
+
+    let rt = runtime::Builder::new_current_thread().build()?;
+    rt.block_on(ResourceInitializer::initialize(&dest, &mut train_resources))?;
+
+    let cache_dir = train_resources.borrow::<CacheDir>();
+    assert_eq!(tempdir.path().join("target").join(".cache"), &**cache_dir);
+    assert!(cache_dir.exists());
+    assert_eq!(
+        tempdir
+            .path()
+            .join("target")
+            .join(".cache")
+            .join("downloads"),
+        cache_dir.namespace_dir("downloads")
+    );
+
+    Ok(())
+}
+
 #[test]
 fn inserts_profile_history_dir() -> Result<(), Box<dyn std::error::Error>> {
     let tempdir = tempfile::tempdir()?;
@@ -145,3 +185,168 @@ fn inserts_station_dirs() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn inserts_station_envs_with_station_dir_cwd_and_station_overrides(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let dest = {
+        let mut dest_builder = Destination::<()>::builder()
+            .with_workspace_spec(WorkspaceSpec::Path(Path::new(tempdir.path()).to_path_buf()))
+            .with_profile(Profile::new("profile")?);
+        dest_builder.add_stations([
+            StationSpec::mock("station_a")?.build(),
+            StationSpec::mock("station_b")?
+                .with_env(
+                    StationEnv::new()
+                        .with_env_var("LANG", "C")
+                        .with_path_prepend("/opt/b/bin"),
+                )
+                .build(),
+        ]);
+
+        dest_builder.build()?
+    };
+    let mut train_resources = TrainResources::new();
+    train_resources.insert(
+        StationEnv::new()
+            .with_env_var("LANG", "en_US.UTF-8")
+            .with_env_var("TZ", "UTC")
+            .with_path_prepend("/opt/tools/bin"),
+    );
+
+    let rt = runtime::Builder::new_current_thread().build()?;
+    rt.block_on(ResourceInitializer::initialize(&dest, &mut train_resources))?;
+
+    let station_dirs = train_resources.borrow::<StationDirs>();
+    let station_envs = train_resources.borrow::<StationEnvs>();
+    let station_env_a = &station_envs[&FnId::new(0)];
+    let station_env_b = &station_envs[&FnId::new(1)];
+    assert_eq!(
+        Some(&*station_dirs[&FnId::new(0)]),
+        station_env_a.cwd(),
+        "Expected station directory to be the default working directory"
+    );
+    assert_eq!(
+        Some("en_US.UTF-8"),
+        station_env_a.env_vars().get("LANG").map(String::as_str)
+    );
+    assert_eq!(
+        Some("C"),
+        station_env_b.env_vars().get("LANG").map(String::as_str)
+    );
+    assert_eq!(
+        Some("UTC"),
+        station_env_b.env_vars().get("TZ").map(String::as_str)
+    );
+    assert_eq!(
+        [Path::new("/opt/b/bin"), Path::new("/opt/tools/bin")],
+        station_env_b
+            .path_prepend()
+            .iter()
+            .map(|dir| dir.as_path())
+            .collect::<Vec<_>>()
+            .as_slice()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn inserts_http_client() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let dest = Destination::<()>::builder()
+        .with_workspace_spec(WorkspaceSpec::Path(Path::new(tempdir.path()).to_path_buf()))
+        .build()?;
+    let mut train_resources = TrainResources::new();
+
+    let rt = runtime::Builder::new_current_thread().build()?;
+    rt.block_on(ResourceInitializer::initialize(&dest, &mut train_resources))?;
+
+    assert!(train_resources.try_borrow::<reqwest::Client>().is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn does_not_replace_http_client_inserted_before_initialize()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let dest = Destination::<()>::builder()
+        .with_workspace_spec(WorkspaceSpec::Path(Path::new(tempdir.path()).to_path_buf()))
+        .build()?;
+    let mut default_headers = HeaderMap::new();
+    default_headers.insert("x-choochoo-client", HeaderValue::from_static("user"));
+    let http_client = reqwest::Client::builder()
+        .default_headers(default_headers)
+        .build()?;
+    let mut train_resources = TrainResources::new();
+    train_resources.insert(http_client);
+
+    let rt = runtime::Builder::new_current_thread().build()?;
+    rt.block_on(ResourceInitializer::initialize(&dest, &mut train_resources))?;
+
+    // `reqwest::Client` has no identity comparison, but its `Debug` output
+    // includes the default headers.
+    let http_client = train_resources.borrow::<reqwest::Client>();
+    assert!(format!("{:?}", *http_client).contains("x-choochoo-client"));
+
+    Ok(())
+}
+
+#[test]
+fn initialize_user_runs_initializers_in_order() -> Result<(), Box<dyn std::error::Error>> {
+    let resource_init_fns = vec![
+        ResourceInitFn::new("first", |train_resources: &mut TrainResources<()>| {
+            train_resources.insert(vec!["first"]);
+            async { Ok(()) }.boxed_local()
+        }),
+        ResourceInitFn::new("second", |train_resources: &mut TrainResources<()>| {
+            train_resources
+                .borrow_mut::<Vec<&'static str>>()
+                .push("second");
+            async { Ok(()) }.boxed_local()
+        }),
+    ];
+    let mut train_resources = TrainResources::new();
+
+    let rt = runtime::Builder::new_current_thread().build()?;
+    rt.block_on(ResourceInitializer::initialize_user(
+        &resource_init_fns,
+        &mut train_resources,
+    ))?;
+
+    assert_eq!(
+        &vec!["first", "second"],
+        &*train_resources.borrow::<Vec<&'static str>>()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn initialize_user_returns_error_and_skips_remaining_initializers_on_failure()
+-> Result<(), Box<dyn std::error::Error>> {
+    let resource_init_fns = vec![
+        ResourceInitFn::err("secrets", ()),
+        ResourceInitFn::new("client", |train_resources: &mut TrainResources<()>| {
+            train_resources.insert(0u8);
+            async { Ok(()) }.boxed_local()
+        }),
+    ];
+    let mut train_resources = TrainResources::new();
+
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let result = rt.block_on(ResourceInitializer::initialize_user(
+        &resource_init_fns,
+        &mut train_resources,
+    ));
+
+    assert!(matches!(
+        result,
+        Err(Error::ResourceInit { ref name, error: () }) if name == "secrets"
+    ));
+    assert!(train_resources.try_borrow::<u8>().is_err());
+
+    Ok(())
+}
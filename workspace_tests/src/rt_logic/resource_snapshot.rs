@@ -0,0 +1,66 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use choochoo_cfg_model::{
+    rt::{ProgressLimit, ResIds, ResourceSerialize, StationMutRef, VisitOp},
+    SetupFn, StationFn, StationSpec,
+};
+use choochoo_rt_logic::{ResourceSnapshot, Train};
+use choochoo_rt_model::{Destination, WorkspaceSpec};
+use futures::future::{FutureExt, LocalBoxFuture};
+use serde::{Deserialize, Serialize};
+use tokio::runtime;
+
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+struct InstanceIds(Vec<String>);
+
+impl ResourceSerialize for InstanceIds {
+    const SNAPSHOT_KEY: &'static str = "instance_ids";
+}
+
+#[test]
+fn reach_loads_resources_saved_by_previous_drive() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let tempdir = tempfile::tempdir()?;
+    let discovery_count = Arc::new(AtomicUsize::new(0));
+    let mut dest = {
+        let discovery_count = discovery_count.clone();
+        let mut dest_builder = Destination::<()>::builder()
+            .with_workspace_spec(WorkspaceSpec::Path(tempdir.path().to_path_buf()));
+        dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_create_setup_fn(SetupFn::new(move |_, train_resources| {
+                    if train_resources.borrow::<InstanceIds>().is_err() {
+                        discovery_count.fetch_add(1, Ordering::SeqCst);
+                        train_resources.insert(InstanceIds(vec![String::from("i-123")]));
+                    }
+                    async { Ok(ProgressLimit::Steps(1)) }.boxed_local()
+                }))
+                .with_create_work_fn(StationFn::new(work_read_instance_ids))
+                .build(),
+        );
+        dest_builder.build()?
+    };
+    let train = Train::default()
+        .with_resource_snapshot(ResourceSnapshot::new().with_resource::<InstanceIds>());
+
+    rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+    let train_report = rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+
+    assert_eq!(1, discovery_count.load(Ordering::SeqCst));
+    assert_eq!(
+        InstanceIds(vec![String::from("i-123")]),
+        *train_report.train_resources().borrow::<InstanceIds>()
+    );
+
+    Ok(())
+}
+
+fn work_read_instance_ids<'f>(
+    _station: &'f mut StationMutRef<'_, ()>,
+    _instance_ids: &'f InstanceIds,
+) -> LocalBoxFuture<'f, Result<ResIds, (ResIds, ())>> {
+    async move { Ok(ResIds::new()) }.boxed_local()
+}
@@ -1,12 +1,41 @@
+use std::{
+    num::NonZeroUsize,
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    thread::{self, ThreadId},
+    time::Duration,
+};
+
 use choochoo_cfg_model::{
     indexmap::IndexMap,
-    rt::{CheckStatus, OpStatus, ProgressLimit, ResIds, StationMutRef, StationRtId, VisitOp},
-    CleanFns, SetupFn, StationFn, StationSpec,
+    rt::{
+        CheckStatus, Effect, EffectRecord, ExecutionModel, OpStatus, ProgressLimit, ResIdLogical,
+        ResIds, Spawner, StationErrorPhase, StationErrors, StationMutRef, StationOutput,
+        StationRtId, StationVersionRecord, StationWarnings, SubStepStatus, TrainResources,
+        VisitOp,
+    },
+    srcerr::codespan_reporting::diagnostic::Severity,
+    CleanFns, CreateFns, MockInvocations, MockOpFn, ResourceInitFn, SetupFn, StationExistsFn,
+    StationFn, StationHookFn, StationId, StationMigrateFn, StationOp, StationSpec,
+};
+use choochoo_resource::Profile;
+use choochoo_rt_logic::{
+    ChaosAction, ChaosPoint, ChaosPolicy, ExecutionPolicy, FastPathStrat, NoopProgressBackend,
+    ResourceLimits, RollbackPolicy, Train,
+};
+use choochoo_rt_model::{
+    error::{Error, StationSpecError},
+    CleanSelection, Destination, ExitCodePolicy, MemoryFileSystem, RollbackOutcome, TrainOutcome,
+    Verbosity, WorkspaceSpec,
+};
+use futures::future::{BoxFuture, FutureExt, LocalBoxFuture};
+use tokio::{
+    runtime,
+    sync::{Barrier, TryLockError},
 };
-use choochoo_rt_logic::Train;
-use choochoo_rt_model::{error::StationSpecError, Destination};
-use futures::future::{FutureExt, LocalBoxFuture};
-use tokio::runtime;
 
 #[test]
 fn reach_create_reaches_empty_dest() -> Result<(), Box<dyn std::error::Error>> {
@@ -79,7 +108,7 @@ fn reach_create_records_successful_and_failed_ops() -> Result<(), Box<dyn std::e
     };
 
     let station_errors = train_report.train_resources().station_errors();
-    assert_eq!(&errors_expected, &*station_errors.try_read()?);
+    assert_eq!(errors_expected, station_errors_latest(&station_errors)?);
     assert_eq!(
         OpStatus::WorkSuccess,
         dest.station_progresses()[&station_a].borrow().op_status
@@ -93,21 +122,68 @@ fn reach_create_records_successful_and_failed_ops() -> Result<(), Box<dyn std::e
 }
 
 #[test]
-fn reach_create_records_check_fn_failure() -> Result<(), Box<dyn std::error::Error>> {
+fn reach_create_uses_replaced_op_and_keeps_edges() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, db_create, app_deploy) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let [db_create, app_deploy] = dest_builder.add_stations([
+            StationSpec::mock("db/create")?
+                .with_create_work_fn(StationFn::err((ResIds::new(), ())))
+                .build(),
+            StationSpec::mock("app/deploy")?.build(),
+        ]);
+        dest_builder.add_edge(db_create, app_deploy)?;
+        dest_builder.replace_op(
+            "db/create",
+            StationOp::new(
+                CreateFns::new(
+                    SetupFn::ok(ProgressLimit::Steps(1)),
+                    StationFn::ok(ResIds::new()),
+                ),
+                None,
+            ),
+        )?;
+        let dest = dest_builder.build()?;
+
+        (dest, db_create, app_deploy)
+    };
+    let train_report = rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+
+    assert!(
+        train_report
+            .train_resources()
+            .station_errors()
+            .try_read()?
+            .is_empty()
+    );
+    assert_eq!(
+        OpStatus::WorkSuccess,
+        dest.station_progresses()[&db_create].borrow().op_status
+    );
+    assert_eq!(
+        OpStatus::WorkSuccess,
+        dest.station_progresses()[&app_deploy].borrow().op_status
+    );
+    assert_eq!(1, dest.station_specs().edge_count());
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_records_verify_fn_failure() -> Result<(), Box<dyn std::error::Error>> {
     let rt = runtime::Builder::new_current_thread().build()?;
     let (mut dest, station_a, station_b) = {
         let mut dest_builder = Destination::<()>::builder();
         let [station_a, station_b] = dest_builder.add_stations([
             StationSpec::mock("a")?
                 .with_create_work_fn(StationFn::ok(ResIds::new()))
-                .with_create_check_fn(StationFn::err(()))
+                .with_create_verify_fn(StationFn::err(()))
                 .build(),
             StationSpec::mock("b")?
-                .with_create_work_fn(StationFn::err((ResIds::new(), ())))
+                .with_create_work_fn(StationFn::ok(ResIds::new()))
                 .build(),
         ]);
         dest_builder.add_edge(station_a, station_b)?;
-
         let dest = dest_builder.build()?;
 
         (dest, station_a, station_b)
@@ -117,14 +193,13 @@ fn reach_create_records_check_fn_failure() -> Result<(), Box<dyn std::error::Err
     let errors_expected = {
         let mut errors = IndexMap::new();
         errors.insert(station_a, ());
-        // station b's err should not be reached, because station a failed.
         errors
     };
 
     let station_errors = train_report.train_resources().station_errors();
-    assert_eq!(&errors_expected, &*station_errors.try_read()?);
+    assert_eq!(errors_expected, station_errors_latest(&station_errors)?);
     assert_eq!(
-        OpStatus::CheckFail,
+        OpStatus::VerifyFail,
         dest.station_progresses()[&station_a].borrow().op_status
     );
     assert_eq!(
@@ -136,177 +211,246 @@ fn reach_create_records_check_fn_failure() -> Result<(), Box<dyn std::error::Err
 }
 
 #[test]
-fn reach_create_records_check_fn_failure_after_op_success() -> Result<(), Box<dyn std::error::Error>>
-{
+fn reach_create_records_error_phase_and_attempt() -> Result<(), Box<dyn std::error::Error>> {
     let rt = runtime::Builder::new_current_thread().build()?;
     let (mut dest, station_a, station_b) = {
         let mut dest_builder = Destination::<()>::builder();
         let [station_a, station_b] = dest_builder.add_stations([
             StationSpec::mock("a")?
-                .with_create_setup_fn(SetupFn::new(|_, train_resources| {
-                    train_resources.insert(0u32);
-                    async { Ok(ProgressLimit::Steps(1)) }.boxed_local()
-                }))
-                .with_create_check_fn(StationFn::ok(CheckStatus::WorkRequired))
                 .with_create_work_fn(StationFn::ok(ResIds::new()))
+                .with_create_verify_fn(StationFn::err(()))
                 .build(),
             StationSpec::mock("b")?
-                .with_create_work_fn(StationFn::err((ResIds::new(), ())))
+                .with_create_check_fn(StationFn::err(()))
                 .build(),
         ]);
-        dest_builder.add_edge(station_a, station_b)?;
-
         let dest = dest_builder.build()?;
 
         (dest, station_a, station_b)
     };
-    let train_resources = rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+    let train_report = rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
 
-    let errors_expected = {
-        let mut errors = IndexMap::new();
-        errors.insert(station_a, ());
-        errors.insert(station_b, ());
-        errors
+    let station_a_errors = rt.block_on(train_report.station_errors_for(station_a));
+    assert_eq!(1, station_a_errors.len());
+    assert_eq!(StationErrorPhase::Verify, station_a_errors[0].phase());
+    assert_eq!(1, station_a_errors[0].attempt());
+    assert_eq!(
+        1,
+        rt.block_on(train_report.station_error_count(station_b, StationErrorPhase::Check))
+    );
+    assert_eq!(
+        0,
+        rt.block_on(train_report.station_error_count(station_b, StationErrorPhase::Work))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_isolates_panicking_station() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, station_a, station_b, station_c) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let [station_a, station_b, station_c] = dest_builder.add_stations([
+            StationSpec::mock("a")?
+                .with_create_work_fn(StationFn::new0(work_panic))
+                .build(),
+            StationSpec::mock("b")?
+                .with_create_work_fn(StationFn::ok(ResIds::new()))
+                .build(),
+            StationSpec::mock("c")?
+                .with_create_work_fn(StationFn::ok(ResIds::new()))
+                .build(),
+        ]);
+        dest_builder.add_edge(station_a, station_b)?;
+        let dest = dest_builder.build()?;
+
+        (dest, station_a, station_b, station_c)
     };
+    let train_report = rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
 
-    let station_errors = train_resources.train_resources().station_errors();
     assert_eq!(
-        OpStatus::WorkSuccess,
+        OpStatus::Panicked,
         dest.station_progresses()[&station_a].borrow().op_status
     );
     assert_eq!(
-        OpStatus::WorkFail,
+        OpStatus::ParentFail,
         dest.station_progresses()[&station_b].borrow().op_status
     );
-    assert_eq!(&errors_expected, &*station_errors.try_read()?);
+    assert_eq!(
+        OpStatus::WorkSuccess,
+        dest.station_progresses()[&station_c].borrow().op_status
+    );
+    assert_eq!(
+        1,
+        rt.block_on(train_report.station_error_count(station_a, StationErrorPhase::Work))
+    );
 
     Ok(())
 }
 
 #[test]
-fn reach_create_sets_work_unnecessary_if_nothing_changed() -> Result<(), Box<dyn std::error::Error>>
-{
+fn reach_create_records_panic_in_phase_of_panicking_function()
+-> Result<(), Box<dyn std::error::Error>> {
     let rt = runtime::Builder::new_current_thread().build()?;
     let (mut dest, station_a, station_b) = {
         let mut dest_builder = Destination::<()>::builder();
         let [station_a, station_b] = dest_builder.add_stations([
             StationSpec::mock("a")?
-                .with_create_check_fn(StationFn::ok(CheckStatus::WorkNotRequired))
-                .with_create_work_fn(StationFn::ok(ResIds::new()))
+                .with_create_check_fn(StationFn::new0(check_panic))
                 .build(),
             StationSpec::mock("b")?
-                .with_create_check_fn(StationFn::ok(CheckStatus::WorkNotRequired))
-                .with_create_work_fn(StationFn::err((ResIds::new(), ()))) // proving this is never used
+                .with_create_work_fn(StationFn::ok(ResIds::new()))
+                .with_create_verify_fn(StationFn::new0(verify_panic))
                 .build(),
         ]);
-        dest_builder.add_edge(station_a, station_b)?;
-
         let dest = dest_builder.build()?;
 
         (dest, station_a, station_b)
     };
-    let train_resources = rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
-
-    let errors_expected = IndexMap::<StationRtId, ()>::new();
+    let train_report = rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
 
-    let station_errors = train_resources.train_resources().station_errors();
-    assert_eq!(&errors_expected, &*station_errors.try_read()?);
     assert_eq!(
-        OpStatus::WorkUnnecessary,
+        OpStatus::Panicked,
         dest.station_progresses()[&station_a].borrow().op_status
     );
     assert_eq!(
-        OpStatus::WorkUnnecessary,
+        OpStatus::Panicked,
         dest.station_progresses()[&station_b].borrow().op_status
     );
+    assert_eq!(
+        1,
+        rt.block_on(train_report.station_error_count(station_a, StationErrorPhase::Check))
+    );
+    assert_eq!(
+        0,
+        rt.block_on(train_report.station_error_count(station_a, StationErrorPhase::Work))
+    );
+    assert_eq!(
+        1,
+        rt.block_on(train_report.station_error_count(station_b, StationErrorPhase::Verify))
+    );
+    assert_eq!(
+        0,
+        rt.block_on(train_report.station_error_count(station_b, StationErrorPhase::Work))
+    );
 
     Ok(())
 }
 
 #[test]
-fn reach_clean_reaches_empty_dest() -> Result<(), Box<dyn std::error::Error>> {
+fn reach_create_rolls_back_failed_station_when_rollback_policy_is_on_failure()
+-> Result<(), Box<dyn std::error::Error>> {
     let rt = runtime::Builder::new_current_thread().build()?;
-    let mut dest = Destination::<()>::builder().build()?;
+    let (mut dest, station_a) = {
+        let mut res_ids = ResIds::new();
+        res_ids.insert(ResIdLogical::new("res_a"), 123u32);
 
-    let train_report = rt.block_on(Train::default().reach(&mut dest, VisitOp::Clean))?;
+        let mut dest_builder = Destination::<()>::builder();
+        let station_a = dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_create_work_fn(StationFn::err((res_ids, ())))
+                .with_clean_fns(CleanFns::ok())
+                .build(),
+        );
+        let dest = dest_builder.build()?;
 
-    let station_errors = train_report.train_resources().station_errors();
-    assert!(station_errors.try_read()?.is_empty());
+        (dest, station_a)
+    };
+    let train = Train::default().with_rollback_policy(RollbackPolicy::OnFailure);
+    let train_report = rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+
+    assert_eq!(
+        OpStatus::WorkFail,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+    assert!(matches!(
+        train_report.station_rollbacks().get(&station_a),
+        Some(RollbackOutcome::Success)
+    ));
 
     Ok(())
 }
 
 #[test]
-fn reach_clean_visits_all_stations_to_destination() -> Result<(), Box<dyn std::error::Error>> {
+fn reach_create_rolls_back_checkpoint_group_when_member_fails()
+-> Result<(), Box<dyn std::error::Error>> {
     let rt = runtime::Builder::new_current_thread().build()?;
-    let mut dest = {
+    let (mut dest, station_a, station_b) = {
         let mut dest_builder = Destination::<()>::builder();
-        dest_builder.add_station(
+        let [station_a, station_b] = dest_builder.add_stations([
             StationSpec::mock("a")?
+                .with_create_work_fn(StationFn::ok(ResIds::new()))
                 .with_clean_fns(CleanFns::ok())
                 .build(),
-        );
-        dest_builder.add_station(
             StationSpec::mock("b")?
+                .with_create_work_fn(StationFn::err((ResIds::new(), ())))
                 .with_clean_fns(CleanFns::ok())
                 .build(),
-        );
-        dest_builder.build()?
+        ]);
+        dest_builder.add_edge(station_a, station_b)?;
+        dest_builder.add_checkpoint_group("ab", [station_a, station_b]);
+        let dest = dest_builder.build()?;
+
+        (dest, station_a, station_b)
     };
-    let train_report = rt.block_on(Train::default().reach(&mut dest, VisitOp::Clean))?;
+    let train_report = rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
 
-    let station_errors = train_report.train_resources().station_errors();
-    assert!(station_errors.try_read()?.is_empty());
+    assert_eq!(
+        OpStatus::WorkFail,
+        dest.station_progresses()[&station_b].borrow().op_status
+    );
+    assert_eq!(
+        vec![&station_b, &station_a],
+        train_report.station_rollbacks().keys().collect::<Vec<_>>()
+    );
     assert!(
-        dest.station_progresses().values().all(|station_progress| {
-            station_progress.borrow().op_status == OpStatus::WorkSuccess
-        }),
-        "Expected all station progresses to be `OpStatus::WorkSuccess`, but they were: {station_progresses:?}",
-        station_progresses = dest
-            .station_progresses()
+        train_report
+            .station_rollbacks()
             .values()
-            .map(|station_progress| { station_progress.borrow().op_status })
-            .collect::<Vec<_>>()
+            .all(|rollback_outcome| matches!(rollback_outcome, RollbackOutcome::Success))
     );
 
     Ok(())
 }
 
 #[test]
-fn reach_clean_records_successful_and_failed_ops() -> Result<(), Box<dyn std::error::Error>> {
+fn reach_create_records_check_fn_failure() -> Result<(), Box<dyn std::error::Error>> {
     let rt = runtime::Builder::new_current_thread().build()?;
     let (mut dest, station_a, station_b) = {
         let mut dest_builder = Destination::<()>::builder();
-        let station_a = dest_builder.add_station(
+        let [station_a, station_b] = dest_builder.add_stations([
             StationSpec::mock("a")?
-                .with_clean_fns(CleanFns::ok())
+                .with_create_work_fn(StationFn::ok(ResIds::new()))
+                .with_create_check_fn(StationFn::err(()))
                 .build(),
-        );
-        let station_b = dest_builder.add_station(
             StationSpec::mock("b")?
-                .with_clean_fns(CleanFns::err(()))
+                .with_create_work_fn(StationFn::err((ResIds::new(), ())))
                 .build(),
-        );
+        ]);
+        dest_builder.add_edge(station_a, station_b)?;
+
         let dest = dest_builder.build()?;
 
         (dest, station_a, station_b)
     };
-    let train_report = rt.block_on(Train::default().reach(&mut dest, VisitOp::Clean))?;
+    let train_report = rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
 
     let errors_expected = {
         let mut errors = IndexMap::new();
-        errors.insert(station_b, ());
+        errors.insert(station_a, ());
+        // station b's err should not be reached, because station a failed.
         errors
     };
 
     let station_errors = train_report.train_resources().station_errors();
-    assert_eq!(&errors_expected, &*station_errors.try_read()?);
+    assert_eq!(errors_expected, station_errors_latest(&station_errors)?);
     assert_eq!(
-        OpStatus::WorkSuccess,
+        OpStatus::CheckFail,
         dest.station_progresses()[&station_a].borrow().op_status
     );
     assert_eq!(
-        OpStatus::WorkFail,
+        OpStatus::ParentFail,
         dest.station_progresses()[&station_b].borrow().op_status
     );
 
@@ -314,25 +458,22 @@ fn reach_clean_records_successful_and_failed_ops() -> Result<(), Box<dyn std::er
 }
 
 #[test]
-fn reach_clean_records_check_fn_failure() -> Result<(), Box<dyn std::error::Error>> {
-    // Note:
-    //
-    // For this test, the create order is `a -> b`, so the clean order is `b -> a`.
-    //
-    // `b` is set up to err on the clean `check_fn`, so `a` should not be cleaned
-    // due to `b`'s error.
-    //
-    // The `errors_expected` should then be a predecessor failure for `a`.
-
+fn reach_create_records_work_not_idempotent_warning_after_op_success()
+-> Result<(), Box<dyn std::error::Error>> {
     let rt = runtime::Builder::new_current_thread().build()?;
     let (mut dest, station_a, station_b) = {
         let mut dest_builder = Destination::<()>::builder();
         let [station_a, station_b] = dest_builder.add_stations([
             StationSpec::mock("a")?
-                .with_clean_fns(CleanFns::err(()))
+                .with_create_setup_fn(SetupFn::new(|_, train_resources| {
+                    train_resources.insert(0u32);
+                    async { Ok(ProgressLimit::Steps(1)) }.boxed_local()
+                }))
+                .with_create_check_fn(StationFn::ok(CheckStatus::WorkRequired))
+                .with_create_work_fn(StationFn::ok(ResIds::new()))
                 .build(),
             StationSpec::mock("b")?
-                .with_clean_fns(CleanFns::ok().with_check_fn(StationFn::err(())))
+                .with_create_work_fn(StationFn::err((ResIds::new(), ())))
                 .build(),
         ]);
         dest_builder.add_edge(station_a, station_b)?;
@@ -341,83 +482,90 @@ fn reach_clean_records_check_fn_failure() -> Result<(), Box<dyn std::error::Erro
 
         (dest, station_a, station_b)
     };
-    let train_report = rt.block_on(Train::default().reach(&mut dest, VisitOp::Clean))?;
+    let train_resources = rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
 
     let errors_expected = {
         let mut errors = IndexMap::new();
         errors.insert(station_b, ());
-        // station b's err should not be reached, because station a failed.
         errors
     };
+    let warnings_expected = {
+        let mut warnings = IndexMap::new();
+        warnings.insert(station_a, vec![()]);
+        warnings
+    };
 
-    let station_errors = train_report.train_resources().station_errors();
-    assert_eq!(&errors_expected, &*station_errors.try_read()?);
+    let station_errors = train_resources.train_resources().station_errors();
+    let station_warnings = train_resources.train_resources().station_warnings();
     assert_eq!(
-        OpStatus::ParentFail,
+        OpStatus::WorkSuccess,
         dest.station_progresses()[&station_a].borrow().op_status
     );
     assert_eq!(
-        OpStatus::CheckFail,
+        OpStatus::WorkFail,
         dest.station_progresses()[&station_b].borrow().op_status
     );
+    assert_eq!(errors_expected, station_errors_latest(&station_errors)?);
+    assert_eq!(&warnings_expected, &*station_warnings.try_read()?);
 
     Ok(())
 }
 
 #[test]
-fn reach_clean_records_check_fn_failure_after_op_success() -> Result<(), Box<dyn std::error::Error>>
-{
-    #[derive(Clone, Debug, PartialEq)]
-    struct Error(u8);
-    impl From<StationSpecError> for Error {
-        fn from(_error: StationSpecError) -> Self {
-            Error(1)
-        }
-    }
+fn reach_create_records_work_not_idempotent_error_when_severity_is_error()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, station_a) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let station_a = dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_create_check_fn(StationFn::ok(CheckStatus::WorkRequired))
+                .with_create_work_fn(StationFn::ok(ResIds::new()))
+                .build(),
+        );
+        let dest = dest_builder.build()?;
 
-    fn b_clean_check<'f>(
-        _: &'f mut StationMutRef<'_, Error>,
-        n: &'f u32,
-    ) -> LocalBoxFuture<'f, Result<CheckStatus, Error>> {
-        async move {
-            if *n == 0 {
-                Ok(CheckStatus::WorkRequired)
-            } else {
-                Err(Error(2))
-            }
-        }
-        .boxed_local()
-    }
+        (dest, station_a)
+    };
+    let train = Train::default().with_work_not_idempotent_severity(Severity::Error);
+    let train_report = rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
 
-    fn b_clean_work<'f>(
-        _: &'f mut StationMutRef<'_, Error>,
-        n: &'f mut u32,
-    ) -> LocalBoxFuture<'f, Result<(), Error>> {
-        async move {
-            *n += 1;
-            Ok(())
-        }
-        .boxed_local()
-    }
+    let errors_expected = {
+        let mut errors = IndexMap::new();
+        errors.insert(station_a, ());
+        errors
+    };
+
+    let station_errors = train_report.train_resources().station_errors();
+    let station_warnings = train_report.train_resources().station_warnings();
+    assert_eq!(errors_expected, station_errors_latest(&station_errors)?);
+    assert!(station_warnings.try_read()?.is_empty());
+    assert_eq!(
+        1,
+        rt.block_on(train_report.station_error_count(station_a, StationErrorPhase::Check))
+    );
+    assert_eq!(
+        0,
+        rt.block_on(train_report.station_error_count(station_a, StationErrorPhase::Verify))
+    );
+
+    Ok(())
+}
 
+#[test]
+fn reach_create_sets_work_unnecessary_if_nothing_changed() -> Result<(), Box<dyn std::error::Error>>
+{
     let rt = runtime::Builder::new_current_thread().build()?;
     let (mut dest, station_a, station_b) = {
-        let mut dest_builder = Destination::<Error>::builder();
+        let mut dest_builder = Destination::<()>::builder();
         let [station_a, station_b] = dest_builder.add_stations([
             StationSpec::mock("a")?
-                .with_clean_fns(CleanFns::ok().with_check_fn(StationFn::err(Error(3))))
+                .with_create_check_fn(StationFn::ok(CheckStatus::WorkNotRequired))
+                .with_create_work_fn(StationFn::ok(ResIds::new()))
                 .build(),
             StationSpec::mock("b")?
-                .with_clean_fns(
-                    CleanFns::new(
-                        SetupFn::new(|_, train_resources| {
-                            train_resources.insert(0u32);
-                            async { Ok(ProgressLimit::Steps(1)) }.boxed_local()
-                        }),
-                        StationFn::new(b_clean_work),
-                    )
-                    .with_check_fn(StationFn::new(b_clean_check)),
-                )
+                .with_create_check_fn(StationFn::ok(CheckStatus::WorkNotRequired))
+                .with_create_work_fn(StationFn::err((ResIds::new(), ()))) // proving this is never used
                 .build(),
         ]);
         dest_builder.add_edge(station_a, station_b)?;
@@ -426,101 +574,2625 @@ fn reach_clean_records_check_fn_failure_after_op_success() -> Result<(), Box<dyn
 
         (dest, station_a, station_b)
     };
-    let train_resources =
-        rt.block_on(Train::<Error>::default().reach(&mut dest, VisitOp::Clean))?;
+    let train_resources = rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
 
-    let errors_expected = {
-        let mut errors = IndexMap::new();
-        errors.insert(station_b, Error(2));
-        errors
-    };
+    let errors_expected = IndexMap::<StationRtId, ()>::new();
 
     let station_errors = train_resources.train_resources().station_errors();
+    assert_eq!(errors_expected, station_errors_latest(&station_errors)?);
     assert_eq!(
-        OpStatus::ParentFail,
+        OpStatus::WorkUnnecessary,
         dest.station_progresses()[&station_a].borrow().op_status
     );
     assert_eq!(
-        OpStatus::CheckFail,
+        OpStatus::WorkUnnecessary,
         dest.station_progresses()[&station_b].borrow().op_status
     );
-    assert_eq!(&errors_expected, &*station_errors.try_read()?);
 
     Ok(())
 }
 
 #[test]
-fn reach_clean_sets_work_unnecessary_if_nothing_changed() -> Result<(), Box<dyn std::error::Error>>
+fn reach_create_records_warnings_without_failing_train() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, station_a) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let station_a = dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_create_work_fn(StationFn::new(work_with_warning))
+                .build(),
+        );
+        let dest = dest_builder.build()?;
+
+        (dest, station_a)
+    };
+    let train_report = rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+
+    let warnings_expected = {
+        let mut warnings = IndexMap::new();
+        warnings.insert(station_a, vec![()]);
+        warnings
+    };
+
+    let station_warnings = train_report.train_resources().station_warnings();
+    assert_eq!(&warnings_expected, &*station_warnings.try_read()?);
+    assert_eq!(
+        OpStatus::WorkSuccess,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+    assert!(!rt.block_on(train_report.is_failed()));
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_records_on_failure_hook_error_as_warning() -> Result<(), Box<dyn std::error::Error>>
+{
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, station_a) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let station_a = dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_create_work_fn(StationFn::err((ResIds::new(), ())))
+                .with_on_success_fn(StationHookFn::ok())
+                .with_on_failure_fn(StationHookFn::err(()))
+                .build(),
+        );
+        let dest = dest_builder.build()?;
+
+        (dest, station_a)
+    };
+    let train_report = rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+
+    let warnings_expected = {
+        let mut warnings = IndexMap::new();
+        warnings.insert(station_a, vec![()]);
+        warnings
+    };
+
+    let station_warnings = train_report.train_resources().station_warnings();
+    assert_eq!(&warnings_expected, &*station_warnings.try_read()?);
+    assert_eq!(
+        OpStatus::WorkFail,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_fails_train_on_warnings_when_severity_threshold_is_warning()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let mut dest = {
+        let mut dest_builder = Destination::<()>::builder();
+        dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_create_work_fn(StationFn::new(work_with_warning))
+                .build(),
+        );
+        dest_builder.build()?
+    };
+    let train = Train::default().with_severity_threshold(Severity::Warning);
+    let train_report = rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+
+    assert_eq!(Severity::Warning, train_report.severity_threshold());
+    assert!(rt.block_on(train_report.is_failed()));
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_continues_independent_stations_by_default() -> Result<(), Box<dyn std::error::Error>>
 {
     let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, [station_a, _station_x, station_b]) =
+        dest_with_failing_and_independent_stations()?;
+    let train_report = rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+
+    assert!(rt.block_on(train_report.is_failed()));
+    assert_eq!(
+        OpStatus::WorkFail,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+    assert_eq!(
+        OpStatus::WorkSuccess,
+        dest.station_progresses()[&station_b].borrow().op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_exit_code_uses_exit_code_policy() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, _stations) = dest_with_failing_and_independent_stations()?;
+    let train_report = rt
+        .block_on(Train::default().reach(&mut dest, VisitOp::Create))?
+        .with_exit_code_policy(ExitCodePolicy::new().with_failed(3));
+
+    assert_eq!(TrainOutcome::Failed, rt.block_on(train_report.outcome()));
+    assert_eq!(3, rt.block_on(train_report.exit_code()));
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_skips_unstarted_stations_when_execution_policy_is_fail_fast()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, [station_a, _station_x, station_b]) =
+        dest_with_failing_and_independent_stations()?;
+    let train = Train::default().with_execution_policy(ExecutionPolicy::FailFast);
+    let _train_report = rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+
+    assert_eq!(
+        OpStatus::WorkFail,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+    assert_eq!(
+        OpStatus::OpSkipped,
+        dest.station_progresses()[&station_b].borrow().op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_marks_stations_deadline_exceeded_when_timeout_reached()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()?;
     let (mut dest, station_a, station_b) = {
         let mut dest_builder = Destination::<()>::builder();
         let [station_a, station_b] = dest_builder.add_stations([
             StationSpec::mock("a")?
-                .with_clean_fns(
-                    CleanFns::ok().with_check_fn(StationFn::ok(CheckStatus::WorkNotRequired)),
-                )
+                .with_create_work_fn(StationFn::new(work_sleep))
                 .build(),
             StationSpec::mock("b")?
-                .with_clean_fns(
-                    CleanFns::ok().with_check_fn(StationFn::ok(CheckStatus::WorkNotRequired)),
-                )
+                .with_create_work_fn(StationFn::ok(ResIds::new()))
                 .build(),
         ]);
         dest_builder.add_edge(station_a, station_b)?;
-
         let dest = dest_builder.build()?;
 
         (dest, station_a, station_b)
     };
-    let train_resources = rt.block_on(Train::default().reach(&mut dest, VisitOp::Clean))?;
-
-    let errors_expected = IndexMap::<StationRtId, ()>::new();
+    let train = Train::default().with_timeout(Duration::from_millis(10));
+    let train_report = rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
 
-    let station_errors = train_resources.train_resources().station_errors();
-    assert_eq!(&errors_expected, &*station_errors.try_read()?);
     assert_eq!(
-        OpStatus::WorkUnnecessary,
+        OpStatus::DeadlineExceeded,
         dest.station_progresses()[&station_a].borrow().op_status
     );
     assert_eq!(
-        OpStatus::WorkUnnecessary,
+        OpStatus::DeadlineExceeded,
         dest.station_progresses()[&station_b].borrow().op_status
     );
+    assert_eq!(
+        &[station_a, station_b],
+        train_report.stations_deadline_exceeded()
+    );
+    assert!(rt.block_on(train_report.is_failed()));
 
     Ok(())
 }
 
 #[test]
-fn reach_clean_sets_work_unnecessary_if_clean_not_supported()
+fn reach_create_records_duration_of_visited_stations() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, [station_a, _station_x, station_b]) =
+        dest_with_failing_and_independent_stations()?;
+    let train = Train::default().with_verbosity(Verbosity::Quiet);
+    let train_report = rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+
+    let station_durations = train_report.station_durations();
+    assert!(station_durations.contains_key(&station_a));
+    assert!(station_durations.contains_key(&station_b));
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_reports_station_sub_steps() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, station_a) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let station_a = dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_sub_step("download")
+                .with_sub_step("extract")
+                .with_create_work_fn(StationFn::new(work_with_sub_steps))
+                .build(),
+        );
+        let dest = dest_builder.build()?;
+
+        (dest, station_a)
+    };
+    let train_report = rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+
+    let sub_steps = &train_report.station_sub_steps()[&station_a];
+    assert_eq!(
+        vec![
+            ("download", SubStepStatus::Success, 100),
+            ("extract", SubStepStatus::InProgress, 1)
+        ],
+        sub_steps
+            .iter()
+            .map(|sub_step| (sub_step.name(), sub_step.status(), sub_step.position()))
+            .collect::<Vec<_>>()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_passes_station_state_from_setup_fn_to_work_fn()
 -> Result<(), Box<dyn std::error::Error>> {
     let rt = runtime::Builder::new_current_thread().build()?;
-    let (mut dest, station_a, station_b) = {
+    let (mut dest, station_a) = {
         let mut dest_builder = Destination::<()>::builder();
-        let [station_a, station_b] = dest_builder.add_stations([
-            StationSpec::mock("a")?.build(),
-            StationSpec::mock("b")?.build(),
-        ]);
-        dest_builder.add_edge(station_a, station_b)?;
+        let station_a = dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_create_setup_fn(SetupFn::new(|station, _| {
+                    Box::pin(async move {
+                        station.state.insert(0u32);
+                        Ok(ProgressLimit::Steps(1))
+                    })
+                }))
+                .with_create_work_fn(StationFn::new(work_with_state))
+                .build(),
+        );
+        let dest = dest_builder.build()?;
+
+        (dest, station_a)
+    };
+    rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+
+    assert_eq!(
+        OpStatus::WorkSuccess,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+    assert_eq!(
+        Some(&1u32),
+        dest.station_states()[&station_a].borrow().get::<u32>()
+    );
 
+    Ok(())
+}
+
+#[test]
+fn reach_create_records_effects_instead_of_running_them_when_dry_run()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, station_a) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let station_a = dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_create_work_fn(StationFn::new(work_with_effect))
+                .build(),
+        );
         let dest = dest_builder.build()?;
 
-        (dest, station_a, station_b)
+        (dest, station_a)
     };
-    let train_resources = rt.block_on(Train::default().reach(&mut dest, VisitOp::Clean))?;
 
-    let errors_expected = IndexMap::<StationRtId, ()>::new();
+    let train_report =
+        rt.block_on(Train::default().with_dry_run(true).reach(&mut dest, VisitOp::Create))?;
 
-    let station_errors = train_resources.train_resources().station_errors();
-    assert_eq!(&errors_expected, &*station_errors.try_read()?);
+    let effects_skipped = rt.block_on(train_report.effects_skipped());
     assert_eq!(
-        OpStatus::WorkUnnecessary,
-        dest.station_progresses()[&station_a].borrow().op_status
+        vec![EffectRecord::new(station_a, String::from("upload app.zip"))],
+        effects_skipped
     );
+    assert_eq!(None, dest.station_states()[&station_a].borrow().get::<u32>());
+
+    let train_report = rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+
+    assert!(rt.block_on(train_report.effects_skipped()).is_empty());
     assert_eq!(
-        OpStatus::WorkUnnecessary,
-        dest.station_progresses()[&station_b].borrow().op_status
+        Some(&1u32),
+        dest.station_states()[&station_a].borrow().get::<u32>()
     );
 
     Ok(())
 }
+
+#[test]
+fn reach_create_passes_station_output_to_downstream_stations()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, [station_a, station_b]) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let [station_a, station_b] = dest_builder.add_stations([
+            StationSpec::mock("a")?
+                .with_create_fns(CreateFns::new_with_output(
+                    SetupFn::ok(ProgressLimit::Steps(1)),
+                    StationFn::new(work_with_output),
+                    StationFn::ok(5u32),
+                ))
+                .build(),
+            StationSpec::mock("b")?
+                .with_create_work_fn(StationFn::new(work_reading_output))
+                .build(),
+        ]);
+        dest_builder.add_edge(station_a, station_b)?;
+        let dest = dest_builder.build()?;
+
+        (dest, [station_a, station_b])
+    };
+    let train_report = rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+
+    assert_eq!(
+        OpStatus::WorkSuccess,
+        dest.station_progresses()[&station_b].borrow().op_status
+    );
+    assert_eq!(
+        Some(&7u32),
+        dest.station_states()[&station_b].borrow().get::<u32>()
+    );
+    assert_eq!(
+        Some(7u32),
+        *train_report
+            .train_resources()
+            .borrow::<StationOutput<u32>>()
+            .try_read()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_provides_station_output_from_output_fn_when_work_is_unnecessary()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, [station_a, station_b]) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let [station_a, station_b] = dest_builder.add_stations([
+            StationSpec::mock("a")?
+                .with_create_fns(
+                    CreateFns::new_with_output(
+                        SetupFn::ok(ProgressLimit::Steps(1)),
+                        StationFn::new(work_with_output),
+                        StationFn::ok(5u32),
+                    )
+                    .with_check_fn(StationFn::ok(CheckStatus::WorkNotRequired)),
+                )
+                .build(),
+            StationSpec::mock("b")?
+                .with_create_work_fn(StationFn::new(work_reading_output))
+                .build(),
+        ]);
+        dest_builder.add_edge(station_a, station_b)?;
+        let dest = dest_builder.build()?;
+
+        (dest, [station_a, station_b])
+    };
+    let train_report = rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+
+    assert_eq!(
+        OpStatus::WorkUnnecessary,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+    assert_eq!(
+        OpStatus::WorkSuccess,
+        dest.station_progresses()[&station_b].borrow().op_status
+    );
+    assert_eq!(
+        Some(&5u32),
+        dest.station_states()[&station_b].borrow().get::<u32>()
+    );
+    assert_eq!(
+        Some(5u32),
+        *train_report
+            .train_resources()
+            .borrow::<StationOutput<u32>>()
+            .try_read()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_fails_work_when_station_output_resource_is_missing()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, station_a) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let station_a = dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_create_fns(CreateFns::new_with_output(
+                    SetupFn::ok(ProgressLimit::Steps(1)),
+                    StationFn::new(work_with_output),
+                    StationFn::ok(5u32),
+                ))
+                // Replaces the setup function that inserts `StationOutput<u32>`.
+                .with_create_setup_fn(SetupFn::ok(ProgressLimit::Steps(1)))
+                .build(),
+        );
+        let dest = dest_builder.build()?;
+
+        (dest, station_a)
+    };
+    rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+
+    assert_eq!(
+        OpStatus::WorkFail,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_clean_reaches_empty_dest() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let mut dest = Destination::<()>::builder().build()?;
+
+    let train_report = rt.block_on(Train::default().reach(&mut dest, VisitOp::Clean))?;
+
+    let station_errors = train_report.train_resources().station_errors();
+    assert!(station_errors.try_read()?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn reach_clean_visits_all_stations_to_destination() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let mut dest = {
+        let mut dest_builder = Destination::<()>::builder();
+        dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_clean_fns(CleanFns::ok())
+                .build(),
+        );
+        dest_builder.add_station(
+            StationSpec::mock("b")?
+                .with_clean_fns(CleanFns::ok())
+                .build(),
+        );
+        dest_builder.build()?
+    };
+    let train_report = rt.block_on(Train::default().reach(&mut dest, VisitOp::Clean))?;
+
+    let station_errors = train_report.train_resources().station_errors();
+    assert!(station_errors.try_read()?.is_empty());
+    assert!(
+        dest.station_progresses().values().all(|station_progress| {
+            station_progress.borrow().op_status == OpStatus::WorkSuccess
+        }),
+        "Expected all station progresses to be `OpStatus::WorkSuccess`, but they were: {station_progresses:?}",
+        station_progresses = dest
+            .station_progresses()
+            .values()
+            .map(|station_progress| { station_progress.borrow().op_status })
+            .collect::<Vec<_>>()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_clean_sets_progress_limit_from_persisted_res_ids()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let tempdir = tempfile::tempdir()?;
+    let (mut dest, station_a) = {
+        let mut res_ids = ResIds::new();
+        res_ids.insert(ResIdLogical::new("network/vpc"), String::from("vpc-1"));
+        res_ids.insert(ResIdLogical::new("network/subnet"), String::from("subnet-1"));
+
+        let mut dest_builder = Destination::<()>::builder()
+            .with_workspace_spec(WorkspaceSpec::Path(tempdir.path().to_path_buf()));
+        let station_a = dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_create_work_fn(StationFn::ok(res_ids))
+                .with_clean_fns(CleanFns::ok())
+                .build(),
+        );
+        (dest_builder.build()?, station_a)
+    };
+
+    rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+    rt.block_on(Train::default().reach(&mut dest, VisitOp::Clean))?;
+
+    assert_eq!(
+        ProgressLimit::Steps(2),
+        dest.station_progresses()[&station_a]
+            .borrow()
+            .progress_limit()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_clean_records_successful_and_failed_ops() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, station_a, station_b) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let station_a = dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_clean_fns(CleanFns::ok())
+                .build(),
+        );
+        let station_b = dest_builder.add_station(
+            StationSpec::mock("b")?
+                .with_clean_fns(CleanFns::err(()))
+                .build(),
+        );
+        let dest = dest_builder.build()?;
+
+        (dest, station_a, station_b)
+    };
+    let train_report = rt.block_on(Train::default().reach(&mut dest, VisitOp::Clean))?;
+
+    let errors_expected = {
+        let mut errors = IndexMap::new();
+        errors.insert(station_b, ());
+        errors
+    };
+
+    let station_errors = train_report.train_resources().station_errors();
+    assert_eq!(errors_expected, station_errors_latest(&station_errors)?);
+    assert_eq!(
+        OpStatus::WorkSuccess,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+    assert_eq!(
+        OpStatus::WorkFail,
+        dest.station_progresses()[&station_b].borrow().op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn audit_reconciles_persisted_res_ids_with_existing_resources()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let tempdir = tempfile::tempdir()?;
+    let exists_fn = StationExistsFn::new(|_, res_ids_persisted, _| {
+        // `network/vpc` was replaced, `network/subnet` was deleted, and
+        // `network/gateway` was created outside of the station.
+        let mut res_ids_existing = IndexMap::new();
+        if let (Some(vpc), Some(subnet)) = (
+            res_ids_persisted.get(&ResIdLogical::new("network/vpc")),
+            res_ids_persisted.get(&ResIdLogical::new("network/subnet")),
+        ) {
+            res_ids_existing.insert(ResIdLogical::new("network/vpc"), subnet.clone());
+            res_ids_existing.insert(ResIdLogical::new("network/gateway"), vpc.clone());
+        }
+        Box::pin(async move { Result::<_, ()>::Ok(res_ids_existing) })
+    });
+    let (mut dest, station_a) = {
+        let mut res_ids = ResIds::new();
+        res_ids.insert(ResIdLogical::new("network/vpc"), String::from("vpc-1"));
+        res_ids.insert(ResIdLogical::new("network/subnet"), String::from("subnet-1"));
+
+        let mut dest_builder = Destination::<()>::builder()
+            .with_workspace_spec(WorkspaceSpec::Path(tempdir.path().to_path_buf()));
+        let station_a = dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_create_work_fn(StationFn::ok(res_ids))
+                .with_exists_fn(exists_fn)
+                .build(),
+        );
+        (dest_builder.build()?, station_a)
+    };
+
+    rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+    let reconciliation_report = rt.block_on(Train::default().audit(&mut dest))?;
+
+    let station_reconciliation = &reconciliation_report.station_reconciliations()[&station_a];
+    assert_eq!(
+        &[ResIdLogical::new("network/subnet")],
+        station_reconciliation.res_ids_missing()
+    );
+    assert_eq!(
+        &[ResIdLogical::new("network/gateway")],
+        station_reconciliation.res_ids_unrecorded()
+    );
+    assert_eq!(
+        &[ResIdLogical::new("network/vpc")],
+        station_reconciliation.res_ids_changed()
+    );
+    assert_eq!(
+        vec![station_a],
+        reconciliation_report.stations_drifted().collect::<Vec<_>>()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn clean_selection_cleans_selected_stations_and_dependents()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, station_a, station_b, station_c) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let station_a = dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_clean_fns(CleanFns::ok())
+                .build(),
+        );
+        let station_b = dest_builder.add_station(
+            StationSpec::mock("b")?
+                .with_clean_fns(CleanFns::ok())
+                .build(),
+        );
+        let station_c = dest_builder.add_station(
+            StationSpec::mock("c")?
+                .with_clean_fns(CleanFns::ok())
+                .build(),
+        );
+        dest_builder.add_edge(station_a, station_b)?;
+        let dest = dest_builder.build()?;
+
+        (dest, station_a, station_b, station_c)
+    };
+    let clean_selection = CleanSelection::new().with_station_id("a");
+
+    let train = Train::default().with_progress_backend(NoopProgressBackend);
+    let train_plan = rt.block_on(train.clean_selection_plan(&dest, &clean_selection))?;
+    rt.block_on(train.clean_selection(&mut dest, &clean_selection))?;
+
+    assert_eq!(
+        vec![station_b, station_a],
+        train_plan.station_order().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        OpStatus::WorkSuccess,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+    assert_eq!(
+        OpStatus::WorkSuccess,
+        dest.station_progresses()[&station_b].borrow().op_status
+    );
+    assert_eq!(
+        OpStatus::OpSkipped,
+        dest.station_progresses()[&station_c].borrow().op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn clean_selection_selects_stations_by_tag() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (dest, station_b) = {
+        let mut dest_builder = Destination::<()>::builder();
+        dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_clean_fns(CleanFns::ok())
+                .build(),
+        );
+        let station_b = dest_builder.add_station(
+            StationSpec::mock("b")?
+                .with_tag("db")
+                .with_clean_fns(CleanFns::ok())
+                .build(),
+        );
+        let dest = dest_builder.build()?;
+
+        (dest, station_b)
+    };
+    let clean_selection = CleanSelection::new().with_tag("db");
+
+    let train_plan =
+        rt.block_on(Train::default().clean_selection_plan(&dest, &clean_selection))?;
+
+    assert_eq!(
+        vec![station_b],
+        train_plan.station_order().collect::<Vec<_>>()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_clean_records_check_fn_failure() -> Result<(), Box<dyn std::error::Error>> {
+    // Note:
+    //
+    // For this test, the create order is `a -> b`, so the clean order is `b -> a`.
+    //
+    // `b` is set up to err on the clean `check_fn`, so `a` should not be cleaned
+    // due to `b`'s error.
+    //
+    // The `errors_expected` should then be a predecessor failure for `a`.
+
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, station_a, station_b) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let [station_a, station_b] = dest_builder.add_stations([
+            StationSpec::mock("a")?
+                .with_clean_fns(CleanFns::err(()))
+                .build(),
+            StationSpec::mock("b")?
+                .with_clean_fns(CleanFns::ok().with_check_fn(StationFn::err(())))
+                .build(),
+        ]);
+        dest_builder.add_edge(station_a, station_b)?;
+
+        let dest = dest_builder.build()?;
+
+        (dest, station_a, station_b)
+    };
+    let train_report = rt.block_on(Train::default().reach(&mut dest, VisitOp::Clean))?;
+
+    let errors_expected = {
+        let mut errors = IndexMap::new();
+        errors.insert(station_b, ());
+        // station b's err should not be reached, because station a failed.
+        errors
+    };
+
+    let station_errors = train_report.train_resources().station_errors();
+    assert_eq!(errors_expected, station_errors_latest(&station_errors)?);
+    assert_eq!(
+        OpStatus::ParentFail,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+    assert_eq!(
+        OpStatus::CheckFail,
+        dest.station_progresses()[&station_b].borrow().op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_clean_records_check_fn_failure_after_op_success() -> Result<(), Box<dyn std::error::Error>>
+{
+    #[derive(Clone, Debug, PartialEq)]
+    struct Error(u8);
+    impl From<StationSpecError> for Error {
+        fn from(_error: StationSpecError) -> Self {
+            Error(1)
+        }
+    }
+
+    fn b_clean_check<'f>(
+        _: &'f mut StationMutRef<'_, Error>,
+        n: &'f u32,
+    ) -> LocalBoxFuture<'f, Result<CheckStatus, Error>> {
+        async move {
+            if *n == 0 {
+                Ok(CheckStatus::WorkRequired)
+            } else {
+                Err(Error(2))
+            }
+        }
+        .boxed_local()
+    }
+
+    fn b_clean_work<'f>(
+        _: &'f mut StationMutRef<'_, Error>,
+        n: &'f mut u32,
+    ) -> LocalBoxFuture<'f, Result<(), Error>> {
+        async move {
+            *n += 1;
+            Ok(())
+        }
+        .boxed_local()
+    }
+
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, station_a, station_b) = {
+        let mut dest_builder = Destination::<Error>::builder();
+        let [station_a, station_b] = dest_builder.add_stations([
+            StationSpec::mock("a")?
+                .with_clean_fns(CleanFns::ok().with_check_fn(StationFn::err(Error(3))))
+                .build(),
+            StationSpec::mock("b")?
+                .with_clean_fns(
+                    CleanFns::new(
+                        SetupFn::new(|_, train_resources| {
+                            train_resources.insert(0u32);
+                            async { Ok(ProgressLimit::Steps(1)) }.boxed_local()
+                        }),
+                        StationFn::new(b_clean_work),
+                    )
+                    .with_check_fn(StationFn::new(b_clean_check)),
+                )
+                .build(),
+        ]);
+        dest_builder.add_edge(station_a, station_b)?;
+
+        let dest = dest_builder.build()?;
+
+        (dest, station_a, station_b)
+    };
+    let train_resources =
+        rt.block_on(Train::<Error>::default().reach(&mut dest, VisitOp::Clean))?;
+
+    let errors_expected = {
+        let mut errors = IndexMap::new();
+        errors.insert(station_b, Error(2));
+        errors
+    };
+
+    let station_errors = train_resources.train_resources().station_errors();
+    assert_eq!(
+        OpStatus::ParentFail,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+    assert_eq!(
+        OpStatus::CheckFail,
+        dest.station_progresses()[&station_b].borrow().op_status
+    );
+    assert_eq!(errors_expected, station_errors_latest(&station_errors)?);
+
+    Ok(())
+}
+
+#[test]
+fn reach_clean_sets_work_unnecessary_if_nothing_changed() -> Result<(), Box<dyn std::error::Error>>
+{
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, station_a, station_b) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let [station_a, station_b] = dest_builder.add_stations([
+            StationSpec::mock("a")?
+                .with_clean_fns(
+                    CleanFns::ok().with_check_fn(StationFn::ok(CheckStatus::WorkNotRequired)),
+                )
+                .build(),
+            StationSpec::mock("b")?
+                .with_clean_fns(
+                    CleanFns::ok().with_check_fn(StationFn::ok(CheckStatus::WorkNotRequired)),
+                )
+                .build(),
+        ]);
+        dest_builder.add_edge(station_a, station_b)?;
+
+        let dest = dest_builder.build()?;
+
+        (dest, station_a, station_b)
+    };
+    let train_resources = rt.block_on(Train::default().reach(&mut dest, VisitOp::Clean))?;
+
+    let errors_expected = IndexMap::<StationRtId, ()>::new();
+
+    let station_errors = train_resources.train_resources().station_errors();
+    assert_eq!(errors_expected, station_errors_latest(&station_errors)?);
+    assert_eq!(
+        OpStatus::WorkUnnecessary,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+    assert_eq!(
+        OpStatus::WorkUnnecessary,
+        dest.station_progresses()[&station_b].borrow().op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_clean_sets_work_unnecessary_if_clean_not_supported()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, station_a, station_b) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let [station_a, station_b] = dest_builder.add_stations([
+            StationSpec::mock("a")?.build(),
+            StationSpec::mock("b")?.build(),
+        ]);
+        dest_builder.add_edge(station_a, station_b)?;
+
+        let dest = dest_builder.build()?;
+
+        (dest, station_a, station_b)
+    };
+    let train_resources = rt.block_on(Train::default().reach(&mut dest, VisitOp::Clean))?;
+
+    let errors_expected = IndexMap::<StationRtId, ()>::new();
+
+    let station_errors = train_resources.train_resources().station_errors();
+    assert_eq!(errors_expected, station_errors_latest(&station_errors)?);
+    assert_eq!(
+        OpStatus::WorkUnnecessary,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+    assert_eq!(
+        OpStatus::WorkUnnecessary,
+        dest.station_progresses()[&station_b].borrow().op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_skips_work_fn_when_check_fn_returns_work_not_required()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let mock_invocations = MockInvocations::new();
+    let mut dest = {
+        let mut dest_builder = Destination::<()>::builder();
+        dest_builder.add_stations([
+            StationSpec::mock("a")?
+                .with_create_check_fn(StationFn::ok(CheckStatus::WorkNotRequired))
+                .with_mock_invocations(mock_invocations.clone())
+                .build(),
+            StationSpec::mock("b")?
+                .with_create_check_fn(StationFn::ok(CheckStatus::WorkRequired))
+                .with_mock_invocations(mock_invocations.clone())
+                .build(),
+        ]);
+        dest_builder.build()?
+    };
+    rt.block_on(Train::default().reach_deterministic(&mut dest, VisitOp::Create))?;
+
+    assert_eq!(1, mock_invocations.count("a", MockOpFn::CreateSetup));
+    assert_eq!(1, mock_invocations.count("a", MockOpFn::CreateCheck));
+    assert_eq!(0, mock_invocations.count("a", MockOpFn::CreateWork));
+    assert_eq!(2, mock_invocations.count("b", MockOpFn::CreateCheck));
+    assert_eq!(1, mock_invocations.count("b", MockOpFn::CreateWork));
+    let b_work_invocation = mock_invocations
+        .invocations()
+        .into_iter()
+        .find(|invocation| {
+            &**invocation.station_id == "b" && invocation.op_fn == MockOpFn::CreateWork
+        })
+        .ok_or("Expected `b` work fn to be invoked.")?;
+    assert_eq!(OpStatus::WorkInProgress, b_work_invocation.op_status);
+    assert_eq!(ProgressLimit::Steps(10), b_work_invocation.progress_limit);
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_records_borrowed_resource_type_names_in_mock_invocations()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let mock_invocations = MockInvocations::new();
+    let mut dest = {
+        let mut dest_builder = Destination::<()>::builder();
+        dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_create_setup_fn(SetupFn::new(|_station, train_resources| {
+                    train_resources.insert(1u8);
+                    train_resources.insert(2u16);
+                    async { Ok(ProgressLimit::Steps(1)) }.boxed_local()
+                }))
+                .with_create_work_fn(StationFn::new(work_read_u8_write_u16))
+                .with_mock_invocations(mock_invocations.clone())
+                .build(),
+        );
+        dest_builder.build()?
+    };
+    rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+
+    let invocations = mock_invocations.invocations();
+    let setup_invocation = invocations
+        .iter()
+        .find(|invocation| invocation.op_fn == MockOpFn::CreateSetup)
+        .ok_or("Expected `a` setup fn to be invoked.")?;
+    let work_invocation = invocations
+        .iter()
+        .find(|invocation| invocation.op_fn == MockOpFn::CreateWork)
+        .ok_or("Expected `a` work fn to be invoked.")?;
+    assert!(setup_invocation.borrow_type_names.is_empty());
+    assert!(setup_invocation.borrow_mut_type_names.is_empty());
+    assert_eq!(vec!["u8"], work_invocation.borrow_type_names);
+    assert_eq!(vec!["u16"], work_invocation.borrow_mut_type_names);
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_uses_speculative_check_unless_parent_work_is_done()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let mock_invocations = MockInvocations::new();
+    let (mut dest, [station_b, station_y]) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let [station_a, station_b, station_x, station_y] = dest_builder.add_stations([
+            StationSpec::mock("a")?
+                .with_create_check_fn(StationFn::ok(CheckStatus::WorkNotRequired))
+                .build(),
+            StationSpec::mock("b")?
+                .with_create_check_fn(StationFn::ok(CheckStatus::WorkNotRequired))
+                .with_check_speculative(true)
+                .with_mock_invocations(mock_invocations.clone())
+                .build(),
+            StationSpec::mock("x")?.build(),
+            StationSpec::mock("y")?
+                .with_create_check_fn(StationFn::ok(CheckStatus::WorkNotRequired))
+                .with_check_speculative(true)
+                .with_mock_invocations(mock_invocations.clone())
+                .build(),
+        ]);
+        dest_builder.add_edge(station_a, station_b)?;
+        dest_builder.add_edge(station_x, station_y)?;
+        let dest = dest_builder.build()?;
+
+        (dest, [station_b, station_y])
+    };
+    rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+
+    // `b`'s speculative result is used, but `y` is checked again as `x` did work.
+    assert_eq!(1, mock_invocations.count("b", MockOpFn::CreateCheck));
+    assert_eq!(2, mock_invocations.count("y", MockOpFn::CreateCheck));
+    assert_eq!(
+        OpStatus::WorkUnnecessary,
+        dest.station_progresses()[&station_b].borrow().op_status
+    );
+    assert_eq!(
+        OpStatus::WorkUnnecessary,
+        dest.station_progresses()[&station_y].borrow().op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_checks_speculative_station_again_when_ancestor_work_is_done()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let mock_invocations = MockInvocations::new();
+    let (mut dest, station_c) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let [station_a, station_b, station_c] = dest_builder.add_stations([
+            StationSpec::mock("a")?.build(),
+            StationSpec::mock("b")?
+                .with_create_check_fn(StationFn::ok(CheckStatus::WorkNotRequired))
+                .build(),
+            StationSpec::mock("c")?
+                .with_create_check_fn(StationFn::ok(CheckStatus::WorkNotRequired))
+                .with_check_speculative(true)
+                .with_mock_invocations(mock_invocations.clone())
+                .build(),
+        ]);
+        dest_builder.add_edge(station_a, station_b)?;
+        dest_builder.add_edge(station_b, station_c)?;
+        let dest = dest_builder.build()?;
+
+        (dest, station_c)
+    };
+    rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+
+    // `b`'s work is unnecessary, but `c` is checked again as `a` did work.
+    assert_eq!(2, mock_invocations.count("c", MockOpFn::CreateCheck));
+    assert_eq!(
+        OpStatus::WorkUnnecessary,
+        dest.station_progresses()[&station_c].borrow().op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_records_speculative_check_panic_when_station_is_visited()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, station_a, station_b) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let [station_a, station_b] = dest_builder.add_stations([
+            StationSpec::mock("a")?
+                .with_create_work_fn(StationFn::ok(ResIds::new()))
+                .build(),
+            StationSpec::mock("b")?
+                .with_create_check_fn(StationFn::new0(check_panic))
+                .with_check_speculative(true)
+                .build(),
+        ]);
+        dest_builder.add_edge(station_a, station_b)?;
+        let dest = dest_builder.build()?;
+
+        (dest, station_a, station_b)
+    };
+    let train_report = rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+
+    assert_eq!(
+        OpStatus::WorkSuccess,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+    assert_eq!(
+        OpStatus::Panicked,
+        dest.station_progresses()[&station_b].borrow().op_status
+    );
+    assert_eq!(
+        1,
+        rt.block_on(train_report.station_error_count(station_b, StationErrorPhase::Check))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_consults_chaos_policy_before_speculative_check()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let mock_invocations = MockInvocations::new();
+    let (mut dest, station_b) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let [station_a, station_b] = dest_builder.add_stations([
+            StationSpec::mock("a")?.build(),
+            StationSpec::mock("b")?
+                .with_create_check_fn(StationFn::ok(CheckStatus::WorkNotRequired))
+                .with_check_speculative(true)
+                .with_mock_invocations(mock_invocations.clone())
+                .build(),
+        ]);
+        dest_builder.add_edge(station_a, station_b)?;
+        let dest = dest_builder.build()?;
+
+        (dest, station_b)
+    };
+    let train = Train::default().with_chaos_policy(ChaosPolicy::new(
+        |station_spec, _visit_op, chaos_point| {
+            if &**station_spec.id() == "b" && chaos_point == ChaosPoint::Check {
+                ChaosAction::Fail(())
+            } else {
+                ChaosAction::Proceed
+            }
+        },
+    ));
+    rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+
+    assert_eq!(0, mock_invocations.count("b", MockOpFn::CreateCheck));
+    assert_eq!(
+        OpStatus::CheckFail,
+        dest.station_progresses()[&station_b].borrow().op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_runs_work_fn_only_when_inputs_change() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let tempdir = tempfile::tempdir()?;
+    let input_path = tempdir.path().join("input.txt");
+    std::fs::write(&input_path, "one")?;
+    let mock_invocations = MockInvocations::new();
+    let mut dest = {
+        let mut dest_builder = Destination::<()>::builder()
+            .with_workspace_spec(WorkspaceSpec::Path(tempdir.path().to_path_buf()));
+        dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_input("*.txt")
+                .with_mock_invocations(mock_invocations.clone())
+                .build(),
+        );
+        dest_builder.build()?
+    };
+
+    rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+    rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+    assert_eq!(1, mock_invocations.count("a", MockOpFn::CreateWork));
+
+    std::fs::write(&input_path, "two")?;
+    rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+    assert_eq!(2, mock_invocations.count("a", MockOpFn::CreateWork));
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_migrates_or_does_work_when_spec_version_changes()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let tempdir = tempfile::tempdir()?;
+    let mock_invocations = MockInvocations::new();
+
+    // The version is recorded even though the work is not required.
+    let mut dest = dest_with_version(tempdir.path(), &mock_invocations, 1, None)?;
+    rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+    assert_eq!(0, mock_invocations.count("a", MockOpFn::CreateWork));
+
+    let mut dest = dest_with_version(tempdir.path(), &mock_invocations, 2, None)?;
+    rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+    assert_eq!(1, mock_invocations.count("a", MockOpFn::CreateWork));
+
+    let versions_migrated = Arc::new(Mutex::new(Vec::new()));
+    let migrate_fn = {
+        let versions_migrated = versions_migrated.clone();
+        StationMigrateFn::new(move |_, version_recorded, _| {
+            if let Ok(mut versions_migrated) = versions_migrated.lock() {
+                versions_migrated.push(version_recorded);
+            }
+            Box::pin(async { Result::<(), ()>::Ok(()) })
+        })
+    };
+    let mut dest = dest_with_version(tempdir.path(), &mock_invocations, 3, Some(migrate_fn))?;
+    rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+    assert_eq!(1, mock_invocations.count("a", MockOpFn::CreateWork));
+    assert_eq!(vec![2], *versions_migrated.lock().unwrap_or_else(PoisonError::into_inner));
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_records_station_files_in_destination_file_system()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let tempdir = tempfile::tempdir()?;
+    let (mut dest, station_a) = {
+        let mut dest_builder = Destination::<()>::builder()
+            .with_workspace_spec(WorkspaceSpec::Path(tempdir.path().to_path_buf()))
+            .with_file_system(MemoryFileSystem::new());
+        let station_a = dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_version(1)
+                .with_create_check_fn(StationFn::ok(CheckStatus::WorkNotRequired))
+                .build(),
+        );
+        (dest_builder.build()?, station_a)
+    };
+
+    rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+
+    let station_dir = dest.dirs().station_dirs()[&station_a].clone();
+    let version_path = StationVersionRecord::version_path(&station_dir);
+    let file_system = dest.file_system();
+    assert!(rt.block_on(file_system.exists(&version_path)));
+    assert!(rt.block_on(file_system.is_dir(&station_dir.outputs_dir())));
+    assert!(!version_path.exists());
+    assert!(!station_dir.outputs_dir().exists());
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_fails_check_until_external_dependency_is_created()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let tempdir = tempfile::tempdir()?;
+    let (mut dest_network, [station_vpc]) = {
+        let mut dest_builder = Destination::<()>::builder()
+            .with_workspace_spec(WorkspaceSpec::Path(tempdir.path().to_path_buf()))
+            .with_profile(Profile::new("network")?);
+        let station_rt_ids = dest_builder.add_stations([StationSpec::mock("vpc")?.build()]);
+        (dest_builder.build()?, station_rt_ids)
+    };
+    let (mut dest_app, [station_app]) = {
+        let mut dest_builder = Destination::<()>::builder()
+            .with_workspace_spec(WorkspaceSpec::Path(tempdir.path().to_path_buf()))
+            .with_profile(Profile::new("app")?);
+        let station_rt_ids = dest_builder.add_stations([StationSpec::mock("app")?
+            .with_external_dependency(Profile::new("network")?, StationId::new("vpc")?)
+            .build()]);
+        (dest_builder.build()?, station_rt_ids)
+    };
+
+    rt.block_on(Train::default().reach(&mut dest_app, VisitOp::Create))?;
+    assert_eq!(
+        OpStatus::CheckFail,
+        dest_app.station_progresses()[&station_app].borrow().op_status
+    );
+
+    rt.block_on(Train::default().reach(&mut dest_network, VisitOp::Create))?;
+    assert_eq!(
+        OpStatus::WorkSuccess,
+        dest_network.station_progresses()[&station_vpc].borrow().op_status
+    );
+    rt.block_on(Train::default().reach(&mut dest_app, VisitOp::Create))?;
+    assert_eq!(
+        OpStatus::WorkSuccess,
+        dest_app.station_progresses()[&station_app].borrow().op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_resolves_file_inputs_published_by_parent() -> Result<(), Box<dyn std::error::Error>>
+{
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, station_a, station_b) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let [station_a, station_b] = dest_builder.add_stations([
+            StationSpec::mock("a")?
+                .with_create_setup_fn(SetupFn::new(|station, _| {
+                    Box::pin(async move {
+                        station.publish_file("out", "out.txt");
+                        Ok(ProgressLimit::Steps(1))
+                    })
+                }))
+                .build(),
+            StationSpec::mock("b")?
+                .with_file_input(StationId::new("a")?, "out")
+                .build(),
+        ]);
+        dest_builder.add_edge(station_a, station_b)?;
+        let dest = dest_builder.build()?;
+
+        (dest, station_a, station_b)
+    };
+    rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+
+    let path_expected = dest
+        .dirs()
+        .station_dirs()
+        .get(&station_a)
+        .map(|station_dir| station_dir.join("out.txt"));
+    let station_progresses = dest.station_progresses();
+    let station_progress_b = station_progresses[&station_b].borrow();
+    assert_eq!(OpStatus::WorkSuccess, station_progress_b.op_status);
+    assert_eq!(
+        path_expected.as_deref(),
+        station_progress_b.file_input(&StationId::new("a")?, "out")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_fails_check_when_file_input_is_unpublished()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, station_a, station_b) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let [station_a, station_b] = dest_builder.add_stations([
+            StationSpec::mock("a")?.build(),
+            StationSpec::mock("b")?
+                .with_file_input(StationId::new("a")?, "out")
+                .build(),
+        ]);
+        dest_builder.add_edge(station_a, station_b)?;
+        let dest = dest_builder.build()?;
+
+        (dest, station_a, station_b)
+    };
+    rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+
+    assert_eq!(
+        OpStatus::WorkSuccess,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+    assert_eq!(
+        OpStatus::CheckFail,
+        dest.station_progresses()[&station_b].borrow().op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_deterministic_visits_one_station_at_a_time_in_the_same_order()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let visit_events = || -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let mut dest = {
+            let mut dest_builder = Destination::<()>::builder();
+            let [a, b, c, d] = dest_builder.add_stations([
+                StationSpec::mock("a")?
+                    .with_create_setup_fn(SetupFn::new(|_station, train_resources| {
+                        Box::pin(async move {
+                            train_resources.insert(VisitEvents::default());
+                            Ok(ProgressLimit::Steps(1))
+                        })
+                    }))
+                    .with_create_work_fn(StationFn::new(work_record_visit))
+                    .build(),
+                StationSpec::mock("b")?
+                    .with_create_work_fn(StationFn::new(work_record_visit))
+                    .build(),
+                StationSpec::mock("c")?
+                    .with_create_work_fn(StationFn::new(work_record_visit))
+                    .build(),
+                StationSpec::mock("d")?
+                    .with_create_work_fn(StationFn::new(work_record_visit))
+                    .build(),
+            ]);
+            dest_builder.add_edges([(a, d), (b, d), (c, d)])?;
+            dest_builder.build()?
+        };
+        let train_report =
+            rt.block_on(Train::default().reach_deterministic(&mut dest, VisitOp::Create))?;
+        let visit_events = train_report
+            .train_resources()
+            .borrow::<VisitEvents>()
+            .0
+            .lock()
+            .map_err(|_| "`VisitEvents` lock poisoned")?
+            .clone();
+
+        Ok(visit_events)
+    };
+
+    let visit_events_first = visit_events()?;
+    let visit_events_second = visit_events()?;
+
+    assert_eq!(8, visit_events_first.len());
+    visit_events_first.chunks(2).for_each(|events| {
+        assert_eq!(
+            events[0].replace("begin", "end"),
+            events[1],
+            "Expected stations to be visited one at a time, but events were: {visit_events_first:?}"
+        );
+    });
+    assert_eq!("d end", visit_events_first[7]);
+    assert_eq!(visit_events_first, visit_events_second);
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_records_op_status_without_rendering_progress_when_progress_backend_is_noop()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, [station_a, station_b]) = dest_with_chain()?;
+    let train = Train::default().with_progress_backend(NoopProgressBackend);
+    let train_report = rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+
+    let station_errors = train_report.train_resources().station_errors();
+    assert!(station_errors.try_read()?.is_empty());
+    [station_a, station_b].iter().for_each(|station_rt_id| {
+        let station_progress = dest.station_progresses()[station_rt_id].borrow();
+        assert_eq!(OpStatus::WorkSuccess, station_progress.op_status);
+        assert!(!station_progress.progress_bar().is_finished());
+    });
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_spawns_send_work_on_worker_thread_when_execution_model_is_multi_thread()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .build()?;
+    let work_thread_id = Arc::new(Mutex::new(None::<ThreadId>));
+    let (mut dest, station_a) = {
+        let work_thread_id = Arc::clone(&work_thread_id);
+        let mut dest_builder = Destination::<()>::builder();
+        let station_a = dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_create_work_fn(StationFn::new_send(
+                    move |_: &mut StationMutRef<'_, ()>| -> BoxFuture<'static, _> {
+                        let work_thread_id = Arc::clone(&work_thread_id);
+                        async move {
+                            *work_thread_id.lock().unwrap() = Some(thread::current().id());
+                            Result::<ResIds, (ResIds, ())>::Ok(ResIds::new())
+                        }
+                        .boxed()
+                    },
+                ))
+                .build(),
+        );
+        (dest_builder.build()?, station_a)
+    };
+    let train = Train::default().with_execution_model(ExecutionModel::MultiThread);
+    let train_report = rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+
+    let station_errors = train_report.train_resources().station_errors();
+    assert!(station_errors.try_read()?.is_empty());
+    assert_eq!(
+        OpStatus::WorkSuccess,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+    let work_thread_id = *work_thread_id.lock().unwrap();
+    assert!(work_thread_id.is_some());
+    assert_ne!(Some(thread::current().id()), work_thread_id);
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_spawns_send_work_using_spawner_when_execution_model_is_multi_thread()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, station_a) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let station_a = dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_create_work_fn(StationFn::new_send(
+                    |_: &mut StationMutRef<'_, ()>| -> BoxFuture<'static, _> {
+                        async { Result::<ResIds, (ResIds, ())>::Ok(ResIds::new()) }.boxed()
+                    },
+                ))
+                .build(),
+        );
+        (dest_builder.build()?, station_a)
+    };
+    let spawn_count = Arc::new(AtomicUsize::new(0));
+    let spawner = {
+        let spawn_count = Arc::clone(&spawn_count);
+        Spawner::new(move |fut| {
+            spawn_count.fetch_add(1, Ordering::SeqCst);
+            tokio::spawn(fut);
+        })
+    };
+    let train = Train::default()
+        .with_execution_model(ExecutionModel::MultiThread)
+        .with_spawner(spawner);
+    let train_report = rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+
+    let station_errors = train_report.train_resources().station_errors();
+    assert!(station_errors.try_read()?.is_empty());
+    assert_eq!(
+        OpStatus::WorkSuccess,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+    assert_eq!(1, spawn_count.load(Ordering::SeqCst));
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_work_fails_when_spawner_drops_send_work_task()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, station_a) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let station_a = dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_create_work_fn(StationFn::new_send(
+                    |_: &mut StationMutRef<'_, ()>| -> BoxFuture<'static, _> {
+                        async { Result::<ResIds, (ResIds, ())>::Ok(ResIds::new()) }.boxed()
+                    },
+                ))
+                .build(),
+        );
+        (dest_builder.build()?, station_a)
+    };
+    let train = Train::default()
+        .with_execution_model(ExecutionModel::MultiThread)
+        .with_spawner(Spawner::new(drop));
+    let train_report = rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+
+    let errors_expected = {
+        let mut errors = IndexMap::new();
+        errors.insert(station_a, ());
+        errors
+    };
+    let station_errors = train_report.train_resources().station_errors();
+    assert_eq!(errors_expected, station_errors_latest(&station_errors)?);
+    assert_eq!(
+        OpStatus::WorkFail,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_passes_resources_to_send_work() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, station_a) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let station_a = dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_create_setup_fn(SetupFn::new(|_station, train_resources| {
+                    Box::pin(async move {
+                        train_resources.insert(12u32);
+                        Ok(ProgressLimit::Steps(1))
+                    })
+                }))
+                .with_create_work_fn(StationFn::new_send(
+                    |_: &mut StationMutRef<'_, ()>, value: &u32| -> BoxFuture<'static, _> {
+                        let value = *value;
+                        async move {
+                            if value == 12 {
+                                Result::<ResIds, (ResIds, ())>::Ok(ResIds::new())
+                            } else {
+                                Err((ResIds::new(), ()))
+                            }
+                        }
+                        .boxed()
+                    },
+                ))
+                .build(),
+        );
+        (dest_builder.build()?, station_a)
+    };
+    let train = Train::default().with_execution_model(ExecutionModel::MultiThread);
+    let train_report = rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+
+    let station_errors = train_report.train_resources().station_errors();
+    assert!(station_errors.try_read()?.is_empty());
+    assert_eq!(
+        OpStatus::WorkSuccess,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_visits_stations_one_at_a_time_when_resource_limit_is_one()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let mut dest = {
+        let mut dest_builder = Destination::<()>::builder();
+        dest_builder.add_stations([
+            StationSpec::mock("a")?
+                .with_create_setup_fn(SetupFn::new(|_station, train_resources| {
+                    Box::pin(async move {
+                        train_resources.insert(VisitEvents::default());
+                        Ok(ProgressLimit::Steps(1))
+                    })
+                }))
+                .with_create_work_fn(StationFn::new(work_record_visit))
+                .build(),
+            StationSpec::mock("b")?
+                .with_create_work_fn(StationFn::new(work_record_visit))
+                .build(),
+            StationSpec::mock("c")?
+                .with_create_work_fn(StationFn::new(work_record_visit))
+                .build(),
+        ]);
+        dest_builder.build()?
+    };
+    let resource_limits =
+        ResourceLimits::new().with_limit::<VisitEvents>(NonZeroUsize::new(1).ok_or("zero")?);
+    let train = Train::default().with_resource_limits(resource_limits);
+    let train_report = rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+
+    let visit_events = train_report
+        .train_resources()
+        .borrow::<VisitEvents>()
+        .0
+        .lock()
+        .map_err(|_| "`VisitEvents` lock poisoned")?
+        .clone();
+    assert_eq!(6, visit_events.len());
+    visit_events.chunks(2).for_each(|events| {
+        let station_id = events[0].trim_end_matches(" begin");
+        assert_eq!(
+            [format!("{station_id} begin"), format!("{station_id} end")],
+            events
+        );
+    });
+    let queue_metrics = train_report.queue_metrics();
+    assert_eq!(3, queue_metrics.station_wait_durations().len());
+    assert_eq!(2, queue_metrics.queue_depth_max());
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_serializes_stations_with_conflicting_borrows()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, station_a, station_b) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let [station_a, station_b] = dest_builder.add_stations([
+            StationSpec::mock("a")?
+                .with_create_setup_fn(SetupFn::new(|_, train_resources| {
+                    train_resources.insert(0u32);
+                    async { Ok(ProgressLimit::Steps(1)) }.boxed_local()
+                }))
+                .with_create_work_fn(StationFn::new(work_write_u32_fail))
+                .build(),
+            StationSpec::mock("b")?
+                .with_create_work_fn(StationFn::new(work_read_u32))
+                .build(),
+        ]);
+        let dest = dest_builder.build()?;
+
+        (dest, station_a, station_b)
+    };
+    rt.block_on(Train::default().reach(&mut dest, VisitOp::Create))?;
+
+    // `b` waits for `a` instead of failing to borrow `u32`, and is visited even
+    // though `a` fails, as there is no edge between them.
+    assert_eq!(0, dest.station_specs().edge_count());
+    assert_eq!(
+        OpStatus::WorkFail,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+    assert_eq!(
+        OpStatus::WorkSuccess,
+        dest.station_progresses()[&station_b].borrow().op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_runs_one_work_fn_at_a_time_when_work_concurrency_max_is_one()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, station_b) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let [_station_a, station_b, _station_c] = dest_builder.add_stations([
+            StationSpec::mock("a")?
+                .with_create_setup_fn(SetupFn::new(|_station, train_resources| {
+                    Box::pin(async move {
+                        train_resources.insert(VisitEvents::default());
+                        Ok(ProgressLimit::Steps(1))
+                    })
+                }))
+                .with_create_work_fn(StationFn::new(work_record_visit))
+                .build(),
+            StationSpec::mock("b")?
+                .with_create_check_fn(StationFn::ok(CheckStatus::WorkNotRequired))
+                .with_create_work_fn(StationFn::new(work_record_visit))
+                .build(),
+            StationSpec::mock("c")?
+                .with_create_work_fn(StationFn::new(work_record_visit))
+                .build(),
+        ]);
+        let dest = dest_builder.build()?;
+
+        (dest, station_b)
+    };
+    let train = Train::default().with_work_concurrency_max(NonZeroUsize::new(1).ok_or("zero")?);
+    let train_report = rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+
+    let visit_events = train_report
+        .train_resources()
+        .borrow::<VisitEvents>()
+        .0
+        .lock()
+        .map_err(|_| "`VisitEvents` lock poisoned")?
+        .clone();
+    assert_eq!(4, visit_events.len());
+    visit_events.chunks(2).for_each(|events| {
+        let station_id = events[0].trim_end_matches(" begin");
+        assert_eq!(
+            [format!("{station_id} begin"), format!("{station_id} end")],
+            events
+        );
+    });
+    assert_eq!(
+        OpStatus::WorkUnnecessary,
+        dest.station_progresses()[&station_b].borrow().op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_records_ready_stations_held_by_concurrency_limit()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let mut dest = {
+        let mut dest_builder = Destination::<()>::builder();
+        dest_builder.add_stations([
+            StationSpec::mock("a")?
+                .with_create_work_fn(StationFn::ok(ResIds::new()))
+                .build(),
+            StationSpec::mock("b")?
+                .with_create_work_fn(StationFn::ok(ResIds::new()))
+                .build(),
+            StationSpec::mock("c")?
+                .with_create_work_fn(StationFn::ok(ResIds::new()))
+                .build(),
+        ]);
+        dest_builder.build()?
+    };
+    let train = Train::default().with_concurrency_max(NonZeroUsize::new(1).ok_or("zero")?);
+    let train_report = rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+
+    assert_eq!(2, train_report.queue_metrics().ready_depth_max());
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_idle_worker_takes_station_queued_on_busy_worker()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, station_a, station_b) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let [station_a, station_b] = dest_builder.add_stations([
+            StationSpec::mock("a")?
+                .with_create_setup_fn(SetupFn::new(|_station, train_resources| {
+                    Box::pin(async move {
+                        train_resources.insert(VisitBarrier(Barrier::new(2)));
+                        Ok(ProgressLimit::Steps(1))
+                    })
+                }))
+                .with_create_work_fn(StationFn::new(work_barrier_wait))
+                .build(),
+            StationSpec::mock("b")?
+                .with_create_work_fn(StationFn::new(work_barrier_wait))
+                .build(),
+        ]);
+        let dest = dest_builder.build()?;
+
+        (dest, station_a, station_b)
+    };
+    let train = Train::default().with_concurrency_max(NonZeroUsize::new(2).ok_or("zero")?);
+    let train_report = rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+
+    // Both stations are queued on the first worker, which waits in `a` until `b`
+    // is visited, so `b` is only visited if the second worker takes it.
+    assert_eq!(1, train_report.queue_metrics().steal_count());
+    [station_a, station_b].iter().for_each(|station_rt_id| {
+        assert_eq!(
+            OpStatus::WorkSuccess,
+            dest.station_progresses()[station_rt_id].borrow().op_status
+        );
+    });
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_completes_unchanged_stations_when_execution_strategy_is_fast_path()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, [station_a, station_b, station_x, station_y]) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let [a, b, x, y] = dest_builder.add_stations([
+            StationSpec::mock("a")?
+                .with_create_check_fn(StationFn::ok(CheckStatus::WorkNotRequired))
+                .with_create_work_fn(StationFn::err((ResIds::new(), ())))
+                .build(),
+            StationSpec::mock("b")?
+                .with_create_check_fn(StationFn::ok(CheckStatus::WorkNotRequired))
+                .with_create_work_fn(StationFn::err((ResIds::new(), ())))
+                .build(),
+            StationSpec::mock("x")?
+                .with_create_check_fn(StationFn::ok(CheckStatus::WorkRequired))
+                .with_create_work_fn(StationFn::ok(ResIds::new()))
+                .build(),
+            StationSpec::mock("y")?
+                .with_create_work_fn(StationFn::ok(ResIds::new()))
+                .build(),
+        ]);
+        dest_builder.add_edges([(a, b), (x, y)])?;
+        (dest_builder.build()?, [a, b, x, y])
+    };
+    let train = Train::default().with_execution_strategy(FastPathStrat);
+    let train_report = rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+
+    let station_errors = train_report.train_resources().station_errors();
+    assert!(station_errors.try_read()?.is_empty());
+    [
+        (station_a, OpStatus::WorkUnnecessary),
+        (station_b, OpStatus::WorkUnnecessary),
+        (station_x, OpStatus::WorkSuccess),
+        (station_y, OpStatus::WorkSuccess),
+    ]
+    .iter()
+    .for_each(|(station_rt_id, op_status_expected)| {
+        assert_eq!(
+            *op_status_expected,
+            dest.station_progresses()[station_rt_id].borrow().op_status
+        );
+    });
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_uses_fast_path_check_result_when_station_is_visited()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let mock_invocations = MockInvocations::new();
+    let mut dest = {
+        let mut dest_builder = Destination::<()>::builder();
+        let [a, b] = dest_builder.add_stations([
+            StationSpec::mock("a")?
+                .with_create_check_fn(StationFn::ok(CheckStatus::WorkNotRequired))
+                .with_mock_invocations(mock_invocations.clone())
+                .build(),
+            StationSpec::mock("b")?
+                .with_create_check_fn(StationFn::ok(CheckStatus::WorkNotRequired))
+                .with_mock_invocations(mock_invocations.clone())
+                .build(),
+        ]);
+        dest_builder.add_edge(a, b)?;
+        dest_builder.build()?
+    };
+    let train = Train::default().with_execution_strategy(FastPathStrat);
+    rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+
+    assert_eq!(1, mock_invocations.count("a", MockOpFn::CreateCheck));
+    assert_eq!(1, mock_invocations.count("b", MockOpFn::CreateCheck));
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_does_work_when_spec_version_changes_and_execution_strategy_is_fast_path()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let tempdir = tempfile::tempdir()?;
+    let mock_invocations = MockInvocations::new();
+    let train = Train::default().with_execution_strategy(FastPathStrat);
+
+    let mut dest = dest_with_version(tempdir.path(), &mock_invocations, 1, None)?;
+    rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+    assert_eq!(0, mock_invocations.count("a", MockOpFn::CreateWork));
+
+    let mut dest = dest_with_version(tempdir.path(), &mock_invocations, 2, None)?;
+    rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+    assert_eq!(1, mock_invocations.count("a", MockOpFn::CreateWork));
+    assert_eq!(
+        OpStatus::WorkSuccess,
+        dest.station_progresses()
+            .values()
+            .next()
+            .ok_or("Expected station `a` to exist.")?
+            .borrow()
+            .op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_does_work_when_inputs_change_and_execution_strategy_is_fast_path()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let tempdir = tempfile::tempdir()?;
+    let input_path = tempdir.path().join("input.txt");
+    std::fs::write(&input_path, "one")?;
+    let mock_invocations = MockInvocations::new();
+    let mut dest = {
+        let mut dest_builder = Destination::<()>::builder()
+            .with_workspace_spec(WorkspaceSpec::Path(tempdir.path().to_path_buf()));
+        dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_input("*.txt")
+                .with_create_check_fn(StationFn::ok(CheckStatus::WorkNotRequired))
+                .with_mock_invocations(mock_invocations.clone())
+                .build(),
+        );
+        dest_builder.build()?
+    };
+    let train = Train::default().with_execution_strategy(FastPathStrat);
+
+    rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+    rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+    assert_eq!(1, mock_invocations.count("a", MockOpFn::CreateWork));
+
+    std::fs::write(&input_path, "two")?;
+    rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+    assert_eq!(2, mock_invocations.count("a", MockOpFn::CreateWork));
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_marks_dependents_parent_fail_when_chaos_policy_fails_station()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, [station_a, station_b]) = dest_with_chain()?;
+    let train = Train::default().with_chaos_policy(ChaosPolicy::new(
+        |station_spec, _visit_op, chaos_point| {
+            if &**station_spec.id() == "a" && chaos_point == ChaosPoint::Work {
+                ChaosAction::Fail(())
+            } else {
+                ChaosAction::Proceed
+            }
+        },
+    ));
+    let train_report = rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+
+    let errors_expected = {
+        let mut errors = IndexMap::new();
+        errors.insert(station_a, ());
+        errors
+    };
+
+    let station_errors = train_report.train_resources().station_errors();
+    assert_eq!(errors_expected, station_errors_latest(&station_errors)?);
+    assert_eq!(
+        OpStatus::WorkFail,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+    assert_eq!(
+        OpStatus::ParentFail,
+        dest.station_progresses()[&station_b].borrow().op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_marks_station_work_interrupted_when_chaos_policy_cancels_station()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()?;
+    let (mut dest, [station_a, station_b]) = dest_with_chain()?;
+    let train = Train::default().with_chaos_policy(ChaosPolicy::new(
+        |station_spec, _visit_op, chaos_point| {
+            if &**station_spec.id() == "a" && chaos_point == ChaosPoint::Check {
+                ChaosAction::Cancel
+            } else {
+                ChaosAction::Delay(Duration::from_millis(1))
+            }
+        },
+    ));
+    let train_report = rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+
+    let station_errors = train_report.train_resources().station_errors();
+    assert!(station_errors.try_read()?.is_empty());
+    assert_eq!(
+        OpStatus::WorkInterrupted,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+    assert_eq!(
+        OpStatus::ParentFail,
+        dest.station_progresses()[&station_b].borrow().op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_clean_marks_dependencies_parent_fail_when_chaos_policy_fails_station()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (mut dest, [station_a, station_b]) = dest_with_chain()?;
+    let train = Train::default().with_chaos_policy(ChaosPolicy::new(
+        |station_spec, visit_op, chaos_point| {
+            if &**station_spec.id() == "b"
+                && visit_op == VisitOp::Clean
+                && chaos_point == ChaosPoint::Work
+            {
+                ChaosAction::Fail(())
+            } else {
+                ChaosAction::Proceed
+            }
+        },
+    ));
+    rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+    let train_report = rt.block_on(train.reach(&mut dest, VisitOp::Clean))?;
+
+    let errors_expected = {
+        let mut errors = IndexMap::new();
+        errors.insert(station_b, ());
+        errors
+    };
+
+    let station_errors = train_report.train_resources().station_errors();
+    assert_eq!(errors_expected, station_errors_latest(&station_errors)?);
+    assert_eq!(
+        OpStatus::WorkFail,
+        dest.station_progresses()[&station_b].borrow().op_status
+    );
+    assert_eq!(
+        OpStatus::ParentFail,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn plan_create_groups_stations_into_waves_in_dependency_order()
+-> Result<(), Box<dyn std::error::Error>> {
+    let (dest, [a, b, c, d, e, f]) = dest_with_dependencies()?;
+
+    let train_plan = Train::default().plan(&dest, VisitOp::Create);
+
+    assert_eq!(VisitOp::Create, train_plan.visit_op());
+    assert_eq!(
+        &[vec![f, c], vec![e, b], vec![a], vec![d]],
+        train_plan.waves()
+    );
+    assert_eq!(
+        vec![f, c, e, b, a, d],
+        train_plan.station_order().collect::<Vec<_>>()
+    );
+    let d_dependencies = &train_plan.station_dependencies()[&d];
+    assert_eq!(2, d_dependencies.len());
+    assert!(d_dependencies.contains(&a));
+    assert!(d_dependencies.contains(&f));
+
+    Ok(())
+}
+
+#[test]
+fn plan_clean_groups_stations_into_waves_in_reverse_dependency_order()
+-> Result<(), Box<dyn std::error::Error>> {
+    let (dest, [a, b, c, d, e, f]) = dest_with_dependencies()?;
+
+    let train_plan = Train::default().plan(&dest, VisitOp::Clean);
+
+    assert_eq!(VisitOp::Clean, train_plan.visit_op());
+    assert_eq!(
+        &[vec![d], vec![a, f], vec![b, e], vec![c]],
+        train_plan.waves()
+    );
+    assert_eq!(vec![d], train_plan.station_dependencies()[&f]);
+
+    Ok(())
+}
+
+#[test]
+fn plan_critical_path_returns_chain_with_longest_duration() -> Result<(), Box<dyn std::error::Error>>
+{
+    let (dest, [a, b, c, d, e, f]) = dest_with_dependencies()?;
+    let station_durations = [(a, 1), (b, 3), (c, 1), (d, 1), (e, 2), (f, 4)]
+        .iter()
+        .map(|(station_rt_id, secs)| (*station_rt_id, Duration::from_secs(*secs)))
+        .collect::<IndexMap<StationRtId, Duration>>();
+
+    let critical_path = Train::default()
+        .plan(&dest, VisitOp::Create)
+        .critical_path(&station_durations);
+
+    assert_eq!(&[c, b, a, d], critical_path.station_rt_ids());
+    assert_eq!(Duration::from_secs(6), critical_path.duration());
+
+    Ok(())
+}
+
+#[test]
+fn eta_returns_critical_path_duration_of_estimated_durations()
+-> Result<(), Box<dyn std::error::Error>> {
+    let (dest, [a, b, c]) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let stations = dest_builder.add_stations([
+            StationSpec::mock("a")?
+                .with_estimated_duration(Duration::from_secs(2))
+                .build(),
+            StationSpec::mock("b")?
+                .with_estimated_duration(Duration::from_secs(3))
+                .build(),
+            StationSpec::mock("c")?
+                .with_estimated_duration(Duration::from_secs(4))
+                .build(),
+        ]);
+        dest_builder.add_edge(stations[0], stations[1])?;
+        (dest_builder.build()?, stations)
+    };
+    let train = Train::default();
+
+    let estimated_durations = train.estimated_durations(&dest, VisitOp::Create)?;
+    let eta = train.eta(&dest, VisitOp::Create)?;
+
+    assert_eq!(Some(&Duration::from_secs(2)), estimated_durations.get(&a));
+    assert_eq!(Some(&Duration::from_secs(3)), estimated_durations.get(&b));
+    assert_eq!(Some(&Duration::from_secs(4)), estimated_durations.get(&c));
+    assert_eq!(Duration::from_secs(5), eta);
+
+    Ok(())
+}
+
+#[test]
+fn plan_includes_reachability_with_unused_limited_resources()
+-> Result<(), Box<dyn std::error::Error>> {
+    let (dest, [_a, _b, c, _d, _e, f]) = dest_with_dependencies()?;
+    let resource_limits =
+        ResourceLimits::new().with_limit::<u32>(NonZeroUsize::new(1).ok_or("zero")?);
+
+    let train_plan = Train::default()
+        .with_resource_limits(resource_limits)
+        .plan(&dest, VisitOp::Create);
+
+    let reachability = train_plan.reachability();
+    assert!(reachability.is_connected());
+    assert_eq!(&[c, f], reachability.roots());
+    assert!(reachability.stations_orphaned().is_empty());
+    assert_eq!(&["u32"], reachability.resources_unused());
+
+    Ok(())
+}
+
+#[test]
+fn plan_does_not_run_station_fns() -> Result<(), Box<dyn std::error::Error>> {
+    let mut dest_builder = Destination::<()>::builder();
+    let station_a = dest_builder.add_station(
+        StationSpec::mock("a")?
+            .with_create_setup_fn(SetupFn::err(()))
+            .with_create_work_fn(StationFn::err((ResIds::new(), ())))
+            .build(),
+    );
+    let dest = dest_builder.build()?;
+
+    let train_plan = Train::default().plan(&dest, VisitOp::Create);
+
+    assert_eq!(&[vec![station_a]], train_plan.waves());
+    assert_eq!(
+        ProgressLimit::Unknown,
+        train_plan.station_progress_limits()[&station_a]
+    );
+    assert_eq!(
+        OpStatus::SetupQueued,
+        dest.station_progresses()[&station_a].borrow().op_status
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_create_visits_ready_stations_in_priority_order() -> Result<(), Box<dyn std::error::Error>>
+{
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let mock_invocations = MockInvocations::new();
+    let (mut dest, [a, b, c]) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let stations = dest_builder.add_stations([
+            StationSpec::mock("a")?
+                .with_mock_invocations(mock_invocations.clone())
+                .build(),
+            StationSpec::mock("b")?
+                .with_priority(5)
+                .with_mock_invocations(mock_invocations.clone())
+                .build(),
+            StationSpec::mock("c")?
+                .with_priority(1)
+                .with_mock_invocations(mock_invocations.clone())
+                .build(),
+        ]);
+        (dest_builder.build()?, stations)
+    };
+
+    let train_plan = Train::default().plan(&dest, VisitOp::Create);
+    rt.block_on(Train::default().reach_deterministic(&mut dest, VisitOp::Create))?;
+
+    assert_eq!(&[vec![b, c, a]], train_plan.waves());
+    assert_eq!(5, train_plan.station_priorities()[&b]);
+    let work_order = mock_invocations
+        .invocations()
+        .into_iter()
+        .filter(|invocation| invocation.op_fn == MockOpFn::CreateWork)
+        .map(|invocation| invocation.station_rt_id)
+        .collect::<Vec<_>>();
+    assert_eq!(vec![b, c, a], work_order);
+
+    Ok(())
+}
+
+#[test]
+fn reach_runs_resource_init_fns_in_order_before_station_setup()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let mut dest = {
+        let mut dest_builder = Destination::<()>::builder();
+        dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_create_setup_fn(SetupFn::new(|_station, train_resources| {
+                    let steps = train_resources.borrow_mut::<Vec<&'static str>>();
+                    let result = steps.map(|mut steps| {
+                        steps.push("setup");
+                        ProgressLimit::Steps(1)
+                    });
+                    async move { result.map_err(|_error| ()) }.boxed_local()
+                }))
+                .with_create_work_fn(StationFn::new(work_push_step))
+                .build(),
+        );
+        dest_builder.build()?
+    };
+    let train = Train::default()
+        .with_resource_init_fn(ResourceInitFn::new(
+            "first",
+            |train_resources: &mut TrainResources<()>| {
+                train_resources.insert(vec!["first"]);
+                async { Ok(()) }.boxed_local()
+            },
+        ))
+        .with_resource_init_fn(ResourceInitFn::new(
+            "second",
+            |train_resources: &mut TrainResources<()>| {
+                train_resources
+                    .borrow_mut::<Vec<&'static str>>()
+                    .push("second");
+                async { Ok(()) }.boxed_local()
+            },
+        ));
+
+    let train_report = rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+
+    assert_eq!(
+        &vec!["first", "second", "setup", "work"],
+        &*train_report
+            .train_resources()
+            .borrow::<Vec<&'static str>>()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reach_returns_err_without_visiting_stations_when_resource_init_fn_fails()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let mock_invocations = MockInvocations::new();
+    let mut dest = {
+        let mut dest_builder = Destination::<()>::builder();
+        dest_builder.add_station(
+            StationSpec::mock("a")?
+                .with_mock_invocations(mock_invocations.clone())
+                .build(),
+        );
+        dest_builder.build()?
+    };
+    let train = Train::default()
+        .with_resource_init_fn(ResourceInitFn::err("secrets", ()))
+        .with_resource_init_fn(ResourceInitFn::new(
+            "client",
+            |train_resources: &mut TrainResources<()>| {
+                train_resources.insert(0u8);
+                async { Ok(()) }.boxed_local()
+            },
+        ));
+
+    let result = rt.block_on(train.reach(&mut dest, VisitOp::Create));
+
+    assert!(matches!(
+        result,
+        Err(Error::ResourceInit { ref name, error: () }) if name == "secrets"
+    ));
+    assert!(mock_invocations.invocations().is_empty());
+
+    Ok(())
+}
+
+fn work_write_u32_fail<'f>(
+    _station: &'f mut StationMutRef<'_, ()>,
+    n: &'f mut u32,
+) -> LocalBoxFuture<'f, Result<ResIds, (ResIds, ())>> {
+    async move {
+        *n += 1;
+        tokio::task::yield_now().await;
+        Err((ResIds::new(), ()))
+    }
+    .boxed_local()
+}
+
+fn work_read_u32<'f>(
+    _station: &'f mut StationMutRef<'_, ()>,
+    _n: &'f u32,
+) -> LocalBoxFuture<'f, Result<ResIds, (ResIds, ())>> {
+    async move { Ok(ResIds::new()) }.boxed_local()
+}
+
+fn work_push_step<'f>(
+    _station: &'f mut StationMutRef<'_, ()>,
+    steps: &'f mut Vec<&'static str>,
+) -> LocalBoxFuture<'f, Result<ResIds, (ResIds, ())>> {
+    async move {
+        steps.push("work");
+        Ok(ResIds::new())
+    }
+    .boxed_local()
+}
+
+fn work_record_visit<'f>(
+    station: &'f mut StationMutRef<'_, ()>,
+    visit_events: &'f VisitEvents,
+) -> LocalBoxFuture<'f, Result<ResIds, (ResIds, ())>> {
+    async move {
+        let station_id = station.spec.id();
+        visit_events.push(format!("{station_id} begin"));
+        tokio::task::yield_now().await;
+        visit_events.push(format!("{station_id} end"));
+        Ok(ResIds::new())
+    }
+    .boxed_local()
+}
+
+fn work_barrier_wait<'f>(
+    _station: &'f mut StationMutRef<'_, ()>,
+    visit_barrier: &'f VisitBarrier,
+) -> LocalBoxFuture<'f, Result<ResIds, (ResIds, ())>> {
+    async move {
+        visit_barrier.0.wait().await;
+        Ok(ResIds::new())
+    }
+    .boxed_local()
+}
+
+fn work_with_warning<'f>(
+    station: &'f mut StationMutRef<'_, ()>,
+    station_warnings: &'f StationWarnings<()>,
+) -> LocalBoxFuture<'f, Result<ResIds, (ResIds, ())>> {
+    async move {
+        station_warnings.insert(station.rt_id, ()).await;
+        Ok(ResIds::new())
+    }
+    .boxed_local()
+}
+
+fn work_with_effect<'f>(
+    station: &'f mut StationMutRef<'_, ()>,
+    effect: &'f Effect,
+) -> LocalBoxFuture<'f, Result<ResIds, (ResIds, ())>> {
+    async move {
+        let station_rt_id = station.rt_id;
+        let state = &mut station.state;
+        effect
+            .run(station_rt_id, "upload app.zip", || async move {
+                state.insert(1u32);
+            })
+            .await;
+        Ok(ResIds::new())
+    }
+    .boxed_local()
+}
+
+fn work_panic<'f>(
+    _station: &'f mut StationMutRef<'_, ()>,
+) -> LocalBoxFuture<'f, Result<ResIds, (ResIds, ())>> {
+    async move { panic!("boom") }.boxed_local()
+}
+
+fn check_panic<'f>(
+    _station: &'f mut StationMutRef<'_, ()>,
+) -> LocalBoxFuture<'f, Result<CheckStatus, ()>> {
+    async move { panic!("boom") }.boxed_local()
+}
+
+fn verify_panic<'f>(_station: &'f mut StationMutRef<'_, ()>) -> LocalBoxFuture<'f, Result<(), ()>> {
+    async move { panic!("boom") }.boxed_local()
+}
+
+fn work_with_state<'f>(
+    station: &'f mut StationMutRef<'_, ()>,
+) -> LocalBoxFuture<'f, Result<ResIds, (ResIds, ())>> {
+    async move {
+        if let Some(count) = station.state.get_mut::<u32>() {
+            *count += 1;
+        }
+        Ok(ResIds::new())
+    }
+    .boxed_local()
+}
+
+fn work_with_output<'f>(
+    _station: &'f mut StationMutRef<'_, ()>,
+) -> LocalBoxFuture<'f, Result<(ResIds, u32), (ResIds, ())>> {
+    async move { Ok((ResIds::new(), 7u32)) }.boxed_local()
+}
+
+fn work_reading_output<'f>(
+    station: &'f mut StationMutRef<'_, ()>,
+    station_output: &'f StationOutput<u32>,
+) -> LocalBoxFuture<'f, Result<ResIds, (ResIds, ())>> {
+    async move {
+        if let Some(output) = *station_output.read().await {
+            station.state.insert(output);
+        }
+        Ok(ResIds::new())
+    }
+    .boxed_local()
+}
+
+fn work_read_u8_write_u16<'f>(
+    _station: &'f mut StationMutRef<'_, ()>,
+    a: &'f u8,
+    b: &'f mut u16,
+) -> LocalBoxFuture<'f, Result<ResIds, (ResIds, ())>> {
+    async move {
+        *b += u16::from(*a);
+        Ok(ResIds::new())
+    }
+    .boxed_local()
+}
+
+fn work_with_sub_steps<'f>(
+    station: &'f mut StationMutRef<'_, ()>,
+) -> LocalBoxFuture<'f, Result<ResIds, (ResIds, ())>> {
+    async move {
+        station.progress.sub_step_begin("download", ProgressLimit::Bytes(100));
+        station.progress.sub_step_inc("download", 100);
+        station.progress.sub_step_complete("download");
+        station.progress.sub_step_begin("extract", ProgressLimit::Items(2));
+        station.progress.sub_step_inc("extract", 1);
+        Ok(ResIds::new())
+    }
+    .boxed_local()
+}
+
+/// Returns a destination where station `a` fails, and station `b` depends on
+/// station `x`, which is independent of `a`.
+fn dest_with_failing_and_independent_stations()
+-> Result<(Destination<()>, [StationRtId; 3]), Box<dyn std::error::Error>> {
+    let mut dest_builder = Destination::<()>::builder();
+    let [station_a, station_x, station_b] = dest_builder.add_stations([
+        StationSpec::mock("a")?
+            .with_create_work_fn(StationFn::err((ResIds::new(), ())))
+            .build(),
+        StationSpec::mock("x")?
+            .with_create_work_fn(StationFn::ok(ResIds::new()))
+            .build(),
+        StationSpec::mock("b")?
+            .with_create_work_fn(StationFn::ok(ResIds::new()))
+            .build(),
+    ]);
+    dest_builder.add_edge(station_x, station_b)?;
+    let dest = dest_builder.build()?;
+
+    Ok((dest, [station_a, station_x, station_b]))
+}
+
+/// Returns a destination where station `b` depends on station `a`.
+fn dest_with_version(
+    workspace_dir: &Path,
+    mock_invocations: &MockInvocations,
+    version: u32,
+    migrate_fn: Option<StationMigrateFn<()>>,
+) -> Result<Destination<()>, Box<dyn std::error::Error>> {
+    let mut station_spec_builder = StationSpec::mock("a")?
+        .with_version(version)
+        .with_create_check_fn(StationFn::ok(CheckStatus::WorkNotRequired))
+        .with_mock_invocations(mock_invocations.clone());
+    if let Some(migrate_fn) = migrate_fn {
+        station_spec_builder = station_spec_builder.with_migrate_fn(migrate_fn);
+    }
+
+    let mut dest_builder = Destination::<()>::builder()
+        .with_workspace_spec(WorkspaceSpec::Path(workspace_dir.to_path_buf()));
+    dest_builder.add_station(station_spec_builder.build());
+    Ok(dest_builder.build()?)
+}
+
+fn dest_with_chain() -> Result<(Destination<()>, [StationRtId; 2]), Box<dyn std::error::Error>> {
+    let mut dest_builder = Destination::<()>::builder();
+    let [station_a, station_b] = dest_builder.add_stations([
+        StationSpec::mock("a")?
+            .with_create_work_fn(StationFn::ok(ResIds::new()))
+            .with_clean_fns(CleanFns::ok())
+            .build(),
+        StationSpec::mock("b")?
+            .with_create_work_fn(StationFn::ok(ResIds::new()))
+            .with_clean_fns(CleanFns::ok())
+            .build(),
+    ]);
+    dest_builder.add_edge(station_a, station_b)?;
+    let dest = dest_builder.build()?;
+
+    Ok((dest, [station_a, station_b]))
+}
+
+/// Returns a destination with the following dependencies:
+///
+/// ```text
+/// c - e - a - d
+///  \    /   /
+///    b -   /
+///         /
+/// f ------
+/// ```
+fn dest_with_dependencies()
+-> Result<(Destination<()>, [StationRtId; 6]), Box<dyn std::error::Error>> {
+    let mut dest_builder = Destination::<()>::builder();
+    let [a, b, c, d, e, f] = dest_builder.add_stations([
+        StationSpec::mock("a")?.build(),
+        StationSpec::mock("b")?.build(),
+        StationSpec::mock("c")?.build(),
+        StationSpec::mock("d")?.build(),
+        StationSpec::mock("e")?.build(),
+        StationSpec::mock("f")?.build(),
+    ]);
+    dest_builder.add_edges([(c, e), (c, b), (b, a), (e, a), (a, d), (f, d)])?;
+    let dest = dest_builder.build()?;
+
+    Ok((dest, [a, b, c, d, e, f]))
+}
+
+fn work_sleep<'f>(
+    _station: &'f mut StationMutRef<'_, ()>,
+) -> LocalBoxFuture<'f, Result<ResIds, (ResIds, ())>> {
+    async move {
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        Ok(ResIds::new())
+    }
+    .boxed_local()
+}
+
+fn station_errors_latest<E>(
+    station_errors: &StationErrors<E>,
+) -> Result<IndexMap<StationRtId, E>, TryLockError>
+where
+    E: Clone,
+{
+    let station_error_records = station_errors.try_read()?;
+    Ok(station_error_records
+        .iter()
+        .map(|record| (record.station_rt_id(), record.error().clone()))
+        .collect())
+}
+
+/// Holds back stations' work until a number of stations are visited at once.
+#[derive(Debug)]
+struct VisitBarrier(Barrier);
+
+/// Records the order in which stations begin and end their work.
+#[derive(Debug, Default)]
+struct VisitEvents(Mutex<Vec<String>>);
+
+impl VisitEvents {
+    fn push(&self, event: String) {
+        if let Ok(mut events) = self.0.lock() {
+            events.push(event);
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use choochoo_cfg_model::{
+    rt::{OpStatus, ResIds, VisitOp},
+    StationFn, StationSpec,
+};
+use choochoo_rt_logic::{Train, TrainMetrics};
+use choochoo_rt_model::Destination;
+use tokio::runtime;
+
+#[test]
+fn records_stations_by_status_and_durations() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let mut dest = {
+        let mut dest_builder = Destination::<()>::builder();
+        let [station_a, station_b] = dest_builder.add_stations([
+            StationSpec::mock("a")?.build(),
+            StationSpec::mock("b")?
+                .with_create_work_fn(StationFn::err((ResIds::new(), ())))
+                .build(),
+        ]);
+        dest_builder.add_edge(station_a, station_b)?;
+        dest_builder.build()?
+    };
+    let train_metrics = TrainMetrics::new();
+    let train = Train::default().with_metrics(train_metrics.clone());
+    rt.block_on(train.reach(&mut dest, VisitOp::Create))?;
+
+    let stations_by_status = train_metrics.stations_by_status();
+    assert_eq!(Some(&1), stations_by_status.get(&OpStatus::WorkSuccess));
+    assert_eq!(Some(&1), stations_by_status.get(&OpStatus::WorkFail));
+    assert_eq!(2, train_metrics.station_durations().len());
+
+    let rendered = train_metrics.render();
+    assert!(rendered.contains("choochoo_stations{status=\"WorkSuccess\"} 1\n"));
+    assert!(rendered.contains("choochoo_station_duration_seconds{station_id=\"a\"} "));
+
+    Ok(())
+}
@@ -1,4 +1,9 @@
 mod destination;
 mod destination_builder;
+mod destination_decl;
 mod destination_dir_calc;
+mod destination_validator;
+mod error;
+mod profile_store;
 mod station_specs;
+mod train_report_diff;
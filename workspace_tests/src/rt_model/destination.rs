@@ -1,5 +1,9 @@
-use choochoo_cfg_model::StationSpec;
-use choochoo_rt_model::Destination;
+use std::any::TypeId;
+
+use choochoo_cfg_model::{rt::StationRtId, StationSpec};
+use choochoo_rt_model::{Destination, Error};
+use futures::{future, stream::StreamExt};
+use tokio::{runtime, task};
 
 #[test]
 fn stations_iter_returns_stations_in_dependency_order() -> Result<(), Box<dyn std::error::Error>> {
@@ -31,3 +35,184 @@ fn stations_iter_returns_stations_in_dependency_order() -> Result<(), Box<dyn st
 
     Ok(())
 }
+
+#[test]
+fn stations_iter_rev_returns_stations_in_reverse_dependency_order()
+-> Result<(), Box<dyn std::error::Error>> {
+    let (dest, [a, b, c, d, e, f]) = dest_with_dependencies()?;
+
+    let stations = dest
+        .stations_iter_rev()
+        .map(|station| station.rt_id)
+        .collect::<Vec<_>>();
+    assert_eq!([d, a, b, e, c, f], stations.as_slice());
+
+    Ok(())
+}
+
+#[test]
+fn clean_order_returns_stations_in_reverse_dependency_order()
+-> Result<(), Box<dyn std::error::Error>> {
+    let (dest, [a, b, c, d, e, f]) = dest_with_dependencies()?;
+
+    assert_eq!(vec![d, a, b, e, c, f], dest.clean_order());
+
+    Ok(())
+}
+
+#[test]
+fn clean_order_does_not_borrow_station_progresses() -> Result<(), Box<dyn std::error::Error>> {
+    let (dest, [a, b, c, d, e, f]) = dest_with_dependencies()?;
+
+    let _station_progress = dest.station_progresses()[&a].borrow_mut();
+
+    assert_eq!(vec![d, a, b, e, c, f], dest.clean_order());
+
+    Ok(())
+}
+
+#[test]
+fn stations_mut_skips_stations_that_are_already_accessed() -> Result<(), Box<dyn std::error::Error>>
+{
+    let (dest, [a, b, c, d, e, f]) = dest_with_dependencies()?;
+
+    let _station_progress = dest.station_progresses()[&a].borrow();
+
+    let stations = dest
+        .stations_mut()
+        .map(|station| station.rt_id)
+        .collect::<Vec<_>>();
+    assert_eq!([b, c, d, e, f], stations.as_slice());
+
+    Ok(())
+}
+
+#[test]
+fn try_stations_mut_returns_borrow_conflict_for_stations_that_are_already_accessed()
+-> Result<(), Box<dyn std::error::Error>> {
+    let (dest, [a, b, c, d, e, f]) = dest_with_dependencies()?;
+
+    let _station_progress = dest.station_progresses()[&a].borrow();
+
+    let stations = dest.try_stations_mut().collect::<Vec<_>>();
+    assert_eq!(6, stations.len());
+    assert!(
+        matches!(
+            &stations[0],
+            Err(Error::StationBorrowConflict { station_id, .. }) if &**station_id == "a"
+        ),
+        "Expected station `a` to be a borrow conflict, but was: {:?}",
+        stations[0]
+    );
+    let station_rt_ids = stations[1..]
+        .iter()
+        .filter_map(|station| station.as_ref().ok())
+        .map(|station| station.rt_id)
+        .collect::<Vec<_>>();
+    assert_eq!([b, c, d, e, f], station_rt_ids.as_slice());
+
+    Ok(())
+}
+
+#[test]
+fn stations_mut_stream_waits_for_stations_that_are_already_accessed()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (dest, [a, b, c, d, e, f]) = dest_with_dependencies()?;
+
+    let mut stations = rt.block_on(async {
+        let station_progress = dest.station_progresses()[&a].borrow();
+        let station_release = async move {
+            task::yield_now().await;
+            drop(station_progress);
+        };
+        let stations = dest
+            .stations_mut_stream()
+            .map(|station| station.rt_id)
+            .collect::<Vec<_>>();
+        future::join(stations, station_release).await.0
+    });
+    stations.sort();
+
+    let mut stations_expected = vec![a, b, c, d, e, f];
+    stations_expected.sort();
+    assert_eq!(stations_expected, stations);
+
+    Ok(())
+}
+
+#[test]
+fn try_stations_mut_stream_returns_borrow_conflict_for_stations_that_are_already_accessed()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let (dest, [_a, _b, c, _d, _e, f]) = dest_with_dependencies()?;
+
+    let _station_progress = dest.station_progresses()[&c].borrow();
+
+    let stations = rt.block_on(
+        dest.try_stations_mut_stream()
+            .map(|station| station.map(|station| station.rt_id))
+            .collect::<Vec<_>>(),
+    );
+    assert!(
+        stations.iter().any(|station| matches!(
+            station,
+            Err(Error::StationBorrowConflict { station_id, .. }) if &**station_id == "c"
+        )),
+        "Expected station `c` to be a borrow conflict, but stations were: {:?}",
+        stations
+    );
+    assert!(
+        stations
+            .iter()
+            .any(|station| matches!(station, Ok(station_rt_id) if *station_rt_id == f))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reachability_returns_islands_and_orphaned_stations() -> Result<(), Box<dyn std::error::Error>> {
+    let (dest, [a, b, c, d]) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let [a, b, c, d] = dest_builder.add_stations([
+            StationSpec::mock("a")?.build(),
+            StationSpec::mock("b")?.build(),
+            StationSpec::mock("c")?.build(),
+            StationSpec::mock("d")?.build(),
+        ]);
+        dest_builder.add_edges([(a, b), (c, b)])?;
+        (dest_builder.build()?, [a, b, c, d])
+    };
+
+    let reachability = dest.reachability([(TypeId::of::<u32>(), "u32")]);
+
+    assert!(!reachability.is_connected());
+    assert_eq!(&[vec![a, b, c], vec![d]], reachability.islands());
+    assert_eq!(&[a, c], reachability.roots());
+    assert_eq!(&[d], reachability.stations_orphaned());
+    assert_eq!(&["u32"], reachability.resources_unused());
+
+    Ok(())
+}
+
+fn dest_with_dependencies()
+-> Result<(Destination<()>, [StationRtId; 6]), Box<dyn std::error::Error>> {
+    let mut dest_builder = Destination::<()>::builder();
+    let [a, b, c, d, e, f] = dest_builder.add_stations([
+        StationSpec::mock("a")?.build(),
+        StationSpec::mock("b")?.build(),
+        StationSpec::mock("c")?.build(),
+        StationSpec::mock("d")?.build(),
+        StationSpec::mock("e")?.build(),
+        StationSpec::mock("f")?.build(),
+    ]);
+
+    // c - e - a - d
+    //  \    /   /
+    //    b -   /
+    //         /
+    // f ------
+    dest_builder.add_edges([(c, e), (c, b), (b, a), (e, a), (a, d), (f, d)])?;
+    Ok((dest_builder.build()?, [a, b, c, d, e, f]))
+}
@@ -1,5 +1,13 @@
+use choochoo_cfg_model::{
+    daggy::Walker,
+    rt::{ProgressLimit, ResIds, StationMutRef},
+    CleanFns, CreateFns, SetupFn, StationFn, StationOp, StationSpec, StationSpecTemplate,
+};
 use choochoo_resource::Profile;
-use choochoo_rt_model::Destination;
+use choochoo_rt_model::{
+    error::Error, Destination, ProfileDirSpec, ResourceConflictPolicy, WorkspaceSpec,
+};
+use futures::future::{FutureExt, LocalBoxFuture};
 
 #[test]
 fn profile_defaults_to_default() -> Result<(), Box<dyn std::error::Error>> {
@@ -20,3 +28,353 @@ fn profile_uses_custom_specified() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn profile_dir_spec_path_is_used_for_dirs() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let data_dir = tempdir.path().join("data");
+    let mut dest_builder = Destination::<()>::builder()
+        .with_workspace_spec(WorkspaceSpec::Path(tempdir.path().join("workspace")))
+        .with_profile_dir_spec(ProfileDirSpec::Path(data_dir.clone()))
+        .with_profile(Profile::new("custom")?);
+    let station_a = dest_builder.add_station(StationSpec::mock("a")?.build());
+    let dest = dest_builder.build()?;
+
+    let dirs = dest.dirs();
+    assert_eq!(data_dir.join("custom"), &**dirs.profile_dir());
+    assert_eq!(data_dir.join(".history"), &**dirs.history_dir());
+    assert_eq!(
+        data_dir.join("custom").join("a"),
+        &*dirs.station_dirs()[&station_a]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn add_station_template_adds_station_per_param_with_edges() -> Result<(), Box<dyn std::error::Error>>
+{
+    let template = StationSpecTemplate::<&'static str, ()>::new(
+        "upload",
+        StationOp::new(
+            CreateFns::new(
+                SetupFn::ok(ProgressLimit::Unknown),
+                StationFn::ok(ResIds::new()),
+            ),
+            None,
+        ),
+        |builder, file_name| builder.with_description(format!("Uploads {file_name}.")),
+    );
+    let (dest, parent, child, template_stations) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let [parent, child] = dest_builder.add_stations([
+            StationSpec::mock("parent")?.build(),
+            StationSpec::mock("child")?.build(),
+        ]);
+        let template_stations = dest_builder.add_station_template(
+            &template,
+            &["a.zip", "b.zip"],
+            &[parent],
+            &[child],
+        )?;
+        (dest_builder.build()?, parent, child, template_stations)
+    };
+
+    let station_specs = dest.station_specs();
+    assert_eq!(2, template_stations.len());
+    assert_eq!("upload_0", &**station_specs[template_stations[0]].id());
+    assert_eq!("upload_1", &**station_specs[template_stations[1]].id());
+    assert_eq!(
+        "Uploads b.zip.",
+        station_specs[template_stations[1]].description()
+    );
+    template_stations.iter().for_each(|station_rt_id| {
+        let parents = station_specs
+            .parents(*station_rt_id)
+            .iter(station_specs)
+            .map(|(_, parent_rt_id)| parent_rt_id)
+            .collect::<Vec<_>>();
+        let children = station_specs
+            .children(*station_rt_id)
+            .iter(station_specs)
+            .map(|(_, child_rt_id)| child_rt_id)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![parent], parents);
+        assert_eq!(vec![child], children);
+    });
+
+    Ok(())
+}
+
+#[test]
+fn merge_adds_stations_and_edges_with_remapped_rt_ids() -> Result<(), Box<dyn std::error::Error>> {
+    let mut dest_builder = Destination::<()>::builder();
+    let [app_build] = dest_builder.add_stations([StationSpec::mock("app/build")?.build()]);
+    let (other_builder, db_create, db_migrate) = {
+        let mut other_builder = Destination::<()>::builder();
+        let [db_create, db_migrate] = other_builder.add_stations([
+            StationSpec::mock("db/create")?.build(),
+            StationSpec::mock("db/migrate")?.build(),
+        ]);
+        other_builder.add_edge(db_create, db_migrate)?;
+        (other_builder, db_create, db_migrate)
+    };
+
+    let station_rt_id_map = dest_builder.merge(other_builder)?;
+    let db_create = station_rt_id_map[&db_create];
+    let db_migrate = station_rt_id_map[&db_migrate];
+    dest_builder.add_edge(db_migrate, app_build)?;
+    let dest = dest_builder.build()?;
+
+    let station_specs = dest.station_specs();
+    assert_eq!("db/create", &**station_specs[db_create].id());
+    assert_eq!("db/migrate", &**station_specs[db_migrate].id());
+    let db_migrate_parents = station_specs
+        .parents(db_migrate)
+        .iter(station_specs)
+        .map(|(_, parent_rt_id)| parent_rt_id)
+        .collect::<Vec<_>>();
+    let db_migrate_children = station_specs
+        .children(db_migrate)
+        .iter(station_specs)
+        .map(|(_, child_rt_id)| child_rt_id)
+        .collect::<Vec<_>>();
+    assert_eq!(vec![db_create], db_migrate_parents);
+    assert_eq!(vec![app_build], db_migrate_children);
+
+    Ok(())
+}
+
+#[test]
+fn merge_remaps_checkpoint_groups_and_keeps_op_replacements()
+-> Result<(), Box<dyn std::error::Error>> {
+    let mut dest_builder = Destination::<()>::builder();
+    let [app_build] = dest_builder.add_stations([StationSpec::mock("app/build")?.build()]);
+    let (other_builder, db_create, db_migrate) = {
+        let mut other_builder = Destination::<()>::builder();
+        let [db_create, db_migrate] = other_builder.add_stations([
+            StationSpec::mock("db/create")?.build(),
+            StationSpec::mock("db/migrate")?.build(),
+        ]);
+        other_builder.add_checkpoint_group("db", [db_create, db_migrate]);
+        other_builder.replace_op(
+            "db/migrate",
+            StationOp::new(
+                CreateFns::new(
+                    SetupFn::ok(ProgressLimit::Steps(1)),
+                    StationFn::ok(ResIds::new()),
+                ),
+                Some(CleanFns::new(
+                    SetupFn::ok(ProgressLimit::Steps(1)),
+                    StationFn::ok(()),
+                )),
+            ),
+        )?;
+        (other_builder, db_create, db_migrate)
+    };
+
+    let station_rt_id_map = dest_builder.merge(other_builder)?;
+    let db_create = station_rt_id_map[&db_create];
+    let db_migrate = station_rt_id_map[&db_migrate];
+    let dest = dest_builder.build()?;
+
+    // `app/build` keeps its runtime ID, so the merged stations are shifted.
+    assert_ne!(app_build, db_create);
+    assert_ne!(app_build, db_migrate);
+    let checkpoint_groups = dest.checkpoint_groups();
+    assert_eq!(1, checkpoint_groups.len());
+    assert_eq!("db", checkpoint_groups[0].name());
+    assert_eq!(
+        &[db_create, db_migrate],
+        checkpoint_groups[0].station_rt_ids()
+    );
+    let station_specs = dest.station_specs();
+    assert!(station_specs[db_create].station_op().clean_fns().is_none());
+    assert!(station_specs[db_migrate].station_op().clean_fns().is_some());
+
+    Ok(())
+}
+
+#[test]
+fn merge_returns_err_with_colliding_station_ids() -> Result<(), Box<dyn std::error::Error>> {
+    let mut dest_builder = Destination::<()>::builder();
+    dest_builder.add_stations([
+        StationSpec::mock("create")?.build(),
+        StationSpec::mock("upload")?.build(),
+    ]);
+    let mut other_builder = Destination::<()>::builder();
+    other_builder.add_stations([
+        StationSpec::mock("upload")?.build(),
+        StationSpec::mock("download")?.build(),
+    ]);
+
+    let error = dest_builder
+        .merge(other_builder)
+        .expect_err("Expected station ID collision.");
+
+    if let Error::DestinationMergeStationIdCollision { station_ids } = &error {
+        assert_eq!(
+            vec!["upload"],
+            station_ids.iter().map(|id| &***id).collect::<Vec<_>>()
+        );
+    } else {
+        panic!("Expected `DestinationMergeStationIdCollision`, but was {error:?}");
+    }
+    assert_eq!("E036", error.code());
+    assert_eq!(2, dest_builder.build()?.station_specs().node_count());
+
+    Ok(())
+}
+
+#[test]
+fn build_returns_err_when_replaced_op_station_not_found() -> Result<(), Box<dyn std::error::Error>>
+{
+    let mut dest_builder = Destination::<()>::builder();
+    dest_builder.add_station(StationSpec::mock("db/create")?.build());
+    dest_builder.replace_op(
+        "db/migrate",
+        StationOp::new(
+            CreateFns::new(
+                SetupFn::ok(ProgressLimit::Steps(1)),
+                StationFn::ok(ResIds::new()),
+            ),
+            None,
+        ),
+    )?;
+
+    let error = dest_builder
+        .build()
+        .expect_err("Expected replaced op station to not be found.");
+
+    if let Error::StationOpReplaceNotFound { station_id } = &error {
+        assert_eq!("db/migrate", &**station_id);
+    } else {
+        panic!("Expected `StationOpReplaceNotFound`, but was {error:?}");
+    }
+    assert_eq!("E038", error.code());
+
+    Ok(())
+}
+
+#[test]
+fn build_does_not_add_edges_between_stations_with_conflicting_borrows()
+-> Result<(), Box<dyn std::error::Error>> {
+    let mut dest_builder = Destination::<()>::builder();
+    let [station_a, station_b] = dest_builder.add_stations([
+        StationSpec::mock("a")?
+            .with_create_work_fn(StationFn::new(work_fn_write))
+            .build(),
+        StationSpec::mock("b")?
+            .with_create_work_fn(StationFn::new(work_fn_read))
+            .build(),
+    ]);
+    let dest = dest_builder.build()?;
+
+    let station_specs = dest.station_specs();
+    assert_eq!(
+        ResourceConflictPolicy::Serialize,
+        dest.resource_conflict_policy()
+    );
+    assert!(
+        station_specs
+            .parents(station_a)
+            .walk_next(station_specs)
+            .is_none()
+    );
+    assert!(
+        station_specs
+            .parents(station_b)
+            .walk_next(station_specs)
+            .is_none()
+    );
+    assert_eq!(0, station_specs.edge_count());
+
+    Ok(())
+}
+
+#[test]
+fn build_returns_err_for_conflicting_borrows_with_error_policy()
+-> Result<(), Box<dyn std::error::Error>> {
+    let mut dest_builder =
+        Destination::<()>::builder().with_resource_conflict_policy(ResourceConflictPolicy::Error);
+    let [station_a, station_b, station_c] = dest_builder.add_stations([
+        StationSpec::mock("a")?
+            .with_create_work_fn(StationFn::new(work_fn_write))
+            .build(),
+        StationSpec::mock("b")?
+            .with_create_work_fn(StationFn::new(work_fn_read))
+            .build(),
+        StationSpec::mock("c")?
+            .with_create_work_fn(StationFn::new(work_fn_write))
+            .build(),
+    ]);
+    dest_builder.add_edges([(station_a, station_b), (station_b, station_c)])?;
+    dest_builder.add_station(
+        StationSpec::mock("d")?
+            .with_create_work_fn(StationFn::new(work_fn_read))
+            .build(),
+    );
+
+    let error = dest_builder
+        .build()
+        .expect_err("Expected stations `a` and `d` to conflict.");
+
+    if let Error::StationResourceConflict {
+        station_a,
+        station_b,
+    } = &error
+    {
+        assert_eq!("a", &**station_a);
+        assert_eq!("d", &**station_b);
+    } else {
+        panic!("Expected `StationResourceConflict`, but was {error:?}");
+    }
+    assert_eq!("E039", error.code());
+
+    Ok(())
+}
+
+#[test]
+fn build_returns_ok_for_create_and_clean_borrows_with_error_policy()
+-> Result<(), Box<dyn std::error::Error>> {
+    let mut dest_builder =
+        Destination::<()>::builder().with_resource_conflict_policy(ResourceConflictPolicy::Error);
+    dest_builder.add_stations([
+        StationSpec::mock("a")?
+            .with_create_work_fn(StationFn::new(work_fn_write))
+            .build(),
+        StationSpec::mock("b")?
+            .with_clean_fns(CleanFns::new(
+                SetupFn::ok(ProgressLimit::Steps(1)),
+                StationFn::new(clean_fn_write),
+            ))
+            .build(),
+    ]);
+
+    let dest = dest_builder.build()?;
+
+    assert_eq!(ResourceConflictPolicy::Error, dest.resource_conflict_policy());
+
+    Ok(())
+}
+
+fn work_fn_write<'f>(
+    _station: &'f mut StationMutRef<'_, ()>,
+    _n: &'f mut u32,
+) -> LocalBoxFuture<'f, Result<ResIds, (ResIds, ())>> {
+    async { Ok(ResIds::new()) }.boxed_local()
+}
+
+fn work_fn_read<'f>(
+    _station: &'f mut StationMutRef<'_, ()>,
+    _n: &'f u32,
+) -> LocalBoxFuture<'f, Result<ResIds, (ResIds, ())>> {
+    async { Ok(ResIds::new()) }.boxed_local()
+}
+
+fn clean_fn_write<'f>(
+    _station: &'f mut StationMutRef<'_, ()>,
+    _n: &'f mut u32,
+) -> LocalBoxFuture<'f, Result<(), ()>> {
+    async { Ok(()) }.boxed_local()
+}
@@ -0,0 +1,124 @@
+use choochoo_cfg_model::{daggy::Walker, StationId, StationSpec};
+use choochoo_rt_model::{DestinationDecl, Error, StationOpRegistry};
+
+const DESTINATION_YAML: &str = r#"
+stations:
+  - id: a
+    name: Station A
+    description: Downloads the application.
+    tags: [fetch]
+    op: mock_op
+  - id: b
+    op: mock_op
+edges:
+  - from: a
+    to: b
+"#;
+
+#[test]
+fn into_builder_builds_declared_stations_and_edges() -> Result<(), Box<dyn std::error::Error>> {
+    let station_op_registry = station_op_registry()?;
+    let destination_decl = serde_yaml::from_str::<DestinationDecl>(DESTINATION_YAML)?;
+
+    let dest = destination_decl
+        .into_builder(&station_op_registry)?
+        .build()?;
+
+    let a = dest.station_id_to_rt_id()[&StationId::new("a")?];
+    let b = dest.station_id_to_rt_id()[&StationId::new("b")?];
+    let station_specs = dest.station_specs();
+    let station_a = &station_specs[a];
+    let station_b = &station_specs[b];
+    assert_eq!("Station A", station_a.name());
+    assert_eq!("Downloads the application.", station_a.description());
+    assert!(station_a.has_tag("fetch"));
+    assert_eq!("b", station_b.name());
+    assert!(station_b.tags().is_empty());
+
+    let b_parents = station_specs
+        .parents(b)
+        .iter(station_specs)
+        .map(|(_, parent_rt_id)| parent_rt_id)
+        .collect::<Vec<_>>();
+    assert_eq!(vec![a], b_parents);
+
+    Ok(())
+}
+
+#[test]
+fn into_builder_returns_error_when_op_not_registered() -> Result<(), Box<dyn std::error::Error>> {
+    let station_op_registry = StationOpRegistry::<()>::new();
+    let destination_decl = serde_yaml::from_str::<DestinationDecl>(DESTINATION_YAML)?;
+
+    let error = destination_decl
+        .into_builder(&station_op_registry)
+        .expect_err("Expected `DeclStationOpNotFound` error.");
+
+    assert!(matches!(
+        error,
+        Error::DeclStationOpNotFound { station_id, op_name }
+        if &*station_id == "a" && op_name == "mock_op"
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn into_builder_returns_error_when_edge_station_not_declared()
+-> Result<(), Box<dyn std::error::Error>> {
+    let station_op_registry = station_op_registry()?;
+    let destination_decl = serde_yaml::from_str::<DestinationDecl>(
+        r#"
+stations:
+  - id: a
+    op: mock_op
+edges:
+  - from: a
+    to: c
+"#,
+    )?;
+
+    let error = destination_decl
+        .into_builder(&station_op_registry)
+        .expect_err("Expected `DeclEdgeStationNotFound` error.");
+
+    assert!(matches!(
+        error,
+        Error::DeclEdgeStationNotFound { station_id }
+        if &*station_id == "c"
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn deserialize_returns_error_when_station_id_invalid() {
+    let result = serde_yaml::from_str::<DestinationDecl>(
+        r#"
+stations:
+  - id: a-b
+    op: mock_op
+"#,
+    );
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn serialize_round_trips() -> Result<(), Box<dyn std::error::Error>> {
+    let destination_decl = serde_yaml::from_str::<DestinationDecl>(DESTINATION_YAML)?;
+
+    let serialized = serde_yaml::to_string(&destination_decl)?;
+    let deserialized = serde_yaml::from_str::<DestinationDecl>(&serialized)?;
+
+    assert_eq!(destination_decl, deserialized);
+    Ok(())
+}
+
+fn station_op_registry() -> Result<StationOpRegistry<()>, Box<dyn std::error::Error>> {
+    let station_op = StationSpec::<()>::mock("mock")?
+        .build()
+        .station_op()
+        .clone();
+    Ok(StationOpRegistry::new().with_station_op("mock_op", station_op))
+}
@@ -5,7 +5,7 @@ use choochoo_cfg_model::{
     StationSpec, StationSpecs,
 };
 use choochoo_resource::Profile;
-use choochoo_rt_model::{DestinationDirCalc, DestinationDirs, WorkspaceSpec};
+use choochoo_rt_model::{DestinationDirCalc, DestinationDirs, ProfileDirSpec, WorkspaceSpec};
 
 #[test]
 fn calculates_workspace_dir_from_working_directory() -> Result<(), Box<dyn std::error::Error>> {
@@ -226,3 +226,112 @@ fn calculates_station_dirs_from_station_id_and_workspace_dir()
 
     Ok(())
 }
+
+#[test]
+fn calculates_profile_dirs_under_profile_dir_spec_path() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace_spec = WorkspaceSpec::FirstDirWithFile(&Path::new("Cargo.lock"));
+    let profile_dir_spec = ProfileDirSpec::Path(tempdir.path().join("data"));
+    let profile = Profile::new("profile")?;
+    let station_specs = StationSpecs::<()>::new(FnGraph::new());
+
+    let DestinationDirs {
+        workspace_dir,
+        history_dir,
+        profile_history_dir,
+        profile_dir,
+        cache_dir,
+        ..
+    } = DestinationDirCalc::calc_with_profile_dir_spec(
+        &workspace_spec,
+        &profile_dir_spec,
+        &profile,
+        &station_specs,
+    )?;
+
+    let data_dir = tempdir.path().join("data");
+    assert!(workspace_dir.ends_with("choochoo"));
+    assert_eq!(data_dir.join(".history"), &*history_dir);
+    assert_eq!(
+        data_dir.join(".history").join("profile"),
+        &*profile_history_dir
+    );
+    assert_eq!(data_dir.join("profile"), &*profile_dir);
+    assert_eq!(data_dir.join(".cache"), &*cache_dir);
+
+    Ok(())
+}
+
+#[test]
+fn calculates_station_dirs_under_absolute_profile_dir_spec_path()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace_spec = WorkspaceSpec::FirstDirWithFile(&Path::new("Cargo.lock"));
+    let profile_dir_spec = ProfileDirSpec::Path(tempdir.path().join("data"));
+    let profile = Profile::new("profile")?;
+    let station_specs = {
+        let mut station_specs_builder = FnGraphBuilder::new();
+        station_specs_builder.add_fns([
+            StationSpec::mock("station_a")?.build(),
+            StationSpec::mock("station_b")?.build(),
+        ]);
+        StationSpecs::<()>::new(station_specs_builder.build())
+    };
+
+    let DestinationDirs {
+        workspace_dir,
+        station_dirs,
+        ..
+    } = DestinationDirCalc::calc_with_profile_dir_spec(
+        &workspace_spec,
+        &profile_dir_spec,
+        &profile,
+        &station_specs,
+    )?;
+
+    let profile_dir = tempdir.path().join("data").join("profile");
+    assert_eq!(
+        profile_dir.join("station_a"),
+        &*station_dirs[&FnId::new(0)]
+    );
+    assert_eq!(
+        profile_dir.join("station_b"),
+        &*station_dirs[&FnId::new(1)]
+    );
+    assert!(
+        station_dirs
+            .values()
+            .all(|station_dir| !station_dir.starts_with(&*workspace_dir))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn calculates_profile_dirs_under_relative_profile_dir_spec_path_in_workspace()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace_spec = WorkspaceSpec::Path(tempdir.path().to_path_buf());
+    let profile_dir_spec = ProfileDirSpec::Path(Path::new("data").to_path_buf());
+    let profile = Profile::new("profile")?;
+    let station_specs = StationSpecs::<()>::new(FnGraph::new());
+
+    let DestinationDirs {
+        history_dir,
+        profile_dir,
+        cache_dir,
+        ..
+    } = DestinationDirCalc::calc_with_profile_dir_spec(
+        &workspace_spec,
+        &profile_dir_spec,
+        &profile,
+        &station_specs,
+    )?;
+
+    let data_dir = tempdir.path().join("data");
+    assert_eq!(data_dir.join(".history"), &*history_dir);
+    assert_eq!(data_dir.join("profile"), &*profile_dir);
+    assert_eq!(data_dir.join(".cache"), &*cache_dir);
+
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use choochoo_cfg_model::{
+    rt::{CheckStatus, ProgressLimit, ResIds},
+    srcerr::codespan_reporting::diagnostic::Severity,
+    CleanFns, CreateFns, SetupFn, StationFn, StationOp, StationSpec,
+};
+use choochoo_rt_model::{Destination, DestinationLint, DestinationValidator};
+
+#[test]
+fn returns_no_lints_for_well_formed_destination() -> Result<(), Box<dyn std::error::Error>> {
+    let dest = {
+        let mut dest_builder = Destination::<()>::builder();
+        let [a, b] = dest_builder.add_stations([
+            StationSpec::builder("a", station_op_complete())?.build(),
+            StationSpec::builder("b", station_op_complete())?.build(),
+        ]);
+        dest_builder.add_edge(a, b)?;
+        dest_builder.build()?
+    };
+
+    let lints = DestinationValidator::validate(&dest);
+
+    assert!(lints.is_empty(), "Expected no lints, got: {lints:?}");
+    Ok(())
+}
+
+#[test]
+fn clean_fns_missing_lint_does_not_assume_resources_are_created()
+-> Result<(), Box<dyn std::error::Error>> {
+    let dest = {
+        let mut dest_builder = Destination::<()>::builder();
+        dest_builder.add_station(StationSpec::mock("a")?.with_name("A").build());
+        dest_builder.build()?
+    };
+
+    let lints = DestinationValidator::validate(&dest);
+    let lint = lints
+        .iter()
+        .find(|lint| matches!(lint, DestinationLint::CleanFnsMissing { .. }))
+        .expect("Expected `CleanFnsMissing` lint.");
+
+    assert!(
+        lint.to_string()
+            .starts_with("Station `a: A` has no clean functions, so resources created by its work")
+    );
+    assert!(lint.help().starts_with("If the station creates resources"));
+    Ok(())
+}
+
+#[test]
+fn returns_lints_for_missing_check_fn_and_clean_fns() -> Result<(), Box<dyn std::error::Error>> {
+    let (dest, a) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let a = dest_builder.add_station(StationSpec::mock("a")?.with_name("A").build());
+        (dest_builder.build()?, a)
+    };
+
+    let lints = DestinationValidator::validate(&dest);
+    let location = dest.station_specs()[a].location();
+
+    assert_eq!(
+        vec![
+            DestinationLint::CreateCheckFnMissing {
+                id: "a".parse()?,
+                name: String::from("A"),
+                location,
+            },
+            DestinationLint::CleanFnsMissing {
+                id: "a".parse()?,
+                name: String::from("A"),
+                location,
+            },
+        ],
+        lints
+    );
+    assert!(
+        lints
+            .iter()
+            .all(|lint| lint.severity() == Severity::Warning)
+    );
+    Ok(())
+}
+
+#[test]
+fn returns_lints_for_duplicate_names_ids_and_unreachable_stations()
+-> Result<(), Box<dyn std::error::Error>> {
+    let (dest, [c, d]) = {
+        let mut dest_builder = Destination::<()>::builder();
+        let [a, b, c, d] = dest_builder.add_stations([
+            StationSpec::builder("a", station_op_complete())?
+                .with_name("Same")
+                .build(),
+            StationSpec::builder("b", station_op_complete())?
+                .with_name("Same")
+                .build(),
+            StationSpec::builder("c", station_op_complete())?.build(),
+            StationSpec::builder("c", station_op_complete())?.build(),
+        ]);
+        dest_builder.add_edge(a, b)?;
+        (dest_builder.build()?, [c, d])
+    };
+
+    let lints = DestinationValidator::validate(&dest);
+
+    assert_eq!(
+        vec![
+            DestinationLint::StationUnreachable {
+                id: "c".parse()?,
+                name: String::from("c"),
+                location: dest.station_specs()[c].location(),
+            },
+            DestinationLint::StationUnreachable {
+                id: "c".parse()?,
+                name: String::from("c"),
+                location: dest.station_specs()[d].location(),
+            },
+            DestinationLint::StationNameDuplicate {
+                name: String::from("Same"),
+                ids: vec!["a".parse()?, "b".parse()?],
+            },
+            DestinationLint::StationNameDuplicate {
+                name: String::from("c"),
+                ids: vec!["c".parse()?, "c".parse()?],
+            },
+            DestinationLint::StationIdDuplicate { id: "c".parse()? },
+        ],
+        lints
+    );
+    Ok(())
+}
+
+fn station_op_complete() -> StationOp<()> {
+    let create_fns = CreateFns::new(
+        SetupFn::ok(ProgressLimit::Steps(1)),
+        StationFn::ok(ResIds::new()),
+    )
+    .with_check_fn(StationFn::ok(CheckStatus::WorkNotRequired));
+    let clean_fns = CleanFns::ok();
+
+    StationOp::new(create_fns, Some(clean_fns))
+}
@@ -0,0 +1,162 @@
+use std::{borrow::Cow, io, panic::Location, path::PathBuf};
+
+use choochoo_cfg_model::{
+    rt::OpStatus,
+    srcerr::{codespan::Span, codespan_reporting::diagnostic::Severity},
+    StationId,
+};
+use choochoo_resource::{Files, WorkspaceDir, WorkspacePathError};
+use choochoo_rt_model::error::{
+    AnyhowStationError, AsDiagnostic, Error, SimpleStationError, StationErrorContext,
+    StationSpecError, WorkspacePathSourceError,
+};
+
+#[test]
+fn as_diagnostic_includes_code_message_and_causes() {
+    let error = Error::<()>::WorkspaceDirCreate {
+        workspace_dir: WorkspaceDir::new(PathBuf::from("workspace")),
+        error: io::Error::new(io::ErrorKind::PermissionDenied, "permission denied"),
+    };
+
+    let diagnostic = error.as_diagnostic(&Files::new());
+
+    assert_eq!(28, error.code_number());
+    assert_eq!(Some(String::from("E028")), diagnostic.code);
+    assert_eq!(
+        "Failed to create workspace directory: `workspace`.",
+        diagnostic.message
+    );
+    assert_eq!(
+        vec![String::from("caused by: permission denied")],
+        diagnostic.notes
+    );
+}
+
+#[test]
+fn stations_stalled_lists_stations_and_op_statuses() -> Result<(), Box<dyn std::error::Error>> {
+    let error = Error::<()>::StationsStalled {
+        stations: vec![
+            (StationId::new("a")?, Some(OpStatus::ParentPending)),
+            (StationId::new("b")?, None),
+        ],
+    };
+
+    let diagnostic = error.as_diagnostic(&Files::new());
+
+    assert_eq!(Some(String::from("E042")), diagnostic.code);
+    assert_eq!(
+        "No station is being visited and none can be queued, but stations remain: \
+        `a` (ParentPending), `b` (progress already accessed).",
+        diagnostic.message
+    );
+
+    Ok(())
+}
+
+#[test]
+fn workspace_path_source_error_diagnostic_labels_path() {
+    let mut files = Files::new();
+    let file_id = files.add("app.toml", Cow::Borrowed("dir = \"../outside\"\n"));
+    let error = WorkspacePathSourceError::new(
+        WorkspacePathError(PathBuf::from("../outside")),
+        file_id,
+        Span::from(6..18),
+    );
+
+    let diagnostic = error.as_diagnostic(&files);
+
+    assert_eq!(
+        "Path `../outside` resolves outside the workspace directory.",
+        diagnostic.message
+    );
+    assert_eq!(1, diagnostic.labels.len());
+    assert_eq!(file_id, diagnostic.labels[0].file_id);
+    assert_eq!(6..18, diagnostic.labels[0].range);
+}
+
+#[test]
+fn station_error_context_diagnostic_includes_contexts_as_notes() {
+    let result: Result<(), io::Error> =
+        Err(io::Error::new(io::ErrorKind::NotFound, "app.zip not found"));
+
+    let error = result
+        .with_station_context("opening app.zip")
+        .with_station_context("uploading app.zip")
+        .expect_err("Expected error to be wrapped with context.");
+    let diagnostic = error.as_diagnostic(&Files::new());
+
+    assert_eq!(["opening app.zip", "uploading app.zip"], error.contexts());
+    assert_eq!("Failed while uploading app.zip.", diagnostic.message);
+    assert_eq!(
+        vec![
+            String::from("while opening app.zip"),
+            String::from("caused by: app.zip not found"),
+        ],
+        diagnostic.notes
+    );
+}
+
+#[test]
+fn anyhow_station_error_diagnostic_includes_error_chain() {
+    let error = anyhow::Error::new(io::Error::new(io::ErrorKind::NotFound, "app.zip not found"))
+        .context("Failed to upload app.zip.");
+    let error = AnyhowStationError::from(error);
+
+    let diagnostic = error.as_diagnostic(&Files::new());
+
+    assert_eq!("Failed to upload app.zip.", diagnostic.message);
+    assert_eq!(
+        vec![String::from("caused by: app.zip not found")],
+        diagnostic.notes
+    );
+}
+
+#[test]
+fn simple_station_error_diagnostic_includes_severity_and_source() {
+    let error = SimpleStationError::new("Failed to upload app.zip.")
+        .with_source(io::Error::new(io::ErrorKind::NotFound, "app.zip not found"))
+        .with_severity(Severity::Warning);
+
+    let diagnostic = error.as_diagnostic(&Files::new());
+
+    assert_eq!(Severity::Warning, diagnostic.severity);
+    assert_eq!("Failed to upload app.zip.", diagnostic.message);
+    assert_eq!(
+        vec![String::from("caused by: app.zip not found")],
+        diagnostic.notes
+    );
+}
+
+#[test]
+fn simple_station_error_from_station_spec_error_is_bug() -> Result<(), Box<dyn std::error::Error>> {
+    let station_spec_error = StationSpecError::TmpDirCleanFail {
+        id: StationId::new("a")?,
+        name: String::from("A"),
+        detail: String::from("permission denied"),
+        location: Location::caller(),
+    };
+
+    let error = SimpleStationError::from(station_spec_error.clone());
+
+    assert_eq!(Severity::Bug, error.severity());
+    assert_eq!(station_spec_error.to_string(), error.message());
+    Ok(())
+}
+
+#[test]
+fn station_spec_error_display_includes_station_location() -> Result<(), Box<dyn std::error::Error>>
+{
+    let location = Location::caller();
+    let station_spec_error = StationSpecError::WorkNotIdempotent {
+        id: StationId::new("a")?,
+        name: String::from("A"),
+        location,
+    };
+
+    assert!(
+        station_spec_error
+            .to_string()
+            .ends_with(&format!(" (defined at {location})"))
+    );
+    Ok(())
+}
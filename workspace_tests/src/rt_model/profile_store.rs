@@ -0,0 +1,53 @@
+use choochoo_resource::ProfileDir;
+use choochoo_rt_model::{error::ProfileStoreError, ProfileStore};
+
+#[test]
+fn put_persists_values_across_stores() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile_dir = ProfileDir::new(tempdir.path().join("profile"));
+
+    let profile_store = ProfileStore::new(&profile_dir);
+    assert_eq!(None, profile_store.get::<String>("version_deployed")?);
+    assert_eq!(0, profile_store.version("version_deployed")?);
+    assert_eq!(1, profile_store.put("version_deployed", &"0.1.0")?);
+    assert_eq!(2, profile_store.put("version_deployed", &"0.2.0")?);
+
+    let profile_store = ProfileStore::new(&profile_dir);
+    assert_eq!(
+        Some(String::from("0.2.0")),
+        profile_store.get::<String>("version_deployed")?
+    );
+    assert_eq!(2, profile_store.version("version_deployed")?);
+    assert!(profile_store.remove("version_deployed")?);
+    assert_eq!(None, profile_store.get::<String>("version_deployed")?);
+    Ok(())
+}
+
+#[test]
+fn put_if_version_returns_conflict_when_value_changed() -> Result<(), Box<dyn std::error::Error>>
+{
+    let tempdir = tempfile::tempdir()?;
+    let profile_dir = ProfileDir::new(tempdir.path().to_path_buf());
+    let profile_store = ProfileStore::new(&profile_dir);
+
+    let version = profile_store.put_if_version("endpoint", &"http://a", 0)?;
+    profile_store.put("endpoint", &"http://b")?;
+
+    match profile_store.put_if_version("endpoint", &"http://c", version) {
+        Err(ProfileStoreError::VersionConflict {
+            key,
+            version_expected,
+            version_actual,
+        }) => {
+            assert_eq!("endpoint", key);
+            assert_eq!(1, version_expected);
+            assert_eq!(2, version_actual);
+        }
+        result => panic!("Expected `VersionConflict`, but was {:?}", result),
+    }
+    assert_eq!(
+        Some(String::from("http://b")),
+        profile_store.get::<String>("endpoint")?
+    );
+    Ok(())
+}
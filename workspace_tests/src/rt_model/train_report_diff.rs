@@ -0,0 +1,62 @@
+use std::time::Duration;
+
+use choochoo_cfg_model::{rt::ResIdLogical, StationId};
+use choochoo_rt_model::{StationDurationRegression, TrainReportDiff, TrainReportRecord};
+
+#[test]
+fn new_summarizes_created_res_ids_newly_failed_and_regressed_stations()
+-> Result<(), Box<dyn std::error::Error>> {
+    let previous = serde_yaml::from_str::<TrainReportRecord>(
+        r#"
+stations:
+  - station_id: a
+    failed: false
+    duration: { secs: 1, nanos: 0 }
+    res_ids: { instance_id: "i-123" }
+  - station_id: b
+    failed: false
+    duration: { secs: 1, nanos: 0 }
+  - station_id: c
+    failed: false
+    duration: { secs: 0, nanos: 10000000 }
+"#,
+    )?;
+    let current = serde_yaml::from_str::<TrainReportRecord>(
+        r#"
+stations:
+  - station_id: a
+    failed: false
+    duration: { secs: 1, nanos: 0 }
+    res_ids: { instance_id: "i-123", volume_id: "v-456" }
+  - station_id: b
+    failed: false
+    duration: { secs: 3, nanos: 0 }
+  - station_id: c
+    failed: true
+    duration: { secs: 0, nanos: 50000000 }
+"#,
+    )?;
+
+    let train_report_diff = TrainReportDiff::new(&previous, &current);
+
+    let station_a = StationId::new("a")?;
+    let res_ids_created = train_report_diff
+        .res_ids_created()
+        .get(&station_a)
+        .map(|res_ids| res_ids.keys().cloned().collect::<Vec<_>>());
+    assert_eq!(Some(vec![ResIdLogical::new("volume_id")]), res_ids_created);
+    assert_eq!(
+        &[StationId::new("c")?],
+        train_report_diff.stations_newly_failed()
+    );
+    assert_eq!(
+        &[StationDurationRegression {
+            station_id: StationId::new("b")?,
+            duration_previous: Duration::from_secs(1),
+            duration_current: Duration::from_secs(3),
+        }],
+        train_report_diff.stations_duration_regressed()
+    );
+
+    Ok(())
+}
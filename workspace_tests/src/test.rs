@@ -0,0 +1,2 @@
+mod check_fn_fuzzer;
+mod station_harness;
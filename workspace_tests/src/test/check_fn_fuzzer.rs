@@ -0,0 +1,85 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use choochoo_cfg_model::{
+    rt::{CheckStatus, ResIds},
+    StationFn, StationSpec,
+};
+use choochoo_test::{CheckFnDivergence, CheckFnFuzzer};
+use tokio::runtime;
+
+#[test]
+fn run_reports_consistent_when_work_converges() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let work_done = Arc::new(AtomicBool::new(false));
+    let check_fn = {
+        let work_done = Arc::clone(&work_done);
+        StationFn::new0(move |_station| {
+            let work_done = Arc::clone(&work_done);
+            Box::pin(async move {
+                let check_status = if work_done.load(Ordering::SeqCst) {
+                    CheckStatus::WorkNotRequired
+                } else {
+                    CheckStatus::WorkRequired
+                };
+                Result::<CheckStatus, ()>::Ok(check_status)
+            })
+        })
+    };
+    let work_fn = {
+        let work_done = Arc::clone(&work_done);
+        StationFn::new0(move |_station| {
+            let work_done = Arc::clone(&work_done);
+            Box::pin(async move {
+                work_done.store(true, Ordering::SeqCst);
+                Result::<ResIds, (ResIds, ())>::Ok(ResIds::new())
+            })
+        })
+    };
+    let station_spec = StationSpec::<()>::mock("a")?
+        .with_create_check_fn(check_fn)
+        .with_create_work_fn(work_fn)
+        .build();
+
+    let fuzzer = CheckFnFuzzer::new(station_spec)?.with_iterations(3);
+    let report = rt.block_on(fuzzer.run())?;
+
+    assert!(report.is_consistent());
+    assert_eq!(3, report.iterations());
+    assert_eq!(
+        &[
+            CheckStatus::WorkRequired,
+            CheckStatus::WorkNotRequired,
+            CheckStatus::WorkNotRequired,
+            CheckStatus::WorkNotRequired,
+            CheckStatus::WorkNotRequired,
+            CheckStatus::WorkNotRequired,
+        ],
+        report.check_statuses()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn run_reports_work_not_converged_when_check_fn_still_requires_work()
+-> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let station_spec = StationSpec::<()>::mock("a")?
+        .with_create_check_fn(StationFn::ok(CheckStatus::WorkRequired))
+        .with_create_work_fn(StationFn::ok(ResIds::new()))
+        .build();
+
+    let report = rt.block_on(CheckFnFuzzer::new(station_spec)?.run())?;
+
+    assert!(!report.is_consistent());
+    assert_eq!(0, report.iterations());
+    assert!(matches!(
+        report.divergence(),
+        Some(CheckFnDivergence::WorkNotConverged { iteration: 0 })
+    ));
+
+    Ok(())
+}
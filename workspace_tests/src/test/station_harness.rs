@@ -0,0 +1,62 @@
+use choochoo_cfg_model::{
+    rt::{OpStatus, ProgressLimit, ResIdLogical, ResIds, VisitOp},
+    CleanFns, SetupFn, StationFn, StationSpec,
+};
+use choochoo_test::StationHarness;
+use tokio::runtime;
+
+#[test]
+fn run_create_runs_station_in_temporary_workspace() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let mut res_ids = ResIds::new();
+    res_ids.insert(ResIdLogical::new("res_a"), 123u32);
+    let station_spec = StationSpec::<()>::mock("a")?
+        .with_create_setup_fn(SetupFn::ok(ProgressLimit::Steps(3)))
+        .with_create_work_fn(StationFn::ok(res_ids))
+        .build();
+
+    let report = rt.block_on(StationHarness::run_create(station_spec))?;
+
+    assert_eq!(OpStatus::WorkSuccess, report.op_status());
+    assert_eq!(ProgressLimit::Steps(3), report.harness().progress_limit());
+    assert_eq!(
+        Some(&123u32),
+        report.res_ids().get::<u32, _>(&ResIdLogical::new("res_a"))
+    );
+    assert!(report.harness().workspace_dir().exists());
+    assert_eq!(None, rt.block_on(report.station_error()));
+
+    Ok(())
+}
+
+#[test]
+fn run_create_records_station_error() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let station_spec = StationSpec::<()>::mock("a")?
+        .with_create_work_fn(StationFn::err((ResIds::new(), ())))
+        .build();
+
+    let report = rt.block_on(StationHarness::run_create(station_spec))?;
+
+    assert_eq!(OpStatus::WorkFail, report.op_status());
+    assert_eq!(Some(()), rt.block_on(report.station_error()));
+
+    Ok(())
+}
+
+#[test]
+fn into_harness_visits_station_again_in_same_workspace() -> Result<(), Box<dyn std::error::Error>> {
+    let rt = runtime::Builder::new_current_thread().build()?;
+    let station_spec = StationSpec::<()>::mock("a")?
+        .with_clean_fns(CleanFns::ok())
+        .build();
+
+    let report = rt.block_on(StationHarness::run_create(station_spec))?;
+    let workspace_dir = report.harness().workspace_dir().to_path_buf();
+    let report = rt.block_on(report.into_harness().run(VisitOp::Clean))?;
+
+    assert_eq!(OpStatus::WorkSuccess, report.op_status());
+    assert_eq!(workspace_dir, report.harness().workspace_dir());
+
+    Ok(())
+}